@@ -89,5 +89,49 @@ fn bench_order_statistic(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_order_statistic);
+fn bench_indexed_data(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let window: Vec<f64> = (0..1_000_000).map(|_| rng.gen()).collect();
+    let mut group = c.benchmark_group("sliding window of 10^6");
+    group.sample_size(10);
+    group.bench_function("indexed data: remove, insert and median", |b| {
+        let mut data = IndexedData::new(&window);
+        let mut i = 0;
+        b.iter(|| {
+            let x = window[i % window.len()];
+            data.remove(x);
+            data.insert(black_box(x));
+            i += 1;
+            data.median()
+        })
+    });
+    group.bench_function("data: clone, replace and median", |b| {
+        let mut i = 0;
+        b.iter_batched(
+            || window.clone(),
+            |mut values| {
+                values[i % window.len()] = black_box(0.5);
+                i += 1;
+                Data::new(values).median()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("sort: clone, replace and sort", |b| {
+        let mut i = 0;
+        b.iter_batched(
+            || window.clone(),
+            |mut values| {
+                values[i % window.len()] = black_box(0.5);
+                i += 1;
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                values[values.len() / 2]
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_order_statistic, bench_indexed_data);
 criterion_main!(benches);
@@ -0,0 +1,31 @@
+extern crate rand;
+extern crate statrs;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::prelude::*;
+use statrs::generate::{uniform_order_statistics, uniform_spacings};
+
+fn bench_uniform_order_statistics(c: &mut Criterion) {
+    let n = black_box(10_000_000);
+    let mut group = c.benchmark_group("uniform order statistics");
+    group.sample_size(10);
+    group.bench_function("exponential spacings", |b| {
+        let mut rng = StdRng::seed_from_u64(0);
+        b.iter(|| uniform_order_statistics(n, &mut rng))
+    });
+    group.bench_function("uniform_spacings", |b| {
+        let mut rng = StdRng::seed_from_u64(0);
+        b.iter(|| uniform_spacings(n, &mut rng))
+    });
+    group.bench_function("sort", |b| {
+        let mut rng = StdRng::seed_from_u64(0);
+        b.iter(|| {
+            let mut x: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+            x.sort_unstable_by(f64::total_cmp);
+            x
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_uniform_order_statistics);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
-use crate::distribution::{Binomial, Discrete, DiscreteCDF};
+use crate::distribution::{Binomial, Discrete, DiscreteCDF, Parameterized};
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
 use rand::Rng;
 
 /// Implements the
@@ -49,6 +49,42 @@ impl Bernoulli {
         Binomial::new(p, 1).map(|b| Bernoulli { b })
     }
 
+    /// Constructs a new bernoulli distribution from the log-odds
+    /// `logit = ln(p / (1 - p))` of success, whose log probabilities stay
+    /// accurate where `p` rounds to `0.0` or `1.0`, as with
+    /// `Binomial::from_logit`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `logit` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Bernoulli, Discrete};
+    ///
+    /// let n = Bernoulli::from_logit(50.0).unwrap();
+    /// assert_eq!(n.p(), 1.0);
+    /// assert!((n.ln_pmf(0) + 50.0).abs() < 1e-12);
+    /// ```
+    pub fn from_logit(logit: f64) -> Result<Bernoulli> {
+        Binomial::from_logit(1, logit).map(|b| Bernoulli { b })
+    }
+
+    /// Returns the log-odds `ln(p / (1 - p))` of success of the bernoulli
+    /// distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Bernoulli;
+    ///
+    /// assert_eq!(Bernoulli::from_logit(-3.0).unwrap().logit(), -3.0);
+    /// ```
+    pub fn logit(&self) -> f64 {
+        self.b.logit()
+    }
+
     /// Returns the probability of success `p` of the
     /// bernoulli distribution.
     ///
@@ -80,6 +116,19 @@ impl Bernoulli {
     }
 }
 
+impl Parameterized for Bernoulli {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("p".into(), self.p())]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Bernoulli> {
+        match *values {
+            [p] => Bernoulli::new(p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Bernoulli {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Bernoulli({})", self.p())
@@ -268,6 +317,7 @@ mod testing {
     use std::fmt::Debug;
     use crate::distribution::DiscreteCDF;
     use super::Bernoulli;
+    use crate::distribution::{Discrete, Parameterized};
 
     fn try_create(p: f64) -> Bernoulli {
         let n = Bernoulli::new(p);
@@ -351,4 +401,23 @@ mod testing {
         test_almost(0.3, 0.3, 1e-15, sf(0));
         test_almost(0.7, 0.7, 1e-15, sf(0));
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(0.3);
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.pmf(0), d.pmf(1)]);
+        assert_eq!(n.set_parameter("p", 1.5), Bernoulli::new(1.5));
+    }
+
+    #[test]
+    fn test_from_logit() {
+        let n = Bernoulli::from_logit(-50.0).unwrap();
+        assert_almost_eq!(n.ln_pmf(1), -50.0, 1e-12);
+        assert_almost_eq!(n.ln_pmf(0), -(-50f64).exp(), 1e-30);
+        assert_eq!(n.logit(), -50.0);
+        let n = Bernoulli::from_logit(0.8).unwrap();
+        assert_almost_eq!(n.pmf(1), try_create(n.p()).pmf(1), 1e-15);
+        assert_almost_eq!(try_create(0.3).logit(), (0.3f64 / 0.7).ln(), 1e-15);
+        assert!(Bernoulli::from_logit(f64::NAN).is_err());
+    }
 }
@@ -1,10 +1,15 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{
+    Continuous, ContinuousCDF, Exp, LMoments, Moments, Parameterized, ScoreFunction,
+};
 use crate::function::{beta, gamma};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
 
+/// The maximum number of Newton iterations of `fit_mle`
+const MAX_FIT_ITERATIONS: usize = 100;
+
 /// Implements the [Beta](https://en.wikipedia.org/wiki/Beta_distribution)
 /// distribution
 ///
@@ -57,6 +62,85 @@ impl Beta {
         Ok(Beta { shape_a, shape_b })
     }
 
+    /// Estimates a beta distribution from the sample `data` by maximum
+    /// likelihood, with Newton's method started from the method of moments
+    ///
+    /// # Formula
+    ///
+    /// The shapes `α` and `β` solve
+    ///
+    /// ```text
+    /// ψ(α) - ψ(α + β) = mean(ln x_i)
+    /// ψ(β) - ψ(α + β) = mean(ln(1 - x_i))
+    /// ```
+    ///
+    /// where `ψ` is the digamma function
+    ///
+    /// # Remarks
+    ///
+    /// Data on another bounded interval should be rescaled to `(0, 1)`
+    /// first. Entries of exactly zero or one make the likelihood degenerate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not in `(0, 1)` or is `NaN`, if all entries are equal, or if Newton's
+    /// method fails to converge
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Beta;
+    ///
+    /// let n = Beta::fit_mle(&[0.2, 0.35, 0.5, 0.15, 0.4, 0.3]).unwrap();
+    /// assert!(n.shape_a() < n.shape_b());
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Beta> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|&x| x.is_nan() || x <= 0.0 || x >= 1.0) {
+            return Err(StatsError::SpecialCase("data must be in (0, 1)"));
+        }
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        if variance <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        let log_a = data.iter().map(|x| x.ln()).sum::<f64>() / n;
+        let log_b = data.iter().map(|x| (-x).ln_1p()).sum::<f64>() / n;
+        // the method of moments, which needs the variance below that of a
+        // bernoulli variable with the same mean
+        let common = (mean * (1.0 - mean) / variance - 1.0).max(1e-3);
+        let (mut a, mut b) = (mean * common, (1.0 - mean) * common);
+        for _ in 0..MAX_FIT_ITERATIONS {
+            let both = gamma::digamma(a + b);
+            let (ga, gb) = (
+                gamma::digamma(a) - both - log_a,
+                gamma::digamma(b) - both - log_b,
+            );
+            let cross = gamma::trigamma(a + b);
+            let (ta, tb) = (gamma::trigamma(a) - cross, gamma::trigamma(b) - cross);
+            let det = ta * tb - cross * cross;
+            let (mut da, mut db) = ((tb * ga + cross * gb) / det, (cross * ga + ta * gb) / det);
+            // the log-likelihood is concave, so halving the step until the
+            // shapes stay positive keeps the iteration converging
+            while a - da <= 0.0 || b - db <= 0.0 {
+                da /= 2.0;
+                db /= 2.0;
+            }
+            a -= da;
+            b -= db;
+            if da.abs() <= 1e-14 * a && db.abs() <= 1e-14 * b {
+                return Beta::new(a, b);
+            }
+        }
+        Err(StatsError::SpecialCase("Newton's method did not converge"))
+    }
+
     /// Returns the shapeA (α) of the beta distribution
     ///
     /// # Examples
@@ -86,6 +170,22 @@ impl Beta {
     }
 }
 
+impl Parameterized for Beta {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("shape_a".into(), self.shape_a),
+            ("shape_b".into(), self.shape_b),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Beta> {
+        match *values {
+            [shape_a, shape_b] => Beta::new(shape_a, shape_b),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Beta {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Beta(a={}, b={})", self.shape_a, self.shape_b)
@@ -407,6 +507,103 @@ impl Continuous<f64, f64> for Beta {
     }
 }
 
+impl ScoreFunction<f64> for Beta {
+    const NUM_PARAMS: usize = 2;
+
+    /// Calculates the gradient of the log probability density function for
+    /// the beta distribution at `x` with respect to `(α, β)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (ln(x) - ψ(α) + ψ(α + β), ln(1 - x) - ψ(β) + ψ(α + β))
+    /// ```
+    ///
+    /// where `α` is shapeA, `β` is shapeB and `ψ` the digamma function
+    fn ln_pdf_grad(&self, x: f64) -> Vec<f64> {
+        let total = gamma::digamma(self.shape_a + self.shape_b);
+        vec![
+            x.ln() - gamma::digamma(self.shape_a) + total,
+            (-x).ln_1p() - gamma::digamma(self.shape_b) + total,
+        ]
+    }
+
+    /// Calculates the Hessian of the log probability density function for
+    /// the beta distribution at `x` with respect to `(α, β)`, which does not
+    /// depend on `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[ψ1(α + β) - ψ1(α), ψ1(α + β)], [ψ1(α + β), ψ1(α + β) - ψ1(β)]]
+    /// ```
+    ///
+    /// where `α` is shapeA, `β` is shapeB and `ψ1` the trigamma function
+    fn ln_pdf_hessian(&self, _x: f64) -> Option<Vec<Vec<f64>>> {
+        let total = gamma::trigamma(self.shape_a + self.shape_b);
+        Some(vec![
+            vec![total - gamma::trigamma(self.shape_a), total],
+            vec![total, total - gamma::trigamma(self.shape_b)],
+        ])
+    }
+}
+
+impl LMoments for Beta {}
+
+impl Moments for Beta {
+    /// Returns the raw moment of order `order` of the beta distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Π_{i = 0}^{r - 1} (α + i) / (α + β + i)
+    /// ```
+    ///
+    /// where `α` is shapeA and `β` is shapeB. An infinite shape is a point
+    /// mass at one end of the support.
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        let moment = if self.shape_a.is_infinite() {
+            1.0
+        } else if self.shape_b.is_infinite() {
+            if order == 0 {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            (0..order)
+                .map(|i| {
+                    let i = i as f64;
+                    (self.shape_a + i) / (self.shape_a + self.shape_b + i)
+                })
+                .product()
+        };
+        Some(moment)
+    }
+
+    /// Returns the standardized moment of order `order` of the beta
+    /// distribution
+    ///
+    /// # Remarks
+    ///
+    /// With an infinite shape the moments are those of the limiting
+    /// exponential distribution, mirrored for an infinite shapeA, in
+    /// agreement with `skewness`
+    fn standardized_moment(&self, order: u32) -> Option<f64> {
+        if self.shape_a.is_infinite() || self.shape_b.is_infinite() {
+            let moment = Exp::new(1.0).ok()?.central_moment(order)?;
+            let sign = if self.shape_a.is_infinite() && order % 2 == 1 {
+                -1.0
+            } else {
+                1.0
+            };
+            return Some(sign * moment);
+        }
+        let variance = self.central_moment(2)?;
+        Some(self.central_moment(order)? / variance.powf(0.5 * order as f64))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -686,4 +883,64 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.2, 3.4), 0.0, 1.0);
         test::check_continuous_distribution(&try_create(4.5, 6.7), 0.0, 1.0);
     }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Beta::new(p[0], p[1]).unwrap();
+        let xs = [0.01, 0.2, 0.5, 0.75, 0.99];
+        test::check_score(make, |d: &Beta, x| d.ln_pdf(x), &[2.0, 3.0], &xs);
+        test::check_score(make, |d: &Beta, x| d.ln_pdf(x), &[0.5, 0.5], &xs);
+        test::check_score(make, |d: &Beta, x| d.ln_pdf(x), &[10.0, 1.5], &xs);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(2.0, 3.0);
+        test::check_parameters(&n, |d| [d.pdf(0.3), d.pdf(0.7)]);
+        assert_eq!(n.set_parameter("shape_a", -1.0), Beta::new(-1.0, 3.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(a, b) in [(1.0, 1.0), (9.0, 1.0), (5.0, 100.0)].iter() {
+            let n = try_create(a, b);
+            test::check_moments(&n, 1e-12);
+            test::check_moments_numeric(&n, 6, 1e-8);
+        }
+        // the density is unbounded at both ends, beyond the quadrature
+        test::check_moments(&try_create(0.5, 0.5), 1e-12);
+        let n = try_create(2.0, 3.0);
+        assert_almost_eq!(n.raw_moment(2).unwrap(), 0.2, 1e-15);
+        // the degenerate limits
+        for &(a, b) in [(1.0, f64::INFINITY), (f64::INFINITY, 1.0)].iter() {
+            let n = try_create(a, b);
+            assert_eq!(n.raw_moment(1), n.mean());
+            assert_eq!(n.central_moment(2), Some(0.0));
+            assert_eq!(n.standardized_moment(3), n.skewness());
+        }
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        use rand::distributions::Distribution as _;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(293);
+        for &(a, b) in [(0.5, 0.5), (2.0, 5.0), (30.0, 3.0)].iter() {
+            let data: Vec<f64> = try_create(a, b).sample_iter(&mut rng).take(20000).collect();
+            let n = Beta::fit_mle(&data).unwrap();
+            assert_almost_eq!(n.shape_a() / a, 1.0, 0.05);
+            assert_almost_eq!(n.shape_b() / b, 1.0, 0.05);
+            // the score equations hold at the estimate
+            let m = data.len() as f64;
+            let both = gamma::digamma(n.shape_a() + n.shape_b());
+            let log_a = data.iter().map(|x| x.ln()).sum::<f64>() / m;
+            assert_almost_eq!(gamma::digamma(n.shape_a()) - both, log_a, 1e-12);
+        }
+        assert!(Beta::fit_mle(&[0.5]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 0.5]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 1.0]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 0.0]).is_err());
+    }
 }
@@ -1,5 +1,5 @@
-use crate::distribution::{Discrete, DiscreteCDF};
-use crate::function::{beta, factorial};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Moments, Parameterized, ScoreFunction};
+use crate::function::{beta, factorial, logistic};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -21,10 +21,19 @@ use std::f64;
 /// assert_eq!(n.pmf(0), 0.03125);
 /// assert_eq!(n.pmf(3), 0.3125);
 /// ```
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub struct Binomial {
     p: f64,
     n: u64,
+    logit: f64,
+}
+
+impl PartialEq for Binomial {
+    /// Compares the parameters `p` and `n`, not the log-odds they were
+    /// constructed from
+    fn eq(&self, other: &Binomial) -> bool {
+        self.p == other.p && self.n == other.n
+    }
 }
 
 impl Binomial {
@@ -52,10 +61,76 @@ impl Binomial {
         if p.is_nan() || !(0.0..=1.0).contains(&p) {
             Err(StatsError::BadParams)
         } else {
-            Ok(Binomial { p, n })
+            Ok(Binomial {
+                p,
+                n,
+                logit: (p / (1.0 - p)).ln(),
+            })
+        }
+    }
+
+    /// Constructs a new binomial distribution of `n` trials from the log-odds
+    /// `logit = ln(p / (1 - p))` of success, which keeps the log
+    /// probabilities accurate where `p` rounds to `0.0` or `1.0`
+    ///
+    /// # Remarks
+    ///
+    /// Where `p` rounds to `0.0` or `1.0`, `ln_pmf` and `pmf` are computed
+    /// from the logit with `log1p_exp`, so logits beyond about `±37` still
+    /// give finite log probabilities. The other methods use the probability
+    /// `p`, rounded to `[0, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `logit` is `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Binomial, Discrete};
+    ///
+    /// // p = e^-800 / (1 + e^-800) rounds to zero
+    /// let n = Binomial::from_logit(10, -800.0).unwrap();
+    /// assert_eq!(n.p(), 0.0);
+    /// // the log probability of 10 successes in 10 trials is still finite
+    /// assert!((n.ln_pmf(10) + 8000.0).abs() < 1e-9);
+    /// let by_p = Binomial::new(n.p(), 10).unwrap();
+    /// assert_eq!(by_p.ln_pmf(10), f64::NEG_INFINITY);
+    /// ```
+    pub fn from_logit(n: u64, logit: f64) -> Result<Binomial> {
+        if logit.is_nan() {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(Binomial {
+                p: logistic::logistic(logit),
+                n,
+                logit,
+            })
         }
     }
 
+    /// Returns the log-odds `ln(p / (1 - p))` of success of the binomial
+    /// distribution, as given to `from_logit` or computed from `p`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Binomial;
+    ///
+    /// assert_eq!(Binomial::from_logit(5, 40.0).unwrap().logit(), 40.0);
+    /// assert_eq!(Binomial::new(0.5, 5).unwrap().logit(), 0.0);
+    /// ```
+    pub fn logit(&self) -> f64 {
+        self.logit
+    }
+
+    /// Returns whether `p` rounds to `0.0` or `1.0` while the logit is
+    /// finite, so that the log probabilities must be computed from the
+    /// logit
+    fn is_saturated(&self) -> bool {
+        self.logit.is_finite() && (is_zero(self.p) || ulps_eq!(self.p, 1.0))
+    }
+
     /// Returns the probability of success `p` of
     /// the binomial distribution.
     ///
@@ -87,6 +162,19 @@ impl Binomial {
     }
 }
 
+impl Parameterized for Binomial {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("p".into(), self.p), ("n".into(), self.n as f64)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Binomial> {
+        match *values {
+            [p, n] => Binomial::new(p, internal::integer_parameter(n)?),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Binomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Bin({},{})", self.p, self.n)
@@ -275,6 +363,8 @@ impl Discrete<u64, f64> for Binomial {
     fn pmf(&self, x: u64) -> f64 {
         if x > self.n {
             0.0
+        } else if self.is_saturated() {
+            self.ln_pmf(x).exp()
         } else if is_zero(self.p) {
             if x == 0 {
                 1.0
@@ -303,9 +393,25 @@ impl Discrete<u64, f64> for Binomial {
     /// ```text
     /// ln((n choose k) * p^k * (1 - p)^(n - k))
     /// ```
+    ///
+    /// evaluated from the logit `l` as
+    /// `ln(n choose k) - k ln(1 + e^-l) - (n - k) ln(1 + e^l)` where `p`
+    /// rounds to `0.0` or `1.0`
     fn ln_pmf(&self, x: u64) -> f64 {
         if x > self.n {
             f64::NEG_INFINITY
+        } else if self.is_saturated() {
+            let logit = self.logit;
+            // ln p = -ln(1 + e^-l) and ln(1 - p) = -ln(1 + e^l), skipping
+            // the terms without trials so that infinite logits give 0 * ∞
+            let term = |count: u64, ln_odds: f64| {
+                if count == 0 {
+                    0.0
+                } else {
+                    -(count as f64) * logistic::log1p_exp(ln_odds)
+                }
+            };
+            factorial::ln_binomial(self.n, x) + term(x, -logit) + term(self.n - x, logit)
         } else if is_zero(self.p) {
             if x == 0 {
                 0.0
@@ -326,6 +432,98 @@ impl Discrete<u64, f64> for Binomial {
     }
 }
 
+impl ScoreFunction<u64> for Binomial {
+    const NUM_PARAMS: usize = 1;
+
+    /// Calculates the gradient of the log probability mass function for the
+    /// binomial distribution at `x` with respect to `p`, the number of
+    /// trials being fixed
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (x / p - (n - x) / (1 - p))
+    /// ```
+    ///
+    /// where `p` is the success probability and `n` the number of trials
+    fn ln_pdf_grad(&self, x: u64) -> Vec<f64> {
+        let (k, n) = (x as f64, self.n as f64);
+        vec![k / self.p - (n - k) / (1.0 - self.p)]
+    }
+
+    /// Calculates the Hessian of the log probability mass function for the
+    /// binomial distribution at `x` with respect to `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-x / p^2 - (n - x) / (1 - p)^2]]
+    /// ```
+    ///
+    /// where `p` is the success probability and `n` the number of trials
+    fn ln_pdf_hessian(&self, x: u64) -> Option<Vec<Vec<f64>>> {
+        let (k, n) = (x as f64, self.n as f64);
+        let q = 1.0 - self.p;
+        Some(vec![vec![-k / (self.p * self.p) - (n - k) / (q * q)]])
+    }
+}
+
+impl Moments for Binomial {
+    /// Returns the raw moment of order `order` of the binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k = 0}^r S(r, k) n (n - 1) ... (n - k + 1) p^k
+    /// ```
+    ///
+    /// where `S(r, k)` are the Stirling numbers of the second kind
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        let stirling = internal::stirling_second_kind(order);
+        let mut falling = 1.0;
+        let mut moment = 0.0;
+        for (k, s) in stirling.iter().enumerate() {
+            moment += s * falling;
+            falling *= (self.n as f64 - k as f64) * self.p;
+        }
+        Some(moment)
+    }
+
+    /// Returns the central moment of order `order` of the binomial
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// The central moments follow from the cumulants, which are `n` times
+    /// those of a bernoulli trial, themselves found from its central moments
+    /// `q (-p)^j + p q^j` through
+    ///
+    /// ```text
+    /// κ_j = μ_j - Σ_{k = 2}^{j - 2} C(j - 1, k) κ_(j - k) μ_k
+    /// ```
+    ///
+    /// where `q = 1 - p`
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        let (p, q) = (self.p, 1.0 - self.p);
+        let bernoulli: Vec<f64> = (0..=order as i32)
+            .map(|j| q * (-p).powi(j) + p * q.powi(j))
+            .collect();
+        let mut cumulants = vec![0.0; order as usize + 1];
+        for j in 2..=order as usize {
+            let mut cumulant = bernoulli[j];
+            for k in 2..j - 1 {
+                cumulant -=
+                    factorial::binomial(j as u64 - 1, k as u64) * cumulants[j - k] * bernoulli[k];
+            }
+            cumulants[j] = cumulant;
+        }
+        let n = self.n as f64;
+        let moments =
+            internal::central_moments_from_cumulants(order, |j| n * cumulants[j as usize]);
+        Some(moments[order as usize])
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -333,6 +531,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, Binomial};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(p: f64, n: u64) -> Binomial {
         let n = Binomial::new(p, n);
@@ -596,4 +795,116 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.3, 5), 5);
         test::check_discrete_distribution(&try_create(0.7, 10), 10);
     }
+
+    #[test]
+    fn test_score() {
+        let make_10 = |p: &[f64]| Binomial::new(p[0], 10).unwrap();
+        let make_100 = |p: &[f64]| Binomial::new(p[0], 100).unwrap();
+        test::check_score(make_10, |d: &Binomial, x| d.ln_pmf(x), &[0.5], &[0, 1, 5, 9, 10]);
+        test::check_score(make_10, |d: &Binomial, x| d.ln_pmf(x), &[0.1], &[0, 1, 5, 9, 10]);
+        test::check_score(make_100, |d: &Binomial, x| d.ln_pmf(x), &[0.73], &[0, 40, 73, 100]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(0.3, 10);
+        test::check_parameters(&n, |d| [d.pmf(0), d.pmf(3), d.pmf(10)]);
+        assert_eq!(n.set_parameter("p", 1.5), Binomial::new(1.5, 10));
+        assert!(n.set_parameter("n", 2.5).is_err());
+        assert!(n.set_parameter("n", -1.0).is_err());
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(p, n) in [(0.3, 1), (0.5, 10), (0.9, 7), (0.01, 200)].iter() {
+            let b = try_create(p, n);
+            test::check_moments(&b, 1e-12);
+            let npq = n as f64 * p * (1.0 - p);
+            assert_almost_eq!(b.standardized_moment(4).unwrap(), 3.0 + (1.0 - 6.0 * p * (1.0 - p)) / npq, 1e-12);
+            // the closed forms agree with the sums over the probability mass
+            let mean = b.mean().unwrap();
+            for r in 0..=6 {
+                let (raw, central) = (0..=n).map(|x| (x as f64, b.pmf(x))).fold((0.0, 0.0), |(s, t), (x, f)| {
+                    (s + f * x.powi(r), t + f * (x - mean).powi(r))
+                });
+                assert_almost_eq!(b.raw_moment(r as u32).unwrap(), raw, 1e-10 * raw.max(1.0));
+                assert_almost_eq!(b.central_moment(r as u32).unwrap(), central, 1e-10 * npq.max(1.0).powi(r / 2 + 1));
+            }
+        }
+        // a single trial is a bernoulli variable, whose raw moments are all p
+        assert_almost_eq!(try_create(0.3, 1).raw_moment(5).unwrap(), 0.3, 1e-15);
+        assert_eq!(try_create(0.0, 5).central_moment(3), Some(0.0));
+    }
+
+    #[test]
+    fn test_from_logit() {
+        // ln C(10, 3) - 3 ln(1 + e^-50) - 7 ln(1 + e^50), where the first
+        // logarithm is e^-50 and the second 50 to double precision
+        let n = Binomial::from_logit(10, 50.0).unwrap();
+        let ln_c = 120f64.ln();
+        assert_almost_eq!(n.ln_pmf(3), ln_c - 350.0, 1e-12);
+        assert_almost_eq!(n.ln_pmf(10), -10.0 * (-50f64).exp(), 1e-30);
+        assert_eq!(n.ln_pmf(11), f64::NEG_INFINITY);
+        let n = Binomial::from_logit(10, -50.0).unwrap();
+        assert_almost_eq!(n.ln_pmf(7), ln_c - 350.0, 1e-12);
+        assert!(n.ln_pmf(10).is_finite());
+        // infinite logits are point masses
+        let n = Binomial::from_logit(4, f64::INFINITY).unwrap();
+        assert_eq!(n.ln_pmf(4), 0.0);
+        assert_eq!(n.ln_pmf(3), f64::NEG_INFINITY);
+        assert_eq!(n.p(), 1.0);
+        assert_eq!(Binomial::from_logit(4, f64::NEG_INFINITY).unwrap().pmf(0), 1.0);
+        assert!(Binomial::from_logit(4, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_from_logit_round_trip() {
+        for &p in [0.01, 0.3, 0.5, 0.77, 0.999].iter() {
+            let by_p = try_create(p, 12);
+            let by_logit = Binomial::from_logit(12, by_p.logit()).unwrap();
+            assert_almost_eq!(by_logit.p(), p, 1e-15);
+            for x in 0..=12 {
+                assert_almost_eq!(by_logit.ln_pmf(x), by_p.ln_pmf(x), 1e-12 * by_p.ln_pmf(x).abs().max(1.0));
+                assert_almost_eq!(by_logit.pmf(x), by_p.pmf(x), 1e-14);
+                assert_eq!(by_logit.cdf(x), Binomial::new(by_logit.p(), 12).unwrap().cdf(x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_logit_equality() {
+        use crate::distribution::Parameterized;
+        // equality compares the parameters whatever the constructor
+        assert_eq!(Binomial::from_logit(5, 0.0).unwrap(), try_create(0.5, 5));
+        assert_ne!(Binomial::from_logit(5, 0.0).unwrap(), try_create(0.5, 6));
+        let n = Binomial::from_logit(10, -50.0).unwrap();
+        assert_eq!(n, try_create(n.p(), 10));
+        // the logit follows the parameters set through with_parameters
+        let m = n.with_parameters(&[0.3, 10.0]).unwrap();
+        assert_almost_eq!(m.logit(), (0.3f64 / 0.7).ln(), 1e-15);
+        assert_eq!(m, try_create(0.3, 10));
+        assert_eq!(try_create(0.0, 3).logit(), f64::NEG_INFINITY);
+        assert_eq!(try_create(1.0, 3).logit(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_from_logit_gradient() {
+        use crate::distribution::ScoreFunction;
+        // d ln f / dl = x - n σ(l), the score for p times dp / dl = p (1 - p)
+        let h = 1e-5;
+        for &logit in [-60.0, -3.0, 0.4, 2.0, 60.0].iter() {
+            let n = Binomial::from_logit(8, logit).unwrap();
+            for x in 0..=8 {
+                let up = Binomial::from_logit(8, logit + h).unwrap().ln_pmf(x);
+                let down = Binomial::from_logit(8, logit - h).unwrap().ln_pmf(x);
+                let exact = x as f64 - 8.0 * n.p();
+                assert_almost_eq!((up - down) / (2.0 * h), exact, 1e-6 * exact.abs().max(1.0));
+                if logit.abs() < 10.0 {
+                    let score = n.ln_pdf_grad(x)[0] * n.p() * (1.0 - n.p());
+                    assert_almost_eq!(score, exact, 1e-12);
+                }
+            }
+        }
+    }
 }
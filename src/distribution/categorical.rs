@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -77,6 +77,20 @@ impl Categorical {
     }
 }
 
+impl Parameterized for Categorical {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        self.norm_pmf
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (format!("prob_mass[{}]", i), p))
+            .collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Categorical> {
+        Categorical::new(&internal::parameter_values(values, self.norm_pmf.len())?)
+    }
+}
+
 impl std::fmt::Display for Categorical {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cat({:#?})", self.norm_pmf)
@@ -356,6 +370,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{Categorical, Discrete, DiscreteCDF};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(prob_mass: &[f64]) -> Categorical {
         let n = Categorical::new(prob_mass);
@@ -551,4 +566,12 @@ mod tests {
         test::check_discrete_distribution(&try_create(&[1.0, 2.0, 3.0, 4.0]), 4);
         test::check_discrete_distribution(&try_create(&[0.0, 1.0, 2.0, 3.0, 4.0]), 5);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(&[1.0, 2.0, 5.0]);
+        test::check_parameters(&n, |d| [d.pmf(0), d.pmf(1), d.pmf(2)]);
+        assert_eq!(n.parameters()[2], ("prob_mass[2]".to_string(), 0.625));
+        assert_eq!(n.set_parameter("prob_mass[1]", -1.0), Categorical::new(&[0.125, -1.0, 0.625]));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Moments, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -79,6 +79,22 @@ impl Cauchy {
     }
 }
 
+impl Parameterized for Cauchy {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Cauchy> {
+        match *values {
+            [location, scale] => Cauchy::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Cauchy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cauchy({}, {})", self.location, self.scale)
@@ -121,6 +137,38 @@ impl ContinuousCDF<f64, f64> for Cauchy {
     fn sf(&self, x: f64) -> f64 {
         (1.0 / f64::consts::PI) * ((self.location - x) / self.scale).atan() + 0.5
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// cauchy distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_0 - γ / tan(π p)          if p < 1/4
+    /// x_0 + γ tan(π (p - 1/2))    if 1/4 <= p <= 3/4
+    /// x_0 + γ / tan(π (1 - p))    if p > 3/4
+    /// ```
+    ///
+    /// where `x_0` is the location and `γ` is the scale. The cotangent
+    /// forms keep full relative accuracy in the tails, where `p - 1/2`
+    /// would lose the digits of `p`.
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let z = if p < 0.25 {
+            -1.0 / (f64::consts::PI * p).tan()
+        } else if p > 0.75 {
+            1.0 / (f64::consts::PI * (1.0 - p)).tan()
+        } else {
+            (f64::consts::PI * (p - 0.5)).tan()
+        };
+        self.location + self.scale * z
+    }
 }
 
 impl Min<f64> for Cauchy {
@@ -231,12 +279,31 @@ impl Continuous<f64, f64> for Cauchy {
     }
 }
 
+impl Moments for Cauchy {
+    /// Returns `None` for every order but zero, since the cauchy
+    /// distribution has no moments
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        if order == 0 {
+            Some(1.0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `None` for every order but zero, since the cauchy
+    /// distribution has no mean
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        self.raw_moment(order)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Cauchy};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(location: f64, scale: f64) -> Cauchy {
         let n = Cauchy::new(location, scale);
@@ -471,4 +538,30 @@ mod tests {
         test::check_continuous_distribution(&try_create(-1.2, 3.4), -1500.0, 1500.0);
         test::check_continuous_distribution(&try_create(-4.5, 6.7), -5000.0, 5000.0);
     }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(1.0, 2.0), [-6.3661977236758133e+299, -6366197723675812.6, 1.0, 5734161139222659.6]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(-1.0), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("scale", 0.0), Cauchy::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        let n = try_create(1.0, 2.0);
+        assert_eq!(n.raw_moment(0), Some(1.0));
+        for r in 1..6 {
+            assert!(n.raw_moment(r).is_none());
+            assert!(n.central_moment(r).is_none());
+            assert!(n.standardized_moment(r).is_none());
+        }
+    }
 }
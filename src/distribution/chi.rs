@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -68,6 +68,19 @@ impl Chi {
     }
 }
 
+impl Parameterized for Chi {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("freedom".into(), self.freedom)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Chi> {
+        match *values {
+            [freedom] => Chi::new(freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Chi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "χ_{}", self.freedom)
@@ -329,6 +342,7 @@ mod tests {
     use crate::distribution::internal::*;
     use crate::distribution::{Chi, Continuous, ContinuousCDF};
     use crate::statistics::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(freedom: f64) -> Chi {
         let n = Chi::new(freedom);
@@ -624,4 +638,11 @@ mod tests {
         test::check_continuous_distribution(&try_create(2.0), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(5.0), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("freedom", -1.0), Chi::new(-1.0));
+    }
 }
@@ -1,6 +1,6 @@
-use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::distribution::{Continuous, ContinuousCDF, Gamma, Moments, Parameterized};
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
 use rand::Rng;
 use std::f64;
 
@@ -96,6 +96,19 @@ impl ChiSquared {
     }
 }
 
+impl Parameterized for ChiSquared {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("freedom".into(), self.freedom)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<ChiSquared> {
+        match *values {
+            [freedom] => ChiSquared::new(freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for ChiSquared {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "χ^2_{}", self.freedom)
@@ -138,6 +151,13 @@ impl ContinuousCDF<f64, f64> for ChiSquared {
     fn sf(&self, x: f64) -> f64 {
         self.g.sf(x)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// chi-squared distribution at `p` by delegating to the equivalent
+    /// gamma distribution
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.g.inverse_cdf(p)
+    }
 }
 
 impl Min<f64> for ChiSquared {
@@ -288,12 +308,36 @@ impl Continuous<f64, f64> for ChiSquared {
     }
 }
 
+impl Moments for ChiSquared {
+    /// Returns the raw moment of order `order` of the chi-squared
+    /// distribution, that of the gamma distribution with a shape of
+    /// `freedom / 2` and a rate of `0.5`
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        self.g.raw_moment(order)
+    }
+
+    /// Returns the central moment of order `order` of the chi-squared
+    /// distribution, that of the gamma distribution with a shape of
+    /// `freedom / 2` and a rate of `0.5`
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        self.g.central_moment(order)
+    }
+
+    /// Returns the standardized moment of order `order` of the chi-squared
+    /// distribution, that of the gamma distribution with a shape of
+    /// `freedom / 2` and a rate of `0.5`
+    fn standardized_moment(&self, order: u32) -> Option<f64> {
+        self.g.standardized_moment(order)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::Median;
     use crate::distribution::ChiSquared;
     use crate::distribution::internal::*;
+    use crate::distribution::{Continuous, Parameterized};
 
     fn try_create(freedom: f64) -> ChiSquared {
         let n = ChiSquared::new(freedom);
@@ -334,4 +378,26 @@ mod tests {
         test::check_continuous_distribution(&try_create(2.0), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(5.0), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("freedom", -1.0), ChiSquared::new(-1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        use crate::statistics::Distribution;
+        for &freedom in [1.0, 2.5, 10.0].iter() {
+            let n = try_create(freedom);
+            test::check_moments(&n, 1e-13);
+            // the kurtosis is 3 + 12 / k
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 3.0 + 12.0 / freedom, 1e-13);
+        }
+        // E[X^2] = k (k + 2)
+        assert_eq!(try_create(3.0).raw_moment(2), Some(15.0));
+        assert_eq!(try_create(3.0).central_moment(2), try_create(3.0).variance());
+    }
 }
@@ -0,0 +1,334 @@
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Implements the [circular uniform](https://en.wikipedia.org/wiki/Circular_uniform_distribution)
+/// distribution of an angle on `[-π, π]`, the directional distribution
+/// without any preferred direction
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{CircularUniform, Continuous};
+///
+/// let n = CircularUniform::new();
+/// assert_eq!(n.mean_resultant_length(), 0.0);
+/// assert_eq!(n.pdf(1.0), 1.0 / (2.0 * std::f64::consts::PI));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct CircularUniform;
+
+impl CircularUniform {
+    /// Constructs a new circular uniform distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::CircularUniform;
+    ///
+    /// let n = CircularUniform::new();
+    /// ```
+    pub fn new() -> CircularUniform {
+        CircularUniform
+    }
+
+    /// Returns the mean direction of the circular uniform distribution,
+    /// which is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::CircularUniform;
+    ///
+    /// assert_eq!(CircularUniform::new().circular_mean(), None);
+    /// ```
+    pub fn circular_mean(&self) -> Option<f64> {
+        None
+    }
+
+    /// Returns the mean resultant length `|E[e^(iθ)]|` of the circular
+    /// uniform distribution, which is `0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::CircularUniform;
+    ///
+    /// assert_eq!(CircularUniform::new().mean_resultant_length(), 0.0);
+    /// ```
+    pub fn mean_resultant_length(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Parameterized for CircularUniform {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        Vec::new()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<CircularUniform> {
+        if values.is_empty() {
+            Ok(CircularUniform)
+        } else {
+            Err(StatsError::ContainersMustBeSameLength)
+        }
+    }
+}
+
+impl std::fmt::Display for CircularUniform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CircularUniform")
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for CircularUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        rng.gen_range(-PI..PI)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for CircularUniform {
+    /// Calculates the cumulative distribution function for the circular
+    /// uniform distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (x + π) / 2π
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= -PI {
+            0.0
+        } else if x >= PI {
+            1.0
+        } else {
+            (x + PI) / (2.0 * PI)
+        }
+    }
+
+    /// Calculates the survival function for the circular uniform
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (π - x) / 2π
+    /// ```
+    fn sf(&self, x: f64) -> f64 {
+        if x <= -PI {
+            1.0
+        } else if x >= PI {
+            0.0
+        } else {
+            (PI - x) / (2.0 * PI)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// circular uniform distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2πp - π
+    /// ```
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        2.0 * PI * p - PI
+    }
+}
+
+impl Min<f64> for CircularUniform {
+    /// Returns the minimum angle of the circular uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -π
+    /// ```
+    fn min(&self) -> f64 {
+        -PI
+    }
+}
+
+impl Max<f64> for CircularUniform {
+    /// Returns the maximum angle of the circular uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// π
+    /// ```
+    fn max(&self) -> f64 {
+        PI
+    }
+}
+
+impl Distribution<f64> for CircularUniform {
+    /// Returns the mean of the angle of the circular uniform distribution
+    /// as a linear quantity on `[-π, π]`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(0.0)
+    }
+
+    /// Returns the variance of the angle of the circular uniform
+    /// distribution as a linear quantity on `[-π, π]`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// π^2 / 3
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        Some(PI * PI / 3.0)
+    }
+
+    /// Returns the entropy of the circular uniform distribution, the
+    /// largest of any distribution on the circle
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(2π)
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some((2.0 * PI).ln())
+    }
+
+    /// Returns the skewness of the angle of the circular uniform
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        Some(0.0)
+    }
+}
+
+impl Median<f64> for CircularUniform {
+    /// Returns the median of the angle of the circular uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn median(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Continuous<f64, f64> for CircularUniform {
+    /// Calculates the probability density function for the circular
+    /// uniform distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 / 2π
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        if (-PI..=PI).contains(&x) {
+            1.0 / (2.0 * PI)
+        } else {
+            0.0
+        }
+    }
+
+    /// Calculates the log probability density function for the circular
+    /// uniform distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -ln(2π)
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if (-PI..=PI).contains(&x) {
+            -(2.0 * PI).ln()
+        } else {
+            f64::NEG_INFINITY
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_cdf() {
+        let n = CircularUniform::new();
+        assert_eq!(n.cdf(-PI), 0.0);
+        assert_eq!(n.cdf(0.0), 0.5);
+        assert_eq!(n.cdf(PI), 1.0);
+        assert_almost_eq!(n.cdf(PI / 2.0), 0.75, 1e-15);
+        assert_almost_eq!(n.sf(PI / 2.0), 0.25, 1e-15);
+        assert_almost_eq!(n.inverse_cdf(0.75), PI / 2.0, 1e-15);
+        assert_eq!(n.pdf(4.0), 0.0);
+    }
+
+    #[test]
+    fn test_moments() {
+        let n = CircularUniform::new();
+        assert_eq!(n.entropy().unwrap(), (2.0 * PI).ln());
+        assert_eq!(n.mean().unwrap(), 0.0);
+        assert_eq!(n.circular_mean(), None);
+        assert_eq!(n.mean_resultant_length(), 0.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let n = CircularUniform::new();
+        let (mut c, mut s) = (0.0, 0.0);
+        for _ in 0..10000 {
+            let x = rand::distributions::Distribution::sample(&n, &mut rng);
+            assert!((-PI..PI).contains(&x));
+            c += x.cos();
+            s += x.sin();
+        }
+        assert!((c * c + s * s).sqrt() / 10000.0 < 0.03);
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&CircularUniform::new(), -PI, PI);
+    }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&CircularUniform::new(), [-PI, -3.1415926535897926, 0.0, 3.1415926535897925]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = CircularUniform::new();
+        test::check_parameters(&n, |d| d.pdf(1.0));
+        assert_eq!(n.n_parameters(), 0);
+    }
+}
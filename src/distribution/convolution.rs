@@ -0,0 +1,636 @@
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use num_traits::{NumAssignOps, PrimInt};
+use rand::Rng;
+use std::convert::TryFrom;
+use std::f64;
+
+/// The largest number of support points tabulated or produced by
+/// [`discrete_convolve`] and [`convolve_n`], which bounds the quadratic cost
+/// of the direct convolution
+pub const MAX_CONVOLUTION_SUPPORT: usize = 1 << 15;
+
+/// Implements a discrete distribution on the consecutive integers
+/// `offset, offset + 1, ..., offset + n - 1` with explicitly tabulated
+/// probabilities, as returned by [`discrete_convolve`] and [`convolve_n`]
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, FiniteDiscrete};
+/// use statrs::statistics::Distribution;
+///
+/// let n = FiniteDiscrete::new(-1, vec![1.0, 2.0, 1.0]).unwrap();
+/// assert_eq!(n.pmf(0), 0.5);
+/// assert_eq!(n.mean().unwrap(), 0.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct FiniteDiscrete {
+    offset: i64,
+    probabilities: Vec<f64>,
+    cdf: Vec<f64>,
+    truncated_mass: f64,
+}
+
+impl FiniteDiscrete {
+    /// Constructs a new finite discrete distribution whose support starts at
+    /// `offset`, with the mass of `offset + i` proportional to
+    /// `probabilities[i]`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `probabilities` is empty, contains a negative or
+    /// non-finite entry, sums to `0`, or if the support would overflow an
+    /// `i64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::FiniteDiscrete;
+    ///
+    /// let mut result = FiniteDiscrete::new(0, vec![0.2, 0.8]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = FiniteDiscrete::new(0, vec![0.2, -0.8]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(offset: i64, probabilities: Vec<f64>) -> Result<FiniteDiscrete> {
+        FiniteDiscrete::with_truncated_mass(offset, probabilities, 0.0)
+    }
+
+    fn with_truncated_mass(
+        offset: i64,
+        mut probabilities: Vec<f64>,
+        truncated_mass: f64,
+    ) -> Result<FiniteDiscrete> {
+        if probabilities.is_empty()
+            || probabilities.iter().any(|p| !p.is_finite() || *p < 0.0)
+            || offset.checked_add(probabilities.len() as i64 - 1).is_none()
+        {
+            return Err(StatsError::BadParams);
+        }
+        let sum: f64 = probabilities.iter().sum();
+        if sum <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        probabilities.iter_mut().for_each(|p| *p /= sum);
+        let mut acc = 0.0;
+        let mut cdf: Vec<f64> = probabilities
+            .iter()
+            .map(|p| {
+                acc += p;
+                acc.min(1.0)
+            })
+            .collect();
+        *cdf.last_mut().unwrap() = 1.0;
+        Ok(FiniteDiscrete {
+            offset,
+            probabilities,
+            cdf,
+            truncated_mass,
+        })
+    }
+
+    /// Returns the smallest point of the support
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Returns the normalized probabilities of the support points
+    /// `offset, offset + 1, ...`
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Returns the probability mass that was discarded by truncating the
+    /// supports of the convolved distributions before renormalizing, or `0`
+    /// for a distribution constructed directly
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{convolve_n, Poisson};
+    ///
+    /// let n = convolve_n(&Poisson::new(2.0).unwrap(), 3, 1e-12).unwrap();
+    /// assert!(n.truncated_mass() > 0.0 && n.truncated_mass() < 3e-12);
+    /// ```
+    pub fn truncated_mass(&self) -> f64 {
+        self.truncated_mass
+    }
+
+    fn index(&self, x: i64) -> Option<usize> {
+        let i = x.checked_sub(self.offset)?;
+        if i >= 0 && (i as usize) < self.probabilities.len() {
+            Some(i as usize)
+        } else {
+            None
+        }
+    }
+
+    fn moment_about(&self, center: f64, k: i32) -> f64 {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p * ((self.offset + i as i64) as f64 - center).powi(k))
+            .sum()
+    }
+}
+
+impl Parameterized for FiniteDiscrete {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let probabilities = self
+            .probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (format!("probabilities[{}]", i), p));
+        std::iter::once(("offset".into(), self.offset as f64))
+            .chain(probabilities)
+            .collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<FiniteDiscrete> {
+        let values = internal::parameter_values(values, self.probabilities.len() + 1)?;
+        FiniteDiscrete::with_truncated_mass(
+            internal::integer_parameter(values[0])?,
+            values[1..].to_vec(),
+            self.truncated_mass,
+        )
+    }
+}
+
+impl std::fmt::Display for FiniteDiscrete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FiniteDiscrete({}..={})",
+            self.offset,
+            self.offset + self.probabilities.len() as i64 - 1
+        )
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for FiniteDiscrete {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        let u: f64 = r.gen();
+        let i = self.cdf.partition_point(|&c| c <= u);
+        (self.offset + i.min(self.cdf.len() - 1) as i64) as f64
+    }
+}
+
+impl DiscreteCDF<i64, f64> for FiniteDiscrete {
+    /// Calculates the cumulative distribution function for the finite
+    /// discrete distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k <= x} p_k
+    /// ```
+    fn cdf(&self, x: i64) -> f64 {
+        if x < self.offset {
+            0.0
+        } else {
+            match self.index(x) {
+                Some(i) => self.cdf[i],
+                None => 1.0,
+            }
+        }
+    }
+
+    /// Calculates the survival function for the finite discrete
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k > x} p_k
+    /// ```
+    fn sf(&self, x: i64) -> f64 {
+        if x < self.offset {
+            1.0
+        } else {
+            match self.index(x) {
+                Some(i) => self.probabilities[i + 1..].iter().sum(),
+                None => 0.0,
+            }
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function, the
+    /// smallest `x` with `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> i64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let i = self.cdf.partition_point(|&c| c < p);
+        self.offset + i.min(self.cdf.len() - 1) as i64
+    }
+}
+
+impl Min<i64> for FiniteDiscrete {
+    /// Returns the smallest point of the support
+    fn min(&self) -> i64 {
+        self.offset
+    }
+}
+
+impl Max<i64> for FiniteDiscrete {
+    /// Returns the largest point of the support
+    fn max(&self) -> i64 {
+        self.offset + self.probabilities.len() as i64 - 1
+    }
+}
+
+impl Distribution<f64> for FiniteDiscrete {
+    /// Returns the mean of the finite discrete distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_k k p_k
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.offset as f64 + self.moment_about(self.offset as f64, 1))
+    }
+
+    /// Returns the variance of the finite discrete distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_k (k - μ)^2 p_k
+    /// ```
+    ///
+    /// where `μ` is the mean
+    fn variance(&self) -> Option<f64> {
+        Some(self.moment_about(self.mean()?, 2))
+    }
+
+    /// Returns the entropy of the finite discrete distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -Σ_k p_k ln(p_k)
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            -self
+                .probabilities
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .map(|p| p * p.ln())
+                .sum::<f64>(),
+        )
+    }
+
+    /// Returns the skewness of the finite discrete distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_k (k - μ)^3 p_k / σ^3
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let sd = self.std_dev()?;
+        Some(self.moment_about(mean, 3) / (sd * sd * sd))
+    }
+}
+
+impl Discrete<i64, f64> for FiniteDiscrete {
+    /// Calculates the probability mass function for the finite discrete
+    /// distribution at `x`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `0.0` if `x` is outside the tabulated support
+    fn pmf(&self, x: i64) -> f64 {
+        match self.index(x) {
+            Some(i) => self.probabilities[i],
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the log probability mass function for the finite discrete
+    /// distribution at `x`
+    fn ln_pmf(&self, x: i64) -> f64 {
+        self.pmf(x).ln()
+    }
+}
+
+/// Returns the exact distribution of the sum of independent draws from
+/// `d1` and `d2`
+///
+/// Each support is tabulated from its minimum upwards, dropping at most
+/// `truncation_epsilon / 2` of mass from each tail, and the two
+/// probability vectors are convolved directly. The result is renormalized
+/// and the mass lost to truncation is available from
+/// [`FiniteDiscrete::truncated_mass`].
+///
+/// # Remarks
+///
+/// The direct convolution costs `O(n m)` for supports of `n` and `m`
+/// points, so the tabulated supports and the result are capped at
+/// [`MAX_CONVOLUTION_SUPPORT`] points. Distributions with unbounded support need a
+/// positive `truncation_epsilon`.
+///
+/// # Errors
+///
+/// Returns an error if `truncation_epsilon` is not in `[0, 1)` or if a
+/// tabulated support or the result would exceed [`MAX_CONVOLUTION_SUPPORT`] points
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{discrete_convolve, Discrete, DiscreteUniform};
+///
+/// let die = DiscreteUniform::new(1, 6).unwrap();
+/// let two_dice = discrete_convolve(&die, &die, 0.0).unwrap();
+/// assert!((two_dice.pmf(7) - 6.0 / 36.0).abs() < 1e-15);
+/// ```
+pub fn discrete_convolve<K1, D1, K2, D2>(
+    d1: &D1,
+    d2: &D2,
+    truncation_epsilon: f64,
+) -> Result<FiniteDiscrete>
+where
+    K1: PrimInt + NumAssignOps,
+    D1: Discrete<K1, f64> + DiscreteCDF<K1, f64>,
+    K2: PrimInt + NumAssignOps,
+    D2: Discrete<K2, f64> + DiscreteCDF<K2, f64>,
+{
+    check_epsilon(truncation_epsilon)?;
+    let (offset_1, pmf_1, kept_1) = tabulate(d1, truncation_epsilon)?;
+    let (offset_2, pmf_2, kept_2) = tabulate(d2, truncation_epsilon)?;
+    if pmf_1.len() + pmf_2.len() - 1 > MAX_CONVOLUTION_SUPPORT {
+        return Err(StatsError::SpecialCase(
+            "convolution support exceeds MAX_CONVOLUTION_SUPPORT",
+        ));
+    }
+    let offset = offset_1
+        .checked_add(offset_2)
+        .ok_or(StatsError::BadParams)?;
+    let pmf = convolve(&pmf_1, &pmf_2);
+    FiniteDiscrete::with_truncated_mass(offset, pmf, 1.0 - kept_1 * kept_2)
+}
+
+/// Returns the exact distribution of the sum of `n` independent draws from
+/// `d`, such as the total of a pool of `n` dice
+///
+/// The support of `d` is tabulated as in [`discrete_convolve`] and the
+/// `n`-fold convolution is built by binary exponentiation, squaring the
+/// probability vector `O(log n)` times.
+///
+/// # Remarks
+///
+/// The result has `n (m - 1) + 1` points for a tabulated support of `m`
+/// points and each direct convolution is quadratic in its input sizes, so
+/// the result is capped at [`MAX_CONVOLUTION_SUPPORT`] points. The truncated mass
+/// compounds to roughly `n` times that of a single tabulation.
+///
+/// # Errors
+///
+/// Returns an error if `n == 0`, if `truncation_epsilon` is not in
+/// `[0, 1)`, or if the tabulated support or the result would exceed
+/// [`MAX_CONVOLUTION_SUPPORT`] points
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{convolve_n, Bernoulli, Discrete};
+///
+/// let n = convolve_n(&Bernoulli::new(0.5).unwrap(), 4, 0.0).unwrap();
+/// assert!((n.pmf(2) - 6.0 / 16.0).abs() < 1e-15);
+/// ```
+pub fn convolve_n<K, D>(d: &D, n: u64, truncation_epsilon: f64) -> Result<FiniteDiscrete>
+where
+    K: PrimInt + NumAssignOps,
+    D: Discrete<K, f64> + DiscreteCDF<K, f64>,
+{
+    if n == 0 {
+        return Err(StatsError::ArgMustBePositive("n"));
+    }
+    check_epsilon(truncation_epsilon)?;
+    let (offset, pmf, kept) = tabulate(d, truncation_epsilon)?;
+    let width = (pmf.len() - 1) as u128;
+    if width * n as u128 + 1 > MAX_CONVOLUTION_SUPPORT as u128 {
+        return Err(StatsError::SpecialCase(
+            "convolution support exceeds MAX_CONVOLUTION_SUPPORT",
+        ));
+    }
+    let offset = i64::try_from(offset as i128 * n as i128).map_err(|_| StatsError::BadParams)?;
+    let mut result = vec![1.0];
+    let mut base = pmf;
+    let mut remaining = n;
+    loop {
+        if remaining & 1 == 1 {
+            result = convolve(&result, &base);
+        }
+        remaining >>= 1;
+        if remaining == 0 {
+            break;
+        }
+        base = convolve(&base, &base);
+    }
+    let truncated = 1.0 - kept.powi(n.min(i32::MAX as u64) as i32);
+    FiniteDiscrete::with_truncated_mass(offset, result, truncated)
+}
+
+fn check_epsilon(truncation_epsilon: f64) -> Result<()> {
+    if !(0.0..1.0).contains(&truncation_epsilon) {
+        Err(StatsError::ArgIntervalExclMax(
+            "truncation_epsilon",
+            0.0,
+            1.0,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Tabulates the pmf of `d` from its minimum until the remaining upper tail
+/// is at most `epsilon / 2`, then drops leading points holding at most
+/// `epsilon / 2` in total. Returns the offset, the probabilities and the
+/// mass kept, which is exactly `1` when nothing was truncated.
+fn tabulate<K, D>(d: &D, epsilon: f64) -> Result<(i64, Vec<f64>, f64)>
+where
+    K: PrimInt + NumAssignOps,
+    D: Discrete<K, f64> + DiscreteCDF<K, f64>,
+{
+    let too_large = || StatsError::SpecialCase("tabulated support exceeds MAX_CONVOLUTION_SUPPORT");
+    let min = d.min();
+    let max = d.max();
+    let offset = min.to_i64().ok_or(StatsError::BadParams)?;
+    let mut pmf = Vec::new();
+    let mut total = 0.0;
+    let mut k = min;
+    let upper_tail = loop {
+        if pmf.len() == MAX_CONVOLUTION_SUPPORT {
+            return Err(too_large());
+        }
+        let p = d.pmf(k);
+        pmf.push(p);
+        total += p;
+        if k >= max {
+            break 0.0;
+        }
+        if 1.0 - total <= 0.5 * epsilon {
+            break (1.0 - total).max(0.0);
+        }
+        k += K::one();
+    };
+    let mut dropped = 0.0;
+    let mut start = 0;
+    while start + 1 < pmf.len() && dropped + pmf[start] <= 0.5 * epsilon {
+        dropped += pmf[start];
+        start += 1;
+    }
+    pmf.drain(..start);
+    let offset = offset
+        .checked_add(start as i64)
+        .ok_or(StatsError::BadParams)?;
+    let kept = 1.0 - upper_tail - dropped;
+    Ok((offset, pmf, kept))
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0.0 {
+            continue;
+        }
+        for (o, &y) in out[i..].iter_mut().zip(b) {
+            *o += x * y;
+        }
+    }
+    out
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Bernoulli, Binomial, DiscreteUniform, Poisson};
+
+    #[test]
+    fn test_create() {
+        let n = FiniteDiscrete::new(3, vec![1.0, 3.0]).unwrap();
+        assert_eq!(n.probabilities(), &[0.25, 0.75]);
+        assert_eq!(n.min(), 3);
+        assert_eq!(n.max(), 4);
+        assert_eq!(n.truncated_mass(), 0.0);
+        assert!(FiniteDiscrete::new(0, vec![]).is_err());
+        assert!(FiniteDiscrete::new(0, vec![0.0, 0.0]).is_err());
+        assert!(FiniteDiscrete::new(0, vec![f64::NAN]).is_err());
+        assert!(FiniteDiscrete::new(i64::MAX, vec![0.5, 0.5]).is_err());
+    }
+
+    #[test]
+    fn test_two_dice() {
+        let die = DiscreteUniform::new(1, 6).unwrap();
+        let n = discrete_convolve(&die, &die, 0.0).unwrap();
+        assert_eq!(n.min(), 2);
+        assert_eq!(n.max(), 12);
+        for s in 2..=12 {
+            let expected = (6 - (s - 7i64).abs()) as f64 / 36.0;
+            assert_almost_eq!(n.pmf(s), expected, 1e-16);
+        }
+        assert_eq!(n.pmf(1), 0.0);
+        assert_eq!(n.pmf(13), 0.0);
+        assert_eq!(n.truncated_mass(), 0.0);
+        assert_almost_eq!(n.mean().unwrap(), 7.0, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 35.0 / 6.0, 1e-13);
+        assert_almost_eq!(n.skewness().unwrap(), 0.0, 1e-14);
+        assert_eq!(n.cdf(7), n.probabilities()[..6].iter().sum::<f64>());
+        assert_eq!(n.inverse_cdf(0.5), 7);
+        assert_eq!(n.inverse_cdf(0.0), 2);
+        assert_eq!(n.inverse_cdf(1.0), 12);
+    }
+
+    #[test]
+    fn test_convolve_n_bernoulli_is_binomial() {
+        let bernoulli = Bernoulli::new(0.3).unwrap();
+        let binomial = Binomial::new(0.3, 10).unwrap();
+        let n = convolve_n(&bernoulli, 10, 0.0).unwrap();
+        assert_eq!(n.min(), 0);
+        assert_eq!(n.max(), 10);
+        for k in 0..=10 {
+            assert_almost_eq!(n.pmf(k as i64), binomial.pmf(k), 1e-14);
+        }
+        assert_almost_eq!(n.probabilities().iter().sum::<f64>(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_convolve_n_matches_repeated_convolution() {
+        let die = DiscreteUniform::new(1, 6).unwrap();
+        let three = convolve_n(&die, 3, 0.0).unwrap();
+        let two = discrete_convolve(&die, &die, 0.0).unwrap();
+        let again = discrete_convolve(&two, &die, 0.0).unwrap();
+        assert_eq!(three.min(), 3);
+        for s in 3..=18 {
+            assert_almost_eq!(three.pmf(s), again.pmf(s), 1e-16);
+        }
+        assert_almost_eq!(three.pmf(10), 27.0 / 216.0, 1e-16);
+        let one = convolve_n(&die, 1, 0.0).unwrap();
+        assert_eq!(one.probabilities().len(), 6);
+    }
+
+    #[test]
+    fn test_truncation() {
+        let poisson = Poisson::new(4.0).unwrap();
+        let n = convolve_n(&poisson, 5, 1e-12).unwrap();
+        assert!(n.truncated_mass() > 0.0 && n.truncated_mass() <= 5e-12);
+        assert_almost_eq!(n.probabilities().iter().sum::<f64>(), 1.0, 1e-12);
+        let sum = Poisson::new(20.0).unwrap();
+        for k in 5..40 {
+            assert_almost_eq!(n.pmf(k), sum.pmf(k as u64), 1e-12);
+        }
+        // an unbounded support cannot be tabulated exactly
+        assert!(convolve_n(&poisson, 2, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        let die = DiscreteUniform::new(1, 6).unwrap();
+        assert!(convolve_n(&die, 0, 0.0).is_err());
+        assert!(convolve_n(&die, 2, 1.0).is_err());
+        assert!(discrete_convolve(&die, &die, -0.1).is_err());
+        assert!(convolve_n(&die, MAX_CONVOLUTION_SUPPORT as u64, 0.0).is_err());
+        let wide = DiscreteUniform::new(0, MAX_CONVOLUTION_SUPPORT as i64).unwrap();
+        assert!(discrete_convolve(&wide, &die, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        use rand::distributions::Distribution;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let die = DiscreteUniform::new(1, 6).unwrap();
+        let n = discrete_convolve(&die, &die, 0.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut counts = [0usize; 13];
+        for _ in 0..36_000 {
+            let x = n.sample(&mut rng);
+            assert!((2.0..=12.0).contains(&x));
+            counts[x as usize] += 1;
+        }
+        assert!((counts[7] as f64 - 6000.0).abs() < 300.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = FiniteDiscrete::new(-2, vec![1.0, 2.0, 5.0]).unwrap();
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.pmf(-2), d.pmf(-1), d.pmf(0)]);
+        assert_eq!(n.parameters()[0], ("offset".to_string(), -2.0));
+        assert_eq!(n.set_parameter("probabilities[0]", -1.0), FiniteDiscrete::new(-2, vec![-1.0, 2.0, 5.0]));
+        assert!(n.set_parameter("offset", 0.5).is_err());
+    }
+}
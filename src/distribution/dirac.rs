@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -45,6 +45,19 @@ impl Dirac {
     }
 }
 
+impl Parameterized for Dirac {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("v".into(), self.0)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Dirac> {
+        match *values {
+            [v] => Dirac::new(v),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Dirac {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "δ_{}", self.0)
@@ -81,6 +94,21 @@ impl ContinuousCDF<f64, f64> for Dirac {
             0.0
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// dirac distribution at `p`
+    ///
+    /// Where the value is `v` for every `p`.
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        self.0
+    }
 }
 
 impl Min<f64> for Dirac {
@@ -195,6 +223,7 @@ impl Mode<Option<f64>> for Dirac {
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Dirac};
+    use crate::distribution::Parameterized;
 
     fn try_create(v: f64) -> Dirac {
         let d = Dirac::new(v);
@@ -303,4 +332,19 @@ mod tests {
         test_case(f64::INFINITY, 1.0, sf(1.0));
         test_case(f64::INFINITY, 0.0, sf(f64::INFINITY));
     }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        use crate::distribution::internal::*;
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(2.5), [2.5, 2.5, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.5);
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.cdf(1.0), d.cdf(2.0)]);
+        assert_eq!(n.set_parameter("v", f64::NAN), Dirac::new(f64::NAN));
+    }
 }
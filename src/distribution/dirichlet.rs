@@ -1,4 +1,4 @@
-use crate::distribution::Continuous;
+use crate::distribution::{internal, Continuous, Parameterized};
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{prec, Result, StatsError};
@@ -104,6 +104,40 @@ impl Dirichlet {
         &self.alpha
     }
 
+    /// Draws the first `k` weights of the GEM(`alpha`) distribution, the
+    /// weights of a Dirichlet process with concentration `alpha`, by
+    /// successive `Beta(1, alpha)` breaks of a unit stick
+    ///
+    /// # Remarks
+    ///
+    /// The last weight takes all the mass left after `k - 1` breaks, so the
+    /// weights sum to `1`; see `StickBreaking` for weights drawn lazily
+    /// until a mass threshold is reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` is not positive and finite or if `k == 0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::distribution::Dirichlet;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let weights = Dirichlet::stick_breaking_sample(1.0, 5, &mut rng).unwrap();
+    /// assert_eq!(weights.len(), 5);
+    /// assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn stick_breaking_sample<R: Rng + ?Sized>(
+        alpha: f64,
+        k: usize,
+        rng: &mut R,
+    ) -> Result<Vec<f64>> {
+        super::stick_breaking::stick_breaking_sample(alpha, k, rng)
+    }
+
     fn alpha_sum(&self) -> f64 {
         self.alpha.fold(0.0, |acc, x| acc + x)
     }
@@ -137,6 +171,20 @@ impl Dirichlet {
     }
 }
 
+impl Parameterized for Dirichlet {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        self.alpha
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| (format!("alpha[{}]", i), a))
+            .collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Dirichlet> {
+        Dirichlet::new(internal::parameter_values(values, self.alpha.len())?)
+    }
+}
+
 impl std::fmt::Display for Dirichlet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Dir({}, {})", self.alpha.len(), &self.alpha)
@@ -145,6 +193,11 @@ impl std::fmt::Display for Dirichlet {
 
 impl ::rand::distributions::Distribution<DVector<f64>> for Dirichlet {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DVector<f64> {
+        // the flat Dirichlet distribution is that of the uniform spacings
+        if self.alpha.iter().all(|&a| a == 1.0) {
+            let spacings = crate::generate::uniform_spacings(self.alpha.len() - 1, rng);
+            return DVector::from_vec(spacings);
+        }
         let mut sum = 0.0;
         let mut samples: Vec<_> = self
             .alpha
@@ -458,4 +511,31 @@ mod tests {
         let n = try_create(&[0.1, 0.3, 0.5, 0.8]);
         n.ln_pdf(&dvec![0.5, 0.25, 0.8, 0.9]);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap();
+        let x = DVector::from_vec(vec![0.2, 0.3, 0.5]);
+        crate::distribution::internal::test::check_parameters(&n, |d| d.pdf(&x));
+        assert_eq!(n.parameters()[2], ("alpha[2]".to_string(), 3.0));
+        assert_eq!(n.set_parameter("alpha[0]", 0.0), Dirichlet::new(vec![0.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn test_sample_flat() {
+        use crate::rng_util::SplitMix64;
+        use rand::distributions::Distribution;
+        let n = Dirichlet::new_with_param(1.0, 4).unwrap();
+        let mut rng = SplitMix64::new(3042);
+        let mut mean = DVector::zeros(4);
+        for _ in 0..10000 {
+            let x = n.sample(&mut rng);
+            assert!(x.iter().all(|&v| v >= 0.0));
+            assert_almost_eq!(x.sum(), 1.0, 1e-15);
+            mean += x;
+        }
+        for m in (mean / 10000.0).iter() {
+            assert!((m - 0.25).abs() < 0.01, "{}", m);
+        }
+    }
 }
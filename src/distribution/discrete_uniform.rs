@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -51,6 +51,25 @@ impl DiscreteUniform {
     }
 }
 
+impl Parameterized for DiscreteUniform {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("min".into(), self.min as f64),
+            ("max".into(), self.max as f64),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<DiscreteUniform> {
+        match *values {
+            [min, max] => DiscreteUniform::new(
+                internal::integer_parameter(min)?,
+                internal::integer_parameter(max)?,
+            ),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for DiscreteUniform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Uni([{}, {}])", self.min, self.max)
@@ -259,6 +278,7 @@ mod tests {
     use std::fmt::Debug;
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, DiscreteUniform};
+    use crate::distribution::Parameterized;
 
     fn try_create(min: i64, max: i64) -> DiscreteUniform {
         let n = DiscreteUniform::new(min, max);
@@ -416,4 +436,12 @@ mod tests {
         let cdf = |arg: i64| move |x: DiscreteUniform| x.cdf(arg);
         test_case(0, 3, 1.0, cdf(5));
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(-2, 5);
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.pmf(-3), d.pmf(0), d.pmf(5)]);
+        assert_eq!(n.set_parameter("min", 10.0), DiscreteUniform::new(10, 5));
+        assert!(n.set_parameter("max", 5.5).is_err());
+    }
 }
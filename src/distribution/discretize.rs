@@ -0,0 +1,518 @@
+//! Provides the discretization of a continuous distribution onto a lattice
+//! of equally spaced points
+
+use crate::distribution::{internal, ContinuousCDF, Discrete, DiscreteCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Tail mass below which the summation of the moments stops
+const TAIL_EPSILON: f64 = 1e-16;
+
+/// Largest number of lattice points summed for the moments
+const MAX_TERMS: usize = 1 << 24;
+
+/// The rule mapping values of the parent distribution to lattice points
+/// `k * step` in [`Discretize`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DiscretizeRule {
+    /// Rounds to the nearest lattice point, so `k` collects
+    /// `[(k - 1/2) step, (k + 1/2) step)`
+    Round,
+    /// Rounds down to a lattice point, so `k` collects `[k step, (k + 1) step)`
+    Floor,
+    /// Assigns `k` the cdf mass of `((k - 1/2) step, (k + 1/2) step]`,
+    /// sampled by inverting the parent cdf
+    CdfMass,
+}
+
+/// Implements the discretization of a continuous distribution onto the
+/// lattice `{k * step}` under a [`DiscretizeRule`]
+///
+/// The probability mass function and cdf are indexed by the lattice index
+/// `k`, while samples, the mean and the variance are on the scale of the
+/// parent, that is of the lattice values `k * step`.
+///
+/// # Remarks
+///
+/// For a continuous parent the `Round` and `CdfMass` rules give the same
+/// probabilities, since the ends of the intervals carry no mass; they
+/// differ in how samples are drawn. Each mass is computed as a difference
+/// of the parent cdf, or of its survival function in the upper tail, so the
+/// masses are exact up to the accuracy of the parent and sum to `1`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, Discretize, DiscretizeRule, Exp};
+///
+/// let n = Discretize::new(Exp::new(1.0).unwrap(), 1.0, DiscretizeRule::Floor).unwrap();
+/// assert!((n.pmf(0) - (1.0 - (-1f64).exp())).abs() < 1e-15);
+/// assert_eq!(n.value(3), 3.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Discretize<D> {
+    dist: D,
+    step: f64,
+    rule: DiscretizeRule,
+}
+
+impl<D> Discretize<D> {
+    /// Constructs the discretization of `dist` onto the multiples of `step`
+    /// under `rule`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `step` is not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Discretize, DiscretizeRule, Normal};
+    ///
+    /// let mut result = Discretize::new(Normal::standard(), 0.5, DiscretizeRule::Round);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Discretize::new(Normal::standard(), 0.0, DiscretizeRule::Round);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(dist: D, step: f64, rule: DiscretizeRule) -> Result<Discretize<D>> {
+        if step.is_nan() || step <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("step"));
+        }
+        if !step.is_finite() {
+            return Err(StatsError::ArgFinite("step"));
+        }
+        Ok(Discretize { dist, step, rule })
+    }
+
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+
+    /// Returns the spacing of the lattice
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Returns the discretization rule
+    pub fn rule(&self) -> DiscretizeRule {
+        self.rule
+    }
+
+    /// Returns the lattice value `k * step` of the index `k`
+    pub fn value(&self, k: i64) -> f64 {
+        k as f64 * self.step
+    }
+
+    /// Returns the position of lattice point `0` within its interval, as a
+    /// fraction of `step` from the lower end
+    fn offset(&self) -> f64 {
+        match self.rule {
+            DiscretizeRule::Floor => 0.0,
+            DiscretizeRule::Round | DiscretizeRule::CdfMass => 0.5,
+        }
+    }
+
+    /// Returns the ends of the interval collected by lattice point `k`
+    fn edges(&self, k: i64) -> (f64, f64) {
+        let lo = (k as f64 - self.offset()) * self.step;
+        (lo, lo + self.step)
+    }
+
+    /// Returns the lattice index that the rule assigns to a value `x` of
+    /// the parent, saturating at the bounds of `i64`
+    fn index(&self, x: f64) -> i64 {
+        let scaled = x / self.step + self.offset();
+        match self.rule {
+            DiscretizeRule::Round | DiscretizeRule::Floor => scaled.floor() as i64,
+            DiscretizeRule::CdfMass => (scaled.ceil() - 1.0) as i64,
+        }
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Discretize<D> {
+    /// Returns the parent mass of the interval `(lo, hi)`, using the
+    /// survival function in the upper tail to avoid cancellation
+    fn mass(&self, lo: f64, hi: f64) -> f64 {
+        let upper = self.dist.cdf(hi);
+        if upper <= 0.5 {
+            upper - self.dist.cdf(lo)
+        } else {
+            self.dist.sf(lo) - self.dist.sf(hi)
+        }
+    }
+
+    /// Returns the first two moments of the lattice values about `center`,
+    /// summing outwards from the lattice point of `center` until the mass
+    /// left in each tail is below `TAIL_EPSILON`
+    fn raw_moments(&self, center: i64) -> Option<(f64, f64)> {
+        let (min, max) = (self.min(), self.max());
+        let center = center.clamp(min, max);
+        let (mut m1, mut m2) = (0.0, 0.0);
+        let mut terms = 0;
+        let mut add = |k: i64, p: f64| {
+            let d = (k - center) as f64 * self.step;
+            m1 += p * d;
+            m2 += p * d * d;
+        };
+        // upwards, with masses as differences of the survival function
+        let mut k = center;
+        let mut upper = self.dist.sf(self.edges(k).0);
+        loop {
+            let tail = self.dist.sf(self.edges(k).1);
+            add(k, upper - tail);
+            upper = tail;
+            terms += 1;
+            if tail < TAIL_EPSILON || k == max {
+                break;
+            }
+            if terms > MAX_TERMS {
+                return None;
+            }
+            k += 1;
+        }
+        // downwards, with masses as differences of the cdf
+        let mut k = center;
+        let mut lower = self.dist.cdf(self.edges(k).0);
+        while lower >= TAIL_EPSILON && k > min {
+            k -= 1;
+            let tail = self.dist.cdf(self.edges(k).0);
+            add(k, lower - tail);
+            lower = tail;
+            terms += 1;
+            if terms > MAX_TERMS {
+                return None;
+            }
+        }
+        Some((m1, m2))
+    }
+}
+
+impl<D: Parameterized> Parameterized for Discretize<D> {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let mut parameters = internal::inner_parameters(&self.dist);
+        parameters.push(("step".into(), self.step));
+        parameters
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Discretize<D>> {
+        match values.split_last() {
+            Some((&step, inner)) => {
+                Discretize::new(self.dist.with_parameters(inner)?, step, self.rule)
+            }
+            None => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for Discretize<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Discretize({}, {}, {:?})",
+            self.dist, self.step, self.rule
+        )
+    }
+}
+
+impl<D> ::rand::distributions::Distribution<f64> for Discretize<D>
+where
+    D: ContinuousCDF<f64, f64> + ::rand::distributions::Distribution<f64>,
+{
+    /// Draws a lattice value `k * step`, by applying the rule to a draw
+    /// from the parent for `Round` and `Floor` and by inverting the parent
+    /// cdf at a uniform draw for `CdfMass`
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        let x = match self.rule {
+            DiscretizeRule::Round | DiscretizeRule::Floor => self.dist.sample(r),
+            DiscretizeRule::CdfMass => self.dist.inverse_cdf(r.gen()),
+        };
+        self.value(self.index(x))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> DiscreteCDF<i64, f64> for Discretize<D> {
+    /// Calculates the cumulative distribution function for the discretized
+    /// distribution at lattice index `k`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F((k + 1 - o) h)
+    /// ```
+    ///
+    /// where `F` is the parent cdf, `h` the step and `o` is `0` for `Floor`
+    /// and `1/2` otherwise
+    fn cdf(&self, k: i64) -> f64 {
+        self.dist.cdf(self.edges(k).1)
+    }
+
+    /// Calculates the survival function for the discretized distribution
+    /// at lattice index `k`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S((k + 1 - o) h)
+    /// ```
+    ///
+    /// where `S` is the parent survival function, `h` the step and `o` is
+    /// `0` for `Floor` and `1/2` otherwise
+    fn sf(&self, k: i64) -> f64 {
+        self.dist.sf(self.edges(k).1)
+    }
+
+    /// Calculates the inverse cumulative distribution function, the
+    /// smallest lattice index `k` with `cdf(k) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> i64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let (min, max) = (self.min(), self.max());
+        let x = self.dist.inverse_cdf(p);
+        let mut k = ((x / self.step + self.offset()).ceil() - 1.0) as i64;
+        k = k.clamp(min, max);
+        // correct for rounding in the parent quantile
+        while k > min && self.cdf(k - 1) >= p {
+            k -= 1;
+        }
+        while k < max && self.cdf(k) < p {
+            k += 1;
+        }
+        k
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Min<i64> for Discretize<D> {
+    /// Returns the smallest lattice index with positive mass, saturating at
+    /// `i64::MIN`
+    fn min(&self) -> i64 {
+        (self.dist.min() / self.step + self.offset()).floor() as i64
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Max<i64> for Discretize<D> {
+    /// Returns the largest lattice index with positive mass, saturating at
+    /// `i64::MAX`
+    fn max(&self) -> i64 {
+        ((self.dist.max() / self.step + self.offset()).ceil() - 1.0) as i64
+    }
+}
+
+impl<D> Distribution<f64> for Discretize<D>
+where
+    D: ContinuousCDF<f64, f64> + Distribution<f64>,
+{
+    /// Returns the mean of the lattice values, or `None` if the parent has
+    /// no mean
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_k k h p_k
+    /// ```
+    ///
+    /// where `h` is the step
+    ///
+    /// # Remarks
+    ///
+    /// The sum runs outwards from the lattice point of the parent mean until
+    /// less than `1e-16` of mass is left in each tail, and returns `None` if
+    /// that takes more than `2^24` lattice points
+    fn mean(&self) -> Option<f64> {
+        let center = self.index(self.dist.mean()?);
+        let (m1, _) = self.raw_moments(center)?;
+        Some(self.value(center.clamp(self.min(), self.max())) + m1)
+    }
+
+    /// Returns the variance of the lattice values, or `None` if the parent
+    /// has no variance
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_k (k h - μ)^2 p_k
+    /// ```
+    ///
+    /// where `h` is the step and `μ` the mean
+    ///
+    /// # Remarks
+    ///
+    /// The sum is truncated as for the mean
+    fn variance(&self) -> Option<f64> {
+        self.dist.variance()?;
+        let center = self.index(self.dist.mean()?);
+        let (m1, m2) = self.raw_moments(center)?;
+        Some((m2 - m1 * m1).max(0.0))
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> Discrete<i64, f64> for Discretize<D> {
+    /// Calculates the probability mass function for the discretized
+    /// distribution at lattice index `k`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F((k + 1 - o) h) - F((k - o) h)
+    /// ```
+    ///
+    /// where `F` is the parent cdf, `h` the step and `o` is `0` for `Floor`
+    /// and `1/2` otherwise
+    fn pmf(&self, k: i64) -> f64 {
+        let (lo, hi) = self.edges(k);
+        self.mass(lo, hi)
+    }
+
+    /// Calculates the log probability mass function for the discretized
+    /// distribution at lattice index `k`
+    fn ln_pmf(&self, k: i64) -> f64 {
+        self.pmf(k).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Normal, Uniform};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn exp_rules(step: f64) -> Vec<Discretize<Exp>> {
+        [DiscretizeRule::Round, DiscretizeRule::Floor, DiscretizeRule::CdfMass]
+            .iter()
+            .map(|&rule| Discretize::new(Exp::new(1.5).unwrap(), step, rule).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_create() {
+        let exp = Exp::new(1.0).unwrap();
+        assert!(Discretize::new(exp, 0.1, DiscretizeRule::Floor).is_ok());
+        assert!(Discretize::new(exp, -0.1, DiscretizeRule::Floor).is_err());
+        assert!(Discretize::new(exp, f64::NAN, DiscretizeRule::Round).is_err());
+        assert!(Discretize::new(exp, f64::INFINITY, DiscretizeRule::Round).is_err());
+    }
+
+    #[test]
+    fn test_exponential_masses() {
+        // P(a <= X < b) = e^(-λa) - e^(-λb)
+        let interval = |a: f64, b: f64| (-1.5 * a.max(0.0)).exp() - (-1.5 * b.max(0.0)).exp();
+        let d = exp_rules(0.25);
+        for k in -2..40 {
+            let v = k as f64 * 0.25;
+            assert_almost_eq!(d[0].pmf(k), interval(v - 0.125, v + 0.125), 1e-15);
+            assert_almost_eq!(d[1].pmf(k), interval(v, v + 0.25), 1e-15);
+            assert_eq!(d[2].pmf(k), d[0].pmf(k));
+            assert_almost_eq!(d[1].cdf(k), 1.0 - (-1.5 * (v + 0.25).max(0.0)).exp(), 1e-15);
+        }
+        assert_eq!(d[0].pmf(-1), 0.0);
+        // relative accuracy is kept deep in the upper tail
+        let tail = d[1].pmf(100);
+        assert_almost_eq!(tail / interval(25.0, 25.25), 1.0, 1e-12);
+        assert_eq!((d[0].min(), d[1].min(), d[0].max()), (0, 0, i64::MAX));
+    }
+
+    #[test]
+    fn test_total_mass() {
+        for d in exp_rules(0.1) {
+            let total: f64 = (d.min()..2000).map(|k| d.pmf(k)).sum();
+            assert_almost_eq!(total, 1.0, 1e-12);
+        }
+        let normal = Discretize::new(Normal::new(1.0, 2.0).unwrap(), 0.3, DiscretizeRule::Round).unwrap();
+        let total: f64 = (-100..100).map(|k| normal.pmf(k)).sum();
+        assert_almost_eq!(total, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_moments() {
+        // floor of an exponential is geometric: mean h / (e^(λh) - 1)
+        let d = exp_rules(0.5);
+        let q = (-1.5f64 * 0.5).exp();
+        assert_almost_eq!(d[1].mean().unwrap(), 0.5 * q / (1.0 - q), 1e-13);
+        assert_almost_eq!(d[1].variance().unwrap(), 0.25 * q / ((1.0 - q) * (1.0 - q)), 1e-13);
+        let normal = Discretize::new(Normal::new(0.0, 1.0).unwrap(), 0.5, DiscretizeRule::Round).unwrap();
+        assert_almost_eq!(normal.mean().unwrap(), 0.0, 1e-15);
+    }
+
+    #[test]
+    fn test_moments_converge() {
+        let exp = Exp::new(1.5).unwrap();
+        let mut previous = f64::INFINITY;
+        for &step in &[0.5, 0.1, 0.02, 0.004] {
+            let d = Discretize::new(exp, step, DiscretizeRule::Round).unwrap();
+            let error = (d.mean().unwrap() - exp.mean().unwrap()).abs()
+                + (d.variance().unwrap() - exp.variance().unwrap()).abs();
+            assert!(error < previous);
+            previous = error;
+        }
+        assert!(previous < 1e-5);
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let d = Discretize::new(normal, 0.01, DiscretizeRule::Floor).unwrap();
+        // flooring shifts the mean down by h/2 and adds h^2/12 to the variance,
+        // up to the accuracy of the normal cdf
+        assert_almost_eq!(d.mean().unwrap(), 2.0 - 0.005, 1e-10);
+        assert_almost_eq!(d.variance().unwrap(), 9.0 + 1e-4 / 12.0, 1e-9);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for d in exp_rules(0.1) {
+            for &p in &[0.0, 1e-9, 0.1, 0.5, 0.9, 0.999999] {
+                let k = d.inverse_cdf(p);
+                assert!(d.cdf(k) >= p);
+                assert!(k == d.min() || d.cdf(k - 1) < p);
+            }
+        }
+        let uniform = Discretize::new(Uniform::new(0.0, 1.0).unwrap(), 0.25, DiscretizeRule::Floor).unwrap();
+        assert_eq!((uniform.min(), uniform.max()), (0, 3));
+        assert_eq!(uniform.inverse_cdf(1.0), 3);
+        assert_eq!(uniform.inverse_cdf(0.5), 1);
+        let uniform = Discretize::new(Uniform::new(0.0, 1.0).unwrap(), 0.25, DiscretizeRule::Round).unwrap();
+        assert_eq!((uniform.min(), uniform.max()), (0, 4));
+        assert_eq!(uniform.pmf(4), 0.125);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for d in exp_rules(0.25) {
+            let n = 100_000;
+            let mut counts = [0usize; 8];
+            for _ in 0..n {
+                let x = d.sample(&mut rng);
+                let k = (x / 0.25).round();
+                assert_eq!(x, k * 0.25);
+                if (k as usize) < counts.len() {
+                    counts[k as usize] += 1;
+                }
+            }
+            for (k, &c) in counts.iter().enumerate() {
+                let p = d.pmf(k as i64);
+                let sd = (p * (1.0 - p) / n as f64).sqrt();
+                assert!((c as f64 / n as f64 - p).abs() < 5.0 * sd + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        use crate::distribution::internal::test;
+        let n = Discretize::new(Normal::new(1.0, 2.0).unwrap(), 0.5, DiscretizeRule::CdfMass).unwrap();
+        test::check_parameters(&n, |d| [d.pmf(-1), d.pmf(2)]);
+        let names: Vec<String> = n.parameters().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["inner.mean", "inner.std_dev", "step"]);
+        assert_eq!(n.set_parameter("step", 1.0).unwrap().rule(), DiscretizeRule::CdfMass);
+        assert_eq!(n.set_parameter("step", 0.0).err(), Some(StatsError::ArgMustBePositive("step")));
+        assert_eq!(n.set_parameter("inner.std_dev", 0.0).err(), Some(StatsError::BadParams));
+    }
+}
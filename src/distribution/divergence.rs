@@ -0,0 +1,302 @@
+//! Provides measures of dissimilarity between probability distributions
+
+use crate::distribution::{Continuous, Normal};
+use crate::statistics::Distribution;
+use std::f64;
+
+/// Computes the [Hellinger distance](https://en.wikipedia.org/wiki/Hellinger_distance)
+/// between the continuous distributions `p` and `q` by integrating
+/// `sqrt(p(x) * q(x))` over `[lower, upper]` with the composite Simpson
+/// rule on `n` subintervals
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(1 - ∫ sqrt(p(x) * q(x)) dx)
+/// ```
+///
+/// # Remarks
+///
+/// The distance is symmetric and bounded in `[0, 1]`, being
+/// `sqrt(1 - BC)` where `BC` is the `bhattacharyya_coefficient`.
+/// `[lower, upper]` should contain essentially all of the mass of both
+/// distributions. An odd `n` is rounded up to the next even number.
+///
+/// Returns `f64::NAN` if `n == 0` or if the bounds are not finite with
+/// `lower < upper`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::hellinger_distance;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(1.0, 1.0).unwrap();
+/// let h = hellinger_distance(&p, &q, -10.0, 11.0, 1000);
+/// assert!((h - 0.3428).abs() < 1e-4);
+/// ```
+pub fn hellinger_distance<D: Continuous<f64, f64>>(
+    p: &D,
+    q: &D,
+    lower: f64,
+    upper: f64,
+    n: usize,
+) -> f64 {
+    (1.0 - bhattacharyya_coefficient(p, q, lower, upper, n)).sqrt()
+}
+
+/// Computes the Hellinger distance between two normal distributions in
+/// closed form
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(1 - sqrt(2σ_p σ_q / (σ_p^2 + σ_q^2)) * exp(-(μ_p - μ_q)^2 / (4 (σ_p^2 + σ_q^2))))
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::hellinger_distance_normal;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// assert_eq!(hellinger_distance_normal(&p, &p), 0.0);
+/// ```
+pub fn hellinger_distance_normal(p: &Normal, q: &Normal) -> f64 {
+    (1.0 - bhattacharyya_coefficient_normal(p, q)).sqrt()
+}
+
+/// Computes the [Bhattacharyya coefficient](https://en.wikipedia.org/wiki/Bhattacharyya_distance)
+/// between the continuous distributions `p` and `q` by integrating
+/// `sqrt(p(x) * q(x))` over `[lower, upper]` with the composite Simpson
+/// rule on `n` subintervals
+///
+/// # Formula
+///
+/// ```text
+/// ∫ sqrt(p(x) * q(x)) dx
+/// ```
+///
+/// # Remarks
+///
+/// The coefficient measures the overlap of the distributions and is bounded
+/// in `[0, 1]`, being `1` only for identical distributions; the result is
+/// clamped to that interval to absorb quadrature error. `[lower, upper]`
+/// should contain essentially all of the mass of both distributions. An odd
+/// `n` is rounded up to the next even number.
+///
+/// Returns `f64::NAN` if `n == 0` or if the bounds are not finite with
+/// `lower < upper`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::bhattacharyya_coefficient;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(1.0, 1.0).unwrap();
+/// let bc = bhattacharyya_coefficient(&p, &q, -10.0, 11.0, 1000);
+/// assert!((bc - (-0.125f64).exp()).abs() < 1e-10);
+/// ```
+pub fn bhattacharyya_coefficient<D: Continuous<f64, f64>>(
+    p: &D,
+    q: &D,
+    lower: f64,
+    upper: f64,
+    n: usize,
+) -> f64 {
+    overlap_integral(p, q, lower, upper, n).clamp(0.0, 1.0)
+}
+
+/// Computes the Bhattacharyya distance between the continuous distributions
+/// `p` and `q` as the negated logarithm of `bhattacharyya_coefficient`
+///
+/// # Formula
+///
+/// ```text
+/// -ln(∫ sqrt(p(x) * q(x)) dx)
+/// ```
+///
+/// # Remarks
+///
+/// The distance is `0` for identical distributions and `f64::INFINITY` for
+/// distributions without overlapping support. It does not satisfy the
+/// triangle inequality.
+///
+/// Returns `f64::NAN` if `n == 0` or if the bounds are not finite with
+/// `lower < upper`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::bhattacharyya_distance;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(1.0, 1.0).unwrap();
+/// let d = bhattacharyya_distance(&p, &q, -10.0, 11.0, 1000);
+/// assert!((d - 0.125).abs() < 1e-10);
+/// ```
+pub fn bhattacharyya_distance<D: Continuous<f64, f64>>(
+    p: &D,
+    q: &D,
+    lower: f64,
+    upper: f64,
+    n: usize,
+) -> f64 {
+    -bhattacharyya_coefficient(p, q, lower, upper, n).ln()
+}
+
+/// Computes the Bhattacharyya coefficient between two normal distributions
+/// in closed form
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(2σ_p σ_q / (σ_p^2 + σ_q^2)) * exp(-(μ_p - μ_q)^2 / (4 (σ_p^2 + σ_q^2)))
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::bhattacharyya_coefficient_normal;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// assert_eq!(bhattacharyya_coefficient_normal(&p, &p), 1.0);
+/// ```
+pub fn bhattacharyya_coefficient_normal(p: &Normal, q: &Normal) -> f64 {
+    (-bhattacharyya_distance_normal(p, q)).exp()
+}
+
+/// Computes the Bhattacharyya distance between two normal distributions in
+/// closed form
+///
+/// # Formula
+///
+/// ```text
+/// (μ_p - μ_q)^2 / (4 (σ_p^2 + σ_q^2)) + ln((σ_p^2 + σ_q^2) / (2σ_p σ_q)) / 2
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::distribution::divergence::bhattacharyya_distance_normal;
+///
+/// let p = Normal::new(0.0, 1.0).unwrap();
+/// let q = Normal::new(1.0, 1.0).unwrap();
+/// assert_eq!(bhattacharyya_distance_normal(&p, &q), 0.125);
+/// ```
+pub fn bhattacharyya_distance_normal(p: &Normal, q: &Normal) -> f64 {
+    let (mu_p, sigma_p) = (p.mean().unwrap(), p.std_dev().unwrap());
+    let (mu_q, sigma_q) = (q.mean().unwrap(), q.std_dev().unwrap());
+    let var_sum = sigma_p * sigma_p + sigma_q * sigma_q;
+    let diff = mu_p - mu_q;
+    diff * diff / (4.0 * var_sum) + 0.5 * (var_sum / (2.0 * sigma_p * sigma_q)).ln()
+}
+
+/// Integrates `sqrt(p(x) * q(x))` over `[lower, upper]` with the composite
+/// Simpson rule on `n` (rounded up to even) subintervals
+fn overlap_integral<D: Continuous<f64, f64>>(
+    p: &D,
+    q: &D,
+    lower: f64,
+    upper: f64,
+    n: usize,
+) -> f64 {
+    if n == 0 || !lower.is_finite() || !upper.is_finite() || lower >= upper {
+        return f64::NAN;
+    }
+    let n = n + n % 2;
+    let h = (upper - lower) / n as f64;
+    let f = |x: f64| (p.pdf(x) * q.pdf(x)).sqrt();
+    let interior: f64 = (1..n)
+        .map(|i| {
+            let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+            weight * f(lower + i as f64 * h)
+        })
+        .sum();
+    (f(lower) + interior + f(upper)) * h / 3.0
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Uniform};
+
+    #[test]
+    fn test_hellinger_self() {
+        let n = Normal::new(2.0, 3.0).unwrap();
+        assert_almost_eq!(hellinger_distance(&n, &n, -40.0, 44.0, 2000), 0.0, 1e-6);
+        assert_eq!(hellinger_distance_normal(&n, &n), 0.0);
+        let e = Exp::new(2.0).unwrap();
+        assert_almost_eq!(hellinger_distance(&e, &e, 0.0, 30.0, 20000), 0.0, 1e-4);
+    }
+
+    #[test]
+    fn test_hellinger_normal_closed_form() {
+        let cases = [((0.0, 1.0), (1.0, 1.0)), ((0.0, 1.0), (0.5, 2.0)), ((-3.0, 0.5), (2.0, 4.0))];
+        for &((m1, s1), (m2, s2)) in &cases {
+            let p = Normal::new(m1, s1).unwrap();
+            let q = Normal::new(m2, s2).unwrap();
+            let numeric = hellinger_distance(&p, &q, -40.0, 40.0, 4000);
+            assert_almost_eq!(numeric, hellinger_distance_normal(&p, &q), 1e-8);
+            assert_almost_eq!(numeric, hellinger_distance(&q, &p, -40.0, 40.0, 4000), 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_hellinger_bounds() {
+        let p = Uniform::new(0.0, 1.0).unwrap();
+        let q = Uniform::new(2.0, 3.0).unwrap();
+        assert_eq!(hellinger_distance(&p, &q, -1.0, 4.0, 100), 1.0);
+        let far = hellinger_distance_normal(&Normal::new(0.0, 1.0).unwrap(), &Normal::new(100.0, 1.0).unwrap());
+        assert_eq!(far, 1.0);
+    }
+
+    #[test]
+    fn test_bhattacharyya_self() {
+        let n = Normal::new(-1.0, 0.5).unwrap();
+        assert_almost_eq!(bhattacharyya_coefficient(&n, &n, -10.0, 8.0, 2000), 1.0, 1e-10);
+        assert_almost_eq!(bhattacharyya_distance(&n, &n, -10.0, 8.0, 2000), 0.0, 1e-10);
+        assert_eq!(bhattacharyya_coefficient_normal(&n, &n), 1.0);
+        assert_eq!(bhattacharyya_distance_normal(&n, &n), 0.0);
+    }
+
+    #[test]
+    fn test_bhattacharyya_normal_closed_form() {
+        let cases = [((0.0, 1.0), (2.0, 1.0)), ((0.0, 1.0), (0.5, 2.0)), ((-3.0, 0.5), (2.0, 4.0))];
+        for &((m1, s1), (m2, s2)) in &cases {
+            let p = Normal::new(m1, s1).unwrap();
+            let q = Normal::new(m2, s2).unwrap();
+            let coefficient = bhattacharyya_coefficient(&p, &q, -40.0, 40.0, 4000);
+            assert_almost_eq!(coefficient, bhattacharyya_coefficient_normal(&p, &q), 1e-8);
+            let distance = bhattacharyya_distance(&p, &q, -40.0, 40.0, 4000);
+            assert_almost_eq!(distance, bhattacharyya_distance_normal(&p, &q), 1e-8);
+            let hellinger = hellinger_distance_normal(&p, &q);
+            assert_almost_eq!(hellinger * hellinger, 1.0 - coefficient, 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_bhattacharyya_disjoint() {
+        let p = Uniform::new(0.0, 1.0).unwrap();
+        let q = Uniform::new(2.0, 3.0).unwrap();
+        assert_eq!(bhattacharyya_coefficient(&p, &q, -1.0, 4.0, 100), 0.0);
+        assert_eq!(bhattacharyya_distance(&p, &q, -1.0, 4.0, 100), f64::INFINITY);
+        assert!(bhattacharyya_distance(&p, &q, -1.0, 4.0, 0).is_nan());
+    }
+
+    #[test]
+    fn test_hellinger_bad_input() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert!(hellinger_distance(&n, &n, -1.0, 1.0, 0).is_nan());
+        assert!(hellinger_distance(&n, &n, 1.0, -1.0, 10).is_nan());
+        assert!(hellinger_distance(&n, &n, f64::NEG_INFINITY, 1.0, 10).is_nan());
+    }
+}
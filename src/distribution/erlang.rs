@@ -1,6 +1,6 @@
-use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::distribution::{internal, Continuous, ContinuousCDF, Exp, Gamma, Parameterized};
 use crate::statistics::*;
-use crate::Result;
+use crate::{Result, StatsError};
 use rand::Rng;
 
 /// Implements the [Erlang](https://en.wikipedia.org/wiki/Erlang_distribution)
@@ -76,6 +76,158 @@ impl Erlang {
     pub fn rate(&self) -> f64 {
         self.g.rate()
     }
+
+    /// Returns the Erlang B blocking probability, the probability that an
+    /// arrival finds all `servers` busy and is lost, for a loss system
+    /// offered `offered_load` Erlangs of traffic
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// B(0) = 1
+    /// B(k) = A B(k - 1) / (k + A B(k - 1))
+    /// ```
+    ///
+    /// where `A` is the offered load. The recursion avoids the overflow of
+    /// the factorial form `(A^c / c!) / Σ_k A^k / k!`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `servers == 0` or if `offered_load` is negative
+    /// or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Erlang;
+    ///
+    /// // traffic tables list 4.461 Erlangs for 10 servers at 1% blocking
+    /// let b = Erlang::erlang_b(10, 4.461).unwrap();
+    /// assert!((b - 0.01).abs() < 1e-5);
+    /// ```
+    pub fn erlang_b(servers: u64, offered_load: f64) -> Result<f64> {
+        if servers == 0 {
+            return Err(StatsError::ArgMustBePositive("servers"));
+        }
+        if !offered_load.is_finite() || offered_load < 0.0 {
+            return Err(StatsError::ArgNotNegative("offered_load"));
+        }
+        let mut b = 1.0;
+        for k in 1..=servers {
+            let ab = offered_load * b;
+            b = ab / (k as f64 + ab);
+        }
+        Ok(b)
+    }
+
+    /// Returns the Erlang C waiting probability, the probability that an
+    /// arrival to an M/M/c queue with `servers` servers and `offered_load`
+    /// Erlangs of traffic has to wait
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// c B / (c - A (1 - B))
+    /// ```
+    ///
+    /// where `c` is the number of servers, `A` the offered load and `B` the
+    /// Erlang B blocking probability
+    ///
+    /// # Remarks
+    ///
+    /// Returns `1` when `offered_load >= servers`, where the queue is
+    /// unstable and every arrival eventually waits
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `servers == 0` or if `offered_load` is negative
+    /// or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Erlang;
+    ///
+    /// // a single server waits with probability equal to its utilization
+    /// assert!((Erlang::erlang_c(1, 0.5).unwrap() - 0.5).abs() < 1e-15);
+    /// ```
+    pub fn erlang_c(servers: u64, offered_load: f64) -> Result<f64> {
+        let b = Erlang::erlang_b(servers, offered_load)?;
+        let c = servers as f64;
+        if offered_load >= c {
+            return Ok(1.0);
+        }
+        Ok(c * b / (c - offered_load * (1.0 - b)))
+    }
+
+    /// Returns the distribution of the waiting time in an M/M/c queue with
+    /// `servers` servers, Poisson arrivals at `arrival_rate` and
+    /// exponential service at `service_rate`, as the probability of waiting
+    /// together with the exponential distribution of the wait given that
+    /// it is positive
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// P(W > t) = C e^(-(c μ - λ) t)
+    /// ```
+    ///
+    /// where `C` is the Erlang C probability for the offered load `λ / μ`,
+    /// `c` the number of servers, `λ` the arrival rate and `μ` the service
+    /// rate. The wait is zero with probability `1 - C`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `servers == 0`, if the rates are not positive and
+    /// finite, or if `arrival_rate >= servers * service_rate` so that the
+    /// queue is unstable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Erlang};
+    ///
+    /// let (wait, conditional) = Erlang::waiting_time_distribution(2, 1.0, 1.0).unwrap();
+    /// assert!((wait - 1.0 / 3.0).abs() < 1e-15);
+    /// // probability of waiting longer than 1
+    /// assert!((wait * conditional.sf(1.0) - (-1f64).exp() / 3.0).abs() < 1e-15);
+    /// ```
+    pub fn waiting_time_distribution(
+        servers: u64,
+        arrival_rate: f64,
+        service_rate: f64,
+    ) -> Result<(f64, Exp)> {
+        if !arrival_rate.is_finite() || arrival_rate <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("arrival_rate"));
+        }
+        if !service_rate.is_finite() || service_rate <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("service_rate"));
+        }
+        let capacity = servers as f64 * service_rate;
+        if arrival_rate >= capacity {
+            return Err(StatsError::SpecialCase(
+                "arrival_rate must be below servers * service_rate",
+            ));
+        }
+        let c = Erlang::erlang_c(servers, arrival_rate / service_rate)?;
+        Ok((c, Exp::new(capacity - arrival_rate)?))
+    }
+}
+
+impl Parameterized for Erlang {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("shape".into(), self.g.shape()),
+            ("rate".into(), self.g.rate()),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Erlang> {
+        match *values {
+            [shape, rate] => Erlang::new(internal::integer_parameter(shape)?, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
 }
 
 impl std::fmt::Display for Erlang {
@@ -280,6 +432,7 @@ impl Continuous<f64, f64> for Erlang {
 mod tests {
     use crate::distribution::Erlang;
     use crate::distribution::internal::*;
+    use crate::distribution::{Continuous, Parameterized};
 
     fn try_create(shape: u64, rate: f64) -> Erlang {
         let n = Erlang::new(shape, rate);
@@ -321,4 +474,53 @@ mod tests {
         test::check_continuous_distribution(&try_create(2, 1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(3, 0.5), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_erlang_b_traffic_table() {
+        // published Erlang B table for 10 servers
+        assert_almost_eq!(Erlang::erlang_b(10, 4.461).unwrap(), 0.0099977866879027662, 1e-15);
+        assert_almost_eq!(Erlang::erlang_b(10, 5.084).unwrap(), 0.01999990859926459, 1e-15);
+        assert_almost_eq!(Erlang::erlang_b(1, 0.5).unwrap(), 1.0 / 3.0, 1e-15);
+        assert_almost_eq!(Erlang::erlang_b(5, 2.0).unwrap(), 0.036697247706422018, 1e-15);
+        assert_almost_eq!(Erlang::erlang_b(20, 15.0).unwrap(), 0.045593215589811839, 1e-15);
+        assert_almost_eq!(Erlang::erlang_b(100, 80.0).unwrap(), 0.0039920286045531975, 1e-15);
+        assert_eq!(Erlang::erlang_b(3, 0.0).unwrap(), 0.0);
+        assert!(Erlang::erlang_b(0, 1.0).is_err());
+        assert!(Erlang::erlang_b(3, -1.0).is_err());
+        assert!(Erlang::erlang_b(3, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_erlang_c() {
+        assert_almost_eq!(Erlang::erlang_c(1, 0.5).unwrap(), 0.5, 1e-15);
+        assert_almost_eq!(Erlang::erlang_c(5, 2.0).unwrap(), 0.059701492537313433, 1e-15);
+        assert_almost_eq!(Erlang::erlang_c(10, 5.0).unwrap(), 0.036105359158320186, 1e-15);
+        assert_almost_eq!(Erlang::erlang_c(20, 15.0).unwrap(), 0.16042938741692362, 1e-14);
+        assert_almost_eq!(Erlang::erlang_c(50, 45.0).unwrap(), 0.36386446720698839, 1e-14);
+        assert_almost_eq!(Erlang::erlang_c(100, 80.0).unwrap(), 0.019646426634359511, 1e-15);
+        assert_eq!(Erlang::erlang_c(4, 4.0).unwrap(), 1.0);
+        assert!(Erlang::erlang_c(0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_waiting_time_distribution() {
+        let (wait, conditional) = Erlang::waiting_time_distribution(10, 5.0, 1.0).unwrap();
+        assert_almost_eq!(wait, 0.036105359158320186, 1e-15);
+        assert_eq!(conditional.rate(), 5.0);
+        let (wait, conditional) = Erlang::waiting_time_distribution(1, 2.0, 4.0).unwrap();
+        assert_almost_eq!(wait, 0.5, 1e-15);
+        assert_eq!(conditional.rate(), 2.0);
+        assert!(Erlang::waiting_time_distribution(2, 2.0, 1.0).is_err());
+        assert!(Erlang::waiting_time_distribution(0, 1.0, 1.0).is_err());
+        assert!(Erlang::waiting_time_distribution(2, 0.0, 1.0).is_err());
+        assert!(Erlang::waiting_time_distribution(2, 1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("rate", -1.0), Erlang::new(3, -1.0));
+        assert!(n.set_parameter("shape", 2.5).is_err());
+    }
 }
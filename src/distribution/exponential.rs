@@ -1,4 +1,8 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
+use crate::distribution::{
+    ziggurat, Continuous, ContinuousCDF, LMoments, Moments, Parameterized, ScoreFunction,
+    TailExpectation,
+};
+use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -52,6 +56,44 @@ impl Exp {
         }
     }
 
+    /// Estimates an exponential distribution from the sample `data` by
+    /// maximum likelihood
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ = 1 / mean(x_i)
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, if an entry is negative or is
+    /// `NaN` or infinite, or if every entry is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Exp;
+    ///
+    /// let n = Exp::fit_mle(&[0.5, 1.5, 2.0]).unwrap();
+    /// assert_eq!(n.rate(), 0.75);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Exp> {
+        if data.is_empty() {
+            return Err(StatsError::SpecialCase("data must not be empty"));
+        }
+        if data.iter().any(|&x| !x.is_finite() || x < 0.0) {
+            return Err(StatsError::SpecialCase(
+                "data must be finite and not negative",
+            ));
+        }
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+        if mean <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be all zero"));
+        }
+        Exp::new(1.0 / mean)
+    }
+
     /// Returns the rate of the exponential distribution
     ///
     /// # Examples
@@ -65,6 +107,51 @@ impl Exp {
     pub fn rate(&self) -> f64 {
         self.rate
     }
+
+    /// Calculates the inverse of the survival function for the exponential
+    /// distribution given the natural logarithm `ln_q` of the survival
+    /// probability, so that quantiles remain available for tail
+    /// probabilities too small to be represented as a `f64`
+    ///
+    /// # Panics
+    ///
+    /// If `ln_q > 0.0` or `ln_q` is `NaN`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -ln_q / λ
+    /// ```
+    ///
+    /// where `λ` is the rate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Exp;
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.inverse_ln_sf(-1000.0), 500.0);
+    /// ```
+    pub fn inverse_ln_sf(&self, ln_q: f64) -> f64 {
+        if ln_q.is_nan() || ln_q > 0.0 {
+            panic!("ln_q must be within [-inf, 0.0]");
+        }
+        -ln_q / self.rate
+    }
+}
+
+impl Parameterized for Exp {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("rate".into(), self.rate)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Exp> {
+        match *values {
+            [rate] => Exp::new(rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
 }
 
 impl std::fmt::Display for Exp {
@@ -276,6 +363,138 @@ impl Continuous<f64, f64> for Exp {
     }
 }
 
+impl ScoreFunction<f64> for Exp {
+    const NUM_PARAMS: usize = 1;
+
+    /// Calculates the gradient of the log probability density function for
+    /// the exponential distribution at `x` with respect to `λ`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (1 / λ - x)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_pdf_grad(&self, x: f64) -> Vec<f64> {
+        vec![1.0 / self.rate - x]
+    }
+
+    /// Calculates the Hessian of the log probability density function for
+    /// the exponential distribution at `x` with respect to `λ`, which does
+    /// not depend on `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-1 / λ^2]]
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_pdf_hessian(&self, _x: f64) -> Option<Vec<Vec<f64>>> {
+        Some(vec![vec![-1.0 / (self.rate * self.rate)]])
+    }
+}
+
+impl LMoments for Exp {
+    /// Returns the first `order` L-moments of the exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [1 / λ, 1 / (2λ), τ3, τ4, ...]  with  τr = 2 / (r (r - 1))
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        (1..=order)
+            .map(|r| match r {
+                1 => 1.0 / self.rate,
+                2 => 0.5 / self.rate,
+                _ => 2.0 / (r * (r - 1)) as f64,
+            })
+            .collect()
+    }
+}
+
+impl Moments for Exp {
+    /// Returns the raw moment of order `order` of the exponential
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// r! / λ^r
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        Some(factorial::factorial(order as u64) / self.rate.powi(order as i32))
+    }
+
+    /// Returns the central moment of order `order` of the exponential
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// !r / λ^r
+    /// ```
+    ///
+    /// where `!r` is the number of derangements of `r` elements, given by
+    /// `!r = (r - 1) (!(r - 1) + !(r - 2))` with `!0 = 1` and `!1 = 0`
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        let (mut previous, mut derangements) = (1.0, 0.0);
+        if order == 0 {
+            derangements = 1.0;
+        }
+        for r in 2..=order {
+            let next = (r - 1) as f64 * (derangements + previous);
+            previous = derangements;
+            derangements = next;
+        }
+        Some(derangements / self.rate.powi(order as i32))
+    }
+}
+
+impl TailExpectation for Exp {
+    /// Returns the partial expectation `E[X 1{a < X <= b}]` of the
+    /// exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (a + 1 / λ) e^(-λa) - (b + 1 / λ) e^(-λb)
+    /// ```
+    ///
+    /// where `λ` is the rate and the bounds are clamped to the support
+    fn partial_expectation(&self, a: f64, b: f64) -> f64 {
+        let (a, b) = (a.max(0.0), b.max(0.0));
+        if a >= b || self.rate.is_infinite() {
+            return 0.0;
+        }
+        let upper = |x: f64| {
+            if x.is_infinite() {
+                0.0
+            } else {
+                (x + 1.0 / self.rate) * (-self.rate * x).exp()
+            }
+        };
+        upper(a) - upper(b)
+    }
+
+    /// Returns the mean excess `E[X - u | X > u]` of the exponential
+    /// distribution, which by memorylessness is the mean `1 / λ` for
+    /// `u >= 0`, even where the survival function underflows
+    fn mean_excess(&self, u: f64) -> f64 {
+        if u.is_nan() || u == f64::INFINITY || self.rate.is_infinite() && u >= 0.0 {
+            f64::NAN
+        } else {
+            1.0 / self.rate - u.min(0.0)
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -283,6 +502,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Exp};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(rate: f64) -> Exp {
         let n = Exp::new(rate);
@@ -530,4 +750,113 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(2.5), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_sample_above() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let n = try_create(0.5);
+        let samples: Vec<f64> = (0..20000).map(|_| n.sample_above(3.0, &mut rng)).collect();
+        assert!(samples.iter().all(|&x| x > 3.0));
+        // memorylessness: E[X | X > t] = t + 1 / λ
+        assert_almost_eq!(samples.iter().sum::<f64>() / samples.len() as f64, 5.0, 0.05);
+        assert!(n.sample_above(f64::INFINITY, &mut rng).is_nan());
+    }
+
+    #[test]
+    fn test_inverse_ln_sf() {
+        let n = try_create(0.5);
+        for &q in &[0.9, 0.5, 1e-3, 1e-12, 1e-300] {
+            assert_almost_eq!(n.inverse_ln_sf(f64::ln(q)), -f64::ln(q) / 0.5, 1e-12);
+            assert_almost_eq!(n.sf(n.inverse_ln_sf(f64::ln(q))), q, 1e-12 * q);
+        }
+        assert_almost_eq!(n.inverse_ln_sf(f64::ln(0.25)), n.inverse_cdf(0.75), 1e-14);
+        assert_eq!(n.inverse_ln_sf(-1e4), 2e4);
+        assert_eq!(n.inverse_ln_sf(0.0), 0.0);
+        assert_eq!(n.inverse_ln_sf(f64::NEG_INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Exp::new(p[0]).unwrap();
+        let xs = [0.0, 0.1, 1.0, 5.0, 30.0];
+        test::check_score(make, |d: &Exp, x| d.ln_pdf(x), &[1.0], &xs);
+        test::check_score(make, |d: &Exp, x| d.ln_pdf(x), &[0.2], &xs);
+        test::check_score(make, |d: &Exp, x| d.ln_pdf(x), &[8.0], &xs);
+    }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(2.0), [5.0000000000000001e-301, 5.0000000000000001e-17, 0.34657359027997265, 18.368400284838551]);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        use crate::distribution::LMoments;
+        let n = try_create(0.25);
+        let closed = n.l_moments(6);
+        assert_eq!(closed[..3], [4.0, 2.0, 1.0 / 3.0]);
+        assert_eq!(closed[5], 1.0 / 15.0);
+        let numeric = l_moments(&n, 6, &[]);
+        for r in 0..6 {
+            assert_almost_eq!(numeric[r], closed[r], 1e-10);
+        }
+        assert!(n.l_moments(0).is_empty());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("rate", -1.0), Exp::new(-1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &rate in [0.25, 1.0, 3.0].iter() {
+            let n = try_create(rate);
+            test::check_moments(&n, 1e-14);
+            test::check_moments_numeric(&n, 6, 1e-9);
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 9.0, 1e-14);
+        }
+        // !5 = 44 and !6 = 265
+        let n = try_create(1.0);
+        assert_eq!(n.central_moment(5), Some(44.0));
+        assert_eq!(n.central_moment(6), Some(265.0));
+        assert_eq!(n.raw_moment(5), Some(120.0));
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let n = Exp::fit_mle(&[0.0, 1.0, 3.0]).unwrap();
+        assert_eq!(n.rate(), 0.75);
+        assert!(Exp::fit_mle(&[]).is_err());
+        assert!(Exp::fit_mle(&[0.0, 0.0]).is_err());
+        assert!(Exp::fit_mle(&[1.0, -1.0]).is_err());
+        assert!(Exp::fit_mle(&[1.0, f64::INFINITY]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        for &rate in [0.25, 1.0, 3.0].iter() {
+            let n = try_create(rate);
+            test::check_tail_expectation(&n, &[-1.0, 0.0, 0.5, 2.0, 5.0], 1e-9);
+            // memorylessness makes the mean excess constant
+            for &u in [0.0, 0.5, 2.0, 1e3].iter() {
+                assert_eq!(n.mean_excess(u), 1.0 / rate);
+            }
+            assert_eq!(n.mean_excess(-2.0), 1.0 / rate + 2.0);
+            assert_almost_eq!(n.partial_expectation(f64::NEG_INFINITY, f64::INFINITY), 1.0 / rate, 1e-15);
+        }
+        let n = try_create(f64::INFINITY);
+        assert_eq!(n.partial_expectation(-1.0, 1.0), 0.0);
+        assert!(n.mean_excess(0.0).is_nan());
+        assert!(try_create(1.0).mean_excess(f64::NAN).is_nan());
+    }
 }
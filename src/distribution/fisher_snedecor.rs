@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
 use crate::function::beta;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -89,6 +89,22 @@ impl FisherSnedecor {
     }
 }
 
+impl Parameterized for FisherSnedecor {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("freedom_1".into(), self.freedom_1),
+            ("freedom_2".into(), self.freedom_2),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<FisherSnedecor> {
+        match *values {
+            [freedom_1, freedom_2] => FisherSnedecor::new(freedom_1, freedom_2),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for FisherSnedecor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "F({},{})", self.freedom_1, self.freedom_2)
@@ -366,6 +382,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, FisherSnedecor};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(freedom_1: f64, freedom_2: f64) -> FisherSnedecor {
         let n = FisherSnedecor::new(freedom_1, freedom_2);
@@ -596,4 +613,11 @@ mod tests {
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(10.0, 10.0), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 5.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("freedom_2", 0.0), FisherSnedecor::new(3.0, 0.0));
+    }
 }
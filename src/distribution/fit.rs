@@ -0,0 +1,512 @@
+//! Provides the automatic choice of a distribution family for a sample,
+//! fitting each candidate family by maximum likelihood and ranking the fits
+//! by the corrected Akaike information criterion
+
+use crate::distribution::{
+    Beta, Continuous, ContinuousCDF, Exp, Gamma, LogNormal, Lomax, Normal, Pareto, Scaled, Shifted,
+    ShiftedExponential, Weibull,
+};
+use crate::statistics::{Max, Min};
+use crate::{Result, StatsError};
+use std::cmp::Ordering;
+use std::f64;
+use std::fmt;
+
+/// A family of continuous distributions that `auto_fit` can estimate
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Family {
+    /// The `Normal` distributions, on the real line
+    Normal,
+    /// The `LogNormal` distributions, on the positive reals
+    LogNormal,
+    /// The `Gamma` distributions, on the positive reals
+    Gamma,
+    /// The `Weibull` distributions, on the positive reals
+    Weibull,
+    /// The `Exp` distributions, on the non-negative reals
+    Exponential,
+    /// The `Pareto` distributions, on the positive reals
+    Pareto,
+    /// The `Beta` distributions, rescaled from `(0, 1)` to the range of the
+    /// data widened by `1 / (n - 1)` of it on either side
+    Beta,
+    /// The `Lomax` distributions, on the positive reals
+    Lomax,
+    /// The `ShiftedExponential` distributions, on the real line
+    ShiftedExponential,
+}
+
+impl Family {
+    /// Every family, in the order of their declaration
+    pub const ALL: [Family; 9] = [
+        Family::Normal,
+        Family::LogNormal,
+        Family::Gamma,
+        Family::Weibull,
+        Family::Exponential,
+        Family::Pareto,
+        Family::Beta,
+        Family::Lomax,
+        Family::ShiftedExponential,
+    ];
+
+    /// Returns the number of parameters estimated for the family, which for
+    /// `Beta` includes the two ends of its rescaled support
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Family;
+    ///
+    /// assert_eq!(Family::Exponential.parameter_count(), 1);
+    /// assert_eq!(Family::Gamma.parameter_count(), 2);
+    /// assert_eq!(Family::Beta.parameter_count(), 4);
+    /// ```
+    pub fn parameter_count(&self) -> usize {
+        match self {
+            Family::Exponential => 1,
+            Family::Beta => 4,
+            _ => 2,
+        }
+    }
+
+    /// Returns whether `x` is in the support of the distributions of the
+    /// family, as far as the fit is concerned, which excludes the
+    /// boundaries where the likelihood degenerates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Family;
+    ///
+    /// assert!(Family::Normal.supports(-1.0));
+    /// assert!(!Family::Gamma.supports(-1.0));
+    /// assert!(Family::Exponential.supports(0.0));
+    /// assert!(Family::Beta.supports(-1.0));
+    /// ```
+    pub fn supports(&self, x: f64) -> bool {
+        match self {
+            Family::Normal | Family::ShiftedExponential | Family::Beta => x.is_finite(),
+            Family::Exponential => x.is_finite() && x >= 0.0,
+            _ => x.is_finite() && x > 0.0,
+        }
+    }
+
+    /// Estimates the distribution of the family from the sample `data` by
+    /// maximum likelihood, with the `fit_mle` of the distribution
+    ///
+    /// # Remarks
+    ///
+    /// For `Beta` the data are first mapped to `(0, 1)` from the interval
+    /// `(min - r / (n - 1), max + r / (n - 1))`, where `r` is the range of
+    /// the `n` observations, which are the unbiased estimates of the ends of
+    /// a uniform sample. The fitted distribution is the `Beta` mapped back,
+    /// so the log-likelihood of the data includes the log-Jacobian
+    /// `-n ln(r (n + 1) / (n - 1))` of the rescaling.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the `fit_mle` of the distribution, or for `Beta`
+    /// an error if the data are not finite or have a range that is zero or
+    /// not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Family, Fitted};
+    ///
+    /// let fitted = Family::Exponential.fit_mle(&[0.5, 1.5, 2.0]).unwrap();
+    /// assert_eq!(fitted.family(), Family::Exponential);
+    /// assert!(Family::Exponential.fit_mle(&[-1.0, 1.0]).is_err());
+    /// ```
+    pub fn fit_mle(&self, data: &[f64]) -> Result<Fitted> {
+        let fitted = match self {
+            Family::Normal => Fitted::Normal(Normal::fit_mle(data)?),
+            Family::LogNormal => Fitted::LogNormal(LogNormal::fit_mle(data)?),
+            Family::Gamma => Fitted::Gamma(Gamma::fit_mle(data)?),
+            Family::Weibull => Fitted::Weibull(Weibull::fit_mle(data)?),
+            Family::Exponential => Fitted::Exponential(Exp::fit_mle(data)?),
+            Family::Pareto => Fitted::Pareto(Pareto::fit_mle(data)?),
+            Family::Beta => Fitted::Beta(fit_rescaled_beta(data)?),
+            Family::Lomax => Fitted::Lomax(Lomax::fit_mle(data)?),
+            Family::ShiftedExponential => {
+                Fitted::ShiftedExponential(ShiftedExponential::fit_mle(data)?)
+            }
+        };
+        Ok(fitted)
+    }
+}
+
+/// Fits a `Beta` distribution to `data` mapped to `(0, 1)` from its range
+/// widened by `1 / (n - 1)` of it on either side, returning it mapped back
+fn fit_rescaled_beta(data: &[f64]) -> Result<Shifted<Scaled<Beta>>> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+    if data.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let margin = (max - min) / (data.len() - 1) as f64;
+    let (lower, width) = (min - margin, (max - min) + 2.0 * margin);
+    if !width.is_finite() || width <= 0.0 {
+        return Err(StatsError::SpecialCase(
+            "the range of the data must be positive and finite",
+        ));
+    }
+    let rescaled: Vec<f64> = data.iter().map(|&x| (x - lower) / width).collect();
+    let beta = Beta::fit_mle(&rescaled)?;
+    Shifted::new(Scaled::new(beta, width)?, lower)
+}
+
+impl fmt::Display for Family {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Family::Normal => "Normal",
+            Family::LogNormal => "LogNormal",
+            Family::Gamma => "Gamma",
+            Family::Weibull => "Weibull",
+            Family::Exponential => "Exponential",
+            Family::Pareto => "Pareto",
+            Family::Beta => "Beta",
+            Family::Lomax => "Lomax",
+            Family::ShiftedExponential => "ShiftedExponential",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A distribution fitted by `Family::fit_mle`, which evaluates as the
+/// distribution it holds
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Fitted {
+    /// A fitted `Normal` distribution
+    Normal(Normal),
+    /// A fitted `LogNormal` distribution
+    LogNormal(LogNormal),
+    /// A fitted `Gamma` distribution
+    Gamma(Gamma),
+    /// A fitted `Weibull` distribution
+    Weibull(Weibull),
+    /// A fitted `Exp` distribution
+    Exponential(Exp),
+    /// A fitted `Pareto` distribution
+    Pareto(Pareto),
+    /// A fitted `Beta` distribution, rescaled to the widened range of the
+    /// data
+    Beta(Shifted<Scaled<Beta>>),
+    /// A fitted `Lomax` distribution
+    Lomax(Lomax),
+    /// A fitted `ShiftedExponential` distribution
+    ShiftedExponential(ShiftedExponential),
+}
+
+/// Evaluates `$body` with `$d` bound to the distribution held by `$fitted`
+macro_rules! dispatch {
+    ($fitted:expr, $d:ident => $body:expr) => {
+        match $fitted {
+            Fitted::Normal($d) => $body,
+            Fitted::LogNormal($d) => $body,
+            Fitted::Gamma($d) => $body,
+            Fitted::Weibull($d) => $body,
+            Fitted::Exponential($d) => $body,
+            Fitted::Pareto($d) => $body,
+            Fitted::Beta($d) => $body,
+            Fitted::Lomax($d) => $body,
+            Fitted::ShiftedExponential($d) => $body,
+        }
+    };
+}
+
+impl Fitted {
+    /// Returns the family of the distribution
+    pub fn family(&self) -> Family {
+        match self {
+            Fitted::Normal(_) => Family::Normal,
+            Fitted::LogNormal(_) => Family::LogNormal,
+            Fitted::Gamma(_) => Family::Gamma,
+            Fitted::Weibull(_) => Family::Weibull,
+            Fitted::Exponential(_) => Family::Exponential,
+            Fitted::Pareto(_) => Family::Pareto,
+            Fitted::Beta(_) => Family::Beta,
+            Fitted::Lomax(_) => Family::Lomax,
+            Fitted::ShiftedExponential(_) => Family::ShiftedExponential,
+        }
+    }
+}
+
+impl Min<f64> for Fitted {
+    fn min(&self) -> f64 {
+        dispatch!(self, d => d.min())
+    }
+}
+
+impl Max<f64> for Fitted {
+    fn max(&self) -> f64 {
+        dispatch!(self, d => d.max())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for Fitted {
+    fn cdf(&self, x: f64) -> f64 {
+        dispatch!(self, d => d.cdf(x))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        dispatch!(self, d => d.sf(x))
+    }
+
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        dispatch!(self, d => d.inverse_cdf(p))
+    }
+}
+
+impl Continuous<f64, f64> for Fitted {
+    fn pdf(&self, x: f64) -> f64 {
+        dispatch!(self, d => d.pdf(x))
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        dispatch!(self, d => d.ln_pdf(x))
+    }
+}
+
+/// The goodness of fit of a distribution fitted to a sample
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Fit {
+    /// The distribution fitted by maximum likelihood
+    pub distribution: Fitted,
+    /// The log-likelihood of the sample under the distribution
+    pub log_likelihood: f64,
+    /// The corrected Akaike information criterion, lower for better fits
+    pub aicc: f64,
+    /// The Kolmogorov-Smirnov distance between the empirical distribution
+    /// of the sample and the fitted one
+    pub ks_statistic: f64,
+}
+
+/// The outcome of fitting a family in `auto_fit`, either a fit or the
+/// reason that the family was skipped
+#[derive(Clone, PartialEq, Debug)]
+pub struct FitReport {
+    /// The candidate family
+    pub family: Family,
+    /// The fit, or the reason that there is none
+    pub fit: Result<Fit>,
+}
+
+/// Fits each of the `candidates` to the sample `data` by maximum likelihood
+/// and ranks them by the corrected Akaike information criterion
+///
+/// # Formula
+///
+/// ```text
+/// AICc = -2 ln L + 2k + 2k (k + 1) / (n - k - 1)
+/// ```
+///
+/// where `ln L` is the maximized log-likelihood, `k` the number of
+/// parameters and `n` the number of observations
+///
+/// # Remarks
+///
+/// The reports of the successful fits come first, by increasing AICc, and
+/// those of the skipped families follow. Ties and skipped families keep
+/// the order of `candidates`, so the ranking is deterministic. A family is
+/// skipped with an error as its reason if an observation is outside its
+/// support, if its `fit_mle` fails, or if there are not more than `k + 1`
+/// observations.
+///
+/// The Kolmogorov-Smirnov distance is descriptive only, its usual
+/// p-values do not apply to distributions estimated from the same sample.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{auto_fit, Family};
+///
+/// let data = [-1.2, 0.4, 2.5, -3.1, 0.9, 1.7, -0.2, 0.6];
+/// let reports = auto_fit(&data, &Family::ALL);
+/// // the negative observations leave the families of the real line and
+/// // the rescaled beta distributions
+/// assert_eq!(reports[0].family, Family::Normal);
+/// assert_eq!(reports[1].family, Family::ShiftedExponential);
+/// assert_eq!(reports[2].family, Family::Beta);
+/// assert!(reports[3..].iter().all(|r| r.fit.is_err()));
+/// ```
+pub fn auto_fit(data: &[f64], candidates: &[Family]) -> Vec<FitReport> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let mut reports: Vec<FitReport> = candidates
+        .iter()
+        .map(|&family| FitReport {
+            family,
+            fit: fit_family(family, &sorted),
+        })
+        .collect();
+    // a stable sort keeps the order of the candidates on ties
+    reports.sort_by(|a, b| match (&a.fit, &b.fit) {
+        (Ok(a), Ok(b)) => a.aicc.partial_cmp(&b.aicc).unwrap_or(Ordering::Equal),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    });
+    reports
+}
+
+/// Fits `family` to the ascending sample `sorted` and measures the fit
+fn fit_family(family: Family, sorted: &[f64]) -> Result<Fit> {
+    if !sorted.iter().all(|&x| family.supports(x)) {
+        return Err(StatsError::SpecialCase(
+            "the data are outside the support of the family",
+        ));
+    }
+    let k = family.parameter_count();
+    if sorted.len() <= k + 1 {
+        return Err(StatsError::SpecialCase(
+            "the AICc needs more than one observation per parameter plus one",
+        ));
+    }
+    let distribution = family.fit_mle(sorted)?;
+    let log_likelihood: f64 = sorted.iter().map(|&x| distribution.ln_pdf(x)).sum();
+    if !log_likelihood.is_finite() {
+        return Err(StatsError::SpecialCase(
+            "the log-likelihood of the fit is not finite",
+        ));
+    }
+    let (n, k) = (sorted.len() as f64, k as f64);
+    let aicc = -2.0 * log_likelihood + 2.0 * k + 2.0 * k * (k + 1.0) / (n - k - 1.0);
+    let ks_statistic = sorted.iter().enumerate().fold(0.0, |acc: f64, (i, &x)| {
+        let cdf = distribution.cdf(x);
+        acc.max((i + 1) as f64 / n - cdf).max(cdf - i as f64 / n)
+    });
+    Ok(Fit {
+        distribution,
+        log_likelihood,
+        aicc,
+        ks_statistic,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_auto_fit_log_normal() {
+        let truth = LogNormal::new(0.5, 0.8).unwrap();
+        for seed in 0..8 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let data: Vec<f64> = truth.sample_iter(&mut rng).take(500).collect();
+            let reports = auto_fit(&data, &Family::ALL);
+            assert_eq!(reports.len(), Family::ALL.len());
+            assert_eq!(reports[0].family, Family::LogNormal, "seed {}", seed);
+            // the rescaled beta distributions fit any range, but worse
+            let beta = reports.iter().find(|r| r.family == Family::Beta).unwrap();
+            assert!(beta.fit.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_auto_fit_rescaled_beta() {
+        let truth = Shifted::new(Scaled::new(Beta::new(2.0, 5.0).unwrap(), 20.0).unwrap(), 10.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let data: Vec<f64> = (0..400).map(|_| truth.sample(&mut rng)).collect();
+        let reports = auto_fit(&data, &Family::ALL);
+        assert_eq!(reports[0].family, Family::Beta);
+        let fit = reports[0].fit.as_ref().unwrap();
+        // the beta fitted to the data mapped from the widened range, with
+        // the log-Jacobian of the map in the log-likelihood
+        let (min, max) = (data.iter().cloned().fold(f64::INFINITY, f64::min), data.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+        let margin = (max - min) / 399.0;
+        let (lower, width) = (min - margin, max - min + 2.0 * margin);
+        let rescaled: Vec<f64> = data.iter().map(|&x| (x - lower) / width).collect();
+        let beta = Beta::fit_mle(&rescaled).unwrap();
+        let log_likelihood: f64 = rescaled.iter().map(|&u| beta.ln_pdf(u)).sum::<f64>() - 400.0 * width.ln();
+        assert_almost_eq!(fit.log_likelihood, log_likelihood, 1e-9);
+        assert_almost_eq!(fit.aicc, -2.0 * log_likelihood + 8.0 + 40.0 / 395.0, 1e-9);
+        assert_almost_eq!(fit.distribution.min(), lower, 1e-12);
+        assert_almost_eq!(fit.distribution.max(), lower + width, 1e-12);
+        assert!((fit.distribution.inverse_cdf(0.5) - truth.inverse_cdf(0.5)).abs() < 0.5);
+        // a constant or infinite sample has no range to rescale
+        assert!(Family::Beta.fit_mle(&[2.0; 6]).is_err());
+        assert_eq!(Family::Beta.fit_mle(&[1.0, f64::INFINITY, 2.0]), Err(StatsError::ArgFinite("data")));
+        assert!(Family::Beta.fit_mle(&[-f64::MAX, f64::MAX]).is_err());
+    }
+
+    #[test]
+    fn test_auto_fit_measures() {
+        let data = [0.3, 1.2, 0.7, 2.9, 0.1, 0.8, 1.6, 0.4];
+        let reports = auto_fit(&data, &[Family::Exponential, Family::Gamma]);
+        let fits: Vec<&Fit> = reports.iter().map(|r| r.fit.as_ref().unwrap()).collect();
+        for (report, fit) in reports.iter().zip(&fits) {
+            assert_eq!(fit.distribution.family(), report.family);
+            let log_likelihood: f64 = data.iter().map(|&x| fit.distribution.ln_pdf(x)).sum();
+            assert_almost_eq!(fit.log_likelihood, log_likelihood, 1e-12);
+        }
+        assert!(fits[0].aicc <= fits[1].aicc);
+        // the exponential fit, with λ = 1 / mean and ln L = n ln λ - n
+        let exponential = fits.iter().find(|f| f.distribution.family() == Family::Exponential).unwrap();
+        let rate: f64 = 8.0 / 8.0;
+        assert_almost_eq!(exponential.log_likelihood, 8.0 * rate.ln() - 8.0, 1e-12);
+        assert_almost_eq!(exponential.aicc, -2.0 * exponential.log_likelihood + 2.0 + 4.0 / 6.0, 1e-12);
+        // the largest gap between the empirical and fitted distributions,
+        // just below 0.3 where the empirical distribution is still 1/8
+        assert_almost_eq!(exponential.ks_statistic, 1.0 - (-0.3f64).exp() - 1.0 / 8.0, 1e-12);
+    }
+
+    #[test]
+    fn test_auto_fit_support() {
+        let data = [-1.2, 0.4, 2.5, -3.1, 0.9, 1.7, -0.2, 0.6];
+        let reports = auto_fit(&data, &Family::ALL);
+        let ranked: Vec<Family> = reports.iter().map(|r| r.family).collect();
+        // the skipped families follow in the order of the candidates
+        assert_eq!(ranked, vec![
+            Family::Normal, Family::ShiftedExponential, Family::Beta, Family::LogNormal,
+            Family::Gamma, Family::Weibull, Family::Exponential, Family::Pareto, Family::Lomax,
+        ]);
+        for report in &reports[3..] {
+            assert_eq!(report.fit, Err(StatsError::SpecialCase("the data are outside the support of the family")));
+        }
+        // a constant sample cannot be fitted by any family
+        let reports = auto_fit(&[2.0; 10], &[Family::Normal, Family::Gamma]);
+        assert!(reports.iter().all(|r| r.fit.is_err()));
+        // too few observations for the AICc
+        assert!(auto_fit(&[1.0, 2.0, 3.0], &[Family::Normal])[0].fit.is_err());
+        assert!(auto_fit(&[1.0, 2.0, 3.0], &[Family::Exponential])[0].fit.is_ok());
+        assert!(auto_fit(&[1.0, f64::NAN, 3.0, 4.0], &[Family::Normal])[0].fit.is_err());
+        assert!(auto_fit(&[], &Family::ALL).iter().all(|r| r.fit.is_err()));
+    }
+
+    #[test]
+    fn test_auto_fit_deterministic() {
+        let mut rng = StdRng::seed_from_u64(293);
+        let data: Vec<f64> = Gamma::new(2.0, 1.0).unwrap().sample_iter(&mut rng).take(200).collect();
+        let reports = auto_fit(&data, &Family::ALL);
+        assert_eq!(auto_fit(&data, &Family::ALL), reports);
+        // the order of the data does not matter
+        let reversed: Vec<f64> = data.iter().rev().cloned().collect();
+        assert_eq!(auto_fit(&reversed, &Family::ALL), reports);
+        // the same candidate twice ties, in the order given
+        let twice = auto_fit(&data, &[Family::Gamma, Family::Gamma]);
+        assert_eq!(twice[0], twice[1]);
+    }
+
+    #[test]
+    fn test_fitted() {
+        let fitted = Family::Gamma.fit_mle(&[1.2, 0.4, 2.5, 3.1, 0.9, 1.7]).unwrap();
+        let gamma = Gamma::fit_mle(&[1.2, 0.4, 2.5, 3.1, 0.9, 1.7]).unwrap();
+        assert_eq!(fitted, Fitted::Gamma(gamma));
+        for &x in [0.1, 1.0, 4.0].iter() {
+            assert_eq!(fitted.pdf(x), gamma.pdf(x));
+            assert_eq!(fitted.cdf(x), gamma.cdf(x));
+        }
+        assert_eq!(fitted.inverse_cdf(0.3), gamma.inverse_cdf(0.3));
+        assert_eq!((fitted.min(), fitted.max()), (gamma.min(), gamma.max()));
+        assert_eq!(Family::ShiftedExponential.to_string(), "ShiftedExponential");
+    }
+}
@@ -1,5 +1,8 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::distribution::{
+    internal, Continuous, ContinuousCDF, LMoments, Moments, Normal, Parameterized, ScoreFunction,
+    TailExpectation,
+};
+use crate::function::{factorial, gamma, root};
 use crate::prec;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -57,6 +60,63 @@ impl Gamma {
         Ok(Gamma { shape, rate })
     }
 
+    /// Estimates a gamma distribution from the sample `data` by maximum
+    /// likelihood
+    ///
+    /// # Formula
+    ///
+    /// The shape `α` solves
+    ///
+    /// ```text
+    /// ln α - ψ(α) = ln mean(x_i) - mean(ln x_i)
+    /// ```
+    ///
+    /// where `ψ` is the digamma function, and the rate is `α / mean(x_i)`
+    ///
+    /// # Remarks
+    ///
+    /// The equation is the score of the shape from `ScoreFunction`, summed
+    /// over `data` at the rate `α / mean(x_i)` that zeroes the score of the
+    /// rate, and is solved with Brent's method
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not positive or is `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Gamma;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let data = [1.2, 0.4, 2.5, 3.1, 0.9, 1.7];
+    /// let n = Gamma::fit_mle(&data).unwrap();
+    /// // the fitted mean is the sample mean
+    /// assert!((n.mean().unwrap() - 9.8 / 6.0).abs() < 1e-14);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Gamma> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|&x| !x.is_finite() || x <= 0.0) {
+            return Err(StatsError::SpecialCase("data must be positive and finite"));
+        }
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let s = mean.ln() - data.iter().map(|x| x.ln()).sum::<f64>() / n;
+        if s <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        // Minka's approximation is within 1.5% of the root
+        let guess = (3.0 - s + ((s - 3.0) * (s - 3.0) + 24.0 * s).sqrt()) / (12.0 * s);
+        let profile_score = |a: f64| Gamma::new(a, a / mean).unwrap().score_sum(data)[0];
+        let shape = root::brent(profile_score, 0.5 * guess, 2.0 * guess, 1e-14 * guess)?;
+        Gamma::new(shape, shape / mean)
+    }
+
     /// Returns the shape (α) of the gamma distribution
     ///
     /// # Examples
@@ -86,6 +146,19 @@ impl Gamma {
     }
 }
 
+impl Parameterized for Gamma {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("shape".into(), self.shape), ("rate".into(), self.rate)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Gamma> {
+        match *values {
+            [shape, rate] => Gamma::new(shape, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Gamma {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Γ({}, {})", self.shape, self.rate)
@@ -363,6 +436,47 @@ impl Continuous<f64, f64> for Gamma {
         }
     }
 }
+
+impl ScoreFunction<f64> for Gamma {
+    const NUM_PARAMS: usize = 2;
+
+    /// Calculates the gradient of the log probability density function for
+    /// the gamma distribution at `x` with respect to `(α, β)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (ln(β) + ln(x) - ψ(α), α / β - x)
+    /// ```
+    ///
+    /// where `α` is the shape, `β` the rate and `ψ` the digamma function
+    fn ln_pdf_grad(&self, x: f64) -> Vec<f64> {
+        vec![
+            self.rate.ln() + x.ln() - gamma::digamma(self.shape),
+            self.shape / self.rate - x,
+        ]
+    }
+
+    /// Calculates the Hessian of the log probability density function for
+    /// the gamma distribution at `x` with respect to `(α, β)`, which does
+    /// not depend on `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-ψ1(α), 1 / β], [1 / β, -α / β^2]]
+    /// ```
+    ///
+    /// where `α` is the shape, `β` the rate and `ψ1` the trigamma function
+    fn ln_pdf_hessian(&self, _x: f64) -> Option<Vec<Vec<f64>>> {
+        let inv_rate = 1.0 / self.rate;
+        Some(vec![
+            vec![-gamma::trigamma(self.shape), inv_rate],
+            vec![inv_rate, -self.shape * inv_rate * inv_rate],
+        ])
+    }
+}
+
 /// Samples from a gamma distribution with a shape of `shape` and a
 /// rate of `rate` using `rng` as the source of randomness. Implementation from:
 /// <br />
@@ -404,6 +518,160 @@ pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, shape: f64, rate: f64) ->
     }
 }
 
+impl LMoments for Gamma {}
+
+impl Moments for Gamma {
+    /// Returns the raw moment of order `order` of the gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α (α + 1) ... (α + r - 1) / β^r
+    /// ```
+    ///
+    /// where `α` is the shape and `β` is the rate. An infinite rate is a
+    /// point mass at zero.
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        if self.rate.is_infinite() {
+            return Some(if order == 0 { 1.0 } else { 0.0 });
+        }
+        Some(
+            (0..order)
+                .map(|i| (self.shape + i as f64) / self.rate)
+                .product(),
+        )
+    }
+
+    /// Returns the central moment of order `order` of the gamma
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// The central moments follow from the cumulants
+    ///
+    /// ```text
+    /// κ_n = α (n - 1)! / β^n
+    /// ```
+    ///
+    /// where `α` is the shape and `β` is the rate. The moments from the
+    /// second onward are infinite for an infinite shape, as is the variance.
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        match order {
+            0 => return Some(1.0),
+            1 => return Some(0.0),
+            _ if self.rate.is_infinite() => return Some(0.0),
+            _ if self.shape.is_infinite() => return Some(f64::INFINITY),
+            _ => (),
+        }
+        let cumulant =
+            |n: u32| self.shape * factorial::factorial(n as u64 - 1) / self.rate.powi(n as i32);
+        Some(internal::central_moments_from_cumulants(order, cumulant)[order as usize])
+    }
+
+    /// Returns the standardized moment of order `order` of the gamma
+    /// distribution, which does not depend on the rate
+    ///
+    /// # Remarks
+    ///
+    /// An infinite shape gives the moments of the standard normal
+    /// distribution, the limit of the standardized gamma distribution, in
+    /// agreement with `skewness`.
+    fn standardized_moment(&self, order: u32) -> Option<f64> {
+        if self.shape.is_infinite() {
+            return Normal::standard().central_moment(order);
+        }
+        let standard = Gamma {
+            shape: self.shape,
+            rate: 1.0,
+        };
+        Some(standard.central_moment(order)? / self.shape.powf(0.5 * order as f64))
+    }
+}
+
+impl TailExpectation for Gamma {
+    /// Returns the partial expectation `E[X 1{a < X <= b}]` of the gamma
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α / β (P(α + 1, βb) - P(α + 1, βa))
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate, `P` is the regularized
+    /// lower incomplete gamma function and the bounds are clamped to the
+    /// support. Upper tails are differenced with the upper incomplete gamma
+    /// function for accuracy.
+    fn partial_expectation(&self, a: f64, b: f64) -> f64 {
+        let (a, b) = (a.max(0.0), b.max(0.0));
+        if a >= b {
+            return 0.0;
+        }
+        if self.rate.is_infinite() {
+            // a point mass at the shape
+            return if a < self.shape && self.shape <= b {
+                self.shape
+            } else {
+                0.0
+            };
+        }
+        let shape = self.shape + 1.0;
+        let (lower, upper) = (a * self.rate, b * self.rate);
+        let difference = if lower > self.shape {
+            let q = |x: f64| {
+                if x.is_infinite() {
+                    0.0
+                } else {
+                    gamma::gamma_ur(shape, x)
+                }
+            };
+            q(lower) - q(upper)
+        } else {
+            let p = |x: f64| {
+                if x == 0.0 {
+                    0.0
+                } else if x.is_infinite() {
+                    1.0
+                } else {
+                    gamma::gamma_lr(shape, x)
+                }
+            };
+            p(upper) - p(lower)
+        };
+        self.shape / self.rate * difference
+    }
+
+    /// Returns the mean excess `E[X - u | X > u]` of the gamma distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α / β Q(α + 1, βu) / Q(α, βu) - u
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate and `Q` is the regularized
+    /// upper incomplete gamma function
+    fn mean_excess(&self, u: f64) -> f64 {
+        if self.rate.is_infinite() {
+            return if u < self.shape {
+                self.shape - u
+            } else {
+                f64::NAN
+            };
+        }
+        let tail = self.sf(u);
+        if tail.is_nan() || tail <= 0.0 {
+            return f64::NAN;
+        }
+        let upper = if u <= 0.0 {
+            1.0
+        } else {
+            gamma::gamma_ur(self.shape + 1.0, u * self.rate)
+        };
+        self.shape / self.rate * upper / tail - u
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -675,4 +943,127 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.0, 0.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(9.0, 2.0), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Gamma::new(p[0], p[1]).unwrap();
+        let xs = [0.01, 0.5, 1.0, 2.5, 10.0];
+        test::check_score(make, |d: &Gamma, x| d.ln_pdf(x), &[1.0, 1.0], &xs);
+        test::check_score(make, |d: &Gamma, x| d.ln_pdf(x), &[0.4, 3.0], &xs);
+        test::check_score(make, |d: &Gamma, x| d.ln_pdf(x), &[12.0, 0.7], &xs);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        use crate::distribution::LMoments;
+        // λ2 = Γ(α + 1/2) / (sqrt(π) Γ(α) β)
+        let l = Gamma::new(3.0, 2.0).unwrap().l_moments(4);
+        assert_almost_eq!(l[0], 1.5, 1e-12);
+        assert_almost_eq!(l[1], 0.46875, 1e-12);
+        // a gamma distribution with unit shape is exponential
+        let l = Gamma::new(1.0, 4.0).unwrap().l_moments(4);
+        assert_almost_eq!(l[2], 1.0 / 3.0, 1e-10);
+        assert_almost_eq!(l[3], 1.0 / 6.0, 1e-10);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(
+            n.set_parameter("shape", f64::NAN),
+            Gamma::new(f64::NAN, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(shape, rate) in [(0.5, 1.0), (3.0, 2.0), (10.0, 0.1)].iter() {
+            let n = Gamma::new(shape, rate).unwrap();
+            test::check_moments(&n, 1e-13);
+            test::check_moments_numeric(&n, 6, 1e-8);
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 3.0 + 6.0 / shape, 1e-13);
+        }
+        // the standardized moments of the limits agree with the skewness
+        for &(shape, rate) in [(10.0, f64::INFINITY), (f64::INFINITY, 1.0)].iter() {
+            let n = Gamma::new(shape, rate).unwrap();
+            assert_almost_eq!(
+                n.standardized_moment(3).unwrap(),
+                n.skewness().unwrap(),
+                1e-15
+            );
+        }
+        assert_eq!(
+            Gamma::new(f64::INFINITY, 1.0)
+                .unwrap()
+                .standardized_moment(4),
+            Some(3.0)
+        );
+        assert_eq!(
+            Gamma::new(2.0, f64::INFINITY).unwrap().raw_moment(2),
+            Some(0.0)
+        );
+        let n = Gamma::new(3.0, 2.0).unwrap();
+        assert_almost_eq!(n.raw_moment(3).unwrap(), 60.0 / 8.0, 1e-15);
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        use rand::distributions::Distribution as _;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(293);
+        for &(shape, rate) in [(0.3, 2.0), (2.5, 0.5), (40.0, 4.0)].iter() {
+            let data: Vec<f64> = Gamma::new(shape, rate)
+                .unwrap()
+                .sample_iter(&mut rng)
+                .take(20000)
+                .collect();
+            let n = Gamma::fit_mle(&data).unwrap();
+            assert_almost_eq!(n.shape() / shape, 1.0, 0.05);
+            assert_almost_eq!(n.rate() / rate, 1.0, 0.05);
+            // the score equations hold at the estimate
+            let m = data.len() as f64;
+            let mean_log = data.iter().map(|x| x.ln()).sum::<f64>() / m;
+            let mean = data.iter().sum::<f64>() / m;
+            assert_almost_eq!(n.shape() / n.rate(), mean, 1e-12 * mean);
+            assert_almost_eq!(
+                n.rate().ln() - crate::function::gamma::digamma(n.shape()),
+                -mean_log,
+                1e-12
+            );
+        }
+        assert!(Gamma::fit_mle(&[1.0]).is_err());
+        assert!(Gamma::fit_mle(&[1.0, 1.0]).is_err());
+        assert!(Gamma::fit_mle(&[1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        for &(shape, rate) in [(0.5, 1.0), (2.5, 1.5), (10.0, 0.2)].iter() {
+            let n = Gamma::new(shape, rate).unwrap();
+            let mean = shape / rate;
+            test::check_tail_expectation(
+                &n,
+                &[0.0, 0.1 * mean, mean, 2.0 * mean, 4.0 * mean],
+                1e-8,
+            );
+            assert_almost_eq!(n.partial_expectation(-1.0, f64::INFINITY), mean, 1e-14);
+            assert_almost_eq!(n.mean_excess(-1.0), mean + 1.0, 1e-14);
+        }
+        // reference values computed with mpmath
+        let n = Gamma::new(2.5, 1.5).unwrap();
+        assert_almost_eq!(n.mean_excess(2.0), 0.93770958150002487, 1e-14);
+        assert_almost_eq!(n.partial_expectation(0.5, 2.0), 0.7376005156854882, 1e-14);
+        // the shape one gamma is exponential
+        let n = Gamma::new(1.0, 2.0).unwrap();
+        assert_almost_eq!(n.mean_excess(3.0), 0.5, 1e-13);
+        let n = Gamma::new(3.0, f64::INFINITY).unwrap();
+        assert_eq!(n.mean_excess(1.0), 2.0);
+        assert!(n.mean_excess(3.0).is_nan());
+        assert_eq!(n.partial_expectation(2.0, 3.0), 3.0);
+        assert_eq!(n.partial_expectation(3.0, 4.0), 0.0);
+    }
 }
@@ -0,0 +1,718 @@
+use crate::distribution::{internal, Continuous, ContinuousCDF, LMoments, Parameterized};
+use crate::function::gamma;
+use crate::statistics::l_moments::sample_l_moments;
+use crate::statistics::*;
+use crate::{consts, Result, StatsError};
+use rand::distributions::Open01;
+use rand::Rng;
+use std::f64;
+
+/// Skewness of the Gumbel distribution, `12 sqrt(6) ζ(3) / π^3`
+const GUMBEL_SKEWNESS: f64 = 1.139_547_099_404_648_7;
+
+/// Implements the [generalized extreme value](https://en.wikipedia.org/wiki/Generalized_extreme_value_distribution)
+/// distribution, the limit law of normalized block maxima, which contains
+/// the Gumbel (`ξ = 0`), Fréchet (`ξ > 0`) and reversed Weibull (`ξ < 0`)
+/// families
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, GeneralizedExtremeValue};
+///
+/// let n = GeneralizedExtremeValue::new(0.0, 1.0, 0.0).unwrap();
+/// assert_eq!(n.cdf(0.0), (-1.0f64).exp());
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GeneralizedExtremeValue {
+    location: f64,
+    scale: f64,
+    shape: f64,
+}
+
+impl GeneralizedExtremeValue {
+    /// Constructs a new generalized extreme value distribution with a
+    /// location (μ) of `location`, a scale (σ) of `scale` and a shape (ξ) of
+    /// `shape`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any parameter is not finite or if `scale <= 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let mut result = GeneralizedExtremeValue::new(0.0, 1.0, 0.1);
+    /// assert!(result.is_ok());
+    ///
+    /// result = GeneralizedExtremeValue::new(0.0, 0.0, 0.1);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<GeneralizedExtremeValue> {
+        if !location.is_finite() || !scale.is_finite() || !shape.is_finite() || scale <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(GeneralizedExtremeValue {
+                location,
+                scale,
+                shape,
+            })
+        }
+    }
+
+    /// Estimates a generalized extreme value distribution from the sample
+    /// `data` by matching its first three
+    /// [sample L-moments](crate::statistics::l_moments::sample_l_moments)
+    ///
+    /// The shape follows from the L-skewness `t3` through the polynomial
+    /// approximation of Hosking, Wallis and Wood (1985), accurate to
+    /// `9e-4` for `-0.5 <= t3 <= 0.5`, and the scale and location from the
+    /// exact L-moments of the distribution.
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// c = 2 / (3 + t3) - ln(2) / ln(3)
+    /// ξ = -(7.8590 c + 2.9554 c^2)
+    /// σ = l2 ξ / ((2^ξ - 1) Γ(1 - ξ))
+    /// μ = l1 - σ (Γ(1 - ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `l1`, `l2` and `t3` are the sample L-location, L-scale and
+    /// L-skewness
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than three elements or contains
+    /// `NaN`, or if its L-scale is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let maxima = [2.1, 3.4, 2.8, 5.9, 3.1, 2.5, 4.2, 3.3, 2.9, 3.8];
+    /// let n = GeneralizedExtremeValue::fit_lmoments(&maxima).unwrap();
+    /// assert!(n.shape() > 0.0);
+    /// ```
+    pub fn fit_lmoments(data: &[f64]) -> Result<GeneralizedExtremeValue> {
+        if data.len() < 3 {
+            return Err(StatsError::SpecialCase(
+                "at least three observations are required",
+            ));
+        }
+        let l = sample_l_moments(data, 3);
+        if l[1].is_nan() {
+            return Err(StatsError::SpecialCase("data must not contain NaN"));
+        }
+        if l[1] <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        let c = 2.0 / (3.0 + l[2]) - f64::consts::LN_2 / 3f64.ln();
+        let shape = -(7.8590 * c + 2.9554 * c * c);
+        let (scale, location) = if shape == 0.0 {
+            let scale = l[1] / f64::consts::LN_2;
+            (scale, l[0] - consts::EULER_MASCHERONI * scale)
+        } else {
+            let g = gamma::gamma(1.0 - shape);
+            let scale = l[1] * shape / ((shape * f64::consts::LN_2).exp_m1() * g);
+            (scale, l[0] - scale * (g - 1.0) / shape)
+        };
+        GeneralizedExtremeValue::new(location, scale, shape)
+    }
+
+    /// Returns the location of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape of the generalized extreme value distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedExtremeValue;
+    ///
+    /// let n = GeneralizedExtremeValue::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.shape(), 0.1);
+    /// ```
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns `y` such that the cdf at `x` is `exp(-exp(-y))`, infinite
+    /// outside the support
+    fn reduced(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if self.shape == 0.0 {
+            z
+        } else if self.shape * z <= -1.0 {
+            if self.shape > 0.0 {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }
+        } else {
+            (self.shape * z).ln_1p() / self.shape
+        }
+    }
+
+    /// Returns `Γ(1 - k ξ)`
+    fn g(&self, k: f64) -> f64 {
+        gamma::gamma(1.0 - k * self.shape)
+    }
+}
+
+impl Parameterized for GeneralizedExtremeValue {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+            ("shape".into(), self.shape),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<GeneralizedExtremeValue> {
+        match *values {
+            [location, scale, shape] => GeneralizedExtremeValue::new(location, scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for GeneralizedExtremeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GEV({}, {}, {})", self.location, self.scale, self.shape)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for GeneralizedExtremeValue {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Open01);
+        self.inverse_cdf(u)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for GeneralizedExtremeValue {
+    /// Calculates the cumulative distribution function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(-t(x))  with  t(x) = (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and
+    /// `t(x) = e^(-(x - μ) / σ)` for `ξ = 0`
+    fn cdf(&self, x: f64) -> f64 {
+        (-(-self.reduced(x)).exp()).exp()
+    }
+
+    /// Calculates the survival function for the generalized extreme value
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - e^(-t(x))
+    /// ```
+    ///
+    /// where `t(x)` is as for the cdf, evaluated with `exp_m1`
+    fn sf(&self, x: f64) -> f64 {
+        -(-(-self.reduced(x)).exp()).exp_m1()
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// generalized extreme value distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ ((-ln(p))^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, evaluated
+    /// with `exp_m1`, and `μ - σ ln(-ln(p))` for `ξ = 0`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let ln_w = (-p.ln()).ln();
+        let z = if self.shape == 0.0 {
+            -ln_w
+        } else {
+            (-self.shape * ln_w).exp_m1() / self.shape
+        };
+        self.location + self.scale * z
+    }
+}
+
+impl Min<f64> for GeneralizedExtremeValue {
+    /// Returns the minimum value in the domain of the generalized extreme
+    /// value distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if ξ > 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     f64::NEG_INFINITY
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn min(&self) -> f64 {
+        if self.shape > 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::NEG_INFINITY
+        }
+    }
+}
+
+impl Max<f64> for GeneralizedExtremeValue {
+    /// Returns the maximum value in the domain of the generalized extreme
+    /// value distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if ξ < 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     f64::INFINITY
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn max(&self) -> f64 {
+        if self.shape < 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Distribution<f64> for GeneralizedExtremeValue {
+    /// Returns the mean of the generalized extreme value distribution, which
+    /// exists for `ξ < 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ (g1 - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale, `ξ` the shape and
+    /// `gk = Γ(1 - k ξ)`, and `μ + σ γ` for `ξ = 0` with `γ` the
+    /// Euler-Mascheroni constant
+    fn mean(&self) -> Option<f64> {
+        if self.shape >= 1.0 {
+            None
+        } else if self.shape == 0.0 {
+            Some(self.location + self.scale * consts::EULER_MASCHERONI)
+        } else {
+            Some(self.location + self.scale * (self.g(1.0) - 1.0) / self.shape)
+        }
+    }
+
+    /// Returns the variance of the generalized extreme value distribution,
+    /// which exists for `ξ < 1 / 2`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// σ^2 (g2 - g1^2) / ξ^2
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` the shape and `gk = Γ(1 - k ξ)`, and
+    /// `σ^2 π^2 / 6` for `ξ = 0`
+    fn variance(&self) -> Option<f64> {
+        if self.shape >= 0.5 {
+            None
+        } else if self.shape == 0.0 {
+            Some(self.scale * self.scale * f64::consts::PI * f64::consts::PI / 6.0)
+        } else {
+            let g1 = self.g(1.0);
+            let v = (self.g(2.0) - g1 * g1) / (self.shape * self.shape);
+            Some(self.scale * self.scale * v)
+        }
+    }
+
+    /// Returns the entropy of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(σ) + γ ξ + γ + 1
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` the shape and `γ` the Euler-Mascheroni
+    /// constant
+    fn entropy(&self) -> Option<f64> {
+        Some(self.scale.ln() + consts::EULER_MASCHERONI * (self.shape + 1.0) + 1.0)
+    }
+
+    /// Returns the skewness of the generalized extreme value distribution,
+    /// which exists for `ξ < 1 / 3`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// sign(ξ) (g3 - 3 g1 g2 + 2 g1^3) / (g2 - g1^2)^(3 / 2)
+    /// ```
+    ///
+    /// where `ξ` is the shape and `gk = Γ(1 - k ξ)`, and
+    /// `12 sqrt(6) ζ(3) / π^3` for `ξ = 0`
+    fn skewness(&self) -> Option<f64> {
+        if self.shape >= 1.0 / 3.0 {
+            None
+        } else if self.shape == 0.0 {
+            Some(GUMBEL_SKEWNESS)
+        } else {
+            let (g1, g2, g3) = (self.g(1.0), self.g(2.0), self.g(3.0));
+            let skew = (g3 - 3.0 * g1 * g2 + 2.0 * g1 * g1 * g1) / (g2 - g1 * g1).powf(1.5);
+            Some(self.shape.signum() * skew)
+        }
+    }
+}
+
+impl Median<f64> for GeneralizedExtremeValue {
+    /// Returns the median of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ (ln(2)^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and
+    /// `μ - σ ln(ln(2))` for `ξ = 0`
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Mode<Option<f64>> for GeneralizedExtremeValue {
+    /// Returns the mode of the generalized extreme value distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if ξ <= -1 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     μ + σ ((1 + ξ)^(-ξ) - 1) / ξ
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and `μ`
+    /// for `ξ = 0`
+    fn mode(&self) -> Option<f64> {
+        let mode = if self.shape == 0.0 {
+            self.location
+        } else if self.shape <= -1.0 {
+            self.location - self.scale / self.shape
+        } else {
+            let z = (-self.shape * self.shape.ln_1p()).exp_m1() / self.shape;
+            self.location + self.scale * z
+        };
+        Some(mode)
+    }
+}
+
+impl Continuous<f64, f64> for GeneralizedExtremeValue {
+    /// Calculates the probability density function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// t(x)^(ξ + 1) e^(-t(x)) / σ
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` the shape and `t(x)` is as for the cdf
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the generalized
+    /// extreme value distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(t(x)^(ξ + 1) e^(-t(x)) / σ)
+    /// ```
+    ///
+    /// where `σ` is the scale, `ξ` the shape and `t(x)` is as for the cdf
+    fn ln_pdf(&self, x: f64) -> f64 {
+        let y = self.reduced(x);
+        if !y.is_finite() {
+            f64::NEG_INFINITY
+        } else {
+            -self.scale.ln() - (self.shape + 1.0) * y - (-y).exp()
+        }
+    }
+}
+
+impl LMoments for GeneralizedExtremeValue {
+    /// Returns the first `order` L-moments of the generalized extreme value
+    /// distribution, which are `NaN` for `ξ >= 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ1 = μ + σ (Γ(1 - ξ) - 1) / ξ
+    /// λ2 = σ (2^ξ - 1) Γ(1 - ξ) / ξ
+    /// τ3 = 2 (3^ξ - 1) / (2^ξ - 1) - 3
+    /// τ4 = (5 (4^ξ - 1) - 10 (3^ξ - 1) + 6 (2^ξ - 1)) / (2^ξ - 1)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, with the
+    /// limits `ξ -> 0` for the Gumbel distribution. Orders above four are
+    /// integrated numerically.
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        if self.shape >= 1.0 {
+            return vec![f64::NAN; order];
+        }
+        let known = if self.shape == 0.0 {
+            let ln_3 = 3f64.ln();
+            [
+                self.location + self.scale * consts::EULER_MASCHERONI,
+                self.scale * f64::consts::LN_2,
+                2.0 * ln_3 / f64::consts::LN_2 - 3.0,
+                16.0 - 10.0 * ln_3 / f64::consts::LN_2,
+            ]
+        } else {
+            let e = |j: f64| (self.shape * j.ln()).exp_m1();
+            let g1 = self.g(1.0);
+            let (e2, e3, e4) = (e(2.0), e(3.0), e(4.0));
+            [
+                self.location + self.scale * (g1 - 1.0) / self.shape,
+                self.scale * e2 * g1 / self.shape,
+                2.0 * e3 / e2 - 3.0,
+                (5.0 * e4 - 10.0 * e3 + 6.0 * e2) / e2,
+            ]
+        };
+        internal::l_moments(self, order, &known)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(location: f64, scale: f64, shape: f64; GeneralizedExtremeValue);
+
+    #[test]
+    fn test_create() {
+        try_create(0.0, 1.0, 0.0);
+        try_create(-5.0, 0.1, 0.5);
+        try_create(10.0, 10.0, -2.0);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0.0, 0.0, 0.1);
+        bad_create_case(0.0, -1.0, 0.1);
+        bad_create_case(f64::NAN, 1.0, 0.1);
+        bad_create_case(0.0, f64::INFINITY, 0.1);
+        bad_create_case(0.0, 1.0, f64::NAN);
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // reference values from mpmath
+        let cdf = |x: f64| move |n: GeneralizedExtremeValue| n.cdf(x);
+        let sf = |x: f64| move |n: GeneralizedExtremeValue| n.sf(x);
+        let pdf = |x: f64| move |n: GeneralizedExtremeValue| n.pdf(x);
+        test_case(1.0, 2.0, 0.2, 0.6690626526678188, cdf(3.0));
+        test_case(1.0, 2.0, 0.2, 0.33093734733218118, sf(3.0));
+        test_case(1.0, 2.0, 0.2, 0.11203386432543155, pdf(3.0));
+        test_case(1.0, 2.0, 0.2, 0.18387321995473519, cdf(0.0));
+        test_case(1.0, 2.0, -0.3, 0.73745436356275464, cdf(3.0));
+        test_case(1.0, 2.0, -0.3, 0.26254563643724536, sf(3.0));
+        test_case(1.0, 2.0, -0.3, 0.16042322672492858, pdf(3.0));
+        test_case(1.0, 2.0, -0.3, 0.20323245649650049, cdf(0.0));
+        test_case(1.0, 2.0, 0.0, 0.69220062755534635, cdf(3.0));
+        test_case(1.0, 2.0, 0.0, 0.12732319002179125, pdf(3.0));
+        // outside the support
+        test_case(1.0, 2.0, 0.2, 0.0, cdf(-9.5));
+        test_case(1.0, 2.0, 0.2, 0.0, pdf(-9.5));
+        test_case(1.0, 2.0, -0.3, 1.0, cdf(8.0));
+        test_case(1.0, 2.0, -0.3, 0.0, sf(8.0));
+        test_case(1.0, 2.0, -0.3, 0.0, pdf(8.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        let mean = |n: GeneralizedExtremeValue| n.mean().unwrap();
+        let variance = |n: GeneralizedExtremeValue| n.variance().unwrap();
+        let skewness = |n: GeneralizedExtremeValue| n.skewness().unwrap();
+        let entropy = |n: GeneralizedExtremeValue| n.entropy().unwrap();
+        test_case(1.0, 2.0, 0.2, 2.6422971372530338, mean);
+        test_case(1.0, 2.0, 0.2, 13.376142249191526, variance);
+        test_case(1.0, 2.0, 0.2, 3.5350716046213949, skewness);
+        test_case(1.0, 2.0, 0.2, 2.3858059784417847, entropy);
+        test_case(1.0, 2.0, -0.3, 1.6835286912914854, mean);
+        test_case(1.0, 2.0, -0.3, 3.9138532692984997, variance);
+        test_case(1.0, 2.0, -0.3, -0.068742099420967061, skewness);
+        test_case(1.0, 2.0, -0.3, 2.0971981459910183, entropy);
+        test_case(1.0, 2.0, 0.0, 2.1544313298030657, mean);
+        test_case(1.0, 2.0, 0.0, 6.5797362673929057, variance);
+        test_case(1.0, 2.0, 0.0, 1.1395470994046487, skewness);
+        test_case(1.0, 2.0, 0.0, 2.2703628454614782, entropy);
+        test_none(1.0, 2.0, 1.0, |n| n.mean());
+        test_none(1.0, 2.0, 0.5, |n| n.variance());
+        test_none(1.0, 2.0, 0.4, |n| n.skewness());
+    }
+
+    #[test]
+    fn test_median_mode_support() {
+        let median = |n: GeneralizedExtremeValue| n.median();
+        test_case(1.0, 2.0, 0.2, 1.7605608513900512, median);
+        test_case(1.0, 2.0, -0.3, 1.6941636296881405, median);
+        test_case(1.0, 2.0, 0.0, 1.7330258411633287, median);
+        // the density peaks at the mode
+        for &shape in &[-0.5, 0.0, 0.3] {
+            let n = try_create(1.0, 2.0, shape);
+            let mode = n.mode().unwrap();
+            assert!(n.pdf(mode) > n.pdf(mode - 1e-4));
+            assert!(n.pdf(mode) > n.pdf(mode + 1e-4));
+        }
+        assert_eq!(try_create(1.0, 2.0, -1.5).mode(), Some(1.0 + 2.0 / 1.5));
+        let n = try_create(1.0, 2.0, 0.2);
+        assert_eq!((n.min(), n.max()), (-9.0, f64::INFINITY));
+        let n = try_create(1.0, 2.0, -0.25);
+        assert_eq!((n.min(), n.max()), (f64::NEG_INFINITY, 9.0));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 2.0, 0.2),
+            [-6.2952158592732942, -4.1389189108769732, 1.7605608513900512, 15511.937641066482],
+        );
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 2.0, -0.3),
+            [-39.725855727410747, -12.003563527403667, 1.6941636296881405, 7.6665576402320827],
+        );
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 2.0, 0.0),
+            [-12.075629839808314, -6.2132423349754741, 1.7330258411633287, 74.473601139354203],
+        );
+        let n = try_create(1.0, 2.0, 0.2);
+        assert_eq!(n.inverse_cdf(0.0), -9.0);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        for &p in &[0.01, 0.3, 0.9, 0.999] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(1.0, 2.0, 0.2), -6.0, 100.0);
+        test::check_continuous_distribution(&try_create(1.0, 2.0, -0.3), -20.0, 7.6);
+        test::check_continuous_distribution(&try_create(1.0, 2.0, 0.0), -5.0, 20.0);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        // reference values from the quantile integrals in mpmath
+        let expected = [1.6835286912914854, 1.123319817146695, -0.0089961072644468849, 0.10624251810341207];
+        let closed = try_create(1.0, 2.0, -0.3).l_moments(4);
+        for r in 0..4 {
+            assert_almost_eq!(closed[r], expected[r], 1e-14);
+        }
+        let expected = [2.6422971372530338, 1.731190432696216, 0.30509291270124832, 0.2180272114789335];
+        let closed = try_create(1.0, 2.0, 0.2).l_moments(4);
+        for r in 0..4 {
+            assert_almost_eq!(closed[r], expected[r], 1e-14);
+        }
+        for &shape in &[-0.3, 0.0, 0.2] {
+            let n = try_create(1.0, 2.0, shape);
+            let closed = n.l_moments(5);
+            let numeric = l_moments(&n, 5, &[]);
+            for r in 0..5 {
+                assert_almost_eq!(closed[r], numeric[r], 1e-8);
+            }
+        }
+        assert!(try_create(1.0, 2.0, 1.0).l_moments(2).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_fit_lmoments() {
+        let mut rng = StdRng::seed_from_u64(275);
+        for &(location, scale, shape) in &[(10.0, 2.0, 0.1), (-3.0, 0.5, -0.25), (0.0, 1.0, 0.0)] {
+            let n = try_create(location, scale, shape);
+            let data: Vec<f64> = (0..50_000).map(|_| n.sample(&mut rng)).collect();
+            let fit = GeneralizedExtremeValue::fit_lmoments(&data).unwrap();
+            assert_almost_eq!(fit.shape(), shape, 0.02);
+            assert_almost_eq!(fit.scale(), scale, 0.03 * scale);
+            assert_almost_eq!(fit.location(), location, 0.03 * scale);
+        }
+        // the fitted distribution reproduces the sample L-moments
+        let data = [2.1, 3.4, 2.8, 5.9, 3.1, 2.5, 4.2, 3.3, 2.9, 3.8];
+        let fit = GeneralizedExtremeValue::fit_lmoments(&data).unwrap();
+        let (sample, fitted) = (sample_l_moments(&data, 3), fit.l_moments(3));
+        assert_almost_eq!(fitted[0], sample[0], 1e-12);
+        assert_almost_eq!(fitted[1], sample[1], 1e-12);
+        assert_almost_eq!(fitted[2], sample[2], 1e-3);
+    }
+
+    #[test]
+    fn test_fit_lmoments_bad_input() {
+        assert!(GeneralizedExtremeValue::fit_lmoments(&[1.0, 2.0]).is_err());
+        assert!(GeneralizedExtremeValue::fit_lmoments(&[1.0, f64::NAN, 2.0]).is_err());
+        assert!(GeneralizedExtremeValue::fit_lmoments(&[3.0; 5]).is_err());
+        // the approximation keeps the shape below one for any L-skewness
+        let fit = GeneralizedExtremeValue::fit_lmoments(&[0.0, 0.0, 0.0, 0.0, 1e6]).unwrap();
+        assert!(fit.shape() < 1.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0, 0.2);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("scale", -2.0), GeneralizedExtremeValue::new(1.0, -2.0, 0.2));
+    }
+}
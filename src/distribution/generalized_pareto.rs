@@ -0,0 +1,640 @@
+use crate::distribution::{Continuous, ContinuousCDF, LMoments, Parameterized};
+use crate::statistics::l_moments::sample_l_moments;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the [generalized Pareto](https://en.wikipedia.org/wiki/Generalized_Pareto_distribution)
+/// distribution, the limit law of excesses over a high threshold, which
+/// contains the exponential (`ξ = 0`), Pareto (`ξ > 0`) and bounded
+/// (`ξ < 0`) families
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, GeneralizedPareto};
+///
+/// let n = GeneralizedPareto::new(0.0, 1.0, 0.5).unwrap();
+/// assert_eq!(n.sf(2.0), 0.25);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GeneralizedPareto {
+    location: f64,
+    scale: f64,
+    shape: f64,
+}
+
+impl GeneralizedPareto {
+    /// Constructs a new generalized Pareto distribution with a location (μ)
+    /// of `location`, a scale (σ) of `scale` and a shape (ξ) of `shape`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any parameter is not finite or if `scale <= 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let mut result = GeneralizedPareto::new(0.0, 1.0, 0.1);
+    /// assert!(result.is_ok());
+    ///
+    /// result = GeneralizedPareto::new(0.0, -1.0, 0.1);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<GeneralizedPareto> {
+        if !location.is_finite() || !scale.is_finite() || !shape.is_finite() || scale <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(GeneralizedPareto {
+                location,
+                scale,
+                shape,
+            })
+        }
+    }
+
+    /// Estimates a generalized Pareto distribution from the sample `data`
+    /// by matching its first three
+    /// [sample L-moments](crate::statistics::l_moments::sample_l_moments),
+    /// as for excesses over a threshold that is itself estimated
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ξ = (3 t3 - 1) / (1 + t3)
+    /// σ = (1 - ξ) (2 - ξ) l2
+    /// μ = l1 - (2 - ξ) l2
+    /// ```
+    ///
+    /// where `l1`, `l2` and `t3` are the sample L-location, L-scale and
+    /// L-skewness
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than three elements or contains
+    /// `NaN`, or if its L-scale is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let excesses = [0.3, 1.2, 0.1, 2.7, 0.6, 0.4, 5.1, 0.9, 0.2, 1.6];
+    /// let n = GeneralizedPareto::fit_lmoments(&excesses).unwrap();
+    /// assert!(n.shape() > 0.0);
+    /// ```
+    pub fn fit_lmoments(data: &[f64]) -> Result<GeneralizedPareto> {
+        if data.len() < 3 {
+            return Err(StatsError::SpecialCase(
+                "at least three observations are required",
+            ));
+        }
+        let l = sample_l_moments(data, 3);
+        if l[1].is_nan() {
+            return Err(StatsError::SpecialCase("data must not contain NaN"));
+        }
+        if l[1] <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        let shape = (3.0 * l[2] - 1.0) / (1.0 + l[2]);
+        let scale = (1.0 - shape) * (2.0 - shape) * l[1];
+        GeneralizedPareto::new(l[0] - (2.0 - shape) * l[1], scale, shape)
+    }
+
+    /// Returns the location of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Returns the scale of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Returns the shape of the generalized Pareto distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::GeneralizedPareto;
+    ///
+    /// let n = GeneralizedPareto::new(1.0, 2.0, 0.1).unwrap();
+    /// assert_eq!(n.shape(), 0.1);
+    /// ```
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the cumulative hazard `-ln(sf(x))`, which is `NaN` below the
+    /// location and infinite above the upper end of the support
+    fn hazard(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        if z < 0.0 {
+            f64::NAN
+        } else if self.shape == 0.0 {
+            z
+        } else if self.shape * z <= -1.0 {
+            f64::INFINITY
+        } else {
+            (self.shape * z).ln_1p() / self.shape
+        }
+    }
+}
+
+impl Parameterized for GeneralizedPareto {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+            ("shape".into(), self.shape),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<GeneralizedPareto> {
+        match *values {
+            [location, scale, shape] => GeneralizedPareto::new(location, scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for GeneralizedPareto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GPD({}, {}, {})", self.location, self.scale, self.shape)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for GeneralizedPareto {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.gen())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for GeneralizedPareto {
+    /// Calculates the cumulative distribution function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and
+    /// `1 - e^(-(x - μ) / σ)` for `ξ = 0`
+    fn cdf(&self, x: f64) -> f64 {
+        let h = self.hazard(x);
+        if h.is_nan() {
+            0.0
+        } else {
+            -(-h).exp_m1()
+        }
+    }
+
+    /// Calculates the survival function for the generalized Pareto
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (1 + ξ (x - μ) / σ)^(-1 / ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and
+    /// `e^(-(x - μ) / σ)` for `ξ = 0`
+    fn sf(&self, x: f64) -> f64 {
+        let h = self.hazard(x);
+        if h.is_nan() {
+            1.0
+        } else {
+            (-h).exp()
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// generalized Pareto distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ ((1 - p)^(-ξ) - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, evaluated
+    /// with `ln_1p` and `exp_m1`, and `μ - σ ln(1 - p)` for `ξ = 0`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let ln_sf = (-p).ln_1p();
+        let z = if self.shape == 0.0 {
+            -ln_sf
+        } else {
+            (-self.shape * ln_sf).exp_m1() / self.shape
+        };
+        self.location + self.scale * z
+    }
+}
+
+impl Min<f64> for GeneralizedPareto {
+    /// Returns the minimum value in the domain of the generalized Pareto
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn min(&self) -> f64 {
+        self.location
+    }
+}
+
+impl Max<f64> for GeneralizedPareto {
+    /// Returns the maximum value in the domain of the generalized Pareto
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if ξ < 0 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     f64::INFINITY
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn max(&self) -> f64 {
+        if self.shape < 0.0 {
+            self.location - self.scale / self.shape
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Distribution<f64> for GeneralizedPareto {
+    /// Returns the mean of the generalized Pareto distribution, which exists
+    /// for `ξ < 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ / (1 - ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn mean(&self) -> Option<f64> {
+        if self.shape >= 1.0 {
+            None
+        } else {
+            Some(self.location + self.scale / (1.0 - self.shape))
+        }
+    }
+
+    /// Returns the variance of the generalized Pareto distribution, which
+    /// exists for `ξ < 1 / 2`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// σ^2 / ((1 - ξ)^2 (1 - 2ξ))
+    /// ```
+    ///
+    /// where `σ` is the scale and `ξ` the shape
+    fn variance(&self) -> Option<f64> {
+        if self.shape >= 0.5 {
+            None
+        } else {
+            let a = self.scale / (1.0 - self.shape);
+            Some(a * a / (1.0 - 2.0 * self.shape))
+        }
+    }
+
+    /// Returns the entropy of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(σ) + ξ + 1
+    /// ```
+    ///
+    /// where `σ` is the scale and `ξ` the shape
+    fn entropy(&self) -> Option<f64> {
+        Some(self.scale.ln() + self.shape + 1.0)
+    }
+
+    /// Returns the skewness of the generalized Pareto distribution, which
+    /// exists for `ξ < 1 / 3`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 (1 + ξ) sqrt(1 - 2ξ) / (1 - 3ξ)
+    /// ```
+    ///
+    /// where `ξ` is the shape
+    fn skewness(&self) -> Option<f64> {
+        if self.shape >= 1.0 / 3.0 {
+            None
+        } else {
+            let xi = self.shape;
+            Some(2.0 * (1.0 + xi) * (1.0 - 2.0 * xi).sqrt() / (1.0 - 3.0 * xi))
+        }
+    }
+}
+
+impl Median<f64> for GeneralizedPareto {
+    /// Returns the median of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + σ (2^ξ - 1) / ξ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape, and
+    /// `μ + σ ln(2)` for `ξ = 0`
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Mode<Option<f64>> for GeneralizedPareto {
+    /// Returns the mode of the generalized Pareto distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if ξ < -1 {
+    ///     μ - σ / ξ
+    /// } else {
+    ///     μ
+    /// }
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn mode(&self) -> Option<f64> {
+        if self.shape < -1.0 {
+            Some(self.location - self.scale / self.shape)
+        } else {
+            Some(self.location)
+        }
+    }
+}
+
+impl Continuous<f64, f64> for GeneralizedPareto {
+    /// Calculates the probability density function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (1 + ξ (x - μ) / σ)^(-1 / ξ - 1) / σ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the generalized
+    /// Pareto distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -(1 / ξ + 1) ln(1 + ξ (x - μ) / σ) - ln(σ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn ln_pdf(&self, x: f64) -> f64 {
+        let h = self.hazard(x);
+        if !h.is_finite() {
+            f64::NEG_INFINITY
+        } else {
+            -(1.0 + self.shape) * h - self.scale.ln()
+        }
+    }
+}
+
+impl LMoments for GeneralizedPareto {
+    /// Returns the first `order` L-moments of the generalized Pareto
+    /// distribution, which are `NaN` for `ξ >= 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ1 = μ + σ / (1 - ξ)
+    /// λ2 = σ / ((1 - ξ) (2 - ξ))
+    /// τr = Π_(j=1..r-2) (j + ξ) / Π_(j=3..r) (j - ξ)
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` the scale and `ξ` the shape
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        if self.shape >= 1.0 {
+            return vec![f64::NAN; order];
+        }
+        let xi = self.shape;
+        let mut ratio = 1.0;
+        (1..=order)
+            .map(|r| match r {
+                1 => self.location + self.scale / (1.0 - xi),
+                2 => self.scale / ((1.0 - xi) * (2.0 - xi)),
+                _ => {
+                    ratio *= (r as f64 - 2.0 + xi) / (r as f64 - xi);
+                    ratio
+                }
+            })
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(location: f64, scale: f64, shape: f64; GeneralizedPareto);
+
+    #[test]
+    fn test_create() {
+        try_create(0.0, 1.0, 0.0);
+        try_create(-5.0, 0.1, 0.5);
+        try_create(10.0, 10.0, -2.0);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0.0, 0.0, 0.1);
+        bad_create_case(0.0, -1.0, 0.1);
+        bad_create_case(f64::NAN, 1.0, 0.1);
+        bad_create_case(0.0, f64::INFINITY, 0.1);
+        bad_create_case(0.0, 1.0, f64::NAN);
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // reference values from mpmath
+        let cdf = |x: f64| move |n: GeneralizedPareto| n.cdf(x);
+        let sf = |x: f64| move |n: GeneralizedPareto| n.sf(x);
+        let pdf = |x: f64| move |n: GeneralizedPareto| n.pdf(x);
+        test_case(1.0, 2.0, 0.25, 0.5904, cdf(3.0));
+        test_case(1.0, 2.0, 0.25, 0.4096, sf(3.0));
+        test_case(1.0, 2.0, 0.25, 0.16384, pdf(3.0));
+        test_case(1.0, 2.0, -0.4, 0.72114519907306599, cdf(3.0));
+        test_case(1.0, 2.0, -0.4, 0.27885480092693401, sf(3.0));
+        test_case(1.0, 2.0, -0.4, 0.23237900077244502, pdf(3.0));
+        test_case(1.0, 2.0, 0.0, 1.0 - (-1.0f64).exp(), cdf(3.0));
+        test_case(1.0, 2.0, 0.0, 0.5 * (-1.0f64).exp(), pdf(3.0));
+        // outside the support
+        test_case(1.0, 2.0, 0.25, 0.0, cdf(0.5));
+        test_case(1.0, 2.0, 0.25, 1.0, sf(0.5));
+        test_case(1.0, 2.0, 0.25, 0.0, pdf(0.5));
+        test_case(1.0, 2.0, -0.4, 1.0, cdf(6.5));
+        test_case(1.0, 2.0, -0.4, 0.0, pdf(6.5));
+    }
+
+    #[test]
+    fn test_moments() {
+        let mean = |n: GeneralizedPareto| n.mean().unwrap();
+        let variance = |n: GeneralizedPareto| n.variance().unwrap();
+        let skewness = |n: GeneralizedPareto| n.skewness().unwrap();
+        let entropy = |n: GeneralizedPareto| n.entropy().unwrap();
+        test_case(1.0, 2.0, 0.25, 11.0 / 3.0, mean);
+        test_case(1.0, 2.0, 0.25, 128.0 / 9.0, variance);
+        test_case(1.0, 2.0, 0.25, 5.0 * 2f64.sqrt(), skewness);
+        test_case(1.0, 2.0, 0.25, 1.9431471805599453, entropy);
+        test_case(1.0, 2.0, -0.4, 17.0 / 7.0, mean);
+        test_case(1.0, 2.0, -0.4, 1.1337868480725623, variance);
+        test_case(1.0, 2.0, -0.4, 0.73180406536356750, skewness);
+        test_case(1.0, 2.0, -0.4, 1.2931471805599453, entropy);
+        test_none(1.0, 2.0, 1.0, |n| n.mean());
+        test_none(1.0, 2.0, 0.5, |n| n.variance());
+        test_none(1.0, 2.0, 0.4, |n| n.skewness());
+    }
+
+    #[test]
+    fn test_median_mode_support() {
+        let median = |n: GeneralizedPareto| n.median();
+        test_case(1.0, 2.0, 0.25, 2.5136569200217685, median);
+        test_case(1.0, 2.0, -0.4, 2.2107085837240048, median);
+        test_case(1.0, 2.0, 0.0, 1.0 + 2.0 * f64::consts::LN_2, median);
+        assert_eq!(try_create(1.0, 2.0, 0.25).mode(), Some(1.0));
+        assert_eq!(try_create(1.0, 2.0, -2.0).mode(), Some(2.0));
+        let n = try_create(1.0, 2.0, -0.4);
+        assert_eq!((n.min(), n.max()), (1.0, 6.0));
+        let n = try_create(1.0, 2.0, 0.0);
+        assert_eq!((n.min(), n.max()), (1.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 2.0, 0.25),
+            [1.0, 1.0000000000000002, 2.5136569200217685, 77928.877488818328],
+        );
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 2.0, -0.4),
+            [1.0, 1.0000000000000002, 2.2107085837240048, 5.9999979244457164],
+        );
+        let n = try_create(1.0, 2.0, -0.4);
+        assert_eq!(n.inverse_cdf(0.0), 1.0);
+        assert_eq!(n.inverse_cdf(1.0), 6.0);
+        for &p in &[0.01, 0.3, 0.9, 0.999] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(1.0, 2.0, 0.25), 1.0, 1000.0);
+        test::check_continuous_distribution(&try_create(1.0, 2.0, -0.4), 1.0, 6.0);
+        test::check_continuous_distribution(&try_create(1.0, 2.0, 0.0), 1.0, 20.0);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        // reference values from the quantile integrals in mpmath
+        let expected = [11.0 / 3.0, 32.0 / 21.0, 5.0 / 11.0, 3.0 / 11.0, 0.18660287081339713];
+        let closed = try_create(1.0, 2.0, 0.25).l_moments(5);
+        for r in 0..5 {
+            assert_almost_eq!(closed[r], expected[r], 1e-14);
+        }
+        // the exponential case
+        let closed = try_create(0.0, 2.0, 0.0).l_moments(5);
+        let exp = crate::distribution::Exp::new(0.5).unwrap().l_moments(5);
+        for r in 0..5 {
+            assert_almost_eq!(closed[r], exp[r], 1e-15);
+        }
+        let n = try_create(1.0, 2.0, -0.4);
+        let (closed, numeric) = (n.l_moments(5), l_moments(&n, 5, &[]));
+        for r in 0..5 {
+            assert_almost_eq!(closed[r], numeric[r], 1e-8);
+        }
+        assert!(try_create(1.0, 2.0, 1.0).l_moments(2).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_fit_lmoments() {
+        let mut rng = StdRng::seed_from_u64(2750);
+        for &(location, scale, shape) in &[(0.0, 1.0, 0.2), (5.0, 3.0, -0.3)] {
+            let n = try_create(location, scale, shape);
+            let data: Vec<f64> = (0..50_000).map(|_| n.sample(&mut rng)).collect();
+            let fit = GeneralizedPareto::fit_lmoments(&data).unwrap();
+            assert_almost_eq!(fit.shape(), shape, 0.02);
+            assert_almost_eq!(fit.scale(), scale, 0.03 * scale);
+            assert_almost_eq!(fit.location(), location, 0.01 * scale);
+        }
+        // the fit matches the sample L-moments exactly
+        let data = [0.3, 1.2, 0.1, 2.7, 0.6, 0.4, 5.1, 0.9, 0.2, 1.6];
+        let fit = GeneralizedPareto::fit_lmoments(&data).unwrap();
+        let (sample, fitted) = (sample_l_moments(&data, 3), fit.l_moments(3));
+        for r in 0..3 {
+            assert_almost_eq!(fitted[r], sample[r], 1e-12);
+        }
+        assert!(GeneralizedPareto::fit_lmoments(&[1.0, 2.0]).is_err());
+        assert!(GeneralizedPareto::fit_lmoments(&[1.0, f64::NAN, 2.0]).is_err());
+        assert!(GeneralizedPareto::fit_lmoments(&[3.0; 5]).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0, 0.2);
+        test::check_parameters(&n, |d| [d.pdf(1.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("scale", -2.0), GeneralizedPareto::new(1.0, -2.0, 0.2));
+    }
+}
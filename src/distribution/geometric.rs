@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{Discrete, DiscreteCDF, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::OpenClosed01;
@@ -68,6 +68,19 @@ impl Geometric {
     }
 }
 
+impl Parameterized for Geometric {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("p".into(), self.p)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Geometric> {
+        match *values {
+            [p] => Geometric::new(p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Geometric {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Geom({})", self.p)
@@ -277,6 +290,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, Geometric};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(p: f64) -> Geometric {
         let n = Geometric::new(p);
@@ -526,4 +540,11 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.6), 100);
         test::check_discrete_distribution(&try_create(1.0), 1);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(0.3);
+        test::check_parameters(&n, |d| [d.pmf(1), d.pmf(4)]);
+        assert_eq!(n.set_parameter("p", 0.0), Geometric::new(0.0));
+    }
 }
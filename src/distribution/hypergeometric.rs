@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
 use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -110,6 +110,27 @@ impl Hypergeometric {
     }
 }
 
+impl Parameterized for Hypergeometric {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("population".into(), self.population as f64),
+            ("successes".into(), self.successes as f64),
+            ("draws".into(), self.draws as f64),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Hypergeometric> {
+        match *values {
+            [population, successes, draws] => Hypergeometric::new(
+                internal::integer_parameter(population)?,
+                internal::integer_parameter(successes)?,
+                internal::integer_parameter(draws)?,
+            ),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Hypergeometric {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -382,6 +403,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, Hypergeometric};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(population: u64, successes: u64, draws: u64) -> Hypergeometric {
         let n = Hypergeometric::new(population, successes, draws);
@@ -600,4 +622,12 @@ mod tests {
         test::check_discrete_distribution(&try_create(5, 4, 3), 4);
         test::check_discrete_distribution(&try_create(3, 2, 1), 2);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(20, 8, 5);
+        test::check_parameters(&n, |d| [d.pmf(0), d.pmf(2), d.pmf(5)]);
+        assert_eq!(n.set_parameter("draws", 30.0), Hypergeometric::new(20, 8, 30));
+        assert!(n.set_parameter("successes", -1.0).is_err());
+    }
 }
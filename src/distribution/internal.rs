@@ -1,4 +1,16 @@
-use num_traits::{Bounded, Float, Num};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
+use crate::function::factorial;
+use crate::function::integrate::integrate;
+use crate::statistics::{Max, Min};
+use crate::{Result, StatsError};
+use num_traits::{Bounded, Float, Num, NumCast};
+
+/// Relative tolerance of the numerical integration in `l_moments`
+const L_MOMENT_TOLERANCE: f64 = 1e-12;
+
+/// Relative tolerance of the numerical integration used for moments that
+/// have no closed form
+const MOMENT_TOLERANCE: f64 = 1e-12;
 
 /// Returns true if there are no elements in `x` in `arr`
 /// such that `x <= 0.0` or `x` is `f64::NAN` and `sum(arr) > 0.0`.
@@ -54,11 +66,260 @@ pub fn integral_bisection_search<K: Num + Clone, T: Num + PartialOrd>(
     }
 }
 
+/// Returns the derivative at `t` of the Legendre polynomial of degree `n`
+fn legendre_derivative(n: usize, t: f64) -> f64 {
+    // P_k and P'_k from P_(k+1) = ((2k + 1) t P_k - k P_(k-1)) / (k + 1)
+    // and P'_(k+1) = P'_(k-1) + (2k + 1) P_k
+    let (mut p0, mut p1) = (1.0, t);
+    let (mut d0, mut d1) = (0.0, 1.0);
+    if n == 0 {
+        return 0.0;
+    }
+    for k in 1..n {
+        let k = k as f64;
+        let p2 = ((2.0 * k + 1.0) * t * p1 - k * p0) / (k + 1.0);
+        let d2 = d0 + (2.0 * k + 1.0) * p1;
+        p0 = p1;
+        p1 = p2;
+        d0 = d1;
+        d1 = d2;
+    }
+    d1
+}
+
+/// Returns the first `order` L-moments `[λ1, λ2, τ3, ...]` of `dist`,
+/// taking the leading ones from `known` and integrating the cdf for the
+/// rest, see `LMoments::l_moments`
+pub fn l_moments<D: ContinuousCDF<f64, f64> + ?Sized>(
+    dist: &D,
+    order: usize,
+    known: &[f64],
+) -> Vec<f64> {
+    if order <= known.len() {
+        return known[..order].to_vec();
+    }
+    let (min, max) = (dist.min(), dist.max());
+    let mut moments = known.to_vec();
+    let mut scale = known.get(1).copied().unwrap_or(f64::NAN);
+    for r in known.len() + 1..=order {
+        if r == 1 {
+            let upper = integrate(
+                |x| dist.sf(x),
+                min.max(0.0),
+                max.max(0.0),
+                L_MOMENT_TOLERANCE,
+            );
+            let lower = integrate(
+                |x| dist.cdf(x),
+                min.min(0.0),
+                max.min(0.0),
+                L_MOMENT_TOLERANCE,
+            );
+            // the sf is one on [0, min) and the cdf is one on (max, 0]
+            moments.push(upper - lower + min.max(0.0) + max.min(0.0));
+            continue;
+        }
+        let integral = integrate(
+            |x| {
+                let (f, s) = (dist.cdf(x), dist.sf(x));
+                if f == 0.0 || s == 0.0 {
+                    0.0
+                } else {
+                    f * s * legendre_derivative(r - 1, f - s)
+                }
+            },
+            min,
+            max,
+            L_MOMENT_TOLERANCE,
+        );
+        let lambda = 2.0 * integral / (r * (r - 1)) as f64;
+        if r == 2 {
+            scale = lambda;
+            moments.push(lambda);
+        } else {
+            moments.push(lambda / scale);
+        }
+    }
+    moments
+}
+
+/// Computes `E[g(X)]` for `X` distributed as `dist` by integrating over its
+/// support, returning `None` if the expectation does not evaluate to a
+/// finite value
+pub fn expectation<D, F>(dist: &D, g: F) -> Option<f64>
+where
+    D: Continuous<f64, f64> + Min<f64> + Max<f64> + ?Sized,
+    F: Fn(f64) -> f64,
+{
+    let value = integrate(
+        |x| {
+            let density = dist.pdf(x);
+            if density == 0.0 {
+                0.0
+            } else {
+                g(x) * density
+            }
+        },
+        dist.min(),
+        dist.max(),
+        MOMENT_TOLERANCE,
+    );
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Computes the partial expectation `E[X 1{a < X <= b}]` of `dist` by
+/// integrating `x f(x)` over the part of `(a, b]` inside the support, see
+/// `TailExpectation::partial_expectation`
+pub fn partial_expectation<D>(dist: &D, a: f64, b: f64) -> f64
+where
+    D: Continuous<f64, f64> + Min<f64> + Max<f64> + ?Sized,
+{
+    let (lower, upper) = (a.max(dist.min()), b.min(dist.max()));
+    if lower >= upper {
+        return 0.0;
+    }
+    integrate(
+        |x| {
+            let density = dist.pdf(x);
+            if density == 0.0 {
+                0.0
+            } else {
+                x * density
+            }
+        },
+        lower,
+        upper,
+        MOMENT_TOLERANCE,
+    )
+}
+
+/// Computes the mean excess `E[X - u | X > u]` of `dist` as
+/// `∫_u^∞ S(x) dx / S(u)` where `S` is the survival function, see
+/// `TailExpectation::mean_excess`
+pub fn mean_excess<D: ContinuousCDF<f64, f64> + ?Sized>(dist: &D, u: f64) -> f64 {
+    let tail = dist.sf(u);
+    if tail.is_nan() || tail <= 0.0 {
+        return f64::NAN;
+    }
+    // below the support the survival function is one; the conditional
+    // survival function is integrated so that the tolerance is relative to
+    // the mean excess even far in the tail
+    let lower = u.max(dist.min());
+    lower - u + integrate(|x| dist.sf(x) / tail, lower, dist.max(), MOMENT_TOLERANCE)
+}
+
+/// Returns the central moments `[μ_0, ..., μ_order]` of a distribution with
+/// the cumulants `κ_n = cumulant(n)` for `n >= 2`, through the recursion
+///
+/// ```text
+/// μ_n = Σ_{k = 0}^{n - 2} C(n - 1, k) κ_{n - k} μ_k
+/// ```
+///
+/// with `μ_0 = 1` and `μ_1 = 0`
+pub fn central_moments_from_cumulants<F: Fn(u32) -> f64>(order: u32, cumulant: F) -> Vec<f64> {
+    let mut moments = vec![1.0, 0.0];
+    for n in 2..=order {
+        let moment = (0..=n - 2)
+            .map(|k| {
+                factorial::binomial((n - 1) as u64, k as u64)
+                    * cumulant(n - k)
+                    * moments[k as usize]
+            })
+            .sum();
+        moments.push(moment);
+    }
+    moments.truncate(order as usize + 1);
+    moments
+}
+
+/// Returns the raw moment `E[X^order]` of `X = location + Y` from the
+/// moments `central(k) = E[Y^k]`, or `None` if one of them is undefined
+pub fn raw_moment_about<F>(order: u32, location: f64, central: F) -> Option<f64>
+where
+    F: Fn(u32) -> Option<f64>,
+{
+    let mut sum = 0.0;
+    for k in 0..=order {
+        let shift = location.powi((order - k) as i32);
+        sum += factorial::binomial(order as u64, k as u64) * shift * central(k)?;
+    }
+    Some(sum)
+}
+
+/// Returns the Stirling numbers of the second kind `S(order, k)` for
+/// `k = 0, ..., order`, the numbers of partitions of `order` elements into
+/// `k` non-empty blocks, through `S(n, k) = k S(n - 1, k) + S(n - 1, k - 1)`
+pub fn stirling_second_kind(order: u32) -> Vec<f64> {
+    let mut row = vec![1.0];
+    for n in 1..=order as usize {
+        let mut next = vec![0.0; n + 1];
+        for k in 1..=n {
+            let previous = if k < n { row[k] } else { 0.0 };
+            next[k] = k as f64 * previous + row[k - 1];
+        }
+        row = next;
+    }
+    row
+}
+
+/// Returns the cumulative distribution and survival functions of a
+/// distribution on `0..pmf.len()` with the probabilities `pmf`, the
+/// survival function being summed from the right so that small upper tail
+/// probabilities keep their relative accuracy
+pub fn cumulative_tables(pmf: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let mut cdf = pmf.to_vec();
+    for j in 1..cdf.len() {
+        cdf[j] = (cdf[j - 1] + cdf[j]).min(1.0);
+    }
+    let mut sf = vec![0.0; pmf.len()];
+    for j in (0..pmf.len().saturating_sub(1)).rev() {
+        sf[j] = (sf[j + 1] + pmf[j + 1]).min(1.0);
+    }
+    (cdf, sf)
+}
+
+/// Converts the parameter value `x` to an integer type, failing with
+/// `StatsError::BadParams` if it is not an integer representable as `T`
+pub fn integer_parameter<T: NumCast>(x: f64) -> Result<T> {
+    if x.fract() == 0.0 {
+        <T as NumCast>::from(x).ok_or(StatsError::BadParams)
+    } else {
+        Err(StatsError::BadParams)
+    }
+}
+
+/// Returns the parameters of a distribution wrapped by another, with their
+/// names prefixed by `inner.`
+pub fn inner_parameters<D: Parameterized>(dist: &D) -> Vec<(String, f64)> {
+    dist.parameters()
+        .into_iter()
+        .map(|(name, value)| (format!("inner.{}", name), value))
+        .collect()
+}
+
+/// Builds the values of a vector-valued parameter from `values`, checking
+/// that there is one per element
+pub fn parameter_values(values: &[f64], len: usize) -> Result<Vec<f64>> {
+    if values.len() == len {
+        Ok(values.to_vec())
+    } else {
+        Err(StatsError::ContainersMustBeSameLength)
+    }
+}
+
 #[macro_use]
 #[cfg(test)]
 pub mod test {
     use super::*;
-    use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+    use crate::distribution::{
+        Continuous, ContinuousCDF, Discrete, DiscreteCDF, Moments, ScoreFunction, TailExpectation,
+    };
+    use crate::statistics::Distribution;
+    use std::fmt::Debug;
 
     #[macro_export]
     macro_rules! testing_boiler {
@@ -202,6 +463,26 @@ pub mod test {
         check_integrate_pdf_is_cdf(dist, x_min, x_max, (x_max - x_min) / 100000.0);
     }
 
+    /// The probabilities at which `check_inverse_cdf_tails` evaluates the
+    /// quantile function, covering both far tails and the median
+    pub const TAIL_PROBABILITIES: [f64; 4] = [1e-300, 1e-16, 0.5, 1.0 - 1e-16];
+
+    /// Checks that the inverse cdf at each of `TAIL_PROBABILITIES` agrees
+    /// with the corresponding entry of `expected` to a relative error below
+    /// `1e-13`, as needed for inverse-transform sampling of heavy tails
+    pub fn check_inverse_cdf_tails<D: ContinuousCDF<f64, f64>>(dist: &D, expected: [f64; 4]) {
+        for (&p, &x) in TAIL_PROBABILITIES.iter().zip(expected.iter()) {
+            let q = dist.inverse_cdf(p);
+            assert!(
+                (q - x).abs() <= 1e-13 * x.abs(),
+                "inverse_cdf({}) = {}, expected {}",
+                p,
+                q,
+                x
+            );
+        }
+    }
+
     /// Does a series of checks that all positive discrete distributions must
     /// obey.
     /// 99% of the probability mass should be between 0 and x_max (inclusive).
@@ -218,6 +499,165 @@ pub mod test {
         check_sum_pmf_is_cdf(dist, x_max);
     }
 
+    /// Checks the gradient, the Hessian and the summed score of the
+    /// distribution built by `make` from `params` against central finite
+    /// differences of `ln_f` at every point of `xs`
+    pub fn check_score<K, D, M, F>(make: M, ln_f: F, params: &[f64], xs: &[K])
+    where
+        K: Copy,
+        D: ScoreFunction<K>,
+        M: Fn(&[f64]) -> D,
+        F: Fn(&D, K) -> f64,
+    {
+        let dist = make(params);
+        let shifted = |i: usize, h: f64| {
+            let mut p = params.to_vec();
+            p[i] += h;
+            make(&p)
+        };
+        let mut total = vec![0.0; D::NUM_PARAMS];
+        for &x in xs {
+            let grad = dist.ln_pdf_grad(x);
+            let hessian = dist.ln_pdf_hessian(x);
+            assert_eq!(grad.len(), D::NUM_PARAMS);
+            for i in 0..params.len() {
+                let h = 1e-5 * params[i].abs().max(1e-2);
+                let (up, down) = (shifted(i, h), shifted(i, -h));
+                let diff = (ln_f(&up, x) - ln_f(&down, x)) / (2.0 * h);
+                assert!((diff - grad[i]).abs() < 1e-6 * grad[i].abs().max(1.0));
+                if let Some(hessian) = &hessian {
+                    let (g_up, g_down) = (up.ln_pdf_grad(x), down.ln_pdf_grad(x));
+                    for j in 0..params.len() {
+                        let diff = (g_up[j] - g_down[j]) / (2.0 * h);
+                        assert!((diff - hessian[j][i]).abs() < 1e-6 * hessian[j][i].abs().max(1.0));
+                        assert_eq!(hessian[i][j], hessian[j][i]);
+                    }
+                }
+                total[i] += grad[i];
+            }
+        }
+        let score = dist.score_sum(xs);
+        for (s, t) in score.iter().zip(&total) {
+            assert!((s - t).abs() < 1e-12 * t.abs().max(1.0));
+        }
+    }
+
+    /// Checks that the moments of orders `0` to `3` of `dist` agree with
+    /// its mean, variance and skewness to a relative error below `tol`,
+    /// and are undefined exactly when those are
+    pub fn check_moments<D: Moments + Distribution<f64>>(dist: &D, tol: f64) {
+        let close = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => {
+                assert!((a - b).abs() <= tol * b.abs().max(1e-300), "{} != {}", a, b)
+            }
+            (a, b) => assert_eq!(a.is_some(), b.is_some(), "{:?} != {:?}", a, b),
+        };
+        assert_eq!(dist.raw_moment(0), Some(1.0));
+        close(dist.raw_moment(1), dist.mean());
+        close(dist.central_moment(2), dist.variance());
+        if let Some(skewness) = dist.skewness() {
+            let moment = dist.standardized_moment(3).unwrap();
+            assert!(
+                (moment - skewness).abs() <= tol * skewness.abs().max(1.0),
+                "{} != {}",
+                moment,
+                skewness
+            );
+        } else {
+            assert!(dist.standardized_moment(3).is_none());
+        }
+        if let Some(variance) = dist.variance() {
+            let first = dist.central_moment(1).unwrap();
+            assert!(first.abs() <= tol * variance.sqrt().max(dist.mean().unwrap().abs()));
+        }
+    }
+
+    /// Checks that the raw and central moments of `dist` of orders up to
+    /// `max_order` agree with the numerical integration of its density to a
+    /// relative error below `tol`
+    pub fn check_moments_numeric<D>(dist: &D, max_order: u32, tol: f64)
+    where
+        D: Moments + Continuous<f64, f64> + Min<f64> + Max<f64>,
+    {
+        let mean = dist.raw_moment(1).unwrap();
+        for r in 0..=max_order {
+            let raw = expectation(dist, |x| x.powi(r as i32)).unwrap();
+            let central = expectation(dist, |x| (x - mean).powi(r as i32)).unwrap();
+            let (a, b) = (dist.raw_moment(r).unwrap(), dist.central_moment(r).unwrap());
+            assert!(
+                (a - raw).abs() <= tol * raw.abs().max(1e-12),
+                "raw {}: {} != {}",
+                r,
+                a,
+                raw
+            );
+            // odd central moments may vanish, so they are compared on the
+            // scale of the standard deviation
+            let scale = central
+                .abs()
+                .max(dist.central_moment(2).unwrap().powf(0.5 * r as f64));
+            assert!(
+                (b - central).abs() <= tol * scale,
+                "central {}: {} != {}",
+                r,
+                b,
+                central
+            );
+        }
+    }
+
+    /// Checks that the partial expectations and mean excesses of `dist` on
+    /// the `points` agree with the numerical integrals to a relative error
+    /// below `tol`, and with each other through
+    /// `e(u) = E[X 1{X > u}] / S(u) - u`
+    pub fn check_tail_expectation<D: TailExpectation>(dist: &D, points: &[f64], tol: f64) {
+        let close =
+            |a: f64, b: f64, scale: f64| assert!((a - b).abs() <= tol * scale, "{} != {}", a, b);
+        for (i, &a) in points.iter().enumerate() {
+            for &b in points.iter().skip(i + 1).chain(&[f64::INFINITY]) {
+                let numeric = partial_expectation(dist, a, b);
+                close(
+                    dist.partial_expectation(a, b),
+                    numeric,
+                    numeric.abs().max(1e-12),
+                );
+            }
+            let excess = dist.mean_excess(a);
+            close(excess, mean_excess(dist, a), excess.abs().max(1e-12));
+            let tail = dist.sf(a);
+            let conditional = dist.partial_expectation(a, f64::INFINITY) / tail;
+            close(excess, conditional - a, conditional.abs().max(1.0));
+        }
+        assert_eq!(dist.partial_expectation(1.0, 1.0), 0.0);
+        assert_eq!(dist.partial_expectation(2.0, 1.0), 0.0);
+    }
+
+    /// Checks that rebuilding `dist` from its own parameters, all at once or
+    /// one at a time, gives the same values of `eval`, and that the
+    /// parameter names are unique and the counts of values are checked
+    pub fn check_parameters<D, F, T>(dist: &D, eval: F)
+    where
+        D: Parameterized,
+        F: Fn(&D) -> T,
+        T: PartialEq + Debug,
+    {
+        let parameters = dist.parameters();
+        assert_eq!(parameters.len(), dist.n_parameters());
+        let values: Vec<f64> = parameters.iter().map(|(_, v)| *v).collect();
+        assert_eq!(eval(&dist.with_parameters(&values).unwrap()), eval(dist));
+        for (i, (name, value)) in parameters.iter().enumerate() {
+            assert!(parameters[..i].iter().all(|(n, _)| n != name));
+            assert_eq!(eval(&dist.set_parameter(name, *value).unwrap()), eval(dist));
+        }
+        let mut longer = values.clone();
+        longer.push(1.0);
+        assert!(dist.with_parameters(&longer).is_err());
+        if !values.is_empty() {
+            assert!(dist.with_parameters(&values[1..]).is_err());
+        }
+        assert!(dist.set_parameter("no such parameter", 1.0).is_err());
+    }
+
     #[test]
     fn test_is_valid_multinomial() {
         use std::f64;
@@ -0,0 +1,96 @@
+//! Provides intervals summarizing the mass of a probability distribution
+
+use crate::distribution::ContinuousCDF;
+use crate::function::optimize::golden_section;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Computes the highest density interval of the continuous distribution
+/// `dist` holding probability `level`, the shortest interval with that
+/// probability
+///
+/// # Formula
+///
+/// The interval is `[Q(p), Q(p + level)]` for the `p` in `[0, 1 - level]`
+/// minimizing its width, where `Q` is the inverse cdf. At that `p` the
+/// density is equal at both ends unless one of them is an end of the
+/// support.
+///
+/// # Remarks
+///
+/// The minimum is found by golden-section search in `p`. For a unimodal
+/// density the width is unimodal in `p` and the interval is the region of
+/// highest density, narrower than the equal-tailed interval unless the
+/// density is symmetric. For a multimodal density the region of highest
+/// density may not be an interval, and some locally shortest interval is
+/// returned. The ends are accurate to about `1e-9` relative to the slope
+/// of `Q`.
+///
+/// # Errors
+///
+/// Returns an error if `level` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Exp;
+/// use statrs::distribution::interval::hdi;
+///
+/// // the density of the exponential distribution is highest at 0
+/// let (lower, upper) = hdi(&Exp::new(1.0).unwrap(), 0.9).unwrap();
+/// assert!(lower.abs() < 1e-8);
+/// assert!((upper - 10f64.ln()).abs() < 1e-8);
+/// ```
+pub fn hdi<D: ContinuousCDF<f64, f64>>(dist: &D, level: f64) -> Result<(f64, f64)> {
+    if level.is_nan() || level <= 0.0 || level >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+    }
+    let ends = |p: f64| (dist.inverse_cdf(p), dist.inverse_cdf((p + level).min(1.0)));
+    let width = |p: f64| {
+        let (lower, upper) = ends(p);
+        upper - lower
+    };
+    let p = golden_section(width, 0.0, 1.0 - level, 1e-12).argmin;
+    Ok(ends(p))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, Gamma, LogNormal, Normal};
+
+    #[test]
+    fn test_symmetric() {
+        // the equal-tailed interval of a symmetric distribution
+        let normal = Normal::new(1.0, 2.0).unwrap();
+        let (lower, upper) = hdi(&normal, 0.95).unwrap();
+        assert_almost_eq!(lower, 1.0 - 2.0 * 1.959963984540054, 1e-7);
+        assert_almost_eq!(upper, 1.0 + 2.0 * 1.959963984540054, 1e-7);
+    }
+
+    #[test]
+    fn test_skewed() {
+        // the ends solve pdf(a) = pdf(b), cdf(b) - cdf(a) = 0.9 with mpmath
+        let gamma = Gamma::new(3.0, 1.0).unwrap();
+        let (lower, upper) = hdi(&gamma, 0.9).unwrap();
+        assert_almost_eq!(lower, 0.44132689536607284, 1e-8);
+        assert_almost_eq!(upper, 5.4791747123163689, 1e-8);
+        assert_almost_eq!(gamma.pdf(lower), gamma.pdf(upper), 1e-8);
+        // narrower than the equal-tailed interval
+        let equal_tailed = gamma.inverse_cdf(0.95) - gamma.inverse_cdf(0.05);
+        assert!(upper - lower < equal_tailed);
+        let log_normal = LogNormal::new(0.0, 0.5).unwrap();
+        let (lower, upper) = hdi(&log_normal, 0.5).unwrap();
+        assert_almost_eq!(log_normal.cdf(upper) - log_normal.cdf(lower), 0.5, 1e-10);
+        assert_almost_eq!(log_normal.pdf(lower), log_normal.pdf(upper), 1e-7);
+    }
+
+    #[test]
+    fn test_bad_level() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        assert!(hdi(&normal, 0.0).is_err());
+        assert!(hdi(&normal, 1.0).is_err());
+        assert!(hdi(&normal, f64::NAN).is_err());
+    }
+}
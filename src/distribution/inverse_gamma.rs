@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized};
 use crate::function::gamma;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -87,6 +87,19 @@ impl InverseGamma {
     }
 }
 
+impl Parameterized for InverseGamma {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("shape".into(), self.shape), ("rate".into(), self.rate)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<InverseGamma> {
+        match *values {
+            [shape, rate] => InverseGamma::new(shape, rate),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for InverseGamma {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Inv-Gamma({}, {})", self.shape, self.rate)
@@ -316,6 +329,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, InverseGamma};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(shape: f64, rate: f64) -> InverseGamma {
         let n = InverseGamma::new(shape, rate);
@@ -485,4 +499,11 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.0, 0.5), 0.0, 100.0);
         test::check_continuous_distribution(&try_create(9.0, 2.0), 0.0, 100.0);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("rate", 0.0), InverseGamma::new(3.0, 0.0));
+    }
 }
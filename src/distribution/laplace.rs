@@ -1,4 +1,5 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{internal, Continuous, ContinuousCDF, LMoments, Moments, Parameterized};
+use crate::function::factorial;
 use crate::statistics::{Distribution, Max, Median, Min, Mode};
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -79,6 +80,22 @@ impl Laplace {
     }
 }
 
+impl Parameterized for Laplace {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Laplace> {
+        match *values {
+            [location, scale] => Laplace::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Laplace {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Laplace({}, {})", self.location, self.scale)
@@ -146,9 +163,13 @@ impl ContinuousCDF<f64, f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location, `b` is the scale
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
     fn inverse_cdf(&self, p: f64) -> f64 {
-        if p <= 0. || 1. <= p {
-            panic!("p must be in [0, 1]");
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
         };
         if p <= 0.5 {
             self.location + self.scale * (2. * p).ln()
@@ -297,6 +318,55 @@ impl Continuous<f64, f64> for Laplace {
     }
 }
 
+impl LMoments for Laplace {
+    /// Returns the first `order` L-moments of the laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [μ, 3b / 4, 0, 17 / 72, ...]
+    /// ```
+    ///
+    /// where `μ` is the location and `b` is the scale. Orders above four are
+    /// integrated numerically.
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        let known = [self.location, 0.75 * self.scale, 0.0, 17.0 / 72.0];
+        internal::l_moments(self, order, &known)
+    }
+}
+
+impl Moments for Laplace {
+    /// Returns the raw moment of order `order` of the laplace distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k = 0}^r C(r, k) μ^(r - k) E[(X - μ)^k]
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        internal::raw_moment_about(order, self.location, |k| self.central_moment(k))
+    }
+
+    /// Returns the central moment of order `order` of the laplace
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// r! b^r
+    /// ```
+    ///
+    /// for even `r` and zero for odd `r`, where `b` is the scale
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        if order % 2 == 1 {
+            return Some(0.0);
+        }
+        Some(factorial::factorial(order as u64) * self.scale.powi(order as i32))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,4 +670,52 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        use crate::distribution::internal::*;
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(
+            &try_create(1.0, 0.5),
+            [
+                -344.04119035882688,
+                -17.074107153672393,
+                1.0,
+                19.021826694558578,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_l_moments() {
+        use crate::distribution::internal::*;
+        use crate::distribution::LMoments;
+        let n = try_create(-1.0, 2.0);
+        let closed = n.l_moments(4);
+        assert_eq!(closed, vec![-1.0, 1.5, 0.0, 17.0 / 72.0]);
+        let numeric = l_moments(&n, 4, &[]);
+        for r in 0..4 {
+            assert!((numeric[r] - closed[r]).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0);
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.pdf(-1.0), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("scale", -1.0), Laplace::new(1.0, -1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::internal::*;
+        use crate::distribution::Moments;
+        for &(location, scale) in [(0.0, 1.0), (-1.0, 2.0), (5.0, 0.1)].iter() {
+            let n = try_create(location, scale);
+            test::check_moments(&n, 1e-14);
+            test::check_moments_numeric(&n, 6, 1e-8);
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 6.0, 1e-14);
+        }
+    }
 }
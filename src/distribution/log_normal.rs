@@ -1,5 +1,7 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::erf;
+use crate::distribution::{
+    Continuous, ContinuousCDF, LMoments, Moments, Normal, Parameterized, TailExpectation,
+};
+use crate::function::{erf, factorial};
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
 use rand::Rng;
@@ -53,6 +55,114 @@ impl LogNormal {
             Ok(LogNormal { location, scale })
         }
     }
+
+    /// Estimates a log-normal distribution from the sample `data` by maximum
+    /// likelihood, the normal estimates on the logarithms of the data
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ = mean(ln x_i)
+    /// σ = sqrt(Σ (ln x_i - μ)^2 / n)
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not positive or is `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    /// use statrs::statistics::Median;
+    ///
+    /// // the location is ln(10), the mean of the logarithms
+    /// let n = LogNormal::fit_mle(&[1.0, 100.0]).unwrap();
+    /// assert!((n.median() - 10.0).abs() < 1e-13);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<LogNormal> {
+        if data.iter().any(|&x| x.is_nan() || x <= 0.0) {
+            return Err(StatsError::SpecialCase("data must be positive"));
+        }
+        let logs: Vec<f64> = data.iter().map(|x| x.ln()).collect();
+        let normal = Normal::fit_mle(&logs)?;
+        LogNormal::new(normal.mean().unwrap(), normal.std_dev().unwrap())
+    }
+
+    /// Calculates the natural logarithm of the cumulative distribution
+    /// function for the log-normal distribution at `x`, which stays finite
+    /// far in the lower tail where the cdf underflows to zero
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(Φ((ln(x) - μ) / σ))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `Φ` the standard
+    /// normal cdf
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    ///
+    /// let n = LogNormal::new(0.0, 1.0).unwrap();
+    /// assert!((n.ln_cdf((-40.0f64).exp()) - -804.60844201375379).abs() < 1e-11);
+    /// ```
+    pub fn ln_cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            super::normal::ln_sf_std((self.location - x.ln()) / self.scale)
+        }
+    }
+
+    /// Calculates the natural logarithm of the survival function for the
+    /// log-normal distribution at `x`, which stays finite far in the upper
+    /// tail where the survival function underflows to zero
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(1 - Φ((ln(x) - μ) / σ))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `Φ` the standard
+    /// normal cdf
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::LogNormal;
+    ///
+    /// let n = LogNormal::new(0.0, 1.0).unwrap();
+    /// assert!((n.ln_sf(40.0f64.exp()) - -804.60844201375379).abs() < 1e-11);
+    /// ```
+    pub fn ln_sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            super::normal::ln_sf_std((x.ln() - self.location) / self.scale)
+        }
+    }
+}
+
+impl Parameterized for LogNormal {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<LogNormal> {
+        match *values {
+            [location, scale] => LogNormal::new(location, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
 }
 
 impl std::fmt::Display for LogNormal {
@@ -83,10 +193,8 @@ impl ContinuousCDF<f64, f64> for LogNormal {
     fn cdf(&self, x: f64) -> f64 {
         if x <= 0.0 {
             0.0
-        } else if x.is_infinite() {
-            1.0
         } else {
-            0.5 * erf::erfc((self.location - x.ln()) / (self.scale * f64::consts::SQRT_2))
+            super::normal::cdf_unchecked(x.ln(), self.location, self.scale)
         }
     }
 
@@ -113,10 +221,8 @@ impl ContinuousCDF<f64, f64> for LogNormal {
     fn sf(&self, x: f64) -> f64 {
         if x <= 0.0 {
             1.0
-        } else if x.is_infinite() {
-            0.0
         } else {
-            0.5 * erf::erfc((x.ln() - self.location) / (self.scale * f64::consts::SQRT_2))
+            super::normal::sf_unchecked(x.ln(), self.location, self.scale)
         }
     }
 
@@ -130,14 +236,18 @@ impl ContinuousCDF<f64, f64> for LogNormal {
     /// # Formula
     ///
     /// ```text
-    /// μ - σ * sqrt(2) * erfc_inv(2p)
+    /// e^(μ - σ * sqrt(2) * erfc_inv(2p))
     /// ```
     ///
     /// where `μ` is the location, `σ` is the scale and `erfc_inv` is
-    /// the inverse of the complementary error function
+    /// the inverse of the complementary error function. For subnormal `p`
+    /// the normal quantile is found from `ln(p)` as by
+    /// `Normal::inverse_ln_sf`.
     fn inverse_cdf(&self, p: f64) -> f64 {
         if p == 0.0 {
             0.0
+        } else if p < f64::MIN_POSITIVE {
+            (self.location - self.scale * super::normal::inverse_ln_sf_std(p.ln())).exp()
         } else if p < 1.0 {
             (self.location - (self.scale * f64::consts::SQRT_2 * erf::erfc_inv(2.0 * p))).exp()
         } else if p == 1.0 {
@@ -278,7 +388,15 @@ impl Continuous<f64, f64> for LogNormal {
             0.0
         } else {
             let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d).exp() / (x * consts::SQRT_2PI * self.scale)
+            let density = (-0.5 * d * d).exp();
+            let width = x * consts::SQRT_2PI * self.scale;
+            if width.is_normal() {
+                density / width
+            } else {
+                // dividing in steps keeps x * σ from overflowing or
+                // underflowing
+                density / x / (consts::SQRT_2PI * self.scale)
+            }
         }
     }
 
@@ -297,17 +415,126 @@ impl Continuous<f64, f64> for LogNormal {
             f64::NEG_INFINITY
         } else {
             let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d) - consts::LN_SQRT_2PI - (x * self.scale).ln()
+            let width = x * self.scale;
+            let ln_width = if width.is_normal() {
+                width.ln()
+            } else {
+                x.ln() + self.scale.ln()
+            };
+            (-0.5 * d * d) - consts::LN_SQRT_2PI - ln_width
         }
     }
 }
 
+impl LMoments for LogNormal {}
+
+impl Moments for LogNormal {
+    /// Returns the raw moment of order `order` of the log-normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(rμ + r^2 σ^2 / 2)
+    /// ```
+    ///
+    /// where `μ` is the location and `σ` is the scale
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        let r = order as f64;
+        Some((r * self.location + r * r * self.scale * self.scale / 2.0).exp())
+    }
+
+    /// Returns the central moment of order `order` of the log-normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// m^r Σ_{k = 0}^r C(r, k) (-1)^(r - k) (e^(σ^2 k (k - 1) / 2) - 1)
+    /// ```
+    ///
+    /// where `m` is the mean and `σ` is the scale. Subtracting one from each
+    /// term leaves the sum unchanged for `r > 0` and avoids the cancellation
+    /// of the terms when `σ` is small.
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        if order == 0 {
+            return Some(1.0);
+        }
+        let sigma2 = self.scale * self.scale;
+        let sum: f64 = (0..=order)
+            .map(|k| {
+                let sign = if (order - k) & 1 == 1 { -1.0 } else { 1.0 };
+                let term = (sigma2 * (k * k.saturating_sub(1)) as f64 / 2.0).exp_m1();
+                sign * factorial::binomial(order as u64, k as u64) * term
+            })
+            .sum();
+        Some(self.mean()?.powi(order as i32) * sum)
+    }
+}
+
+impl TailExpectation for LogNormal {
+    /// Returns the partial expectation `E[X 1{a < X <= b}]` of the
+    /// log-normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(μ + σ^2 / 2) (Φ(z(b)) - Φ(z(a)))  with  z(x) = (ln(x) - μ - σ^2) / σ
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `Φ` is the standard
+    /// normal cdf. Upper tails are differenced as survival functions for
+    /// accuracy.
+    fn partial_expectation(&self, a: f64, b: f64) -> f64 {
+        let (a, b) = (a.max(0.0), b.max(0.0));
+        if a >= b {
+            return 0.0;
+        }
+        let shift = self.location + self.scale * self.scale;
+        let (lower, upper) = (a.ln(), b.ln());
+        let difference = if lower > shift {
+            super::normal::sf_unchecked(lower, shift, self.scale)
+                - super::normal::sf_unchecked(upper, shift, self.scale)
+        } else {
+            super::normal::cdf_unchecked(upper, shift, self.scale)
+                - super::normal::cdf_unchecked(lower, shift, self.scale)
+        };
+        (self.location + self.scale * self.scale / 2.0).exp() * difference
+    }
+
+    /// Returns the mean excess `E[X - u | X > u]` of the log-normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(μ + σ^2 / 2) (1 - Φ(z(u))) / (1 - Φ((ln(u) - μ) / σ)) - u
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale, `Φ` is the standard
+    /// normal cdf and `z(x) = (ln(x) - μ - σ^2) / σ`
+    fn mean_excess(&self, u: f64) -> f64 {
+        let tail = self.sf(u);
+        if tail.is_nan() || tail <= 0.0 {
+            return f64::NAN;
+        }
+        let shift = self.location + self.scale * self.scale;
+        let upper = if u <= 0.0 {
+            1.0
+        } else {
+            super::normal::sf_unchecked(u.ln(), shift, self.scale)
+        };
+        (self.location + self.scale * self.scale / 2.0).exp() * upper / tail - u
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, LogNormal};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(mean: f64, std_dev: f64) -> LogNormal {
         let n = LogNormal::new(mean, std_dev);
@@ -750,4 +977,123 @@ mod tests {
         test::check_continuous_distribution(&try_create(0.0, 0.25), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(0.0, 0.5), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_extreme_tails() {
+        // reference values computed with mpmath
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.ln_cdf((-40.0f64).exp()), -804.60844201375378817, 1e-11);
+        assert_almost_eq!(n.ln_sf(40.0f64.exp()), -804.60844201375378817, 1e-11);
+        assert_almost_eq!(n.ln_sf(f64::MAX), -251903.23366054914921, 1e-9);
+        assert_almost_eq!(n.ln_cdf(f64::MIN_POSITIVE), -250920.22483904544977, 1e-9);
+        assert_almost_eq!(n.ln_cdf(5e-324), -277103.04191384776892, 1e-9);
+        assert_almost_eq!(n.ln_sf((-3.0f64).exp()), -0.0013508099647481937988, 1e-12);
+        assert_eq!(n.ln_sf((-40.0f64).exp()), 0.0);
+        assert_almost_eq!(n.ln_cdf(1.0), -std::f64::consts::LN_2, 1e-15);
+        assert_eq!(n.ln_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(n.ln_sf(0.0), 0.0);
+        assert_eq!(n.ln_cdf(f64::INFINITY), 0.0);
+        assert_eq!(n.ln_sf(f64::INFINITY), f64::NEG_INFINITY);
+        // agrees with the logarithms where those are representable
+        for &x in &[1e-100, 0.01, 0.5, 2.0, 30.0, 1e10] {
+            assert_almost_eq!(n.ln_cdf(x), n.cdf(x).ln(), 1e-10 * n.cdf(x).ln().abs().max(1e-3));
+            assert_almost_eq!(n.ln_sf(x), n.sf(x).ln(), 1e-10 * n.sf(x).ln().abs().max(1e-3));
+        }
+        assert_eq!(n.cdf(f64::INFINITY), 1.0);
+        assert_eq!(n.sf(f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_extreme_densities() {
+        // x * σ overflows for x near f64::MAX and σ > 1; reference values
+        // computed with mpmath
+        let n = try_create(700.0, 10.0);
+        assert_almost_eq!(n.ln_pdf(f64::MAX), -713.4827438773546226, 1e-11);
+        assert_almost_eq!(n.pdf(f64::MAX), 1.37524919964063934e-310, 1e-320);
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.ln_pdf(f64::MAX), -252606.45141257257713, 1e-9);
+        assert_almost_eq!(n.ln_pdf(f64::MIN_POSITIVE), -250205.26541467028899, 1e-9);
+        assert_almost_eq!(n.ln_pdf(5e-324), -276351.98920776747667, 1e-9);
+        let n = try_create(-700.0, 0.5);
+        assert_almost_eq!(n.ln_pdf(f64::MIN_POSITIVE), 567.17093884172312316, 1e-11);
+        assert_almost_eq!(n.pdf(f64::MIN_POSITIVE).ln(), 567.17093884172312316, 1e-11);
+    }
+
+    #[test]
+    fn test_extreme_quantiles() {
+        // reference values computed with mpmath
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.inverse_cdf(1e-300), 8.1404892411001860544e-17, 1e-30);
+        assert_almost_eq!(n.inverse_cdf(1e-310), 4.3968406501138367908e-17, 1e-30);
+        let n = try_create(2.0, 3.0);
+        assert_almost_eq!(n.inverse_cdf(1e-300), 3.9860292726536954012e-48, 1e-60);
+        assert_almost_eq!(n.inverse_cdf(1e-310), 6.2807447289378858155e-49, 1e-61);
+        // round trip through the log-cdf over 300 decades of probability
+        for &(mean, std_dev) in &[(0.0, 1.0), (2.0, 3.0), (-5.0, 0.1)] {
+            let n = try_create(mean, std_dev);
+            for k in 1..=300 {
+                let ln_p = -(k as f64) * std::f64::consts::LN_10;
+                let x = n.inverse_cdf(ln_p.exp());
+                assert!(x > 0.0 && x.is_finite());
+                assert_almost_eq!(n.ln_cdf(x), ln_p, 1e-9 * ln_p.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("scale", 0.0), LogNormal::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(location, scale) in [(0.0, 0.25), (1.0, 0.5), (-2.0, 1.0)].iter() {
+            let n = try_create(location, scale);
+            test::check_moments(&n, 1e-12);
+            test::check_moments_numeric(&n, 4, 1e-8);
+        }
+        // the kurtosis is e^(4σ²) + 2e^(3σ²) + 3e^(2σ²) - 3
+        let n = try_create(0.0, 0.5);
+        let e = |x: f64| x.exp();
+        assert_almost_eq!(n.standardized_moment(4).unwrap(), e(1.0) + 2.0 * e(0.75) + 3.0 * e(0.5) - 3.0, 1e-12);
+        assert_eq!(n.raw_moment(2), Some(e(0.5)));
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        use rand::rngs::StdRng;
+        use rand::distributions::Distribution as _;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(293);
+        let data: Vec<f64> = try_create(1.5, 0.5).sample_iter(&mut rng).take(10000).collect();
+        let n = LogNormal::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.location, 1.5, 0.02);
+        assert_almost_eq!(n.scale, 0.5, 0.02);
+        // the location is the mean of the logarithms
+        let mean = data.iter().map(|x| x.ln()).sum::<f64>() / 10000.0;
+        assert_almost_eq!(n.location, mean, 1e-14);
+        assert!(LogNormal::fit_mle(&[1.0, 0.0]).is_err());
+        assert!(LogNormal::fit_mle(&[1.0, -2.0]).is_err());
+        assert!(LogNormal::fit_mle(&[2.0, 2.0]).is_err());
+        assert!(LogNormal::fit_mle(&[2.0]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        for &(location, scale) in [(0.0, 0.25), (1.0, 0.5), (-2.0, 1.0)].iter() {
+            let n = try_create(location, scale);
+            let median = n.median();
+            test::check_tail_expectation(&n, &[0.0, 0.5 * median, median, 2.0 * median, 5.0 * median], 1e-8);
+            assert_almost_eq!(n.partial_expectation(0.0, f64::INFINITY), n.mean().unwrap(), 1e-14);
+        }
+        // reference values computed with mpmath, to the accuracy of erfc
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.mean_excess(3.0), 2.5866123514053233, 1e-9);
+        assert_almost_eq!(n.partial_expectation(1.0, 3.0), 0.62753907598203868, 1e-9);
+        assert!(n.mean_excess(f64::INFINITY).is_nan());
+    }
 }
@@ -0,0 +1,703 @@
+use crate::distribution::{Continuous, ContinuousCDF, GeneralizedPareto, LMoments, Parameterized};
+use crate::function::root;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the [Lomax](https://en.wikipedia.org/wiki/Lomax_distribution)
+/// distribution, also known as the Pareto type II distribution, a heavy
+/// tailed distribution on `[0, ∞)` often used for insurance claim sizes
+///
+/// The Lomax distribution is the exponential distribution with a rate that
+/// follows a gamma distribution with the same shape and with a rate equal
+/// to the Lomax scale, and it is a
+/// [generalized Pareto](crate::distribution::GeneralizedPareto) distribution
+/// with location `0`, scale `λ / α` and shape `1 / α`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Lomax};
+/// use statrs::statistics::Distribution;
+///
+/// let n = Lomax::new(3.0, 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 1.0);
+/// assert!((n.cdf(3.0) - 0.936).abs() < 1e-15);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Lomax {
+    shape: f64,
+    scale: f64,
+}
+
+impl Lomax {
+    /// Constructs a new Lomax distribution with a shape (α) of `shape` and
+    /// a scale (λ) of `scale`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shape` or `scale` is not finite or is not
+    /// positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let mut result = Lomax::new(3.0, 2.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Lomax::new(0.0, 2.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(shape: f64, scale: f64) -> Result<Lomax> {
+        if !shape.is_finite() || !scale.is_finite() || shape <= 0.0 || scale <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(Lomax { shape, scale })
+        }
+    }
+
+    /// Estimates the shape of a Lomax distribution with a known `scale`
+    /// from the sample `data` by maximum likelihood, as in Hill's estimator
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α = n / Σ ln(1 + x_i / λ)
+    /// ```
+    ///
+    /// where `λ` is the scale and `n` the number of observations
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `scale` is not finite and positive, if `data` is
+    /// empty, if an entry is negative or `NaN`, or if every entry is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let claims = [0.4, 2.9, 0.1, 1.3, 0.7, 8.2, 0.2, 1.1];
+    /// let n = Lomax::fit_mle_with_scale(&claims, 2.0).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// assert!((n.shape() - 1.9555669395697496).abs() < 1e-12);
+    /// ```
+    pub fn fit_mle_with_scale(data: &[f64], scale: f64) -> Result<Lomax> {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        check_fit_data(data)?;
+        let s: f64 = data.iter().map(|&x| (x / scale).ln_1p()).sum();
+        if s <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be all zero"));
+        }
+        Lomax::new(data.len() as f64 / s, scale)
+    }
+
+    /// Estimates a Lomax distribution from the sample `data` by maximum
+    /// likelihood, maximizing over the scale the likelihood profiled with
+    /// the shape of `fit_mle_with_scale`
+    ///
+    /// # Formula
+    ///
+    /// The scale `λ` solves
+    ///
+    /// ```text
+    /// (α(λ) + 1) Σ x_i / (λ + x_i) = n
+    /// ```
+    ///
+    /// with `α(λ) = n / Σ ln(1 + x_i / λ)`, which is then the shape
+    ///
+    /// # Remarks
+    ///
+    /// The likelihood has a finite maximum only for samples with a tail
+    /// heavier than the exponential, roughly when the coefficient of
+    /// variation is above one. Otherwise it keeps increasing towards the
+    /// exponential limit of a large shape and scale with a fixed ratio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not positive or is `NaN` or infinite, as a zero makes the likelihood
+    /// unbounded, or if the likelihood has no maximum at a finite scale
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let claims = [0.4, 2.9, 0.1, 1.3, 0.7, 8.2, 0.2, 1.1, 0.3, 15.6];
+    /// let n = Lomax::fit_mle(&claims).unwrap();
+    /// assert!(n.shape() > 0.0 && n.scale() > 0.0);
+    /// assert!(Lomax::fit_mle(&[1.0, 1.1, 0.9, 1.05]).is_err());
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Lomax> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        check_fit_data(data)?;
+        if data.iter().any(|&x| x == 0.0 || x.is_infinite()) {
+            return Err(StatsError::SpecialCase("data must be positive and finite"));
+        }
+        let n = data.len() as f64;
+        let profile_shape = |scale: f64| n / data.iter().map(|&x| (x / scale).ln_1p()).sum::<f64>();
+        // the derivative of the profile log-likelihood times the scale, as
+        // a function of the log scale; it is positive for small scales
+        let slope = |t: f64| {
+            let scale = t.exp();
+            let weights: f64 = data.iter().map(|&x| x / (scale + x)).sum();
+            (profile_shape(scale) + 1.0) * weights - n
+        };
+        let lowest = data.iter().fold(f64::INFINITY, |acc, &x| acc.min(x)).ln() - 10.0;
+        let highest = data.iter().fold(0.0, |acc: f64, &x| acc.max(x)).ln() + 25.0;
+        let mut lower = lowest;
+        while lower < highest {
+            let upper = lower + f64::consts::LN_2;
+            if slope(upper) < 0.0 {
+                let t = root::brent(slope, lower, upper, 1e-12)?;
+                let scale = t.exp();
+                return Lomax::new(profile_shape(scale), scale);
+            }
+            lower = upper;
+        }
+        Err(StatsError::SpecialCase(
+            "the likelihood has no maximum at a finite scale",
+        ))
+    }
+
+    /// Returns the shape of the Lomax distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let n = Lomax::new(3.0, 2.0).unwrap();
+    /// assert_eq!(n.shape(), 3.0);
+    /// ```
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the scale of the Lomax distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let n = Lomax::new(3.0, 2.0).unwrap();
+    /// assert_eq!(n.scale(), 2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Calculates the hazard rate `pdf(x) / sf(x)` of the Lomax
+    /// distribution at `x`, which decreases over the support
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α / (λ + x)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale, and `0` for `x < 0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Lomax;
+    ///
+    /// let n = Lomax::new(3.0, 2.0).unwrap();
+    /// assert_eq!(n.hazard(0.0), 1.5);
+    /// assert_eq!(n.hazard(4.0), 0.5);
+    /// ```
+    pub fn hazard(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            self.shape / (self.scale + x)
+        }
+    }
+
+    /// Returns the generalized Pareto distribution equal to this one
+    fn generalized_pareto(&self) -> GeneralizedPareto {
+        GeneralizedPareto::new(0.0, self.scale / self.shape, 1.0 / self.shape).unwrap()
+    }
+}
+
+/// Checks that `data` is not empty and has no negative or `NaN` entry
+fn check_fit_data(data: &[f64]) -> Result<()> {
+    if data.is_empty() {
+        Err(StatsError::SpecialCase("data must not be empty"))
+    } else if data.iter().any(|x| x.is_nan() || *x < 0.0) {
+        Err(StatsError::SpecialCase(
+            "data must not be negative or contain NaN",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+impl Parameterized for Lomax {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("shape".into(), self.shape), ("scale".into(), self.scale)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Lomax> {
+        match *values {
+            [shape, scale] => Lomax::new(shape, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for Lomax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Lomax({}, {})", self.shape, self.scale)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Lomax {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.gen())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for Lomax {
+    /// Calculates the cumulative distribution function for the Lomax
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - (1 + x / λ)^(-α)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -(-self.shape * (x / self.scale).ln_1p()).exp_m1()
+        }
+    }
+
+    /// Calculates the survival function for the Lomax distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (1 + x / λ)^(-α)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            (-self.shape * (x / self.scale).ln_1p()).exp()
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Lomax distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ ((1 - p)^(-1 / α) - 1)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale, evaluated with `ln_1p` and
+    /// `exp_m1`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        self.scale * (-(-p).ln_1p() / self.shape).exp_m1()
+    }
+}
+
+impl Min<f64> for Lomax {
+    /// Returns the minimum value in the domain of the Lomax distribution
+    /// representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for Lomax {
+    /// Returns the maximum value in the domain of the Lomax distribution
+    /// representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for Lomax {
+    /// Returns the mean of the Lomax distribution, which exists for `α > 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ / (α - 1)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn mean(&self) -> Option<f64> {
+        if self.shape <= 1.0 {
+            None
+        } else {
+            Some(self.scale / (self.shape - 1.0))
+        }
+    }
+
+    /// Returns the variance of the Lomax distribution, which exists for
+    /// `α > 2`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ^2 α / ((α - 1)^2 (α - 2))
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn variance(&self) -> Option<f64> {
+        if self.shape <= 2.0 {
+            None
+        } else {
+            let a = self.scale / (self.shape - 1.0);
+            Some(a * a * self.shape / (self.shape - 2.0))
+        }
+    }
+
+    /// Returns the entropy of the Lomax distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(λ / α) + 1 / α + 1
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn entropy(&self) -> Option<f64> {
+        Some((self.scale / self.shape).ln() + 1.0 / self.shape + 1.0)
+    }
+
+    /// Returns the skewness of the Lomax distribution, which exists for
+    /// `α > 3`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 (1 + α) / (α - 3) sqrt((α - 2) / α)
+    /// ```
+    ///
+    /// where `α` is the shape
+    fn skewness(&self) -> Option<f64> {
+        if self.shape <= 3.0 {
+            None
+        } else {
+            let a = self.shape;
+            Some(2.0 * (1.0 + a) / (a - 3.0) * ((a - 2.0) / a).sqrt())
+        }
+    }
+}
+
+impl Median<f64> for Lomax {
+    /// Returns the median of the Lomax distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ (2^(1 / α) - 1)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn median(&self) -> f64 {
+        self.scale * (f64::consts::LN_2 / self.shape).exp_m1()
+    }
+}
+
+impl Mode<Option<f64>> for Lomax {
+    /// Returns the mode of the Lomax distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn mode(&self) -> Option<f64> {
+        Some(0.0)
+    }
+}
+
+impl Continuous<f64, f64> for Lomax {
+    /// Calculates the probability density function for the Lomax
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (α / λ) (1 + x / λ)^(-α - 1)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the Lomax
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(α / λ) - (α + 1) ln(1 + x / λ)
+    /// ```
+    ///
+    /// where `α` is the shape and `λ` the scale
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            (self.shape / self.scale).ln() - (self.shape + 1.0) * (x / self.scale).ln_1p()
+        }
+    }
+}
+
+impl LMoments for Lomax {
+    /// Returns the first `order` L-moments of the Lomax distribution, which
+    /// are `NaN` for `α <= 1`
+    ///
+    /// # Formula
+    ///
+    /// The L-moments of the generalized Pareto distribution with location
+    /// `0`, scale `λ / α` and shape `1 / α`, where `α` is the shape and `λ`
+    /// the scale
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        if self.shape <= 1.0 {
+            return vec![f64::NAN; order];
+        }
+        self.generalized_pareto().l_moments(order)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Exp, Gamma};
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(shape: f64, scale: f64; Lomax);
+
+    #[test]
+    fn test_create() {
+        try_create(3.0, 2.0);
+        try_create(0.1, 1e-3);
+        try_create(100.0, 50.0);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0.0, 1.0);
+        bad_create_case(1.0, 0.0);
+        bad_create_case(-1.0, 1.0);
+        bad_create_case(f64::NAN, 1.0);
+        bad_create_case(1.0, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_cdf_pdf() {
+        // reference values from mpmath
+        let cdf = |x: f64| move |n: Lomax| n.cdf(x);
+        let sf = |x: f64| move |n: Lomax| n.sf(x);
+        let pdf = |x: f64| move |n: Lomax| n.pdf(x);
+        test_case_special(3.0, 2.0, 0.936, 1e-15, cdf(3.0));
+        test_case_special(3.0, 2.0, 0.064, 1e-15, sf(3.0));
+        test_case_special(3.0, 2.0, 0.0384, 1e-15, pdf(3.0));
+        test_case_special(2.5, 0.75, 0.91904037165106775, 1e-15, cdf(1.3));
+        test_case_special(2.5, 0.75, 0.080959628348932251, 1e-15, sf(1.3));
+        test_case_special(2.5, 0.75, 0.098731254084063719, 1e-15, pdf(1.3));
+        test_case(3.0, 2.0, 1.5, pdf(0.0));
+        test_case(3.0, 2.0, 0.0, cdf(-1.0));
+        test_case(3.0, 2.0, 1.0, sf(-1.0));
+        test_case(3.0, 2.0, 0.0, pdf(-1.0));
+        test_case(3.0, 2.0, f64::NEG_INFINITY, |n| n.ln_pdf(-1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        let mean = |n: Lomax| n.mean().unwrap();
+        let variance = |n: Lomax| n.variance().unwrap();
+        let skewness = |n: Lomax| n.skewness().unwrap();
+        let entropy = |n: Lomax| n.entropy().unwrap();
+        test_case(5.0, 2.0, 0.5, mean);
+        test_case_special(5.0, 2.0, 5.0 / 12.0, 1e-15, variance);
+        test_case_special(5.0, 2.0, 4.6475800154489003, 1e-14, skewness);
+        test_case_special(5.0, 2.0, 0.28370926812584493, 1e-15, entropy);
+        test_none(1.0, 2.0, |n| n.mean());
+        test_none(2.0, 2.0, |n| n.variance());
+        test_none(3.0, 2.0, |n| n.skewness());
+        // the generalized Pareto form
+        let n = try_create(5.0, 2.0);
+        let gpd = n.generalized_pareto();
+        assert_almost_eq!(n.entropy().unwrap(), gpd.entropy().unwrap(), 1e-15);
+        assert_almost_eq!(n.skewness().unwrap(), gpd.skewness().unwrap(), 1e-14);
+        for &x in &[0.1, 1.0, 7.5] {
+            assert_almost_eq!(n.cdf(x), gpd.cdf(x), 1e-15);
+            assert_almost_eq!(n.ln_pdf(x), gpd.ln_pdf(x), 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_median_mode() {
+        test_case_special(5.0, 2.0, 0.29739670999407001, 1e-15, |n| n.median());
+        assert_eq!(try_create(5.0, 2.0).mode(), Some(0.0));
+        let n = try_create(5.0, 2.0);
+        assert_eq!((n.min(), n.max()), (0.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(
+            &try_create(5.0, 2.0),
+            [4.0e-301, 4.0000000000000002e-17, 0.29739670999407001, 3102.1875282132953],
+        );
+        let n = try_create(2.5, 0.75);
+        assert_eq!(n.inverse_cdf(0.0), 0.0);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        for &p in &[0.01, 0.3, 0.9, 0.999] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_hazard() {
+        let n = try_create(2.5, 0.75);
+        assert_almost_eq!(n.hazard(1.3), 1.2195121951219512, 1e-15);
+        assert_eq!(n.hazard(-1.0), 0.0);
+        // decreasing, and equal to pdf / sf
+        let mut previous = f64::INFINITY;
+        for i in 0..200 {
+            let x = 0.05 * i as f64;
+            let h = n.hazard(x);
+            assert!(h < previous);
+            assert_almost_eq!(h, n.pdf(x) / n.sf(x), 1e-13 * h);
+            previous = h;
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(3.0, 2.0), 0.0, 1000.0);
+        test::check_continuous_distribution(&try_create(8.0, 0.5), 0.0, 50.0);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        let n = try_create(5.0, 2.0);
+        let (closed, numeric) = (n.l_moments(4), l_moments(&n, 4, &[]));
+        for r in 0..4 {
+            assert_almost_eq!(closed[r], numeric[r], 1e-8);
+        }
+        assert_eq!(closed[0], 0.5);
+        assert!(try_create(1.0, 2.0).l_moments(2).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_exponential_gamma_mixture() {
+        // an exponential with a Gamma(α, λ) rate is Lomax(α, λ)
+        let mut rng = StdRng::seed_from_u64(2822);
+        let (shape, scale) = (2.5, 3.0);
+        let gamma = Gamma::new(shape, scale).unwrap();
+        let n = 100_000;
+        let mut data: Vec<f64> = (0..n)
+            .map(|_| Exp::new(gamma.sample(&mut rng)).unwrap().sample(&mut rng))
+            .collect();
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let lomax = try_create(shape, scale);
+        let distance = data.iter().enumerate().fold(0.0, |acc: f64, (i, &x)| {
+            let p = lomax.cdf(x);
+            acc.max(p - i as f64 / n as f64).max((i + 1) as f64 / n as f64 - p)
+        });
+        // the 0.1% critical value of the Kolmogorov-Smirnov distance
+        assert!(distance < 1.95 / (n as f64).sqrt());
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let mut rng = StdRng::seed_from_u64(2823);
+        for &(shape, scale) in &[(3.0, 2.0), (1.5, 10.0)] {
+            let n = try_create(shape, scale);
+            let data: Vec<f64> = (0..20_000).map(|_| n.sample(&mut rng)).collect();
+            let fit = Lomax::fit_mle(&data).unwrap();
+            assert_almost_eq!(fit.shape(), shape, 0.1 * shape);
+            assert_almost_eq!(fit.scale(), scale, 0.1 * scale);
+            let fit = Lomax::fit_mle_with_scale(&data, scale).unwrap();
+            assert_almost_eq!(fit.shape(), shape, 0.05 * shape);
+            // the fitted parameters maximize the likelihood
+            let fit = Lomax::fit_mle(&data).unwrap();
+            let ln_likelihood = |d: Lomax| data.iter().map(|&x| d.ln_pdf(x)).sum::<f64>();
+            let best = ln_likelihood(fit);
+            for &(da, dl) in &[(1.01, 1.0), (0.99, 1.0), (1.0, 1.01), (1.0, 0.99), (1.01, 1.01)] {
+                let other = try_create(fit.shape() * da, fit.scale() * dl);
+                assert!(ln_likelihood(other) < best);
+            }
+        }
+        let claims = [0.4, 2.9, 0.1, 1.3, 0.7, 8.2, 0.2, 1.1];
+        let fit = Lomax::fit_mle_with_scale(&claims, 2.0).unwrap();
+        let s: f64 = claims.iter().map(|x| (x / 2.0f64).ln_1p()).sum();
+        assert_almost_eq!(fit.shape(), 8.0 / s, 1e-15);
+    }
+
+    #[test]
+    fn test_fit_mle_bad_data() {
+        assert!(Lomax::fit_mle(&[1.0]).is_err());
+        assert!(Lomax::fit_mle(&[1.0, 0.0, 3.0]).is_err());
+        assert!(Lomax::fit_mle(&[1.0, -2.0, 3.0]).is_err());
+        assert!(Lomax::fit_mle(&[1.0, f64::NAN, 3.0]).is_err());
+        // lighter tailed than the exponential
+        assert!(Lomax::fit_mle(&[1.0, 1.1, 0.9, 1.05]).is_err());
+        assert!(Lomax::fit_mle_with_scale(&[], 1.0).is_err());
+        assert!(Lomax::fit_mle_with_scale(&[0.0, 0.0], 1.0).is_err());
+        assert!(Lomax::fit_mle_with_scale(&[1.0], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("shape", 0.0), Lomax::new(0.0, 2.0));
+    }
+}
@@ -0,0 +1,850 @@
+use crate::distribution::{internal, Continuous, ContinuousCDF, Parameterized};
+use crate::function::integrate::integrate;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use rand::distributions::Open01;
+use rand::Rng;
+use std::f64;
+
+/// Number of interior probabilities on which the monotonicity of the
+/// quantile function is checked, in addition to the logarithmically spaced
+/// tail points
+const FEASIBILITY_GRID: usize = 2000;
+
+/// Absolute tolerance of the numerical integrals for the moments and the
+/// entropy
+const TOLERANCE: f64 = 1e-10;
+
+/// Support of a metalog distribution, selecting the transform applied to
+/// the unbounded metalog quantile function `M(y)`
+///
+/// # Formula
+///
+/// ```text
+/// Unbounded:  Q(y) = M(y)
+/// Lower(l):   Q(y) = l + e^M(y)
+/// Upper(u):   Q(y) = u - e^(-M(y))
+/// Both(l, u): Q(y) = (l + u e^M(y)) / (1 + e^M(y))
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MetalogBounds {
+    /// Supported on the whole real line
+    Unbounded,
+    /// Supported on `(l, ∞)`
+    Lower(f64),
+    /// Supported on `(-∞, u)`
+    Upper(f64),
+    /// Supported on `(l, u)`
+    Both(f64, f64),
+}
+
+/// Implements the [metalog](https://en.wikipedia.org/wiki/Metalog_distribution)
+/// distribution, a quantile-parameterized distribution whose quantile
+/// function is linear in its coefficients and can therefore be fitted to
+/// `(probability, quantile)` pairs by least squares
+///
+/// # Formula
+///
+/// With `L = ln(y / (1 - y))` and `c = y - 1/2`, the unbounded quantile
+/// function with `k` terms is
+///
+/// ```text
+/// M(y) = a1 + a2 L + a3 c L + a4 c + a5 c^2 + a6 c^2 L + a7 c^3 + a8 c^3 L + ...
+/// ```
+///
+/// and the bounded variants apply the transforms of `MetalogBounds`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Metalog};
+///
+/// let pairs = [(0.1, -1.0), (0.5, 0.5), (0.9, 3.0)];
+/// let n = Metalog::fit(&pairs, 3).unwrap();
+/// assert!((n.quantile(0.5) - 0.5).abs() < 1e-12);
+/// assert!((n.cdf(3.0) - 0.9).abs() < 1e-12);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Metalog {
+    coefficients: Vec<f64>,
+    bounds: MetalogBounds,
+}
+
+impl Metalog {
+    /// Fits an unbounded metalog distribution with `n_terms` terms to the
+    /// `(probability, quantile)` pairs `quantile_pairs` by linear least
+    /// squares
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n_terms < 2`, if there are fewer pairs than
+    /// terms, if a probability is not in `(0, 1)` or a quantile not finite,
+    /// or if the fitted quantile function is not increasing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Metalog;
+    ///
+    /// let pairs = [(0.1, -1.0), (0.5, 0.5), (0.9, 3.0)];
+    /// assert!(Metalog::fit(&pairs, 3).is_ok());
+    /// assert!(Metalog::fit(&pairs, 4).is_err());
+    /// ```
+    pub fn fit(quantile_pairs: &[(f64, f64)], n_terms: usize) -> Result<Metalog> {
+        Metalog::fit_with_bounds(quantile_pairs, n_terms, MetalogBounds::Unbounded)
+    }
+
+    /// Fits a metalog distribution with `n_terms` terms and support given by
+    /// `bounds` to the `(probability, quantile)` pairs `quantile_pairs`,
+    /// solving the least squares problem on the log or logit transformed
+    /// quantiles
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds are not finite or not ordered, if a
+    /// quantile lies outside the open support, and in the cases described
+    /// for `fit`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Metalog, MetalogBounds};
+    /// use statrs::statistics::Min;
+    ///
+    /// let pairs = [(0.1, 1.0), (0.5, 2.0), (0.9, 5.0)];
+    /// let n = Metalog::fit_with_bounds(&pairs, 3, MetalogBounds::Lower(0.0)).unwrap();
+    /// assert_eq!(n.min(), 0.0);
+    /// assert!(Metalog::fit_with_bounds(&pairs, 3, MetalogBounds::Upper(4.0)).is_err());
+    /// ```
+    pub fn fit_with_bounds(
+        quantile_pairs: &[(f64, f64)],
+        n_terms: usize,
+        bounds: MetalogBounds,
+    ) -> Result<Metalog> {
+        check_bounds(bounds)?;
+        if n_terms < 2 {
+            return Err(StatsError::ArgGte("n_terms", 2.0));
+        }
+        if quantile_pairs.len() < n_terms {
+            return Err(StatsError::ArgLteArg("n_terms", "quantile_pairs.len()"));
+        }
+        let mut basis = DMatrix::zeros(quantile_pairs.len(), n_terms);
+        let mut targets = DVector::zeros(quantile_pairs.len());
+        for (i, &(p, x)) in quantile_pairs.iter().enumerate() {
+            if p.is_nan() || p <= 0.0 || p >= 1.0 {
+                return Err(StatsError::ArgIntervalExcl("p", 0.0, 1.0));
+            }
+            if !x.is_finite() {
+                return Err(StatsError::ArgFinite("x"));
+            }
+            let z = to_unbounded(bounds, x);
+            if !z.is_finite() {
+                return Err(StatsError::BadParams);
+            }
+            let (y, l) = (p, (p / (1.0 - p)).ln());
+            for j in 0..n_terms {
+                basis[(i, j)] = basis_term(j + 1, y, l);
+            }
+            targets[i] = z;
+        }
+        let coefficients = basis
+            .svd(true, true)
+            .solve(&targets, f64::EPSILON)
+            .map_err(|_| StatsError::ComputationFailedToConverge)?;
+        Metalog::from_coefficients(coefficients.iter().cloned().collect(), bounds)
+    }
+
+    /// Constructs a metalog distribution from its coefficients `a1, a2, ...`
+    /// and support `bounds`
+    ///
+    /// # Remarks
+    ///
+    /// The quantile function is checked to be increasing on a grid of
+    /// probabilities that reaches `1e-12` into both tails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two coefficients, if any is
+    /// not finite, if the bounds are not finite or not ordered, or if the
+    /// quantile function is not increasing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Metalog, MetalogBounds};
+    ///
+    /// let mut result = Metalog::from_coefficients(vec![0.0, 1.0, 0.5], MetalogBounds::Unbounded);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Metalog::from_coefficients(vec![0.0, 1.0, 5.0], MetalogBounds::Unbounded);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn from_coefficients(coefficients: Vec<f64>, bounds: MetalogBounds) -> Result<Metalog> {
+        check_bounds(bounds)?;
+        if coefficients.len() < 2 {
+            return Err(StatsError::ArgGte("coefficients.len()", 2.0));
+        }
+        if coefficients.iter().any(|a| !a.is_finite()) {
+            return Err(StatsError::ArgFinite("coefficients"));
+        }
+        let n = Metalog {
+            coefficients,
+            bounds,
+        };
+        if !n.is_feasible() {
+            return Err(StatsError::SpecialCase(
+                "metalog quantile function is not increasing",
+            ));
+        }
+        Ok(n)
+    }
+
+    /// Returns the coefficients of the metalog distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Metalog, MetalogBounds};
+    ///
+    /// let n = Metalog::from_coefficients(vec![1.0, 2.0], MetalogBounds::Unbounded).unwrap();
+    /// assert_eq!(n.coefficients(), &[1.0, 2.0]);
+    /// ```
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Returns the support of the metalog distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Metalog, MetalogBounds};
+    ///
+    /// let n = Metalog::from_coefficients(vec![1.0, 2.0], MetalogBounds::Lower(0.0)).unwrap();
+    /// assert_eq!(n.bounds(), MetalogBounds::Lower(0.0));
+    /// ```
+    pub fn bounds(&self) -> MetalogBounds {
+        self.bounds
+    }
+
+    /// Evaluates the quantile function of the metalog distribution at `p`
+    /// in closed form
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Metalog, MetalogBounds};
+    ///
+    /// // two terms give the logistic distribution
+    /// let n = Metalog::from_coefficients(vec![1.0, 2.0], MetalogBounds::Unbounded).unwrap();
+    /// assert!((n.quantile(0.75) - (1.0 + 2.0 * 3f64.ln())).abs() < 1e-14);
+    /// ```
+    pub fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            return self.min();
+        }
+        if p == 1.0 {
+            return self.max();
+        }
+        from_unbounded(
+            self.bounds,
+            self.unbounded_quantile(p, (p / (1.0 - p)).ln()),
+        )
+    }
+
+    /// Evaluates the unbounded quantile function `M` at probability `y`
+    /// with log-odds `l`
+    fn unbounded_quantile(&self, y: f64, l: f64) -> f64 {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, a)| a * basis_term(j + 1, y, l))
+            .sum()
+    }
+
+    /// Returns `M` and `dM/dy` at log-odds `t`
+    fn eval_logit(&self, t: f64) -> (f64, f64) {
+        let y = 1.0 / (1.0 + (-t).exp());
+        let e = (-t.abs()).exp();
+        // y (1 - y) without cancellation in either tail
+        let w = e / ((1.0 + e) * (1.0 + e));
+        let c = y - 0.5;
+        let mut value = 0.0;
+        let mut derivative = 0.0;
+        for (j, a) in self.coefficients.iter().enumerate() {
+            value += a * basis_term(j + 1, y, t);
+            derivative += a * basis_derivative(j + 1, c, t, w);
+        }
+        (value, derivative)
+    }
+
+    /// Returns the log-odds of the probability at which the quantile
+    /// function reaches `x`, found by bisection on the increasing `M`
+    fn logit_cdf(&self, x: f64) -> f64 {
+        let z = to_unbounded(self.bounds, x);
+        let (mut lo, mut hi) = (-700.0f64, 700.0f64);
+        for _ in 0..200 {
+            let mid = 0.5 * (lo + hi);
+            if mid == lo || mid == hi || hi - lo < 1e-15 * (1.0 + mid.abs()) {
+                break;
+            }
+            if self.eval_logit(mid).0 < z {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// Returns the logarithm of the quantile density `dQ/dy` at log-odds
+    /// `t`
+    fn ln_quantile_density(&self, t: f64) -> f64 {
+        let (m, dm) = self.eval_logit(t);
+        let ln_dm = dm.ln();
+        match self.bounds {
+            MetalogBounds::Unbounded => ln_dm,
+            MetalogBounds::Lower(_) => m + ln_dm,
+            MetalogBounds::Upper(_) => -m + ln_dm,
+            MetalogBounds::Both(l, u) => {
+                (u - l).ln() - m.abs() - 2.0 * (-m.abs()).exp().ln_1p() + ln_dm
+            }
+        }
+    }
+
+    /// Checks that `dM/dy > 0` on the feasibility grid
+    fn is_feasible(&self) -> bool {
+        let interior = (1..FEASIBILITY_GRID).map(|i| {
+            let y = i as f64 / FEASIBILITY_GRID as f64;
+            (y / (1.0 - y)).ln()
+        });
+        let tails = (3..=12).flat_map(|k| {
+            let t = (10f64.powi(-k) / (1.0 - 10f64.powi(-k))).ln();
+            vec![t, -t]
+        });
+        interior.chain(tails).all(|t| self.eval_logit(t).1 > 0.0)
+    }
+
+    /// Integrates `g(Q(y))` over `y ∈ (0, 1)` on the log-odds scale
+    fn expect<F: Fn(f64) -> f64>(&self, g: F) -> f64 {
+        let f = |t: f64| {
+            let e = (-t.abs()).exp();
+            let w = e / ((1.0 + e) * (1.0 + e));
+            if w == 0.0 {
+                return 0.0;
+            }
+            g(from_unbounded(self.bounds, self.eval_logit(t).0)) * w
+        };
+        integrate(f, f64::NEG_INFINITY, f64::INFINITY, TOLERANCE)
+    }
+
+    /// Returns the coefficients of `L` in `M` as `y → 0` and `y → 1`, which
+    /// govern how heavy the tails of the bounded variants are
+    fn tail_exponents(&self) -> (f64, f64) {
+        let at = |c: f64| {
+            self.coefficients
+                .iter()
+                .enumerate()
+                .map(|(j, a)| match j + 1 {
+                    2 => *a,
+                    3 => a * c,
+                    k if k >= 6 && k % 2 == 0 => a * c.powi(k as i32 / 2 - 1),
+                    _ => 0.0,
+                })
+                .sum::<f64>()
+        };
+        (at(-0.5), at(0.5))
+    }
+
+    /// Returns whether the moment of order `order` is finite
+    fn has_moment(&self, order: f64) -> bool {
+        let (lower, upper) = self.tail_exponents();
+        match self.bounds {
+            MetalogBounds::Unbounded | MetalogBounds::Both(..) => true,
+            MetalogBounds::Lower(_) => upper * order < 1.0,
+            MetalogBounds::Upper(_) => lower * order < 1.0,
+        }
+    }
+}
+
+/// Checks that the bounds are finite and ordered
+fn check_bounds(bounds: MetalogBounds) -> Result<()> {
+    match bounds {
+        MetalogBounds::Unbounded => Ok(()),
+        MetalogBounds::Lower(b) | MetalogBounds::Upper(b) if !b.is_finite() => {
+            Err(StatsError::ArgFinite("bound"))
+        }
+        MetalogBounds::Both(l, u) if !l.is_finite() || !u.is_finite() => {
+            Err(StatsError::ArgFinite("bound"))
+        }
+        MetalogBounds::Both(l, u) if l >= u => Err(StatsError::ArgLtArg("lower", "upper")),
+        _ => Ok(()),
+    }
+}
+
+/// Maps `x` to the scale on which the unbounded metalog is fitted, giving a
+/// non-finite value outside the support
+fn to_unbounded(bounds: MetalogBounds, x: f64) -> f64 {
+    match bounds {
+        MetalogBounds::Unbounded => x,
+        MetalogBounds::Lower(l) => (x - l).ln(),
+        MetalogBounds::Upper(u) => -(u - x).ln(),
+        MetalogBounds::Both(l, u) => ((x - l) / (u - x)).ln(),
+    }
+}
+
+/// Inverse of `to_unbounded`
+fn from_unbounded(bounds: MetalogBounds, m: f64) -> f64 {
+    match bounds {
+        MetalogBounds::Unbounded => m,
+        MetalogBounds::Lower(l) => l + m.exp(),
+        MetalogBounds::Upper(u) => u - (-m).exp(),
+        MetalogBounds::Both(l, u) => {
+            if m > 0.0 {
+                let e = (-m).exp();
+                (l * e + u) / (1.0 + e)
+            } else {
+                let e = m.exp();
+                (l + u * e) / (1.0 + e)
+            }
+        }
+    }
+}
+
+/// Returns the `j`-th basis function at probability `y` with log-odds `l`
+fn basis_term(j: usize, y: f64, l: f64) -> f64 {
+    let c = y - 0.5;
+    match j {
+        1 => 1.0,
+        2 => l,
+        3 => c * l,
+        4 => c,
+        _ if j % 2 == 1 => c.powi((j as i32 - 1) / 2),
+        _ => c.powi(j as i32 / 2 - 1) * l,
+    }
+}
+
+/// Returns the derivative with respect to `y` of the `j`-th basis function,
+/// given `c = y - 1/2`, the log-odds `l` and `w = y (1 - y)`
+fn basis_derivative(j: usize, c: f64, l: f64, w: f64) -> f64 {
+    match j {
+        1 => 0.0,
+        2 => 1.0 / w,
+        3 => l + c / w,
+        4 => 1.0,
+        _ if j % 2 == 1 => {
+            let e = (j as i32 - 1) / 2;
+            e as f64 * c.powi(e - 1)
+        }
+        _ => {
+            let e = j as i32 / 2 - 1;
+            e as f64 * c.powi(e - 1) * l + c.powi(e) / w
+        }
+    }
+}
+
+impl Parameterized for Metalog {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let mut parameters: Vec<(String, f64)> = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| (format!("coefficients[{}]", i), a))
+            .collect();
+        match self.bounds {
+            MetalogBounds::Unbounded => {}
+            MetalogBounds::Lower(lower) => parameters.push(("lower".into(), lower)),
+            MetalogBounds::Upper(upper) => parameters.push(("upper".into(), upper)),
+            MetalogBounds::Both(lower, upper) => {
+                parameters.push(("lower".into(), lower));
+                parameters.push(("upper".into(), upper));
+            }
+        }
+        parameters
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Metalog> {
+        let values = internal::parameter_values(values, self.n_parameters())?;
+        let (coefficients, bounds) = values.split_at(self.coefficients.len());
+        let bounds = match self.bounds {
+            MetalogBounds::Unbounded => MetalogBounds::Unbounded,
+            MetalogBounds::Lower(_) => MetalogBounds::Lower(bounds[0]),
+            MetalogBounds::Upper(_) => MetalogBounds::Upper(bounds[0]),
+            MetalogBounds::Both(..) => MetalogBounds::Both(bounds[0], bounds[1]),
+        };
+        Metalog::from_coefficients(coefficients.to_vec(), bounds)
+    }
+}
+
+impl std::fmt::Display for Metalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Metalog({:?}, {:?})", self.coefficients, self.bounds)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Metalog {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Open01);
+        self.quantile(u)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for Metalog {
+    /// Calculates the cumulative distribution function for the metalog
+    /// distribution at `x` by inverting the quantile function with
+    /// bisection
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// y such that Q(y) = x
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if x <= self.min() {
+            return 0.0;
+        }
+        if x >= self.max() {
+            return 1.0;
+        }
+        1.0 / (1.0 + (-self.logit_cdf(x)).exp())
+    }
+
+    /// Calculates the survival function for the metalog distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - y such that Q(y) = x
+    /// ```
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if x <= self.min() {
+            return 1.0;
+        }
+        if x >= self.max() {
+            return 0.0;
+        }
+        1.0 / (1.0 + self.logit_cdf(x).exp())
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// metalog distribution at `p`, see `quantile`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.quantile(p)
+    }
+}
+
+impl Min<f64> for Metalog {
+    /// Returns the minimum value in the domain of the metalog distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// l for lower bounded variants, otherwise -∞
+    /// ```
+    fn min(&self) -> f64 {
+        match self.bounds {
+            MetalogBounds::Lower(l) | MetalogBounds::Both(l, _) => l,
+            _ => f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Max<f64> for Metalog {
+    /// Returns the maximum value in the domain of the metalog distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// u for upper bounded variants, otherwise ∞
+    /// ```
+    fn max(&self) -> f64 {
+        match self.bounds {
+            MetalogBounds::Upper(u) | MetalogBounds::Both(_, u) => u,
+            _ => f64::INFINITY,
+        }
+    }
+}
+
+impl Distribution<f64> for Metalog {
+    /// Returns the mean of the metalog distribution, computed by numerical
+    /// integration of the quantile function
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` for the semi-bounded variants when the coefficient of
+    /// `L` in the unbounded tail is at least `1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ Q(y) dy over (0, 1)
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        if !self.has_moment(1.0) {
+            return None;
+        }
+        Some(self.expect(|x| x))
+    }
+
+    /// Returns the variance of the metalog distribution, computed by
+    /// numerical integration of the quantile function
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` for the semi-bounded variants when the coefficient of
+    /// `L` in the unbounded tail is at least `1/2`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ (Q(y) - μ)^2 dy over (0, 1)
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        if !self.has_moment(2.0) {
+            return None;
+        }
+        let mean = self.mean()?;
+        Some(self.expect(|x| (x - mean) * (x - mean)))
+    }
+
+    /// Returns the entropy of the metalog distribution, computed by
+    /// numerical integration of the quantile density
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ ln(dQ/dy) dy over (0, 1)
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        let f = |t: f64| {
+            let e = (-t.abs()).exp();
+            let w = e / ((1.0 + e) * (1.0 + e));
+            if w == 0.0 {
+                return 0.0;
+            }
+            self.ln_quantile_density(t) * w
+        };
+        Some(integrate(f, f64::NEG_INFINITY, f64::INFINITY, TOLERANCE))
+    }
+}
+
+impl Median<f64> for Metalog {
+    /// Returns the median of the metalog distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Q(1/2)
+    /// ```
+    fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for Metalog {
+    /// Calculates the probability density function for the metalog
+    /// distribution at `x` as the reciprocal of the quantile density
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 / Q'(y) where y = F(x)
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the metalog
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -ln(Q'(y)) where y = F(x)
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x <= self.min() || x >= self.max() {
+            return f64::NEG_INFINITY;
+        }
+        let density = -self.ln_quantile_density(self.logit_cdf(x));
+        if density.is_nan() {
+            f64::NEG_INFINITY
+        } else {
+            density
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::Normal;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn normal_pairs() -> Vec<(f64, f64)> {
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        (1..100).map(|i| {
+            let p = i as f64 / 100.0;
+            (p, normal.inverse_cdf(p))
+        }).collect()
+    }
+
+    #[test]
+    fn test_create() {
+        let pairs = normal_pairs();
+        assert!(Metalog::fit(&pairs, 2).is_ok());
+        assert!(Metalog::fit(&pairs, 1).is_err());
+        assert!(Metalog::fit(&pairs[..3], 4).is_err());
+        assert!(Metalog::fit(&[(0.0, 1.0), (0.5, 2.0)], 2).is_err());
+        assert!(Metalog::fit(&[(0.2, f64::NAN), (0.5, 2.0)], 2).is_err());
+        assert!(Metalog::fit_with_bounds(&pairs, 3, MetalogBounds::Lower(0.0)).is_err());
+        assert!(Metalog::fit_with_bounds(&pairs, 3, MetalogBounds::Both(2.0, 1.0)).is_err());
+        assert!(Metalog::from_coefficients(vec![0.0], MetalogBounds::Unbounded).is_err());
+        assert!(Metalog::from_coefficients(vec![0.0, f64::INFINITY], MetalogBounds::Unbounded).is_err());
+        assert!(Metalog::from_coefficients(vec![0.0, 1.0], MetalogBounds::Upper(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_fit_normal() {
+        // the odd and even parts of the basis are needed together, so 11
+        // terms reach a maximum error of 1.1e-4 on the fitted probabilities
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        let n = Metalog::fit(&normal_pairs(), 11).unwrap();
+        for i in 1..100 {
+            let p = i as f64 / 100.0;
+            assert_almost_eq!(n.quantile(p), normal.inverse_cdf(p), 1e-3);
+        }
+        assert_almost_eq!(n.mean().unwrap(), 3.0, 1e-3);
+        assert_almost_eq!(n.variance().unwrap(), 4.0, 1e-2);
+        assert_almost_eq!(n.median(), 3.0, 1e-3);
+        assert_almost_eq!(n.pdf(3.0), normal.pdf(3.0), 1e-3);
+    }
+
+    #[test]
+    fn test_feasibility() {
+        // with three terms the quantile function is increasing only if
+        // |a3| / a2 < 1.66711
+        assert!(Metalog::from_coefficients(vec![0.0, 1.0, 1.6], MetalogBounds::Unbounded).is_ok());
+        let result = Metalog::from_coefficients(vec![0.0, 1.0, 5.0], MetalogBounds::Unbounded);
+        assert_eq!(result, Err(StatsError::SpecialCase("metalog quantile function is not increasing")));
+        assert!(Metalog::from_coefficients(vec![0.0, 1.0, -1.7], MetalogBounds::Unbounded).is_err());
+        assert!(Metalog::from_coefficients(vec![0.0, -1.0], MetalogBounds::Lower(0.0)).is_err());
+        // quantiles that cannot be matched by an increasing function
+        let pairs = [(0.1, 0.0), (0.3, 5.0), (0.5, 0.0), (0.7, 5.0), (0.9, 0.0)];
+        assert!(Metalog::fit(&pairs, 5).is_err());
+    }
+
+    #[test]
+    fn test_logistic() {
+        let n = Metalog::from_coefficients(vec![1.0, 2.0], MetalogBounds::Unbounded).unwrap();
+        for &x in &[-10.0, -1.0, 0.0, 1.0, 2.5, 30.0] {
+            let z: f64 = (x - 1.0) / 2.0;
+            let cdf = 1.0 / (1.0 + (-z).exp());
+            assert_almost_eq!(n.cdf(x), cdf, 1e-14);
+            assert_almost_eq!(n.sf(x), 1.0 - cdf, 1e-14);
+            assert_almost_eq!(n.pdf(x), cdf * (1.0 - cdf) / 2.0, 1e-14);
+        }
+        assert_almost_eq!(n.mean().unwrap(), 1.0, 1e-9);
+        assert_almost_eq!(n.variance().unwrap(), 4.0 * f64::consts::PI * f64::consts::PI / 3.0, 1e-8);
+        assert_almost_eq!(n.entropy().unwrap(), 2f64.ln() + 2.0, 1e-9);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bounds = [
+            MetalogBounds::Unbounded,
+            MetalogBounds::Lower(-5.0),
+            MetalogBounds::Upper(20.0),
+            MetalogBounds::Both(-5.0, 20.0),
+        ];
+        for &b in &bounds {
+            let n = Metalog::fit_with_bounds(&normal_pairs(), 7, b).unwrap();
+            for i in 1..1000 {
+                let p = i as f64 / 1000.0;
+                let x = n.quantile(p);
+                assert!(x > n.min() && x < n.max());
+                assert_almost_eq!(n.cdf(x), p, 1e-12);
+                assert_almost_eq!(n.sf(x), 1.0 - p, 1e-12);
+            }
+            for &x in &[0.0, 1.0, 3.0, 7.5] {
+                assert_almost_eq!(n.quantile(n.cdf(x)), x, 1e-9);
+            }
+            assert_eq!(n.quantile(0.0), n.min());
+            assert_eq!(n.quantile(1.0), n.max());
+        }
+    }
+
+    #[test]
+    fn test_bounded() {
+        let pairs = [(0.1, 1.0), (0.5, 2.0), (0.9, 5.0)];
+        let n = Metalog::fit_with_bounds(&pairs, 3, MetalogBounds::Both(0.0, 10.0)).unwrap();
+        for &(p, x) in &pairs {
+            assert_almost_eq!(n.quantile(p), x, 1e-12);
+        }
+        assert_eq!(n.cdf(-1.0), 0.0);
+        assert_eq!(n.cdf(10.0), 1.0);
+        assert_eq!(n.pdf(11.0), 0.0);
+        // a two-term lower bounded metalog is log-logistic, whose mean is
+        // finite only when a2 < 1
+        let heavy = Metalog::from_coefficients(vec![0.0, 1.5], MetalogBounds::Lower(0.0)).unwrap();
+        assert_eq!(heavy.mean(), None);
+        let light = Metalog::from_coefficients(vec![0.0, 0.25], MetalogBounds::Lower(0.0)).unwrap();
+        let s = 0.25 * f64::consts::PI;
+        assert_almost_eq!(light.mean().unwrap(), s / s.sin(), 1e-9);
+        assert_almost_eq!(light.variance().unwrap(), 2.0 * s / (2.0 * s).sin() - (s / s.sin()).powi(2), 1e-8);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = Metalog::fit(&normal_pairs(), 5).unwrap();
+        let count = 20000;
+        let below = (0..count)
+            .filter(|_| rand::distributions::Distribution::sample(&n, &mut rng) < n.quantile(0.3))
+            .count();
+        assert!((below as f64 / count as f64 - 0.3).abs() < 0.015);
+    }
+
+    #[test]
+    fn test_continuous() {
+        let n = Metalog::fit(&normal_pairs(), 5).unwrap();
+        test::check_continuous_distribution(&n, -10.0, 16.0);
+        let b = Metalog::fit_with_bounds(&normal_pairs(), 5, MetalogBounds::Both(-4.0, 12.0)).unwrap();
+        test::check_continuous_distribution(&b, -4.0, 12.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = Metalog::from_coefficients(vec![0.0, 1.0, 0.5], MetalogBounds::Both(-5.0, 10.0)).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(-1.0), d.pdf(2.0)]);
+        let names: Vec<String> = n.parameters().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["coefficients[0]", "coefficients[1]", "coefficients[2]", "lower", "upper"]);
+        assert_eq!(n.set_parameter("coefficients[2]", 5.0), Metalog::from_coefficients(vec![0.0, 1.0, 5.0], MetalogBounds::Both(-5.0, 10.0)));
+        assert_eq!(n.set_parameter("upper", -6.0), Metalog::from_coefficients(vec![0.0, 1.0, 0.5], MetalogBounds::Both(-5.0, -6.0)));
+        let n = Metalog::from_coefficients(vec![0.0, 1.0], MetalogBounds::Unbounded).unwrap();
+        test::check_parameters(&n, |d| d.pdf(0.5));
+        assert_eq!(n.n_parameters(), 2);
+    }
+}
@@ -4,6 +4,8 @@
 use super::statistics::{Max, Min};
 use ::num_traits::{Bounded, Float, Num};
 use num_traits::{NumAssign, NumAssignOps, NumAssignRef};
+use rand::distributions::Open01;
+use rand::Rng;
 
 pub use self::bernoulli::Bernoulli;
 pub use self::beta::Beta;
@@ -12,26 +14,51 @@ pub use self::categorical::Categorical;
 pub use self::cauchy::Cauchy;
 pub use self::chi::Chi;
 pub use self::chi_squared::ChiSquared;
+pub use self::circular_uniform::CircularUniform;
+pub use self::convolution::{
+    convolve_n, discrete_convolve, FiniteDiscrete, MAX_CONVOLUTION_SUPPORT,
+};
 pub use self::dirac::Dirac;
 pub use self::dirichlet::Dirichlet;
 pub use self::discrete_uniform::DiscreteUniform;
+pub use self::discretize::{Discretize, DiscretizeRule};
 pub use self::empirical::Empirical;
 pub use self::erlang::Erlang;
 pub use self::exponential::Exp;
 pub use self::fisher_snedecor::FisherSnedecor;
+pub use self::fit::{auto_fit, Family, Fit, FitReport, Fitted};
 pub use self::gamma::Gamma;
+pub use self::generalized_extreme_value::GeneralizedExtremeValue;
+pub use self::generalized_pareto::GeneralizedPareto;
 pub use self::geometric::Geometric;
 pub use self::hypergeometric::Hypergeometric;
 pub use self::inverse_gamma::InverseGamma;
 pub use self::laplace::Laplace;
 pub use self::log_normal::LogNormal;
+pub use self::lomax::Lomax;
+pub use self::metalog::{Metalog, MetalogBounds};
 pub use self::multinomial::Multinomial;
 pub use self::multivariate_normal::MultivariateNormal;
 pub use self::negative_binomial::NegativeBinomial;
+pub use self::noncentral_fisher_snedecor::NoncentralFisherSnedecor;
+pub use self::noncentral_students_t::NoncentralStudentsT;
 pub use self::normal::Normal;
+pub use self::numeric_cdf::{CacheStats, NumericCdf};
 pub use self::pareto::Pareto;
+pub use self::phase_type::PhaseType;
 pub use self::poisson::Poisson;
+pub use self::poisson_binomial::PoissonBinomial;
+pub use self::projected_normal::ProjectedNormal;
+pub use self::records::Records;
+pub use self::runs::Runs;
+pub use self::sample_range::SampleRange;
+pub use self::shifted_exponential::ShiftedExponential;
+pub use self::stick_breaking::{
+    dirichlet_process_sample, DirichletProcessSample, StickBreaking, StickBreakingWeights,
+};
+pub use self::studentized_range::StudentizedRange;
 pub use self::students_t::StudentsT;
+pub use self::transform::{ExpOf, LogOf, Scaled, Shifted};
 pub use self::triangular::Triangular;
 pub use self::uniform::Uniform;
 pub use self::weibull::Weibull;
@@ -43,35 +70,59 @@ mod categorical;
 mod cauchy;
 mod chi;
 mod chi_squared;
+mod circular_uniform;
+mod convolution;
 mod dirac;
 mod dirichlet;
 mod discrete_uniform;
+mod discretize;
+pub mod divergence;
 mod empirical;
 mod erlang;
 mod exponential;
 mod fisher_snedecor;
+mod fit;
 mod gamma;
+mod generalized_extreme_value;
+mod generalized_pareto;
 mod geometric;
 mod hypergeometric;
 #[macro_use]
 mod internal;
+pub mod interval;
 mod inverse_gamma;
 mod laplace;
 mod log_normal;
+mod lomax;
+mod metalog;
 mod multinomial;
 mod multivariate_normal;
 mod negative_binomial;
+mod noncentral_fisher_snedecor;
+mod noncentral_students_t;
 mod normal;
+mod numeric_cdf;
 mod pareto;
+mod phase_type;
 mod poisson;
+mod poisson_binomial;
+mod projected_normal;
+mod records;
+mod runs;
+mod sample_range;
+mod shifted_exponential;
+mod stick_breaking;
+mod studentized_range;
 mod students_t;
+pub mod summation;
+mod transform;
 mod triangular;
 mod uniform;
 mod weibull;
 mod ziggurat;
 mod ziggurat_tables;
 
-use crate::Result;
+use crate::{Result, StatsError};
 
 /// The `ContinuousCDF` trait is used to specify an interface for univariate
 /// distributions for which cdf float arguments are sensible.
@@ -142,6 +193,76 @@ pub trait ContinuousCDF<K: Float, T: Float>: Min<K> + Max<K> {
         }
         (high + low) / two
     }
+
+    /// Draws a sample conditioned on exceeding `threshold` by inverting the
+    /// survival function of the conditional distribution rather than by
+    /// rejection, so the cost does not grow as `threshold` moves into the
+    /// tail.
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S^-1(u * S(t))
+    /// ```
+    ///
+    /// where `S` is the survival function, `t` the threshold and `u` a
+    /// uniform draw on `(0, 1)`
+    ///
+    /// # Remarks
+    ///
+    /// The draw is found by bisection on `S` above the threshold, which
+    /// keeps the relative accuracy of the tail where `1 - F(t)` would round
+    /// to `0`, and always exceeds `threshold`. Returns `NaN` if `S(t) == 0`,
+    /// i.e. if the threshold is beyond the support or so far into the tail
+    /// that no mass remains above it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::distribution::{ContinuousCDF, Normal};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!(n.sample_above(4.0, &mut rng) > 4.0);
+    /// assert!(n.sample_above(9.0, &mut rng) > 9.0);
+    /// assert!(n.sample_above(f64::INFINITY, &mut rng).is_nan());
+    /// ```
+    fn sample_above<R: Rng + ?Sized>(&self, threshold: K, rng: &mut R) -> K
+    where
+        Open01: rand::distributions::Distribution<T>,
+    {
+        let tail = self.sf(threshold);
+        if tail.is_nan() || tail <= T::zero() {
+            return K::nan();
+        }
+        let u: T = rng.sample(Open01);
+        let target = u * tail;
+        // bracket the draw by doubling steps above the threshold
+        let upper = self.max().min(K::max_value());
+        let mut low = threshold.max(K::min_value());
+        let mut step = low.abs().max(K::one());
+        let mut high = (low + step).min(upper);
+        while high < upper && self.sf(high) > target {
+            low = high;
+            step = step + step;
+            high = (low + step).min(upper);
+        }
+        // then bisect it down to adjacent floats
+        let two = K::one() + K::one();
+        loop {
+            let mid = low + (high - low) / two;
+            if mid <= low || mid >= high {
+                return high;
+            }
+            if self.sf(mid) > target {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+    }
 }
 
 /// The `DiscreteCDF` trait is used to specify an interface for univariate
@@ -280,3 +401,330 @@ pub trait Discrete<K, T> {
     /// ```
     fn ln_pmf(&self, x: K) -> T;
 }
+
+/// The `ScoreFunction` trait provides the derivatives of the log density, or
+/// of the log probability mass, of a distribution with respect to its
+/// parameters, as needed to fit distributions by gradient-based optimization
+///
+/// # Remarks
+///
+/// Every implementor documents the order of its parameters. Like those of
+/// `Continuous` and `Discrete`, the methods are unchecked and may return
+/// non-finite values outside the support or at the boundary of the parameter
+/// space.
+pub trait ScoreFunction<K> {
+    /// The number of parameters, i.e. the length of the gradient
+    const NUM_PARAMS: usize;
+
+    /// Returns the gradient of the log density at `x` with respect to the
+    /// parameters of the distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, ScoreFunction};
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.ln_pdf_grad(0.25), vec![0.25]);
+    /// ```
+    fn ln_pdf_grad(&self, x: K) -> Vec<f64>;
+
+    /// Returns the Hessian of the log density at `x` with respect to the
+    /// parameters of the distribution, or `None` if the implementor does not
+    /// provide it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, ScoreFunction};
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.ln_pdf_hessian(0.25), Some(vec![vec![-0.25]]));
+    /// ```
+    fn ln_pdf_hessian(&self, _x: K) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+
+    /// Returns the score of the sample `data`, i.e. the sum of the gradients
+    /// of the log density over its elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, ScoreFunction};
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.score_sum(&[0.25, 0.5, 1.0]), vec![-0.25]);
+    /// ```
+    fn score_sum(&self, data: &[K]) -> Vec<f64>
+    where
+        K: Copy,
+    {
+        let mut score = vec![0.0; Self::NUM_PARAMS];
+        for &x in data {
+            for (s, g) in score.iter_mut().zip(self.ln_pdf_grad(x)) {
+                *s += g;
+            }
+        }
+        score
+    }
+}
+
+/// The `LMoments` trait provides the
+/// [L-moments](https://en.wikipedia.org/wiki/L-moment) of a continuous
+/// distribution, the expectations of linear combinations of order statistics
+/// used for robust parameter estimation
+///
+/// # Remarks
+///
+/// The default implementation integrates the cdf numerically, which
+/// requires the distribution to have a finite mean. Implementors with
+/// closed forms override it.
+pub trait LMoments: ContinuousCDF<f64, f64> {
+    /// Returns the first `order` L-moments as `[λ1, λ2, τ3, τ4, ...]`: the
+    /// L-location, the L-scale and the L-moment ratios `τr = λr / λ2` for
+    /// `r >= 3`, such as the L-skewness `τ3` and the L-kurtosis `τ4`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ1 = E[X]
+    /// λr = 2 / (r (r - 1)) ∫ F(x) (1 - F(x)) P'_(r-1)(2F(x) - 1) dx,  r >= 2
+    /// ```
+    ///
+    /// where `F` is the cdf and `P'_n` the derivative of the Legendre
+    /// polynomial of degree `n`, equivalent to
+    /// `λr = r⁻¹ Σ_k (-1)^k C(r - 1, k) E[X_(r-k:r)]`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, LMoments};
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.l_moments(4), vec![0.5, 0.25, 1.0 / 3.0, 1.0 / 6.0]);
+    /// ```
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        internal::l_moments(self, order, &[])
+    }
+}
+
+/// The `TailExpectation` trait provides the partial expectations and the
+/// [mean excess function](https://en.wikipedia.org/wiki/Mean_excess_function)
+/// of a continuous distribution, as used for the conditional tail
+/// expectation of losses and to choose the threshold of peaks over
+/// threshold models
+///
+/// # Remarks
+///
+/// The default implementations integrate the density and the survival
+/// function numerically. Implementors with closed forms override them.
+pub trait TailExpectation: ContinuousCDF<f64, f64> + Continuous<f64, f64> {
+    /// Returns the partial expectation `E[X 1{a < X <= b}]`, the
+    /// contribution of the interval `(a, b]` to the mean, which is zero if
+    /// `a >= b`. Either bound may be infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, TailExpectation};
+    ///
+    /// let n = Exp::new(1.0).unwrap();
+    /// // the whole support gives the mean
+    /// assert_eq!(n.partial_expectation(f64::NEG_INFINITY, f64::INFINITY), 1.0);
+    /// // E[X 1{X > 1}] = 2 / e
+    /// assert!((n.partial_expectation(1.0, f64::INFINITY) - 2.0 / 1f64.exp()).abs() < 1e-15);
+    /// ```
+    fn partial_expectation(&self, a: f64, b: f64) -> f64 {
+        internal::partial_expectation(self, a, b)
+    }
+
+    /// Returns the mean excess `e(u) = E[X - u | X > u]` over the threshold
+    /// `u`, which is `f64::NAN` if `X > u` has probability zero and
+    /// infinite if the mean is
+    ///
+    /// # Remarks
+    ///
+    /// The conditional tail expectation `E[X | X > u]` is `u + e(u)`. The
+    /// mean excess is constant for the exponential distribution, increases
+    /// linearly in the threshold for heavy tails and decreases for light
+    /// ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Pareto, TailExpectation};
+    ///
+    /// // u / (α - 1) above the scale
+    /// let n = Pareto::new(1.0, 3.0).unwrap();
+    /// assert_eq!(n.mean_excess(4.0), 2.0);
+    /// ```
+    fn mean_excess(&self, u: f64) -> f64 {
+        internal::mean_excess(self, u)
+    }
+}
+
+/// The `Moments` trait provides the raw, central and standardized
+/// [moments](https://en.wikipedia.org/wiki/Moment_(mathematics)) of a
+/// distribution of arbitrary order, as needed by the method of moments
+///
+/// # Remarks
+///
+/// A moment that does not exist, because its defining integral or sum
+/// diverges, is `None`, as for `Distribution::mean`. Moments of order `0`
+/// are one. Implementors give closed forms where they are known and
+/// otherwise integrate the density numerically.
+pub trait Moments {
+    /// Returns the raw moment `E[X^order]`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Moments};
+    ///
+    /// // E[X^r] = r! / λ^r
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.raw_moment(3), Some(0.75));
+    /// ```
+    fn raw_moment(&self, order: u32) -> Option<f64>;
+
+    /// Returns the central moment `E[(X - μ)^order]` where `μ` is the mean
+    ///
+    /// # Remarks
+    ///
+    /// The default implementation expands the central moment into raw
+    /// moments, `Σ_k C(r, k) E[X^k] (-μ)^(r - k)`, which loses accuracy when
+    /// the mean is large compared to the standard deviation. Implementors
+    /// with closed forms override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Moments, Normal};
+    ///
+    /// // E[(X - μ)^4] = 3 σ^4
+    /// let n = Normal::new(5.0, 2.0).unwrap();
+    /// assert_eq!(n.central_moment(4), Some(48.0));
+    /// ```
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        let mean = self.raw_moment(1)?;
+        let mut sum = 0.0;
+        let mut coefficient = 1.0;
+        for k in 0..=order {
+            sum += coefficient * self.raw_moment(k)? * (-mean).powi((order - k) as i32);
+            coefficient = coefficient * (order - k) as f64 / (k + 1) as f64;
+        }
+        Some(sum)
+    }
+
+    /// Returns the standardized moment `E[(X - μ)^order] / σ^order`, the
+    /// skewness for `order = 3` and the kurtosis, not the excess kurtosis,
+    /// for `order = 4`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Moments};
+    ///
+    /// let n = Exp::new(2.0).unwrap();
+    /// assert_eq!(n.standardized_moment(3), Some(2.0));
+    /// assert_eq!(n.standardized_moment(4), Some(9.0));
+    /// ```
+    fn standardized_moment(&self, order: u32) -> Option<f64> {
+        let variance = self.central_moment(2)?;
+        let central = self.central_moment(order)?;
+        Some(central / variance.powf(0.5 * order as f64))
+    }
+}
+
+/// The `Parameterized` trait exposes the parameters of a distribution by
+/// name, so that generic code such as fitting reports, serialization or
+/// plot legends can list and change them without knowing the concrete type
+///
+/// # Remarks
+///
+/// Parameters are listed in the order of the constructor arguments.
+/// Vector-valued parameters contribute one entry per element, named with
+/// their index as in `alpha[3]`, and matrices one entry per element in
+/// row-major order as in `cov[0][1]`. Integer-valued parameters are
+/// reported as `f64` and must be set to integers. Wrappers such as
+/// `Shifted` list the parameters of the wrapped distribution with the
+/// prefix `inner.`.
+pub trait Parameterized: Sized {
+    /// Returns the names and values of the parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Parameterized};
+    ///
+    /// let n = Normal::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.parameters(), vec![("mean".to_string(), 1.0), ("std_dev".to_string(), 2.0)]);
+    /// ```
+    fn parameters(&self) -> Vec<(String, f64)>;
+
+    /// Constructs a distribution of the same kind, and of the same
+    /// dimensions for vector-valued parameters, from the parameter values
+    /// `values` given in the order of `parameters`
+    ///
+    /// # Errors
+    ///
+    /// Returns `StatsError::ContainersMustBeSameLength` if `values` does not
+    /// hold one value per parameter, and otherwise the error of the
+    /// constructor for invalid values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Parameterized};
+    ///
+    /// let n = Normal::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.with_parameters(&[0.0, 1.0]).unwrap(), Normal::new(0.0, 1.0).unwrap());
+    /// assert!(n.with_parameters(&[0.0, -1.0]).is_err());
+    /// ```
+    fn with_parameters(&self, values: &[f64]) -> Result<Self>;
+
+    /// Returns the number of parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Dirichlet, Parameterized};
+    ///
+    /// let n = Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap();
+    /// assert_eq!(n.n_parameters(), 3);
+    /// ```
+    fn n_parameters(&self) -> usize {
+        self.parameters().len()
+    }
+
+    /// Constructs a copy of the distribution with the parameter `name` set
+    /// to `value`, validated by the constructor
+    ///
+    /// # Errors
+    ///
+    /// Returns `StatsError::BadParams` if there is no parameter `name`, and
+    /// otherwise the error of the constructor for invalid values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Parameterized};
+    /// use statrs::StatsError;
+    ///
+    /// let n = Normal::new(1.0, 2.0).unwrap();
+    /// assert_eq!(n.set_parameter("mean", 3.0).unwrap(), Normal::new(3.0, 2.0).unwrap());
+    /// assert_eq!(n.set_parameter("std_dev", 0.0), Err(StatsError::BadParams));
+    /// assert!(n.set_parameter("rate", 1.0).is_err());
+    /// ```
+    fn set_parameter(&self, name: &str, value: f64) -> Result<Self> {
+        let parameters = self.parameters();
+        let i = parameters
+            .iter()
+            .position(|(n, _)| n == name)
+            .ok_or(StatsError::BadParams)?;
+        let mut values: Vec<f64> = parameters.into_iter().map(|(_, v)| v).collect();
+        values[i] = value;
+        self.with_parameters(&values)
+    }
+}
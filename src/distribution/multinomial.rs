@@ -1,4 +1,4 @@
-use crate::distribution::Discrete;
+use crate::distribution::{internal, Binomial, Discrete, Parameterized};
 use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -88,6 +88,248 @@ impl Multinomial {
     pub fn n(&self) -> u64 {
         self.n
     }
+
+    /// Returns the normalized probability of the `i`th category
+    fn prob(&self, i: usize) -> f64 {
+        self.p[i] / self.p.iter().sum::<f64>()
+    }
+
+    /// Returns the mean count of every category as a `Vec`, the same values
+    /// as `MeanN::mean`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n * p_i for i in 1...k
+    /// ```
+    ///
+    /// where `n` is the number of trials, `p_i` is the `i`th normalized
+    /// probability, and `k` is the total number of probabilities
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    ///
+    /// let n = Multinomial::new(&[1.0, 3.0], 8).unwrap();
+    /// assert_eq!(n.means(), vec![2.0, 6.0]);
+    /// ```
+    pub fn means(&self) -> Vec<f64> {
+        (0..self.p.len())
+            .map(|i| self.n as f64 * self.prob(i))
+            .collect()
+    }
+
+    /// Returns the variance of the count of every category as a `Vec`, the
+    /// diagonal of `covariance_matrix`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n * p_i * (1 - p_i) for i in 1...k
+    /// ```
+    ///
+    /// where `n` is the number of trials, `p_i` is the `i`th normalized
+    /// probability, and `k` is the total number of probabilities
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    ///
+    /// let n = Multinomial::new(&[1.0, 3.0], 8).unwrap();
+    /// assert_eq!(n.variances(), vec![1.5, 1.5]);
+    /// ```
+    pub fn variances(&self) -> Vec<f64> {
+        (0..self.p.len()).map(|i| self.covariance(i, i)).collect()
+    }
+
+    /// Returns the covariance between the counts of the `i`th and `j`th
+    /// categories
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if i == j {
+    ///     n * p_i * (1 - p_i)
+    /// } else {
+    ///     -n * p_i * p_j
+    /// }
+    /// ```
+    ///
+    /// where `n` is the number of trials and `p_i` is the `i`th normalized
+    /// probability
+    ///
+    /// # Panics
+    ///
+    /// If `i` or `j` is not less than the number of categories
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    ///
+    /// let n = Multinomial::new(&[0.25, 0.25, 0.5], 8).unwrap();
+    /// assert_eq!(n.covariance(0, 2), -1.0);
+    /// ```
+    pub fn covariance(&self, i: usize, j: usize) -> f64 {
+        let (p_i, p_j) = (self.prob(i), self.prob(j));
+        if i == j {
+            self.n as f64 * p_i * (1.0 - p_i)
+        } else {
+            -(self.n as f64 * (p_i * p_j))
+        }
+    }
+
+    /// Returns the covariance matrix of the counts, `n (diag(p) - p p^T)`,
+    /// which is singular since the counts sum to `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    /// use nalgebra::DMatrix;
+    ///
+    /// let n = Multinomial::new(&[0.5, 0.5], 4).unwrap();
+    /// assert_eq!(n.covariance_matrix(), DMatrix::from_vec(2, 2, vec![1.0, -1.0, -1.0, 1.0]));
+    /// ```
+    pub fn covariance_matrix(&self) -> DMatrix<f64> {
+        let k = self.p.len();
+        DMatrix::from_fn(k, k, |i, j| self.covariance(i, j))
+    }
+
+    /// Returns the correlation between the counts of the `i`th and `j`th
+    /// categories, which does not depend on the number of trials
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -sqrt(p_i * p_j / ((1 - p_i) * (1 - p_j)))
+    /// ```
+    ///
+    /// for `i != j` and `1` for `i == j`, where `p_i` is the `i`th
+    /// normalized probability. The result is `NaN` if either count is
+    /// degenerate, that is if its probability is `0` or `1`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` or `j` is not less than the number of categories
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    ///
+    /// let n = Multinomial::new(&[0.5, 0.5], 4).unwrap();
+    /// assert_eq!(n.correlation(0, 1), -1.0);
+    /// ```
+    pub fn correlation(&self, i: usize, j: usize) -> f64 {
+        let (p_i, p_j) = (self.prob(i), self.prob(j));
+        if p_i == 0.0 || p_i == 1.0 || p_j == 0.0 || p_j == 1.0 {
+            f64::NAN
+        } else if i == j {
+            1.0
+        } else {
+            -(p_i * p_j / ((1.0 - p_i) * (1.0 - p_j))).sqrt()
+        }
+    }
+
+    /// Returns the marginal distribution of the count of the `i`th
+    /// category, a binomial distribution with `n` trials and success
+    /// probability `p_i`
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than the number of categories
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Binomial, Multinomial};
+    ///
+    /// let n = Multinomial::new(&[1.0, 3.0], 8).unwrap();
+    /// assert_eq!(n.marginal(1), Binomial::new(0.75, 8).unwrap());
+    /// ```
+    pub fn marginal(&self, i: usize) -> Binomial {
+        Binomial::new(self.prob(i), self.n).unwrap()
+    }
+
+    /// Returns the distribution of the counts of the remaining categories
+    /// given the counts `fixed` of some categories, as `(index, count)`
+    /// pairs
+    ///
+    /// The remaining categories keep their order and share the remaining
+    /// `n - Σ count` trials in proportion to their probabilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an index is out of range or repeated, if all
+    /// categories are fixed, if the fixed counts exceed `n`, or if the
+    /// conditioning event is impossible because a category with
+    /// probability zero has a positive count or the remaining categories
+    /// have probability zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Multinomial;
+    ///
+    /// let n = Multinomial::new(&[0.2, 0.3, 0.5], 10).unwrap();
+    /// let c = n.conditional_on(&[(1, 4)]).unwrap();
+    /// assert_eq!(c.n(), 6);
+    /// assert!((c.p()[0] - 2.0 / 7.0).abs() < 1e-15);
+    /// assert!((c.p()[1] - 5.0 / 7.0).abs() < 1e-15);
+    /// ```
+    pub fn conditional_on(&self, fixed: &[(usize, u64)]) -> Result<Multinomial> {
+        let k = self.p.len();
+        let mut is_fixed = vec![false; k];
+        let mut trials = 0u64;
+        for &(i, count) in fixed {
+            if i >= k || is_fixed[i] {
+                return Err(StatsError::BadParams);
+            }
+            if count > 0 && self.p[i] == 0.0 {
+                return Err(StatsError::SpecialCase(
+                    "a category with probability zero has a positive count",
+                ));
+            }
+            is_fixed[i] = true;
+            trials = trials
+                .checked_add(count)
+                .filter(|&t| t <= self.n)
+                .ok_or(StatsError::ArgLteArg("fixed counts", "n"))?;
+        }
+        let p: Vec<f64> = (0..k)
+            .filter(|&i| !is_fixed[i])
+            .map(|i| self.prob(i))
+            .collect();
+        let total: f64 = p.iter().sum();
+        if total == 0.0 {
+            return Err(StatsError::SpecialCase(
+                "the remaining categories must have positive probability",
+            ));
+        }
+        let p: Vec<f64> = p.iter().map(|x| x / total).collect();
+        Multinomial::new(&p, self.n - trials)
+    }
+}
+
+impl Parameterized for Multinomial {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let p = self
+            .p
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (format!("p[{}]", i), p));
+        p.chain(std::iter::once(("n".into(), self.n as f64)))
+            .collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Multinomial> {
+        let values = internal::parameter_values(values, self.p.len() + 1)?;
+        let (p, n) = values.split_at(self.p.len());
+        Multinomial::new(p, internal::integer_parameter(n[0])?)
+    }
 }
 
 impl std::fmt::Display for Multinomial {
@@ -118,33 +360,28 @@ impl MeanN<DVector<f64>> for Multinomial {
     /// n * p_i for i in 1...k
     /// ```
     ///
-    /// where `n` is the number of trials, `p_i` is the `i`th probability,
-    /// and `k` is the total number of probabilities
+    /// where `n` is the number of trials, `p_i` is the `i`th normalized
+    /// probability, and `k` is the total number of probabilities
     fn mean(&self) -> Option<DVector<f64>> {
-        Some(DVector::from_vec(
-            self.p.iter().map(|x| x * self.n as f64).collect(),
-        ))
+        Some(DVector::from_vec(self.means()))
     }
 }
 
 impl VarianceN<DMatrix<f64>> for Multinomial {
-    /// Returns the variance of the multinomial distribution
+    /// Returns the covariance matrix of the multinomial distribution, see
+    /// `covariance_matrix`
     ///
     /// # Formula
     ///
     /// ```text
-    /// n * p_i * (1 - p_i) for i in 1...k
+    /// n * p_i * (1 - p_i) for i == j
+    /// -n * p_i * p_j for i != j
     /// ```
     ///
-    /// where `n` is the number of trials, `p_i` is the `i`th probability,
-    /// and `k` is the total number of probabilities
+    /// where `n` is the number of trials, `p_i` is the `i`th normalized
+    /// probability, and `i`, `j` range over the categories
     fn variance(&self) -> Option<DMatrix<f64>> {
-        let cov: Vec<_> = self
-            .p
-            .iter()
-            .map(|x| x * self.n as f64 * (1.0 - x))
-            .collect();
-        Some(DMatrix::from_diagonal(&DVector::from_vec(cov)))
+        Some(self.covariance_matrix())
     }
 }
 
@@ -244,6 +481,137 @@ impl<'a> Discrete<&'a [u64], f64> for Multinomial {
     }
 }
 
+#[rustfmt::skip]
+#[cfg(test)]
+mod moment_tests {
+    use super::*;
+    use crate::distribution::{Binomial, Discrete};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_moments() {
+        let n = Multinomial::new(&[0.15, 0.35, 0.3, 0.2], 20).unwrap();
+        let means = n.means();
+        let variances = n.variances();
+        for (i, (&m, &v)) in [3.0, 7.0, 6.0, 4.0].iter().zip([2.55, 4.55, 4.2, 3.2].iter()).enumerate() {
+            assert_almost_eq!(means[i], m, 1e-14);
+            assert_almost_eq!(variances[i], v, 1e-14);
+        }
+        assert_eq!(n.mean().unwrap(), DVector::from_vec(means));
+        let cov = n.covariance_matrix();
+        assert_eq!(n.variance().unwrap(), cov);
+        assert_almost_eq!(cov[(0, 1)], -20.0 * 0.15 * 0.35, 1e-14);
+        // every row sums to zero since the counts sum to n
+        for i in 0..4 {
+            assert_almost_eq!(cov.row(i).sum(), 0.0, 1e-14);
+            assert_eq!(n.correlation(i, i), 1.0);
+            for j in 0..4 {
+                assert_eq!(cov[(i, j)], cov[(j, i)]);
+                assert_almost_eq!(n.correlation(i, j), cov[(i, j)] / (cov[(i, i)] * cov[(j, j)]).sqrt(), 1e-14);
+            }
+        }
+        // unnormalized probabilities are normalized
+        let m = Multinomial::new(&[3.0, 7.0, 6.0, 4.0], 20).unwrap();
+        assert_eq!(m.means(), vec![3.0, 7.0, 6.0, 4.0]);
+        assert!(Multinomial::new(&[0.0, 1.0], 3).unwrap().correlation(0, 1).is_nan());
+    }
+
+    #[test]
+    fn test_simulated_covariance() {
+        let n = Multinomial::new(&[0.2, 0.5, 0.3], 10).unwrap();
+        let mut rng = StdRng::seed_from_u64(2752);
+        let draws = 1_000_000;
+        let (mut sum, mut cross) = ([0.0; 3], [[0.0; 3]; 3]);
+        for _ in 0..draws {
+            let x = n.sample(&mut rng);
+            for i in 0..3 {
+                sum[i] += x[i];
+                for j in 0..3 {
+                    cross[i][j] += x[i] * x[j];
+                }
+            }
+        }
+        let cov = n.covariance_matrix();
+        let draws = draws as f64;
+        for i in 0..3 {
+            assert_almost_eq!(sum[i] / draws, n.means()[i], 0.01);
+            for j in 0..3 {
+                let sample = cross[i][j] / draws - sum[i] * sum[j] / (draws * draws);
+                // the Monte Carlo standard error is below 0.003
+                assert_almost_eq!(sample, cov[(i, j)], 0.015);
+            }
+        }
+    }
+
+    #[test]
+    fn test_marginal() {
+        let n = Multinomial::new(&[0.2, 0.5, 0.3], 4).unwrap();
+        for i in 0..3 {
+            let marginal = n.marginal(i);
+            assert_eq!(marginal, Binomial::new(n.p()[i], 4).unwrap());
+            for k in 0..=4 {
+                let mut total = 0.0;
+                for a in 0..=4 - k {
+                    let mut x = [k; 3];
+                    let rest: Vec<usize> = (0..3).filter(|&j| j != i).collect();
+                    x[rest[0]] = a;
+                    x[rest[1]] = 4 - k - a;
+                    total += n.pmf(&x);
+                }
+                assert_almost_eq!(total, marginal.pmf(k), 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditional() {
+        let n = Multinomial::new(&[0.1, 0.2, 0.3, 0.4], 6).unwrap();
+        let c = n.conditional_on(&[(2, 2)]).unwrap();
+        assert_eq!(c.n(), 4);
+        // P(X | X_2 = 2) = P(X) / P(X_2 = 2)
+        let p_fixed = n.marginal(2).pmf(2);
+        for a in 0..=4u64 {
+            for b in 0..=4 - a {
+                let x = [a, b, 2, 4 - a - b];
+                assert_almost_eq!(c.pmf(&[a, b, 4 - a - b]), n.pmf(&x) / p_fixed, 1e-14);
+            }
+        }
+        // conditioning on two counts at once or one after the other agree
+        let both = n.conditional_on(&[(3, 1), (0, 2)]).unwrap();
+        let steps = n.conditional_on(&[(3, 1)]).unwrap().conditional_on(&[(0, 2)]).unwrap();
+        assert_eq!(both.n(), 3);
+        assert_eq!(both.n(), steps.n());
+        for (a, b) in both.p().iter().zip(steps.p()) {
+            assert_almost_eq!(*a, *b, 1e-15);
+        }
+        assert_eq!(n.conditional_on(&[]).unwrap().p(), n.p());
+    }
+
+    #[test]
+    fn test_conditional_bad_input() {
+        let n = Multinomial::new(&[0.0, 0.5, 0.5], 6).unwrap();
+        assert!(n.conditional_on(&[(3, 1)]).is_err());
+        assert!(n.conditional_on(&[(1, 1), (1, 1)]).is_err());
+        assert!(n.conditional_on(&[(1, 4), (2, 3)]).is_err());
+        assert!(n.conditional_on(&[(0, 1)]).is_err());
+        assert!(n.conditional_on(&[(1, 1), (2, 5)]).is_err());
+        assert!(n.conditional_on(&[(0, 0), (1, 1), (2, 5)]).is_err());
+        assert!(n.conditional_on(&[(1, u64::MAX), (2, 1)]).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = Multinomial::new(&[0.2, 0.3, 0.5], 4).unwrap();
+        crate::distribution::internal::test::check_parameters(&n, |d| [d.pmf(&[1, 1, 2]), d.pmf(&[0, 0, 4])]);
+        let names: Vec<String> = n.parameters().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["p[0]", "p[1]", "p[2]", "n"]);
+        assert_eq!(n.set_parameter("p[1]", -0.3), Multinomial::new(&[0.2, -0.3, 0.5], 4));
+        assert!(n.set_parameter("n", 1.5).is_err());
+    }
+}
+
 // TODO: fix tests
 // #[rustfmt::skip]
 // #[cfg(test)]
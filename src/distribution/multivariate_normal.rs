@@ -1,5 +1,5 @@
-use crate::distribution::Continuous;
 use crate::distribution::Normal;
+use crate::distribution::{internal, Continuous, Parameterized};
 use crate::statistics::{Max, MeanN, Min, Mode, VarianceN};
 use crate::{Result, StatsError};
 use nalgebra::{
@@ -113,6 +113,30 @@ impl MultivariateNormal {
     }
 }
 
+impl Parameterized for MultivariateNormal {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let mean = self
+            .mu
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| (format!("mean[{}]", i), m));
+        let cov = (0..self.dim * self.dim).map(|k| {
+            let (i, j) = (k / self.dim, k % self.dim);
+            (format!("cov[{}][{}]", i, j), self.cov[(i, j)])
+        });
+        mean.chain(cov).collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<MultivariateNormal> {
+        let values = internal::parameter_values(values, self.dim + self.dim * self.dim)?;
+        let (mean, cov) = values.split_at(self.dim);
+        MultivariateNormal::new_from_nalgebra(
+            DVector::from_column_slice(mean),
+            DMatrix::from_row_slice(self.dim, self.dim, cov),
+        )
+    }
+}
+
 impl std::fmt::Display for MultivariateNormal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "N({}, {})", &self.mu, &self.cov)
@@ -255,9 +279,10 @@ mod tests  {
     use core::fmt::Debug;
     use nalgebra::base::allocator::Allocator;
     use nalgebra::{
-        DefaultAllocator, Dim, DimMin, DimName, Matrix2, Matrix3, Vector2, Vector3,
+        DefaultAllocator, Dim, DimMin, DimName, DVector, Matrix2, Matrix3, Vector2, Vector3,
         U1, U2,
     };
+    use crate::distribution::Parameterized;
 
     fn try_create(mean: Vec<f64>, covariance: Vec<f64>) -> MultivariateNormal
     {
@@ -397,4 +422,13 @@ mod tests  {
         test_case(vec![0., 0.], vec![f64::INFINITY, 0., 0., f64::INFINITY], f64::NEG_INFINITY, ln_pdf(dvec![10., 10.]));
         test_case(vec![0., 0.], vec![f64::INFINITY, 0., 0., f64::INFINITY], f64::NEG_INFINITY, ln_pdf(dvec![100., 100.]));
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(vec![1.0, -1.0], vec![2.0, 0.5, 0.5, 1.0]);
+        let x = DVector::from_vec(vec![0.5, 0.2]);
+        crate::distribution::internal::test::check_parameters(&n, |d| d.pdf(&x));
+        assert_eq!(n.parameters()[3], ("cov[0][1]".to_string(), 0.5));
+        assert_eq!(n.set_parameter("cov[1][0]", 0.7), MultivariateNormal::new(vec![1.0, -1.0], vec![2.0, 0.7, 0.5, 1.0]));
+    }
 }
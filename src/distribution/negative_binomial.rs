@@ -1,4 +1,4 @@
-use crate::distribution::{self, poisson, Discrete, DiscreteCDF};
+use crate::distribution::{self, poisson, Discrete, DiscreteCDF, Parameterized, ScoreFunction};
 use crate::function::{beta, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -104,6 +104,19 @@ impl NegativeBinomial {
     }
 }
 
+impl Parameterized for NegativeBinomial {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("r".into(), self.r), ("p".into(), self.p)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<NegativeBinomial> {
+        match *values {
+            [r, p] => NegativeBinomial::new(r, p),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for NegativeBinomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "NB({},{})", self.r, self.p)
@@ -112,7 +125,8 @@ impl std::fmt::Display for NegativeBinomial {
 
 impl ::rand::distributions::Distribution<u64> for NegativeBinomial {
     fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> u64 {
-        let lambda = distribution::gamma::sample_unchecked(r, self.r, (1.0 - self.p) / self.p);
+        // the gamma mixing distribution has mean r (1 - p) / p, hence rate p / (1 - p)
+        let lambda = distribution::gamma::sample_unchecked(r, self.r, self.p / (1.0 - self.p));
         poisson::sample_unchecked(r, lambda).floor() as u64
     }
 }
@@ -288,6 +302,49 @@ impl Discrete<u64, f64> for NegativeBinomial {
     }
 }
 
+impl ScoreFunction<u64> for NegativeBinomial {
+    const NUM_PARAMS: usize = 2;
+
+    /// Calculates the gradient of the log probability mass function for the
+    /// negative binomial distribution at `x` with respect to `(r, p)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (ψ(x + r) - ψ(r) + ln(p), r / p - x / (1 - p))
+    /// ```
+    ///
+    /// where `ψ` is the digamma function
+    fn ln_pdf_grad(&self, x: u64) -> Vec<f64> {
+        let k = x as f64;
+        vec![
+            gamma::digamma(k + self.r) - gamma::digamma(self.r) + self.p.ln(),
+            self.r / self.p - k / (1.0 - self.p),
+        ]
+    }
+
+    /// Calculates the Hessian of the log probability mass function for the
+    /// negative binomial distribution at `x` with respect to `(r, p)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[ψ1(x + r) - ψ1(r), 1 / p], [1 / p, -r / p^2 - x / (1 - p)^2]]
+    /// ```
+    ///
+    /// where `ψ1` is the trigamma function
+    fn ln_pdf_hessian(&self, x: u64) -> Option<Vec<Vec<f64>>> {
+        let k = x as f64;
+        let q = 1.0 - self.p;
+        let rr = gamma::trigamma(k + self.r) - gamma::trigamma(self.r);
+        let rp = 1.0 / self.p;
+        Some(vec![
+            vec![rr, rp],
+            vec![rp, -self.r / (self.p * self.p) - k / (q * q)],
+        ])
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -295,6 +352,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, NegativeBinomial};
     use crate::distribution::internal::test;
+    use crate::distribution::Parameterized;
 
     fn try_create(r: f64, p: f64) -> NegativeBinomial {
         let r = NegativeBinomial::new(r, p);
@@ -509,4 +567,36 @@ mod tests {
         let sf = |arg: u64| move |x: NegativeBinomial| x.sf(arg);
         test_almost(3.0, 0.5, 5.282409836586059e-28, 1e-28, sf(100));
     }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| NegativeBinomial::new(p[0], p[1]).unwrap();
+        let xs = [0, 1, 4, 15, 60];
+        test::check_score(make, |d: &NegativeBinomial, x| d.ln_pmf(x), &[4.0, 0.5], &xs);
+        test::check_score(make, |d: &NegativeBinomial, x| d.ln_pmf(x), &[0.7, 0.2], &xs);
+        test::check_score(make, |d: &NegativeBinomial, x| d.ln_pmf(x), &[12.5, 0.85], &xs);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(2.5, 0.4);
+        test::check_parameters(&n, |d| [d.pmf(0), d.pmf(3)]);
+        assert_eq!(n.set_parameter("p", 1.5), NegativeBinomial::new(2.5, 1.5));
+    }
+
+    #[test]
+    fn test_sample_moments() {
+        use rand::distributions::Distribution as _;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // mean 5 and variance 17.5
+        let dist = try_create(2.0, 2.0 / 7.0);
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples: Vec<f64> = dist.sample_iter(&mut rng).take(100_000).map(|x: u64| x as f64).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / samples.len() as f64;
+        assert_almost_eq!(mean, 5.0, 0.05);
+        assert_almost_eq!(variance, 17.5, 0.5);
+    }
 }
@@ -0,0 +1,689 @@
+use crate::distribution::{Continuous, ContinuousCDF, FisherSnedecor, Parameterized};
+use crate::function::{beta, gamma};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Relative size below which the remaining terms of a series are dropped
+const SERIES_EPSILON: f64 = 1e-17;
+
+/// Maximum number of terms summed on each side of the mode of a series
+const SERIES_MAX_TERMS: usize = 100_000;
+
+/// Implements the [noncentral
+/// F](https://en.wikipedia.org/wiki/Noncentral_F-distribution) distribution
+/// of `(U / d1) / (V / d2)` where `U` is a noncentral chi-squared variable
+/// with `d1` degrees of freedom and noncentrality `λ`, and `V` an
+/// independent chi-squared variable with `d2` degrees of freedom
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, NoncentralFisherSnedecor};
+/// use statrs::statistics::Distribution;
+/// use statrs::prec;
+///
+/// let n = NoncentralFisherSnedecor::new(3.0, 10.0, 2.0).unwrap();
+/// assert!(prec::almost_eq(n.mean().unwrap(), 25.0 / 12.0, 1e-15));
+/// assert!(prec::almost_eq(n.cdf(2.0), 0.63136085282770099509, 1e-14));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct NoncentralFisherSnedecor {
+    freedom_1: f64,
+    freedom_2: f64,
+    noncentrality: f64,
+}
+
+impl NoncentralFisherSnedecor {
+    /// Constructs a new noncentral fisher-snedecor distribution with
+    /// degrees of freedom `freedom_1` and `freedom_2` and noncentrality
+    /// `noncentrality`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `freedom_1` or `freedom_2` are not positive and
+    /// finite, or if `noncentrality` is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralFisherSnedecor;
+    ///
+    /// let mut result = NoncentralFisherSnedecor::new(3.0, 10.0, 2.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NoncentralFisherSnedecor::new(3.0, 10.0, -1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(
+        freedom_1: f64,
+        freedom_2: f64,
+        noncentrality: f64,
+    ) -> Result<NoncentralFisherSnedecor> {
+        if !freedom_1.is_finite() || freedom_1 <= 0.0 || !freedom_2.is_finite() || freedom_2 <= 0.0
+        {
+            return Err(StatsError::BadParams);
+        }
+        if !noncentrality.is_finite() || noncentrality < 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(NoncentralFisherSnedecor {
+            freedom_1,
+            freedom_2,
+            noncentrality,
+        })
+    }
+
+    /// Returns the first degree of freedom for the noncentral
+    /// fisher-snedecor distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralFisherSnedecor;
+    ///
+    /// let n = NoncentralFisherSnedecor::new(2.0, 3.0, 4.0).unwrap();
+    /// assert_eq!(n.freedom_1(), 2.0);
+    /// ```
+    pub fn freedom_1(&self) -> f64 {
+        self.freedom_1
+    }
+
+    /// Returns the second degree of freedom for the noncentral
+    /// fisher-snedecor distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralFisherSnedecor;
+    ///
+    /// let n = NoncentralFisherSnedecor::new(2.0, 3.0, 4.0).unwrap();
+    /// assert_eq!(n.freedom_2(), 3.0);
+    /// ```
+    pub fn freedom_2(&self) -> f64 {
+        self.freedom_2
+    }
+
+    /// Returns the noncentrality for the noncentral fisher-snedecor
+    /// distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralFisherSnedecor;
+    ///
+    /// let n = NoncentralFisherSnedecor::new(2.0, 3.0, 4.0).unwrap();
+    /// assert_eq!(n.noncentrality(), 4.0);
+    /// ```
+    pub fn noncentrality(&self) -> f64 {
+        self.noncentrality
+    }
+
+    /// Returns the central fisher-snedecor distribution with the same
+    /// degrees of freedom
+    fn central(&self) -> FisherSnedecor {
+        FisherSnedecor::new(self.freedom_1, self.freedom_2).unwrap()
+    }
+
+    /// Returns `z = d1 x / (d1 x + d2)` and `1 - z` without cancellation
+    fn beta_argument(&self, x: f64) -> (f64, f64) {
+        let denominator = self.freedom_1 * x + self.freedom_2;
+        (
+            self.freedom_1 * x / denominator,
+            self.freedom_2 / denominator,
+        )
+    }
+}
+
+/// Computes the Poisson mixture
+///
+/// ```text
+/// Σ_j e^(-h) h^j / j! * I_z(a + j, b)
+/// ```
+///
+/// with `h = λ / 2`, or the same mixture of `1 - I_z(a + j, b)` when `upper`
+/// is set. The sum starts at the mode of the Poisson weights and proceeds
+/// in both directions, updating the incomplete beta functions by
+/// recurrence, so that large noncentralities do not underflow.
+fn mixture_cdf(a: f64, b: f64, z: f64, y: f64, lambda: f64, upper: bool) -> f64 {
+    let h = 0.5 * lambda;
+    let k = h.floor();
+    let ln_poisson = if h == 0.0 { 0.0 } else { -h + k * h.ln() };
+    let p_mode = (ln_poisson - gamma::ln_gamma(k + 1.0)).exp();
+    let (ln_z, ln_y) = (z.ln(), y.ln());
+    // `step(s)` is `I_z(s, b) - I_z(s + 1, b)`
+    let step = |s: f64| {
+        (gamma::ln_gamma(s + b) - gamma::ln_gamma(s + 1.0) - gamma::ln_gamma(b)
+            + s * ln_z
+            + b * ln_y)
+            .exp()
+    };
+    // the probabilities move by `sign * step` from one term to the next
+    let sign = if upper { 1.0 } else { -1.0 };
+    let s_mode = a + k;
+    let i_mode = if upper {
+        beta::beta_reg(b, s_mode, y)
+    } else {
+        beta::beta_reg(s_mode, b, z)
+    };
+    let mut sum = p_mode * i_mode;
+
+    // forward from the mode
+    let (mut p, mut s, mut i_s) = (p_mode, s_mode, i_mode);
+    let mut d = step(s);
+    let mut j = k;
+    for _ in 0..SERIES_MAX_TERMS {
+        j += 1.0;
+        i_s += sign * d;
+        d *= z * (s + b) / (s + 1.0);
+        s += 1.0;
+        p *= h / j;
+        let term = p * i_s.clamp(0.0, 1.0);
+        sum += term;
+        if term <= SERIES_EPSILON * sum || p == 0.0 {
+            break;
+        }
+    }
+
+    // backward from the mode
+    let (mut p, mut s, mut i_s) = (p_mode, s_mode, i_mode);
+    let mut d = if k > 0.0 { step(s - 1.0) } else { 0.0 };
+    let mut j = k;
+    while j > 0.0 {
+        i_s -= sign * d;
+        s -= 1.0;
+        p *= j / h;
+        j -= 1.0;
+        sum += p * i_s.clamp(0.0, 1.0);
+        if p <= SERIES_EPSILON * sum {
+            break;
+        }
+        if j > 0.0 {
+            d *= s / (z * (s - 1.0 + b));
+        }
+    }
+    sum
+}
+
+impl Parameterized for NoncentralFisherSnedecor {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("freedom_1".into(), self.freedom_1),
+            ("freedom_2".into(), self.freedom_2),
+            ("noncentrality".into(), self.noncentrality),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<NoncentralFisherSnedecor> {
+        match *values {
+            [freedom_1, freedom_2, noncentrality] => {
+                NoncentralFisherSnedecor::new(freedom_1, freedom_2, noncentrality)
+            }
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for NoncentralFisherSnedecor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "F'({},{},{})",
+            self.freedom_1, self.freedom_2, self.noncentrality
+        )
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for NoncentralFisherSnedecor {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // a noncentral chi-squared variable is a Poisson mixture of central
+        // ones with `2j` additional degrees of freedom
+        let j = super::poisson::sample_unchecked(rng, 0.5 * self.noncentrality);
+        let numerator = super::gamma::sample_unchecked(rng, 0.5 * self.freedom_1 + j, 0.5);
+        let denominator = super::gamma::sample_unchecked(rng, 0.5 * self.freedom_2, 0.5);
+        (numerator * self.freedom_2) / (denominator * self.freedom_1)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for NoncentralFisherSnedecor {
+    /// Calculates the cumulative distribution function for the noncentral
+    /// fisher-snedecor distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_j e^(-λ/2) (λ/2)^j / j! * I_z(d1 / 2 + j, d2 / 2)
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom, `λ` the
+    /// noncentrality, `I` the regularized incomplete beta function and
+    /// `z = d1 x / (d1 x + d2)`. The infinite sum is truncated once its
+    /// terms fall below `1e-17` of the partial sum.
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if self.noncentrality == 0.0 {
+            self.central().cdf(x)
+        } else if x <= 0.0 {
+            0.0
+        } else if x.is_infinite() {
+            1.0
+        } else {
+            let (z, y) = self.beta_argument(x);
+            let (a, b) = (0.5 * self.freedom_1, 0.5 * self.freedom_2);
+            mixture_cdf(a, b, z, y, self.noncentrality, false).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the survival function for the noncentral fisher-snedecor
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_j e^(-λ/2) (λ/2)^j / j! * I_(1 - z)(d2 / 2, d1 / 2 + j)
+    /// ```
+    ///
+    /// with the notation of `cdf`
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if self.noncentrality == 0.0 {
+            self.central().sf(x)
+        } else if x <= 0.0 {
+            1.0
+        } else if x.is_infinite() {
+            0.0
+        } else {
+            let (z, y) = self.beta_argument(x);
+            let (a, b) = (0.5 * self.freedom_1, 0.5 * self.freedom_2);
+            mixture_cdf(a, b, z, y, self.noncentrality, true).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// noncentral fisher-snedecor distribution at `p` by safeguarded Newton
+    /// iteration on the cdf, starting from the central quantile scaled by
+    /// `1 + λ / d1`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            return 0.0;
+        }
+        if p == 1.0 {
+            return f64::INFINITY;
+        }
+        if self.noncentrality == 0.0 {
+            return self.central().inverse_cdf(p);
+        }
+        let mut x = self.central().inverse_cdf(p) * (1.0 + self.noncentrality / self.freedom_1);
+        let mut lo = 0.0;
+        let mut hi = x.max(1.0);
+        while self.cdf(hi) < p {
+            lo = hi;
+            hi *= 2.0;
+        }
+        x = x.clamp(lo, hi);
+        for _ in 0..100 {
+            let error = self.cdf(x) - p;
+            if error < 0.0 {
+                lo = x;
+            } else {
+                hi = x;
+            }
+            let newton = x - error / self.pdf(x);
+            let next = if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+            let step = (next - x).abs();
+            x = next;
+            if step <= 1e-14 * x.max(1e-300) || hi - lo <= 1e-14 * x.max(1e-300) {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl Min<f64> for NoncentralFisherSnedecor {
+    /// Returns the minimum value in the domain of the noncentral
+    /// fisher-snedecor distribution representable by a double precision
+    /// float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for NoncentralFisherSnedecor {
+    /// Returns the maximum value in the domain of the noncentral
+    /// fisher-snedecor distribution representable by a double precision
+    /// float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for NoncentralFisherSnedecor {
+    /// Returns the mean of the noncentral fisher-snedecor distribution
+    ///
+    /// # None
+    ///
+    /// If `freedom_2 <= 2.0`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// d2 (d1 + λ) / (d1 (d2 - 2))
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom and `λ` the
+    /// noncentrality
+    fn mean(&self) -> Option<f64> {
+        if self.freedom_2 <= 2.0 {
+            return None;
+        }
+        Some(
+            self.freedom_2 * (self.freedom_1 + self.noncentrality)
+                / (self.freedom_1 * (self.freedom_2 - 2.0)),
+        )
+    }
+
+    /// Returns the variance of the noncentral fisher-snedecor distribution
+    ///
+    /// # None
+    ///
+    /// If `freedom_2 <= 4.0`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 (d2 / d1)^2 ((d1 + λ)^2 + (d1 + 2λ)(d2 - 2)) / ((d2 - 2)^2 (d2 - 4))
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom and `λ` the
+    /// noncentrality
+    fn variance(&self) -> Option<f64> {
+        if self.freedom_2 <= 4.0 {
+            return None;
+        }
+        let (d1, d2, lambda) = (self.freedom_1, self.freedom_2, self.noncentrality);
+        let ratio = d2 / d1;
+        Some(
+            2.0 * ratio * ratio * ((d1 + lambda).powi(2) + (d1 + 2.0 * lambda) * (d2 - 2.0))
+                / ((d2 - 2.0).powi(2) * (d2 - 4.0)),
+        )
+    }
+}
+
+impl Median<f64> for NoncentralFisherSnedecor {
+    /// Returns the median of the noncentral fisher-snedecor distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(0.5)
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for NoncentralFisherSnedecor {
+    /// Calculates the probability density function for the noncentral
+    /// fisher-snedecor distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// d1 d2 / (d1 x + d2)^2 * Σ_j e^(-λ/2) (λ/2)^j / j! * z^(d1/2 + j - 1) (1 - z)^(d2/2 - 1) / β(d1/2 + j, d2/2)
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom, `λ` the
+    /// noncentrality, `β` the beta function and `z = d1 x / (d1 x + d2)`.
+    /// The sum starts at its largest term and proceeds in both directions
+    /// until the terms fall below `1e-17` of the partial sum.
+    fn pdf(&self, x: f64) -> f64 {
+        if self.noncentrality == 0.0 {
+            self.central().pdf(x)
+        } else {
+            self.ln_pdf(x).exp()
+        }
+    }
+
+    /// Calculates the log probability density function for the noncentral
+    /// fisher-snedecor distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(x))
+    /// ```
+    ///
+    /// where `f` is the pdf
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if self.noncentrality == 0.0 {
+            return self.central().ln_pdf(x);
+        }
+        if x <= 0.0 || x.is_infinite() {
+            return f64::NEG_INFINITY;
+        }
+        let (z, y) = self.beta_argument(x);
+        let (a, b) = (0.5 * self.freedom_1, 0.5 * self.freedom_2);
+        let h = 0.5 * self.noncentrality;
+        // ratio of consecutive terms, whose logarithm decreases in `j`
+        let ratio = |j: f64| h / (j + 1.0) * z * (a + j + b) / (a + j);
+        let mut mode = h.floor();
+        while ratio(mode) > 1.0 {
+            mode += 1.0;
+        }
+        while mode > 0.0 && ratio(mode - 1.0) < 1.0 {
+            mode -= 1.0;
+        }
+        let ln_mode_term = -h + mode * h.ln() - gamma::ln_gamma(mode + 1.0)
+            + (a + mode - 1.0) * z.ln()
+            + (b - 1.0) * y.ln()
+            - beta::ln_beta(a + mode, b);
+
+        let mut sum = 1.0;
+        let (mut term, mut j) = (1.0, mode);
+        for _ in 0..SERIES_MAX_TERMS {
+            term *= ratio(j);
+            j += 1.0;
+            sum += term;
+            if term <= SERIES_EPSILON * sum {
+                break;
+            }
+        }
+        let (mut term, mut j) = (1.0, mode);
+        while j > 0.0 {
+            j -= 1.0;
+            term /= ratio(j);
+            sum += term;
+            if term <= SERIES_EPSILON * sum {
+                break;
+            }
+        }
+        ln_mode_term + sum.ln() + (self.freedom_1 * self.freedom_2).ln()
+            - 2.0 * (self.freedom_1 * x + self.freedom_2).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::function::integrate::integrate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn try_create(freedom_1: f64, freedom_2: f64, noncentrality: f64) -> NoncentralFisherSnedecor {
+        let n = NoncentralFisherSnedecor::new(freedom_1, freedom_2, noncentrality);
+        assert!(n.is_ok());
+        n.unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        try_create(1.0, 1.0, 0.0);
+        try_create(0.5, 3.0, 200.0);
+        assert!(NoncentralFisherSnedecor::new(0.0, 1.0, 1.0).is_err());
+        assert!(NoncentralFisherSnedecor::new(1.0, -1.0, 1.0).is_err());
+        assert!(NoncentralFisherSnedecor::new(f64::INFINITY, 1.0, 1.0).is_err());
+        assert!(NoncentralFisherSnedecor::new(1.0, f64::NAN, 1.0).is_err());
+        assert!(NoncentralFisherSnedecor::new(1.0, 1.0, -0.5).is_err());
+        assert!(NoncentralFisherSnedecor::new(1.0, 1.0, f64::NAN).is_err());
+        assert!(NoncentralFisherSnedecor::new(1.0, 1.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_reduces_to_central() {
+        for &(d1, d2) in &[(1.0, 1.0), (3.0, 10.0), (0.5, 4.5), (20.0, 60.0)] {
+            let n = try_create(d1, d2, 0.0);
+            let f = FisherSnedecor::new(d1, d2).unwrap();
+            for &x in &[0.0, 0.01, 0.5, 1.0, 2.5, 10.0, 1000.0] {
+                assert_eq!(n.pdf(x), f.pdf(x));
+                assert_eq!(n.ln_pdf(x), f.ln_pdf(x));
+                assert_eq!(n.cdf(x), f.cdf(x));
+                assert_eq!(n.sf(x), f.sf(x));
+            }
+            assert_eq!(n.inverse_cdf(0.9), f.inverse_cdf(0.9));
+            assert_eq!(n.mean(), f.mean());
+            if let Some(v) = f.variance() {
+                assert_almost_eq!(n.variance().unwrap(), v, 1e-14 * v);
+            }
+        }
+        // the series agrees with the central distribution as λ → 0
+        let n = try_create(3.0, 10.0, 1e-12);
+        let f = FisherSnedecor::new(3.0, 10.0).unwrap();
+        for &x in &[0.2, 1.0, 4.0] {
+            assert_almost_eq!(n.pdf(x), f.pdf(x), 1e-11);
+            assert_almost_eq!(n.cdf(x), f.cdf(x), 1e-11);
+        }
+    }
+
+    // reference values computed with mpmath by summing the Poisson mixture
+    // at 40 digits
+    const CASES: [(f64, f64, f64, f64, f64, f64); 13] = [
+        (3.0, 10.0, 2.0, 0.5, 0.39378206392788663325, 0.15514329671239541502),
+        (3.0, 10.0, 2.0, 2.0, 0.21156750179827033146, 0.63136085282770099509),
+        (3.0, 10.0, 2.0, 8.0, 0.0081809613690175283251, 0.97797798050612620553),
+        (5.0, 20.0, 10.0, 0.3, 0.017834783438909563124, 0.0018927367873845236444),
+        (5.0, 20.0, 10.0, 3.0, 0.22770561126805375621, 0.524036069210167481),
+        (5.0, 20.0, 10.0, 12.0, 0.002029650770377205339, 0.99557427349769701536),
+        (1.0, 5.0, 4.0, 0.05, 0.25019866392161429578, 0.023659869973431750493),
+        (1.0, 5.0, 4.0, 6.0, 0.056516592154215327231, 0.59928463153981777919),
+        (2.0, 4.0, 0.5, 1.0, 0.29348183260096393308, 0.48594321243720437584),
+        (10.0, 30.0, 100.0, 5.0, 0.010147327885416294657, 0.005636122040526118198),
+        (10.0, 30.0, 100.0, 12.0, 0.10007176191636386224, 0.59257442253451774769),
+        (10.0, 30.0, 100.0, 25.0, 0.0024892056795679939886, 0.99184794298217544948),
+        (4.0, 2.5, 3.0, 40.0, 0.00071288687675922514089, 0.97634325761866518518),
+    ];
+
+    #[test]
+    fn test_pdf() {
+        for &(d1, d2, lambda, x, pdf, _) in &CASES {
+            let n = try_create(d1, d2, lambda);
+            assert_almost_eq!(n.pdf(x), pdf, 1e-12 * pdf);
+            assert_almost_eq!(n.ln_pdf(x), pdf.ln(), 1e-12);
+        }
+        let n = try_create(3.0, 10.0, 2.0);
+        assert_eq!(n.pdf(0.0), 0.0);
+        assert_eq!(n.pdf(-1.0), 0.0);
+        assert_eq!(n.pdf(f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_cdf() {
+        for &(d1, d2, lambda, x, _, cdf) in &CASES {
+            let n = try_create(d1, d2, lambda);
+            assert_almost_eq!(n.cdf(x), cdf, 1e-12);
+            assert_almost_eq!(n.sf(x), 1.0 - cdf, 1e-12);
+        }
+        let n = try_create(3.0, 10.0, 2.0);
+        assert_eq!(n.cdf(0.0), 0.0);
+        assert_eq!(n.sf(0.0), 1.0);
+        assert_eq!(n.cdf(f64::INFINITY), 1.0);
+        assert_eq!(n.sf(f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &(d1, d2, lambda, x, pdf, cdf) in &CASES {
+            let n = try_create(d1, d2, lambda);
+            let quantile = n.inverse_cdf(cdf);
+            assert_almost_eq!(n.cdf(quantile), cdf, 1e-14);
+            assert_almost_eq!(quantile, x, 1e-12 / pdf);
+        }
+        let n = try_create(4.0, 6.0, 1.0);
+        assert_eq!(n.inverse_cdf(0.0), 0.0);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_mean() {
+        // d2 (d1 + λ) / (d1 (d2 - 2)) = 10 * 5 / (3 * 8)
+        let n = try_create(3.0, 10.0, 2.0);
+        assert_almost_eq!(n.mean().unwrap(), 50.0 / 24.0, 1e-15);
+        // the mean and variance are the moments of the density
+        for &(d1, d2, lambda) in &[(3.0, 10.0, 2.0), (5.0, 20.0, 10.0), (1.5, 9.0, 0.7)] {
+            let n = try_create(d1, d2, lambda);
+            let mean = n.mean().unwrap();
+            let first = integrate(|x| x * n.pdf(x), 0.0, f64::INFINITY, 1e-12);
+            let second = integrate(|x| (x - mean) * (x - mean) * n.pdf(x), 0.0, f64::INFINITY, 1e-12);
+            assert_almost_eq!(mean, first, 1e-9);
+            assert_almost_eq!(n.variance().unwrap(), second, 1e-8);
+        }
+        assert_eq!(try_create(3.0, 2.0, 1.0).mean(), None);
+        assert_eq!(try_create(3.0, 4.0, 1.0).variance(), None);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = try_create(4.0, 12.0, 6.0);
+        let count = 20000;
+        let samples: Vec<f64> = (0..count).map(|_| rand::distributions::Distribution::sample(&n, &mut rng)).collect();
+        let below = samples.iter().filter(|&&x| x <= 2.0).count() as f64 / count as f64;
+        assert!((below - n.cdf(2.0)).abs() < 0.015);
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        assert!((mean - n.mean().unwrap()).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(3.0, 10.0, 2.0), 0.0, 30.0);
+        test::check_continuous_distribution(&try_create(10.0, 30.0, 100.0), 0.0, 40.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 5.0, 1.5);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("noncentrality", -1.0), NoncentralFisherSnedecor::new(3.0, 5.0, -1.0));
+    }
+}
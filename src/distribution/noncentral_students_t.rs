@@ -0,0 +1,918 @@
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized, StudentsT};
+use crate::function::integrate::integrate;
+use crate::function::{beta, erf, gamma, normal, root};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Relative size below which the remaining terms of a series are dropped
+const SERIES_EPSILON: f64 = 1e-17;
+
+/// Maximum number of terms summed on each side of the mode of a series
+const SERIES_MAX_TERMS: usize = 100_000;
+
+/// Implements the [noncentral Student's
+/// T](https://en.wikipedia.org/wiki/Noncentral_t-distribution) distribution
+/// of `(Z + δ) / sqrt(V / ν)` where `Z` is standard normal and `V` is an
+/// independent chi-squared variable with `ν` degrees of freedom
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, NoncentralStudentsT};
+/// use statrs::statistics::Distribution;
+/// use statrs::prec;
+///
+/// let n = NoncentralStudentsT::new(10.0, 2.0).unwrap();
+/// assert!(prec::almost_eq(n.mean().unwrap(), 2.1674446158782867, 1e-14));
+/// assert!(prec::almost_eq(n.cdf(2.0), 0.48097315281790721, 1e-11));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct NoncentralStudentsT {
+    freedom: f64,
+    noncentrality: f64,
+}
+
+impl NoncentralStudentsT {
+    /// Constructs a new noncentral student's t-distribution with `freedom`
+    /// degrees of freedom and noncentrality `noncentrality`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `freedom` is not positive and finite, or if
+    /// `noncentrality` is not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralStudentsT;
+    ///
+    /// let mut result = NoncentralStudentsT::new(5.0, -1.5);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NoncentralStudentsT::new(0.0, 1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(freedom: f64, noncentrality: f64) -> Result<NoncentralStudentsT> {
+        if freedom.is_nan() || freedom <= 0.0 || freedom.is_infinite() {
+            return Err(StatsError::BadParams);
+        }
+        if !noncentrality.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        Ok(NoncentralStudentsT {
+            freedom,
+            noncentrality,
+        })
+    }
+
+    /// Returns the degrees of freedom of the noncentral student's
+    /// t-distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralStudentsT;
+    ///
+    /// let n = NoncentralStudentsT::new(5.0, -1.5).unwrap();
+    /// assert_eq!(n.freedom(), 5.0);
+    /// ```
+    pub fn freedom(&self) -> f64 {
+        self.freedom
+    }
+
+    /// Returns the noncentrality of the noncentral student's t-distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralStudentsT;
+    ///
+    /// let n = NoncentralStudentsT::new(5.0, -1.5).unwrap();
+    /// assert_eq!(n.noncentrality(), -1.5);
+    /// ```
+    pub fn noncentrality(&self) -> f64 {
+        self.noncentrality
+    }
+
+    /// Returns the central student's t-distribution with the same degrees
+    /// of freedom
+    fn central(&self) -> StudentsT {
+        StudentsT::new(0.0, 1.0, self.freedom).unwrap()
+    }
+}
+
+/// Returns `x = t^2 / (ν + t^2)` and `1 - x = ν / (ν + t^2)`, both computed
+/// from `ν / t^2` so that neither is lost to the overflow or underflow of
+/// `t^2`
+fn beta_arguments(t: f64, freedom: f64) -> (f64, f64) {
+    let ratio = freedom / t / t;
+    if ratio.is_infinite() {
+        (0.0, 1.0)
+    } else {
+        (1.0 / (1.0 + ratio), ratio / (1.0 + ratio))
+    }
+}
+
+/// Computes `P(T <= t)` for `t >= 0` as the Poisson mixture
+///
+/// ```text
+/// Φ(-δ) + 1/2 Σ_i [P_i I_x(i + 1/2, ν/2) + δ / sqrt(2) Q_i I_x(i + 1, ν/2)]
+/// ```
+///
+/// where `x = t^2 / (ν + t^2)`, `P_i = e^(-λ) λ^i / i!`,
+/// `Q_i = e^(-λ) λ^i / Γ(i + 3/2)` and `λ = δ^2 / 2`
+fn lower_tail(t: f64, freedom: f64, delta: f64) -> f64 {
+    let base = super::normal::cdf_unchecked(-delta, 0.0, 1.0);
+    let (x, y) = beta_arguments(t, freedom);
+    if x == 0.0 {
+        base
+    } else if y == 0.0 {
+        1.0
+    } else {
+        base + 0.5 * poisson_mixture(x, y, freedom, delta, false)
+    }
+}
+
+/// Computes `P(T > t)` for `t >= 0`. For `δ > 0` this is the Poisson
+/// mixture of `lower_tail` with each `I_x(a, ν/2)` replaced by its
+/// complement `I_(1-x)(ν/2, a)`, whose terms are all positive; for `δ < 0`
+/// those terms cancel and the tail is obtained by quadrature instead.
+fn upper_tail(t: f64, freedom: f64, delta: f64) -> f64 {
+    let (x, y) = beta_arguments(t, freedom);
+    if x == 0.0 {
+        super::normal::cdf_unchecked(delta, 0.0, 1.0)
+    } else if y == 0.0 {
+        0.0
+    } else if delta > 0.0 {
+        0.5 * poisson_mixture(x, y, freedom, delta, true)
+    } else {
+        upper_tail_quadrature(t, freedom, delta)
+    }
+}
+
+/// Sums `Σ_i [P_i B(i + 1/2) + δ / sqrt(2) Q_i B(i + 1)]` where `B(a)` is
+/// `I_x(a, ν/2)`, or `I_y(ν/2, a)` with `y = 1 - x` when `upper` is set.
+/// Following Benton and Krishnamoorthy (2003) the sum starts at the mode of
+/// the Poisson weights and proceeds in both directions, updating the
+/// incomplete beta functions by recurrence, so that large noncentralities
+/// do not underflow.
+fn poisson_mixture(x: f64, y: f64, freedom: f64, delta: f64, upper: bool) -> f64 {
+    let ln_x = x.ln();
+    let ln_y = y.ln();
+    let b = 0.5 * freedom;
+    let lambda = 0.5 * delta * delta;
+    let k = lambda.floor();
+    let ln_poisson = if lambda == 0.0 {
+        0.0
+    } else {
+        -lambda + k * lambda.ln()
+    };
+    let p_mode = (ln_poisson - gamma::ln_gamma(k + 1.0)).exp();
+    let q_mode = delta / f64::consts::SQRT_2 * (ln_poisson - gamma::ln_gamma(k + 1.5)).exp();
+    // `step(a)` is `I_x(a, b) - I_x(a + 1, b)`, which `I_y(b, a)` gains
+    // from `a` to `a + 1` as `I_x(a, b)` loses it
+    let step = |a: f64| {
+        (gamma::ln_gamma(a + b) - gamma::ln_gamma(a + 1.0) - gamma::ln_gamma(b)
+            + a * ln_x
+            + b * ln_y)
+            .exp()
+    };
+    let sign = if upper { 1.0 } else { -1.0 };
+    let incomplete = |a: f64| {
+        if upper {
+            beta::beta_reg(b, a, y)
+        } else {
+            beta::beta_reg(a, b, x)
+        }
+    };
+    let (a_mode, c_mode) = (k + 0.5, k + 1.0);
+    let ia_mode = incomplete(a_mode);
+    let ic_mode = incomplete(c_mode);
+    let mut sum = p_mode * ia_mode + q_mode * ic_mode;
+
+    // forward from the mode
+    let (mut p, mut q) = (p_mode, q_mode);
+    let (mut a, mut c) = (a_mode, c_mode);
+    let (mut ia, mut ic) = (ia_mode, ic_mode);
+    let (mut da, mut dc) = (step(a), step(c));
+    let mut i = k;
+    for _ in 0..SERIES_MAX_TERMS {
+        i += 1.0;
+        ia += sign * da;
+        ic += sign * dc;
+        da *= x * (a + b) / (a + 1.0);
+        dc *= x * (c + b) / (c + 1.0);
+        a += 1.0;
+        c += 1.0;
+        p *= lambda / i;
+        q *= lambda / (i + 0.5);
+        let term = p * ia.clamp(0.0, 1.0) + q * ic.clamp(0.0, 1.0);
+        sum += term;
+        if term.abs() <= SERIES_EPSILON * sum.abs() || p + q.abs() == 0.0 {
+            break;
+        }
+    }
+
+    // backward from the mode
+    let (mut p, mut q) = (p_mode, q_mode);
+    let (mut a, mut c) = (a_mode, c_mode);
+    let (mut ia, mut ic) = (ia_mode, ic_mode);
+    let (mut da, mut dc) = (step(a - 1.0), step(c - 1.0));
+    let mut i = k;
+    while i > 0.0 {
+        ia -= sign * da;
+        ic -= sign * dc;
+        a -= 1.0;
+        c -= 1.0;
+        da *= (a - 1.0 + 1.0) / (x * (a - 1.0 + b));
+        dc *= (c - 1.0 + 1.0) / (x * (c - 1.0 + b));
+        p *= i / lambda;
+        q *= (i + 0.5) / lambda;
+        i -= 1.0;
+        sum += p * ia.clamp(0.0, 1.0) + q * ic.clamp(0.0, 1.0);
+        if p + q.abs() <= SERIES_EPSILON * sum.abs() {
+            break;
+        }
+    }
+    sum
+}
+
+/// Computes `P(T > t)` for `t > 0` by quadrature of `E[Φ(δ - t S)]`, where
+/// `S = sqrt(V / ν)`, over `u = ln(S)`
+fn upper_tail_quadrature(t: f64, nu: f64, delta: f64) -> f64 {
+    // the log integrand is concave in `u`, so it is rescaled by its maximum
+    // for the quadrature to be relative and cut where it has fallen by
+    // `DROP` on either side
+    const DROP: f64 = 50.0;
+    let ln_kernel = |u: f64| {
+        super::normal::ln_sf_std(t * u.exp() - delta) + nu * u - 0.5 * nu * (2.0 * u).exp()
+    };
+    let slope = |u: f64| {
+        let s = t * u.exp();
+        -s * normal::inverse_mills_ratio(s - delta) + nu * (1.0 - (2.0 * u).exp())
+    };
+    // the slope is negative at `u = 0` and tends to `ν` as `u → -∞`
+    let mut below = -1.0;
+    while slope(below) <= 0.0 && below > -1e3 {
+        below *= 2.0;
+    }
+    let peak = root::brent(slope, below, 0.0, 1e-12).unwrap_or(below);
+    let top = ln_kernel(peak);
+    let s = t * peak.exp();
+    let hazard = normal::inverse_mills_ratio(s - delta);
+    let curvature =
+        2.0 * nu * (2.0 * peak).exp() + s * hazard + s * s * hazard * (hazard - s + delta);
+    let width = 1.0 / curvature.max(f64::MIN_POSITIVE).sqrt();
+    let mut lo = width;
+    while ln_kernel(peak - lo) - top > -DROP && lo < 1e3 {
+        lo *= 2.0;
+    }
+    let mut hi = width;
+    while ln_kernel(peak + hi) - top > -DROP && hi < 1e3 {
+        hi *= 2.0;
+    }
+    let integral = integrate(|u| (ln_kernel(u) - top).exp(), peak - lo, peak + hi, 1e-14);
+    let ln_constant = f64::consts::LN_2 + 0.5 * nu * (0.5 * nu).ln() - gamma::ln_gamma(0.5 * nu);
+    (ln_constant + top + integral.ln()).exp()
+}
+
+impl Parameterized for NoncentralStudentsT {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("freedom".into(), self.freedom),
+            ("noncentrality".into(), self.noncentrality),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<NoncentralStudentsT> {
+        match *values {
+            [freedom, noncentrality] => NoncentralStudentsT::new(freedom, noncentrality),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for NoncentralStudentsT {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "t'_{}({})", self.freedom, self.noncentrality)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for NoncentralStudentsT {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        let chi_squared = super::gamma::sample_unchecked(r, 0.5 * self.freedom, 0.5);
+        let z = super::normal::sample_unchecked(r, self.noncentrality, 1.0);
+        z / (chi_squared / self.freedom).sqrt()
+    }
+}
+
+impl ContinuousCDF<f64, f64> for NoncentralStudentsT {
+    /// Calculates the cumulative distribution function for the noncentral
+    /// student's t-distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Φ(-δ) + 1/2 Σ_i [P_i I_z(i + 1/2, ν/2) + δ / sqrt(2) Q_i I_z(i + 1, ν/2)]
+    /// ```
+    ///
+    /// for `x >= 0` and the survival function `S(-x; ν, -δ)` otherwise,
+    /// where `ν` is the degrees of freedom, `δ` the noncentrality, `Φ` the
+    /// standard normal cdf, `I` the regularized incomplete beta function,
+    /// `z = x^2 / (ν + x^2)`, `P_i = e^(-δ^2/2) (δ^2/2)^i / i!` and
+    /// `Q_i = e^(-δ^2/2) (δ^2/2)^i / Γ(i + 3/2)`. The infinite sum is
+    /// truncated once its terms fall below `1e-17` of the partial sum.
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if x == f64::NEG_INFINITY {
+            0.0
+        } else if x == f64::INFINITY {
+            1.0
+        } else if self.noncentrality == 0.0 {
+            self.central().cdf(x)
+        } else if x >= 0.0 {
+            lower_tail(x, self.freedom, self.noncentrality).clamp(0.0, 1.0)
+        } else {
+            upper_tail(-x, self.freedom, -self.noncentrality).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the survival function for the noncentral student's
+    /// t-distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1/2 Σ_i [P_i I_w(ν/2, i + 1/2) + δ / sqrt(2) Q_i I_w(ν/2, i + 1)]
+    /// ```
+    ///
+    /// for `x >= 0` and `δ > 0`, where `w = ν / (ν + x^2)` and the rest is
+    /// as in the cdf. For `δ < 0` the terms cancel and the survival function
+    /// is instead obtained by quadrature of `E[Φ(δ - x sqrt(V / ν))]`, and
+    /// for `x < 0` it is the cdf `F(-x; ν, -δ)`.
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if x == f64::NEG_INFINITY {
+            1.0
+        } else if x == f64::INFINITY {
+            0.0
+        } else if self.noncentrality == 0.0 {
+            self.central().sf(x)
+        } else if x >= 0.0 {
+            upper_tail(x, self.freedom, self.noncentrality).clamp(0.0, 1.0)
+        } else {
+            lower_tail(-x, self.freedom, -self.noncentrality).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// noncentral student's t-distribution at `p` by safeguarded Newton
+    /// iteration on the cdf, or on the survival function for `p > 0.5`,
+    /// starting from the standard normal quantile shifted by the
+    /// noncentrality
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if p == 1.0 {
+            return f64::INFINITY;
+        }
+        if self.noncentrality == 0.0 {
+            return self.central().inverse_cdf(p);
+        }
+        // increasing in `x` and evaluated on the smaller tail, so that
+        // probabilities near 1 keep their relative accuracy
+        let error = |x: f64| {
+            if p <= 0.5 {
+                self.cdf(x) - p
+            } else {
+                (1.0 - p) - self.sf(x)
+            }
+        };
+        let mut x = self.noncentrality - f64::consts::SQRT_2 * erf::erfc_inv(2.0 * p);
+        let mut width = x.abs().max(1.0);
+        let mut lo = x - width;
+        while error(lo) > 0.0 && lo > f64::MIN {
+            width *= 2.0;
+            lo = (lo - width).max(f64::MIN);
+        }
+        let mut hi = x + width;
+        while error(hi) < 0.0 && hi < f64::MAX {
+            width *= 2.0;
+            hi = (hi + width).min(f64::MAX);
+        }
+        x = x.clamp(lo, hi);
+        for _ in 0..200 {
+            let error = error(x);
+            if error < 0.0 {
+                lo = x;
+            } else {
+                hi = x;
+            }
+            let newton = x - error / self.pdf(x);
+            // far in the tails the bracket spans orders of magnitude and is
+            // first narrowed by bisection of the exponent
+            let next = if lo * hi > 0.0 && (hi / lo).max(lo / hi) > 2.0 {
+                lo.signum() * lo.abs().sqrt() * hi.abs().sqrt()
+            } else if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * lo + 0.5 * hi
+            };
+            let step = (next - x).abs();
+            x = next;
+            if step <= 1e-14 * x.abs().max(1.0) || hi - lo <= 1e-14 * x.abs().max(1.0) {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl Min<f64> for NoncentralStudentsT {
+    /// Returns the minimum value in the domain of the noncentral student's
+    /// t-distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::NEG_INFINITY
+    /// ```
+    fn min(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+}
+
+impl Max<f64> for NoncentralStudentsT {
+    /// Returns the maximum value in the domain of the noncentral student's
+    /// t-distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for NoncentralStudentsT {
+    /// Returns the mean of the noncentral student's t-distribution
+    ///
+    /// # None
+    ///
+    /// If `freedom <= 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// δ * sqrt(ν / 2) * Γ((ν - 1) / 2) / Γ(ν / 2)
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom, `δ` the noncentrality and `Γ`
+    /// the gamma function
+    fn mean(&self) -> Option<f64> {
+        if self.freedom <= 1.0 {
+            return None;
+        }
+        let ratio =
+            gamma::ln_gamma(0.5 * (self.freedom - 1.0)) - gamma::ln_gamma(0.5 * self.freedom);
+        Some(self.noncentrality * (0.5 * self.freedom).sqrt() * ratio.exp())
+    }
+
+    /// Returns the variance of the noncentral student's t-distribution
+    ///
+    /// # None
+    ///
+    /// If `freedom <= 2.0`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ν (1 + δ^2) / (ν - 2) - μ^2
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom, `δ` the noncentrality and `μ`
+    /// the mean
+    fn variance(&self) -> Option<f64> {
+        if self.freedom <= 2.0 {
+            return None;
+        }
+        let mean = self.mean()?;
+        let second =
+            self.freedom * (1.0 + self.noncentrality * self.noncentrality) / (self.freedom - 2.0);
+        Some(second - mean * mean)
+    }
+}
+
+impl Median<f64> for NoncentralStudentsT {
+    /// Returns the median of the noncentral student's t-distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(0.5)
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for NoncentralStudentsT {
+    /// Calculates the probability density function for the noncentral
+    /// student's t-distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ν^(ν/2) e^(-δ^2/2) / (sqrt(π) Γ(ν/2) (ν + x^2)^((ν+1)/2))
+    ///     * Σ_j Γ((ν + j + 1) / 2) / j! * (δ x sqrt(2 / (ν + x^2)))^j
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom and `δ` the noncentrality. The
+    /// terms of the series are positive when `δ x >= 0`; otherwise they
+    /// alternate, and where they cancel too much the density is instead
+    /// obtained by quadrature of `∫ u φ(x u - δ) g(u) du` over `u > 0`, `g`
+    /// being the density of `sqrt(V / ν)`
+    fn pdf(&self, x: f64) -> f64 {
+        if self.noncentrality == 0.0 {
+            self.central().pdf(x)
+        } else {
+            self.ln_pdf(x).exp()
+        }
+    }
+
+    /// Calculates the log probability density function for the noncentral
+    /// student's t-distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(x))
+    /// ```
+    ///
+    /// where `f` is the pdf
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if x.is_infinite() {
+            return f64::NEG_INFINITY;
+        }
+        if self.noncentrality == 0.0 {
+            return self.central().ln_pdf(x);
+        }
+        let nu = self.freedom;
+        let delta = self.noncentrality;
+        let q = delta * x * (2.0 / (nu + x * x)).sqrt();
+        let series = if q >= 0.0 {
+            Some(ln_series_positive(nu, q))
+        } else {
+            ln_series_alternating(nu, q)
+        };
+        match series {
+            Some(ln_sum) => {
+                0.5 * nu * nu.ln()
+                    - 0.5 * delta * delta
+                    - 0.5 * f64::consts::PI.ln()
+                    - gamma::ln_gamma(0.5 * nu)
+                    - 0.5 * (nu + 1.0) * (nu + x * x).ln()
+                    + ln_sum
+            }
+            None => ln_pdf_quadrature(x, nu, delta),
+        }
+    }
+}
+
+/// Computes the logarithms of the first two terms of
+/// `Σ_j Γ((ν + j + 1) / 2) / j! |q|^j` and the increment of the logarithm
+/// from term `j` to term `j + 2`
+fn series_terms(nu: f64, q: f64) -> ([f64; 2], impl Fn(f64) -> f64) {
+    let ln_q = q.abs().ln();
+    let first = [
+        gamma::ln_gamma(0.5 * (nu + 1.0)),
+        gamma::ln_gamma(0.5 * (nu + 2.0)) + ln_q,
+    ];
+    let increment =
+        move |j: f64| 2.0 * ln_q + (0.5 * (nu + j + 1.0) / ((j + 1.0) * (j + 2.0))).ln();
+    (first, increment)
+}
+
+/// Computes `ln(Σ_j Γ((ν + j + 1) / 2) / j! q^j)` for `q >= 0` by a
+/// streaming log-sum-exp of the positive terms
+fn ln_series_positive(nu: f64, q: f64) -> f64 {
+    if q == 0.0 {
+        return gamma::ln_gamma(0.5 * (nu + 1.0));
+    }
+    let (mut terms, increment) = series_terms(nu, q);
+    let (mut max, mut sum) = (f64::NEG_INFINITY, 0.0);
+    for j in 0..SERIES_MAX_TERMS {
+        let term = terms[j % 2];
+        terms[j % 2] += increment(j as f64);
+        if term > max {
+            sum = sum * (max - term).exp() + 1.0;
+            max = term;
+        } else {
+            let ratio = (term - max).exp();
+            sum += ratio;
+            if ratio <= SERIES_EPSILON * sum {
+                break;
+            }
+        }
+    }
+    max + sum.ln()
+}
+
+/// Computes `ln(Σ_j Γ((ν + j + 1) / 2) / j! q^j)` for `q < 0`, or returns
+/// `None` when the largest alternating term exceeds the sum by more than
+/// `CANCELLATION` and too many digits would be lost
+fn ln_series_alternating(nu: f64, q: f64) -> Option<f64> {
+    const CANCELLATION: f64 = 100.0;
+    let (mut terms, increment) = series_terms(nu, q);
+    let ln_first = terms[0];
+    let (mut sum, mut largest, mut previous) = (0.0, 0.0f64, f64::INFINITY);
+    for j in 0..SERIES_MAX_TERMS {
+        let magnitude = (terms[j % 2] - ln_first).exp();
+        terms[j % 2] += increment(j as f64);
+        if magnitude > CANCELLATION * CANCELLATION {
+            return None;
+        }
+        sum += if j % 2 == 0 { magnitude } else { -magnitude };
+        largest = largest.max(magnitude);
+        if magnitude < previous && magnitude <= SERIES_EPSILON * sum.abs() {
+            break;
+        }
+        previous = magnitude;
+    }
+    if sum > 0.0 && largest <= CANCELLATION * sum {
+        Some(ln_first + sum.ln())
+    } else {
+        None
+    }
+}
+
+/// Computes the log density at `x` by quadrature of
+/// `∫ u φ(x u - δ) g(u) du` over `u > 0`, where `g` is the density of
+/// `sqrt(V / ν)`
+fn ln_pdf_quadrature(x: f64, nu: f64, delta: f64) -> f64 {
+    // the integrand peaks where its log-derivative vanishes, so it is
+    // rescaled by its maximum for the quadrature to be relative
+    let a = nu + x * x;
+    let peak = (x * delta + (x * x * delta * delta + 4.0 * nu * a).sqrt()) / (2.0 * a);
+    let ln_kernel =
+        |u: f64| -0.5 * (x * u - delta) * (x * u - delta) + nu * u.ln() - 0.5 * nu * u * u;
+    let top = ln_kernel(peak);
+    // the kernel is log-concave, so it is negligible beyond 20 widths of
+    // its curvature at the peak
+    let width = 1.0 / (a + nu / (peak * peak)).sqrt();
+    let integral = integrate(
+        |u| {
+            if u <= 0.0 {
+                0.0
+            } else {
+                (ln_kernel(u) - top).exp()
+            }
+        },
+        (peak - 20.0 * width).max(0.0),
+        peak + 20.0 * width,
+        1e-14,
+    );
+    let ln_constant = (1.0 - 0.5 * nu) * f64::consts::LN_2 + 0.5 * nu * nu.ln()
+        - gamma::ln_gamma(0.5 * nu)
+        - 0.5 * (2.0 * f64::consts::PI).ln();
+    ln_constant + top + integral.ln()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn try_create(freedom: f64, noncentrality: f64) -> NoncentralStudentsT {
+        let n = NoncentralStudentsT::new(freedom, noncentrality);
+        assert!(n.is_ok());
+        n.unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        try_create(1.0, 0.0);
+        try_create(0.3, -4.0);
+        try_create(1e5, 50.0);
+        assert!(NoncentralStudentsT::new(0.0, 1.0).is_err());
+        assert!(NoncentralStudentsT::new(-1.0, 1.0).is_err());
+        assert!(NoncentralStudentsT::new(f64::INFINITY, 1.0).is_err());
+        assert!(NoncentralStudentsT::new(f64::NAN, 1.0).is_err());
+        assert!(NoncentralStudentsT::new(5.0, f64::NAN).is_err());
+        assert!(NoncentralStudentsT::new(5.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_reduces_to_central() {
+        for &freedom in &[0.5, 1.0, 3.5, 30.0] {
+            let n = try_create(freedom, 0.0);
+            let t = StudentsT::new(0.0, 1.0, freedom).unwrap();
+            for &x in &[-20.0, -2.5, -0.1, 0.0, 0.7, 3.0, 100.0] {
+                assert_eq!(n.pdf(x), t.pdf(x));
+                assert_eq!(n.ln_pdf(x), t.ln_pdf(x));
+                assert_eq!(n.cdf(x), t.cdf(x));
+                assert_eq!(n.sf(x), t.sf(x));
+            }
+            assert_eq!(n.inverse_cdf(0.9), t.inverse_cdf(0.9));
+        }
+        // the series agrees with the central distribution as δ → 0
+        let n = try_create(7.0, 1e-12);
+        let t = StudentsT::new(0.0, 1.0, 7.0).unwrap();
+        for &x in &[-3.0, -0.2, 0.4, 2.0] {
+            assert_almost_eq!(n.pdf(x), t.pdf(x), 1e-11);
+            assert_almost_eq!(n.cdf(x), t.cdf(x), 1e-11);
+        }
+    }
+
+    // reference values computed with mpmath by quadrature of the mixture
+    // representation
+    const CASES: [(f64, f64, f64, f64, f64); 20] = [
+        (10.0, 2.0, -1.0, 0.0048090241205533645433, 0.0018164334104296490679),
+        (10.0, 2.0, 0.0, 0.052660093353783456791, 0.0227501319481792072),
+        (10.0, 2.0, 0.5, 0.12966367710231645662, 0.066401776593194396483),
+        (10.0, 2.0, 2.0, 0.35564363036162998074, 0.48097315281790721174),
+        (10.0, 2.0, 5.0, 0.027470355161998905698, 0.97618725414641737735),
+        (10.0, 2.0, 15.0, 2.4192908042749608389e-6, 0.99999610212657691553),
+        (3.5, -1.5, -8.0, 0.0051134773338533220712, 0.012718433185410681161),
+        (3.5, -1.5, -1.5, 0.3249722173332008854, 0.53896348490551093185),
+        (3.5, -1.5, 0.0, 0.12069680868822125583, 0.933192798731141934),
+        (3.5, -1.5, 1.0, 0.018516708101190910182, 0.98923303199266069169),
+        (3.5, -1.5, 4.0, 0.00024309611659910446961, 0.999697173283946639),
+        (50.0, 10.0, 5.0, 0.000019660760857398952271, 3.935164475203456574e-6),
+        (50.0, 10.0, 9.0, 0.22746224385068308307, 0.21786041067803217069),
+        (50.0, 10.0, 10.0, 0.28096454951012080928, 0.48354940311976257065),
+        (50.0, 10.0, 12.0, 0.10778431492038403826, 0.8939939602826094285),
+        (50.0, 10.0, 20.0, 1.8687113483743491694e-6, 0.99999881125140498656),
+        (20.0, 40.0, 30.0, 0.011652015887818624618, 0.019075453843711087627),
+        (20.0, 40.0, 40.0, 0.061787101970346907702, 0.45869328246719446478),
+        (20.0, 40.0, 45.0, 0.042764768669055731646, 0.72689132504422561423),
+        (20.0, 40.0, 60.0, 0.0033396402534244726445, 0.98354167944227495985),
+    ];
+
+    #[test]
+    fn test_pdf() {
+        for &(freedom, delta, x, pdf, _) in &CASES {
+            let n = try_create(freedom, delta);
+            assert_almost_eq!(n.pdf(x), pdf, 1e-11 * pdf);
+            assert_almost_eq!(n.ln_pdf(x), pdf.ln(), 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_cdf() {
+        // the references agree to 1e-11, the accuracy of `erfc` in `Φ(-δ)`,
+        // and so do the two tails, which are computed separately
+        for &(freedom, delta, x, _, cdf) in &CASES {
+            let n = try_create(freedom, delta);
+            assert_almost_eq!(n.cdf(x), cdf, 1e-11);
+            assert_almost_eq!(n.sf(x), 1.0 - cdf, 1e-11);
+            assert_almost_eq!(n.cdf(x) + n.sf(x), 1.0, 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_cdf_far_tails() {
+        // reference values computed with mpmath by quadrature of
+        // `φ(z) P(V < ν ((z + δ) / t)^2)` over `z > -δ`
+        let tails = [
+            (0.5, -10.0, 1e10, 1.6518974841167921991e-29),
+            (3.5, -1.5, 1e6, 4.5202412035719177583e-23),
+            (3.5, -1.5, 4.0, 0.0003028267160533610016),
+            (10.0, 2.0, 1e8, 3.205957433308063999691e-74),
+        ];
+        for &(freedom, delta, t, sf) in &tails {
+            let n = try_create(freedom, delta);
+            assert_almost_eq!(n.sf(t), sf, 1e-10 * sf);
+            assert_almost_eq!(try_create(freedom, -delta).cdf(-t), sf, 1e-10 * sf);
+        }
+        assert!(try_create(0.5, -10.0).sf(1e10) <= 7.6e-24);
+    }
+
+    #[test]
+    fn test_cdf_extreme() {
+        for &(freedom, delta) in &[(10.0, 2.0), (3.5, -1.5), (0.5, -10.0), (100.0, 40.0)] {
+            let n = try_create(freedom, delta);
+            for &x in &[1e160, 1e300] {
+                assert_almost_eq!(n.cdf(x), 1.0, 1e-11);
+                assert!(n.sf(x) >= 0.0 && n.sf(x) < 1e-20);
+                assert!(n.cdf(-x) >= 0.0 && n.cdf(-x) < 1e-20);
+                assert_almost_eq!(n.sf(-x), 1.0, 1e-11);
+            }
+            assert_eq!(n.cdf(f64::MAX), 1.0);
+            assert_eq!(n.sf(f64::MAX), 0.0);
+            assert_eq!(n.cdf(f64::MIN), 0.0);
+            assert_eq!(n.sf(f64::MIN), 1.0);
+        }
+        // `x^2` underflows but the cdf is continuous at 0, where it is `Φ(-δ)`
+        let n = try_create(3.0, 5.0);
+        assert_almost_eq!(n.cdf(-1e-300), 2.866515718791939e-7, 1e-17);
+        assert_almost_eq!(n.cdf(1e-300), 2.866515718791939e-7, 1e-17);
+        assert_almost_eq!(n.sf(1e-300), 1.0 - 2.866515718791939e-7, 1e-15);
+    }
+
+    #[test]
+    fn test_cdf_monotone_tails() {
+        for &(freedom, delta) in &[(100.0, 40.0), (100.0, 10.0), (0.5, -10.0), (3.0, 5.0)] {
+            let n = try_create(freedom, delta);
+            let (mut cdf, mut sf) = (1.0, 1.0);
+            for k in 0..=60 {
+                let x = 10f64.powf(0.25 * k as f64);
+                assert!(n.cdf(-x) <= cdf);
+                assert!(n.sf(x) <= sf);
+                cdf = n.cdf(-x);
+                sf = n.sf(x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &(freedom, delta, x, pdf, cdf) in &CASES {
+            if cdf > 1e-4 && cdf < 1.0 - 1e-4 {
+                let n = try_create(freedom, delta);
+                let quantile = n.inverse_cdf(cdf);
+                if cdf <= 0.5 {
+                    assert_almost_eq!(n.cdf(quantile), cdf, 1e-14);
+                } else {
+                    assert_almost_eq!(n.sf(quantile), 1.0 - cdf, 1e-14);
+                }
+                assert_almost_eq!(quantile, x, 1e-11 / pdf);
+            }
+        }
+        // the extreme quantiles are found on the smaller tail
+        for &(freedom, delta) in &[(10.0, 2.0), (3.5, -1.5), (3.0, 5.0)] {
+            let n = try_create(freedom, delta);
+            for &p in &[1e-300, 1e-16] {
+                let quantile = n.inverse_cdf(p);
+                assert_almost_eq!(n.cdf(quantile), p, 1e-12 * p);
+            }
+            let quantile = n.inverse_cdf(1.0 - 1e-12);
+            assert_almost_eq!(n.sf(quantile), 1.0 - (1.0 - 1e-12), 1e-24);
+        }
+        let n = try_create(4.0, 1.0);
+        assert_eq!(n.inverse_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_mean() {
+        // δ sqrt(ν / 2) Γ((ν - 1) / 2) / Γ(ν / 2) = 2 sqrt(5) Γ(4.5) / Γ(5)
+        let n = try_create(10.0, 2.0);
+        assert_almost_eq!(n.mean().unwrap(), 2.0 * 5f64.sqrt() * 11.631728396567448 / 24.0, 1e-14);
+        // for ν = 3 the ratio is sqrt(3 / 2) / Γ(3 / 2) = sqrt(6 / π)
+        let n = try_create(3.0, -1.5);
+        assert_almost_eq!(n.mean().unwrap(), -1.5 * (6.0 / f64::consts::PI).sqrt(), 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 3.0 * 3.25 - 2.25 * 6.0 / f64::consts::PI, 1e-13);
+        assert_eq!(try_create(1.0, 2.0).mean(), None);
+        assert_eq!(try_create(2.0, 2.0).variance(), None);
+        // the mean is the first moment of the density
+        let n = try_create(6.0, 1.3);
+        let moment = integrate(|x| x * n.pdf(x), f64::NEG_INFINITY, f64::INFINITY, 1e-12);
+        assert_almost_eq!(n.mean().unwrap(), moment, 1e-9);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = try_create(8.0, 1.5);
+        let count = 20000;
+        let samples: Vec<f64> = (0..count).map(|_| rand::distributions::Distribution::sample(&n, &mut rng)).collect();
+        let below = samples.iter().filter(|&&x| x <= 2.0).count() as f64 / count as f64;
+        assert!((below - n.cdf(2.0)).abs() < 0.015);
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        assert!((mean - n.mean().unwrap()).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(10.0, 2.0), -5.0, 15.0);
+        test::check_continuous_distribution(&try_create(3.5, -1.5), -40.0, 10.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(3.0, 1.5);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("freedom", 0.0), NoncentralStudentsT::new(0.0, 1.5));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
+use crate::distribution::{
+    internal, ziggurat, Continuous, ContinuousCDF, LMoments, Moments, Parameterized, ScoreFunction,
+    TailExpectation,
+};
 use crate::function::erf;
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
@@ -52,6 +55,54 @@ impl Normal {
         }
     }
 
+    /// Estimates a normal distribution from the sample `data` by maximum
+    /// likelihood
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ = mean(x_i)
+    /// σ = sqrt(Σ (x_i - μ)^2 / n)
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The standard deviation has the divisor `n` of the likelihood, not
+    /// the `n - 1` of the unbiased sample variance
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let n = Normal::fit_mle(&[1.0, 2.0, 3.0, 6.0]).unwrap();
+    /// assert_eq!(n.mean().unwrap(), 3.0);
+    /// assert_eq!(n.std_dev().unwrap(), 3.5f64.sqrt());
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Normal> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::SpecialCase("data must be finite"));
+        }
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        if variance <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        Normal::new(mean, variance.sqrt())
+    }
+
     /// Constructs a new standard normal distribution with a mean of 0
     /// and a standard deviation of 1.
     ///
@@ -69,6 +120,107 @@ impl Normal {
             std_dev: 1.0,
         }
     }
+
+    /// Calculates the inverse of the survival function for the normal
+    /// distribution given the natural logarithm `ln_q` of the survival
+    /// probability, so that quantiles remain available for tail
+    /// probabilities too small to be represented as a `f64`
+    ///
+    /// # Panics
+    ///
+    /// If `ln_q > 0.0` or `ln_q` is `NaN`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + sqrt(2) * σ * erfc_inv(2 * e^ln_q)
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` is the standard deviation and `erfc_inv` is
+    /// the inverse of the complementary error function. Below the smallest
+    /// representable probabilities the standard quantile is found by Newton
+    /// iteration on the asymptotic expansion of `ln(Φc(z))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// let x = n.inverse_ln_sf(-1000.0);
+    /// assert!((x - 44.61574773196940).abs() < 1e-12);
+    /// ```
+    pub fn inverse_ln_sf(&self, ln_q: f64) -> f64 {
+        if ln_q.is_nan() || ln_q > 0.0 {
+            panic!("ln_q must be within [-inf, 0.0]");
+        }
+        self.mean + self.std_dev * inverse_ln_sf_std(ln_q)
+    }
+
+    /// Calculates the natural logarithm of the cumulative distribution
+    /// function for the normal distribution at `x`, which stays finite
+    /// where the cdf itself underflows to zero
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(Φ((x - μ) / σ))
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` is the standard deviation and `Φ` the
+    /// standard normal cdf. Beyond 37 standard deviations below the mean it
+    /// is computed from the asymptotic expansion of the Mills ratio.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!((n.ln_cdf(-40.0) - -804.60844201375379).abs() < 1e-11);
+    /// ```
+    pub fn ln_cdf(&self, x: f64) -> f64 {
+        ln_sf_std((self.mean - x) / self.std_dev)
+    }
+
+    /// Calculates the natural logarithm of the survival function for the
+    /// normal distribution at `x`, which stays finite where the survival
+    /// function itself underflows to zero
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(1 - Φ((x - μ) / σ))
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` is the standard deviation and `Φ` the
+    /// standard normal cdf. Beyond 37 standard deviations above the mean it
+    /// is computed from the asymptotic expansion of the Mills ratio.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    ///
+    /// let n = Normal::new(0.0, 1.0).unwrap();
+    /// assert!((n.ln_sf(40.0) - -804.60844201375379).abs() < 1e-11);
+    /// ```
+    pub fn ln_sf(&self, x: f64) -> f64 {
+        ln_sf_std((x - self.mean) / self.std_dev)
+    }
+}
+
+impl Parameterized for Normal {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("mean".into(), self.mean), ("std_dev".into(), self.std_dev)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Normal> {
+        match *values {
+            [mean, std_dev] => Normal::new(mean, std_dev),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
 }
 
 impl std::fmt::Display for Normal {
@@ -292,6 +444,44 @@ impl Continuous<f64, f64> for Normal {
     }
 }
 
+impl ScoreFunction<f64> for Normal {
+    const NUM_PARAMS: usize = 2;
+
+    /// Calculates the gradient of the log probability density function for
+    /// the normal distribution at `x` with respect to `(μ, σ)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (z / σ, (z^2 - 1) / σ)
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` the standard deviation and `z = (x - μ) / σ`
+    fn ln_pdf_grad(&self, x: f64) -> Vec<f64> {
+        let z = (x - self.mean) / self.std_dev;
+        vec![z / self.std_dev, (z * z - 1.0) / self.std_dev]
+    }
+
+    /// Calculates the Hessian of the log probability density function for
+    /// the normal distribution at `x` with respect to `(μ, σ)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-1, -2z], [-2z, 1 - 3z^2]] / σ^2
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` the standard deviation and `z = (x - μ) / σ`
+    fn ln_pdf_hessian(&self, x: f64) -> Option<Vec<Vec<f64>>> {
+        let z = (x - self.mean) / self.std_dev;
+        let s2 = self.std_dev * self.std_dev;
+        Some(vec![
+            vec![-1.0 / s2, -2.0 * z / s2],
+            vec![-2.0 * z / s2, (1.0 - 3.0 * z * z) / s2],
+        ])
+    }
+}
+
 /// performs an unchecked cdf calculation for a normal distribution
 /// with the given mean and standard deviation at x
 pub fn cdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
@@ -318,6 +508,64 @@ pub fn ln_pdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
     (-0.5 * d * d) - consts::LN_SQRT_2PI - std_dev.ln()
 }
 
+/// Log probabilities above which the survival probability is represented
+/// accurately enough to invert through `erfc_inv`
+const LN_SF_DIRECT_MIN: f64 = -700.0;
+
+/// natural logarithm of the standard normal survival function for large `z`
+/// from the asymptotic expansion of the Mills ratio, accurate to double
+/// precision for `z >= 37`
+fn ln_sf_std_asymptotic(z: f64) -> f64 {
+    let w = 1.0 / (z * z);
+    let series = 1.0 - w * (1.0 - 3.0 * w * (1.0 - 5.0 * w * (1.0 - 7.0 * w * (1.0 - 9.0 * w))));
+    -0.5 * z * z - z.ln() - consts::LN_SQRT_2PI + series.ln()
+}
+
+/// Standard scores above which the survival function is computed from its
+/// asymptotic expansion
+const LN_SF_ASYMPTOTIC_MIN: f64 = 37.0;
+
+/// natural logarithm of the standard normal survival function at `z`
+pub(crate) fn ln_sf_std(z: f64) -> f64 {
+    if z.is_nan() {
+        f64::NAN
+    } else if z >= LN_SF_ASYMPTOTIC_MIN {
+        ln_sf_std_asymptotic(z)
+    } else if z > -1.0 {
+        (0.5 * erf::erfc(z * f64::consts::FRAC_1_SQRT_2)).ln()
+    } else {
+        // the complement of a small cdf
+        (-0.5 * erf::erfc(-z * f64::consts::FRAC_1_SQRT_2)).ln_1p()
+    }
+}
+
+/// computes the standard normal quantile with survival probability `e^ln_q`
+pub(crate) fn inverse_ln_sf_std(ln_q: f64) -> f64 {
+    if ln_q == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if ln_q >= LN_SF_DIRECT_MIN {
+        return f64::consts::SQRT_2 * erf::erfc_inv(2.0 * ln_q.exp());
+    }
+    if ln_q == f64::NEG_INFINITY {
+        return f64::INFINITY;
+    }
+    // ln(Φc(z)) ≈ -z^2 / 2 - ln(z * sqrt(2π)) solved for z^2 gives the
+    // starting point, refined by Newton steps on the concave ln(Φc(z))
+    let l = -ln_q;
+    let mut z = (2.0 * l - (4.0 * f64::consts::PI * l).ln()).sqrt();
+    for _ in 0..20 {
+        let ln_sf = ln_sf_std_asymptotic(z);
+        let hazard = (-0.5 * z * z - consts::LN_SQRT_2PI - ln_sf).exp();
+        let step = (ln_sf - ln_q) / hazard;
+        z += step;
+        if step.abs() <= f64::EPSILON * z {
+            break;
+        }
+    }
+    z
+}
+
 /// draws a sample from a normal distribution using the Box-Muller algorithm
 pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -> f64 {
     mean + std_dev * ziggurat::sample_std_normal(rng)
@@ -331,12 +579,67 @@ impl std::default::Default for Normal {
     }
 }
 
+impl LMoments for Normal {
+    /// Returns the first `order` L-moments of the normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [μ, σ / sqrt(π), 0, 30 arctan(sqrt(2)) / π - 9, ...]
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` is the standard deviation. Orders above
+    /// four are integrated numerically.
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        // 30 arctan(sqrt(2)) / π - 9, which cancels badly in floating point
+        let tau4 = 0.122_601_719_540_890_95;
+        let known = [self.mean, self.std_dev / f64::consts::PI.sqrt(), 0.0, tau4];
+        internal::l_moments(self, order, &known)
+    }
+}
+
+impl Moments for Normal {
+    /// Returns the raw moment of order `order` of the normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{j = 0}^{⌊r / 2⌋} C(r, 2j) μ^(r - 2j) σ^(2j) (2j - 1)!!
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` is the standard deviation
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        internal::raw_moment_about(order, self.mean, |k| self.central_moment(k))
+    }
+
+    /// Returns the central moment of order `order` of the normal
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// σ^r (r - 1)!!
+    /// ```
+    ///
+    /// for even `r` and zero for odd `r`, where `σ` is the standard deviation
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        if order % 2 == 1 {
+            return Some(0.0);
+        }
+        let double_factorial: f64 = (1..order).step_by(2).map(|i| i as f64).product();
+        Some(self.std_dev.powi(order as i32) * double_factorial)
+    }
+}
+
+impl TailExpectation for Normal {}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Normal};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(mean: f64, std_dev: f64) -> Normal {
         let n = Normal::new(mean, std_dev);
@@ -563,4 +866,163 @@ mod tests {
         // Check that the standard deviation of the distribution is close to 1
         assert_almost_eq!(n_std, 1.0, 1e-15);
     }
+
+    #[test]
+    fn test_sample_above() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for &(mean, std_dev, threshold) in &[(1.0, 2.0, 1.5), (0.0, 1.0, 3.0), (0.0, 1.0, -2.0)] {
+            let n = try_create(mean, std_dev);
+            let samples: Vec<f64> = (0..20000).map(|_| n.sample_above(threshold, &mut rng)).collect();
+            assert!(samples.iter().all(|&x| x > threshold));
+            // E[X | X > t] = μ + σφ(a) / (1 - Φ(a)) with a = (t - μ) / σ
+            let a = (threshold - mean) / std_dev;
+            let expected = mean + std_dev * Normal::standard().pdf(a) / Normal::standard().sf(a);
+            let actual = samples.iter().sum::<f64>() / samples.len() as f64;
+            assert_almost_eq!(actual, expected, 0.03 * std_dev);
+        }
+        // far in the tail, where `1 - F(t)` rounds to 0 or to a few ulps,
+        // the draws are still above the threshold with the conditional mean
+        let n = try_create(0.0, 1.0);
+        for &threshold in &[8.0, 8.5, 9.0, 9.5, 10.0] {
+            let samples: Vec<f64> = (0..2000).map(|_| n.sample_above(threshold, &mut rng)).collect();
+            assert!(samples.iter().all(|&x| x > threshold));
+            let expected = n.pdf(threshold) / n.sf(threshold);
+            let actual = samples.iter().sum::<f64>() / samples.len() as f64;
+            assert_almost_eq!(actual, expected, 0.01);
+        }
+        assert!(try_create(0.0, 1.0).sample_above(f64::INFINITY, &mut rng).is_nan());
+        assert!(try_create(0.0, 1.0).sample_above(40.0, &mut rng).is_nan());
+    }
+
+    #[test]
+    fn test_inverse_ln_sf() {
+        let n = try_create(0.0, 1.0);
+        for &q in &[0.9, 0.5, 0.1, 1e-5, 1e-12, 1e-100, 1e-300] {
+            assert_almost_eq!(n.inverse_ln_sf(f64::ln(q)), -n.inverse_cdf(q), 1e-12 * n.inverse_cdf(q).abs().max(1.0));
+        }
+        let n = try_create(5.0, 2.0);
+        assert_almost_eq!(n.inverse_ln_sf(f64::ln(0.3)), n.inverse_cdf(0.7), 1e-14);
+        assert_eq!(n.inverse_ln_sf(0.0), f64::NEG_INFINITY);
+        assert_eq!(n.inverse_ln_sf(f64::NEG_INFINITY), f64::INFINITY);
+
+        // below the smallest representable probabilities, reference values
+        // computed with mpmath
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.inverse_ln_sf(-1000.0), 44.61574773196940302, 1e-12);
+        assert_almost_eq!(n.inverse_ln_sf(-5000.0), 99.944748174841092478, 1e-12);
+        assert_almost_eq!(n.inverse_ln_sf(-1e5), 447.19789367852505149, 1e-11);
+        assert_almost_eq!(n.inverse_ln_sf(-1e10), 141421.35614695230614, 1e-9);
+        // continuous across the switch to the asymptotic expansion
+        assert_almost_eq!(n.inverse_ln_sf(-700.0 - 1e-9), n.inverse_ln_sf(-700.0), 1e-9);
+        let m = try_create(1.0, 3.0);
+        assert_almost_eq!(m.inverse_ln_sf(-1000.0), 1.0 + 3.0 * 44.61574773196940302, 1e-11);
+    }
+
+    #[test]
+    fn test_ln_cdf_ln_sf() {
+        // reference values computed with mpmath
+        let n = try_create(0.0, 1.0);
+        assert_almost_eq!(n.ln_sf(40.0), -804.60844201375378817, 1e-11);
+        assert_almost_eq!(n.ln_cdf(-40.0), -804.60844201375378817, 1e-11);
+        assert_almost_eq!(n.ln_cdf(-100.0), -5005.5242086942050886, 1e-9);
+        assert_almost_eq!(n.ln_cdf(-5.0), -15.064998393988725736, 1e-10);
+        assert_almost_eq!(n.ln_sf(-2.0), -0.023012909328963488465, 1e-11);
+        // agrees with the logarithms where those are representable
+        for &x in &[-30.0, -8.0, -1.5, -0.5, 0.0, 0.7, 3.0] {
+            assert_almost_eq!(n.ln_cdf(x), n.cdf(x).ln(), 1e-10 * n.cdf(x).ln().abs().max(1e-3));
+            assert_almost_eq!(n.ln_sf(-x), n.cdf(x).ln(), 1e-10 * n.cdf(x).ln().abs().max(1e-3));
+        }
+        // continuous across the switch to the asymptotic expansion
+        assert_almost_eq!(n.ln_sf(37.0 - 1e-9), n.ln_sf(37.0), 1e-7);
+        let m = try_create(1.0, 3.0);
+        assert_almost_eq!(m.ln_sf(121.0), -804.60844201375378817, 1e-11);
+        assert_eq!(n.ln_cdf(f64::INFINITY), 0.0);
+        assert_eq!(n.ln_cdf(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        assert!(n.ln_sf(f64::NAN).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_ln_sf_positive() {
+        try_create(0.0, 1.0).inverse_ln_sf(0.5);
+    }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Normal::new(p[0], p[1]).unwrap();
+        let xs = [-3.0, -0.5, 0.0, 1.2, 4.0, 10.0];
+        test::check_score(make, |d: &Normal, x| d.ln_pdf(x), &[0.0, 1.0], &xs);
+        test::check_score(make, |d: &Normal, x| d.ln_pdf(x), &[1.5, 0.3], &xs);
+        test::check_score(make, |d: &Normal, x| d.ln_pdf(x), &[-2.0, 7.5], &xs);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        use crate::distribution::LMoments;
+        let n = try_create(1.5, 2.0);
+        let closed = n.l_moments(4);
+        assert_eq!(closed[0], 1.5);
+        assert_almost_eq!(closed[1], 2.0 / std::f64::consts::PI.sqrt(), 1e-15);
+        assert_almost_eq!(closed[3], 0.12260171954089094744, 1e-15);
+        // the numerical fallback agrees with the closed forms
+        let numeric = l_moments(&n, 6, &[]);
+        for r in 0..4 {
+            assert_almost_eq!(numeric[r], closed[r], 1e-10);
+        }
+        assert_almost_eq!(n.l_moments(6)[4], 0.0, 1e-10);
+        assert_eq!(n.l_moments(6)[..4], closed[..]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(-1.0), d.pdf(0.5), d.pdf(4.0)]);
+        assert_eq!(n.set_parameter("std_dev", -1.0), Normal::new(1.0, -1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(mean, std_dev) in [(0.0, 1.0), (1.5, 2.0), (-3.0, 0.5)].iter() {
+            let n = try_create(mean, std_dev);
+            test::check_moments(&n, 1e-14);
+            test::check_moments_numeric(&n, 6, 1e-9);
+            assert_eq!(n.standardized_moment(4), Some(3.0));
+            assert_eq!(n.standardized_moment(5), Some(0.0));
+        }
+        let n = try_create(0.0, 2.0);
+        assert_eq!(n.raw_moment(6), Some(15.0 * 64.0));
+        assert_eq!(n.central_moment(8), Some(105.0 * 256.0));
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let n = Normal::fit_mle(&data).unwrap();
+        assert_eq!(n.mean().unwrap(), 5.0);
+        assert_eq!(n.std_dev().unwrap(), 2.0);
+        assert!(Normal::fit_mle(&[1.0]).is_err());
+        assert!(Normal::fit_mle(&[1.0, 1.0, 1.0]).is_err());
+        assert!(Normal::fit_mle(&[1.0, f64::NAN]).is_err());
+        assert!(Normal::fit_mle(&[1.0, f64::INFINITY]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        // the numerical defaults against E[X | X > u] = μ + σ φ(z) / (1 - Φ(z))
+        let n = try_create(1.0, 2.0);
+        let std = try_create(0.0, 1.0);
+        for &u in [-3.0, 0.0, 1.0, 4.0].iter() {
+            let z = (u - 1.0) / 2.0;
+            let expected = 1.0 + 2.0 * std.pdf(z) / std.sf(z) - u;
+            assert_almost_eq!(n.mean_excess(u), expected, 1e-9);
+        }
+        assert_almost_eq!(n.partial_expectation(f64::NEG_INFINITY, f64::INFINITY), 1.0, 1e-10);
+        assert_almost_eq!(n.partial_expectation(1.0, f64::INFINITY), 0.5 + 2.0 * std.pdf(0.0), 1e-10);
+        assert!(n.mean_excess(f64::INFINITY).is_nan());
+    }
 }
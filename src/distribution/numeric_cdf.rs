@@ -0,0 +1,450 @@
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::function::integrate::{adaptive_panels, integrate_finite, Substitution};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::cell::Cell;
+use std::f64;
+
+/// Maximum number of Newton steps taken by `NumericCdf::inverse_cdf`
+const MAX_NEWTON_STEPS: usize = 60;
+
+/// Summarizes the work done by a `NumericCdf`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CacheStats {
+    /// Number of cached panels partitioning the support
+    pub panels: usize,
+    /// Number of pdf evaluations spent building the cache
+    pub build_evaluations: usize,
+    /// Number of pdf evaluations spent answering queries since construction
+    pub query_evaluations: usize,
+    /// Number of `cdf`, `sf` and `inverse_cdf` queries answered
+    pub queries: usize,
+}
+
+/// Implements a continuous distribution whose cdf is obtained by numerically
+/// integrating the pdf of a wrapped distribution, for distributions that are
+/// only known through their density
+///
+/// # Remarks
+///
+/// On construction the support is partitioned by adaptive Gauss-Kronrod
+/// quadrature and the integral over every panel is cached, so that a
+/// `cdf(x)` query only integrates over the part of the panel containing `x`.
+/// Infinite bounds are handled by a change of variables mapping the support
+/// onto a finite interval. The density is normalized to the mass found on
+/// the support. `inverse_cdf` interpolates the cached cumulative masses and
+/// polishes the result with Newton steps against the pdf.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Normal, NumericCdf};
+///
+/// let n = Normal::new(0.0, 1.0).unwrap();
+/// let m = NumericCdf::new(n, f64::NEG_INFINITY, f64::INFINITY, 1e-12).unwrap();
+/// assert!((m.cdf(1.0) - n.cdf(1.0)).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NumericCdf<D> {
+    dist: D,
+    lower: f64,
+    upper: f64,
+    tol: f64,
+    sub: Substitution,
+    // panel bounds in the substituted variable, sorted and contiguous
+    knots: Vec<f64>,
+    // unnormalized mass below each knot
+    cumulative: Vec<f64>,
+    // unnormalized mass above each knot, summed from the upper end to keep
+    // the relative accuracy of the upper tail
+    cumulative_above: Vec<f64>,
+    build_evaluations: usize,
+    query_evaluations: Cell<usize>,
+    queries: Cell<usize>,
+}
+
+impl<D: Continuous<f64, f64>> NumericCdf<D> {
+    /// Constructs a new numeric cdf for `dist` supported on `[lower, upper]`,
+    /// integrating its pdf to a relative tolerance of `tol`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower` or `upper` are `NaN`, if `lower >= upper`,
+    /// if `tol` is not positive, or if the pdf does not integrate to a
+    /// positive finite mass within the tolerance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, NumericCdf};
+    ///
+    /// let d = Exp::new(1.0).unwrap();
+    /// let mut result = NumericCdf::new(d, 0.0, f64::INFINITY, 1e-12);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NumericCdf::new(d, 1.0, 0.0, 1e-12);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(dist: D, lower: f64, upper: f64, tol: f64) -> Result<NumericCdf<D>> {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(StatsError::ArgLtArg("lower", "upper"));
+        }
+        if tol.is_nan() || tol <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("tol"));
+        }
+        let sub = Substitution::new(lower, upper);
+        let (a, b) = sub.range();
+        let evaluations = Cell::new(0);
+        let mut panels = {
+            let f = |t: f64| {
+                evaluations.set(evaluations.get() + 1);
+                density(&dist, &sub, t)
+            };
+            adaptive_panels(&f, a, b, tol)
+        };
+        let total: f64 = panels.iter().map(|p| p.2).sum();
+        let error: f64 = panels.iter().map(|p| p.3).sum();
+        if !total.is_finite() || total <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        if error.is_nan() || error > tol * total.max(1.0) {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+        panels.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+        let mut knots = Vec::with_capacity(panels.len() + 1);
+        let mut cumulative = Vec::with_capacity(panels.len() + 1);
+        knots.push(a);
+        cumulative.push(0.0);
+        let mut mass = 0.0;
+        for &(_, hi, value, _) in &panels {
+            mass += value;
+            knots.push(hi);
+            cumulative.push(mass);
+        }
+        let mut cumulative_above = vec![0.0; knots.len()];
+        for (i, &(_, _, value, _)) in panels.iter().enumerate().rev() {
+            cumulative_above[i] = cumulative_above[i + 1] + value;
+        }
+        Ok(NumericCdf {
+            dist,
+            lower,
+            upper,
+            tol,
+            sub,
+            knots,
+            cumulative,
+            cumulative_above,
+            build_evaluations: evaluations.get(),
+            query_evaluations: Cell::new(0),
+            queries: Cell::new(0),
+        })
+    }
+}
+
+impl<D> NumericCdf<D> {
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+
+    /// Returns statistics on the size of the cache and the number of pdf
+    /// evaluations spent building and querying it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{ContinuousCDF, Normal, NumericCdf};
+    ///
+    /// let m = NumericCdf::new(Normal::standard(), -10.0, 10.0, 1e-12).unwrap();
+    /// m.cdf(0.5);
+    /// let stats = m.cache_stats();
+    /// assert_eq!(stats.queries, 1);
+    /// assert!(stats.query_evaluations < stats.build_evaluations);
+    /// ```
+    pub fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            panels: self.knots.len() - 1,
+            build_evaluations: self.build_evaluations,
+            query_evaluations: self.query_evaluations.get(),
+            queries: self.queries.get(),
+        }
+    }
+
+    fn total(&self) -> f64 {
+        *self.cumulative.last().unwrap()
+    }
+
+    // index of the panel containing `t`
+    fn panel(&self, t: f64) -> usize {
+        let i = self.knots.partition_point(|&k| k <= t);
+        i.clamp(1, self.knots.len() - 1) - 1
+    }
+}
+
+/// Evaluates the density of `dist` in the substituted variable `t`
+fn density<D: Continuous<f64, f64>>(dist: &D, sub: &Substitution, t: f64) -> f64 {
+    let pdf = dist.pdf(sub.to_x(t));
+    if pdf == 0.0 {
+        0.0
+    } else {
+        pdf * sub.jacobian(t)
+    }
+}
+
+impl<D: Continuous<f64, f64>> NumericCdf<D> {
+    // unnormalized mass over `[a, b]` within a single panel, counting pdf
+    // evaluations
+    fn partial(&self, a: f64, b: f64) -> f64 {
+        if a >= b {
+            return 0.0;
+        }
+        let f = |s: f64| {
+            self.query_evaluations.set(self.query_evaluations.get() + 1);
+            density(&self.dist, &self.sub, s)
+        };
+        integrate_finite(&f, a, b, self.tol)
+    }
+
+    // unnormalized mass below `t`
+    fn mass_below(&self, t: f64) -> f64 {
+        let i = self.panel(t);
+        self.cumulative[i] + self.partial(self.knots[i], t)
+    }
+
+    // unnormalized mass above `t`
+    fn mass_above(&self, t: f64) -> f64 {
+        let i = self.panel(t);
+        self.cumulative_above[i + 1] + self.partial(t, self.knots[i + 1])
+    }
+}
+
+impl<D: Continuous<f64, f64>> ::rand::distributions::Distribution<f64> for NumericCdf<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inverse_cdf(rng.gen())
+    }
+}
+
+impl<D: Continuous<f64, f64>> ContinuousCDF<f64, f64> for NumericCdf<D> {
+    /// Calculates the cumulative distribution function at `x` by adding the
+    /// cached mass of the panels below `x` to the integral of the pdf over
+    /// the part of the panel containing `x`
+    fn cdf(&self, x: f64) -> f64 {
+        self.queries.set(self.queries.get() + 1);
+        if x <= self.lower {
+            return 0.0;
+        }
+        if x >= self.upper {
+            return 1.0;
+        }
+        (self.mass_below(self.sub.to_t(x)) / self.total()).clamp(0.0, 1.0)
+    }
+
+    /// Calculates the survival function at `x` by adding the cached mass of
+    /// the panels above `x` to the integral of the pdf over the part of the
+    /// panel containing `x`, rather than subtracting the cdf from `1`
+    fn sf(&self, x: f64) -> f64 {
+        self.queries.set(self.queries.get() + 1);
+        if x <= self.lower {
+            return 1.0;
+        }
+        if x >= self.upper {
+            return 0.0;
+        }
+        (self.mass_above(self.sub.to_t(x)) / self.total()).clamp(0.0, 1.0)
+    }
+
+    /// Calculates the inverse cumulative distribution function at `p` by
+    /// interpolating the cached cumulative masses of the panel containing the
+    /// quantile and polishing the result with safeguarded Newton steps
+    /// against the pdf
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be within [0.0, 1.0]");
+        }
+        self.queries.set(self.queries.get() + 1);
+        if p == 0.0 {
+            return self.lower;
+        }
+        if p == 1.0 {
+            return self.upper;
+        }
+        let target = p * self.total();
+        let i = self
+            .cumulative
+            .partition_point(|&c| c < target)
+            .clamp(1, self.knots.len() - 1)
+            - 1;
+        let (mut lo, mut hi) = (self.knots[i], self.knots[i + 1]);
+        let (c_lo, c_hi) = (self.cumulative[i], self.cumulative[i + 1]);
+        let mut t = if c_hi > c_lo {
+            lo + (hi - lo) * (target - c_lo) / (c_hi - c_lo)
+        } else {
+            0.5 * (lo + hi)
+        };
+        for _ in 0..MAX_NEWTON_STEPS {
+            let residual = self.mass_below(t) - target;
+            if residual == 0.0 {
+                break;
+            }
+            if residual > 0.0 {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let slope = density(&self.dist, &self.sub, t);
+            let mut next = t - residual / slope;
+            if !(next > lo && next < hi) {
+                next = 0.5 * (lo + hi);
+            }
+            if (next - t).abs() <= f64::EPSILON * t.abs().max(f64::MIN_POSITIVE) {
+                t = next;
+                break;
+            }
+            t = next;
+        }
+        self.sub.to_x(t).clamp(self.lower, self.upper)
+    }
+}
+
+impl<D> Min<f64> for NumericCdf<D> {
+    /// Returns the lower bound of the support given on construction
+    fn min(&self) -> f64 {
+        self.lower
+    }
+}
+
+impl<D> Max<f64> for NumericCdf<D> {
+    /// Returns the upper bound of the support given on construction
+    fn max(&self) -> f64 {
+        self.upper
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for NumericCdf<D> {
+    /// Calculates the probability density function of the wrapped
+    /// distribution at `x`, normalized to the mass found on the support
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            0.0
+        } else {
+            self.dist.pdf(x) / self.total()
+        }
+    }
+
+    /// Calculates the log probability density function of the wrapped
+    /// distribution at `x`, normalized to the mass found on the support
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            f64::NEG_INFINITY
+        } else {
+            self.dist.ln_pdf(x) - self.total().ln()
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Cauchy, Exp, Normal};
+    use crate::prec;
+
+    #[test]
+    fn test_bad_create() {
+        let n = Normal::standard();
+        assert!(NumericCdf::new(n, 1.0, 1.0, 1e-10).is_err());
+        assert!(NumericCdf::new(n, f64::NAN, 1.0, 1e-10).is_err());
+        assert!(NumericCdf::new(n, -1.0, 1.0, 0.0).is_err());
+        // no mass on the support
+        let e = Exp::new(1.0).unwrap();
+        assert!(NumericCdf::new(e, -2.0, -1.0, 1e-10).is_err());
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        for &(lower, upper) in &[(f64::NEG_INFINITY, f64::INFINITY), (-40.0, 40.0)] {
+            let n = Normal::new(1.0, 2.0).unwrap();
+            let m = NumericCdf::new(n, lower, upper, 1e-13).unwrap();
+            let mut x = -15.0;
+            while x <= 17.0 {
+                assert_almost_eq!(m.cdf(x), n.cdf(x), 1e-10);
+                assert_almost_eq!(m.sf(x), n.sf(x), 1e-10);
+                x += 0.25;
+            }
+            assert_eq!(m.cdf(f64::NEG_INFINITY), 0.0);
+            assert_eq!(m.cdf(f64::INFINITY), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_normal_inverse_cdf() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        let m = NumericCdf::new(n, f64::NEG_INFINITY, f64::INFINITY, 1e-13).unwrap();
+        for &p in &[1e-8, 0.001, 0.1, 0.3, 0.5, 0.77, 0.99, 1.0 - 1e-8] {
+            assert_almost_eq!(m.inverse_cdf(p), n.inverse_cdf(p), 1e-8);
+        }
+        assert_eq!(m.inverse_cdf(0.0), f64::NEG_INFINITY);
+        assert_eq!(m.inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_cauchy_infinite_support() {
+        let c = Cauchy::new(-1.0, 0.5).unwrap();
+        let m = NumericCdf::new(c, f64::NEG_INFINITY, f64::INFINITY, 1e-13).unwrap();
+        for &x in &[-1e4, -100.0, -3.0, -1.0, 0.0, 2.0, 50.0, 1e5] {
+            assert_almost_eq!(m.cdf(x), c.cdf(x), 1e-10);
+        }
+        assert!(prec::almost_eq(m.sf(1e5) / c.sf(1e5), 1.0, 1e-6));
+        for &p in &[0.001, 0.25, 0.5, 0.9, 0.999] {
+            assert_almost_eq!(m.cdf(m.inverse_cdf(p)), p, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_half_infinite_support() {
+        let e = Exp::new(2.0).unwrap();
+        let m = NumericCdf::new(e, 0.0, f64::INFINITY, 1e-13).unwrap();
+        for &x in &[0.01, 0.5, 1.0, 4.0, 10.0] {
+            assert_almost_eq!(m.cdf(x), e.cdf(x), 1e-10);
+        }
+        assert_almost_eq!(m.inverse_cdf(0.5), 2f64.ln() / 2.0, 1e-10);
+        assert_eq!(m.cdf(-1.0), 0.0);
+        assert_eq!(m.pdf(-1.0), 0.0);
+    }
+
+    #[test]
+    fn test_truncated_support_is_normalized() {
+        let n = Normal::standard();
+        let m = NumericCdf::new(n, 0.0, f64::INFINITY, 1e-13).unwrap();
+        assert_almost_eq!(m.cdf(1.0), 2.0 * (n.cdf(1.0) - 0.5), 1e-10);
+        assert_almost_eq!(m.pdf(1.0), 2.0 * n.pdf(1.0), 1e-13);
+        assert_almost_eq!(m.ln_pdf(1.0), n.ln_pdf(1.0) + 2f64.ln(), 1e-13);
+    }
+
+    #[test]
+    fn test_queries_reuse_cache() {
+        let n = Normal::standard();
+        let m = NumericCdf::new(n, f64::NEG_INFINITY, f64::INFINITY, 1e-13).unwrap();
+        let build = m.cache_stats().build_evaluations;
+        for i in 0..100 {
+            m.cdf(-5.0 + 0.1 * i as f64);
+        }
+        let stats = m.cache_stats();
+        assert_eq!(stats.queries, 100);
+        // each query only integrates over part of a single panel
+        assert!(stats.query_evaluations < 100 * build / 10);
+        assert!(stats.panels > 1);
+    }
+
+    #[test]
+    fn test_continuous() {
+        let m = NumericCdf::new(Normal::standard(), f64::NEG_INFINITY, f64::INFINITY, 1e-12).unwrap();
+        test::check_continuous_distribution(&m, -5.0, 5.0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Parameterized, TailExpectation};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::OpenClosed01;
@@ -54,6 +54,54 @@ impl Pareto {
         }
     }
 
+    /// Estimates a Pareto distribution from the sample `data` by maximum
+    /// likelihood
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_m = min(x_i)
+    /// α = n / Σ ln(x_i / x_m)
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The sample minimum overestimates the scale, and the shape is biased
+    /// upwards by a factor `n / (n - 2)`, which is negligible for large
+    /// samples
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not positive or is `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Pareto;
+    ///
+    /// let e = std::f64::consts::E;
+    /// let n = Pareto::fit_mle(&[1.0, e, e * e]).unwrap();
+    /// assert_eq!(n.scale(), 1.0);
+    /// assert!((n.shape() - 1.0).abs() < 1e-15);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Pareto> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|&x| !x.is_finite() || x <= 0.0) {
+            return Err(StatsError::SpecialCase("data must be positive and finite"));
+        }
+        let scale = data.iter().fold(f64::INFINITY, |acc, &x| acc.min(x));
+        let s: f64 = data.iter().map(|&x| (x / scale).ln()).sum();
+        if s <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        Pareto::new(scale, data.len() as f64 / s)
+    }
+
     /// Returns the scale of the Pareto distribution
     ///
     /// # Examples
@@ -81,6 +129,51 @@ impl Pareto {
     pub fn shape(&self) -> f64 {
         self.shape
     }
+
+    /// Calculates the inverse of the survival function for the Pareto
+    /// distribution given the natural logarithm `ln_q` of the survival
+    /// probability, so that quantiles remain available for tail
+    /// probabilities too small to be represented as a `f64`
+    ///
+    /// # Panics
+    ///
+    /// If `ln_q > 0.0` or `ln_q` is `NaN`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_m * e^(-ln_q / α)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Pareto;
+    ///
+    /// let n = Pareto::new(1.0, 100.0).unwrap();
+    /// assert!((n.inverse_ln_sf(-1000.0) - 10f64.exp()).abs() < 1e-10);
+    /// ```
+    pub fn inverse_ln_sf(&self, ln_q: f64) -> f64 {
+        if ln_q.is_nan() || ln_q > 0.0 {
+            panic!("ln_q must be within [-inf, 0.0]");
+        }
+        self.scale * (-ln_q / self.shape).exp()
+    }
+}
+
+impl Parameterized for Pareto {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("scale".into(), self.scale), ("shape".into(), self.shape)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Pareto> {
+        match *values {
+            [scale, shape] => Pareto::new(scale, shape),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
 }
 
 impl std::fmt::Display for Pareto {
@@ -141,6 +234,28 @@ impl ContinuousCDF<f64, f64> for Pareto {
             (self.scale / x).powf(self.shape)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Pareto distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_m * e^(-ln(1 - p) / α)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape, with `ln(1 - p)`
+    /// evaluated as `ln_1p(-p)`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        self.scale * (-(-p).ln_1p() / self.shape).exp()
+    }
 }
 
 impl Min<f64> for Pareto {
@@ -333,12 +448,72 @@ impl Continuous<f64, f64> for Pareto {
     }
 }
 
+impl TailExpectation for Pareto {
+    /// Returns the partial expectation `E[X 1{a < X <= b}]` of the Pareto
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α x_m / (α - 1) ((x_m / a)^(α - 1) - (x_m / b)^(α - 1))  if α != 1
+    /// x_m ln(b / a)                                          if α == 1
+    /// ```
+    ///
+    /// where `x_m` is the scale, `α` the shape and the bounds are clamped
+    /// to the support
+    fn partial_expectation(&self, a: f64, b: f64) -> f64 {
+        let (a, b) = (a.max(self.scale), b.max(self.scale));
+        if a >= b {
+            0.0
+        } else if self.shape.is_infinite() {
+            // a point mass at the scale, which is never above a
+            0.0
+        } else if self.shape == 1.0 {
+            self.scale * (b / a).ln()
+        } else {
+            let power = |x: f64| (self.scale / x).powf(self.shape - 1.0);
+            self.shape * self.scale / (self.shape - 1.0) * (power(a) - power(b))
+        }
+    }
+
+    /// Returns the mean excess `E[X - u | X > u]` of the Pareto
+    /// distribution, which is infinite for `α <= 1`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// u / (α - 1)             if u >= x_m
+    /// α x_m / (α - 1) - u     if u < x_m
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` the shape, linear in `u` above the
+    /// scale with slope `1 / (α - 1)`
+    fn mean_excess(&self, u: f64) -> f64 {
+        if u.is_nan() || u == f64::INFINITY {
+            f64::NAN
+        } else if self.shape.is_infinite() {
+            if u < self.scale {
+                self.scale - u
+            } else {
+                f64::NAN
+            }
+        } else if self.shape <= 1.0 {
+            f64::INFINITY
+        } else if u >= self.scale {
+            u / (self.shape - 1.0)
+        } else {
+            self.shape * self.scale / (self.shape - 1.0) - u
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Pareto};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(scale: f64, shape: f64) -> Pareto {
         let p = Pareto::new(scale, shape);
@@ -535,4 +710,72 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.0, 10.0), 1.0, 10.0);
         test::check_continuous_distribution(&try_create(0.1, 2.0), 0.1, 100.0);
     }
+
+    #[test]
+    fn test_inverse_ln_sf() {
+        let n = try_create(2.0, 3.0);
+        for &q in &[0.9, 0.5, 1e-3, 1e-12, 1e-300] {
+            let x = n.inverse_ln_sf(f64::ln(q));
+            assert_almost_eq!(n.sf(x), q, 1e-12 * q);
+        }
+        assert_almost_eq!(n.inverse_ln_sf(f64::ln(0.25)), 2.0 * 0.25f64.powf(-1.0 / 3.0), 1e-14);
+        // x_m * e^(1000 / α) with q = e^-1000 far below f64::MIN_POSITIVE
+        let n = try_create(2.0, 100.0);
+        assert_almost_eq!(n.inverse_ln_sf(-1000.0), 2.0 * 10f64.exp(), 1e-10);
+        assert_eq!(n.inverse_ln_sf(0.0), 2.0);
+        assert_eq!(n.inverse_ln_sf(f64::NEG_INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(1.5, 2.5), [1.5, 1.5000000000000001, 1.9792618661593414, 3613492.5788681129]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(1.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("shape", 0.0), Pareto::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        use rand::rngs::StdRng;
+        use rand::distributions::Distribution as _;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(293);
+        let data: Vec<f64> = try_create(2.0, 3.0).sample_iter(&mut rng).take(10000).collect();
+        let n = Pareto::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.scale(), 2.0, 1e-3);
+        assert_almost_eq!(n.shape(), 3.0, 0.1);
+        assert!(Pareto::fit_mle(&[1.0]).is_err());
+        assert!(Pareto::fit_mle(&[1.0, 1.0]).is_err());
+        assert!(Pareto::fit_mle(&[1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        for &(scale, shape) in [(1.0, 2.5), (2.0, 4.0), (0.5, 3.0)].iter() {
+            let n = try_create(scale, shape);
+            test::check_tail_expectation(&n, &[0.0, scale, 1.5 * scale, 4.0 * scale, 10.0 * scale], 1e-8);
+            // linear above the scale with slope 1 / (α - 1)
+            let slope = (n.mean_excess(7.0 * scale) - n.mean_excess(3.0 * scale)) / (4.0 * scale);
+            assert_almost_eq!(slope, 1.0 / (shape - 1.0), 1e-14);
+            assert_almost_eq!(n.mean_excess(0.0), n.mean().unwrap(), 1e-14);
+        }
+        assert_eq!(try_create(1.0, 3.0).mean_excess(4.0), 2.0);
+        // the mean is infinite for α <= 1
+        let n = try_create(1.0, 1.0);
+        assert_eq!(n.mean_excess(2.0), f64::INFINITY);
+        assert_eq!(n.partial_expectation(1.0, f64::INFINITY), f64::INFINITY);
+        assert_almost_eq!(n.partial_expectation(2.0, 8.0), 4f64.ln(), 1e-15);
+        assert_eq!(try_create(1.0, 0.5).partial_expectation(2.0, f64::INFINITY), f64::INFINITY);
+        let n = try_create(2.0, f64::INFINITY);
+        assert_eq!(n.mean_excess(0.5), 1.5);
+        assert!(n.mean_excess(2.0).is_nan());
+        assert_eq!(n.partial_expectation(1.0, 3.0), 0.0);
+    }
 }
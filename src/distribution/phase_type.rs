@@ -0,0 +1,589 @@
+use crate::distribution::{internal, ziggurat, Continuous, ContinuousCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use std::f64;
+
+/// Implements the continuous
+/// [phase-type](https://en.wikipedia.org/wiki/Phase-type_distribution)
+/// distribution, the time until absorption of a continuous-time Markov
+/// chain with transient states started from an initial distribution `α`
+/// and moving according to a subgenerator matrix `T`
+///
+/// Exponential, Erlang, hyperexponential and Coxian distributions are all
+/// special cases.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, PhaseType};
+/// use statrs::statistics::Distribution;
+///
+/// // Erlang(2, 1) as two exponential phases in series
+/// let n = PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 1.0], vec![0.0, -1.0]]).unwrap();
+/// assert!((n.mean().unwrap() - 2.0).abs() < 1e-14);
+/// assert!((n.pdf(1.0) - (-1f64).exp()).abs() < 1e-14);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct PhaseType {
+    initial: DVector<f64>,
+    subgenerator: DMatrix<f64>,
+    exit: DVector<f64>,
+    green: DMatrix<f64>,
+}
+
+impl PhaseType {
+    /// Constructs a new phase-type distribution with initial probabilities
+    /// `initial` over the transient phases and subgenerator `subgenerator`,
+    /// given as a vector of rows
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if
+    ///
+    /// - `initial` is empty, contains a negative or non-finite entry, or
+    ///   does not sum to `1`
+    /// - `subgenerator` is not square with one row per phase
+    /// - a diagonal entry of `subgenerator` is not negative, an off-diagonal
+    ///   entry is negative, an entry is not finite, or a row sums to more
+    ///   than `0`
+    /// - `subgenerator` is singular, so some phase is never absorbed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PhaseType;
+    ///
+    /// let mut result = PhaseType::new(vec![0.5, 0.5], vec![vec![-1.0, 0.0], vec![0.0, -3.0]]);
+    /// assert!(result.is_ok());
+    ///
+    /// // the second phase is never left
+    /// result = PhaseType::new(vec![0.5, 0.5], vec![vec![-1.0, 1.0], vec![0.0, 0.0]]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(initial: Vec<f64>, subgenerator: Vec<Vec<f64>>) -> Result<PhaseType> {
+        let n = initial.len();
+        if n == 0 {
+            return Err(StatsError::BadParams);
+        }
+        if initial.iter().any(|&a| !a.is_finite() || a < 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        if (initial.iter().sum::<f64>() - 1.0).abs() > 1e-10 {
+            return Err(StatsError::BadParams);
+        }
+        if subgenerator.len() != n || subgenerator.iter().any(|row| row.len() != n) {
+            return Err(StatsError::BadParams);
+        }
+        for (i, row) in subgenerator.iter().enumerate() {
+            if row.iter().any(|x| !x.is_finite()) {
+                return Err(StatsError::BadParams);
+            }
+            if row[i] >= 0.0 {
+                return Err(StatsError::BadParams);
+            }
+            if row.iter().enumerate().any(|(j, &x)| j != i && x < 0.0) {
+                return Err(StatsError::BadParams);
+            }
+            if row.iter().sum::<f64>() > 1e-12 * row[i].abs() {
+                return Err(StatsError::BadParams);
+            }
+        }
+        let subgenerator = DMatrix::from_fn(n, n, |i, j| subgenerator[i][j]);
+        let green = match (-&subgenerator).try_inverse() {
+            Some(g) if g.iter().all(|x| x.is_finite()) => g,
+            _ => return Err(StatsError::BadParams),
+        };
+        let exit = DVector::from_fn(n, |i, _| -subgenerator.row(i).sum()).map(|x| x.max(0.0));
+        Ok(PhaseType {
+            initial: DVector::from_vec(initial),
+            subgenerator,
+            exit,
+            green,
+        })
+    }
+
+    /// Returns the initial probabilities of the phases
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PhaseType;
+    ///
+    /// let n = PhaseType::new(vec![1.0], vec![vec![-2.0]]).unwrap();
+    /// assert_eq!(n.initial().as_slice(), &[1.0]);
+    /// ```
+    pub fn initial(&self) -> &DVector<f64> {
+        &self.initial
+    }
+
+    /// Returns the subgenerator matrix of the transient phases
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PhaseType;
+    ///
+    /// let n = PhaseType::new(vec![1.0], vec![vec![-2.0]]).unwrap();
+    /// assert_eq!(n.subgenerator()[(0, 0)], -2.0);
+    /// ```
+    pub fn subgenerator(&self) -> &DMatrix<f64> {
+        &self.subgenerator
+    }
+
+    /// Returns the number of transient phases
+    pub fn phases(&self) -> usize {
+        self.initial.len()
+    }
+
+    /// Returns the `k`-th raw moment of the phase-type distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k! α (-T)^(-k) 1
+    /// ```
+    ///
+    /// where `α` is the initial distribution and `T` the subgenerator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PhaseType;
+    ///
+    /// let n = PhaseType::new(vec![1.0], vec![vec![-2.0]]).unwrap();
+    /// assert!((n.moment(3) - 6.0 / 8.0).abs() < 1e-15);
+    /// ```
+    pub fn moment(&self, k: u32) -> f64 {
+        let mut v = DVector::from_element(self.phases(), 1.0);
+        let mut factorial = 1.0;
+        for i in 1..=k {
+            v = &self.green * v;
+            factorial *= i as f64;
+        }
+        factorial * self.initial.dot(&v)
+    }
+
+    /// Returns the survival function `α exp(T x) 1` and the density
+    /// `α exp(T x) t` at `x >= 0` from a single matrix exponential
+    fn sf_pdf(&self, x: f64) -> (f64, f64) {
+        let transient = (&self.subgenerator * x).exp().tr_mul(&self.initial);
+        let sf = transient.sum().clamp(0.0, 1.0);
+        let pdf = transient.dot(&self.exit).max(0.0);
+        (sf, pdf)
+    }
+}
+
+impl Parameterized for PhaseType {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let n = self.initial.len();
+        let initial = self
+            .initial
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| (format!("initial[{}]", i), a));
+        let subgenerator = (0..n * n).map(|k| {
+            let (i, j) = (k / n, k % n);
+            (
+                format!("subgenerator[{}][{}]", i, j),
+                self.subgenerator[(i, j)],
+            )
+        });
+        initial.chain(subgenerator).collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<PhaseType> {
+        let n = self.initial.len();
+        let values = internal::parameter_values(values, n + n * n)?;
+        let (initial, subgenerator) = values.split_at(n);
+        PhaseType::new(
+            initial.to_vec(),
+            subgenerator.chunks(n).map(|row| row.to_vec()).collect(),
+        )
+    }
+}
+
+impl std::fmt::Display for PhaseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PH({:?}, [", self.initial.as_slice())?;
+        for (i, row) in self.subgenerator.row_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", row.iter().collect::<Vec<_>>())?;
+        }
+        write!(f, "])")
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for PhaseType {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        let n = self.phases();
+        let mut u: f64 = r.gen();
+        let mut state = n - 1;
+        for (i, &a) in self.initial.iter().enumerate() {
+            if u < a {
+                state = i;
+                break;
+            }
+            u -= a;
+        }
+        let mut time = 0.0;
+        loop {
+            let rate = -self.subgenerator[(state, state)];
+            time += ziggurat::sample_exp_1(r) / rate;
+            let mut u = r.gen::<f64>() * rate;
+            if u < self.exit[state] {
+                return time;
+            }
+            u -= self.exit[state];
+            let mut next = None;
+            for j in (0..n).filter(|&j| j != state) {
+                let q = self.subgenerator[(state, j)];
+                if u < q {
+                    next = Some(j);
+                    break;
+                }
+                u -= q;
+            }
+            match next {
+                Some(j) => state = j,
+                // rounding left the uniform past every rate, so absorb
+                None => return time,
+            }
+        }
+    }
+}
+
+impl ContinuousCDF<f64, f64> for PhaseType {
+    /// Calculates the cumulative distribution function for the phase-type
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - α exp(T x) 1
+    /// ```
+    ///
+    /// where `α` is the initial distribution and `T` the subgenerator
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else if x == f64::INFINITY {
+            1.0
+        } else {
+            1.0 - self.sf_pdf(x).0
+        }
+    }
+
+    /// Calculates the survival function for the phase-type distribution
+    /// at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α exp(T x) 1
+    /// ```
+    ///
+    /// where `α` is the initial distribution and `T` the subgenerator
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else if x == f64::INFINITY {
+            0.0
+        } else {
+            self.sf_pdf(x).0
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// phase-type distribution at `p` by safeguarded Newton iteration
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            return 0.0;
+        }
+        if p == 1.0 {
+            return f64::INFINITY;
+        }
+        let mean = self.moment(1);
+        let mut lo = 0.0;
+        let mut hi = mean;
+        while self.cdf(hi) < p {
+            lo = hi;
+            hi *= 2.0;
+        }
+        let mut x = 0.5 * (lo + hi);
+        for _ in 0..100 {
+            let (sf, pdf) = self.sf_pdf(x);
+            let error = (1.0 - sf) - p;
+            if error < 0.0 {
+                lo = x;
+            } else {
+                hi = x;
+            }
+            let newton = x - error / pdf;
+            let next = if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+            let step = (next - x).abs();
+            x = next;
+            if step <= 1e-14 * x.max(1e-300) || hi - lo <= 1e-14 * x.max(1e-300) {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl Min<f64> for PhaseType {
+    /// Returns the minimum value in the domain of the phase-type
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for PhaseType {
+    /// Returns the maximum value in the domain of the phase-type
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for PhaseType {
+    /// Returns the mean of the phase-type distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α (-T)^(-1) 1
+    /// ```
+    ///
+    /// where `α` is the initial distribution and `T` the subgenerator
+    fn mean(&self) -> Option<f64> {
+        Some(self.moment(1))
+    }
+
+    /// Returns the variance of the phase-type distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 α (-T)^(-2) 1 - (α (-T)^(-1) 1)^2
+    /// ```
+    ///
+    /// where `α` is the initial distribution and `T` the subgenerator
+    fn variance(&self) -> Option<f64> {
+        let m1 = self.moment(1);
+        Some((self.moment(2) - m1 * m1).max(0.0))
+    }
+
+    /// Returns the skewness of the phase-type distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (m_3 - 3 m_1 σ^2 - m_1^3) / σ^3
+    /// ```
+    ///
+    /// where `m_k` is the `k`-th raw moment and `σ^2` the variance
+    fn skewness(&self) -> Option<f64> {
+        let m1 = self.moment(1);
+        let var = self.variance()?;
+        Some((self.moment(3) - 3.0 * m1 * var - m1 * m1 * m1) / (var * var.sqrt()))
+    }
+}
+
+impl Median<f64> for PhaseType {
+    /// Returns the median of the phase-type distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^(-1)(0.5)
+    /// ```
+    ///
+    /// where `F` is the cumulative distribution function
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for PhaseType {
+    /// Calculates the probability density function for the phase-type
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// α exp(T x) t
+    /// ```
+    ///
+    /// where `α` is the initial distribution, `T` the subgenerator and
+    /// `t = -T 1` the exit rates
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 || x == f64::INFINITY {
+            0.0
+        } else {
+            self.sf_pdf(x).1
+        }
+    }
+
+    /// Calculates the log probability density function for the phase-type
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(α exp(T x) t)
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::Erlang;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn hyperexponential(p: f64, rate_1: f64, rate_2: f64) -> PhaseType {
+        PhaseType::new(
+            vec![p, 1.0 - p],
+            vec![vec![-rate_1, 0.0], vec![0.0, -rate_2]],
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        assert!(PhaseType::new(vec![1.0], vec![vec![-1.0]]).is_ok());
+        assert!(PhaseType::new(vec![0.3, 0.7], vec![vec![-2.0, 1.0], vec![0.5, -1.0]]).is_ok());
+    }
+
+    #[test]
+    fn test_bad_create() {
+        assert!(PhaseType::new(vec![], vec![]).is_err());
+        assert!(PhaseType::new(vec![0.5], vec![vec![-1.0]]).is_err());
+        assert!(PhaseType::new(vec![1.5, -0.5], vec![vec![-1.0, 0.0], vec![0.0, -1.0]]).is_err());
+        assert!(PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 0.0]]).is_err());
+        assert!(PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 0.0], vec![0.0]]).is_err());
+        assert!(PhaseType::new(vec![1.0], vec![vec![0.0]]).is_err());
+        assert!(PhaseType::new(vec![1.0], vec![vec![f64::NAN]]).is_err());
+        assert!(PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, -0.5], vec![0.0, -1.0]]).is_err());
+        assert!(PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 2.0], vec![0.0, -1.0]]).is_err());
+        // closed class: both phases only move between each other
+        assert!(PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 1.0], vec![1.0, -1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_hyperexponential() {
+        let (p, l1, l2) = (0.3, 0.5, 4.0);
+        let n = hyperexponential(p, l1, l2);
+        for &x in &[0.0, 0.01, 0.3, 1.0, 2.5, 7.0, 20.0, 60.0] {
+            let pdf = p * l1 * (-l1 * x).exp() + (1.0 - p) * l2 * (-l2 * x).exp();
+            let sf = p * (-l1 * x).exp() + (1.0 - p) * (-l2 * x).exp();
+            assert_almost_eq!(n.pdf(x), pdf, 1e-13 * pdf.max(1e-300));
+            assert_almost_eq!(n.sf(x), sf, 1e-13 * sf.max(1e-300));
+            assert_almost_eq!(n.cdf(x), 1.0 - sf, 1e-14);
+        }
+        let mean = p / l1 + (1.0 - p) / l2;
+        let second = 2.0 * (p / (l1 * l1) + (1.0 - p) / (l2 * l2));
+        assert_almost_eq!(n.mean().unwrap(), mean, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), second - mean * mean, 1e-14);
+        assert_almost_eq!(n.moment(3), 6.0 * (p / l1.powi(3) + (1.0 - p) / l2.powi(3)), 1e-12);
+    }
+
+    #[test]
+    fn test_erlang_as_phase_type() {
+        let rate = 1.5;
+        let n = PhaseType::new(
+            vec![1.0, 0.0, 0.0],
+            vec![vec![-rate, rate, 0.0], vec![0.0, -rate, rate], vec![0.0, 0.0, -rate]],
+        ).unwrap();
+        let e = Erlang::new(3, rate).unwrap();
+        for &x in &[0.1, 0.5, 1.0, 2.0, 5.0, 10.0] {
+            assert_almost_eq!(n.pdf(x), e.pdf(x), 1e-13);
+            assert_almost_eq!(n.cdf(x), e.cdf(x), 1e-13);
+        }
+        assert_almost_eq!(n.mean().unwrap(), 2.0, 1e-14);
+        assert_almost_eq!(n.variance().unwrap(), 3.0 / 2.25, 1e-14);
+        assert_almost_eq!(n.skewness().unwrap(), 2.0 / 3f64.sqrt(), 1e-12);
+        assert_almost_eq!(e.cdf(n.median()), 0.5, 1e-13);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let n = hyperexponential(0.3, 0.5, 4.0);
+        for &p in &[1e-8, 0.01, 0.25, 0.5, 0.9, 0.999, 1.0 - 1e-9] {
+            let x = n.inverse_cdf(p);
+            assert_almost_eq!(n.cdf(x), p, 1e-12);
+        }
+        assert_eq!(n.inverse_cdf(0.0), 0.0);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_cdf_bad_p() {
+        hyperexponential(0.3, 0.5, 4.0).inverse_cdf(1.5);
+    }
+
+    #[test]
+    fn test_sample_mean() {
+        use rand::distributions::Distribution;
+        let n = PhaseType::new(vec![0.4, 0.6], vec![vec![-2.0, 1.0], vec![0.5, -1.0]]).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let samples = 200_000;
+        let sum: f64 = (0..samples).map(|_| n.sample(&mut rng)).sum();
+        let sd = n.std_dev().unwrap() / (samples as f64).sqrt();
+        assert!((sum / samples as f64 - n.mean().unwrap()).abs() < 4.0 * sd);
+    }
+
+    #[test]
+    fn test_display() {
+        let n = PhaseType::new(vec![1.0, 0.0], vec![vec![-1.0, 1.0], vec![0.0, -2.0]]).unwrap();
+        assert_eq!(n.to_string(), "PH([1.0, 0.0], [[-1.0, 1.0], [0.0, -2.0]])");
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&hyperexponential(0.3, 0.5, 4.0), 0.0, 60.0);
+        let n = PhaseType::new(vec![0.4, 0.6], vec![vec![-2.0, 1.0], vec![0.5, -1.0]]).unwrap();
+        test::check_continuous_distribution(&n, 0.0, 50.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = PhaseType::new(vec![0.4, 0.6], vec![vec![-2.0, 1.5], vec![0.0, -3.0]]).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(0.2), d.pdf(1.5)]);
+        assert_eq!(n.parameters()[3], ("subgenerator[0][1]".to_string(), 1.5));
+        assert_eq!(n.set_parameter("subgenerator[1][1]", 1.0), PhaseType::new(vec![0.4, 0.6], vec![vec![-2.0, 1.5], vec![0.0, 1.0]]));
+    }
+}
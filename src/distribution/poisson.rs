@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Moments, Parameterized, ScoreFunction};
 use crate::function::{factorial, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -66,6 +66,19 @@ impl Poisson {
     }
 }
 
+impl Parameterized for Poisson {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("lambda".into(), self.lambda)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Poisson> {
+        match *values {
+            [lambda] => Poisson::new(lambda),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Poisson {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Pois({})", self.lambda)
@@ -260,6 +273,39 @@ impl Discrete<u64, f64> for Poisson {
         -self.lambda + x as f64 * self.lambda.ln() - factorial::ln_factorial(x)
     }
 }
+
+impl ScoreFunction<u64> for Poisson {
+    const NUM_PARAMS: usize = 1;
+
+    /// Calculates the gradient of the log probability mass function for the
+    /// poisson distribution at `x` with respect to `λ`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (x / λ - 1)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_pdf_grad(&self, x: u64) -> Vec<f64> {
+        vec![x as f64 / self.lambda - 1.0]
+    }
+
+    /// Calculates the Hessian of the log probability mass function for the
+    /// poisson distribution at `x` with respect to `λ`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-x / λ^2]]
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn ln_pdf_hessian(&self, x: u64) -> Option<Vec<Vec<f64>>> {
+        Some(vec![vec![-(x as f64) / (self.lambda * self.lambda)]])
+    }
+}
+
 /// Generates one sample from the Poisson distribution either by
 /// Knuth's method if lambda < 30.0 or Rejection method PA by
 /// A. C. Atkinson from the Journal of the Royal Statistical Society
@@ -301,6 +347,40 @@ pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> f64 {
     }
 }
 
+impl Moments for Poisson {
+    /// Returns the raw moment of order `order` of the poisson distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k = 0}^r S(r, k) λ^k
+    /// ```
+    ///
+    /// the Touchard polynomial, where `S(r, k)` are the Stirling numbers of
+    /// the second kind and `λ` is the rate
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        let stirling = internal::stirling_second_kind(order);
+        let moment = stirling
+            .iter()
+            .enumerate()
+            .map(|(k, s)| s * self.lambda.powi(k as i32))
+            .sum();
+        Some(moment)
+    }
+
+    /// Returns the central moment of order `order` of the poisson
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// The central moments follow from the cumulants, which all equal the
+    /// rate `λ`
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        let moments = internal::central_moments_from_cumulants(order, |_| self.lambda);
+        Some(moments[order as usize])
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -308,6 +388,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, Poisson};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(lambda: f64) -> Poisson {
         let n = Poisson::new(lambda);
@@ -483,4 +564,41 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.3), 10);
         test::check_discrete_distribution(&try_create(4.5), 30);
     }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Poisson::new(p[0]).unwrap();
+        let xs = [0, 1, 3, 10, 40];
+        test::check_score(make, |d: &Poisson, x| d.ln_pmf(x), &[1.0], &xs);
+        test::check_score(make, |d: &Poisson, x| d.ln_pmf(x), &[0.3], &xs);
+        test::check_score(make, |d: &Poisson, x| d.ln_pmf(x), &[25.0], &xs);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(2.5);
+        test::check_parameters(&n, |d| [d.pmf(0), d.pmf(3)]);
+        assert_eq!(n.set_parameter("lambda", -1.0), Poisson::new(-1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &lambda in [0.1, 1.5, 5.4, 100.0].iter() {
+            let n = try_create(lambda);
+            test::check_moments(&n, 1e-13);
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 3.0 + 1.0 / lambda, 1e-13);
+            // the closed forms agree with the sums over the probability mass
+            let mean = n.mean().unwrap();
+            for r in 0..=6 {
+                let (raw, central) = (0..1000u64).map(|x| (x as f64, n.pmf(x))).fold((0.0, 0.0), |(a, b), (x, p)| {
+                    (a + p * x.powi(r), b + p * (x - mean).powi(r))
+                });
+                assert_almost_eq!(n.raw_moment(r as u32).unwrap(), raw, 1e-10 * raw);
+                assert_almost_eq!(n.central_moment(r as u32).unwrap(), central, 1e-10 * n.central_moment(2).unwrap().powi(r / 2 + 1));
+            }
+        }
+        // E[X^3] = λ^3 + 3λ^2 + λ
+        assert_eq!(try_create(2.0).raw_moment(3), Some(22.0));
+    }
 }
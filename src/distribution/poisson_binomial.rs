@@ -0,0 +1,802 @@
+use crate::consts;
+use crate::distribution::{internal, Binomial, Discrete, DiscreteCDF, Parameterized, Poisson};
+use crate::function::erf;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// The largest number of non-degenerate trials for which the distribution
+/// is computed exactly
+const EXACT_LIMIT: usize = 50_000;
+
+/// The constant of the Berry–Esseen bound for sums of independent,
+/// non-identically distributed variables (Shevtsova, 2010)
+const BERRY_ESSEEN: f64 = 0.56;
+
+/// Implements the
+/// [Poisson-binomial](https://en.wikipedia.org/wiki/Poisson_binomial_distribution)
+/// distribution, the number of successes in independent Bernoulli trials
+/// with possibly different probabilities of success
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, PoissonBinomial};
+/// use statrs::statistics::Distribution;
+///
+/// let n = PoissonBinomial::new(vec![0.5, 0.2, 1.0]).unwrap();
+/// assert_eq!(n.mean().unwrap(), 1.7);
+/// assert_eq!(n.pmf(1), 0.4);
+/// assert_eq!(n.pmf(0), 0.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct PoissonBinomial {
+    probs: Vec<f64>,
+    offset: u64,
+    trials: u64,
+    method: Method,
+    mean: f64,
+    variance: f64,
+    third_moment: f64,
+}
+
+/// How the distribution of the `trials` non-degenerate trials is computed
+#[derive(Clone, PartialEq, Debug)]
+enum Method {
+    Binomial(Binomial),
+    Exact {
+        pmf: Vec<f64>,
+        cdf: Vec<f64>,
+        sf: Vec<f64>,
+    },
+    Poisson {
+        poisson: Poisson,
+        bound: f64,
+    },
+    Normal {
+        skewness: f64,
+        bound: f64,
+    },
+}
+
+impl PoissonBinomial {
+    /// Constructs a new Poisson-binomial distribution with the
+    /// probabilities of success `probs` of the trials.
+    ///
+    /// # Remarks
+    ///
+    /// Trials with a probability of `0` or `1` only shift the support. If
+    /// all the other probabilities are equal the distribution is computed
+    /// as a `Binomial`; otherwise it is computed exactly by convolving the
+    /// trials one at a time, in `O(n^2)` time and `O(n)` memory, for up to
+    /// `50_000` trials, and approximated beyond, see `error_bound`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `probs` is empty or if a probability is `NaN`,
+    /// less than `0.0` or greater than `1.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PoissonBinomial;
+    ///
+    /// let mut result = PoissonBinomial::new(vec![0.1, 0.5, 0.9]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = PoissonBinomial::new(vec![0.1, 1.5]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(probs: Vec<f64>) -> Result<PoissonBinomial> {
+        PoissonBinomial::with_exact_limit(probs, EXACT_LIMIT)
+    }
+
+    fn with_exact_limit(probs: Vec<f64>, exact_limit: usize) -> Result<PoissonBinomial> {
+        if probs.is_empty() || probs.iter().any(|p| p.is_nan() || !(0.0..=1.0).contains(p)) {
+            return Err(StatsError::BadParams);
+        }
+        let offset = probs.iter().filter(|&&p| p == 1.0).count() as u64;
+        let open: Vec<f64> = probs
+            .iter()
+            .copied()
+            .filter(|&p| p > 0.0 && p < 1.0)
+            .collect();
+        let trials = open.len() as u64;
+        let mean = probs.iter().sum();
+        let variance = open.iter().map(|p| p * (1.0 - p)).sum();
+        let third_moment = open.iter().map(|p| p * (1.0 - p) * (1.0 - 2.0 * p)).sum();
+        let method = if trials > 0 && open.iter().all(|&p| p == open[0]) {
+            Method::Binomial(Binomial::new(open[0], trials)?)
+        } else if open.len() <= exact_limit {
+            exact(&open)
+        } else {
+            approximate(&open, variance, third_moment)
+        };
+        Ok(PoissonBinomial {
+            probs,
+            offset,
+            trials,
+            method,
+            mean,
+            variance,
+            third_moment,
+        })
+    }
+
+    /// Returns the probabilities of success of the trials
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PoissonBinomial;
+    ///
+    /// let n = PoissonBinomial::new(vec![0.1, 0.5]).unwrap();
+    /// assert_eq!(n.probs(), &[0.1, 0.5]);
+    /// ```
+    pub fn probs(&self) -> &[f64] {
+        &self.probs
+    }
+
+    /// Returns a bound on the absolute error of the cumulative distribution
+    /// function, which is `0` when it is computed exactly
+    ///
+    /// # Formula
+    ///
+    /// Beyond `50_000` trials with different probabilities, the smaller of
+    /// the Le Cam bound of the Poisson approximation (in the refined form of
+    /// Barbour and Hall)
+    ///
+    /// ```text
+    /// (1 - e^(-λ)) / λ * Σ p_i^2
+    /// ```
+    ///
+    /// and the bound of the refined normal approximation, from the
+    /// Berry–Esseen bound of the normal approximation and the largest
+    /// skewness correction
+    ///
+    /// ```text
+    /// 0.56 Σ p_i q_i (p_i^2 + q_i^2) / σ^3 + |γ| / (6 sqrt(2π))
+    /// ```
+    ///
+    /// is used, where `q_i = 1 - p_i`, `λ = Σ p_i`, `σ^2 = Σ p_i q_i` and
+    /// `γ` is the skewness. Both are of order `1 / σ` or smaller, and the
+    /// error observed in practice is much smaller than the bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::PoissonBinomial;
+    ///
+    /// let n = PoissonBinomial::new(vec![0.1, 0.5]).unwrap();
+    /// assert_eq!(n.error_bound(), 0.0);
+    /// ```
+    pub fn error_bound(&self) -> f64 {
+        match self.method {
+            Method::Binomial(_) | Method::Exact { .. } => 0.0,
+            Method::Poisson { bound, .. } | Method::Normal { bound, .. } => bound,
+        }
+    }
+
+    /// Draws `count` samples by inverting the cumulative distribution
+    /// function, which is tabulated once, so each sample takes
+    /// `O(log n)` time instead of the `O(n)` of flipping every trial
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::distribution::PoissonBinomial;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let n = PoissonBinomial::new(vec![0.2, 0.7, 1.0]).unwrap();
+    /// let samples = n.sample_many(&mut rng, 100);
+    /// assert_eq!(samples.len(), 100);
+    /// assert!(samples.iter().all(|&x| (1.0..=3.0).contains(&x)));
+    /// ```
+    pub fn sample_many<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> Vec<f64> {
+        let table: Vec<f64> = match self.method {
+            Method::Exact { ref cdf, .. } => cdf.clone(),
+            _ => (0..=self.trials).map(|j| self.inner_cdf(j)).collect(),
+        };
+        (0..count)
+            .map(|_| {
+                let u: f64 = rng.gen();
+                let j = table.partition_point(|&c| c <= u).min(self.trials as usize);
+                (self.offset + j as u64) as f64
+            })
+            .collect()
+    }
+
+    /// The cumulative distribution function of the non-degenerate trials
+    fn inner_cdf(&self, j: u64) -> f64 {
+        if j >= self.trials {
+            return 1.0;
+        }
+        match self.method {
+            Method::Binomial(ref b) => b.cdf(j),
+            Method::Exact { ref cdf, .. } => cdf[j as usize],
+            Method::Poisson { ref poisson, .. } => poisson.cdf(j),
+            Method::Normal { skewness, .. } => {
+                let (x, correction) = self.refined_normal(j, skewness);
+                (0.5 * erf::erfc(-x / f64::consts::SQRT_2) + correction).clamp(0.0, 1.0)
+            }
+        }
+    }
+
+    /// The survival function of the non-degenerate trials
+    fn inner_sf(&self, j: u64) -> f64 {
+        if j >= self.trials {
+            return 0.0;
+        }
+        match self.method {
+            Method::Binomial(ref b) => b.sf(j),
+            Method::Exact { ref sf, .. } => sf[j as usize],
+            Method::Poisson { ref poisson, .. } => poisson.sf(j),
+            Method::Normal { skewness, .. } => {
+                let (x, correction) = self.refined_normal(j, skewness);
+                (0.5 * erf::erfc(x / f64::consts::SQRT_2) - correction).clamp(0.0, 1.0)
+            }
+        }
+    }
+
+    /// Returns the continuity-corrected standardized `x` of `j` successes
+    /// and the skewness correction `γ (1 - x^2) φ(x) / 6` of the refined
+    /// normal approximation `Φ(x) + γ (1 - x^2) φ(x) / 6`
+    fn refined_normal(&self, j: u64, skewness: f64) -> (f64, f64) {
+        let x = (j as f64 + 0.5 - self.mean + self.offset as f64) / self.variance.sqrt();
+        let density = (-0.5 * x * x).exp() / consts::SQRT_2PI;
+        (x, skewness * (1.0 - x * x) * density / 6.0)
+    }
+}
+
+/// Convolves the trials one at a time, keeping the probabilities of every
+/// number of successes so far
+fn exact(probs: &[f64]) -> Method {
+    let mut pmf = vec![0.0; probs.len() + 1];
+    pmf[0] = 1.0;
+    for (i, &p) in probs.iter().enumerate() {
+        for j in (1..=i + 1).rev() {
+            pmf[j] = pmf[j] * (1.0 - p) + pmf[j - 1] * p;
+        }
+        pmf[0] *= 1.0 - p;
+    }
+    let (cdf, sf) = internal::cumulative_tables(&pmf);
+    Method::Exact { pmf, cdf, sf }
+}
+
+/// Chooses the Poisson or the refined normal approximation, whichever has
+/// the smaller error bound
+fn approximate(probs: &[f64], variance: f64, third_moment: f64) -> Method {
+    let lambda: f64 = probs.iter().sum();
+    let squares: f64 = probs.iter().map(|p| p * p).sum();
+    let poisson_bound = (1.0 - (-lambda).exp()) / lambda * squares;
+    let sigma = variance.sqrt();
+    let skewness = third_moment / (variance * sigma);
+    let absolute: f64 = probs
+        .iter()
+        .map(|p| p * (1.0 - p) * (p * p + (1.0 - p) * (1.0 - p)))
+        .sum();
+    let normal_bound =
+        BERRY_ESSEEN * absolute / (variance * sigma) + skewness.abs() / (6.0 * consts::SQRT_2PI);
+    if poisson_bound < normal_bound {
+        Method::Poisson {
+            poisson: Poisson::new(lambda).unwrap(),
+            bound: poisson_bound,
+        }
+    } else {
+        Method::Normal {
+            skewness,
+            bound: normal_bound,
+        }
+    }
+}
+
+impl Parameterized for PoissonBinomial {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        self.probs
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (format!("probs[{}]", i), p))
+            .collect()
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<PoissonBinomial> {
+        PoissonBinomial::new(internal::parameter_values(values, self.probs.len())?)
+    }
+}
+
+impl std::fmt::Display for PoissonBinomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PoiBin({:?})", self.probs)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for PoissonBinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.probs.iter().fold(0.0, |acc, &p| {
+            let u: f64 = rng.gen();
+            if u < p {
+                acc + 1.0
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl DiscreteCDF<u64, f64> for PoissonBinomial {
+    /// Calculates the cumulative distribution function for the
+    /// Poisson-binomial distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k <= x} P(X = k)
+    /// ```
+    ///
+    /// or its approximation, see `error_bound`
+    fn cdf(&self, x: u64) -> f64 {
+        if x < self.offset {
+            0.0
+        } else {
+            self.inner_cdf(x - self.offset)
+        }
+    }
+
+    /// Calculates the survival function for the
+    /// Poisson-binomial distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k > x} P(X = k)
+    /// ```
+    ///
+    /// or its approximation, see `error_bound`
+    fn sf(&self, x: u64) -> f64 {
+        if x < self.offset {
+            1.0
+        } else {
+            self.inner_sf(x - self.offset)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function, the
+    /// smallest `x` such that `cdf(x) >= p`, by bisection over the support
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not on `[0, 1]`
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be on [0, 1]");
+        }
+        let (mut lower, mut upper) = (self.min(), self.max());
+        while lower < upper {
+            let mid = lower + (upper - lower) / 2;
+            if self.cdf(mid) >= p {
+                upper = mid;
+            } else {
+                lower = mid + 1;
+            }
+        }
+        lower
+    }
+}
+
+impl Min<u64> for PoissonBinomial {
+    /// Returns the minimum value in the domain of the
+    /// Poisson-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// #{i : p_i = 1}
+    /// ```
+    fn min(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl Max<u64> for PoissonBinomial {
+    /// Returns the maximum value in the domain of the
+    /// Poisson-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// #{i : p_i > 0}
+    /// ```
+    fn max(&self) -> u64 {
+        self.offset + self.trials
+    }
+}
+
+impl Distribution<f64> for PoissonBinomial {
+    /// Returns the mean of the Poisson-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ p_i
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.mean)
+    }
+
+    /// Returns the variance of the Poisson-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ p_i (1 - p_i)
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        Some(self.variance)
+    }
+
+    /// Returns the entropy of the Poisson-binomial distribution, or `None`
+    /// if the distribution is approximated
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -Σ P(X = k) ln(P(X = k))
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        match self.method {
+            Method::Binomial(ref b) => b.entropy(),
+            Method::Exact { ref pmf, .. } => Some(
+                pmf.iter()
+                    .filter(|&&p| p > 0.0)
+                    .fold(0.0, |acc, p| acc - p * p.ln()),
+            ),
+            Method::Poisson { .. } | Method::Normal { .. } => None,
+        }
+    }
+
+    /// Returns the skewness of the Poisson-binomial distribution, or `None`
+    /// if its variance is zero
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ p_i (1 - p_i) (1 - 2 p_i) / σ^3
+    /// ```
+    ///
+    /// where `σ^2` is the variance
+    fn skewness(&self) -> Option<f64> {
+        if self.variance > 0.0 {
+            Some(self.third_moment / (self.variance * self.variance.sqrt()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Median<f64> for PoissonBinomial {
+    /// Returns the median of the Poisson-binomial distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// min {x : F(x) >= 1 / 2}
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5) as f64
+    }
+}
+
+impl Mode<Option<u64>> for PoissonBinomial {
+    /// Returns the mode of the Poisson-binomial distribution, the smallest
+    /// if there are two
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// argmax_k P(X = k)
+    /// ```
+    ///
+    /// which is `floor(μ)` or `ceil(μ)` for the mean `μ` (Darroch, 1964), so
+    /// only these are compared when the distribution is approximated
+    fn mode(&self) -> Option<u64> {
+        let mode = match self.method {
+            Method::Binomial(ref b) => self.offset + b.mode()?,
+            Method::Exact { ref pmf, .. } => {
+                let best = pmf
+                    .iter()
+                    .enumerate()
+                    .fold(0, |best, (j, &p)| if p > pmf[best] { j } else { best });
+                self.offset + best as u64
+            }
+            Method::Poisson { .. } | Method::Normal { .. } => {
+                let floor = self.mean.floor() as u64;
+                if self.pmf(floor + 1) > self.pmf(floor) {
+                    floor + 1
+                } else {
+                    floor
+                }
+            }
+        };
+        Some(mode)
+    }
+}
+
+impl Discrete<u64, f64> for PoissonBinomial {
+    /// Calculates the probability mass function for the Poisson-binomial
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{A ⊆ {1..n}, |A| = x} Π_{i ∈ A} p_i Π_{i ∉ A} (1 - p_i)
+    /// ```
+    ///
+    /// computed by convolution, or approximated by differences of the
+    /// approximate cumulative distribution function, see `error_bound`
+    fn pmf(&self, x: u64) -> f64 {
+        if x < self.offset || x > self.offset + self.trials {
+            return 0.0;
+        }
+        let j = x - self.offset;
+        match self.method {
+            Method::Binomial(ref b) => b.pmf(j),
+            Method::Exact { ref pmf, .. } => pmf[j as usize],
+            Method::Poisson { .. } | Method::Normal { .. } => {
+                let below = if j == 0 { 0.0 } else { self.inner_cdf(j - 1) };
+                (self.inner_cdf(j) - below).max(0.0)
+            }
+        }
+    }
+
+    /// Calculates the log probability mass function for the
+    /// Poisson-binomial distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(P(X = x))
+    /// ```
+    fn ln_pmf(&self, x: u64) -> f64 {
+        match self.method {
+            Method::Binomial(ref b) if x >= self.offset => b.ln_pmf(x - self.offset),
+            _ => self.pmf(x).ln(),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(probs: Vec<f64>; PoissonBinomial);
+
+    fn random_probs(n: usize, scale: f64, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| scale * rng.gen::<f64>()).collect()
+    }
+
+    /// Sums the probabilities of every outcome of the trials
+    fn brute_force(probs: &[f64]) -> Vec<f64> {
+        let n = probs.len();
+        let mut pmf = vec![0.0; n + 1];
+        for outcome in 0..1u32 << n {
+            let mass = probs.iter().enumerate().fold(1.0, |acc, (i, p)| {
+                if outcome & (1 << i) != 0 { acc * p } else { acc * (1.0 - p) }
+            });
+            pmf[outcome.count_ones() as usize] += mass;
+        }
+        pmf
+    }
+
+    #[test]
+    fn test_create() {
+        try_create(vec![0.5]);
+        try_create(vec![0.0, 1.0]);
+        try_create(vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(vec![]);
+        bad_create_case(vec![0.5, f64::NAN]);
+        bad_create_case(vec![-0.1, 0.5]);
+        bad_create_case(vec![1.1]);
+    }
+
+    #[test]
+    fn test_pmf_brute_force() {
+        for (seed, &n) in [1usize, 2, 5, 12, 20].iter().enumerate() {
+            let mut probs = random_probs(n, 1.0, seed as u64);
+            if n > 4 {
+                probs[1] = 0.0;
+                probs[3] = 1.0;
+            }
+            let expected = brute_force(&probs);
+            let dist = try_create(probs);
+            let mut cdf = 0.0;
+            for (k, p) in expected.iter().enumerate() {
+                cdf += p;
+                assert_almost_eq!(dist.pmf(k as u64), *p, 1e-14);
+                assert_almost_eq!(dist.cdf(k as u64), cdf, 1e-13);
+                assert_almost_eq!(dist.sf(k as u64), 1.0 - cdf, 1e-13);
+            }
+            assert_eq!(dist.pmf(n as u64 + 1), 0.0);
+            let mean: f64 = expected.iter().enumerate().map(|(k, p)| k as f64 * p).sum();
+            let variance: f64 = expected.iter().enumerate().map(|(k, p)| (k as f64 - mean).powi(2) * p).sum();
+            assert_almost_eq!(dist.mean().unwrap(), mean, 1e-12);
+            assert_almost_eq!(dist.variance().unwrap(), variance, 1e-12);
+            let argmax = (0..expected.len()).fold(0, |best, k| if expected[k] > expected[best] { k } else { best });
+            assert_eq!(dist.mode(), Some(argmax as u64));
+        }
+    }
+
+    #[test]
+    fn test_equal_probs_match_binomial() {
+        let dist = try_create(vec![0.3; 40]);
+        let binomial = Binomial::new(0.3, 40).unwrap();
+        for k in 0..42 {
+            assert_eq!(dist.pmf(k), binomial.pmf(k));
+            assert_eq!(dist.ln_pmf(k), binomial.ln_pmf(k));
+            assert_eq!(dist.cdf(k), binomial.cdf(k));
+            assert_eq!(dist.sf(k), binomial.sf(k));
+        }
+        assert_eq!(dist.mode(), binomial.mode());
+        assert_eq!(dist.entropy(), binomial.entropy());
+        // degenerate trials shift the binomial
+        let dist = try_create(vec![1.0, 0.25, 0.0, 0.25, 1.0, 0.25]);
+        let binomial = Binomial::new(0.25, 3).unwrap();
+        assert_eq!((dist.min(), dist.max()), (2, 5));
+        for k in 0..4 {
+            assert_eq!(dist.pmf(k + 2), binomial.pmf(k));
+            assert_eq!(dist.cdf(k + 2), binomial.cdf(k));
+        }
+        assert_eq!(dist.pmf(1), 0.0);
+        assert_eq!(dist.cdf(1), 0.0);
+        assert_eq!(dist.sf(1), 1.0);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let dist = try_create(vec![1.0, 0.0, 1.0]);
+        assert_eq!((dist.min(), dist.max()), (2, 2));
+        assert_eq!(dist.pmf(2), 1.0);
+        assert_eq!(dist.ln_pmf(2), 0.0);
+        assert_eq!(dist.pmf(1), 0.0);
+        assert_eq!(dist.cdf(1), 0.0);
+        assert_eq!(dist.cdf(2), 1.0);
+        assert_eq!(dist.mean(), Some(2.0));
+        assert_eq!(dist.variance(), Some(0.0));
+        assert_eq!(dist.entropy(), Some(0.0));
+        assert_eq!(dist.mode(), Some(2));
+        assert_eq!(dist.median(), 2.0);
+        test_none(vec![0.0, 1.0], |x| x.skewness());
+        let mut rng = StdRng::seed_from_u64(285);
+        assert_eq!(dist.sample(&mut rng), 2.0);
+        assert_eq!(dist.sample_many(&mut rng, 3), vec![2.0; 3]);
+        // all trials impossible or certain reproduce the binomial as well
+        assert_eq!(try_create(vec![0.0; 3]).pmf(0), Binomial::new(0.0, 3).unwrap().pmf(0));
+        assert_eq!(try_create(vec![1.0; 3]).pmf(3), Binomial::new(1.0, 3).unwrap().pmf(3));
+    }
+
+    #[test]
+    fn test_skewness_entropy() {
+        let probs = vec![0.1, 0.6, 0.3, 0.95];
+        let expected = brute_force(&probs);
+        let dist = try_create(probs);
+        let mean = dist.mean().unwrap();
+        let sd = dist.std_dev().unwrap();
+        let skewness: f64 = expected.iter().enumerate().map(|(k, p)| ((k as f64 - mean) / sd).powi(3) * p).sum();
+        let entropy: f64 = expected.iter().map(|p| -p * p.ln()).sum();
+        test_case(vec![0.1, 0.6, 0.3, 0.95], skewness, |x| x.skewness().unwrap());
+        test_case(vec![0.1, 0.6, 0.3, 0.95], entropy, |x| x.entropy().unwrap());
+    }
+
+    #[test]
+    fn test_inverse_cdf_median() {
+        let dist = try_create(vec![0.1, 0.6, 0.3, 0.95, 1.0]);
+        assert_eq!(dist.inverse_cdf(0.0), 1);
+        assert_eq!(dist.inverse_cdf(1.0), 5);
+        for k in 1..5 {
+            let p = dist.cdf(k);
+            assert_eq!(dist.inverse_cdf(p), k);
+            assert_eq!(dist.inverse_cdf(p + 1e-9), k + 1);
+        }
+        assert_eq!(dist.median(), dist.inverse_cdf(0.5) as f64);
+        assert!(dist.cdf(dist.median() as u64) >= 0.5);
+        assert!(dist.cdf(dist.median() as u64 - 1) < 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_cdf_bad_p() {
+        try_create(vec![0.5, 0.2]).inverse_cdf(1.5);
+    }
+
+    #[test]
+    fn test_normal_approximation() {
+        let probs = random_probs(10_000, 1.0, 2850);
+        let exact = try_create(probs.clone());
+        let approx = PoissonBinomial::with_exact_limit(probs, 0).unwrap();
+        assert_eq!(exact.error_bound(), 0.0);
+        assert!(matches!(approx.method, Method::Normal { .. }));
+        let mut error: f64 = 0.0;
+        for k in 0..=10_000 {
+            error = error.max((exact.cdf(k) - approx.cdf(k)).abs());
+            assert_almost_eq!(exact.sf(k), approx.sf(k), approx.error_bound());
+        }
+        assert!(error <= approx.error_bound());
+        // the refined normal approximation is much closer than its bound
+        assert!(error < 1e-5);
+        assert_eq!(approx.mode(), exact.mode());
+        assert_eq!(approx.median(), exact.median());
+        assert_eq!(approx.mean(), exact.mean());
+        assert_eq!(approx.variance(), exact.variance());
+        assert_eq!(approx.entropy(), None);
+    }
+
+    #[test]
+    fn test_poisson_approximation() {
+        let probs = random_probs(10_000, 1e-3, 2851);
+        let exact = try_create(probs.clone());
+        let approx = PoissonBinomial::with_exact_limit(probs, 0).unwrap();
+        assert!(matches!(approx.method, Method::Poisson { .. }));
+        let mut error: f64 = 0.0;
+        for k in 0..100 {
+            error = error.max((exact.cdf(k) - approx.cdf(k)).abs());
+        }
+        assert!(error <= approx.error_bound());
+        assert!(approx.error_bound() < 1e-3);
+        test::check_discrete_distribution(&approx, 30);
+    }
+
+    #[test]
+    fn test_discrete() {
+        test::check_discrete_distribution(&try_create(vec![0.1, 0.6, 0.3, 0.95]), 4);
+        test::check_discrete_distribution(&try_create(random_probs(200, 0.5, 2852)), 200);
+        test::check_discrete_distribution(&try_create(vec![0.0, 1.0, 0.7]), 2);
+    }
+
+    #[test]
+    fn test_sample() {
+        let dist = try_create(random_probs(50, 1.0, 2853));
+        let mean = dist.mean().unwrap();
+        let se = (dist.variance().unwrap() / 20_000.0).sqrt();
+        let mut rng = StdRng::seed_from_u64(2854);
+        let flipped: Vec<f64> = (0..20_000).map(|_| dist.sample(&mut rng)).collect();
+        let inverted = dist.sample_many(&mut rng, 20_000);
+        for samples in &[flipped, inverted] {
+            assert!((samples.iter().sum::<f64>() / 20_000.0 - mean).abs() < 4.0 * se);
+            assert!(samples.iter().all(|&x| x.fract() == 0.0 && x <= 50.0));
+        }
+        // both samplers agree on the frequency of every value
+        let dist = try_create(vec![0.1, 0.6, 0.3, 0.95]);
+        let inverted = dist.sample_many(&mut rng, 40_000);
+        for k in 0..5 {
+            let frequency = inverted.iter().filter(|&&x| x == k as f64).count() as f64 / 40_000.0;
+            assert!((frequency - dist.pmf(k)).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let dist = try_create(vec![0.1, 0.0, 0.7]);
+        let names: Vec<String> = dist.parameters().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["probs[0]", "probs[1]", "probs[2]"]);
+        test::check_parameters(&dist, |d| d.probs().to_vec());
+        assert_eq!(dist.set_parameter("probs[1]", 0.5).unwrap().pmf(3), 0.1 * 0.5 * 0.7);
+        assert!(dist.set_parameter("probs[1]", 2.0).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(try_create(vec![0.5, 1.0]).to_string(), "PoiBin([0.5, 1.0])");
+    }
+}
@@ -0,0 +1,580 @@
+use crate::distribution::{ziggurat, Continuous, ContinuousCDF, Normal, Parameterized};
+use crate::function::integrate::integrate;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Absolute tolerance of the quadratures behind the cdf and the moments
+const TOLERANCE: f64 = 1e-12;
+
+/// Implements the [projected normal](https://en.wikipedia.org/wiki/Projected_normal_distribution)
+/// distribution of the angle `atan2(y, x)` on `[-π, π]` of a bivariate
+/// normal vector `(x, y)` with mean `mu` and covariance `sigma`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ProjectedNormal};
+///
+/// let n = ProjectedNormal::new([0.0, 0.0], [[1.0, 0.0], [0.0, 1.0]]).unwrap();
+/// assert!((n.pdf(1.0) - 1.0 / (2.0 * std::f64::consts::PI)).abs() < 1e-15);
+/// assert_eq!(n.circular_mean(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ProjectedNormal {
+    mu: [f64; 2],
+    sigma: [[f64; 2]; 2],
+    precision: [[f64; 2]; 2],
+    det: f64,
+    chol: [f64; 3],
+    quad: f64,
+}
+
+impl ProjectedNormal {
+    /// Constructs a new projected normal distribution from the mean `mu` and
+    /// the covariance matrix `sigma` of the underlying bivariate normal
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry is not finite, or if `sigma` is not
+    /// symmetric positive definite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ProjectedNormal;
+    ///
+    /// let mut result = ProjectedNormal::new([1.0, 0.5], [[1.0, 0.3], [0.3, 2.0]]);
+    /// assert!(result.is_ok());
+    ///
+    /// result = ProjectedNormal::new([1.0, 0.5], [[1.0, 2.0], [2.0, 1.0]]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(mu: [f64; 2], sigma: [[f64; 2]; 2]) -> Result<ProjectedNormal> {
+        let finite = mu
+            .iter()
+            .chain(sigma.iter().flatten())
+            .all(|x| x.is_finite());
+        if !finite || sigma[0][1] != sigma[1][0] {
+            return Err(StatsError::BadParams);
+        }
+        let det = sigma[0][0] * sigma[1][1] - sigma[0][1] * sigma[1][0];
+        if sigma[0][0] <= 0.0 || det <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let precision = [
+            [sigma[1][1] / det, -sigma[0][1] / det],
+            [-sigma[1][0] / det, sigma[0][0] / det],
+        ];
+        let l11 = sigma[0][0].sqrt();
+        let l21 = sigma[1][0] / l11;
+        let chol = [l11, l21, (det / sigma[0][0]).sqrt()];
+        let quad = quadratic_form(&precision, mu, mu);
+        Ok(ProjectedNormal {
+            mu,
+            sigma,
+            precision,
+            det,
+            chol,
+            quad,
+        })
+    }
+
+    /// Returns the mean of the underlying bivariate normal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ProjectedNormal;
+    ///
+    /// let n = ProjectedNormal::new([1.0, 0.5], [[1.0, 0.0], [0.0, 1.0]]).unwrap();
+    /// assert_eq!(n.mu(), [1.0, 0.5]);
+    /// ```
+    pub fn mu(&self) -> [f64; 2] {
+        self.mu
+    }
+
+    /// Returns the covariance matrix of the underlying bivariate normal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ProjectedNormal;
+    ///
+    /// let n = ProjectedNormal::new([1.0, 0.5], [[1.0, 0.0], [0.0, 1.0]]).unwrap();
+    /// assert_eq!(n.sigma(), [[1.0, 0.0], [0.0, 1.0]]);
+    /// ```
+    pub fn sigma(&self) -> [[f64; 2]; 2] {
+        self.sigma
+    }
+
+    /// Returns the mean direction `atan2(E[sin θ], E[cos θ])` of the
+    /// projected normal distribution, computed by numerical integration, or
+    /// `None` when `mu` is zero and the mean direction is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ProjectedNormal;
+    ///
+    /// let n = ProjectedNormal::new([1.0, 1.0], [[1.0, 0.0], [0.0, 1.0]]).unwrap();
+    /// let mean = n.circular_mean().unwrap();
+    /// assert!((mean - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+    /// ```
+    pub fn circular_mean(&self) -> Option<f64> {
+        if self.mu == [0.0, 0.0] {
+            return None;
+        }
+        let (c, s) = self.trigonometric_moment();
+        Some(s.atan2(c))
+    }
+
+    /// Returns the mean resultant length `|E[e^(iθ)]|` of the projected
+    /// normal distribution, computed by numerical integration
+    ///
+    /// # Remarks
+    ///
+    /// The angle of a zero-mean bivariate normal is symmetric under
+    /// `θ → θ + π`, so the resultant length is exactly `0` when `mu` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ProjectedNormal;
+    ///
+    /// let n = ProjectedNormal::new([0.0, 0.0], [[1.0, 0.5], [0.5, 3.0]]).unwrap();
+    /// assert_eq!(n.mean_resultant_length(), 0.0);
+    /// ```
+    pub fn mean_resultant_length(&self) -> f64 {
+        if self.mu == [0.0, 0.0] {
+            return 0.0;
+        }
+        let (c, s) = self.trigonometric_moment();
+        c.hypot(s)
+    }
+
+    /// Returns `(E[cos θ], E[sin θ])`
+    fn trigonometric_moment(&self) -> (f64, f64) {
+        (self.expect(f64::cos), self.expect(f64::sin))
+    }
+
+    /// Integrates `g(θ) * pdf(θ)` over `[-π, π]`
+    fn expect<F: Fn(f64) -> f64>(&self, g: F) -> f64 {
+        integrate(|t| g(t) * self.pdf(t), -PI, PI, TOLERANCE)
+    }
+}
+
+/// Computes `uᵀ P v`
+fn quadratic_form(p: &[[f64; 2]; 2], u: [f64; 2], v: [f64; 2]) -> f64 {
+    u[0] * (p[0][0] * v[0] + p[0][1] * v[1]) + u[1] * (p[1][0] * v[0] + p[1][1] * v[1])
+}
+
+/// Computes `ln(e^(-d^2 / 2) + sqrt(2π) d Φ(d))`
+///
+/// The two terms cancel for negative `d`, so below `-2` the sum is written
+/// as `e^(-d^2 / 2) r / (t + r)` with `t = -d`, where `1 / (t + r)` is the
+/// continued fraction of the Mills ratio `Q(t) / φ(t)`
+fn ln_g(d: f64) -> f64 {
+    if d < -2.0 {
+        let t = -d;
+        let mut tail = 0.0;
+        for k in (2..=100).rev() {
+            tail = k as f64 / (t + tail);
+        }
+        let r = 1.0 / (t + tail);
+        -0.5 * t * t + (r / (t + r)).ln()
+    } else {
+        let phi = Normal::standard().cdf(d);
+        ((-0.5 * d * d).exp() + (2.0 * PI).sqrt() * d * phi).ln()
+    }
+}
+
+impl Parameterized for ProjectedNormal {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("mu[0]".into(), self.mu[0]),
+            ("mu[1]".into(), self.mu[1]),
+            ("sigma[0][0]".into(), self.sigma[0][0]),
+            ("sigma[0][1]".into(), self.sigma[0][1]),
+            ("sigma[1][0]".into(), self.sigma[1][0]),
+            ("sigma[1][1]".into(), self.sigma[1][1]),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<ProjectedNormal> {
+        match *values {
+            [m0, m1, s00, s01, s10, s11] => {
+                ProjectedNormal::new([m0, m1], [[s00, s01], [s10, s11]])
+            }
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for ProjectedNormal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PN({:?}, {:?})", self.mu, self.sigma)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for ProjectedNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let z1 = ziggurat::sample_std_normal(rng);
+        let z2 = ziggurat::sample_std_normal(rng);
+        let x = self.mu[0] + self.chol[0] * z1;
+        let y = self.mu[1] + self.chol[1] * z1 + self.chol[2] * z2;
+        y.atan2(x)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for ProjectedNormal {
+    /// Calculates the cumulative distribution function for the projected
+    /// normal distribution at `x` by numerical integration of the pdf
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ pdf(t) dt over [-π, x]
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= -PI {
+            0.0
+        } else if x >= PI {
+            1.0
+        } else {
+            integrate(|t| self.pdf(t), -PI, x, TOLERANCE).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the survival function for the projected normal
+    /// distribution at `x` by numerical integration of the pdf
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ pdf(t) dt over [x, π]
+    /// ```
+    fn sf(&self, x: f64) -> f64 {
+        if x <= -PI {
+            1.0
+        } else if x >= PI {
+            0.0
+        } else {
+            integrate(|t| self.pdf(t), x, PI, TOLERANCE).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// projected normal distribution at `p` by safeguarded Newton iteration
+    /// on the cdf
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            return -PI;
+        }
+        if p == 1.0 {
+            return PI;
+        }
+        let (mut lo, mut hi) = (-PI, PI);
+        let mut x = 2.0 * PI * p - PI;
+        for _ in 0..100 {
+            let error = self.cdf(x) - p;
+            if error < 0.0 {
+                lo = x;
+            } else {
+                hi = x;
+            }
+            let newton = x - error / self.pdf(x);
+            let next = if newton > lo && newton < hi {
+                newton
+            } else {
+                0.5 * (lo + hi)
+            };
+            let step = (next - x).abs();
+            x = next;
+            if step <= 1e-15 * (1.0 + x.abs()) || hi - lo <= 1e-15 {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl Min<f64> for ProjectedNormal {
+    /// Returns the minimum angle of the projected normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -π
+    /// ```
+    fn min(&self) -> f64 {
+        -PI
+    }
+}
+
+impl Max<f64> for ProjectedNormal {
+    /// Returns the maximum angle of the projected normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// π
+    /// ```
+    fn max(&self) -> f64 {
+        PI
+    }
+}
+
+impl Distribution<f64> for ProjectedNormal {
+    /// Returns the mean of the angle of the projected normal distribution as
+    /// a linear quantity on `[-π, π]`, computed by numerical integration
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ θ pdf(θ) dθ over [-π, π]
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.expect(|t| t))
+    }
+
+    /// Returns the variance of the angle of the projected normal
+    /// distribution as a linear quantity on `[-π, π]`, computed by numerical
+    /// integration
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ (θ - μ)^2 pdf(θ) dθ over [-π, π]
+    /// ```
+    ///
+    /// where `μ` is the mean
+    fn variance(&self) -> Option<f64> {
+        let mean = self.expect(|t| t);
+        Some(self.expect(|t| (t - mean) * (t - mean)))
+    }
+
+    /// Returns the entropy of the projected normal distribution, computed
+    /// by numerical integration
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -∫ pdf(θ) ln(pdf(θ)) dθ over [-π, π]
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        let value = integrate(
+            |t| {
+                let density = self.pdf(t);
+                if density == 0.0 {
+                    0.0
+                } else {
+                    -density * density.ln()
+                }
+            },
+            -PI,
+            PI,
+            TOLERANCE,
+        );
+        Some(value)
+    }
+
+    /// Returns the skewness of the angle of the projected normal
+    /// distribution as a linear quantity on `[-π, π]`, computed by numerical
+    /// integration
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ ((θ - μ) / σ)^3 pdf(θ) dθ over [-π, π]
+    /// ```
+    ///
+    /// where `μ` is the mean and `σ` the standard deviation
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.expect(|t| t);
+        let std_dev = self.expect(|t| (t - mean) * (t - mean)).sqrt();
+        Some(self.expect(|t| ((t - mean) / std_dev).powi(3)))
+    }
+}
+
+impl Median<f64> for ProjectedNormal {
+    /// Returns the median of the angle of the projected normal distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(0.5)
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for ProjectedNormal {
+    /// Calculates the probability density function for the projected normal
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (e^(-C / 2) + sqrt(2π) D Φ(D) e^(-(C - D^2) / 2)) / (2π A sqrt(|Σ|))
+    /// ```
+    ///
+    /// where `u = (cos x, sin x)`, `A = uᵀ Σ^-1 u`, `B = uᵀ Σ^-1 μ`,
+    /// `C = μᵀ Σ^-1 μ`, `D = B / sqrt(A)` and `Φ` is the standard normal cdf
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the projected
+    /// normal distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(e^(-D^2 / 2) + sqrt(2π) D Φ(D)) - (C - D^2) / 2 - ln(2π A sqrt(|Σ|))
+    /// ```
+    ///
+    /// with `A`, `C` and `D` as for the pdf
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if !(-PI..=PI).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        let u = [x.cos(), x.sin()];
+        let a = quadratic_form(&self.precision, u, u);
+        let d = quadratic_form(&self.precision, u, self.mu) / a.sqrt();
+        ln_g(d) - 0.5 * (self.quad - d * d) - (2.0 * PI * a * self.det.sqrt()).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::CircularUniform;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const PARAMS: [([f64; 2], [[f64; 2]; 2]); 5] = [
+        ([0.0, 0.0], [[1.0, 0.0], [0.0, 1.0]]),
+        ([0.0, 0.0], [[1.0, 0.7], [0.7, 2.0]]),
+        ([1.0, 0.0], [[1.0, 0.0], [0.0, 1.0]]),
+        ([-2.0, 0.5], [[0.3, -0.1], [-0.1, 1.5]]),
+        ([3.0, 1.0], [[0.5, 0.2], [0.2, 0.3]]),
+    ];
+
+    fn try_create(mu: [f64; 2], sigma: [[f64; 2]; 2]) -> ProjectedNormal {
+        ProjectedNormal::new(mu, sigma).unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        for &(mu, sigma) in &PARAMS {
+            try_create(mu, sigma);
+        }
+        assert!(ProjectedNormal::new([f64::NAN, 0.0], [[1.0, 0.0], [0.0, 1.0]]).is_err());
+        assert!(ProjectedNormal::new([0.0, 0.0], [[1.0, 0.1], [0.0, 1.0]]).is_err());
+        assert!(ProjectedNormal::new([0.0, 0.0], [[-1.0, 0.0], [0.0, -1.0]]).is_err());
+        assert!(ProjectedNormal::new([0.0, 0.0], [[1.0, 1.0], [1.0, 1.0]]).is_err());
+        assert!(ProjectedNormal::new([0.0, 0.0], [[f64::INFINITY, 0.0], [0.0, 1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_isotropic_reduces_to_circular_uniform() {
+        let u = CircularUniform::new();
+        for &s in &[0.1, 1.0, 7.5] {
+            let n = try_create([0.0, 0.0], [[s, 0.0], [0.0, s]]);
+            for i in 0..=20 {
+                let x = -PI + 2.0 * PI * i as f64 / 20.0;
+                assert_almost_eq!(n.pdf(x), u.pdf(x), 1e-15);
+                assert_almost_eq!(n.cdf(x), u.cdf(x), 1e-12);
+            }
+            assert_almost_eq!(n.entropy().unwrap(), u.entropy().unwrap(), 1e-12);
+            assert_almost_eq!(n.variance().unwrap(), u.variance().unwrap(), 1e-12);
+            assert_eq!(n.circular_mean(), u.circular_mean());
+            assert_eq!(n.mean_resultant_length(), u.mean_resultant_length());
+        }
+    }
+
+    #[test]
+    fn test_pdf_normalized() {
+        for &(mu, sigma) in &PARAMS {
+            let n = try_create(mu, sigma);
+            let total = integrate(|t| n.pdf(t), -PI, PI, 1e-13);
+            assert_almost_eq!(total, 1.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_pdf() {
+        // reference values computed with mpmath
+        let n = try_create([3.0, 1.0], [[0.5, 0.2], [0.2, 0.3]]);
+        assert_almost_eq!(n.pdf(0.3), 2.724917184408226119, 1e-13);
+        assert_almost_eq!(n.pdf(2.0), 2.238613369418006668e-6, 1e-15);
+        assert_almost_eq!(n.pdf(-2.5), 2.152428389564336981e-6, 1e-18);
+        assert_almost_eq!(n.cdf(0.0), 0.03394457743091448976, 1e-12);
+        assert_almost_eq!(n.cdf(0.4), 0.71628866472595894273, 1e-12);
+        assert_almost_eq!(n.sf(0.4), 1.0 - 0.71628866472595894273, 1e-12);
+        assert_almost_eq!(n.inverse_cdf(0.71628866472595894273), 0.4, 1e-10);
+        assert_eq!(n.pdf(4.0), 0.0);
+    }
+
+    #[test]
+    fn test_circular_moments() {
+        // for an isotropic covariance the resultant length is
+        // sqrt(π / 2) (r / 2) e^(-r^2 / 4) (I0(r^2 / 4) + I1(r^2 / 4))
+        let expected = [(0.5, 0.30383520526347913), (1.0, 0.55717946838224782), (2.0, 0.84432016364055657)];
+        for &(r, rho) in &expected {
+            let n = try_create([r * 0.6, r * 0.8], [[1.0, 0.0], [0.0, 1.0]]);
+            assert_almost_eq!(n.mean_resultant_length(), rho, 1e-12);
+            assert_almost_eq!(n.circular_mean().unwrap(), 0.8f64.atan2(0.6), 1e-12);
+        }
+        let n = try_create([3.0, 1.0], [[0.5, 0.2], [0.2, 0.3]]);
+        assert_almost_eq!(n.circular_mean().unwrap(), 0.31069531272929167, 1e-12);
+        assert_almost_eq!(n.mean_resultant_length(), 0.98783271933579739, 1e-12);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = try_create([-2.0, 0.5], [[0.3, -0.1], [-0.1, 1.5]]);
+        let (mut c, mut s) = (0.0, 0.0);
+        let count = 20000;
+        for _ in 0..count {
+            let x = rand::distributions::Distribution::sample(&n, &mut rng);
+            assert!((-PI..=PI).contains(&x));
+            c += x.cos();
+            s += x.sin();
+        }
+        let (c, s) = (c / count as f64, s / count as f64);
+        let rho = n.mean_resultant_length();
+        let mean = n.circular_mean().unwrap();
+        assert!((c.hypot(s) - rho).abs() < 0.02);
+        assert!((c - rho * mean.cos()).abs() < 0.02);
+        assert!((s - rho * mean.sin()).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_continuous() {
+        for &(mu, sigma) in &PARAMS {
+            test::check_continuous_distribution(&try_create(mu, sigma), -PI, PI);
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = ProjectedNormal::new([1.0, 0.5], [[1.0, 0.3], [0.3, 2.0]]).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(0.4), d.pdf(-2.0)]);
+        assert_eq!(n.set_parameter("sigma[0][1]", 0.5), ProjectedNormal::new([1.0, 0.5], [[1.0, 0.5], [0.3, 2.0]]));
+    }
+}
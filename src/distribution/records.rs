@@ -0,0 +1,451 @@
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the distribution of the number of
+/// [records](https://en.wikipedia.org/wiki/Record_value) among `n`
+/// independent and identically distributed continuous observations, the
+/// observations larger than all the previous ones, the first being a
+/// record
+///
+/// # Remarks
+///
+/// The `i`-th observation is a record with probability `1 / i`,
+/// independently of the others, so the number of records is the number of
+/// cycles of a uniformly random permutation of `n` elements and
+///
+/// ```text
+/// P(X = k) = |s(n, k)| / n!
+/// ```
+///
+/// where `|s(n, k)|` are the unsigned Stirling numbers of the first kind.
+/// It does not depend on the distribution of the observations, which
+/// makes it the null distribution of tests of trend based on records.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, Records};
+/// use statrs::statistics::Distribution;
+///
+/// let n = Records::new(3).unwrap();
+/// // |s(3, k)| = 2, 3, 1 for k = 1, 2, 3
+/// assert!((n.pmf(1) - 2.0 / 6.0).abs() < 1e-15);
+/// assert!((n.pmf(2) - 3.0 / 6.0).abs() < 1e-15);
+/// assert!((n.mean().unwrap() - (1.0 + 1.0 / 2.0 + 1.0 / 3.0)).abs() < 1e-15);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Records {
+    n: u64,
+    pmf: Vec<f64>,
+    cdf: Vec<f64>,
+    sf: Vec<f64>,
+    mean: f64,
+    variance: f64,
+}
+
+impl Records {
+    /// Constructs a new distribution of the number of records among `n`
+    /// observations
+    ///
+    /// # Remarks
+    ///
+    /// The probabilities are computed with the recurrence of the Stirling
+    /// numbers divided by the factorials,
+    ///
+    /// ```text
+    /// P_i(k) = (1 - 1 / i) P_{i-1}(k) + P_{i-1}(k - 1) / i
+    /// ```
+    ///
+    /// which convolves the indicators of the records one at a time. The
+    /// probabilities that underflow to zero are not tabulated, so the
+    /// construction takes `O(n K)` time and `O(K)` memory, where `K`, the
+    /// largest number of records whose probability does not underflow, is
+    /// `n` up to `170` and at most a few hundred beyond.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Records;
+    ///
+    /// let mut result = Records::new(10);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Records::new(0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(n: u64) -> Result<Records> {
+        if n == 0 {
+            return Err(StatsError::ArgMustBePositive("n"));
+        }
+        // pmf[k] is the probability of k records among the first i
+        // observations, for the k where it does not underflow
+        let mut pmf = vec![0.0, 1.0];
+        let (mut mean, mut variance) = (1.0, 0.0);
+        for i in 2..=n {
+            let p = 1.0 / i as f64;
+            mean += p;
+            variance += p * (1.0 - p);
+            let top = pmf.len() - 1;
+            let next = pmf[top] * p;
+            for k in (2..=top).rev() {
+                pmf[k] = pmf[k] * (1.0 - p) + pmf[k - 1] * p;
+            }
+            pmf[1] *= 1.0 - p;
+            if next > 0.0 {
+                pmf.push(next);
+            }
+        }
+        let (cdf, sf) = internal::cumulative_tables(&pmf);
+        Ok(Records {
+            n,
+            pmf,
+            cdf,
+            sf,
+            mean,
+            variance,
+        })
+    }
+
+    /// Returns the number of observations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Records;
+    ///
+    /// let n = Records::new(10).unwrap();
+    /// assert_eq!(n.n(), 10);
+    /// ```
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+}
+
+impl Parameterized for Records {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("n".into(), self.n as f64)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Records> {
+        match *values {
+            [n] => Records::new(internal::integer_parameter(n)?),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for Records {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Records({})", self.n)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Records {
+    /// Simulates the record indicators of the `n` observations, the `i`-th
+    /// being a record with probability `1 / i`, in `O(n)` time
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        (1..=self.n).fold(0.0, |acc, i| {
+            if rng.gen::<f64>() * (i as f64) < 1.0 {
+                acc + 1.0
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl DiscreteCDF<u64, f64> for Records {
+    /// Calculates the cumulative distribution function for the
+    /// distribution of the number of records at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k <= x} |s(n, k)| / n!
+    /// ```
+    fn cdf(&self, x: u64) -> f64 {
+        match self.cdf.get(x as usize) {
+            Some(&c) => c,
+            None => 1.0,
+        }
+    }
+
+    /// Calculates the survival function for the distribution of the
+    /// number of records at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k > x} |s(n, k)| / n!
+    /// ```
+    fn sf(&self, x: u64) -> f64 {
+        match self.sf.get(x as usize) {
+            Some(&s) => s,
+            None => 0.0,
+        }
+    }
+}
+
+impl Min<u64> for Records {
+    /// Returns the minimum value in the domain of the distribution of the
+    /// number of records, the first observation being always a record
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1
+    /// ```
+    fn min(&self) -> u64 {
+        1
+    }
+}
+
+impl Max<u64> for Records {
+    /// Returns the maximum value in the domain of the distribution of the
+    /// number of records, reached by increasing observations
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n
+    /// ```
+    fn max(&self) -> u64 {
+        self.n
+    }
+}
+
+impl Distribution<f64> for Records {
+    /// Returns the mean of the distribution of the number of records
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H_n = Σ_{i=1}^n 1 / i
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(self.mean)
+    }
+
+    /// Returns the variance of the distribution of the number of records
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H_n - H_n^(2) = Σ_{i=1}^n (1 / i - 1 / i^2)
+    /// ```
+    ///
+    /// where `H_n^(2) = Σ_{i=1}^n 1 / i^2`, summed term by term while
+    /// constructing the distribution, which avoids the cancellation of the
+    /// difference for small `n`
+    fn variance(&self) -> Option<f64> {
+        Some(self.variance)
+    }
+
+    /// Returns the entropy of the distribution of the number of records
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -Σ P(X = k) ln(P(X = k))
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            self.pmf
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .fold(0.0, |acc, p| acc - p * p.ln()),
+        )
+    }
+}
+
+impl Mode<Option<u64>> for Records {
+    /// Returns the mode of the distribution of the number of records, the
+    /// smallest if there are two
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// argmax_k |s(n, k)|
+    /// ```
+    fn mode(&self) -> Option<u64> {
+        let best = self
+            .pmf
+            .iter()
+            .enumerate()
+            .fold(1, |best, (k, &p)| if p > self.pmf[best] { k } else { best });
+        Some(best as u64)
+    }
+}
+
+impl Discrete<u64, f64> for Records {
+    /// Calculates the probability mass function for the distribution of
+    /// the number of records at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// |s(n, x)| / n!
+    /// ```
+    fn pmf(&self, x: u64) -> f64 {
+        match self.pmf.get(x as usize) {
+            Some(&p) => p,
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the log probability mass function for the distribution
+    /// of the number of records at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(|s(n, x)| / n!)
+    /// ```
+    fn ln_pmf(&self, x: u64) -> f64 {
+        self.pmf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::function::{factorial, harmonic};
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(n: u64; Records);
+
+    /// Counts the records of every permutation of `0..n`
+    fn enumerate(n: usize) -> Vec<f64> {
+        let mut counts = vec![0.0; n + 1];
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut total = 0.0;
+        loop {
+            let mut best = None;
+            let records = perm.iter().filter(|&&x| {
+                let record = best < Some(x);
+                if record {
+                    best = Some(x);
+                }
+                record
+            }).count();
+            counts[records] += 1.0;
+            total += 1.0;
+            // the next permutation in lexicographic order
+            let i = match (1..n).rev().find(|&i| perm[i - 1] < perm[i]) {
+                Some(i) => i,
+                None => break,
+            };
+            let j = (i..n).rev().find(|&j| perm[j] > perm[i - 1]).unwrap();
+            perm.swap(i - 1, j);
+            perm[i..].reverse();
+        }
+        counts.iter().map(|c| c / total).collect()
+    }
+
+    #[test]
+    fn test_create() {
+        try_create(1);
+        try_create(5);
+        try_create(100_000);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0);
+    }
+
+    #[test]
+    fn test_pmf_enumeration() {
+        for n in 1..=7 {
+            let dist = try_create(n as u64);
+            let expected = enumerate(n);
+            for (k, p) in expected.iter().enumerate() {
+                assert_almost_eq!(dist.pmf(k as u64), *p, 1e-15);
+            }
+            assert_eq!(dist.pmf(n as u64 + 1), 0.0);
+        }
+        // |s(10, k)| for k = 1 to 10
+        let stirling = [362880.0, 1026576.0, 1172700.0, 723680.0, 269325.0, 63273.0, 9450.0, 870.0, 45.0, 1.0];
+        let dist = try_create(10);
+        let factorial = factorial::factorial(10);
+        for (k, s) in stirling.iter().enumerate() {
+            assert_almost_eq!(dist.pmf(k as u64 + 1), s / factorial, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_moments() {
+        for &n in &[1u64, 2, 10, 365, 5000] {
+            let dist = try_create(n);
+            let mean: f64 = (0..=n).map(|k| k as f64 * dist.pmf(k)).sum();
+            let second: f64 = (0..=n).map(|k| (k * k) as f64 * dist.pmf(k)).sum();
+            assert_almost_eq!(dist.mean().unwrap(), harmonic::harmonic(n), 1e-13);
+            assert_almost_eq!(mean, harmonic::harmonic(n), 1e-12);
+            assert_almost_eq!(second - mean * mean, dist.variance().unwrap(), 1e-11);
+            let harmonics = harmonic::harmonic(n) - harmonic::gen_harmonic(n, 2.0);
+            assert_almost_eq!(dist.variance().unwrap(), harmonics, 1e-12);
+        }
+        assert_eq!(try_create(1).variance(), Some(0.0));
+        assert_eq!(try_create(1).entropy(), Some(0.0));
+        assert_eq!(try_create(3).mode(), Some(2));
+        test_case(3, 11.0 / 6.0, |x| x.mean().unwrap());
+        test_case(4, 11.0 / 24.0, |x| x.pmf(2));
+        assert_eq!(try_create(1).mode(), Some(1));
+    }
+
+    #[test]
+    fn test_cdf_sf() {
+        let dist = try_create(20);
+        assert_eq!(dist.cdf(0), 0.0);
+        assert_eq!(dist.sf(0), 1.0);
+        let mut cdf = 0.0;
+        for k in 1..=20 {
+            cdf += dist.pmf(k);
+            assert_almost_eq!(dist.cdf(k), cdf, 1e-15);
+            assert_almost_eq!(dist.sf(k), 1.0 - cdf, 1e-15);
+        }
+        assert_eq!(dist.cdf(25), 1.0);
+        assert_eq!(dist.sf(25), 0.0);
+        // one record in twenty is 1 / 20
+        assert_almost_eq!(dist.cdf(1), 0.05, 1e-15);
+        // twenty records is 1 / 20!
+        assert_almost_eq!(dist.sf(19), 1.0 / factorial::factorial(20), 1e-30);
+        assert_eq!((dist.min(), dist.max()), (1, 20));
+    }
+
+    #[test]
+    fn test_sample() {
+        let dist = try_create(50);
+        let mut rng = StdRng::seed_from_u64(300);
+        let samples: Vec<f64> = (0..20_000).map(|_| dist.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let sd = dist.variance().unwrap().sqrt() / (samples.len() as f64).sqrt();
+        assert!((mean - dist.mean().unwrap()).abs() < 4.0 * sd);
+        assert!(samples.iter().all(|&x| (1.0..=50.0).contains(&x)));
+    }
+
+    #[test]
+    fn test_discrete() {
+        test::check_discrete_distribution(&try_create(12), 12);
+        test::check_discrete_distribution(&try_create(1), 1);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(8);
+        test::check_parameters(&n, |d| [d.pmf(1), d.pmf(3), d.pmf(8)]);
+        assert!(n.set_parameter("n", 0.0).is_err());
+        assert!(n.set_parameter("n", 2.5).is_err());
+    }
+}
@@ -0,0 +1,467 @@
+use crate::distribution::{internal, Discrete, DiscreteCDF, Parameterized};
+use crate::function::factorial;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the distribution of the number of
+/// [runs](https://en.wikipedia.org/wiki/Wald%E2%80%93Wolfowitz_runs_test),
+/// the maximal blocks of equal symbols, in a uniformly random arrangement
+/// of `m` symbols of one kind and `n` of another
+///
+/// # Remarks
+///
+/// This is the null distribution of the Wald-Wolfowitz runs test, see
+/// `stats_tests::runs`. With `C = binomial(m + n, m)` equally likely
+/// arrangements,
+///
+/// ```text
+/// P(X = 2k)     = 2 binomial(m - 1, k - 1) binomial(n - 1, k - 1) / C
+/// P(X = 2k + 1) = (binomial(m - 1, k - 1) binomial(n - 1, k)
+///                  + binomial(m - 1, k) binomial(n - 1, k - 1)) / C
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Discrete, Runs};
+/// use statrs::statistics::Distribution;
+///
+/// // aabb, abab, abba, baab, baba, bbaa
+/// let n = Runs::new(2, 2).unwrap();
+/// assert!((n.pmf(2) - 2.0 / 6.0).abs() < 1e-15);
+/// assert!((n.pmf(3) - 2.0 / 6.0).abs() < 1e-15);
+/// assert!((n.pmf(4) - 2.0 / 6.0).abs() < 1e-15);
+/// assert_eq!(n.mean().unwrap(), 3.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Runs {
+    m: u64,
+    n: u64,
+    pmf: Vec<f64>,
+    cdf: Vec<f64>,
+    sf: Vec<f64>,
+}
+
+impl Runs {
+    /// Constructs a new distribution of the number of runs in an
+    /// arrangement of `m` symbols of one kind and `n` of another
+    ///
+    /// # Remarks
+    ///
+    /// The probabilities are tabulated from their closed form, in
+    /// `O(min(m, n))` time and memory. If `m` or `n` is zero there is a
+    /// single run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `m` and `n` are both zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Runs;
+    ///
+    /// let mut result = Runs::new(5, 3);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Runs::new(0, 0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(m: u64, n: u64) -> Result<Runs> {
+        if m == 0 && n == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let pmf = if m == 0 || n == 0 {
+            vec![0.0, 1.0]
+        } else {
+            let max = 2 * m.min(n) + if m == n { 0 } else { 1 };
+            let ln_total = factorial::ln_binomial(m + n, m);
+            // the binomial coefficients of the arrangements of a symbol in
+            // `j` runs, with `binomial(a, -1) = 0`
+            let ln_ways = |a: u64, j: u64| {
+                if j == 0 || j > a {
+                    f64::NEG_INFINITY
+                } else {
+                    factorial::ln_binomial(a - 1, j - 1)
+                }
+            };
+            (0..=max)
+                .map(|r| {
+                    let k = r / 2;
+                    let ways = if r < 2 {
+                        0.0
+                    } else if r & 1 == 0 {
+                        2.0 * (ln_ways(m, k) + ln_ways(n, k) - ln_total).exp()
+                    } else {
+                        (ln_ways(m, k) + ln_ways(n, k + 1) - ln_total).exp()
+                            + (ln_ways(m, k + 1) + ln_ways(n, k) - ln_total).exp()
+                    };
+                    ways.min(1.0)
+                })
+                .collect()
+        };
+        let (cdf, sf) = internal::cumulative_tables(&pmf);
+        Ok(Runs { m, n, pmf, cdf, sf })
+    }
+
+    /// Returns the number of symbols of the first kind
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Runs;
+    ///
+    /// let n = Runs::new(5, 3).unwrap();
+    /// assert_eq!(n.m(), 5);
+    /// ```
+    pub fn m(&self) -> u64 {
+        self.m
+    }
+
+    /// Returns the number of symbols of the second kind
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Runs;
+    ///
+    /// let n = Runs::new(5, 3).unwrap();
+    /// assert_eq!(n.n(), 3);
+    /// ```
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+}
+
+impl Parameterized for Runs {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("m".into(), self.m as f64), ("n".into(), self.n as f64)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Runs> {
+        match *values {
+            [m, n] => Runs::new(
+                internal::integer_parameter(m)?,
+                internal::integer_parameter(n)?,
+            ),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for Runs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Runs({},{})", self.m, self.n)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Runs {
+    /// Simulates a random arrangement symbol by symbol, drawing each with
+    /// probability proportional to the number of its kind left, and counts
+    /// its runs, in `O(m + n)` time
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (mut m, mut n) = (self.m, self.n);
+        let mut runs = 0.0;
+        let mut previous = None;
+        while m + n > 0 {
+            let first = rng.gen_range(0..m + n) < m;
+            if first {
+                m -= 1;
+            } else {
+                n -= 1;
+            }
+            if previous != Some(first) {
+                runs += 1.0;
+            }
+            previous = Some(first);
+        }
+        runs
+    }
+}
+
+impl DiscreteCDF<u64, f64> for Runs {
+    /// Calculates the cumulative distribution function for the
+    /// distribution of the number of runs at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{r <= x} P(X = r)
+    /// ```
+    fn cdf(&self, x: u64) -> f64 {
+        match self.cdf.get(x as usize) {
+            Some(&c) => c,
+            None => 1.0,
+        }
+    }
+
+    /// Calculates the survival function for the distribution of the
+    /// number of runs at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{r > x} P(X = r)
+    /// ```
+    fn sf(&self, x: u64) -> f64 {
+        match self.sf.get(x as usize) {
+            Some(&s) => s,
+            None => 0.0,
+        }
+    }
+}
+
+impl Min<u64> for Runs {
+    /// Returns the minimum value in the domain of the distribution of the
+    /// number of runs
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2, or 1 if m = 0 or n = 0
+    /// ```
+    fn min(&self) -> u64 {
+        if self.m == 0 || self.n == 0 {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+impl Max<u64> for Runs {
+    /// Returns the maximum value in the domain of the distribution of the
+    /// number of runs, reached by alternating the symbols
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 min(m, n) + 1, or 2 min(m, n) if m = n, or 1 if m = 0 or n = 0
+    /// ```
+    fn max(&self) -> u64 {
+        self.pmf.len() as u64 - 1
+    }
+}
+
+impl Distribution<f64> for Runs {
+    /// Returns the mean of the distribution of the number of runs
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 + 2 m n / (m + n)
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        let (m, n) = (self.m as f64, self.n as f64);
+        Some(1.0 + 2.0 * m * n / (m + n))
+    }
+
+    /// Returns the variance of the distribution of the number of runs
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2 m n (2 m n - m - n) / ((m + n)^2 (m + n - 1))
+    /// ```
+    ///
+    /// which is zero if `m + n = 1`
+    fn variance(&self) -> Option<f64> {
+        let (m, n) = (self.m as f64, self.n as f64);
+        if m + n < 2.0 {
+            return Some(0.0);
+        }
+        let total = m + n;
+        Some(2.0 * m * n * (2.0 * m * n - total) / (total * total * (total - 1.0)))
+    }
+
+    /// Returns the entropy of the distribution of the number of runs
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -Σ P(X = r) ln(P(X = r))
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            self.pmf
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .fold(0.0, |acc, p| acc - p * p.ln()),
+        )
+    }
+}
+
+impl Mode<Option<u64>> for Runs {
+    /// Returns the mode of the distribution of the number of runs, the
+    /// smallest if there are several
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// argmax_r P(X = r)
+    /// ```
+    fn mode(&self) -> Option<u64> {
+        let best = self
+            .pmf
+            .iter()
+            .enumerate()
+            .fold(0, |best, (r, &p)| if p > self.pmf[best] { r } else { best });
+        Some(best as u64)
+    }
+}
+
+impl Discrete<u64, f64> for Runs {
+    /// Calculates the probability mass function for the distribution of
+    /// the number of runs at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// P(X = 2k)     = 2 binomial(m - 1, k - 1) binomial(n - 1, k - 1) / C
+    /// P(X = 2k + 1) = (binomial(m - 1, k - 1) binomial(n - 1, k)
+    ///                  + binomial(m - 1, k) binomial(n - 1, k - 1)) / C
+    /// ```
+    ///
+    /// where `C = binomial(m + n, m)`
+    fn pmf(&self, x: u64) -> f64 {
+        match self.pmf.get(x as usize) {
+            Some(&p) => p,
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the log probability mass function for the distribution
+    /// of the number of runs at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(P(X = x))
+    /// ```
+    fn ln_pmf(&self, x: u64) -> f64 {
+        self.pmf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(m: u64, n: u64; Runs);
+
+    /// Counts the runs of every arrangement of `m` ones and `n` zeros
+    fn enumerate(m: u32, n: u32) -> Vec<f64> {
+        let len = m + n;
+        let mut counts = vec![0.0; len as usize + 2];
+        let mut total = 0.0;
+        for bits in 0..1u32 << len {
+            if bits.count_ones() != m {
+                continue;
+            }
+            let changes = (1..len).filter(|&i| ((bits >> i) ^ (bits >> (i - 1))) & 1 == 1).count();
+            counts[changes + 1] += 1.0;
+            total += 1.0;
+        }
+        counts.iter().map(|c| c / total).collect()
+    }
+
+    #[test]
+    fn test_create() {
+        try_create(1, 1);
+        try_create(0, 4);
+        try_create(7, 0);
+        try_create(1000, 2000);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0, 0);
+    }
+
+    #[test]
+    fn test_moments() {
+        test_case(2, 2, 3.0, |x| x.mean().unwrap());
+        test_case(3, 5, 1.0 + 30.0 / 8.0, |x| x.mean().unwrap());
+        test_case(2, 2, 2.0 / 3.0, |x| x.variance().unwrap());
+        test_case(0, 3, 1.0, |x| x.mean().unwrap());
+        assert_eq!(try_create(1, 0).variance(), Some(0.0));
+        // four and five runs are equally likely, 18 / 70
+        assert_eq!(try_create(4, 4).mode(), Some(4));
+    }
+
+    #[test]
+    fn test_pmf_enumeration() {
+        for m in 0..=7 {
+            for n in 0..=7 {
+                if m + n == 0 {
+                    continue;
+                }
+                let dist = try_create(m as u64, n as u64);
+                let expected = enumerate(m, n);
+                let mut total = 0.0;
+                for (r, p) in expected.iter().enumerate() {
+                    assert_almost_eq!(dist.pmf(r as u64), *p, 1e-14);
+                    total += dist.pmf(r as u64);
+                }
+                assert_almost_eq!(total, 1.0, 1e-14);
+                let last = expected.iter().rposition(|&p| p > 0.0).unwrap() as u64;
+                let first = expected.iter().position(|&p| p > 0.0).unwrap() as u64;
+                assert_eq!((dist.min(), dist.max()), (first, last));
+                let mean: f64 = expected.iter().enumerate().map(|(r, p)| r as f64 * p).sum();
+                let variance: f64 = expected.iter().enumerate().map(|(r, p)| (r as f64 - mean).powi(2) * p).sum();
+                assert_almost_eq!(dist.mean().unwrap(), mean, 1e-13);
+                assert_almost_eq!(dist.variance().unwrap(), variance, 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let dist = try_create(1000, 2000);
+        // the log binomial coefficients are accurate to about 1e-12
+        let total: f64 = (0..=dist.max()).map(|r| dist.pmf(r)).sum();
+        assert_almost_eq!(total, 1.0, 1e-10);
+        assert_eq!(dist.max(), 2001);
+        let mean: f64 = (0..=dist.max()).map(|r| r as f64 * dist.pmf(r)).sum();
+        assert_almost_eq!(mean, dist.mean().unwrap(), 1e-8);
+        assert_almost_eq!(dist.cdf(1000) + dist.sf(1000), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_sample() {
+        let dist = try_create(12, 20);
+        let mut rng = StdRng::seed_from_u64(3002);
+        let count = 20_000;
+        let mut frequencies = vec![0.0; dist.max() as usize + 1];
+        for _ in 0..count {
+            frequencies[dist.sample(&mut rng) as usize] += 1.0 / count as f64;
+        }
+        for (r, f) in frequencies.iter().enumerate() {
+            let p = dist.pmf(r as u64);
+            assert!((f - p).abs() < 4.0 * (p * (1.0 - p) / count as f64).sqrt() + 1e-12);
+        }
+        assert_eq!(try_create(0, 5).sample(&mut rng), 1.0);
+    }
+
+    #[test]
+    fn test_discrete() {
+        test::check_discrete_distribution(&try_create(5, 7), 11);
+        test::check_discrete_distribution(&try_create(4, 4), 8);
+        test::check_discrete_distribution(&try_create(3, 0), 1);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(6, 9);
+        test::check_parameters(&n, |d| [d.pmf(2), d.pmf(7), d.pmf(13)]);
+        assert!(n.with_parameters(&[0.0, 0.0]).is_err());
+        assert!(n.set_parameter("m", 1.5).is_err());
+    }
+}
@@ -0,0 +1,469 @@
+use crate::distribution::{internal, normal, ziggurat, Continuous, ContinuousCDF, Parameterized};
+use crate::function::integrate::composite_gauss_legendre;
+use crate::function::root;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Bound beyond which the standard normal density is neglected by the
+/// quadratures over the position of the smallest observation
+const X_BOUND: f64 = 9.0;
+
+/// Number of Gauss-Legendre panels partitioning `[-X_BOUND, X_BOUND]`
+const X_PANELS: usize = 18;
+
+/// Maximum number of doublings when bracketing a quantile, enough to reach
+/// the largest double from `1`
+const MAX_EXPANSIONS: usize = 1024;
+
+/// Implements the distribution of the
+/// [range](https://en.wikipedia.org/wiki/Range_(statistics)) `max - min` of
+/// `n` independent standard normal observations
+///
+/// The range of normal samples underlies range-based control charts, and
+/// divided by an independent estimate of the standard deviation it gives the
+/// [studentized range](crate::distribution::StudentizedRange).
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, SampleRange};
+/// use statrs::statistics::Distribution;
+///
+/// // the range of two observations is |Z1 - Z2|, a half-normal of scale √2
+/// let n = SampleRange::new(2).unwrap();
+/// assert!((n.mean().unwrap() - 2.0 / std::f64::consts::PI.sqrt()).abs() < 1e-10);
+/// assert!((n.cdf(2.0) - 0.8427007929497149).abs() < 1e-10);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SampleRange {
+    n: u64,
+}
+
+impl SampleRange {
+    /// Constructs the distribution of the range of `n` standard normal
+    /// observations
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `n < 2`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::SampleRange;
+    ///
+    /// let mut result = SampleRange::new(5);
+    /// assert!(result.is_ok());
+    ///
+    /// result = SampleRange::new(1);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(n: u64) -> Result<SampleRange> {
+        if n < 2 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(SampleRange { n })
+        }
+    }
+
+    /// Returns the number of observations whose range is taken
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::SampleRange;
+    ///
+    /// let n = SampleRange::new(5).unwrap();
+    /// assert_eq!(n.n(), 5);
+    /// ```
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+}
+
+/// Returns `Φ(x + w) - Φ(x)`, subtracting lower tail probabilities left of
+/// `-w / 2` and upper tail probabilities right of it to avoid cancellation
+fn normal_mass(x: f64, w: f64) -> f64 {
+    if x + 0.5 * w < 0.0 {
+        normal::cdf_unchecked(x + w, 0.0, 1.0) - normal::cdf_unchecked(x, 0.0, 1.0)
+    } else {
+        normal::sf_unchecked(x, 0.0, 1.0) - normal::sf_unchecked(x + w, 0.0, 1.0)
+    }
+}
+
+/// Returns the cdf of the range of `n` standard normal observations at `w`,
+/// integrating over the smallest observation `x`
+///
+/// ```text
+/// n ∫ φ(x) (Φ(x + w) - Φ(x))^(n - 1) dx
+/// ```
+pub(crate) fn range_cdf(w: f64, n: u64) -> f64 {
+    if w.is_nan() {
+        return f64::NAN;
+    }
+    if w <= 0.0 {
+        return 0.0;
+    }
+    if w == f64::INFINITY {
+        return 1.0;
+    }
+    let m = (n - 1) as f64;
+    let integrand = |x: f64| normal::pdf_unchecked(x, 0.0, 1.0) * normal_mass(x, w).powf(m);
+    (n as f64 * composite_gauss_legendre(&integrand, -X_BOUND, X_BOUND, X_PANELS)).min(1.0)
+}
+
+/// Returns the survival function of the range of `n` standard normal
+/// observations at `w`, integrating the difference between the cdf of the
+/// range at infinity and at `w` as
+///
+/// ```text
+/// n ∫ φ(x) (Q(x)^(n - 1) - (Q(x) - Q(x + w))^(n - 1)) dx
+/// ```
+///
+/// where `Q = 1 - Φ`, with the difference of powers evaluated through
+/// `ln_1p` and `exp_m1` so that upper tail probabilities keep their
+/// relative accuracy
+pub(crate) fn range_sf(w: f64, n: u64) -> f64 {
+    if w.is_nan() {
+        return f64::NAN;
+    }
+    if w <= 0.0 {
+        return 1.0;
+    }
+    if w == f64::INFINITY {
+        return 0.0;
+    }
+    let m = (n - 1) as f64;
+    let integrand = |x: f64| {
+        let q = normal::sf_unchecked(x, 0.0, 1.0);
+        let tail = normal::sf_unchecked(x + w, 0.0, 1.0);
+        if q == 0.0 {
+            0.0
+        } else {
+            -normal::pdf_unchecked(x, 0.0, 1.0) * q.powf(m) * (m * (-tail / q).ln_1p()).exp_m1()
+        }
+    };
+    (n as f64 * composite_gauss_legendre(&integrand, -X_BOUND, X_BOUND, X_PANELS)).min(1.0)
+}
+
+/// Returns the density of the range of `n` standard normal observations at
+/// `w`
+///
+/// ```text
+/// n (n - 1) ∫ φ(x) φ(x + w) (Φ(x + w) - Φ(x))^(n - 2) dx
+/// ```
+pub(crate) fn range_pdf(w: f64, n: u64) -> f64 {
+    if w.is_nan() {
+        return f64::NAN;
+    }
+    if w < 0.0 || w == f64::INFINITY {
+        return 0.0;
+    }
+    let m = (n - 2) as f64;
+    let integrand = |x: f64| {
+        normal::pdf_unchecked(x, 0.0, 1.0)
+            * normal::pdf_unchecked(x + w, 0.0, 1.0)
+            * normal_mass(x, w).powf(m)
+    };
+    let scale = n as f64 * (n - 1) as f64;
+    scale * composite_gauss_legendre(&integrand, -X_BOUND, X_BOUND, X_PANELS)
+}
+
+/// Returns the value of a continuous distribution function `f` on
+/// `[0, ∞)` that is increasing or decreasing in `w` and equals `target` by
+/// bracketing the root from `guess` and refining it with Brent's method.
+/// `increasing` tells whether `f` is a cdf or a survival function.
+pub(crate) fn invert_on_positive<F: Fn(f64) -> f64>(
+    f: F,
+    target: f64,
+    increasing: bool,
+    guess: f64,
+) -> f64 {
+    let g = |w: f64| f(w) - target;
+    let (lo, hi) = if (g(guess) < 0.0) == increasing {
+        match root::bracket_root(g, guess, 2.0, MAX_EXPANSIONS) {
+            Ok(bracket) => bracket,
+            // the target is not reached at any finite value
+            Err(_) => return f64::INFINITY,
+        }
+    } else {
+        (0.0, guess)
+    };
+    root::brent(g, lo, hi, 1e-14 * hi).unwrap_or(f64::NAN)
+}
+
+impl Parameterized for SampleRange {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("n".into(), self.n as f64)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<SampleRange> {
+        match *values {
+            [n] => SampleRange::new(internal::integer_parameter(n)?),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for SampleRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Range({})", self.n)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for SampleRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for _ in 0..self.n {
+            let z = ziggurat::sample_std_normal(rng);
+            min = min.min(z);
+            max = max.max(z);
+        }
+        max - min
+    }
+}
+
+impl ContinuousCDF<f64, f64> for SampleRange {
+    /// Calculates the cumulative distribution function for the range of
+    /// `n` standard normal observations at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n ∫ φ(t) (Φ(t + x) - Φ(t))^(n - 1) dt
+    /// ```
+    ///
+    /// where `φ` and `Φ` are the standard normal density and cdf, the
+    /// integral being taken by composite Gauss-Legendre quadrature
+    fn cdf(&self, x: f64) -> f64 {
+        range_cdf(x, self.n)
+    }
+
+    /// Calculates the survival function for the range of `n` standard
+    /// normal observations at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n ∫ φ(t) (Q(t)^(n - 1) - (Q(t) - Q(t + x))^(n - 1)) dt
+    /// ```
+    ///
+    /// where `φ` is the standard normal density and `Q = 1 - Φ` its
+    /// survival function, which keeps the relative accuracy in the upper
+    /// tail
+    fn sf(&self, x: f64) -> f64 {
+        range_sf(x, self.n)
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// range of `n` standard normal observations at `p` by root-finding
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            0.0
+        } else if p == 1.0 {
+            f64::INFINITY
+        } else if p <= 0.5 {
+            invert_on_positive(|w| range_cdf(w, self.n), p, true, 1.0)
+        } else {
+            invert_on_positive(|w| range_sf(w, self.n), 1.0 - p, false, 1.0)
+        }
+    }
+}
+
+impl Min<f64> for SampleRange {
+    /// Returns the minimum value in the domain of the range of normal
+    /// observations representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for SampleRange {
+    /// Returns the maximum value in the domain of the range of normal
+    /// observations representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for SampleRange {
+    /// Returns the mean of the range of `n` standard normal observations,
+    /// twice the mean of their maximum
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2n ∫ t φ(t) Φ(t)^(n - 1) dt
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        let m = (self.n - 1) as f64;
+        let integrand = |t: f64| {
+            t * normal::pdf_unchecked(t, 0.0, 1.0) * normal::cdf_unchecked(t, 0.0, 1.0).powf(m)
+        };
+        let max = composite_gauss_legendre(&integrand, -X_BOUND, X_BOUND, X_PANELS);
+        Some(2.0 * self.n as f64 * max)
+    }
+}
+
+impl Median<f64> for SampleRange {
+    /// Returns the median of the range of `n` standard normal observations
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(0.5)
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for SampleRange {
+    /// Calculates the probability density function for the range of `n`
+    /// standard normal observations at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// n (n - 1) ∫ φ(t) φ(t + x) (Φ(t + x) - Φ(t))^(n - 2) dt
+    /// ```
+    ///
+    /// where `φ` and `Φ` are the standard normal density and cdf
+    fn pdf(&self, x: f64) -> f64 {
+        range_pdf(x, self.n)
+    }
+
+    /// Calculates the log probability density function for the range of
+    /// `n` standard normal observations at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(n: u64; SampleRange);
+
+    #[test]
+    fn test_create() {
+        try_create(2);
+        try_create(100);
+        bad_create_case(0);
+        bad_create_case(1);
+    }
+
+    #[test]
+    fn test_two_observations() {
+        // the range of two observations is √2 |Z|
+        let n = try_create(2);
+        for &w in &[0.1, 0.5, 1.0, 2.5, 4.0, 7.0] {
+            let z = w / f64::consts::SQRT_2;
+            let sf = 2.0 * normal::sf_unchecked(z, 0.0, 1.0);
+            assert_almost_eq!(n.cdf(w), 1.0 - sf, 1e-10);
+            assert!((n.sf(w) - sf).abs() < 1e-9 * sf);
+            assert_almost_eq!(n.pdf(w), f64::consts::SQRT_2 * normal::pdf_unchecked(z, 0.0, 1.0), 1e-14);
+        }
+        assert_eq!(n.pdf(-1.0), 0.0);
+        assert_eq!(n.ln_pdf(-1.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_cdf() {
+        // reference values from mpmath
+        let cdf = |x: f64| move |n: SampleRange| n.cdf(x);
+        let sf = |x: f64| move |n: SampleRange| n.sf(x);
+        test_case_special(5, 0.7891234950364622, 1e-10, cdf(3.0));
+        test_case_special(5, 0.2108765049635378, 1e-10, sf(3.0));
+        test_case_special(5, 7.362389863057474e-6, 1e-15, sf(7.0));
+        test_case_special(20, 0.8533913941553594, 1e-10, cdf(4.5));
+        test_case(5, 0.0, cdf(0.0));
+        test_case(5, 1.0, cdf(f64::INFINITY));
+        test_case(5, 0.0, sf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_mean() {
+        // expected ranges of normal samples, the d2 constants of control
+        // charts
+        let mean = |n: SampleRange| n.mean().unwrap();
+        test_case_special(3, 3.0 / f64::consts::PI.sqrt(), 1e-10, mean);
+        test_case_special(5, 2.325929, 1e-6, mean);
+        test_case_special(10, 3.077505, 1e-6, mean);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &n in &[2, 5, 30] {
+            let d = try_create(n);
+            for &p in &[1e-6, 0.05, 0.5, 0.95, 1.0 - 1e-9] {
+                let w = d.inverse_cdf(p);
+                if p <= 0.5 {
+                    assert_almost_eq!(d.cdf(w), p, 1e-11 * p);
+                } else {
+                    assert_almost_eq!(d.sf(w), 1.0 - p, 1e-11 * (1.0 - p));
+                }
+            }
+        }
+        assert_eq!(try_create(5).inverse_cdf(0.0), 0.0);
+        assert_eq!(try_create(5).inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = try_create(5);
+        let mut rng = StdRng::seed_from_u64(2912);
+        let draws: Vec<f64> = (0..20000).map(|_| n.sample(&mut rng)).collect();
+        let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+        assert!((mean - n.mean().unwrap()).abs() < 0.03);
+        let below = draws.iter().filter(|&&w| w <= 2.0).count() as f64 / draws.len() as f64;
+        assert!((below - n.cdf(2.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_pdf_is_derivative_of_cdf() {
+        for &n in &[3, 12] {
+            let d = try_create(n);
+            for &w in &[0.3, 1.5, 3.0, 5.5] {
+                let h = 1e-4;
+                let slope = (d.cdf(w + h) - d.cdf(w - h)) / (2.0 * h);
+                assert_almost_eq!(d.pdf(w), slope, 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(5);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert!(n.with_parameters(&[2.5]).is_err());
+    }
+}
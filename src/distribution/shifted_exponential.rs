@@ -0,0 +1,513 @@
+use crate::distribution::{ziggurat, Continuous, ContinuousCDF, LMoments, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the two-parameter
+/// [exponential](https://en.wikipedia.org/wiki/Exponential_distribution)
+/// distribution shifted to start at a location, as for lifetimes that
+/// cannot be shorter than a threshold
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ShiftedExponential};
+/// use statrs::statistics::Distribution;
+///
+/// let n = ShiftedExponential::new(2.0, 1.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 1.5);
+/// assert_eq!(n.pdf(1.0), 2.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ShiftedExponential {
+    rate: f64,
+    location: f64,
+}
+
+impl ShiftedExponential {
+    /// Constructs a new shifted exponential distribution with a rate (λ)
+    /// of `rate` and a location (μ) of `location`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `rate` or `location` is not finite or if
+    /// `rate <= 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ShiftedExponential;
+    ///
+    /// let mut result = ShiftedExponential::new(2.0, 1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = ShiftedExponential::new(-2.0, 1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(rate: f64, location: f64) -> Result<ShiftedExponential> {
+        if !rate.is_finite() || !location.is_finite() || rate <= 0.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(ShiftedExponential { rate, location })
+        }
+    }
+
+    /// Estimates a shifted exponential distribution from the sample `data`
+    /// by maximum likelihood
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ = min(x_i)
+    /// λ = 1 / (mean(x_i) - μ)
+    /// ```
+    ///
+    /// # Remarks
+    ///
+    /// The sample minimum overestimates the location by `1 / (n λ)` on
+    /// average, which is negligible for large samples
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ShiftedExponential;
+    ///
+    /// let lifetimes = [3.2, 5.0, 3.6, 4.1, 7.9, 3.3];
+    /// let n = ShiftedExponential::fit_mle(&lifetimes).unwrap();
+    /// assert_eq!(n.location(), 3.2);
+    /// assert!((n.rate() - 6.0 / 7.9).abs() < 1e-14);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<ShiftedExponential> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::SpecialCase("data must be finite"));
+        }
+        let location = data.iter().fold(f64::INFINITY, |acc, &x| acc.min(x));
+        let excess = data.iter().map(|&x| x - location).sum::<f64>() / data.len() as f64;
+        if excess <= 0.0 {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        ShiftedExponential::new(1.0 / excess, location)
+    }
+
+    /// Returns the rate of the shifted exponential distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ShiftedExponential;
+    ///
+    /// let n = ShiftedExponential::new(2.0, 1.0).unwrap();
+    /// assert_eq!(n.rate(), 2.0);
+    /// ```
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the location of the shifted exponential distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::ShiftedExponential;
+    ///
+    /// let n = ShiftedExponential::new(2.0, 1.0).unwrap();
+    /// assert_eq!(n.location(), 1.0);
+    /// ```
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+}
+
+impl Parameterized for ShiftedExponential {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("rate".into(), self.rate),
+            ("location".into(), self.location),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<ShiftedExponential> {
+        match *values {
+            [rate, location] => ShiftedExponential::new(rate, location),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for ShiftedExponential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ShiftedExp({}, {})", self.rate, self.location)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for ShiftedExponential {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        self.location + ziggurat::sample_exp_1(r) / self.rate
+    }
+}
+
+impl ContinuousCDF<f64, f64> for ShiftedExponential {
+    /// Calculates the cumulative distribution function for the shifted
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - e^(-λ (x - μ))
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.location {
+            0.0
+        } else {
+            -(-self.rate * (x - self.location)).exp_m1()
+        }
+    }
+
+    /// Calculates the survival function for the shifted exponential
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(-λ (x - μ))
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn sf(&self, x: f64) -> f64 {
+        if x <= self.location {
+            1.0
+        } else {
+            (-self.rate * (x - self.location)).exp()
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// shifted exponential distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ - ln(1 - p) / λ
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        self.location - (-p).ln_1p() / self.rate
+    }
+}
+
+impl Min<f64> for ShiftedExponential {
+    /// Returns the minimum value in the domain of the shifted exponential
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn min(&self) -> f64 {
+        self.location
+    }
+}
+
+impl Max<f64> for ShiftedExponential {
+    /// Returns the maximum value in the domain of the shifted exponential
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for ShiftedExponential {
+    /// Returns the mean of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + 1 / λ
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn mean(&self) -> Option<f64> {
+        Some(self.location + 1.0 / self.rate)
+    }
+
+    /// Returns the variance of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 / λ^2
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn variance(&self) -> Option<f64> {
+        Some(1.0 / (self.rate * self.rate))
+    }
+
+    /// Returns the entropy of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - ln(λ)
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn entropy(&self) -> Option<f64> {
+        Some(1.0 - self.rate.ln())
+    }
+
+    /// Returns the skewness of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 2
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        Some(2.0)
+    }
+}
+
+impl Median<f64> for ShiftedExponential {
+    /// Returns the median of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ + ln(2) / λ
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn median(&self) -> f64 {
+        self.location + f64::consts::LN_2 / self.rate
+    }
+}
+
+impl Mode<Option<f64>> for ShiftedExponential {
+    /// Returns the mode of the shifted exponential distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// μ
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn mode(&self) -> Option<f64> {
+        Some(self.location)
+    }
+}
+
+impl Continuous<f64, f64> for ShiftedExponential {
+    /// Calculates the probability density function for the shifted
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ e^(-λ (x - μ))
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.location {
+            0.0
+        } else {
+            self.rate * (-self.rate * (x - self.location)).exp()
+        }
+    }
+
+    /// Calculates the log probability density function for the shifted
+    /// exponential distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(λ) - λ (x - μ)
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < self.location {
+            f64::NEG_INFINITY
+        } else {
+            self.rate.ln() - self.rate * (x - self.location)
+        }
+    }
+}
+
+impl LMoments for ShiftedExponential {
+    /// Returns the first `order` L-moments of the shifted exponential
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [μ + 1 / λ, 1 / (2λ), τ3, τ4, ...]  with  τr = 2 / (r (r - 1))
+    /// ```
+    ///
+    /// where `λ` is the rate and `μ` the location
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        (1..=order)
+            .map(|r| match r {
+                1 => self.location + 1.0 / self.rate,
+                2 => 0.5 / self.rate,
+                _ => 2.0 / (r * (r - 1)) as f64,
+            })
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::Exp;
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(rate: f64, location: f64; ShiftedExponential);
+
+    #[test]
+    fn test_create() {
+        try_create(2.0, 1.0);
+        try_create(0.1, -50.0);
+        try_create(10.0, 0.0);
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case(0.0, 1.0);
+        bad_create_case(-2.0, 1.0);
+        bad_create_case(f64::NAN, 1.0);
+        bad_create_case(f64::INFINITY, 1.0);
+        bad_create_case(2.0, f64::NAN);
+        bad_create_case(2.0, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_shifted_exp() {
+        // the exponential distribution moved by the location
+        let exp = Exp::new(2.0).unwrap();
+        let n = try_create(2.0, -1.0);
+        for &x in &[0.0, 0.01, 0.5, 1.0, 3.0, 20.0] {
+            assert_almost_eq!(n.cdf(x - 1.0), exp.cdf(x), 1e-15);
+            assert_almost_eq!(n.sf(x - 1.0), exp.sf(x), 1e-15);
+            assert_almost_eq!(n.pdf(x - 1.0), exp.pdf(x), 1e-15);
+            assert_almost_eq!(n.ln_pdf(x - 1.0), exp.ln_pdf(x), 1e-15);
+        }
+        assert_eq!(n.mean(), Some(-0.5));
+        assert_eq!(n.variance(), exp.variance());
+        assert_eq!(n.entropy(), exp.entropy());
+        assert_eq!(n.skewness(), Some(2.0));
+        assert_eq!(n.median(), exp.median() - 1.0);
+        assert_eq!(n.mode(), Some(-1.0));
+        assert_eq!((n.min(), n.max()), (-1.0, f64::INFINITY));
+        let (shifted, closed) = (n.l_moments(5), exp.l_moments(5));
+        assert_eq!(shifted[0], closed[0] - 1.0);
+        assert_eq!(shifted[1..], closed[1..]);
+    }
+
+    #[test]
+    fn test_outside_support() {
+        test_case(2.0, 1.0, 0.0, |n| n.cdf(0.5));
+        test_case(2.0, 1.0, 1.0, |n| n.sf(0.5));
+        test_case(2.0, 1.0, 0.0, |n| n.pdf(0.5));
+        test_case(2.0, 1.0, f64::NEG_INFINITY, |n| n.ln_pdf(0.5));
+        test_case(2.0, 1.0, 0.0, |n| n.cdf(1.0));
+        test_case(2.0, 1.0, 2.0, |n| n.pdf(1.0));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(
+            &try_create(2.0, 1.0),
+            [1.0, 1.0, 1.3465735902799727, 19.368400284838551],
+        );
+        let n = try_create(0.5, -3.0);
+        assert_eq!(n.inverse_cdf(0.0), -3.0);
+        assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        for &p in &[0.01, 0.3, 0.9, 0.999] {
+            assert_almost_eq!(n.cdf(n.inverse_cdf(p)), p, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create(0.5, -3.0), -3.0, 50.0);
+        test::check_continuous_distribution(&try_create(2.5, 10.0), 10.0, 30.0);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        let n = try_create(0.5, 2.0);
+        let (closed, numeric) = (n.l_moments(5), l_moments(&n, 5, &[]));
+        for r in 0..5 {
+            assert_almost_eq!(closed[r], numeric[r], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let mut rng = StdRng::seed_from_u64(2824);
+        for &(rate, location) in &[(2.0, 1.0), (0.05, -300.0)] {
+            let n = try_create(rate, location);
+            let data: Vec<f64> = (0..20_000).map(|_| n.sample(&mut rng)).collect();
+            let fit = ShiftedExponential::fit_mle(&data).unwrap();
+            assert_almost_eq!(fit.rate(), rate, 0.03 * rate);
+            // the minimum is above the location by about 1 / (n λ)
+            assert!(fit.location() >= location);
+            assert!(fit.location() - location < 10.0 / (20_000.0 * rate));
+        }
+        let lifetimes = [3.2, 5.0, 3.6, 4.1, 7.9, 3.3];
+        let fit = ShiftedExponential::fit_mle(&lifetimes).unwrap();
+        assert_eq!(fit.location(), 3.2);
+        assert_almost_eq!(fit.rate(), 6.0 / 7.9, 1e-14);
+        assert!(ShiftedExponential::fit_mle(&[1.0]).is_err());
+        assert!(ShiftedExponential::fit_mle(&[2.0, 2.0, 2.0]).is_err());
+        assert!(ShiftedExponential::fit_mle(&[2.0, f64::NAN, 3.0]).is_err());
+        assert!(ShiftedExponential::fit_mle(&[2.0, f64::INFINITY, 3.0]).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(2.0, 1.0);
+        test::check_parameters(&n, |d| [d.pdf(1.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("rate", 0.0), ShiftedExponential::new(0.0, 1.0));
+    }
+}
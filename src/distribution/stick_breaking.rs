@@ -0,0 +1,364 @@
+use crate::distribution::{Beta, Parameterized};
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the
+/// [stick-breaking process](https://en.wikipedia.org/wiki/Dirichlet_process#The_stick-breaking_process)
+/// generating the GEM(α) weights of a Dirichlet process with concentration
+/// `α`
+///
+/// A stick of unit length is broken repeatedly: the `i`-th weight is the
+/// fraction `V_i ~ Beta(1, α)` of what remains after the first `i - 1`
+/// breaks. Smaller `α` puts most of the mass on the first few weights.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::StickBreaking;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let process = StickBreaking::new(2.0).unwrap();
+/// let weights: Vec<f64> = process.weights(&mut rng, 1e-6).collect();
+/// let total: f64 = weights.iter().sum();
+/// assert!(total >= 1.0 - 1e-6 && total <= 1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StickBreaking {
+    alpha: f64,
+    fraction: Beta,
+}
+
+impl StickBreaking {
+    /// Constructs a new stick-breaking process with concentration `alpha`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` is not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::StickBreaking;
+    ///
+    /// assert!(StickBreaking::new(1.0).is_ok());
+    /// assert!(StickBreaking::new(0.0).is_err());
+    /// ```
+    pub fn new(alpha: f64) -> Result<StickBreaking> {
+        if !alpha.is_finite() || alpha <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("alpha"));
+        }
+        Ok(StickBreaking {
+            alpha,
+            fraction: Beta::new(1.0, alpha)?,
+        })
+    }
+
+    /// Returns the concentration of the stick-breaking process
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns an iterator drawing the weights lazily from `rng`, which
+    /// stops once the mass left on the stick is at most `threshold`
+    ///
+    /// # Remarks
+    ///
+    /// The weights yielded sum to between `1 - threshold` and `1`. The
+    /// number of weights needed grows like `α ln(1 / threshold)`. A
+    /// `threshold` of `0` or less never stops and the iterator is infinite.
+    pub fn weights<'a, R: Rng + ?Sized>(
+        &self,
+        rng: &'a mut R,
+        threshold: f64,
+    ) -> StickBreakingWeights<'a, R> {
+        StickBreakingWeights {
+            fraction: self.fraction,
+            remaining: 1.0,
+            threshold,
+            rng,
+        }
+    }
+
+    /// Draws the first `k` weights, with the last one taking all the mass
+    /// left on the stick so that they sum to `1`
+    fn truncated<R: Rng + ?Sized>(&self, k: usize, rng: &mut R) -> Vec<f64> {
+        let mut weights: Vec<f64> = self
+            .weights(rng, f64::NEG_INFINITY)
+            .take(k.saturating_sub(1))
+            .collect();
+        let used: f64 = weights.iter().sum();
+        weights.push((1.0 - used).max(0.0));
+        weights
+    }
+}
+
+/// The iterator returned by [`StickBreaking::weights`]
+impl Parameterized for StickBreaking {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("alpha".into(), self.alpha)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<StickBreaking> {
+        match *values {
+            [alpha] => StickBreaking::new(alpha),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StickBreakingWeights<'a, R: Rng + ?Sized> {
+    fraction: Beta,
+    remaining: f64,
+    threshold: f64,
+    rng: &'a mut R,
+}
+
+impl<'a, R: Rng + ?Sized> StickBreakingWeights<'a, R> {
+    /// Returns the mass left on the stick after the weights drawn so far
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+}
+
+impl<'a, R: Rng + ?Sized> Iterator for StickBreakingWeights<'a, R> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        use rand::distributions::Distribution;
+        if self.remaining <= self.threshold {
+            return None;
+        }
+        let v = self.fraction.sample(self.rng);
+        let weight = self.remaining * v;
+        self.remaining -= weight;
+        Some(weight)
+    }
+}
+
+/// Returns the first `k` weights of the GEM(`alpha`) stick-breaking
+/// process, the last one taking all the remaining mass so that the weights
+/// sum to `1`. Used by `Dirichlet::stick_breaking_sample`.
+pub(crate) fn stick_breaking_sample<R: Rng + ?Sized>(
+    alpha: f64,
+    k: usize,
+    rng: &mut R,
+) -> Result<Vec<f64>> {
+    if k == 0 {
+        return Err(StatsError::ArgMustBePositive("k"));
+    }
+    Ok(StickBreaking::new(alpha)?.truncated(k, rng))
+}
+
+/// A draw of `n` observations from a Dirichlet process, as returned by
+/// [`dirichlet_process_sample`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct DirichletProcessSample {
+    /// The observations, `values[i] == atoms[clusters[i]]`
+    pub values: Vec<f64>,
+    /// The cluster of each observation, numbered in order of appearance
+    pub clusters: Vec<usize>,
+    /// The value shared by the observations of each cluster, drawn from
+    /// the base distribution
+    pub atoms: Vec<f64>,
+}
+
+impl DirichletProcessSample {
+    /// Returns the number of observations in each cluster
+    pub fn cluster_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.atoms.len()];
+        for &c in &self.clusters {
+            sizes[c] += 1;
+        }
+        sizes
+    }
+}
+
+/// Draws `n` observations from a Dirichlet process with concentration
+/// `alpha` and base distribution `base` by the
+/// [Chinese restaurant process](https://en.wikipedia.org/wiki/Chinese_restaurant_process)
+///
+/// The `i`-th observation (counting from zero) joins an existing cluster
+/// of size `n_k` with probability `n_k / (i + α)` and starts a new cluster,
+/// whose value is drawn from `base`, with probability `α / (i + α)`.
+///
+/// # Remarks
+///
+/// The expected number of clusters is `Σ_{i < n} α / (α + i)`, about
+/// `α ln(1 + n / α)`. The joint distribution of the cluster assignments is
+/// exchangeable. Each draw costs time proportional to the number of
+/// clusters.
+///
+/// # Errors
+///
+/// Returns an error if `alpha` is not positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::{dirichlet_process_sample, Normal};
+///
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let base = Normal::new(0.0, 10.0).unwrap();
+/// let draw = dirichlet_process_sample(1.0, &base, 100, &mut rng).unwrap();
+/// assert_eq!(draw.values.len(), 100);
+/// assert_eq!(draw.cluster_sizes().iter().sum::<usize>(), 100);
+/// assert_eq!(draw.values[0], draw.atoms[0]);
+/// ```
+pub fn dirichlet_process_sample<D, R>(
+    alpha: f64,
+    base: &D,
+    n: usize,
+    rng: &mut R,
+) -> Result<DirichletProcessSample>
+where
+    D: ::rand::distributions::Distribution<f64> + ?Sized,
+    R: Rng + ?Sized,
+{
+    if !alpha.is_finite() || alpha <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("alpha"));
+    }
+    let mut sizes: Vec<usize> = Vec::new();
+    let mut atoms = Vec::new();
+    let mut clusters = Vec::with_capacity(n);
+    let mut values = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut u = rng.gen::<f64>() * (i as f64 + alpha);
+        let mut chosen = None;
+        for (k, &size) in sizes.iter().enumerate() {
+            if u < size as f64 {
+                chosen = Some(k);
+                break;
+            }
+            u -= size as f64;
+        }
+        let k = match chosen {
+            Some(k) => k,
+            None => {
+                atoms.push(base.sample(rng));
+                sizes.push(0);
+                atoms.len() - 1
+            }
+        };
+        sizes[k] += 1;
+        clusters.push(k);
+        values.push(atoms[k]);
+    }
+    Ok(DirichletProcessSample {
+        values,
+        clusters,
+        atoms,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Dirichlet, Normal};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_create() {
+        assert_eq!(StickBreaking::new(0.5).unwrap().alpha(), 0.5);
+        assert!(StickBreaking::new(-1.0).is_err());
+        assert!(StickBreaking::new(f64::NAN).is_err());
+        assert!(StickBreaking::new(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_weights_threshold() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for &alpha in &[0.1, 1.0, 5.0, 50.0] {
+            let process = StickBreaking::new(alpha).unwrap();
+            for &threshold in &[1e-3, 1e-9] {
+                let mut weights = process.weights(&mut rng, threshold);
+                let total: f64 = weights.by_ref().sum();
+                assert!(weights.remaining() <= threshold);
+                assert!(total >= 1.0 - threshold && total <= 1.0 + 1e-12);
+                assert_almost_eq!(total + weights.remaining(), 1.0, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stick_breaking_sample() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let alpha = 3.0;
+        let reps = 20_000;
+        let mut first = 0.0;
+        for _ in 0..reps {
+            let w = Dirichlet::stick_breaking_sample(alpha, 10, &mut rng).unwrap();
+            assert_eq!(w.len(), 10);
+            assert!(w.iter().all(|&x| x >= 0.0));
+            assert_almost_eq!(w.iter().sum::<f64>(), 1.0, 1e-14);
+            first += w[0];
+        }
+        // E[V_1] = 1 / (1 + α) with standard deviation about 0.19
+        assert!((first / reps as f64 - 0.25).abs() < 0.005);
+        assert_eq!(Dirichlet::stick_breaking_sample(alpha, 1, &mut rng).unwrap(), vec![1.0]);
+        assert!(Dirichlet::stick_breaking_sample(alpha, 0, &mut rng).is_err());
+        assert!(Dirichlet::stick_breaking_sample(0.0, 3, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_expected_number_of_clusters() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let base = Normal::new(0.0, 1.0).unwrap();
+        let (alpha, n, reps) = (2.0, 1000, 400);
+        let mut total = 0;
+        for _ in 0..reps {
+            let draw = dirichlet_process_sample(alpha, &base, n, &mut rng).unwrap();
+            assert_eq!(draw.clusters.len(), n);
+            for (v, &c) in draw.values.iter().zip(&draw.clusters) {
+                assert_eq!(*v, draw.atoms[c]);
+            }
+            total += draw.atoms.len();
+        }
+        let mean = total as f64 / reps as f64;
+        let expected: f64 = (0..n).map(|i| alpha / (alpha + i as f64)).sum();
+        // the variance of the count is below its mean, about 11.4
+        assert!((mean - expected).abs() < 4.0 * (expected / reps as f64).sqrt());
+        assert!((mean - alpha * (n as f64).ln()).abs() < 0.2 * mean);
+    }
+
+    #[test]
+    fn test_exchangeability() {
+        // any two observations share a cluster with probability 1 / (1 + α)
+        let mut rng = StdRng::seed_from_u64(13);
+        let base = Normal::new(0.0, 1.0).unwrap();
+        let (alpha, reps) = (1.5, 20_000);
+        let (mut early, mut late) = (0, 0);
+        for _ in 0..reps {
+            let c = dirichlet_process_sample(alpha, &base, 8, &mut rng).unwrap().clusters;
+            early += (c[0] == c[1]) as usize;
+            late += (c[5] == c[7]) as usize;
+        }
+        let p = 1.0 / (1.0 + alpha);
+        let sd = (p * (1.0 - p) / reps as f64).sqrt();
+        assert!((early as f64 / reps as f64 - p).abs() < 4.0 * sd);
+        assert!((late as f64 / reps as f64 - p).abs() < 4.0 * sd);
+    }
+
+    #[test]
+    fn test_dirichlet_process_edge_cases() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let base = Normal::new(0.0, 1.0).unwrap();
+        let draw = dirichlet_process_sample(1.0, &base, 0, &mut rng).unwrap();
+        assert!(draw.values.is_empty() && draw.atoms.is_empty());
+        assert!(dirichlet_process_sample(0.0, &base, 5, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = StickBreaking::new(2.0).unwrap();
+        crate::distribution::internal::test::check_parameters(&n, |d| d.alpha());
+        assert_eq!(n.set_parameter("alpha", 0.0), StickBreaking::new(0.0));
+    }
+}
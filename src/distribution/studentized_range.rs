@@ -0,0 +1,431 @@
+use crate::distribution::sample_range::{invert_on_positive, range_cdf, range_pdf, range_sf};
+use crate::distribution::{
+    internal, ChiSquared, Continuous, ContinuousCDF, Parameterized, SampleRange,
+};
+use crate::function::integrate::composite_gauss_legendre;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Number of Gauss-Legendre panels of the outer quadrature over the
+/// studentizing standard deviation
+const S_PANELS: usize = 16;
+
+/// Half-width, in units of `1 / sqrt(ν)`, of the interval around `1` over
+/// which the studentizing standard deviation is integrated; its mass
+/// outside is below `e^-50`
+const S_BOUND: f64 = 10.0;
+
+/// Implements the [studentized
+/// range](https://en.wikipedia.org/wiki/Studentized_range_distribution)
+/// distribution of `W / S` where `W` is the range of `k` independent
+/// standard normal observations and `S^2` an independent chi-squared
+/// variable with `ν` degrees of freedom divided by `ν`
+///
+/// It is the null distribution of the largest difference between `k` group
+/// means divided by their standard error, used by Tukey's honestly
+/// significant difference test. With infinite degrees of freedom it is the
+/// distribution of the [range](crate::distribution::SampleRange) itself.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, StudentizedRange};
+///
+/// // the tabulated 5% critical value for 3 groups and 10 degrees of freedom
+/// let n = StudentizedRange::new(3, 10.0).unwrap();
+/// assert!((n.inverse_cdf(0.95) - 3.877).abs() < 1e-3);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StudentizedRange {
+    k: u64,
+    freedom: f64,
+}
+
+impl StudentizedRange {
+    /// Constructs a new studentized range distribution of `k` means with
+    /// `freedom` degrees of freedom, which may be `f64::INFINITY`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k < 2`, or if `freedom` is `NaN` or less than
+    /// `1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::StudentizedRange;
+    ///
+    /// let mut result = StudentizedRange::new(3, 10.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = StudentizedRange::new(1, 10.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(k: u64, freedom: f64) -> Result<StudentizedRange> {
+        if k < 2 || freedom.is_nan() || freedom < 1.0 {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(StudentizedRange { k, freedom })
+        }
+    }
+
+    /// Returns the number of means whose range is studentized
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::StudentizedRange;
+    ///
+    /// let n = StudentizedRange::new(3, 10.0).unwrap();
+    /// assert_eq!(n.k(), 3);
+    /// ```
+    pub fn k(&self) -> u64 {
+        self.k
+    }
+
+    /// Returns the degrees of freedom of the studentizing variance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::StudentizedRange;
+    ///
+    /// let n = StudentizedRange::new(3, 10.0).unwrap();
+    /// assert_eq!(n.freedom(), 10.0);
+    /// ```
+    pub fn freedom(&self) -> f64 {
+        self.freedom
+    }
+
+    /// Averages `f(s)` over the distribution of the studentizing standard
+    /// deviation `s`, substituting `s = u^2` so that the integrand is
+    /// smooth at `s = 0` for small degrees of freedom. The density of `s`
+    /// is normalized numerically on the same nodes.
+    fn expect<F: Fn(f64) -> f64>(&self, f: F) -> f64 {
+        let nu = self.freedom;
+        let half_width = S_BOUND / nu.sqrt();
+        let lo = (1.0 - half_width).max(0.0).sqrt();
+        let hi = (1.0 + half_width).sqrt();
+        // density of s up to a constant, relative to its value at s = 1,
+        // times the jacobian 2u
+        let weight = |u: f64| {
+            let s = u * u;
+            2.0 * u * ((nu - 1.0) * s.ln() - 0.5 * nu * (s * s - 1.0)).exp()
+        };
+        let mass = composite_gauss_legendre(&weight, lo, hi, S_PANELS);
+        composite_gauss_legendre(&|u| weight(u) * f(u * u), lo, hi, S_PANELS) / mass
+    }
+}
+
+impl Parameterized for StudentizedRange {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("k".into(), self.k as f64),
+            ("freedom".into(), self.freedom),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<StudentizedRange> {
+        match *values {
+            [k, freedom] => StudentizedRange::new(internal::integer_parameter(k)?, freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl std::fmt::Display for StudentizedRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Q({}, {})", self.k, self.freedom)
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for StudentizedRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let range = SampleRange::new(self.k).unwrap().sample(rng);
+        if self.freedom.is_infinite() {
+            range
+        } else {
+            let variance = ChiSquared::new(self.freedom).unwrap().sample(rng) / self.freedom;
+            range / variance.sqrt()
+        }
+    }
+}
+
+impl ContinuousCDF<f64, f64> for StudentizedRange {
+    /// Calculates the cumulative distribution function for the studentized
+    /// range distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ g(s) k ∫ φ(t) (Φ(t + x s) - Φ(t))^(k - 1) dt ds
+    /// ```
+    ///
+    /// where `φ` and `Φ` are the standard normal density and cdf and `g`
+    /// the density of `S`, the double integral being evaluated by composite
+    /// Gauss-Legendre quadrature
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if x <= 0.0 {
+            0.0
+        } else if x == f64::INFINITY {
+            1.0
+        } else if self.freedom.is_infinite() {
+            range_cdf(x, self.k)
+        } else {
+            self.expect(|s| range_cdf(x * s, self.k)).min(1.0)
+        }
+    }
+
+    /// Calculates the survival function for the studentized range
+    /// distribution at `x`, integrating the survival function of the range
+    /// so that small upper tail probabilities keep their relative accuracy
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ g(s) P(W > x s) ds
+    /// ```
+    ///
+    /// where `W` is the range of `k` standard normal observations and `g`
+    /// the density of `S`
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if x <= 0.0 {
+            1.0
+        } else if x == f64::INFINITY {
+            0.0
+        } else if self.freedom.is_infinite() {
+            range_sf(x, self.k)
+        } else {
+            self.expect(|s| range_sf(x * s, self.k)).min(1.0)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// studentized range distribution at `p` with Brent's method, root
+    /// finding on the survival function for `p > 0.5`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        if p == 0.0 {
+            0.0
+        } else if p == 1.0 {
+            f64::INFINITY
+        } else if p <= 0.5 {
+            invert_on_positive(|x| self.cdf(x), p, true, 2.0)
+        } else {
+            invert_on_positive(|x| self.sf(x), 1.0 - p, false, 2.0)
+        }
+    }
+}
+
+impl Min<f64> for StudentizedRange {
+    /// Returns the minimum value in the domain of the studentized range
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for StudentizedRange {
+    /// Returns the maximum value in the domain of the studentized range
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f64::INFINITY
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Median<f64> for StudentizedRange {
+    /// Returns the median of the studentized range distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(0.5)
+    /// ```
+    fn median(&self) -> f64 {
+        self.inverse_cdf(0.5)
+    }
+}
+
+impl Continuous<f64, f64> for StudentizedRange {
+    /// Calculates the probability density function for the studentized
+    /// range distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ s g(s) f_W(x s) ds
+    /// ```
+    ///
+    /// where `f_W` is the density of the range of `k` standard normal
+    /// observations and `g` the density of `S`
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            f64::NAN
+        } else if x < 0.0 || x == f64::INFINITY {
+            0.0
+        } else if self.freedom.is_infinite() {
+            range_pdf(x, self.k)
+        } else {
+            self.expect(|s| s * range_pdf(x * s, self.k))
+        }
+    }
+
+    /// Calculates the log probability density function for the studentized
+    /// range distribution at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::StudentsT;
+    use crate::testing_boiler;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!(k: u64, freedom: f64; StudentizedRange);
+
+    #[test]
+    fn test_create() {
+        try_create(2, 1.0);
+        try_create(10, 120.0);
+        try_create(3, f64::INFINITY);
+        bad_create_case(1, 10.0);
+        bad_create_case(3, 0.5);
+        bad_create_case(3, f64::NAN);
+    }
+
+    #[test]
+    fn test_two_means() {
+        // the studentized range of two means is √2 |T|
+        for &freedom in &[1.0, 4.0, 30.0] {
+            let q = try_create(2, freedom);
+            let t = StudentsT::new(0.0, 1.0, freedom).unwrap();
+            for &x in &[0.5, 2.0, 6.0] {
+                let sf = 2.0 * t.sf(x / f64::consts::SQRT_2);
+                assert_almost_eq!(q.cdf(x), 1.0 - sf, 1e-9);
+                assert_almost_eq!(q.sf(x), sf, 1e-9 * sf);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cdf() {
+        // reference values from nested adaptive quadrature in mpmath
+        let cdf = |x: f64| move |n: StudentizedRange| n.cdf(x);
+        test_case_special(4, 15.0, 0.89488991152021, 1e-9, cdf(3.5));
+        test_case_special(3, 5.0, 0.59763756900422, 1e-9, cdf(2.0));
+        test_case_special(2, 1.0, 0.39182655203061, 1e-9, cdf(1.0));
+        test_case_special(10, 40.0, 0.96881195014948, 1e-9, cdf(5.0));
+        test_case(3, 10.0, 0.0, cdf(0.0));
+        test_case(3, 10.0, 1.0, cdf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_critical_values() {
+        // upper quantiles of the published tables of the studentized range as
+        // (level, k, freedom, quantile), confirmed with mpmath
+        let table = [
+            (0.95, 2, 5.0, 3.635),
+            (0.95, 3, 10.0, 3.877),
+            (0.95, 4, 20.0, 3.958),
+            (0.95, 5, 30.0, 4.102),
+            (0.95, 6, 60.0, 4.163),
+            (0.95, 8, 24.0, 4.684),
+            (0.95, 10, 10.0, 5.598),
+            (0.95, 10, 120.0, 4.560),
+            (0.95, 10, f64::INFINITY, 4.474),
+            (0.95, 3, f64::INFINITY, 3.314),
+            (0.99, 3, 10.0, 5.270),
+            (0.99, 5, 20.0, 5.293),
+            (0.99, 10, 120.0, 5.299),
+        ];
+        for &(level, k, freedom, expected) in table.iter() {
+            let q = try_create(k, freedom).inverse_cdf(level);
+            assert!((q - expected).abs() < 1e-3, "q({}; {}, {}) = {}, expected {}", level, k, freedom, q, expected);
+        }
+    }
+
+    #[test]
+    fn test_infinite_freedom() {
+        let q = try_create(5, f64::INFINITY);
+        let w = SampleRange::new(5).unwrap();
+        assert_eq!(q.cdf(3.0), w.cdf(3.0));
+        // large degrees of freedom approach the range
+        let q = try_create(5, 1e6);
+        assert_almost_eq!(q.cdf(3.0), w.cdf(3.0), 1e-5);
+        assert_almost_eq!(q.pdf(3.0), w.pdf(3.0), 1e-5);
+    }
+
+    #[test]
+    fn test_pdf_is_derivative_of_cdf() {
+        for &(k, freedom) in &[(3, 1.0), (4, 2.5), (6, 20.0)] {
+            let q = try_create(k, freedom);
+            for &x in &[0.4, 2.0, 4.5] {
+                let h = 1e-4;
+                let slope = (q.cdf(x + h) - q.cdf(x - h)) / (2.0 * h);
+                assert_almost_eq!(q.pdf(x), slope, 1e-7);
+            }
+        }
+        assert_eq!(try_create(3, 10.0).pdf(-1.0), 0.0);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let q = try_create(4, 12.0);
+        for &p in &[0.01, 0.5, 0.99, 1.0 - 1e-8] {
+            let x = q.inverse_cdf(p);
+            if p <= 0.5 {
+                assert_almost_eq!(q.cdf(x), p, 1e-11 * p);
+            } else {
+                assert_almost_eq!(q.sf(x), 1.0 - p, 1e-11 * (1.0 - p));
+            }
+        }
+        assert_eq!(q.inverse_cdf(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_sample() {
+        let q = try_create(3, 8.0);
+        let mut rng = StdRng::seed_from_u64(2913);
+        let draws: Vec<f64> = (0..20000).map(|_| q.sample(&mut rng)).collect();
+        let below = draws.iter().filter(|&&x| x <= 3.0).count() as f64 / draws.len() as f64;
+        assert!((below - q.cdf(3.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let q = try_create(3, 10.0);
+        test::check_parameters(&q, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(q.set_parameter("freedom", 0.0), StudentizedRange::new(3, 0.0));
+    }
+}
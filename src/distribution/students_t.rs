@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{internal, Continuous, ContinuousCDF, Moments, Parameterized};
 use crate::function::{beta, gamma};
 use crate::is_zero;
 use crate::statistics::*;
@@ -104,6 +104,23 @@ impl StudentsT {
     }
 }
 
+impl Parameterized for StudentsT {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("location".into(), self.location),
+            ("scale".into(), self.scale),
+            ("freedom".into(), self.freedom),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<StudentsT> {
+        match *values {
+            [location, scale, freedom] => StudentsT::new(location, scale, freedom),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for StudentsT {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "t_{}({},{})", self.freedom, self.location, self.scale)
@@ -420,10 +437,62 @@ impl Continuous<f64, f64> for StudentsT {
     }
 }
 
+impl Moments for StudentsT {
+    /// Returns the raw moment of order `order` of the student's
+    /// t-distribution, or `None` if `order >= freedom`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ_{k = 0}^r C(r, k) μ^(r - k) E[(X - μ)^k]
+    /// ```
+    ///
+    /// where `μ` is the location
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        internal::raw_moment_about(order, self.location, |k| self.central_moment(k))
+    }
+
+    /// Returns the central moment of order `order` of the student's
+    /// t-distribution, or `None` if `order >= freedom`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// σ^r ν^(r / 2) Π_{i = 1}^{r / 2} (2i - 1) / (ν - 2i)
+    /// ```
+    ///
+    /// for even `r` and zero for odd `r`, where `σ` is the scale and `ν` is
+    /// the degrees of freedom. An infinite `ν` gives the moments of the
+    /// normal distribution, `σ^r (r - 1)!!`.
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        if order == 0 {
+            return Some(1.0);
+        }
+        if order as f64 >= self.freedom {
+            return None;
+        }
+        if order % 2 == 1 {
+            return Some(0.0);
+        }
+        let product: f64 = (1..=order / 2)
+            .map(|i| {
+                let odd = (2 * i - 1) as f64;
+                if self.freedom.is_infinite() {
+                    odd
+                } else {
+                    odd * self.freedom / (self.freedom - (2 * i) as f64)
+                }
+            })
+            .product();
+        Some(self.scale.powi(order as i32) * product)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::consts::ACC;
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
     use crate::distribution::{Continuous, ContinuousCDF, StudentsT};
     use crate::statistics::*;
     use crate::testing_boiler;
@@ -1168,4 +1237,42 @@ mod tests {
         let d = StudentsT::new(0.0, 1.0, 12.0).unwrap();
         assert_eq!(d.inverse_cdf(1.0), f64::INFINITY);
     }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.0, 2.0, 3.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(
+            n.set_parameter("freedom", 0.0),
+            StudentsT::new(1.0, 2.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(location, scale, freedom) in [
+            (0.0, 1.0, 1.0),
+            (1.0, 2.0, 2.5),
+            (-1.0, 0.5, 3.5),
+            (2.0, 1.5, 10.0),
+            (0.0, 1.0, f64::INFINITY),
+        ]
+        .iter()
+        {
+            test::check_moments(&try_create(location, scale, freedom), 1e-13);
+        }
+        // the kurtosis is 3 + 6 / (ν - 4)
+        let n = try_create(1.0, 2.0, 10.0);
+        assert_almost_eq!(n.standardized_moment(4).unwrap(), 4.0, 1e-14);
+        assert!(n.central_moment(10).is_none());
+        assert!(n.raw_moment(10).is_none());
+        assert_eq!(n.central_moment(9), Some(0.0));
+        assert_eq!(
+            try_create(0.0, 2.0, f64::INFINITY).central_moment(4),
+            Some(48.0)
+        );
+        // moments of half-integer order below the freedom exist
+        assert!(try_create(0.0, 1.0, 4.5).central_moment(4).is_some());
+    }
 }
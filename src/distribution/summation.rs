@@ -0,0 +1,391 @@
+//! Provides sums of the probability mass function of discrete distributions
+//! over ranges and infinite tails, with compensated summation and a bounded
+//! truncation error
+
+use crate::distribution::Discrete;
+use crate::statistics::{Max, Min, Mode};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The maximum number of terms summed by any of these functions, after
+/// which the sum is reported as failing to converge
+pub const MAX_TERMS: u64 = 10_000_000;
+
+/// Computes the probability `P(from <= X <= to)` by summing the probability
+/// mass function of `dist`, with compensated summation
+///
+/// # Remarks
+///
+/// `to` is clamped to the end of the support and the sum is `0` when
+/// `from > to`. The rounding error of the sum does not grow with the number
+/// of terms, so it is as accurate as the values of the probability mass
+/// function.
+///
+/// # Errors
+///
+/// Returns an error if the range holds more than [`MAX_TERMS`] terms of the
+/// support
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::summation::sum_pmf_range;
+/// use statrs::distribution::{DiscreteCDF, Poisson};
+///
+/// let n = Poisson::new(3.0).unwrap();
+/// let p = sum_pmf_range(&n, 0, 4).unwrap();
+/// assert!((p - n.cdf(4)).abs() < 1e-15);
+/// ```
+pub fn sum_pmf_range<D>(dist: &D, from: u64, to: u64) -> Result<f64>
+where
+    D: Discrete<u64, f64> + Max<u64>,
+{
+    let to = to.min(dist.max());
+    if from > to {
+        return Ok(0.0);
+    }
+    if to - from >= MAX_TERMS {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let mut sum = CompensatedSum::default();
+    for k in from..=to {
+        sum.add(dist.pmf(k));
+    }
+    Ok(sum.value())
+}
+
+/// Computes the probability `P(X >= from)` by summing the probability mass
+/// function of `dist` from `from`, stopping once the remaining tail is at
+/// most `tail_tol`
+///
+/// # Remarks
+///
+/// The terms below the mode are summed exactly, then the walk goes upwards
+/// from the mode and stops once the terms fall geometrically with a ratio
+/// `r` such that the tail bound `p_k r / (1 - r)` is at most `tail_tol`, or
+/// at the end of the support. The bound holds when the ratios of
+/// consecutive terms do not increase beyond the mode, as for the
+/// log-concave Poisson, binomial and geometric distributions. For heavier
+/// tails, such as Zipf's, it underestimates the tail and
+/// [`sum_pmf_until_with`] should be given a bound of the tail instead.
+///
+/// # Errors
+///
+/// Returns an error if `tail_tol` is not positive, or if more than
+/// [`MAX_TERMS`] terms are needed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::summation::sum_pmf_until;
+/// use statrs::distribution::{DiscreteCDF, Poisson};
+///
+/// let n = Poisson::new(3.0).unwrap();
+/// let p = sum_pmf_until(&n, 5, 1e-16).unwrap();
+/// assert!((p - n.sf(4)).abs() < 1e-15);
+/// ```
+pub fn sum_pmf_until<D>(dist: &D, from: u64, tail_tol: f64) -> Result<f64>
+where
+    D: Discrete<u64, f64> + Mode<Option<u64>> + Max<u64>,
+{
+    walk(dist, from, tail_tol, |_| 1.0, geometric_bound())
+}
+
+/// Computes the probability `P(X >= from)` by summing the probability mass
+/// function of `dist` from `from`, stopping once `tail_bound(k)`, an upper
+/// bound of `P(X > k)`, is at most `tail_tol`
+///
+/// # Remarks
+///
+/// The terms below the mode are summed exactly and the walk goes upwards
+/// from the mode, so the truncation error is at most `tail_tol` whenever
+/// `tail_bound` bounds the tail beyond the mode.
+///
+/// # Errors
+///
+/// Returns an error if `tail_tol` is not positive, or if more than
+/// [`MAX_TERMS`] terms are needed, as when `tail_bound` does not tend to `0`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::summation::sum_pmf_until_with;
+/// use statrs::distribution::{DiscreteCDF, Geometric};
+///
+/// // the tail of the geometric distribution is P(X > k) = (1 - p)^k
+/// let n = Geometric::new(0.25).unwrap();
+/// let p = sum_pmf_until_with(&n, 3, 1e-15, |k| 0.75f64.powf(k as f64)).unwrap();
+/// assert!((p - n.sf(2)).abs() < 1e-14);
+/// ```
+pub fn sum_pmf_until_with<D, B>(dist: &D, from: u64, tail_tol: f64, tail_bound: B) -> Result<f64>
+where
+    D: Discrete<u64, f64> + Mode<Option<u64>> + Max<u64>,
+    B: Fn(u64) -> f64,
+{
+    walk(dist, from, tail_tol, |_| 1.0, |k, _| tail_bound(k))
+}
+
+/// Computes the expected value `E[f(X)]` for `X` following `dist`, summing
+/// `f(k) P(X = k)` over the support until the remaining tail of the
+/// distribution is at most `tail_tol`
+///
+/// # Remarks
+///
+/// The tail is bounded as in [`sum_pmf_until`], so the truncation error is
+/// at most `tail_tol` times the largest `|f|` over the tail for a
+/// log-concave distribution. For an unbounded `f` the tolerance should be
+/// small enough for the tail of `f(X)` to be negligible.
+///
+/// # Errors
+///
+/// Returns an error if `tail_tol` is not positive, or if more than
+/// [`MAX_TERMS`] terms are needed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::summation::expect_discrete;
+/// use statrs::distribution::Poisson;
+///
+/// // the second moment of a Poisson variable is λ + λ²
+/// let n = Poisson::new(3.0).unwrap();
+/// let m = expect_discrete(&n, |k| (k * k) as f64, 1e-18).unwrap();
+/// assert!((m - 12.0).abs() < 1e-13);
+/// ```
+pub fn expect_discrete<D, F>(dist: &D, f: F, tail_tol: f64) -> Result<f64>
+where
+    D: Discrete<u64, f64> + Mode<Option<u64>> + Min<u64> + Max<u64>,
+    F: Fn(u64) -> f64,
+{
+    walk(dist, dist.min(), tail_tol, f, geometric_bound())
+}
+
+/// Sums `f(k) P(X = k)` from `from`, exactly up to the mode and then
+/// upwards until `tail_bound(k, P(X = k))` is at most `tail_tol`
+fn walk<D, F, B>(dist: &D, from: u64, tail_tol: f64, f: F, mut tail_bound: B) -> Result<f64>
+where
+    D: Discrete<u64, f64> + Mode<Option<u64>> + Max<u64>,
+    F: Fn(u64) -> f64,
+    B: FnMut(u64, f64) -> f64,
+{
+    if tail_tol.is_nan() || tail_tol <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tail_tol"));
+    }
+    let max = dist.max();
+    if from > max {
+        return Ok(0.0);
+    }
+    let start = dist.mode().unwrap_or(from).clamp(from, max);
+    if start - from >= MAX_TERMS {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let mut sum = CompensatedSum::default();
+    for k in from..start {
+        sum.add(f(k) * dist.pmf(k));
+    }
+    let end = start.saturating_add(MAX_TERMS - (start - from));
+    for k in start..end {
+        let p = dist.pmf(k);
+        sum.add(f(k) * p);
+        if k == max || tail_bound(k, p) <= tail_tol {
+            return Ok(sum.value());
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Returns a bound of the tail beyond the current term, `p_k r / (1 - r)`
+/// with `r` the ratio of the current term to the previous one, which holds
+/// once the ratios stop increasing
+fn geometric_bound() -> impl FnMut(u64, f64) -> f64 {
+    let mut previous: Option<f64> = None;
+    move |_, p| {
+        let bound = match previous {
+            _ if p == 0.0 => 0.0,
+            Some(q) if p < q => {
+                let r = p / q;
+                p * r / (1.0 - r)
+            }
+            _ => f64::INFINITY,
+        };
+        previous = Some(p);
+        bound
+    }
+}
+
+/// Accumulates a sum with Neumaier's compensated summation, whose error
+/// does not grow with the number of terms
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    pub(crate) fn add(&mut self, v: f64) {
+        let t = self.sum + v;
+        if self.sum.abs() >= v.abs() {
+            self.compensation += (self.sum - t) + v;
+        } else {
+            self.compensation += (v - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    pub(crate) fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Binomial, DiscreteCDF, Geometric, Poisson};
+    use std::f64::consts::PI;
+
+    /// Zipf's distribution with exponent 2 on the positive integers, whose
+    /// tail beyond `k` is bounded by `6 / (π² k)`
+    struct Zipf;
+
+    impl Discrete<u64, f64> for Zipf {
+        fn pmf(&self, x: u64) -> f64 {
+            if x == 0 { 0.0 } else { 6.0 / (PI * PI * (x as f64).powi(2)) }
+        }
+
+        fn ln_pmf(&self, x: u64) -> f64 {
+            self.pmf(x).ln()
+        }
+    }
+
+    impl Mode<Option<u64>> for Zipf {
+        fn mode(&self) -> Option<u64> {
+            Some(1)
+        }
+    }
+
+    impl Min<u64> for Zipf {
+        fn min(&self) -> u64 {
+            1
+        }
+    }
+
+    impl Max<u64> for Zipf {
+        fn max(&self) -> u64 {
+            u64::MAX
+        }
+    }
+
+    fn zipf_tail(k: u64) -> f64 {
+        6.0 / (PI * PI * k as f64)
+    }
+
+    #[test]
+    fn test_range_matches_cdf() {
+        // the pmf of a large rate is only accurate to about 1e-13, from the
+        // cancellation of the terms of its logarithm
+        for &(lambda, tol) in &[(0.5, 1e-13), (3.0, 1e-13), (25.0, 1e-13), (400.0, 1e-12)] {
+            let n = Poisson::new(lambda).unwrap();
+            for &x in &[0, 1, 5, 20, 380, 450] {
+                assert_almost_eq!(sum_pmf_range(&n, 0, x).unwrap(), n.cdf(x), tol);
+            }
+        }
+        let n = Binomial::new(0.3, 60).unwrap();
+        for x in 0..=60 {
+            assert_almost_eq!(sum_pmf_range(&n, 0, x).unwrap(), n.cdf(x), 1e-13);
+            assert_almost_eq!(sum_pmf_range(&n, x, 60).unwrap(), n.sf(x) + n.pmf(x), 1e-13);
+        }
+        assert_eq!(sum_pmf_range(&n, 5, 4).unwrap(), 0.0);
+        // the range ends with the support
+        assert_almost_eq!(sum_pmf_range(&n, 0, u64::MAX).unwrap(), 1.0, 1e-14);
+        assert_eq!(sum_pmf_range(&n, 61, u64::MAX).unwrap(), 0.0);
+        // an unbounded support is summed over at most MAX_TERMS terms
+        let n = Poisson::new(3.0).unwrap();
+        assert_eq!(sum_pmf_range(&n, 0, u64::MAX), Err(StatsError::ComputationFailedToConverge));
+        assert_eq!(sum_pmf_range(&n, 0, MAX_TERMS), Err(StatsError::ComputationFailedToConverge));
+        assert_almost_eq!(sum_pmf_range(&n, 0, 1000).unwrap(), 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_until_matches_sf() {
+        for &(lambda, tol) in &[(0.5, 1e-13), (3.0, 1e-13), (25.0, 1e-13), (400.0, 1e-12)] {
+            let n = Poisson::new(lambda).unwrap();
+            for &x in &[0, 1, 5, 20, 380, 450] {
+                let p = sum_pmf_until(&n, x, 1e-17).unwrap();
+                assert_almost_eq!(p, n.sf(x) + n.pmf(x), tol);
+            }
+        }
+        // the walk stops at the end of a finite support
+        let n = Binomial::new(0.9, 40).unwrap();
+        assert_almost_eq!(sum_pmf_until(&n, 0, 1e-300).unwrap(), 1.0, 1e-14);
+        assert_eq!(sum_pmf_until(&n, 41, 1e-10).unwrap(), 0.0);
+        let n = Geometric::new(0.01).unwrap();
+        assert_almost_eq!(sum_pmf_until(&n, 1, 1e-16).unwrap(), 1.0, 1e-13);
+    }
+
+    #[test]
+    fn test_geometric_bound_holds() {
+        let n = Poisson::new(10.0).unwrap();
+        for &tol in &[1e-3, 1e-6, 1e-9] {
+            let error = n.sf(0) + n.pmf(0) - sum_pmf_until(&n, 0, tol).unwrap();
+            assert!(error >= -1e-15 && error <= tol, "{} {}", tol, error);
+        }
+    }
+
+    #[test]
+    fn test_heavy_tail_bound() {
+        for &tol in &[1e-2, 1e-3, 1e-4] {
+            let p = sum_pmf_until_with(&Zipf, 1, tol, zipf_tail).unwrap();
+            let error = 1.0 - p;
+            assert!(error > 0.0 && error <= tol, "{} {}", tol, error);
+            // the geometric bound does not bound such a tail
+            let p = sum_pmf_until(&Zipf, 1, tol).unwrap();
+            assert!(1.0 - p > tol);
+        }
+        // a later start includes the exact terms of the upper tail only
+        let p = sum_pmf_until_with(&Zipf, 10, 1e-4, zipf_tail).unwrap();
+        let head = sum_pmf_range(&Zipf, 1, 9).unwrap();
+        assert!((1.0 - head - p).abs() <= 1e-4);
+    }
+
+    #[test]
+    fn test_expect_discrete() {
+        let n = Poisson::new(7.5).unwrap();
+        assert_almost_eq!(expect_discrete(&n, |_| 1.0, 1e-18).unwrap(), 1.0, 1e-14);
+        assert_almost_eq!(expect_discrete(&n, |k| k as f64, 1e-18).unwrap(), 7.5, 1e-13);
+        let variance = expect_discrete(&n, |k| (k as f64 - 7.5).powi(2), 1e-18).unwrap();
+        assert_almost_eq!(variance, 7.5, 1e-12);
+        let n = Binomial::new(0.4, 30).unwrap();
+        assert_almost_eq!(expect_discrete(&n, |k| k as f64, 1e-18).unwrap(), 12.0, 1e-13);
+        // the support of Zipf's distribution starts at 1
+        let p = expect_discrete(&Zipf, |k| if k == 1 { 1.0 } else { 0.0 }, 1e-3).unwrap();
+        assert_almost_eq!(p, 6.0 / (PI * PI), 1e-16);
+    }
+
+    #[test]
+    fn test_errors() {
+        let n = Poisson::new(3.0).unwrap();
+        assert!(sum_pmf_until(&n, 0, 0.0).is_err());
+        assert!(sum_pmf_until(&n, 0, f64::NAN).is_err());
+        assert!(expect_discrete(&n, |_| 1.0, -1.0).is_err());
+        // a bound that never falls below the tolerance stops after MAX_TERMS
+        assert_eq!(
+            sum_pmf_until_with(&Zipf, 1, 1e-3, |_| 1.0),
+            Err(StatsError::ComputationFailedToConverge)
+        );
+        assert_eq!(
+            sum_pmf_until_with(&Zipf, 1, 1e-3, |_| f64::NAN),
+            Err(StatsError::ComputationFailedToConverge)
+        );
+    }
+
+    #[test]
+    fn test_compensated_sum() {
+        let mut sum = CompensatedSum::default();
+        for &v in &[1.0, 1e100, 1.0, -1e100] {
+            sum.add(v);
+        }
+        assert_eq!(sum.value(), 2.0);
+        assert_eq!(CompensatedSum::default().value(), 0.0);
+    }
+}
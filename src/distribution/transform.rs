@@ -0,0 +1,1212 @@
+//! Provides generic wrappers deriving new continuous distributions from
+//! existing ones through a change of variables
+
+use crate::distribution::{internal, Continuous, ContinuousCDF, Parameterized};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// Implements the distribution of `X + c` where `X` follows the wrapped
+/// distribution and `c` is a constant shift
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, Exp, Shifted};
+/// use statrs::statistics::Distribution;
+///
+/// let n = Shifted::new(Exp::new(1.0).unwrap(), 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 3.0);
+/// assert_eq!(n.pdf(2.0), 1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Shifted<D> {
+    dist: D,
+    shift: f64,
+}
+
+impl<D> Shifted<D> {
+    /// Constructs a new distribution of `dist` shifted by `shift`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shift` is `NaN` or infinite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Shifted};
+    ///
+    /// let mut result = Shifted::new(Normal::standard(), 1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Shifted::new(Normal::standard(), f64::INFINITY);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(dist: D, shift: f64) -> Result<Shifted<D>> {
+        if !shift.is_finite() {
+            return Err(StatsError::ArgFinite("shift"));
+        }
+        Ok(Shifted { dist, shift })
+    }
+
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+
+    /// Returns the shift of the distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Shifted};
+    ///
+    /// let n = Shifted::new(Normal::standard(), 1.0).unwrap();
+    /// assert_eq!(n.shift(), 1.0);
+    /// ```
+    pub fn shift(&self) -> f64 {
+        self.shift
+    }
+}
+
+impl<D: Parameterized> Parameterized for Shifted<D> {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let mut parameters = internal::inner_parameters(&self.dist);
+        parameters.push(("shift".into(), self.shift));
+        parameters
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Shifted<D>> {
+        match values.split_last() {
+            Some((&shift, inner)) => Shifted::new(self.dist.with_parameters(inner)?, shift),
+            None => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for Shifted<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}", self.dist, self.shift)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for Shifted<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.dist.sample(rng) + self.shift
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for Shifted<D> {
+    /// Calculates the cumulative distribution function for the shifted
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F(x - c)
+    /// ```
+    ///
+    /// where `F` is the cdf of the wrapped distribution and `c` the shift
+    fn cdf(&self, x: f64) -> f64 {
+        self.dist.cdf(x - self.shift)
+    }
+
+    /// Calculates the survival function for the shifted distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S(x - c)
+    /// ```
+    ///
+    /// where `S` is the survival function of the wrapped distribution and
+    /// `c` the shift
+    fn sf(&self, x: f64) -> f64 {
+        self.dist.sf(x - self.shift)
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// shifted distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F^-1(p) + c
+    /// ```
+    ///
+    /// where `F^-1` is the inverse cdf of the wrapped distribution and `c`
+    /// the shift
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.dist.inverse_cdf(p) + self.shift
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for Shifted<D> {
+    /// Returns the minimum value in the domain of the shifted distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// min(X) + c
+    /// ```
+    fn min(&self) -> f64 {
+        self.dist.min() + self.shift
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for Shifted<D> {
+    /// Returns the maximum value in the domain of the shifted distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// max(X) + c
+    /// ```
+    fn max(&self) -> f64 {
+        self.dist.max() + self.shift
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for Shifted<D> {
+    /// Returns the mean of the shifted distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// E[X] + c
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        self.dist.mean().map(|m| m + self.shift)
+    }
+
+    /// Returns the variance of the shifted distribution, which is that of
+    /// the wrapped distribution
+    fn variance(&self) -> Option<f64> {
+        self.dist.variance()
+    }
+
+    /// Returns the entropy of the shifted distribution, which is that of
+    /// the wrapped distribution
+    fn entropy(&self) -> Option<f64> {
+        self.dist.entropy()
+    }
+
+    /// Returns the skewness of the shifted distribution, which is that of
+    /// the wrapped distribution
+    fn skewness(&self) -> Option<f64> {
+        self.dist.skewness()
+    }
+}
+
+impl<D: Median<f64>> Median<f64> for Shifted<D> {
+    /// Returns the median of the shifted distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// median(X) + c
+    /// ```
+    fn median(&self) -> f64 {
+        self.dist.median() + self.shift
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for Shifted<D> {
+    /// Calculates the probability density function for the shifted
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f(x - c)
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution and `c` the shift
+    fn pdf(&self, x: f64) -> f64 {
+        self.dist.pdf(x - self.shift)
+    }
+
+    /// Calculates the log probability density function for the shifted
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(x - c))
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.dist.ln_pdf(x - self.shift)
+    }
+}
+
+/// Implements the distribution of `kX` where `X` follows the wrapped
+/// distribution and `k` is a non-zero scale factor
+///
+/// # Remarks
+///
+/// A negative `k` mirrors the support of the wrapped distribution, so that
+/// its lower tail becomes the upper tail of the scaled distribution
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, Exp, Scaled};
+/// use statrs::statistics::Distribution;
+///
+/// let n = Scaled::new(Exp::new(1.0).unwrap(), -1.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), -1.0);
+/// assert_eq!(n.cdf(0.0), 1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Scaled<D> {
+    dist: D,
+    scale: f64,
+}
+
+impl<D> Scaled<D> {
+    /// Constructs a new distribution of `dist` multiplied by `scale`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `scale` is `NaN`, infinite or `0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Scaled};
+    ///
+    /// let mut result = Scaled::new(Normal::standard(), -2.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = Scaled::new(Normal::standard(), 0.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(dist: D, scale: f64) -> Result<Scaled<D>> {
+        if !scale.is_finite() {
+            return Err(StatsError::ArgFinite("scale"));
+        }
+        if scale == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Scaled { dist, scale })
+    }
+
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+
+    /// Returns the scale factor of the distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Normal, Scaled};
+    ///
+    /// let n = Scaled::new(Normal::standard(), -2.0).unwrap();
+    /// assert_eq!(n.scale(), -2.0);
+    /// ```
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl<D: Parameterized> Parameterized for Scaled<D> {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        let mut parameters = internal::inner_parameters(&self.dist);
+        parameters.push(("scale".into(), self.scale));
+        parameters
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Scaled<D>> {
+        match values.split_last() {
+            Some((&scale, inner)) => Scaled::new(self.dist.with_parameters(inner)?, scale),
+            None => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for Scaled<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} * {}", self.scale, self.dist)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for Scaled<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.scale * self.dist.sample(rng)
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for Scaled<D> {
+    /// Calculates the cumulative distribution function for the scaled
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F(x / k)        if k > 0
+    /// 1 - F(x / k)    if k < 0
+    /// ```
+    ///
+    /// where `F` is the cdf of the wrapped distribution and `k` the scale
+    fn cdf(&self, x: f64) -> f64 {
+        if self.scale > 0.0 {
+            self.dist.cdf(x / self.scale)
+        } else {
+            self.dist.sf(x / self.scale)
+        }
+    }
+
+    /// Calculates the survival function for the scaled distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// 1 - F(x / k)    if k > 0
+    /// F(x / k)        if k < 0
+    /// ```
+    ///
+    /// where `F` is the cdf of the wrapped distribution and `k` the scale
+    fn sf(&self, x: f64) -> f64 {
+        if self.scale > 0.0 {
+            self.dist.sf(x / self.scale)
+        } else {
+            self.dist.cdf(x / self.scale)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// scaled distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k * F^-1(p)        if k > 0
+    /// k * F^-1(1 - p)    if k < 0
+    /// ```
+    ///
+    /// where `F^-1` is the inverse cdf of the wrapped distribution and `k`
+    /// the scale
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if self.scale > 0.0 {
+            self.scale * self.dist.inverse_cdf(p)
+        } else {
+            self.scale * self.dist.inverse_cdf(1.0 - p)
+        }
+    }
+}
+
+impl<D: Min<f64> + Max<f64>> Min<f64> for Scaled<D> {
+    /// Returns the minimum value in the domain of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k * min(X)    if k > 0
+    /// k * max(X)    if k < 0
+    /// ```
+    fn min(&self) -> f64 {
+        if self.scale > 0.0 {
+            self.scale * self.dist.min()
+        } else {
+            self.scale * self.dist.max()
+        }
+    }
+}
+
+impl<D: Min<f64> + Max<f64>> Max<f64> for Scaled<D> {
+    /// Returns the maximum value in the domain of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k * max(X)    if k > 0
+    /// k * min(X)    if k < 0
+    /// ```
+    fn max(&self) -> f64 {
+        if self.scale > 0.0 {
+            self.scale * self.dist.max()
+        } else {
+            self.scale * self.dist.min()
+        }
+    }
+}
+
+impl<D: Distribution<f64>> Distribution<f64> for Scaled<D> {
+    /// Returns the mean of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k * E[X]
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        self.dist.mean().map(|m| self.scale * m)
+    }
+
+    /// Returns the variance of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k^2 * Var[X]
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        self.dist.variance().map(|v| self.scale * self.scale * v)
+    }
+
+    /// Returns the entropy of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H(X) + ln(|k|)
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        self.dist.entropy().map(|h| h + self.scale.abs().ln())
+    }
+
+    /// Returns the skewness of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// sign(k) * skew(X)
+    /// ```
+    fn skewness(&self) -> Option<f64> {
+        self.dist.skewness().map(|s| self.scale.signum() * s)
+    }
+}
+
+impl<D: Median<f64>> Median<f64> for Scaled<D> {
+    /// Returns the median of the scaled distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// k * median(X)
+    /// ```
+    fn median(&self) -> f64 {
+        self.scale * self.dist.median()
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for Scaled<D> {
+    /// Calculates the probability density function for the scaled
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f(x / k) / |k|
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution and `k` the scale
+    fn pdf(&self, x: f64) -> f64 {
+        self.dist.pdf(x / self.scale) / self.scale.abs()
+    }
+
+    /// Calculates the log probability density function for the scaled
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(x / k)) - ln(|k|)
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.dist.ln_pdf(x / self.scale) - self.scale.abs().ln()
+    }
+}
+
+/// Implements the distribution of `e^X` where `X` follows the wrapped
+/// distribution
+///
+/// # Remarks
+///
+/// The moments of `e^X` do not follow from those of `X` and are computed by
+/// numerical integration against the density of `X`; they are `None` when
+/// the integral does not converge to a finite value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, ExpOf, LogNormal, Normal};
+///
+/// let n = ExpOf::new(Normal::new(0.0, 1.0).unwrap());
+/// let m = LogNormal::new(0.0, 1.0).unwrap();
+/// assert_eq!(n.cdf(2.0), m.cdf(2.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ExpOf<D> {
+    dist: D,
+}
+
+impl<D> ExpOf<D> {
+    /// Constructs a new distribution of the exponential of `dist`
+    pub fn new(dist: D) -> ExpOf<D> {
+        ExpOf { dist }
+    }
+
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+}
+
+impl<D: Parameterized> Parameterized for ExpOf<D> {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        internal::inner_parameters(&self.dist)
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<ExpOf<D>> {
+        Ok(ExpOf::new(self.dist.with_parameters(values)?))
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for ExpOf<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "exp({})", self.dist)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for ExpOf<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.dist.sample(rng).exp()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for ExpOf<D> {
+    /// Calculates the cumulative distribution function for the exponential
+    /// of the wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F(ln(x))
+    /// ```
+    ///
+    /// where `F` is the cdf of the wrapped distribution
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            self.dist.cdf(x.ln())
+        }
+    }
+
+    /// Calculates the survival function for the exponential of the wrapped
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S(ln(x))
+    /// ```
+    ///
+    /// where `S` is the survival function of the wrapped distribution
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            self.dist.sf(x.ln())
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// exponential of the wrapped distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^(F^-1(p))
+    /// ```
+    ///
+    /// where `F^-1` is the inverse cdf of the wrapped distribution
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.dist.inverse_cdf(p).exp()
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for ExpOf<D> {
+    /// Returns the minimum value in the domain of the exponential of the
+    /// wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^min(X)
+    /// ```
+    fn min(&self) -> f64 {
+        self.dist.min().exp()
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for ExpOf<D> {
+    /// Returns the maximum value in the domain of the exponential of the
+    /// wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^max(X)
+    /// ```
+    fn max(&self) -> f64 {
+        self.dist.max().exp()
+    }
+}
+
+impl<D> Distribution<f64> for ExpOf<D>
+where
+    D: Distribution<f64> + Continuous<f64, f64> + Min<f64> + Max<f64>,
+{
+    /// Returns the mean of the exponential of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ e^x f(x) dx
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution
+    fn mean(&self) -> Option<f64> {
+        internal::expectation(&self.dist, f64::exp)
+    }
+
+    /// Returns the variance of the exponential of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ (e^x - μ)^2 f(x) dx
+    /// ```
+    ///
+    /// where `μ` is the mean and `f` is the pdf of the wrapped distribution
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        internal::expectation(&self.dist, |x| {
+            let d = x.exp() - mean;
+            d * d
+        })
+    }
+
+    /// Returns the entropy of the exponential of the wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H(X) + E[X]
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(self.dist.entropy()? + self.dist.mean()?)
+    }
+
+    /// Returns the skewness of the exponential of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ ((e^x - μ) / σ)^3 f(x) dx
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` the standard deviation and `f` is the pdf
+    /// of the wrapped distribution
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let std_dev = self.variance()?.sqrt();
+        internal::expectation(&self.dist, |x| ((x.exp() - mean) / std_dev).powi(3))
+    }
+}
+
+impl<D: Median<f64>> Median<f64> for ExpOf<D> {
+    /// Returns the median of the exponential of the wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// e^median(X)
+    /// ```
+    fn median(&self) -> f64 {
+        self.dist.median().exp()
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for ExpOf<D> {
+    /// Calculates the probability density function for the exponential of
+    /// the wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f(ln(x)) / x
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 || x.is_infinite() {
+            0.0
+        } else {
+            self.dist.pdf(x.ln()) / x
+        }
+    }
+
+    /// Calculates the log probability density function for the exponential
+    /// of the wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(ln(x))) - ln(x)
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 || x.is_infinite() {
+            f64::NEG_INFINITY
+        } else {
+            let ln_x = x.ln();
+            self.dist.ln_pdf(ln_x) - ln_x
+        }
+    }
+}
+
+/// Implements the distribution of `ln(X)` where `X` follows a wrapped
+/// distribution supported on the non-negative reals
+///
+/// # Remarks
+///
+/// The moments of `ln(X)` do not follow from those of `X` and are computed
+/// by numerical integration against the density of `X`; they are `None`
+/// when the integral does not converge to a finite value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, LogNormal, LogOf, Normal};
+///
+/// let n = LogOf::new(LogNormal::new(0.0, 1.0).unwrap()).unwrap();
+/// let m = Normal::new(0.0, 1.0).unwrap();
+/// assert!((n.cdf(0.5) - m.cdf(0.5)).abs() < 1e-15);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LogOf<D> {
+    dist: D,
+}
+
+impl<D: Min<f64>> LogOf<D> {
+    /// Constructs a new distribution of the natural logarithm of `dist`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the support of `dist` extends below `0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, LogOf, Normal};
+    ///
+    /// let mut result = LogOf::new(Exp::new(1.0).unwrap());
+    /// assert!(result.is_ok());
+    ///
+    /// let result = LogOf::new(Normal::standard());
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(dist: D) -> Result<LogOf<D>> {
+        if dist.min() >= 0.0 {
+            Ok(LogOf { dist })
+        } else {
+            Err(StatsError::ArgNotNegative("min"))
+        }
+    }
+}
+
+impl<D> LogOf<D> {
+    /// Returns the wrapped distribution
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+}
+
+impl<D: Parameterized + Min<f64>> Parameterized for LogOf<D> {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        internal::inner_parameters(&self.dist)
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<LogOf<D>> {
+        LogOf::new(self.dist.with_parameters(values)?)
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for LogOf<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ln({})", self.dist)
+    }
+}
+
+impl<D: ::rand::distributions::Distribution<f64>> ::rand::distributions::Distribution<f64>
+    for LogOf<D>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.dist.sample(rng).ln()
+    }
+}
+
+impl<D: ContinuousCDF<f64, f64>> ContinuousCDF<f64, f64> for LogOf<D> {
+    /// Calculates the cumulative distribution function for the logarithm
+    /// of the wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// F(e^x)
+    /// ```
+    ///
+    /// where `F` is the cdf of the wrapped distribution
+    fn cdf(&self, x: f64) -> f64 {
+        self.dist.cdf(x.exp())
+    }
+
+    /// Calculates the survival function for the logarithm of the wrapped
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S(e^x)
+    /// ```
+    ///
+    /// where `S` is the survival function of the wrapped distribution
+    fn sf(&self, x: f64) -> f64 {
+        self.dist.sf(x.exp())
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// logarithm of the wrapped distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(F^-1(p))
+    /// ```
+    ///
+    /// where `F^-1` is the inverse cdf of the wrapped distribution
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        self.dist.inverse_cdf(p).ln()
+    }
+}
+
+impl<D: Min<f64>> Min<f64> for LogOf<D> {
+    /// Returns the minimum value in the domain of the logarithm of the
+    /// wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(min(X))
+    /// ```
+    fn min(&self) -> f64 {
+        self.dist.min().ln()
+    }
+}
+
+impl<D: Max<f64>> Max<f64> for LogOf<D> {
+    /// Returns the maximum value in the domain of the logarithm of the
+    /// wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(max(X))
+    /// ```
+    fn max(&self) -> f64 {
+        self.dist.max().ln()
+    }
+}
+
+impl<D> Distribution<f64> for LogOf<D>
+where
+    D: Distribution<f64> + Continuous<f64, f64> + Min<f64> + Max<f64>,
+{
+    /// Returns the mean of the logarithm of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ ln(x) f(x) dx
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution
+    fn mean(&self) -> Option<f64> {
+        internal::expectation(&self.dist, f64::ln)
+    }
+
+    /// Returns the variance of the logarithm of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ (ln(x) - μ)^2 f(x) dx
+    /// ```
+    ///
+    /// where `μ` is the mean and `f` is the pdf of the wrapped distribution
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        internal::expectation(&self.dist, |x| {
+            let d = x.ln() - mean;
+            d * d
+        })
+    }
+
+    /// Returns the entropy of the logarithm of the wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H(X) - E[ln(X)]
+    /// ```
+    fn entropy(&self) -> Option<f64> {
+        Some(self.dist.entropy()? - self.mean()?)
+    }
+
+    /// Returns the skewness of the logarithm of the wrapped distribution,
+    /// computed numerically
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ∫ ((ln(x) - μ) / σ)^3 f(x) dx
+    /// ```
+    ///
+    /// where `μ` is the mean, `σ` the standard deviation and `f` is the pdf
+    /// of the wrapped distribution
+    fn skewness(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let std_dev = self.variance()?.sqrt();
+        internal::expectation(&self.dist, |x| ((x.ln() - mean) / std_dev).powi(3))
+    }
+}
+
+impl<D: Median<f64>> Median<f64> for LogOf<D> {
+    /// Returns the median of the logarithm of the wrapped distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(median(X))
+    /// ```
+    fn median(&self) -> f64 {
+        self.dist.median().ln()
+    }
+}
+
+impl<D: Continuous<f64, f64>> Continuous<f64, f64> for LogOf<D> {
+    /// Calculates the probability density function for the logarithm of the
+    /// wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// f(e^x) * e^x
+    /// ```
+    ///
+    /// where `f` is the pdf of the wrapped distribution
+    fn pdf(&self, x: f64) -> f64 {
+        let e_x = x.exp();
+        if e_x == 0.0 || e_x.is_infinite() {
+            0.0
+        } else {
+            self.dist.pdf(e_x) * e_x
+        }
+    }
+
+    /// Calculates the log probability density function for the logarithm of
+    /// the wrapped distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// ln(f(e^x)) + x
+    /// ```
+    fn ln_pdf(&self, x: f64) -> f64 {
+        let e_x = x.exp();
+        if e_x == 0.0 || e_x.is_infinite() {
+            f64::NEG_INFINITY
+        } else {
+            self.dist.ln_pdf(e_x) + x
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Exp, LogNormal, Normal, Uniform};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bad_create() {
+        assert!(Shifted::new(Normal::standard(), f64::NAN).is_err());
+        assert!(Scaled::new(Normal::standard(), 0.0).is_err());
+        assert!(Scaled::new(Normal::standard(), f64::NEG_INFINITY).is_err());
+        assert!(LogOf::new(Uniform::new(-1.0, 1.0).unwrap()).is_err());
+        assert!(LogOf::new(Uniform::new(0.0, 1.0).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_exp_of_normal_is_log_normal() {
+        for &(mu, sigma) in &[(0.0, 1.0), (-1.5, 0.3), (2.0, 2.5)] {
+            let n = ExpOf::new(Normal::new(mu, sigma).unwrap());
+            let m = LogNormal::new(mu, sigma).unwrap();
+            for &x in &[1e-3, 0.1, 0.5, 1.0, 2.0, 7.5, 40.0] {
+                assert_almost_eq!(n.pdf(x), m.pdf(x), 1e-15 * m.pdf(x).max(1.0));
+                assert_almost_eq!(n.ln_pdf(x), m.ln_pdf(x), 1e-13);
+                assert_eq!(n.cdf(x), m.cdf(x));
+                assert_eq!(n.sf(x), m.sf(x));
+            }
+            for &p in &[0.0, 1e-6, 0.05, 0.5, 0.9, 0.999, 1.0] {
+                assert_eq!(n.inverse_cdf(p), m.inverse_cdf(p));
+            }
+            assert_eq!(n.pdf(-1.0), 0.0);
+            assert_eq!(n.cdf(0.0), 0.0);
+            assert_eq!(n.min(), m.min());
+            assert_eq!(n.max(), m.max());
+            assert_eq!(n.median(), m.median());
+            assert_almost_eq!(n.mean().unwrap(), m.mean().unwrap(), 1e-10 * m.mean().unwrap());
+            assert_almost_eq!(n.variance().unwrap(), m.variance().unwrap(), 1e-8 * m.variance().unwrap());
+            assert_almost_eq!(n.entropy().unwrap(), m.entropy().unwrap(), 1e-14);
+        }
+        let n = ExpOf::new(Normal::new(0.0, 0.5).unwrap());
+        let m = LogNormal::new(0.0, 0.5).unwrap();
+        assert_almost_eq!(n.skewness().unwrap(), m.skewness().unwrap(), 1e-8);
+    }
+
+    #[test]
+    fn test_log_of_log_normal_is_normal() {
+        let n = LogOf::new(LogNormal::new(1.0, 2.0).unwrap()).unwrap();
+        let m = Normal::new(1.0, 2.0).unwrap();
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 3.0, 6.0] {
+            assert_almost_eq!(n.pdf(x), m.pdf(x), 1e-15);
+            assert_almost_eq!(n.cdf(x), m.cdf(x), 1e-15);
+        }
+        assert_almost_eq!(n.inverse_cdf(0.3), m.inverse_cdf(0.3), 1e-14);
+        assert_eq!(n.min(), f64::NEG_INFINITY);
+        assert_almost_eq!(n.mean().unwrap(), 1.0, 1e-10);
+        assert_almost_eq!(n.variance().unwrap(), 4.0, 1e-9);
+        assert_almost_eq!(n.skewness().unwrap(), 0.0, 1e-9);
+        assert_almost_eq!(n.entropy().unwrap(), m.entropy().unwrap(), 1e-10);
+    }
+
+    #[test]
+    fn test_log_of_exp_is_gumbel_min() {
+        // ln of a unit exponential is a minimum Gumbel with mean -γ
+        let n = LogOf::new(Exp::new(1.0).unwrap()).unwrap();
+        assert_almost_eq!(n.mean().unwrap(), -0.5772156649015329, 1e-10);
+        assert_almost_eq!(n.variance().unwrap(), f64::consts::PI.powi(2) / 6.0, 1e-9);
+        assert_almost_eq!(n.cdf(0.0), 1.0 - (-1.0f64).exp(), 1e-15);
+    }
+
+    #[test]
+    fn test_scaled_negative_exponential() {
+        let n = Scaled::new(Exp::new(2.0).unwrap(), -1.0).unwrap();
+        assert_eq!(n.min(), f64::NEG_INFINITY);
+        assert_eq!(n.max(), 0.0);
+        assert_eq!(n.cdf(0.0), 1.0);
+        assert_eq!(n.cdf(1.0), 1.0);
+        assert_eq!(n.sf(0.5), 0.0);
+        assert_almost_eq!(n.cdf(-1.0), (-2.0f64).exp(), 1e-15);
+        assert_almost_eq!(n.sf(-1.0), 1.0 - (-2.0f64).exp(), 1e-15);
+        assert_almost_eq!(n.pdf(-1.0), 2.0 * (-2.0f64).exp(), 1e-15);
+        assert_eq!(n.pdf(1.0), 0.0);
+        assert_almost_eq!(n.inverse_cdf(n.cdf(-0.7)), -0.7, 1e-14);
+        assert_almost_eq!(n.inverse_cdf(0.25), -(4.0f64).ln() / 2.0, 1e-15);
+        assert_eq!(n.mean().unwrap(), -0.5);
+        assert_eq!(n.skewness().unwrap(), -2.0);
+        assert_almost_eq!(n.median(), -(2.0f64).ln() / 2.0, 1e-15);
+    }
+
+    #[test]
+    fn test_affine_moments() {
+        let d = Exp::new(0.5).unwrap();
+        let n = Shifted::new(Scaled::new(d, 3.0).unwrap(), -4.0).unwrap();
+        assert_eq!(n.mean().unwrap(), 3.0 * 2.0 - 4.0);
+        assert_eq!(n.variance().unwrap(), 9.0 * 4.0);
+        assert_eq!(n.std_dev().unwrap(), 6.0);
+        assert_eq!(n.skewness().unwrap(), 2.0);
+        assert_almost_eq!(n.entropy().unwrap(), d.entropy().unwrap() + 3.0f64.ln(), 1e-15);
+        assert_eq!(n.min(), -4.0);
+        assert_almost_eq!(n.cdf(2.0), d.cdf(2.0), 1e-15);
+
+        let s = Shifted::new(Normal::new(1.0, 2.0).unwrap(), 5.0).unwrap();
+        let m = Normal::new(6.0, 2.0).unwrap();
+        for &x in &[-2.0, 3.0, 6.0, 11.0] {
+            assert_almost_eq!(s.pdf(x), m.pdf(x), 1e-15);
+            assert_almost_eq!(s.cdf(x), m.cdf(x), 1e-15);
+        }
+        assert_almost_eq!(s.inverse_cdf(0.8), m.inverse_cdf(0.8), 1e-14);
+        let k = Scaled::new(Normal::new(1.0, 2.0).unwrap(), -3.0).unwrap();
+        let m = Normal::new(-3.0, 6.0).unwrap();
+        for &x in &[-20.0, -3.0, 0.0, 4.0] {
+            assert_almost_eq!(k.pdf(x), m.pdf(x), 1e-15);
+            assert_almost_eq!(k.ln_pdf(x), m.ln_pdf(x), 1e-14);
+            assert_almost_eq!(k.cdf(x), m.cdf(x), 1e-15);
+        }
+        assert_almost_eq!(k.inverse_cdf(0.1), m.inverse_cdf(0.1), 1e-13);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = Scaled::new(Exp::new(1.0).unwrap(), -2.0).unwrap();
+        let e = ExpOf::new(Normal::standard());
+        for _ in 0..1000 {
+            assert!(rand::distributions::Distribution::sample(&n, &mut rng) <= 0.0);
+            assert!(rand::distributions::Distribution::sample(&e, &mut rng) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&Shifted::new(Normal::standard(), 3.0).unwrap(), -2.0, 8.0);
+        test::check_continuous_distribution(&Scaled::new(Normal::standard(), -2.0).unwrap(), -10.0, 10.0);
+        test::check_continuous_distribution(&ExpOf::new(Normal::new(0.0, 0.5).unwrap()), 0.0, 10.0);
+        test::check_continuous_distribution(&LogOf::new(Exp::new(1.0).unwrap()).unwrap(), -10.0, 3.0);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = Shifted::new(Normal::new(1.0, 2.0).unwrap(), 3.0).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(4.0)]);
+        let names: Vec<String> = n.parameters().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["inner.mean", "inner.std_dev", "shift"]);
+        assert!(n.set_parameter("shift", f64::NAN).is_err());
+        assert!(n.set_parameter("inner.std_dev", -1.0).is_err());
+        // the prefix keeps the wrapper's scale apart from the wrapped one's
+        let n = Scaled::new(LogNormal::new(0.0, 1.0).unwrap(), 2.0).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(4.0)]);
+        let rescaled = n.set_parameter("inner.scale", 0.5).unwrap();
+        assert_eq!(rescaled.inner(), &LogNormal::new(0.0, 0.5).unwrap());
+        assert_eq!(rescaled.scale(), 2.0);
+        assert!(n.set_parameter("scale", 0.0).is_err());
+        let n = ExpOf::new(Normal::new(1.0, 2.0).unwrap());
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(4.0)]);
+        assert_eq!(n.n_parameters(), 2);
+        let n = LogOf::new(Exp::new(2.0).unwrap()).unwrap();
+        test::check_parameters(&n, |d| [d.pdf(-1.0), d.pdf(1.0)]);
+        assert!(n.set_parameter("inner.rate", -2.0).is_err());
+    }
+}
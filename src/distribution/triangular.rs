@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{internal, Continuous, ContinuousCDF, LMoments, Moments, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -59,6 +59,23 @@ impl Triangular {
     }
 }
 
+impl Parameterized for Triangular {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![
+            ("min".into(), self.min),
+            ("max".into(), self.max),
+            ("mode".into(), self.mode),
+        ]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Triangular> {
+        match *values {
+            [min, max, mode] => Triangular::new(min, max, mode),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Triangular {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Triangular([{},{}], {})", self.min, self.max, self.mode)
@@ -134,6 +151,36 @@ impl ContinuousCDF<f64, f64> for Triangular {
             0.0
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// triangular distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// if p < (mode - min) / (max - min) {
+    ///     min + sqrt(p * (max - min) * (mode - min))
+    /// } else {
+    ///     max - sqrt((1 - p) * (max - min) * (max - mode))
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        let a = self.min;
+        let b = self.max;
+        let c = self.mode;
+        if p < (c - a) / (b - a) {
+            a + (p * (b - a) * (c - a)).sqrt()
+        } else {
+            b - ((1.0 - p) * (b - a) * (b - c)).sqrt()
+        }
+    }
 }
 
 impl Min<f64> for Triangular {
@@ -316,6 +363,26 @@ fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64, mode: f64)
     }
 }
 
+impl LMoments for Triangular {}
+
+impl Moments for Triangular {
+    /// Returns the raw moment of order `order` of the triangular
+    /// distribution, integrated numerically
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        if order == 0 {
+            return Some(1.0);
+        }
+        internal::expectation(self, |x| x.powi(order as i32))
+    }
+
+    /// Returns the central moment of order `order` of the triangular
+    /// distribution, integrated numerically
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        let mean = self.mean()?;
+        internal::expectation(self, |x| (x - mean).powi(order as i32))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -323,6 +390,7 @@ mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Triangular};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(min: f64, max: f64, mode: f64) -> Triangular {
         let n = Triangular::new(min, max, mode);
@@ -543,4 +611,31 @@ mod tests {
         test::check_continuous_distribution(&try_create(-5.0, 5.0, 0.0), -5.0, 5.0);
         test::check_continuous_distribution(&try_create(-15.0, -2.0, -3.0), -15.0, -2.0);
     }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(1.0, 4.0, 2.0), [1.0, 1.0000000173205081, 2.2679491924311227, 3.9999999741904317]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(0.0, 3.0, 1.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(2.0)]);
+        assert_eq!(n.set_parameter("mode", 4.0), Triangular::new(0.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(min, max, mode) in [(0.0, 1.0, 0.5), (-1.0, 3.0, 0.0), (2.0, 5.0, 5.0), (0.0, 1.0, 0.0)].iter() {
+            test::check_moments(&try_create(min, max, mode), 1e-10);
+        }
+        // the kurtosis of every triangular distribution is 12 / 5
+        let n = try_create(-1.0, 3.0, 0.0);
+        assert_almost_eq!(n.standardized_moment(4).unwrap(), 2.4, 1e-10);
+        // E[X^2] = (a^2 + b^2 + c^2 + ab + ac + bc) / 6 on (0, 1) with mode 0
+        assert_almost_eq!(try_create(0.0, 1.0, 0.0).raw_moment(2).unwrap(), 1.0 / 6.0, 1e-12);
+    }
 }
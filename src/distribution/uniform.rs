@@ -1,4 +1,4 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{internal, Continuous, ContinuousCDF, LMoments, Moments, Parameterized};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::Uniform as RandUniform;
@@ -63,6 +63,19 @@ impl Uniform {
     }
 }
 
+impl Parameterized for Uniform {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("min".into(), self.min), ("max".into(), self.max)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Uniform> {
+        match *values {
+            [min, max] => Uniform::new(min, max),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Uniform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Uni([{},{}])", self.min, self.max)
@@ -260,12 +273,68 @@ impl Continuous<f64, f64> for Uniform {
     }
 }
 
+impl LMoments for Uniform {
+    /// Returns the first `order` L-moments of the continuous uniform
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [(min + max) / 2, (max - min) / 6, 0, 0, ...]
+    /// ```
+    fn l_moments(&self, order: usize) -> Vec<f64> {
+        (1..=order)
+            .map(|r| match r {
+                1 => (self.min + self.max) / 2.0,
+                2 => (self.max - self.min) / 6.0,
+                _ => 0.0,
+            })
+            .collect()
+    }
+}
+
+impl Moments for Uniform {
+    /// Returns the raw moment of order `order` of the uniform distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (max^(r + 1) - min^(r + 1)) / ((r + 1) (max - min))
+    /// ```
+    ///
+    /// evaluated as an expansion around the midpoint, which does not cancel
+    /// when the interval is narrow
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        let midpoint = (self.min + self.max) / 2.0;
+        internal::raw_moment_about(order, midpoint, |k| self.central_moment(k))
+    }
+
+    /// Returns the central moment of order `order` of the uniform
+    /// distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// h^r / (r + 1)
+    /// ```
+    ///
+    /// for even `r` and zero for odd `r`, where `h = (max - min) / 2`
+    fn central_moment(&self, order: u32) -> Option<f64> {
+        if order % 2 == 1 {
+            return Some(0.0);
+        }
+        let half_width = (self.max - self.min) / 2.0;
+        Some(half_width.powi(order as i32) / (order + 1) as f64)
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Uniform};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(min: f64, max: f64) -> Uniform {
         let n = Uniform::new(min, max);
@@ -497,4 +566,32 @@ mod tests {
             .all(|v| (min <= v) && (v < max))
         );
     }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(1.0, 3.0), [1.0, 1.0000000000000002, 2.0, 2.9999999999999998]);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(-1.0, 3.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(4.0)]);
+        assert_eq!(n.set_parameter("max", -2.0), Uniform::new(-1.0, -2.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(min, max) in [(0.0, 1.0), (-3.0, 5.0), (10.0, 10.5)].iter() {
+            let n = try_create(min, max);
+            test::check_moments(&n, 1e-14);
+            test::check_moments_numeric(&n, 6, 1e-9);
+            assert_almost_eq!(n.standardized_moment(4).unwrap(), 1.8, 1e-14);
+        }
+        let n = try_create(1.0, 3.0);
+        // (3^4 - 1) / (4 * 2)
+        assert_almost_eq!(n.raw_moment(3).unwrap(), 10.0, 1e-14);
+    }
 }
@@ -1,5 +1,7 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::distribution::{
+    Continuous, ContinuousCDF, LMoments, Moments, Parameterized, ScoreFunction, TailExpectation,
+};
+use crate::function::{gamma, root};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
@@ -61,6 +63,78 @@ impl Weibull {
         }
     }
 
+    /// Estimates a weibull distribution from the sample `data` by maximum
+    /// likelihood
+    ///
+    /// # Formula
+    ///
+    /// The shape `k` solves
+    ///
+    /// ```text
+    /// Σ x_i^k ln x_i / Σ x_i^k - 1 / k = mean(ln x_i)
+    /// ```
+    ///
+    /// and the scale is `mean(x_i^k)^(1 / k)`
+    ///
+    /// # Remarks
+    ///
+    /// The equation is the score of the shape from `ScoreFunction`, summed
+    /// over `data` at the scale `mean(x_i^k)^(1 / k)` that zeroes the score
+    /// of the scale, and is solved with Brent's method
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, if an entry is
+    /// not positive or is `NaN` or infinite, or if all entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Weibull;
+    ///
+    /// let data = [1.2, 0.4, 2.5, 3.1, 0.9, 1.7];
+    /// let n = Weibull::fit_mle(&data).unwrap();
+    /// let mean_power = data.iter().map(|x| x.powf(n.shape())).sum::<f64>() / 6.0;
+    /// assert!((n.scale() - mean_power.powf(1.0 / n.shape())).abs() < 1e-12);
+    /// ```
+    pub fn fit_mle(data: &[f64]) -> Result<Weibull> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|&x| !x.is_finite() || x <= 0.0) {
+            return Err(StatsError::SpecialCase("data must be positive and finite"));
+        }
+        // the equation is invariant to scaling, and scaling by the maximum
+        // keeps the powers from overflowing
+        let largest = data.iter().fold(0.0, |acc: f64, &x| acc.max(x));
+        let scaled: Vec<f64> = data.iter().map(|&x| x / largest).collect();
+        if scaled.iter().all(|&x| x == 1.0) {
+            return Err(StatsError::SpecialCase("data must not be constant"));
+        }
+        // the scaled maximum is 1, so the profile scale never vanishes
+        let profile_scale = |k: f64| {
+            let mean_power = scaled.iter().map(|x| x.powf(k)).sum::<f64>() / scaled.len() as f64;
+            mean_power.powf(1.0 / k)
+        };
+        // decreasing in the shape, from +∞ at zero to a negative limit
+        let profile_score = |k: f64| {
+            Weibull::new(k, profile_scale(k))
+                .unwrap()
+                .score_sum(&scaled)[0]
+        };
+        let (mut lower, mut upper) = (0.5, 2.0);
+        while profile_score(lower) < 0.0 {
+            lower /= 2.0;
+        }
+        while profile_score(upper) > 0.0 {
+            upper *= 2.0;
+        }
+        let shape = root::brent(profile_score, lower, upper, 1e-14 * upper)?;
+        Weibull::new(shape, largest * profile_scale(shape))
+    }
+
     /// Returns the shape of the weibull distribution
     ///
     /// # Examples
@@ -90,6 +164,19 @@ impl Weibull {
     }
 }
 
+impl Parameterized for Weibull {
+    fn parameters(&self) -> Vec<(String, f64)> {
+        vec![("shape".into(), self.shape), ("scale".into(), self.scale)]
+    }
+
+    fn with_parameters(&self, values: &[f64]) -> Result<Weibull> {
+        match *values {
+            [shape, scale] => Weibull::new(shape, scale),
+            _ => Err(StatsError::ContainersMustBeSameLength),
+        }
+    }
+}
+
 impl std::fmt::Display for Weibull {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Weibull({},{})", self.scale, self.shape)
@@ -139,6 +226,28 @@ impl ContinuousCDF<f64, f64> for Weibull {
             (-x.powf(self.shape) * self.scale_pow_shape_inv).exp()
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// weibull distribution at `p`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ (-ln(1 - p))^(1 / k)
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the scale, with `ln(1 - p)`
+    /// evaluated as `ln_1p(-p)`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1], was {}", p);
+        }
+        self.scale * (-(-p).ln_1p()).powf(1.0 / self.shape)
+    }
 }
 
 impl Min<f64> for Weibull {
@@ -330,12 +439,83 @@ impl Continuous<f64, f64> for Weibull {
     }
 }
 
+impl ScoreFunction<f64> for Weibull {
+    const NUM_PARAMS: usize = 2;
+
+    /// Calculates the gradient of the log probability density function for
+    /// the weibull distribution at `x` with respect to `(k, λ)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// (1 / k + (1 - t) y, k (t - 1) / λ)
+    /// ```
+    ///
+    /// where `k` is the shape, `λ` the scale, `y = ln(x / λ)` and
+    /// `t = (x / λ)^k`
+    fn ln_pdf_grad(&self, x: f64) -> Vec<f64> {
+        let y = (x / self.scale).ln();
+        let t = (self.shape * y).exp();
+        vec![
+            1.0 / self.shape + (1.0 - t) * y,
+            self.shape * (t - 1.0) / self.scale,
+        ]
+    }
+
+    /// Calculates the Hessian of the log probability density function for
+    /// the weibull distribution at `x` with respect to `(k, λ)`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [[-1 / k^2 - t y^2, (t (1 + k y) - 1) / λ],
+    ///  [(t (1 + k y) - 1) / λ, k (1 - (k + 1) t) / λ^2]]
+    /// ```
+    ///
+    /// where `k` is the shape, `λ` the scale, `y = ln(x / λ)` and
+    /// `t = (x / λ)^k`
+    fn ln_pdf_hessian(&self, x: f64) -> Option<Vec<Vec<f64>>> {
+        let (k, scale) = (self.shape, self.scale);
+        let y = (x / scale).ln();
+        let t = (k * y).exp();
+        let cross = (t * (1.0 + k * y) - 1.0) / scale;
+        Some(vec![
+            vec![-1.0 / (k * k) - t * y * y, cross],
+            vec![cross, k * (1.0 - (k + 1.0) * t) / (scale * scale)],
+        ])
+    }
+}
+
+impl LMoments for Weibull {}
+
+impl Moments for Weibull {
+    /// Returns the raw moment of order `order` of the weibull distribution
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// λ^r Γ(1 + r / k)
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the scale
+    fn raw_moment(&self, order: u32) -> Option<f64> {
+        if order == 0 {
+            return Some(1.0);
+        }
+        let r = order as f64;
+        Some(self.scale.powi(order as i32) * gamma::gamma(1.0 + r / self.shape))
+    }
+}
+
+impl TailExpectation for Weibull {}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Weibull};
     use crate::distribution::internal::*;
+    use crate::distribution::Parameterized;
 
     fn try_create(shape: f64, scale: f64) -> Weibull {
         let n = Weibull::new(shape, scale);
@@ -530,4 +710,90 @@ mod tests {
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(1.0, 0.2), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_score() {
+        let make = |p: &[f64]| Weibull::new(p[0], p[1]).unwrap();
+        let xs = [0.05, 0.5, 1.0, 2.0, 6.0];
+        test::check_score(make, |d: &Weibull, x| d.ln_pdf(x), &[1.0, 1.0], &xs);
+        test::check_score(make, |d: &Weibull, x| d.ln_pdf(x), &[0.6, 2.5], &xs);
+        test::check_score(make, |d: &Weibull, x| d.ln_pdf(x), &[3.5, 1.2], &xs);
+    }
+
+    #[test]
+    fn test_inverse_cdf_tails() {
+        // reference values computed with mpmath at the quantiles of
+        // test::TAIL_PROBABILITIES
+        test::check_inverse_cdf_tails(&try_create(1.5, 2.0), [2.0e-200, 4.3088693800637675e-11, 1.5664395375493027, 22.101964792433338]);
+    }
+
+    #[test]
+    fn test_l_moments() {
+        use crate::distribution::LMoments;
+        // λ2 = λ (1 - 2^(-1/k)) Γ(1 + 1/k), computed with mpmath
+        let n = try_create(1.5, 2.0);
+        let l = n.l_moments(3);
+        assert_almost_eq!(l[0], n.mean().unwrap(), 1e-12);
+        assert_almost_eq!(l[1], 0.66810278861947210424, 1e-12);
+        // a weibull distribution with unit shape is exponential
+        assert_almost_eq!(try_create(1.0, 3.0).l_moments(3)[2], 1.0 / 3.0, 1e-10);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let n = try_create(1.5, 2.0);
+        test::check_parameters(&n, |d| [d.pdf(0.5), d.pdf(3.0)]);
+        assert_eq!(n.set_parameter("shape", 0.0), Weibull::new(0.0, 2.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        use crate::distribution::Moments;
+        for &(shape, scale) in [(1.0, 1.0), (1.5, 2.0), (5.0, 0.5)].iter() {
+            let n = try_create(shape, scale);
+            test::check_moments(&n, 1e-12);
+            test::check_moments_numeric(&n, 5, 1e-8);
+        }
+        // a shape of one is the exponential distribution
+        let n = try_create(1.0, 0.5);
+        assert_almost_eq!(n.raw_moment(3).unwrap(), 0.75, 1e-14);
+        assert_almost_eq!(n.standardized_moment(4).unwrap(), 9.0, 1e-12);
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        use crate::distribution::ScoreFunction;
+        use rand::distributions::Distribution as _;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        let mut rng = StdRng::seed_from_u64(293);
+        for &(shape, scale) in [(0.5, 2.0), (1.5, 1e-3), (8.0, 1e5)].iter() {
+            let data: Vec<f64> = try_create(shape, scale).sample_iter(&mut rng).take(20000).collect();
+            let n = Weibull::fit_mle(&data).unwrap();
+            assert_almost_eq!(n.shape() / shape, 1.0, 0.03);
+            assert_almost_eq!(n.scale() / scale, 1.0, 0.03);
+            // the score equations hold at the estimate
+            let score = n.score_sum(&data);
+            assert_almost_eq!(score[0] / 20000.0, 0.0, 1e-10);
+            assert_almost_eq!(score[1] * n.scale() / 20000.0, 0.0, 1e-10);
+        }
+        assert!(Weibull::fit_mle(&[1.0]).is_err());
+        assert!(Weibull::fit_mle(&[3.0, 3.0]).is_err());
+        assert!(Weibull::fit_mle(&[1.0, -1.0]).is_err());
+    }
+
+    #[test]
+    fn test_tail_expectation() {
+        use crate::distribution::TailExpectation;
+        // the numerical defaults against the exponential shape one
+        let n = try_create(1.0, 2.0);
+        for &u in [0.0, 1.0, 5.0].iter() {
+            assert_almost_eq!(n.mean_excess(u), 2.0, 1e-9);
+        }
+        assert_almost_eq!(n.partial_expectation(0.0, f64::INFINITY), 2.0, 1e-9);
+        // a shape above one has a light tail and a decreasing mean excess
+        let n = try_create(2.0, 1.0);
+        assert!(n.mean_excess(2.0) < n.mean_excess(1.0));
+        assert_almost_eq!(n.partial_expectation(0.0, f64::INFINITY), n.mean().unwrap(), 1e-9);
+    }
 }
@@ -3,7 +3,6 @@
 
 use crate::error::StatsError;
 use crate::function::gamma;
-use crate::is_zero;
 use crate::prec;
 use crate::Result;
 use std::f64;
@@ -118,7 +117,7 @@ pub fn checked_beta_reg(a: f64, b: f64, x: f64) -> Result<f64> {
     } else if !(0.0..=1.0).contains(&x) {
         Err(StatsError::ArgIntervalIncl("x", 0.0, 1.0))
     } else {
-        let bt = if is_zero(x) || ulps_eq!(x, 1.0) {
+        let bt = if x == 0.0 || x == 1.0 {
             0.0
         } else {
             (gamma::ln_gamma(a + b) - gamma::ln_gamma(a) - gamma::ln_gamma(b)
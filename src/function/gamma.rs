@@ -391,6 +391,43 @@ pub fn digamma(x: f64) -> f64 {
     result
 }
 
+/// Computes the Trigamma function which is defined as the derivative of
+/// the digamma function, using the recurrence `ψ1(x) = ψ1(x + 1) + 1 / x^2`
+/// to shift the argument above `12` followed by the asymptotic expansion,
+/// and the reflection formula for negative arguments
+pub fn trigamma(x: f64) -> f64 {
+    let c = 12.0;
+    let b2 = 1.0 / 6.0;
+    let b4 = 1.0 / 30.0;
+    let b6 = 1.0 / 42.0;
+    let b8 = 1.0 / 30.0;
+    let b10 = 5.0 / 66.0;
+    let b12 = 691.0 / 2730.0;
+
+    if x == f64::NEG_INFINITY || x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 && ulps_eq!(x.floor(), x) {
+        return f64::INFINITY;
+    }
+    if x < 0.0 {
+        let s = (f64::consts::PI * x).sin();
+        return f64::consts::PI * f64::consts::PI / (s * s) - trigamma(1.0 - x);
+    }
+
+    let mut result = 0.0;
+    let mut z = x;
+    while z < c {
+        result += 1.0 / (z * z);
+        z += 1.0;
+    }
+
+    let r = 1.0 / z;
+    let r2 = r * r;
+    result += r + 0.5 * r2;
+    result + r * r2 * (b2 - r2 * (b4 - r2 * (b6 - r2 * (b8 - r2 * (b10 - r2 * b12)))))
+}
+
 pub fn inv_digamma(x: f64) -> f64 {
     if x.is_nan() {
         return f64::NAN;
@@ -786,6 +823,24 @@ mod tests {
         assert_almost_eq!(super::digamma(10.1), 2.2622143570941481235561593642219403924532310597356171, 1e-14);
     }
 
+    #[test]
+    fn test_trigamma() {
+        assert!(super::trigamma(f64::NAN).is_nan());
+        assert_eq!(super::trigamma(0.0), f64::INFINITY);
+        assert_eq!(super::trigamma(-2.0), f64::INFINITY);
+        assert_almost_eq!(super::trigamma(-1.5), 9.37924664498912375386169, 1e-13);
+        assert_almost_eq!(super::trigamma(-0.5), 8.934802200544679309417246, 1e-13);
+        assert_almost_eq!(super::trigamma(0.001), 1000001.64253319582734467, 1e-8);
+        assert_almost_eq!(super::trigamma(0.1), 101.433299150792747704652, 1e-12);
+        assert_almost_eq!(super::trigamma(1.0), 1.644934066848226436472415, 1e-14);
+        assert_almost_eq!(super::trigamma(1.5), 0.9348022005446793094172455, 1e-14);
+        assert_almost_eq!(super::trigamma(2.0), 0.6449340668482264364724152, 1e-14);
+        assert_almost_eq!(super::trigamma(3.5), 0.3303577561002348649728011, 1e-14);
+        assert_almost_eq!(super::trigamma(5.0), 0.2213229557371153253613041, 1e-14);
+        assert_almost_eq!(super::trigamma(10.1), 0.1040728312974960444904786, 1e-14);
+        assert_almost_eq!(super::trigamma(100.0), 0.01005016666333357139524567, 1e-15);
+    }
+
     #[test]
     fn test_inv_digamma() {
         assert!(super::inv_digamma(f64::NAN).is_nan());
@@ -0,0 +1,316 @@
+//! Provides adaptive numerical quadrature used internally wherever a
+//! closed form is not available
+
+use std::f64;
+
+/// Maximum number of interval bisections performed by `integrate`
+const MAX_SUBDIVISIONS: usize = 2000;
+
+/// Abscissae of the 15-point Kronrod rule on `[-1, 1]`, the odd-indexed
+/// ones being the abscissae of the embedded 7-point Gauss rule
+const XGK: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.000000000000000000000000000000000,
+];
+
+/// Weights of the 15-point Kronrod rule
+const WGK: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+/// Weights of the embedded 7-point Gauss rule
+const WG: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+/// Applies the Gauss-Kronrod 7/15 rule to `f` on `[a, b]`, returning the
+/// Kronrod estimate and the absolute difference to the Gauss estimate
+pub(crate) fn gauss_kronrod<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> (f64, f64) {
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let fc = f(center);
+    let mut kronrod = fc * WGK[7];
+    let mut gauss = fc * WG[3];
+    for (j, (&x, &w)) in XGK.iter().zip(WGK.iter()).take(7).enumerate() {
+        let dx = half * x;
+        let sum = f(center - dx) + f(center + dx);
+        kronrod += w * sum;
+        if j % 2 == 1 {
+            gauss += WG[j / 2] * sum;
+        }
+    }
+    (kronrod * half, ((kronrod - gauss) * half).abs())
+}
+
+/// Positive abscissae of the 16-point Gauss-Legendre rule on `[-1, 1]`
+const XGL: [f64; 8] = [
+    0.989400934991649932596154173450333,
+    0.944575023073232576077988415534608,
+    0.865631202387831743880467897712393,
+    0.755404408355003033895101194847442,
+    0.617876244402643748446671764048791,
+    0.458016777657227386342419442983578,
+    0.281603550779258913230460501460496,
+    0.095012509837637440185319335424958,
+];
+
+/// Weights of the 16-point Gauss-Legendre rule
+const WGL: [f64; 8] = [
+    0.027152459411754094851780572456018,
+    0.062253523938647892862843836994378,
+    0.095158511682492784809925107602246,
+    0.124628971255533872052476282192016,
+    0.149595988816576732081501730547479,
+    0.169156519395002538189312079030360,
+    0.182603415044923588866763667969220,
+    0.189450610455068496285396723208283,
+];
+
+/// Applies the 16-point Gauss-Legendre rule to `f` on `[a, b]`, which is
+/// exact for polynomials of degree up to 31
+pub(crate) fn gauss_legendre<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> f64 {
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let sum: f64 = XGL
+        .iter()
+        .zip(WGL.iter())
+        .map(|(&x, &w)| w * (f(center - half * x) + f(center + half * x)))
+        .sum();
+    sum * half
+}
+
+/// Sums the 16-point Gauss-Legendre rule over `panels` subintervals of
+/// equal width partitioning `[a, b]`. Unlike `integrate` the number of
+/// evaluations of `f` is fixed, which suits smooth integrands evaluated
+/// inside other quadratures.
+pub(crate) fn composite_gauss_legendre<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+    panels: usize,
+) -> f64 {
+    let width = (b - a) / panels as f64;
+    (0..panels)
+        .map(|i| {
+            let lo = a + i as f64 * width;
+            gauss_legendre(f, lo, lo + width)
+        })
+        .sum()
+}
+
+/// A panel of an adaptive quadrature as `(lower, upper, value, error)`
+pub(crate) type Panel = (f64, f64, f64, f64);
+
+/// Partitions the finite interval `[a, b]` by globally adaptive
+/// Gauss-Kronrod quadrature, repeatedly bisecting the panel with the
+/// largest error estimate until the total error estimate falls below
+/// `tol * max(1, |result|)`. The panels are returned in no particular order.
+pub(crate) fn adaptive_panels<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64, tol: f64) -> Vec<Panel> {
+    let (value, error) = gauss_kronrod(f, a, b);
+    let mut panels = vec![(a, b, value, error)];
+    let mut total = value;
+    let mut total_error = error;
+    for _ in 0..MAX_SUBDIVISIONS {
+        if total_error <= tol * total.abs().max(1.0) || !total_error.is_finite() {
+            break;
+        }
+        let worst = panels
+            .iter()
+            .enumerate()
+            .max_by(|x, y| x.1 .3.partial_cmp(&y.1 .3).unwrap())
+            .map(|(i, _)| i)
+            .unwrap();
+        let (lo, hi, value, error) = panels.swap_remove(worst);
+        let mid = 0.5 * (lo + hi);
+        let left = gauss_kronrod(f, lo, mid);
+        let right = gauss_kronrod(f, mid, hi);
+        total += left.0 + right.0 - value;
+        total_error += left.1 + right.1 - error;
+        panels.push((lo, mid, left.0, left.1));
+        panels.push((mid, hi, right.0, right.1));
+    }
+    panels
+}
+
+/// Integrates `f` over the finite interval `[a, b]`, see `adaptive_panels`
+pub(crate) fn integrate_finite<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64, tol: f64) -> f64 {
+    adaptive_panels(f, a, b, tol).iter().map(|p| p.2).sum()
+}
+
+/// Change of variables mapping an interval with possibly infinite bounds
+/// onto a finite one, through `x = a + t / (1 - t)`, `x = b - (1 - t) / t`
+/// or `x = t / (1 - t^2)` depending on which bounds are infinite
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) enum Substitution {
+    /// Both bounds are finite and `x = t`
+    Identity(f64, f64),
+    /// `[a, ∞)` mapped from `[0, 1)`
+    Upper(f64),
+    /// `(-∞, b]` mapped from `(0, 1]`
+    Lower(f64),
+    /// `(-∞, ∞)` mapped from `(-1, 1)`
+    Both,
+}
+
+impl Substitution {
+    /// Returns the substitution for the interval `[a, b]` with `a < b`
+    pub(crate) fn new(a: f64, b: f64) -> Substitution {
+        match (a.is_finite(), b.is_finite()) {
+            (true, true) => Substitution::Identity(a, b),
+            (true, false) => Substitution::Upper(a),
+            (false, true) => Substitution::Lower(b),
+            (false, false) => Substitution::Both,
+        }
+    }
+
+    /// Returns the finite interval of the substituted variable `t`
+    pub(crate) fn range(self) -> (f64, f64) {
+        match self {
+            Substitution::Identity(a, b) => (a, b),
+            Substitution::Upper(_) | Substitution::Lower(_) => (0.0, 1.0),
+            Substitution::Both => (-1.0, 1.0),
+        }
+    }
+
+    /// Maps `t` to `x`
+    pub(crate) fn to_x(self, t: f64) -> f64 {
+        match self {
+            Substitution::Identity(..) => t,
+            Substitution::Upper(a) => a + t / (1.0 - t),
+            Substitution::Lower(b) => b - (1.0 - t) / t,
+            Substitution::Both => t / (1.0 - t * t),
+        }
+    }
+
+    /// Maps `x` to `t`, the inverse of `to_x`
+    pub(crate) fn to_t(self, x: f64) -> f64 {
+        match self {
+            Substitution::Identity(..) => x,
+            Substitution::Upper(a) => {
+                let d = x - a;
+                if d.is_infinite() {
+                    1.0
+                } else {
+                    d / (1.0 + d)
+                }
+            }
+            Substitution::Lower(b) => 1.0 / (1.0 + (b - x)),
+            Substitution::Both => {
+                if x.is_infinite() {
+                    x.signum()
+                } else {
+                    2.0 * x / (1.0 + (1.0 + 4.0 * x * x).sqrt())
+                }
+            }
+        }
+    }
+
+    /// Returns `dx / dt` at `t`
+    pub(crate) fn jacobian(self, t: f64) -> f64 {
+        match self {
+            Substitution::Identity(..) => 1.0,
+            Substitution::Upper(_) => {
+                let u = 1.0 - t;
+                1.0 / (u * u)
+            }
+            Substitution::Lower(_) => 1.0 / (t * t),
+            Substitution::Both => {
+                let u = 1.0 - t * t;
+                (1.0 + t * t) / (u * u)
+            }
+        }
+    }
+}
+
+/// Integrates `f` over `[a, b]` where either bound may be infinite.
+/// Infinite ranges are mapped onto finite ones by a `Substitution`.
+///
+/// # Remarks
+///
+/// Returns `0` if `a == b` and the negated integral if `a > b`. The
+/// integrand is never evaluated at the interval endpoints.
+pub(crate) fn integrate<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+    if a > b {
+        return -integrate(f, b, a, tol);
+    }
+    let sub = Substitution::new(a, b);
+    let (lo, hi) = sub.range();
+    match sub {
+        Substitution::Identity(..) => integrate_finite(&f, lo, hi, tol),
+        _ => integrate_finite(&|t| f(sub.to_x(t)) * sub.jacobian(t), lo, hi, tol),
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gauss_legendre() {
+        assert_almost_eq!(gauss_legendre(&|x: f64| x.powi(31) + x.powi(30), -1.0, 1.0), 2.0 / 31.0, 1e-15);
+        assert_almost_eq!(gauss_legendre(&|x: f64| x.powi(3), 1.0, 2.0), 3.75, 1e-14);
+        assert_almost_eq!(composite_gauss_legendre(&f64::exp, 0.0, 10.0, 10), 10f64.exp() - 1.0, 1e-10);
+        assert_almost_eq!(composite_gauss_legendre(&f64::sin, 0.0, f64::consts::PI, 1), 2.0, 1e-15);
+    }
+
+    #[test]
+    fn test_integrate_finite() {
+        assert_almost_eq!(integrate(|x| x * x, 0.0, 3.0, 1e-12), 9.0, 1e-12);
+        assert_almost_eq!(integrate(f64::sin, 0.0, f64::consts::PI, 1e-12), 2.0, 1e-12);
+        assert_almost_eq!(integrate(|x| x * x, 3.0, 0.0, 1e-12), -9.0, 1e-12);
+        assert_eq!(integrate(|x| x, 1.0, 1.0, 1e-12), 0.0);
+        // integrable endpoint singularity
+        assert_almost_eq!(integrate(|x: f64| 1.0 / x.sqrt(), 0.0, 1.0, 1e-10), 2.0, 1e-8);
+    }
+
+    #[test]
+    fn test_integrate_infinite() {
+        let gauss = |x: f64| (-x * x / 2.0).exp();
+        let expected = (2.0 * f64::consts::PI).sqrt();
+        assert_almost_eq!(integrate(gauss, f64::NEG_INFINITY, f64::INFINITY, 1e-12), expected, 1e-11);
+        assert_almost_eq!(integrate(gauss, 0.0, f64::INFINITY, 1e-12), expected / 2.0, 1e-11);
+        assert_almost_eq!(integrate(gauss, f64::NEG_INFINITY, 0.0, 1e-12), expected / 2.0, 1e-11);
+        assert_almost_eq!(integrate(|x: f64| (-x).exp(), 1.0, f64::INFINITY, 1e-12), (-1.0f64).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_substitution_round_trip() {
+        let subs = [
+            Substitution::new(-1.0, 2.0),
+            Substitution::new(1.5, f64::INFINITY),
+            Substitution::new(f64::NEG_INFINITY, -3.0),
+            Substitution::new(f64::NEG_INFINITY, f64::INFINITY),
+        ];
+        for sub in &subs {
+            let (lo, hi) = sub.range();
+            for i in 1..20 {
+                let t = lo + (hi - lo) * i as f64 / 20.0;
+                assert_almost_eq!(sub.to_t(sub.to_x(t)), t, 1e-14);
+            }
+        }
+        assert_eq!(subs[3].to_t(f64::INFINITY), 1.0);
+        assert_eq!(subs[3].to_t(f64::NEG_INFINITY), -1.0);
+        assert_eq!(subs[1].to_t(f64::INFINITY), 1.0);
+        assert_eq!(subs[2].to_t(f64::NEG_INFINITY), 0.0);
+    }
+}
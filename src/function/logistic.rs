@@ -31,6 +31,31 @@ pub fn checked_logit(p: f64) -> Result<f64> {
     }
 }
 
+/// Computes `ln(1 + e^x)`, the softplus function, without overflow for
+/// large `x` or loss of precision for very negative `x`
+///
+/// # Remarks
+///
+/// The logarithms of the logistic function and its complement are
+/// `ln σ(x) = -log1p_exp(-x)` and `ln(1 - σ(x)) = -log1p_exp(x)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::logistic::log1p_exp;
+///
+/// assert_eq!(log1p_exp(0.0), 2f64.ln());
+/// assert_eq!(log1p_exp(1000.0), 1000.0);
+/// assert_eq!(log1p_exp(-50.0), (-50f64).exp());
+/// ```
+pub fn log1p_exp(x: f64) -> f64 {
+    if x > 0.0 {
+        x + (-x).exp().ln_1p()
+    } else {
+        x.exp().ln_1p()
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -83,4 +108,18 @@ mod tests {
     fn test_checked_logit_p_gt_1() {
         assert!(super::checked_logit(2.0).is_err());
     }
+
+    #[test]
+    fn test_log1p_exp() {
+        for &x in [-30.0, -2.5, -1e-3, 0.0, 0.7, 5.0, 30.0].iter() {
+            assert_almost_eq!(super::log1p_exp(x), (1.0 + f64::exp(x)).ln(), 1e-14);
+        }
+        assert_eq!(super::log1p_exp(800.0), 800.0);
+        assert_eq!(super::log1p_exp(-800.0), 0.0);
+        assert_eq!(super::log1p_exp(f64::INFINITY), f64::INFINITY);
+        assert_eq!(super::log1p_exp(f64::NEG_INFINITY), 0.0);
+        // ln σ(x) + ln(1 - σ(x)) agree with the logistic function
+        assert_almost_eq!(-super::log1p_exp(-2.0), super::logistic(2.0).ln(), 1e-15);
+        assert_almost_eq!(-super::log1p_exp(2.0), (1.0 - super::logistic(2.0)).ln(), 1e-14);
+    }
 }
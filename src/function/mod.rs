@@ -8,4 +8,8 @@ pub mod exponential;
 pub mod factorial;
 pub mod gamma;
 pub mod harmonic;
+pub(crate) mod integrate;
 pub mod logistic;
+pub mod normal;
+pub mod optimize;
+pub mod root;
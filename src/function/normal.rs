@@ -0,0 +1,349 @@
+//! Provides the [Mills ratio](https://en.wikipedia.org/wiki/Mills_ratio) of
+//! the standard normal distribution and the moments of the truncated
+//! standard normal distribution built on it, computed stably far in the
+//! tails where the density and survival function underflow
+
+use crate::consts;
+use std::f64;
+
+/// Arguments from which the Mills ratio is computed by continued fraction
+const CONTINUED_FRACTION_MIN: f64 = 2.0;
+
+/// Number of terms of the continued fraction, enough for double precision
+/// from `CONTINUED_FRACTION_MIN` on
+const CONTINUED_FRACTION_TERMS: usize = 100;
+
+/// Returns the tails `t_1` and `t_2` of Laplace's continued fraction of the
+/// Mills ratio at `x >= CONTINUED_FRACTION_MIN`, where
+/// `R(x) = 1 / (x + t_1)`, `t_1 = 1 / (x + t_2)` and
+/// `t_k = k / (x + t_(k + 1))`
+fn continued_fraction_tails(x: f64) -> (f64, f64) {
+    let mut tail = 0.0;
+    for k in (2..=CONTINUED_FRACTION_TERMS).rev() {
+        tail = k as f64 / (x + tail);
+    }
+    (1.0 / (x + tail), tail)
+}
+
+/// Computes Marsaglia's series `S(x) = Σ x^(2n + 1) / (2n + 1)!!`, with
+/// `Φ(x) = 1 / 2 + φ(x) S(x)`
+fn marsaglia_series(x: f64) -> f64 {
+    let (mut term, mut sum) = (x, x);
+    let mut n = 0.0;
+    while term.abs() > f64::EPSILON * sum.abs() {
+        n += 1.0;
+        term *= x * x / (2.0 * n + 1.0);
+        sum += term;
+    }
+    sum
+}
+
+/// Computes the standard normal density at `x`
+fn pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / consts::SQRT_2PI
+}
+
+/// Computes the standard normal survival function at `x` as `φ(x) R(x)`
+fn sf(x: f64) -> f64 {
+    if x >= 0.0 {
+        pdf(x) * mills_ratio(x)
+    } else {
+        1.0 - pdf(x) * mills_ratio(-x)
+    }
+}
+
+/// Computes the Mills ratio of the standard normal distribution at `x`,
+/// the ratio of the survival function to the density
+///
+/// # Formula
+///
+/// ```text
+/// R(x) = (1 - Φ(x)) / φ(x) = 1 / (x + 1 / (x + 2 / (x + 3 / (x + ...))))
+/// ```
+///
+/// # Remarks
+///
+/// The ratio is computed from Laplace's continued fraction from `2` on,
+/// from Marsaglia's series for `Φ(x) - 1 / 2` on `[0, 2)` and for negative
+/// `x` from `R(x) = 1 / φ(x) - R(-x)`, so it stays accurate to about
+/// `1e-15` where both the survival function and the density underflow; it
+/// tends to `1 / x` as `x` grows. It overflows to
+/// `f64::INFINITY` below about `-37.6`. Returns `f64::NAN` if `x` is
+/// `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::normal::mills_ratio;
+///
+/// assert!((mills_ratio(0.0) - (std::f64::consts::PI / 2.0).sqrt()).abs() < 1e-15);
+/// assert!((mills_ratio(1e8) - 1e-8).abs() < 1e-23);
+/// ```
+pub fn mills_ratio(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x >= CONTINUED_FRACTION_MIN {
+        let (tail, _) = continued_fraction_tails(x);
+        1.0 / (x + tail)
+    } else if x >= 0.0 {
+        0.5 * consts::SQRT_2PI * (0.5 * x * x).exp() - marsaglia_series(x)
+    } else {
+        consts::SQRT_2PI * (0.5 * x * x).exp() - mills_ratio(-x)
+    }
+}
+
+/// Computes the inverse Mills ratio of the standard normal distribution at
+/// `x`, the hazard rate `φ(x) / (1 - Φ(x))`, which is also the mean of the
+/// standard normal distribution truncated to `(x, ∞)`
+///
+/// # Remarks
+///
+/// This is `1 / mills_ratio(x)`, computed without overflow for negative
+/// `x`, where it tends to `0`, and tending to `x` as `x` grows. Returns
+/// `f64::NAN` if `x` is `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::normal::inverse_mills_ratio;
+///
+/// assert!((inverse_mills_ratio(0.0) - (2.0 / std::f64::consts::PI).sqrt()).abs() < 1e-15);
+/// assert!(inverse_mills_ratio(-40.0) < 1e-300);
+/// ```
+pub fn inverse_mills_ratio(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x >= CONTINUED_FRACTION_MIN {
+        let (tail, _) = continued_fraction_tails(x);
+        x + tail
+    } else if x >= 0.0 {
+        1.0 / mills_ratio(x)
+    } else {
+        pdf(x) / sf(x)
+    }
+}
+
+/// Computes the partial expectation `E[Z | a < Z < b]` of a standard normal
+/// `Z`, the mean of the standard normal distribution truncated to `(a, b)`
+///
+/// # Formula
+///
+/// ```text
+/// (φ(a) - φ(b)) / (Φ(b) - Φ(a))
+/// ```
+///
+/// # Remarks
+///
+/// Either bound may be infinite, `E[Z | Z > a]` being
+/// `inverse_mills_ratio(a)`. Returns `f64::NAN` if `a > b` or if either
+/// bound is `f64::NAN`, and `a` if `a == b`. See `truncated_normal_moments`
+/// for the accuracy.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::normal::normal_partial_expectation;
+///
+/// // the mean of a half-normal distribution
+/// let mean = normal_partial_expectation(0.0, f64::INFINITY);
+/// assert!((mean - (2.0 / std::f64::consts::PI).sqrt()).abs() < 1e-15);
+/// assert_eq!(normal_partial_expectation(-1.0, 1.0), 0.0);
+/// ```
+pub fn normal_partial_expectation(a: f64, b: f64) -> f64 {
+    truncated_normal_moments(a, b).0
+}
+
+/// Computes the mean and variance of the standard normal distribution
+/// truncated to `(a, b)`
+///
+/// # Formula
+///
+/// ```text
+/// m = (φ(a) - φ(b)) / Z
+/// v = 1 + (a φ(a) - b φ(b)) / Z - m^2
+/// ```
+///
+/// where `Z = Φ(b) - Φ(a)`. The moments of a normal distribution with mean
+/// `μ` and standard deviation `σ` truncated to `(l, u)` are `μ + σ m` and
+/// `σ^2 v` with `a = (l - μ) / σ` and `b = (u - μ) / σ`.
+///
+/// # Remarks
+///
+/// An interval in the upper tail is handled through the Mills ratio,
+/// dividing out `φ(a)`, and one in the lower tail by symmetry, so the
+/// moments stay finite where `Z` underflows. With one bound infinite the
+/// variance is computed from the tails of the continued fraction without
+/// cancellation, accurate to about `1e-14` however far in the tail. With
+/// both bounds finite and far in a tail, the variance is a small
+/// difference of terms of the order of `a^2` and loses relative accuracy in
+/// proportion, as it does for intervals much narrower than `1`.
+///
+/// Returns `(f64::NAN, f64::NAN)` if `a > b` or if either bound is
+/// `f64::NAN`, and `(a, 0.0)` if `a == b`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::normal::truncated_normal_moments;
+///
+/// // far in the tail the distribution is close to an exponential with
+/// // rate a, mean a + 1 / a and variance 1 / a^2
+/// let (mean, variance) = truncated_normal_moments(1e4, f64::INFINITY);
+/// assert!((mean - 10000.0001).abs() < 1e-10);
+/// assert!((variance - 1e-8).abs() < 1e-15);
+/// ```
+pub fn truncated_normal_moments(a: f64, b: f64) -> (f64, f64) {
+    if a.is_nan() || b.is_nan() || a > b {
+        (f64::NAN, f64::NAN)
+    } else if a == b {
+        (a, 0.0)
+    } else if a >= 0.0 {
+        upper_tail_moments(a, b)
+    } else if b <= 0.0 {
+        let (mean, variance) = upper_tail_moments(-b, -a);
+        (-mean, variance)
+    } else {
+        // the interval contains 0, so Z is at least about min(b, -a) / 2.5
+        let z = 1.0 - sf(b) - sf(-a);
+        let mean = (pdf(a) - pdf(b)) / z;
+        let term = |x: f64| if x.is_infinite() { 0.0 } else { x * pdf(x) };
+        (mean, 1.0 + (term(a) - term(b)) / z - mean * mean)
+    }
+}
+
+/// Computes the moments of the standard normal distribution truncated to
+/// `(a, b)` for `0 <= a < b`, in terms of the Mills ratio
+fn upper_tail_moments(a: f64, b: f64) -> (f64, f64) {
+    if b.is_infinite() {
+        let mean = inverse_mills_ratio(a);
+        let variance = if a >= CONTINUED_FRACTION_MIN {
+            // with mean = a + t_1 and t_1 = 1 / (a + t_2), the variance
+            // 1 - t_1 (a + t_1) is (t_2 - t_1) / (a + t_2)
+            let (t1, t2) = continued_fraction_tails(a);
+            (t2 - t1) / (a + t2)
+        } else {
+            1.0 - mean * (mean - a)
+        };
+        return (mean, variance);
+    }
+    // everything is divided by φ(a), with r = φ(b) / φ(a)
+    let exponent = -0.5 * (b - a) * (b + a);
+    let r = exponent.exp();
+    let z = mills_ratio(a) - r * mills_ratio(b);
+    let mean = -exponent.exp_m1() / z;
+    // 1 + (a φ(a) - b φ(b)) / Z - m^2 with a φ(a) - b φ(b) = a (φ(a) - φ(b)) - (b - a) φ(b)
+    let variance = 1.0 - mean * (mean - a) - (b - a) * r / z;
+    (mean, variance)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, ContinuousCDF, Normal};
+
+    #[test]
+    fn test_mills_ratio() {
+        // reference values computed with mpmath
+        let cases = [
+            (-37.0, 4.7169665550365805e297),
+            (-10.0, 1.2996129473592023e22),
+            (-1.0, 3.4770518117036945),
+            (0.0, 1.2533141373155003),
+            (0.5, 0.87636445645369235),
+            (1.0, 0.65567954241879847),
+            (2.0, 0.42136922928805447),
+            (2.99, 0.30545489070367075),
+            (3.0, 0.3045902987101033),
+            (5.0, 0.19280810471531576),
+            (10.0, 0.099028596471731921),
+            (37.0, 0.027007327965128336),
+            (100.0, 0.0099990002998501049),
+            (1e3, 0.00099999900000299999),
+            (1e6, 9.99999999999e-7),
+            (1e8, 9.999999999999999e-9),
+        ];
+        for &(x, expected) in &cases {
+            assert!((mills_ratio(x) / expected - 1.0).abs() < 1e-14, "{} {}", x, mills_ratio(x));
+            assert!((inverse_mills_ratio(x) * expected - 1.0).abs() < 1e-14, "{}", x);
+        }
+        assert_eq!(mills_ratio(-40.0), f64::INFINITY);
+        assert_eq!(mills_ratio(f64::INFINITY), 0.0);
+        assert_eq!(inverse_mills_ratio(f64::NEG_INFINITY), 0.0);
+        assert_eq!(inverse_mills_ratio(f64::INFINITY), f64::INFINITY);
+        assert!(mills_ratio(f64::NAN).is_nan());
+        assert!(inverse_mills_ratio(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_matches_distribution() {
+        // the normal survival function is computed from erfc, accurate to about 1e-11
+        let normal = Normal::standard();
+        for &x in &[-5.0, -1.5, 0.3, 2.5, 3.5, 8.0] {
+            assert_almost_eq!(mills_ratio(x), normal.sf(x) / normal.pdf(x), 1e-10 * mills_ratio(x));
+            assert_almost_eq!(inverse_mills_ratio(x), normal.pdf(x) / normal.sf(x), 1e-10 * inverse_mills_ratio(x));
+        }
+    }
+
+    #[test]
+    fn test_truncated_moments() {
+        // reference values computed with mpmath
+        let inf = f64::INFINITY;
+        let cases = [
+            (-1.0, 2.0, 0.22963717909132897, 0.51976253921153394, 1e-14),
+            (0.0, inf, 0.79788456080286536, 0.36338022763241866, 1e-14),
+            (1.0, inf, 1.5251352761609812, 0.19909766557034879, 1e-14),
+            (5.0, inf, 5.1865039671258421, 0.032696434617112225, 1e-14),
+            (40.0, inf, 40.024968847207264, 0.00062266837859138877, 1e-14),
+            (1e4, inf, 10000.000099999998, 9.99999940000005e-9, 1e-14),
+            (0.5, 1.5, 0.92064460522203532, 0.076942097942449192, 1e-13),
+            (6.0, 7.0, 6.1572109033782798, 0.022748381117511731, 1e-12),
+            (3.0, 3.5, 3.1855943984006725, 0.018228721911119799, 1e-12),
+            (20.0, 21.0, 20.049753067339751, 0.0024632604300108294, 1e-10),
+            (-inf, -3.0, -3.2830986549304365, 0.070559186785268117, 1e-14),
+            (-2.0, -1.0, -1.3831690466315528, 0.072742886100601289, 1e-13),
+            (-inf, inf, 0.0, 1.0, 1e-15),
+        ];
+        for &(a, b, mean, variance, tol) in &cases {
+            let (m, v) = truncated_normal_moments(a, b);
+            assert!((m - mean).abs() <= tol * mean.abs().max(1.0), "{} {} {}", a, b, m);
+            assert!((v / variance - 1.0).abs() <= tol, "{} {} {}", a, b, v);
+            assert_eq!(normal_partial_expectation(a, b), m);
+        }
+    }
+
+    #[test]
+    fn test_truncated_mean_formulas() {
+        // E[Z | Z > a] is the inverse Mills ratio and E[Z | Z < b] its negation at -b
+        for &x in &[-3.0, -0.5, 0.0, 1.2, 4.0, 50.0] {
+            assert_eq!(normal_partial_expectation(x, f64::INFINITY), inverse_mills_ratio(x));
+            assert_almost_eq!(normal_partial_expectation(f64::NEG_INFINITY, x), -inverse_mills_ratio(-x), 1e-15);
+        }
+        // the variance of the upper tail is 1 + a λ(a) - λ(a)^2
+        for &a in &[0.5, 2.0, 3.0, 4.0] {
+            let lambda = inverse_mills_ratio(a);
+            let (_, v) = truncated_normal_moments(a, f64::INFINITY);
+            assert_almost_eq!(v, 1.0 + a * lambda - lambda * lambda, 1e-13);
+        }
+        // the law of total expectation over a split of the line
+        let split = [-1.0, 0.5, 2.0];
+        let cells = [(f64::NEG_INFINITY, split[0]), (split[0], split[1]), (split[1], split[2]), (split[2], f64::INFINITY)];
+        let total: f64 = cells.iter().map(|&(a, b)| (sf(a) - sf(b)) * normal_partial_expectation(a, b)).sum();
+        assert_almost_eq!(total, 0.0, 1e-15);
+        // a symmetric interval has mean 0
+        assert_eq!(normal_partial_expectation(-2.5, 2.5), 0.0);
+    }
+
+    #[test]
+    fn test_degenerate_bounds() {
+        assert_eq!(truncated_normal_moments(1.5, 1.5), (1.5, 0.0));
+        let (m, v) = truncated_normal_moments(2.0, 1.0);
+        assert!(m.is_nan() && v.is_nan());
+        assert!(normal_partial_expectation(f64::NAN, 1.0).is_nan());
+        assert!(normal_partial_expectation(0.0, f64::NAN).is_nan());
+        // a narrow interval has its mean near the midpoint
+        let (m, v) = truncated_normal_moments(1.0, 1.001);
+        assert_almost_eq!(m, 1.0005, 1e-6);
+        assert_almost_eq!(v, 1e-6 / 12.0, 1e-9);
+    }
+}
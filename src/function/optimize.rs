@@ -0,0 +1,442 @@
+//! Provides derivative-free minimization of functions of one variable
+
+use std::f64;
+
+/// Maximum number of iterations performed by `brent_min`
+const MAX_ITERATIONS: usize = 500;
+
+/// The golden-section fraction `(3 - sqrt(5)) / 2`
+const GOLDEN: f64 = 0.381_966_011_250_105_1;
+
+/// The result of a minimizer with diagnostics, as returned by
+/// `brent_min_with` and `golden_section`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinResult {
+    /// The point where the minimum was found
+    pub argmin: f64,
+    /// The value of the function at `argmin`
+    pub minimum: f64,
+    /// The number of iterations performed
+    pub iterations: usize,
+    /// The number of evaluations of the function
+    pub evaluations: usize,
+}
+
+impl MinResult {
+    /// Returns the result of `iterations` steps, each evaluating the
+    /// function once after `initial` evaluations and before the final one
+    /// at the minimizer
+    fn new(argmin: f64, minimum: f64, iterations: usize, initial: usize) -> Self {
+        MinResult {
+            argmin,
+            minimum,
+            iterations,
+            evaluations: initial + iterations + 1,
+        }
+    }
+}
+
+/// Finds a local minimum of `f` in the interval with ends `a` and `b` using
+/// [Brent's method](https://en.wikipedia.org/wiki/Brent%27s_method#Brent's_minimization_method),
+/// returning the minimizer and the minimum value
+///
+/// # Remarks
+///
+/// The method combines golden-section search with successive parabolic
+/// interpolation: it converges superlinearly on smooth functions and is
+/// never much slower than golden-section search. For a unimodal `f` the
+/// minimizer is found to within about `tol + sqrt(ε) |x|`, where `ε` is the
+/// machine epsilon, since a smooth function is flat to within rounding over
+/// a neighbourhood of that width. Otherwise some local minimum is returned,
+/// possibly at an end of the interval. `NaN` values of `f` are treated as
+/// larger than any other value.
+///
+/// # Panics
+///
+/// If `a` or `b` is not finite or if `tol` is not positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize::brent_min;
+///
+/// let (x, fx) = brent_min(|x| (x - 2.0) * (x - 2.0) + 1.0, 0.0, 5.0, 1e-10);
+/// assert!((x - 2.0).abs() < 1e-8);
+/// assert!((fx - 1.0).abs() < 1e-15);
+/// ```
+pub fn brent_min<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> (f64, f64) {
+    let result = brent_min_with(f, a, b, tol, MAX_ITERATIONS);
+    (result.argmin, result.minimum)
+}
+
+/// Finds a local minimum of `f` in the interval with ends `a` and `b` using
+/// Brent's method as `brent_min`, taking at most `max_iter` steps and
+/// returning the minimizer and minimum value with the numbers of iterations
+/// and evaluations of `f` it took
+///
+/// # Remarks
+///
+/// When `max_iter` steps do not reach the tolerance the best point found
+/// so far is returned; `iterations == max_iter` then tells that the
+/// tolerance may not have been met.
+///
+/// # Panics
+///
+/// If `a` or `b` is not finite or if `tol` is not positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize::brent_min_with;
+///
+/// let result = brent_min_with(|x: f64| x + 1.0 / x, 0.1, 5.0, 1e-10, 100);
+/// assert!((result.argmin - 1.0).abs() < 1e-7);
+/// assert!((result.minimum - 2.0).abs() < 1e-12);
+/// assert_eq!(result.evaluations, result.iterations + 2);
+/// ```
+pub fn brent_min_with<F: Fn(f64) -> f64>(
+    f: F,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_iter: usize,
+) -> MinResult {
+    check_interval(a, b, tol);
+    let eval = |x: f64| not_nan(f(x));
+    let sqrt_eps = f64::EPSILON.sqrt();
+    let (mut a, mut b) = if a < b { (a, b) } else { (b, a) };
+    let mut x = a + GOLDEN * (b - a);
+    let (mut w, mut v) = (x, x);
+    let mut fx = eval(x);
+    let (mut fw, mut fv) = (fx, fx);
+    let (mut d, mut e): (f64, f64) = (0.0, 0.0);
+    let mut iterations = 0;
+    while iterations < max_iter {
+        let xm = 0.5 * (a + b);
+        let tol1 = sqrt_eps * x.abs() + tol / 3.0;
+        let tol2 = 2.0 * tol1;
+        if (x - xm).abs() <= tol2 - 0.5 * (b - a) {
+            break;
+        }
+        iterations += 1;
+        let mut golden = true;
+        if e.abs() > tol1 {
+            // fit a parabola through x, w and v
+            let r = (x - w) * (fx - fv);
+            let mut q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            let previous = e;
+            e = d;
+            if p.abs() < (0.5 * q * previous).abs() && p > q * (a - x) && p < q * (b - x) {
+                d = p / q;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = tol1.copysign(xm - x);
+                }
+                golden = false;
+            }
+        }
+        if golden {
+            e = if x < xm { b - x } else { a - x };
+            d = GOLDEN * e;
+        }
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else {
+            x + tol1.copysign(d)
+        };
+        let fu = eval(u);
+        if fu <= fx {
+            if u < x {
+                b = x;
+            } else {
+                a = x;
+            }
+            v = w;
+            fv = fw;
+            w = x;
+            fw = fx;
+            x = u;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+    MinResult::new(x, f(x), iterations, 1)
+}
+
+/// Finds a local minimum of `f` in the interval with ends `a` and `b` by
+/// [golden-section search](https://en.wikipedia.org/wiki/Golden-section_search),
+/// returning the minimizer and minimum value with the numbers of
+/// iterations and evaluations of `f` it took
+///
+/// # Remarks
+///
+/// Every step shrinks the interval by the golden ratio `1.618...` at the
+/// cost of one evaluation, so about `4.8 log10(|b - a| / tol)` steps are
+/// taken whatever the shape of `f`. For a unimodal `f` the minimizer is
+/// found to within about `tol + sqrt(ε) |x|`, as with `brent_min`, which is
+/// faster on smooth functions; golden-section search only compares values
+/// of `f`, so it is preferable for functions that are not smooth, such as
+/// piecewise-linear ones. Otherwise some local minimum is returned,
+/// possibly at an end of the interval. `NaN` values of `f` are treated as
+/// larger than any other value.
+///
+/// # Panics
+///
+/// If `a` or `b` is not finite or if `tol` is not positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::optimize::golden_section;
+///
+/// let result = golden_section(|x: f64| (x - 0.3).abs(), 0.0, 1.0, 1e-10);
+/// assert!((result.argmin - 0.3).abs() < 1e-8);
+/// assert!(result.evaluations < 60);
+/// ```
+pub fn golden_section<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> MinResult {
+    check_interval(a, b, tol);
+    let eval = |x: f64| not_nan(f(x));
+    let sqrt_eps = f64::EPSILON.sqrt();
+    let (mut a, mut b) = if a < b { (a, b) } else { (b, a) };
+    // the interior points divide [a, b] in the golden ratio
+    let mut x = a + GOLDEN * (b - a);
+    let mut y = b - GOLDEN * (b - a);
+    let (mut fx, mut fy) = (eval(x), eval(y));
+    let mut iterations = 0;
+    while iterations < MAX_ITERATIONS {
+        let best = if fx <= fy { x } else { y };
+        let tol1 = sqrt_eps * best.abs() + tol / 3.0;
+        if b - a <= 3.0 * tol1 {
+            break;
+        }
+        iterations += 1;
+        if fx <= fy {
+            b = y;
+            y = x;
+            fy = fx;
+            x = a + GOLDEN * (b - a);
+            fx = eval(x);
+        } else {
+            a = x;
+            x = y;
+            fx = fy;
+            y = b - GOLDEN * (b - a);
+            fy = eval(y);
+        }
+    }
+    let best = if fx <= fy { x } else { y };
+    MinResult::new(best, f(best), iterations, 2)
+}
+
+/// Panics unless the ends of the interval are finite and `tol` is positive
+fn check_interval(a: f64, b: f64, tol: f64) {
+    if !a.is_finite() || !b.is_finite() {
+        panic!("interval ends must be finite, were {} and {}", a, b);
+    }
+    if tol.is_nan() || tol <= 0.0 {
+        panic!("tol must be positive, was {}", tol);
+    }
+}
+
+/// Maps `NaN` to infinity, so that it is never taken for a minimum
+fn not_nan(y: f64) -> f64 {
+    if y.is_nan() {
+        f64::INFINITY
+    } else {
+        y
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, Exp, Gamma};
+    use std::cell::Cell;
+
+    #[test]
+    fn test_quadratic() {
+        let (x, fx) = brent_min(|x| (x - 2.0) * (x - 2.0) + 1.0, 0.0, 5.0, 1e-12);
+        assert_almost_eq!(x, 2.0, 1e-10);
+        assert_eq!(fx, 1.0);
+        // the ends may be given in either order
+        let (x, _) = brent_min(|x| 3.0 * x * x - 2.0 * x, 4.0, -4.0, 1e-12);
+        assert_almost_eq!(x, 1.0 / 3.0, 1e-10);
+    }
+
+    #[test]
+    fn test_smooth_functions() {
+        let (x, fx) = brent_min(|x: f64| x.cos(), 0.0, 6.0, 1e-10);
+        assert_almost_eq!(x, f64::consts::PI, 1e-7);
+        assert_almost_eq!(fx, -1.0, 1e-14);
+        // (x - 1)^4 is flat around its minimum
+        let (x, _) = brent_min(|x: f64| (x - 1.0).powi(4), -3.0, 4.0, 1e-10);
+        assert_almost_eq!(x, 1.0, 1e-3);
+        let (x, _) = brent_min(|x: f64| x * x.ln(), 0.01, 2.0, 1e-12);
+        assert_almost_eq!(x, (-1f64).exp(), 1e-8);
+    }
+
+    #[test]
+    fn test_boundary_minimum() {
+        // a monotone function is minimized at the nearer end
+        let (x, _) = brent_min(|x| x, 1.0, 3.0, 1e-10);
+        assert_almost_eq!(x, 1.0, 1e-7);
+        let (x, _) = brent_min(|x| -x, 1.0, 3.0, 1e-10);
+        assert_almost_eq!(x, 3.0, 1e-7);
+    }
+
+    #[test]
+    fn test_exponential_mle() {
+        let data = [0.8, 2.1, 0.3, 1.7, 0.9, 3.4, 0.2, 1.1];
+        let nll = |rate: f64| -data.iter().map(|&x| Exp::new(rate).unwrap().ln_pdf(x)).sum::<f64>();
+        let (rate, _) = brent_min(nll, 0.01, 10.0, 1e-12);
+        assert_almost_eq!(rate, data.len() as f64 / data.iter().sum::<f64>(), 1e-7);
+    }
+
+    #[test]
+    fn test_gamma_shape_mle() {
+        // profile likelihood of the shape with the rate at its MLE shape / mean;
+        // the maximizer solves ln(k) - ψ(k) = ln(mean) - mean(ln x), which
+        // mpmath gives as 3.7679838484197370 for this sample
+        let data = [1.2, 3.4, 2.2, 0.7, 1.9, 2.8, 4.1, 1.5, 2.6, 0.9];
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+        let nll = |shape: f64| {
+            let g = Gamma::new(shape, shape / mean).unwrap();
+            -data.iter().map(|&x| g.ln_pdf(x)).sum::<f64>()
+        };
+        let (shape, _) = brent_min(nll, 0.1, 50.0, 1e-12);
+        assert_almost_eq!(shape, 3.7679838484197370, 1e-7);
+    }
+
+    #[test]
+    fn test_evaluations() {
+        let calls = Cell::new(0);
+        let f = |x: f64| {
+            calls.set(calls.get() + 1);
+            (x - 0.7).powi(2) * (x + 3.0)
+        };
+        let (x, _) = brent_min(f, -1.0, 3.0, 1e-10);
+        assert_almost_eq!(x, 0.7, 1e-8);
+        assert!(calls.get() < 40, "{}", calls.get());
+    }
+
+    #[test]
+    fn test_nan_is_avoided() {
+        let (x, _) = brent_min(|x: f64| if x < 0.5 { f64::NAN } else { (x - 1.0).powi(2) }, 0.0, 3.0, 1e-10);
+        assert_almost_eq!(x, 1.0, 1e-8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_tol() {
+        brent_min(|x| x * x, -1.0, 1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_interval() {
+        brent_min(|x| x * x, f64::NEG_INFINITY, 1.0, 1e-10);
+    }
+
+    /// Checks the minimum of `f` on `[a, b]` found by both minimizers
+    fn check_minimum<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, argmin: f64, minimum: f64) {
+        for result in &[brent_min_with(&f, a, b, 1e-12, 500), golden_section(&f, a, b, 1e-12)] {
+            assert_almost_eq!(result.minimum, minimum, 1e-10);
+            assert_almost_eq!(result.argmin, argmin, 1e-7);
+            assert!(result.iterations < 500);
+        }
+    }
+
+    #[test]
+    fn test_standard_functions() {
+        check_minimum(|x| (x - 2.0) * (x - 2.0) + 1.0, 0.0, 5.0, 2.0, 1.0);
+        check_minimum(|x: f64| x + 1.0 / x, 0.1, 5.0, 1.0, 2.0);
+        check_minimum(|x: f64| -x * (-x).exp(), 0.0, 5.0, 1.0, -(-1f64).exp());
+        check_minimum(|x: f64| x.sin(), 3.0, 6.0, 1.5 * f64::consts::PI, -1.0);
+        check_minimum(|x: f64| x.powi(4) - 3.0 * x * x + x, 0.0, 3.0, 1.1309011226299859, -1.0702301817761541);
+        // minima at 0 are found to within the tolerance itself
+        let result = brent_min_with(|x: f64| x.cosh(), -1.0, 2.0, 1e-12, 500);
+        assert_almost_eq!(result.argmin, 0.0, 1e-10);
+        let result = golden_section(|x: f64| x.abs(), -1.0, 2.0, 1e-12);
+        assert_almost_eq!(result.argmin, 0.0, 1e-11);
+    }
+
+    #[test]
+    fn test_narrow_valley() {
+        // a steep, narrow well and a cusp at an irrational point
+        let well = |x: f64| 1.0 - 1.0 / (1.0 + 1e6 * (x - 0.1 * f64::consts::PI).powi(2));
+        check_minimum(well, 0.0, 1.0, 0.1 * f64::consts::PI, 0.0);
+        let cusp = |x: f64| (x - 0.1 * f64::consts::PI).abs().sqrt();
+        let result = golden_section(cusp, 0.0, 1.0, 1e-12);
+        assert_almost_eq!(result.argmin, 0.1 * f64::consts::PI, 1e-8);
+        let result = brent_min_with(cusp, 0.0, 1.0, 1e-12, 500);
+        assert_almost_eq!(result.argmin, 0.1 * f64::consts::PI, 1e-8);
+    }
+
+    #[test]
+    fn test_evaluation_counts() {
+        let calls = Cell::new(0);
+        let f = |x: f64| {
+            calls.set(calls.get() + 1);
+            (x - 0.7).powi(2) * (x + 3.0)
+        };
+        let brent = brent_min_with(f, -1.0, 3.0, 1e-10, 500);
+        assert_eq!(brent.evaluations, calls.replace(0));
+        assert_eq!(brent.evaluations, brent.iterations + 2);
+        let golden = golden_section(f, -1.0, 3.0, 1e-10);
+        assert_eq!(golden.evaluations, calls.get());
+        assert_eq!(golden.evaluations, golden.iterations + 3);
+        // the interval of length 4 shrinks by 0.618 per step to about 1e-8
+        let bound = (4.0f64 / 1e-8).ln() / (1.0 / (1.0 - GOLDEN)).ln();
+        assert!((golden.iterations as f64) <= bound + 1.0, "{:?}", golden);
+        assert!(brent.evaluations < golden.evaluations / 2, "{:?}", brent);
+    }
+
+    #[test]
+    fn test_iteration_budget() {
+        let f = |x: f64| (x - 0.7).powi(2);
+        let result = brent_min_with(f, -1.0, 3.0, 1e-10, 3);
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.evaluations, 5);
+        assert!(result.minimum < f(-1.0 + GOLDEN * 4.0));
+        let result = brent_min_with(f, -1.0, 3.0, 1e-10, 0);
+        assert_eq!(result.argmin, -1.0 + GOLDEN * 4.0);
+    }
+
+    #[test]
+    fn test_multimodal() {
+        // some local minimum is returned, not necessarily the global one
+        for result in &[brent_min_with(|x: f64| x.sin(), 0.0, 20.0, 1e-10, 500), golden_section(|x: f64| x.sin(), 0.0, 20.0, 1e-10)] {
+            assert!(result.argmin.cos().abs() < 1e-6, "{:?}", result);
+            assert_almost_eq!(result.minimum, -1.0, 1e-10);
+        }
+        let result = golden_section(|x: f64| if x < 0.5 { f64::NAN } else { (x - 1.0).powi(2) }, 0.0, 3.0, 1e-10);
+        assert_almost_eq!(result.argmin, 1.0, 1e-8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_golden_bad_tol() {
+        golden_section(|x| x * x, -1.0, 1.0, -1.0);
+    }
+}
@@ -0,0 +1,672 @@
+//! Provides [root-finding](https://en.wikipedia.org/wiki/Root-finding_algorithms)
+//! methods for continuous functions of one variable, such as solving
+//! `cdf(x) - p = 0` for a distribution without a closed-form quantile
+
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Maximum number of iterations performed by `brent`
+const MAX_ITERATIONS: usize = 500;
+
+/// Maximum number of step halvings in a damped step of `newton`
+const MAX_HALVINGS: usize = 60;
+
+/// The result of a root finder with diagnostics, as returned by
+/// `brent_with`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootResult {
+    /// The root found
+    pub root: f64,
+    /// The number of iterations performed
+    pub iterations: usize,
+    /// The number of evaluations of the function, including those at the
+    /// ends of the interval
+    pub evaluations: usize,
+}
+
+impl RootResult {
+    /// Returns the result of `iterations` steps of a bracketing method,
+    /// each evaluating the function once after the two ends
+    fn new(root: f64, iterations: usize) -> Self {
+        RootResult {
+            root,
+            iterations,
+            evaluations: iterations + 2,
+        }
+    }
+}
+
+/// Checks the interval and tolerance and evaluates `f` at both ends,
+/// returning `Ok(Err(root))` when an end is already a root
+fn check_bracket<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+    tol: f64,
+) -> Result<std::result::Result<(f64, f64), f64>> {
+    if !a.is_finite() {
+        return Err(StatsError::ArgFinite("a"));
+    }
+    if !b.is_finite() {
+        return Err(StatsError::ArgFinite("b"));
+    }
+    if tol.is_nan() || tol <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tol"));
+    }
+    let (fa, fb) = (f(a), f(b));
+    if fa.is_nan() || fb.is_nan() {
+        return Err(StatsError::SpecialCase(
+            "the function is NaN at an end of the interval",
+        ));
+    }
+    if fa == 0.0 {
+        return Ok(Err(a));
+    }
+    if fb == 0.0 {
+        return Ok(Err(b));
+    }
+    if fa.signum() == fb.signum() {
+        return Err(StatsError::SpecialCase(
+            "the interval does not bracket a root",
+        ));
+    }
+    Ok(Ok((fa, fb)))
+}
+
+/// Finds a root of `f` in the interval with ends `a` and `b` using
+/// [Brent's method](https://en.wikipedia.org/wiki/Brent%27s_method), to
+/// within an absolute tolerance `tol` in `x`
+///
+/// # Remarks
+///
+/// Brent's method combines inverse quadratic interpolation and the secant
+/// method with bisection, so it converges superlinearly on smooth functions
+/// while never doing worse than bisection. `f` must be continuous and
+/// change sign between `a` and `b`, which may be given in either order.
+/// The achievable tolerance is limited by about `4 ε |x|` where `ε` is the
+/// machine epsilon.
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` is not finite, if `tol` is not positive,
+/// if `f` is `NaN` at `a` or `b`, if `f(a)` and `f(b)` have the same sign,
+/// or if the method fails to converge
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::root::brent;
+///
+/// let root = brent(|x| x * x - 2.0, 0.0, 2.0, 1e-15).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-15);
+/// assert!(brent(|x| x * x + 1.0, -1.0, 1.0, 1e-15).is_err());
+/// ```
+pub fn brent<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> Result<f64> {
+    brent_with(f, a, b, tol, MAX_ITERATIONS).map(|result| result.root)
+}
+
+/// Finds a root of `f` in the interval with ends `a` and `b` using
+/// Brent's method as `brent`, taking at most `max_iter` steps and
+/// returning the root with the numbers of iterations and evaluations of
+/// `f` it took
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` is not finite, if `tol` is not positive,
+/// if `max_iter` is `0`, if `f` is `NaN` at `a` or `b`, if `f(a)` and
+/// `f(b)` have the same sign, or if the method fails to converge within
+/// `max_iter` steps
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::root::brent_with;
+///
+/// let result = brent_with(|x: f64| x.exp() - 10.0, 0.0, 10.0, 1e-14, 100).unwrap();
+/// assert!((result.root - 10f64.ln()).abs() < 1e-14);
+/// assert_eq!(result.evaluations, result.iterations + 2);
+/// assert!(result.iterations < 20);
+/// ```
+pub fn brent_with<F: Fn(f64) -> f64>(
+    f: F,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<RootResult> {
+    if max_iter == 0 {
+        return Err(StatsError::ArgMustBePositive("max_iter"));
+    }
+    let (mut fa, mut fb) = match check_bracket(&f, a, b, tol)? {
+        Ok(values) => values,
+        Err(root) => return Ok(RootResult::new(root, 0)),
+    };
+    let (mut a, mut b) = (a, b);
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    for iterations in 0..max_iter {
+        if fb.signum() == fc.signum() {
+            // keep the root bracketed between b and c
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            // make b the best estimate so far
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Ok(RootResult::new(b, iterations));
+        }
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // secant step
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                // inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = 3.0 * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if 2.0 * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+        if fb.is_nan() {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Searches for an interval bracketing a root of `f` by stepping away from
+/// `x0` in the direction in which `|f|` decreases, multiplying the step by
+/// `factor` after each of at most `max_expansions` steps, and returns the
+/// ends of the interval in increasing order
+///
+/// # Remarks
+///
+/// The first step is `(factor - 1) max(|x0|, 1)`, so that for a positive
+/// `x0` the first point tried is `factor x0`, and the points then grow
+/// geometrically. Both neighbours at the first step are evaluated, and
+/// unless one of them already brackets a root the direction is that of the
+/// smaller `|f|`, upwards on a tie. The interval returned is the last
+/// step, which can be refined by `brent`.
+/// When `f(x0)` is `0` the interval is `(x0, x0)`.
+///
+/// # Errors
+///
+/// Returns an error if `x0` is not finite, if `factor` is not greater than
+/// `1`, if `f` is `NaN` at `x0`, or if no sign change is found within
+/// `max_expansions` steps or before the steps overflow or `f` becomes `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::root::{brent, bracket_root};
+///
+/// let f = |x: f64| x.ln() - 10.0;
+/// let (lo, hi) = bracket_root(f, 1.0, 2.0, 100).unwrap();
+/// assert!(f(lo) < 0.0 && f(hi) > 0.0);
+/// let root = brent(f, lo, hi, 1e-9).unwrap();
+/// assert!((root - 10f64.exp()).abs() < 1e-9);
+/// ```
+pub fn bracket_root<F: Fn(f64) -> f64>(
+    f: F,
+    x0: f64,
+    factor: f64,
+    max_expansions: usize,
+) -> Result<(f64, f64)> {
+    if !x0.is_finite() {
+        return Err(StatsError::ArgFinite("x0"));
+    }
+    if factor.is_nan() || factor <= 1.0 {
+        return Err(StatsError::ArgGt("factor", 1.0));
+    }
+    let f0 = f(x0);
+    if f0.is_nan() {
+        return Err(StatsError::SpecialCase(
+            "the function is NaN at the starting point",
+        ));
+    }
+    if f0 == 0.0 {
+        return Ok((x0, x0));
+    }
+    let step = (factor - 1.0) * x0.abs().max(1.0);
+    let (up, down) = (f(x0 + step), f(x0 - step));
+    // all points before a sign change have the sign of f(x0)
+    let changes_sign = |fx: f64| fx == 0.0 || !fx.is_nan() && fx.signum() != f0.signum();
+    if changes_sign(up) {
+        return Ok((x0, x0 + step));
+    }
+    if changes_sign(down) {
+        return Ok((x0 - step, x0));
+    }
+    let mut step = match (up.is_nan(), down.is_nan()) {
+        (true, true) => return Err(StatsError::ComputationFailedToConverge),
+        (false, true) => step,
+        (true, false) => -step,
+        (false, false) if up.abs() <= down.abs() => step,
+        (false, false) => -step,
+    };
+    let mut x = x0;
+    for _ in 0..max_expansions {
+        let next = x + step;
+        if !next.is_finite() {
+            break;
+        }
+        let fnext = f(next);
+        if fnext.is_nan() {
+            break;
+        }
+        if changes_sign(fnext) {
+            return Ok(if x < next { (x, next) } else { (next, x) });
+        }
+        x = next;
+        step *= factor;
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Finds a root of `f` in the interval with ends `a` and `b` by
+/// [bisection](https://en.wikipedia.org/wiki/Bisection_method), to within
+/// an absolute tolerance `tol` in `x`
+///
+/// # Remarks
+///
+/// Bisection halves the bracketing interval at every step, so it needs
+/// about `log2(|b - a| / tol)` evaluations of `f`. It is slower than
+/// [`brent`] but only relies on the sign of `f`. Iteration stops early when
+/// the interval can no longer be split in floating point.
+///
+/// # Errors
+///
+/// Returns an error if `a` or `b` is not finite, if `tol` is not positive,
+/// if `f` is `NaN` at `a` or `b`, or if `f(a)` and `f(b)` have the same
+/// sign
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::root::bisect;
+///
+/// let root = bisect(|x: f64| x.cos() - x, 0.0, 1.0, 1e-12).unwrap();
+/// assert!((root - 0.7390851332151607).abs() < 1e-12);
+/// ```
+pub fn bisect<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> Result<f64> {
+    let (fa, _) = match check_bracket(&f, a, b, tol)? {
+        Ok(values) => values,
+        Err(root) => return Ok(root),
+    };
+    let (mut lo, mut hi) = (a, b);
+    let lo_sign = fa.signum();
+    loop {
+        let mid = 0.5 * (lo + hi);
+        if (hi - lo).abs() <= 2.0 * tol || mid == lo || mid == hi {
+            return Ok(mid);
+        }
+        let fm = f(mid);
+        if fm == 0.0 {
+            return Ok(mid);
+        }
+        if fm.signum() == lo_sign {
+            lo = mid;
+        } else {
+            // NaN is treated as the sign of the far end
+            hi = mid;
+        }
+    }
+}
+
+/// Finds a root of `f` starting from `x0` by the
+/// [Newton-Raphson method](https://en.wikipedia.org/wiki/Newton%27s_method)
+/// with the derivative `fprime`, taking at most `max_iter` steps
+///
+/// Iteration stops when a step is at most `tol`, or when a bracketing
+/// interval is at most `2 tol` wide
+///
+/// # Remarks
+///
+/// The iteration is safeguarded so that it never diverges. Until a sign
+/// change of `f` has been seen, a Newton step that does not reduce `|f|`
+/// is halved until it does. Once two points with opposite signs of `f`
+/// bracket a root, a Newton step that would leave the bracket is replaced
+/// by a bisection step, so the iteration converges quadratically near a
+/// simple root and at least linearly otherwise.
+///
+/// # Errors
+///
+/// Returns an error if `x0` is not finite, if `tol` is not positive, if
+/// `max_iter` is `0`, if `f` is `NaN` at `x0`, or if the method fails to
+/// converge, for example because the derivative vanishes before a root is
+/// bracketed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::function::root::newton;
+///
+/// let root = newton(|x| x * x - 2.0, |x| 2.0 * x, 1.0, 1e-15, 50).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-15);
+/// ```
+pub fn newton<F, G>(f: F, fprime: G, x0: f64, tol: f64, max_iter: usize) -> Result<f64>
+where
+    F: Fn(f64) -> f64,
+    G: Fn(f64) -> f64,
+{
+    if !x0.is_finite() {
+        return Err(StatsError::ArgFinite("x0"));
+    }
+    if tol.is_nan() || tol <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tol"));
+    }
+    if max_iter == 0 {
+        return Err(StatsError::ArgMustBePositive("max_iter"));
+    }
+    let mut x = x0;
+    let mut fx = f(x);
+    if fx.is_nan() {
+        return Err(StatsError::SpecialCase(
+            "the function is NaN at the starting point",
+        ));
+    }
+    // the latest points where f is negative and positive
+    let mut negative = None;
+    let mut positive = None;
+    for _ in 0..max_iter {
+        if fx == 0.0 {
+            return Ok(x);
+        }
+        if fx < 0.0 {
+            negative = Some(x);
+        } else {
+            positive = Some(x);
+        }
+        let newton = x - fx / fprime(x);
+        let (next, fnext) = match (negative, positive) {
+            (Some(a), Some(b)) => {
+                let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+                if hi - lo <= 2.0 * tol {
+                    return Ok(x);
+                }
+                let next = if newton > lo && newton < hi {
+                    newton
+                } else {
+                    0.5 * (lo + hi)
+                };
+                let fnext = f(next);
+                if fnext.is_nan() {
+                    return Err(StatsError::ComputationFailedToConverge);
+                }
+                (next, fnext)
+            }
+            _ => {
+                if !newton.is_finite() {
+                    return Err(StatsError::ComputationFailedToConverge);
+                }
+                damped_step(&f, x, fx, newton - x)?
+            }
+        };
+        let step = (next - x).abs();
+        x = next;
+        fx = fnext;
+        if step <= tol {
+            return Ok(x);
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Halves the step `step` from `x` until `f` changes sign or `|f|`
+/// decreases, returning the new point and the value of `f` there
+fn damped_step<F: Fn(f64) -> f64>(f: &F, x: f64, fx: f64, step: f64) -> Result<(f64, f64)> {
+    let mut step = step;
+    for _ in 0..MAX_HALVINGS {
+        let next = x + step;
+        let fnext = f(next);
+        if fnext.signum() != fx.signum() && !fnext.is_nan() || fnext.abs() < fx.abs() {
+            return Ok((next, fnext));
+        }
+        step *= 0.5;
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Continuous, ContinuousCDF, Gamma, Normal};
+    use std::cell::Cell;
+
+    #[test]
+    fn test_polynomial_roots() {
+        // Wallis's example, x^3 - 2x - 5
+        let wallis = |x: f64| x * x * x - 2.0 * x - 5.0;
+        assert_almost_eq!(brent(wallis, 2.0, 3.0, 1e-15).unwrap(), 2.0945514815423265, 1e-15);
+        assert_almost_eq!(bisect(wallis, 2.0, 3.0, 1e-15).unwrap(), 2.0945514815423265, 1e-15);
+        // (x - 1)(x - 2)(x - 3) with one root in each interval
+        let cubic = |x: f64| (x - 1.0) * (x - 2.0) * (x - 3.0);
+        assert_almost_eq!(brent(cubic, 0.0, 1.5, 1e-14).unwrap(), 1.0, 1e-14);
+        assert_almost_eq!(brent(cubic, 2.5, 1.5, 1e-14).unwrap(), 2.0, 1e-14);
+        assert_almost_eq!(bisect(cubic, 2.5, 10.0, 1e-14).unwrap(), 3.0, 1e-14);
+        // a root of multiplicity three
+        let triple = |x: f64| (x - 0.5).powi(3);
+        assert_almost_eq!(brent(triple, -1.0, 3.0, 1e-12).unwrap(), 0.5, 1e-4);
+    }
+
+    #[test]
+    fn test_endpoint_roots() {
+        assert_eq!(brent(|x| x, 0.0, 1.0, 1e-10).unwrap(), 0.0);
+        assert_eq!(brent(|x| x - 1.0, 0.0, 1.0, 1e-10).unwrap(), 1.0);
+        assert_eq!(bisect(|x| x, -1.0, 0.0, 1e-10).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_cdf_inversion() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        for &p in &[1e-6, 0.01, 0.3, 0.5, 0.975] {
+            // the normal cdf and quantile agree to about 1e-10 in the tail
+            let x = brent(|x| normal.cdf(x) - p, -10.0, 10.0, 1e-14).unwrap();
+            assert_almost_eq!(x, normal.inverse_cdf(p), 1e-10);
+            let y = bisect(|x| normal.cdf(x) - p, -10.0, 10.0, 1e-13).unwrap();
+            assert_almost_eq!(y, x, 1e-13);
+        }
+        let gamma = Gamma::new(3.0, 2.0).unwrap();
+        let x = brent(|x| gamma.cdf(x) - 0.5, 0.0, 20.0, 1e-14).unwrap();
+        assert_almost_eq!(gamma.cdf(x), 0.5, 1e-14);
+        assert_almost_eq!(x, 1.3370301568617802, 1e-14);
+    }
+
+    #[test]
+    fn test_brent_is_faster_than_bisection() {
+        let calls = Cell::new(0);
+        let f = |x: f64| {
+            calls.set(calls.get() + 1);
+            x.exp() - 10.0
+        };
+        brent(f, 0.0, 10.0, 1e-14).unwrap();
+        let brent_calls = calls.replace(0);
+        bisect(f, 0.0, 10.0, 1e-14).unwrap();
+        assert!(brent_calls < 20, "{}", brent_calls);
+        assert!(brent_calls < calls.get());
+    }
+
+    #[test]
+    fn test_discontinuous_sign_change() {
+        // no root, but the sign change is located as with any bracketing method
+        let step = |x: f64| if x < 0.25 { -1.0 } else { 1.0 };
+        assert_almost_eq!(brent(step, 0.0, 1.0, 1e-12).unwrap(), 0.25, 1e-11);
+        assert_almost_eq!(bisect(step, 0.0, 1.0, 1e-12).unwrap(), 0.25, 1e-11);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(brent(|x| x, 1.0, 2.0, 1e-10).is_err());
+        assert!(bisect(|x| x * x + 1.0, -1.0, 1.0, 1e-10).is_err());
+        assert!(brent(|x| x, f64::NEG_INFINITY, 1.0, 1e-10).is_err());
+        assert!(brent(|x| x, -1.0, f64::NAN, 1e-10).is_err());
+        assert!(brent(|x| x, -1.0, 1.0, 0.0).is_err());
+        assert!(bisect(|x| x, -1.0, 1.0, f64::NAN).is_err());
+        assert!(brent(|x: f64| x.ln(), -1.0, 2.0, 1e-10).is_err());
+    }
+
+    #[test]
+    fn test_newton_quadratic_convergence() {
+        let iterates = std::cell::RefCell::new(Vec::new());
+        let f = |x: f64| {
+            iterates.borrow_mut().push(x);
+            x * x - 2.0
+        };
+        let root = newton(f, |x| 2.0 * x, 1.0, 1e-15, 50).unwrap();
+        assert_almost_eq!(root, 2f64.sqrt(), 1e-15);
+        // e_(n+1) ≈ e_n^2 / (2 sqrt(2)) near the root
+        let errors: Vec<f64> = iterates.borrow().iter().map(|x| (x - 2f64.sqrt()).abs()).collect();
+        for w in errors.windows(2).filter(|w| w[0] < 0.1 && w[0] > 1e-7) {
+            assert_almost_eq!(w[1] / (w[0] * w[0]), 0.5 / 2f64.sqrt(), 0.05);
+        }
+        assert!(errors.len() <= 8, "{}", errors.len());
+    }
+
+    #[test]
+    fn test_newton_cdf_inversion() {
+        let gamma = Gamma::new(3.0, 2.0).unwrap();
+        let x = newton(|x| gamma.cdf(x) - 0.9, |x| gamma.pdf(x), 1.5, 1e-14, 50).unwrap();
+        assert_almost_eq!(gamma.cdf(x), 0.9, 1e-15);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let x = newton(|x| normal.cdf(x) - 0.975, |x| normal.pdf(x), 0.0, 1e-14, 50).unwrap();
+        assert_almost_eq!(x, brent(|x| normal.cdf(x) - 0.975, 0.0, 5.0, 1e-15).unwrap(), 1e-14);
+    }
+
+    #[test]
+    fn test_newton_safeguards() {
+        // plain Newton on atan diverges from |x0| > 1.39
+        let root = newton(|x: f64| x.atan(), |x| 1.0 / (1.0 + x * x), 3.0, 1e-14, 100).unwrap();
+        assert_almost_eq!(root, 0.0, 1e-14);
+        let root = newton(|x: f64| x.atan(), |x| 1.0 / (1.0 + x * x), -50.0, 1e-14, 100).unwrap();
+        assert_almost_eq!(root, 0.0, 1e-14);
+        // a derivative of the wrong magnitude only slows the bracketed iteration down
+        let root = newton(|x: f64| x.cos() - x, |_| -0.1, 0.0, 1e-12, 200).unwrap();
+        assert_almost_eq!(root, 0.7390851332151607, 1e-11);
+        // plain Newton cycles between 0 and 1 on x^3 - 2x + 2, damping escapes
+        let cubic = |x: f64| x * x * x - 2.0 * x + 2.0;
+        assert_almost_eq!(newton(cubic, |x| 3.0 * x * x - 2.0, 0.0, 1e-15, 100).unwrap(), -1.7692923542386314, 1e-14);
+        assert_almost_eq!(newton(cubic, |x| 3.0 * x * x - 2.0, -3.0, 1e-15, 100).unwrap(), -1.7692923542386314, 1e-14);
+    }
+
+    #[test]
+    fn test_newton_bad_input() {
+        assert!(newton(|x| x * x + 1.0, |x| 2.0 * x, 1.0, 1e-10, 100).is_err());
+        assert!(newton(|x| x, |_| 1.0, f64::NAN, 1e-10, 10).is_err());
+        assert!(newton(|x| x, |_| 1.0, 1.0, 0.0, 10).is_err());
+        assert!(newton(|x| x, |_| 1.0, 1.0, 1e-10, 0).is_err());
+        assert!(newton(|x: f64| x.ln(), |x| 1.0 / x, -1.0, 1e-10, 10).is_err());
+        assert_eq!(newton(|x| x - 1.0, |_| 1.0, 1.0, 1e-10, 10).unwrap(), 1.0);
+    }
+
+    /// Checks the root of `f` found by `brent_with` on `[a, b]` and that it
+    /// took no more evaluations than Brent's bound of `(log2((b - a) / tol) + 1)^2`
+    fn check_brent<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, root: f64) {
+        let tol = 1e-12;
+        let result = brent_with(&f, a, b, tol, 500).unwrap();
+        assert!(f(result.root) == 0.0 || (result.root - root).abs() <= tol, "{:?}", result);
+        let bound = ((b - a) / tol).log2() + 1.0;
+        assert!((result.evaluations as f64) <= bound * bound, "{:?}", result);
+        assert_eq!(result.evaluations, result.iterations + 2);
+    }
+
+    #[test]
+    fn test_brent_standard_functions() {
+        // test problems of Alefeld, Potra and Shi (1995)
+        check_brent(|x: f64| x.sin() - 0.5 * x, 0.5 * f64::consts::PI, f64::consts::PI, 1.8954942670339809);
+        check_brent(|x: f64| x.powi(20) - 1.0, 0.0, 5.0, 1.0);
+        check_brent(|x: f64| 2.0 * x * (-20f64).exp() + 1.0 - 2.0 * (-20.0 * x).exp(), 0.0, 1.0, 0.03465735902085385);
+        check_brent(|x: f64| (1.0 / x - 25.0) * 1e-4, 0.01, 1.0, 0.04);
+        // nearby roots of (x - 1)(x - 1.001)(x - 3), one in each interval
+        let cubic = |x: f64| (x - 1.0) * (x - 1.001) * (x - 3.0);
+        check_brent(cubic, 0.0, 1.0005, 1.0);
+        check_brent(cubic, 1.0005, 2.0, 1.001);
+        // flat about the root, where the function underflows to 0
+        check_brent(|x: f64| if x == 1.0 { 0.0 } else { (x - 1.0) * (-1.0 / ((x - 1.0) * (x - 1.0))).exp() }, -1.0, 4.0, 1.0);
+        // a step, whose only sign change is located as by bisection
+        check_brent(|x: f64| if x < 1.0 / 3.0 { -1.0 } else { 1.0 }, 0.0, 1.0, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_brent_with_budget() {
+        let f = |x: f64| x.exp() - 10.0;
+        let result = brent_with(f, 0.0, 10.0, 1e-14, 500).unwrap();
+        assert_eq!(result.root, brent(f, 0.0, 10.0, 1e-14).unwrap());
+        assert!(brent_with(f, 0.0, 10.0, 1e-14, result.iterations + 1).is_ok());
+        assert!(matches!(
+            brent_with(f, 0.0, 10.0, 1e-14, result.iterations),
+            Err(StatsError::ComputationFailedToConverge)
+        ));
+        assert_eq!(brent_with(|x| x, 0.0, 1.0, 1e-10, 1).unwrap(), RootResult { root: 0.0, iterations: 0, evaluations: 2 });
+        assert!(brent_with(f, 0.0, 10.0, 1e-14, 0).is_err());
+        assert!(brent_with(|x| x * x + 1.0, -1.0, 1.0, 1e-10, 100).is_err());
+        assert!(brent_with(|x| x - 3.0, 0.0, 1.0, 1e-10, 100).is_err());
+    }
+
+    #[test]
+    fn test_bracket_root() {
+        // increasing steps of 1, 2, 4, ... from 1 reach past e^10
+        let f = |x: f64| x.ln() - 10.0;
+        let (lo, hi) = bracket_root(f, 1.0, 2.0, 100).unwrap();
+        assert!(f(lo) < 0.0 && f(hi) > 0.0);
+        assert_eq!((lo, hi), (16384.0, 32768.0));
+        // downhill to the left, where the neighbour to the right is NaN
+        let (lo, hi) = bracket_root(|x| 1.0 - (-x).sqrt(), -0.5, 3.0, 20).unwrap();
+        assert!(lo < -1.0 && hi > -1.0 && hi <= -0.5);
+        let (lo, hi) = bracket_root(|x| x + 100.0, 0.0, 1.5, 100).unwrap();
+        assert!(lo < -100.0 && hi > -100.0);
+        assert_almost_eq!(brent(|x| x + 100.0, lo, hi, 1e-13).unwrap(), -100.0, 1e-13);
+        // the quantile of the standard normal distribution
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let q = |x: f64| normal.cdf(x) - 0.999;
+        let (lo, hi) = bracket_root(q, 0.0, 2.0, 10).unwrap();
+        assert_almost_eq!(brent(q, lo, hi, 1e-14).unwrap(), normal.inverse_cdf(0.999), 1e-10);
+        assert_eq!(bracket_root(|x| x - 2.0, 2.0, 2.0, 10).unwrap(), (2.0, 2.0));
+    }
+
+    #[test]
+    fn test_bracket_root_bad_input() {
+        assert!(bracket_root(|x| x * x + 1.0, 0.0, 2.0, 50).is_err());
+        assert!(bracket_root(|x| x - 1e6, 0.0, 2.0, 5).is_err());
+        assert!(bracket_root(|x| x, 1.0, 1.0, 10).is_err());
+        assert!(bracket_root(|x| x, 1.0, f64::NAN, 10).is_err());
+        assert!(bracket_root(|x| x, f64::INFINITY, 2.0, 10).is_err());
+        assert!(bracket_root(|x: f64| x.ln(), -1.0, 2.0, 10).is_err());
+        // the root e^1000 is beyond the largest double
+        assert!(bracket_root(|x: f64| x.ln() - 1000.0, 1.0, 2.0, 10_000).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! Provides utility functions for generating data sequences
 
+use crate::distribution::summation::CompensatedSum;
 use crate::euclid::Modulus;
+use rand::Rng;
 use std::f64::consts;
 /// Generates a base 10 log spaced vector of the given length between the
 /// specified decade exponents (inclusive). Equivalent to MATLAB logspace
@@ -370,3 +372,176 @@ impl Iterator for InfiniteSawtooth {
         self.periodic.next().map(|x| x + self.low_value)
     }
 }
+
+/// Draws a standard exponential variate by inversion, `-ln(1 - U)` for a
+/// uniform `U` in `[0, 1)`, which is never infinite
+fn standard_exponential<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    -(-rng.gen::<f64>()).ln_1p()
+}
+
+/// Generates the `n` order statistics of a sample of `n` standard uniform
+/// variates, in increasing order, without sorting
+///
+/// # Remarks
+///
+/// The order statistics are the normalized partial sums of `n + 1`
+/// standard exponential variates,
+///
+/// ```text
+/// U_(k) = (E_1 + ... + E_k) / (E_1 + ... + E_(n + 1))
+/// ```
+///
+/// which takes `O(n)` time instead of the `O(n log n)` of sorting a sample,
+/// and gives a non-decreasing sequence in `[0, 1]`. The `k`-th value
+/// follows the `Beta(k, n - k + 1)` distribution.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::generate::uniform_order_statistics;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let x = uniform_order_statistics(100, &mut rng);
+/// assert_eq!(x.len(), 100);
+/// assert!(x.windows(2).all(|w| w[0] <= w[1]));
+/// assert!(x[0] >= 0.0 && x[99] <= 1.0);
+/// ```
+pub fn uniform_order_statistics<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<f64> {
+    let mut sum = 0.0;
+    let mut partial_sums: Vec<f64> = (0..n)
+        .map(|_| {
+            sum += standard_exponential(rng);
+            sum
+        })
+        .collect();
+    let total = sum + standard_exponential(rng);
+    for x in partial_sums.iter_mut() {
+        *x /= total;
+    }
+    partial_sums
+}
+
+/// Generates the `n + 1` spacings of `n` standard uniform variates, the
+/// lengths of the intervals into which they cut `[0, 1]`, which sum to `1`
+///
+/// # Remarks
+///
+/// The spacings are `n + 1` standard exponential variates divided by their
+/// sum, so they are exchangeable and jointly follow the flat Dirichlet
+/// distribution with `n + 1` components. The sum is computed with
+/// compensated summation and the rounding left over is added to the
+/// largest spacing, so that the exact sum of the spacings is `1` to within
+/// an ulp.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::generate::uniform_spacings;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let spacings = uniform_spacings(9, &mut rng);
+/// assert_eq!(spacings.len(), 10);
+/// assert!(spacings.iter().all(|&s| s >= 0.0));
+/// assert!((spacings.iter().sum::<f64>() - 1.0).abs() < 1e-15);
+/// ```
+pub fn uniform_spacings<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<f64> {
+    let mut spacings: Vec<f64> = (0..=n).map(|_| standard_exponential(rng)).collect();
+    let total = compensated_sum(&spacings);
+    for s in spacings.iter_mut() {
+        *s /= total;
+    }
+    let largest = (0..spacings.len())
+        .max_by(|&i, &j| spacings[i].total_cmp(&spacings[j]))
+        .unwrap();
+    spacings[largest] += 1.0 - compensated_sum(&spacings);
+    spacings
+}
+
+/// Computes the sum of `values` with compensated summation
+fn compensated_sum(values: &[f64]) -> f64 {
+    let mut sum = CompensatedSum::default();
+    for &v in values {
+        sum.add(v);
+    }
+    sum.value()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Beta, ContinuousCDF};
+    use crate::rng_util::SplitMix64;
+
+    #[test]
+    fn test_order_statistics_sorted() {
+        let mut rng = SplitMix64::new(304);
+        for &n in &[0, 1, 2, 10, 1000] {
+            let x = uniform_order_statistics(n, &mut rng);
+            assert_eq!(x.len(), n);
+            assert!(x.windows(2).all(|w| w[0] <= w[1]));
+            assert!(x.iter().all(|&v| (0.0..=1.0).contains(&v)));
+        }
+    }
+
+    #[test]
+    fn test_order_statistics_marginals() {
+        // the k-th of n order statistics follows Beta(k, n - k + 1)
+        let mut rng = SplitMix64::new(3042);
+        let (n, reps) = (9, 20000);
+        for &k in &[1, 3, 9] {
+            let mut values: Vec<f64> = (0..reps).map(|_| uniform_order_statistics(n, &mut rng)[k - 1]).collect();
+            values.sort_by(f64::total_cmp);
+            let beta = Beta::new(k as f64, (n - k + 1) as f64).unwrap();
+            let ks = values.iter().enumerate().map(|(i, &v)| {
+                let f = beta.cdf(v);
+                (f - i as f64 / reps as f64).max((i + 1) as f64 / reps as f64 - f)
+            }).fold(0.0, f64::max);
+            // the 0.1% critical value of the Kolmogorov-Smirnov statistic
+            assert!(ks < 1.95 / (reps as f64).sqrt(), "{} {}", k, ks);
+        }
+    }
+
+    #[test]
+    fn test_spacings_sum() {
+        let mut rng = SplitMix64::new(30421);
+        for &n in &[0, 1, 5, 100, 100_000] {
+            let spacings = uniform_spacings(n, &mut rng);
+            assert_eq!(spacings.len(), n + 1);
+            assert!(spacings.iter().all(|&s| s >= 0.0));
+            assert!((compensated_sum(&spacings) - 1.0).abs() <= f64::EPSILON);
+        }
+        assert_eq!(uniform_spacings(0, &mut rng), vec![1.0]);
+    }
+
+    #[test]
+    fn test_spacings_distribution() {
+        // each of the n + 1 spacings follows Beta(1, n)
+        let mut rng = SplitMix64::new(304212);
+        let n = 4;
+        let mut first: Vec<f64> = (0..20000).map(|_| uniform_spacings(n, &mut rng)[0]).collect();
+        let mut last: Vec<f64> = (0..20000).map(|_| uniform_spacings(n, &mut rng)[n]).collect();
+        let beta = Beta::new(1.0, n as f64).unwrap();
+        for values in [&mut first, &mut last] {
+            values.sort_by(f64::total_cmp);
+            let len = values.len() as f64;
+            let ks = values.iter().enumerate().map(|(i, &v)| {
+                let f = beta.cdf(v);
+                (f - i as f64 / len).max((i + 1) as f64 / len - f)
+            }).fold(0.0, f64::max);
+            assert!(ks < 1.95 / len.sqrt(), "{}", ks);
+        }
+    }
+
+    #[test]
+    fn test_compensated_sum() {
+        assert_eq!(compensated_sum(&[1.0, 1e100, 1.0, -1e100]), 2.0);
+        assert_eq!(compensated_sum(&[]), 0.0);
+        let tenths = vec![0.1; 10];
+        assert_eq!(compensated_sum(&tenths), 1.0);
+    }
+}
@@ -72,7 +72,9 @@ pub mod euclid;
 pub mod function;
 pub mod generate;
 pub mod prec;
+pub mod rng_util;
 pub mod statistics;
+pub mod stats_tests;
 
 mod error;
 
@@ -0,0 +1,424 @@
+use crate::distribution::{Beta, Continuous, ContinuousCDF};
+use crate::function::gamma::ln_gamma;
+use crate::statistics::{Max, Min};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A Bayesian histogram, a density estimate that is constant on equal-width
+/// bins spanning the data, with a symmetric Dirichlet prior on the bin
+/// probabilities
+///
+/// # Remarks
+///
+/// With `K` bins and the prior `Dirichlet(a, ..., a)`, the posterior of the
+/// bin probabilities given the counts `n_k` of `n` observations is
+/// `Dirichlet(a + n_1, ..., a + n_K)`, so the posterior mean density of bin
+/// `k` is
+///
+/// ```text
+/// (a + n_k) / (K a + n) / h
+/// ```
+///
+/// where `h` is the bin width. It shrinks the histogram towards the uniform
+/// density over the range of the data, strongly for small samples, and
+/// converges to the histogram density as `n` grows. The bin probabilities
+/// have `Beta(a + n_k, K a + n - a - n_k)` marginal posteriors, from which
+/// the credible intervals follow. As a distribution it is the mixture of
+/// uniform distributions on the bins with the posterior mean
+/// probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF};
+/// use statrs::statistics::BayesHistogram;
+///
+/// let data = [0.1, 0.2, 0.3, 0.35, 0.8, 1.0];
+/// let hist = BayesHistogram::fit(&data, 1.0, 3).unwrap();
+/// // bins of width 0.3 holding 4, 0 and 2 observations
+/// assert!((hist.pdf(0.2) - 5.0 / 9.0 / 0.3).abs() < 1e-12);
+/// assert!((hist.pdf(0.5) - 1.0 / 9.0 / 0.3).abs() < 1e-12);
+/// assert!((hist.cdf(1.0) - 1.0).abs() < 1e-15);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct BayesHistogram {
+    edges: Vec<f64>,
+    counts: Vec<u64>,
+    prior_pseudo_count: f64,
+    probabilities: Vec<f64>,
+    log_marginal_likelihood: f64,
+}
+
+impl BayesHistogram {
+    /// Fits a Bayesian histogram with `bins` equal-width bins from the
+    /// smallest to the largest observation, with `prior_pseudo_count`
+    /// prior observations in every bin
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two distinct values, a
+    /// value that is not finite or a range too wide to represent, if
+    /// `prior_pseudo_count` is not finite and positive, or if `bins` is zero
+    pub fn fit(data: &[f64], prior_pseudo_count: f64, bins: usize) -> Result<BayesHistogram> {
+        if !prior_pseudo_count.is_finite() || prior_pseudo_count <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("prior_pseudo_count"));
+        }
+        if bins == 0 {
+            return Err(StatsError::ArgMustBePositive("bins"));
+        }
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::SpecialCase("data must be finite"));
+        }
+        let lower = data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let upper = data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        if lower >= upper {
+            return Err(StatsError::SpecialCase(
+                "at least two distinct observations are required",
+            ));
+        }
+        if !(upper - lower).is_finite() {
+            return Err(StatsError::SpecialCase(
+                "the range of the data must be finite",
+            ));
+        }
+        let width = (upper - lower) / bins as f64;
+        let mut edges: Vec<f64> = (0..bins).map(|i| lower + i as f64 * width).collect();
+        edges.push(upper);
+        let mut counts = vec![0; bins];
+        for &x in data {
+            let i = (((x - lower) / width) as usize).min(bins - 1);
+            counts[i] += 1;
+        }
+
+        let n = data.len() as f64;
+        let a = prior_pseudo_count;
+        let total = bins as f64 * a + n;
+        let probabilities = counts.iter().map(|&f| (a + f as f64) / total).collect();
+        // the Dirichlet-multinomial likelihood of the bins times the uniform
+        // densities within them
+        let log_marginal_likelihood = -n * width.ln() + ln_gamma(bins as f64 * a) - ln_gamma(total)
+            + counts
+                .iter()
+                .map(|&f| ln_gamma(a + f as f64) - ln_gamma(a))
+                .sum::<f64>();
+        Ok(BayesHistogram {
+            edges,
+            counts,
+            prior_pseudo_count,
+            probabilities,
+            log_marginal_likelihood,
+        })
+    }
+
+    /// Fits a Bayesian histogram for every number of bins in `candidates`
+    /// and returns the one with the largest marginal likelihood
+    ///
+    /// # Remarks
+    ///
+    /// With `prior_pseudo_count = 0.5` this is the optimal binning of Knuth
+    /// (2006). The chosen number of bins grows with the sample size, as the
+    /// data can support finer detail.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `candidates` is empty or under the conditions of
+    /// `fit`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::BayesHistogram;
+    ///
+    /// let data: Vec<f64> = (0..200).map(|i| (i as f64 / 200.0).powi(2)).collect();
+    /// let candidates: Vec<usize> = (1..=50).collect();
+    /// let hist = BayesHistogram::fit_auto(&data, 0.5, &candidates).unwrap();
+    /// assert!(hist.bins() > 1);
+    /// ```
+    pub fn fit_auto(
+        data: &[f64],
+        prior_pseudo_count: f64,
+        candidates: &[usize],
+    ) -> Result<BayesHistogram> {
+        let mut best: Option<BayesHistogram> = None;
+        for &bins in candidates {
+            let hist = BayesHistogram::fit(data, prior_pseudo_count, bins)?;
+            match best {
+                Some(ref b) if b.log_marginal_likelihood >= hist.log_marginal_likelihood => {}
+                _ => best = Some(hist),
+            }
+        }
+        best.ok_or(StatsError::SpecialCase("candidates must not be empty"))
+    }
+
+    /// Returns the number of bins
+    pub fn bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the bin edges
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the number of observations in every bin
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the prior pseudo-count of every bin
+    pub fn prior_pseudo_count(&self) -> f64 {
+        self.prior_pseudo_count
+    }
+
+    /// Returns the log marginal likelihood of the data given the number of
+    /// bins
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// -n ln(h) + ln Γ(K a) - ln Γ(K a + n) + Σ (ln Γ(a + n_k) - ln Γ(a))
+    /// ```
+    ///
+    /// where `h` is the bin width, `K` the number of bins, `a` the prior
+    /// pseudo-count and `n_k` the count of bin `k`
+    pub fn log_marginal_likelihood(&self) -> f64 {
+        self.log_marginal_likelihood
+    }
+
+    /// Returns the posterior mean densities of the bins
+    pub fn densities(&self) -> Vec<f64> {
+        let width = self.width();
+        self.probabilities.iter().map(|p| p / width).collect()
+    }
+
+    /// Returns the equal-tailed credible interval of the density of `bin`
+    /// with probability `level`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bin` is out of range or `level` is not in
+    /// `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::BayesHistogram;
+    ///
+    /// let hist = BayesHistogram::fit(&[0.0, 0.1, 0.2, 1.0], 1.0, 2).unwrap();
+    /// let (lower, upper) = hist.credible_interval(0, 0.95).unwrap();
+    /// let density = hist.densities()[0];
+    /// assert!(lower < density && density < upper);
+    /// ```
+    pub fn credible_interval(&self, bin: usize, level: f64) -> Result<(f64, f64)> {
+        if bin >= self.bins() {
+            return Err(StatsError::SpecialCase("bin out of range"));
+        }
+        if !(level > 0.0 && level < 1.0) {
+            return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+        }
+        let n: u64 = self.counts.iter().sum();
+        let shape_a = self.prior_pseudo_count + self.counts[bin] as f64;
+        let shape_b = self.bins() as f64 * self.prior_pseudo_count + n as f64 - shape_a;
+        let beta = Beta::new(shape_a, shape_b)?;
+        let width = self.width();
+        let tail = (1.0 - level) / 2.0;
+        Ok((
+            beta.inverse_cdf(tail) / width,
+            beta.inverse_cdf(1.0 - tail) / width,
+        ))
+    }
+
+    fn width(&self) -> f64 {
+        (self.edges[self.bins()] - self.edges[0]) / self.bins() as f64
+    }
+
+    /// Returns the bin containing `x`, the last one for the upper edge
+    fn bin_of(&self, x: f64) -> usize {
+        (((x - self.edges[0]) / self.width()) as usize).min(self.bins() - 1)
+    }
+}
+
+impl Min<f64> for BayesHistogram {
+    /// Returns the lower edge of the first bin, the smallest observation
+    fn min(&self) -> f64 {
+        self.edges[0]
+    }
+}
+
+impl Max<f64> for BayesHistogram {
+    /// Returns the upper edge of the last bin, the largest observation
+    fn max(&self) -> f64 {
+        self.edges[self.bins()]
+    }
+}
+
+impl ContinuousCDF<f64, f64> for BayesHistogram {
+    /// Calculates the posterior mean cumulative distribution function at
+    /// `x`, linear within every bin
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.min() {
+            return 0.0;
+        }
+        if x >= self.max() {
+            return 1.0;
+        }
+        let i = self.bin_of(x);
+        let below: f64 = self.probabilities[..i].iter().sum();
+        let fraction = (x - self.edges[i]) / self.width();
+        (below + fraction * self.probabilities[i]).min(1.0)
+    }
+
+    /// Calculates the inverse of the posterior mean cumulative distribution
+    /// function, exactly by inverting it within the bin
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in `[0, 1]`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let mut below = 0.0;
+        for (i, &prob) in self.probabilities.iter().enumerate() {
+            if p <= below + prob {
+                let fraction = ((p - below) / prob).clamp(0.0, 1.0);
+                return self.edges[i] + fraction * self.width();
+            }
+            below += prob;
+        }
+        self.max()
+    }
+}
+
+impl Continuous<f64, f64> for BayesHistogram {
+    /// Calculates the posterior mean density at `x`, zero outside the range
+    /// of the data
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.min() || x > self.max() {
+            0.0
+        } else {
+            self.probabilities[self.bin_of(x)] / self.width()
+        }
+    }
+
+    /// Calculates the log of the posterior mean density at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        (0..n).map(|_| normal.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.0, 0.5, 0.9, 1.2, 2.7, 3.0];
+        let hist = BayesHistogram::fit(&data, 0.5, 3).unwrap();
+        assert_eq!(hist.bins(), 3);
+        assert_eq!(hist.edges(), &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(hist.counts(), &[3, 1, 2]);
+        assert_eq!(hist.prior_pseudo_count(), 0.5);
+        let densities = hist.densities();
+        for (d, f) in densities.iter().zip(&[3.0, 1.0, 2.0]) {
+            assert_almost_eq!(*d, (0.5 + f) / 7.5, 1e-15);
+        }
+        // the density integrates to one and the cdf is its integral
+        assert_almost_eq!(densities.iter().sum::<f64>(), 1.0, 1e-15);
+        assert_almost_eq!(hist.cdf(1.5), 3.5 / 7.5 + 0.5 * 1.5 / 7.5, 1e-15);
+        assert_eq!(hist.cdf(-1.0), 0.0);
+        assert_eq!(hist.cdf(3.0), 1.0);
+        assert_eq!(hist.pdf(3.0), densities[2]);
+        assert_eq!(hist.pdf(3.1), 0.0);
+        assert_eq!(hist.ln_pdf(-0.1), f64::NEG_INFINITY);
+        assert_almost_eq!(hist.inverse_cdf(3.5 / 7.5), 1.0, 1e-12);
+        assert_almost_eq!(hist.inverse_cdf(hist.cdf(2.4)), 2.4, 1e-12);
+        assert_eq!((hist.inverse_cdf(0.0), hist.inverse_cdf(1.0)), (0.0, 3.0));
+        assert_eq!((hist.min(), hist.max()), (0.0, 3.0));
+    }
+
+    #[test]
+    fn test_log_marginal_likelihood() {
+        // one bin is the uniform distribution over the range
+        let data = [0.0, 0.5, 2.0];
+        let hist = BayesHistogram::fit(&data, 1.0, 1).unwrap();
+        assert_almost_eq!(hist.log_marginal_likelihood(), -3.0 * 2f64.ln(), 1e-12);
+        // two bins with a unit prior: Γ(2) / Γ(5) Γ(3) Γ(2) / (Γ(1)^2) / h^3
+        let hist = BayesHistogram::fit(&data, 1.0, 2).unwrap();
+        assert_almost_eq!(hist.log_marginal_likelihood(), (2.0f64 / 24.0).ln(), 1e-12);
+    }
+
+    #[test]
+    fn test_shrinks_toward_uniform() {
+        let data = [0.0, 0.1, 0.15, 0.2, 4.0];
+        let uniform = 1.0 / 4.0;
+        let weak = BayesHistogram::fit(&data, 0.1, 4).unwrap();
+        let strong = BayesHistogram::fit(&data, 100.0, 4).unwrap();
+        for (w, s) in weak.densities().iter().zip(strong.densities()) {
+            assert!((s - uniform).abs() < (w - uniform).abs());
+            assert!((s - uniform).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_converges_to_histogram() {
+        let data = normal_sample(100_000, 2872);
+        let hist = BayesHistogram::fit(&data, 1.0, 40).unwrap();
+        let width = hist.edges()[1] - hist.edges()[0];
+        for (d, &f) in hist.densities().iter().zip(hist.counts()) {
+            let histogram = f as f64 / 100_000.0 / width;
+            assert!((d - histogram).abs() < 1e-3 * (histogram + 1.0));
+        }
+        // the credible intervals narrow around the histogram density
+        let (lower, upper) = hist.credible_interval(20, 0.95).unwrap();
+        assert!(lower < hist.densities()[20] && hist.densities()[20] < upper);
+        assert!(upper - lower < 0.02);
+        let small = BayesHistogram::fit(&data[..100], 1.0, 40).unwrap();
+        let (small_lower, small_upper) = small.credible_interval(20, 0.95).unwrap();
+        assert!(small_upper - small_lower > 5.0 * (upper - lower));
+    }
+
+    #[test]
+    fn test_fit_auto_grows_with_n() {
+        let candidates: Vec<usize> = (1..=200).collect();
+        let mut chosen = Vec::new();
+        for &n in &[50, 1000, 20_000] {
+            let data = normal_sample(n, 2873 + n as u64);
+            let hist = BayesHistogram::fit_auto(&data, 0.5, &candidates).unwrap();
+            for &bins in &[1, 10, 100] {
+                let other = BayesHistogram::fit(&data, 0.5, bins).unwrap();
+                assert!(hist.log_marginal_likelihood() >= other.log_marginal_likelihood());
+            }
+            chosen.push(hist.bins());
+        }
+        assert!(chosen[0] > 1);
+        assert!(chosen[0] < chosen[1] && chosen[1] < chosen[2]);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(BayesHistogram::fit(&[1.0, 2.0], 0.0, 2).is_err());
+        assert!(BayesHistogram::fit(&[1.0, 2.0], f64::INFINITY, 2).is_err());
+        assert!(BayesHistogram::fit(&[1.0, 2.0], 1.0, 0).is_err());
+        assert!(BayesHistogram::fit(&[1.0, 1.0], 1.0, 2).is_err());
+        assert!(BayesHistogram::fit(&[], 1.0, 2).is_err());
+        assert!(BayesHistogram::fit(&[1.0, f64::NAN], 1.0, 2).is_err());
+        assert!(BayesHistogram::fit(&[-f64::MAX, f64::MAX], 1.0, 2).is_err());
+        assert!(BayesHistogram::fit(&[-f64::MAX, 0.0], 1.0, 2).is_ok());
+        assert!(BayesHistogram::fit_auto(&[1.0, 2.0], 1.0, &[]).is_err());
+        let hist = BayesHistogram::fit(&[1.0, 2.0], 1.0, 2).unwrap();
+        assert!(hist.credible_interval(2, 0.9).is_err());
+        assert!(hist.credible_interval(0, 1.0).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Provides descriptive statistics of directional data, angles in radians
+//! on the circle
+//!
+//! Angles differing by a multiple of `2π` are the same direction, so these
+//! statistics depend on the data only through `cos θ` and `sin θ`.
+
+use std::f64;
+
+/// Returns the means of `cos θ` and `sin θ` over `angles`, or `None` if
+/// `angles` is empty
+pub(crate) fn mean_resultant(angles: &[f64]) -> Option<(f64, f64)> {
+    if angles.is_empty() {
+        return None;
+    }
+    let (c, s) = angles
+        .iter()
+        .fold((0.0, 0.0), |(c, s), x| (c + x.cos(), s + x.sin()));
+    let n = angles.len() as f64;
+    Some((c / n, s / n))
+}
+
+/// Computes the mean direction of `angles`
+///
+/// # Formula
+///
+/// ```text
+/// atan2(Σ sin θ_i / n, Σ cos θ_i / n)
+/// ```
+///
+/// # Remarks
+///
+/// The result is in `(-π, π]`. It is meaningless when the mean resultant
+/// length is close to zero, as for directions spread evenly around the
+/// circle. Returns `f64::NAN` if `angles` is empty or contains `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::circular_mean;
+/// use std::f64::consts::PI;
+///
+/// // the arithmetic mean of these angles would point the opposite way
+/// let mean = circular_mean(&[0.1, 2.0 * PI - 0.3]);
+/// assert!((mean - -0.1).abs() < 1e-12);
+/// ```
+pub fn circular_mean(angles: &[f64]) -> f64 {
+    match mean_resultant(angles) {
+        Some((c, s)) => {
+            let mean = s.atan2(c);
+            if mean == -f64::consts::PI {
+                f64::consts::PI
+            } else {
+                mean
+            }
+        }
+        None => f64::NAN,
+    }
+}
+
+/// Computes the mean resultant length `R` of `angles`, between `0` for
+/// directions that cancel out and `1` for identical directions
+///
+/// # Formula
+///
+/// ```text
+/// R = sqrt((Σ cos θ_i / n)^2 + (Σ sin θ_i / n)^2)
+/// ```
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `angles` is empty or contains `f64::NAN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::mean_resultant_length;
+/// use std::f64::consts::PI;
+///
+/// assert!((mean_resultant_length(&[0.0, PI / 2.0]) - 0.5f64.sqrt()).abs() < 1e-15);
+/// ```
+pub fn mean_resultant_length(angles: &[f64]) -> f64 {
+    match mean_resultant(angles) {
+        Some((c, s)) => c.hypot(s),
+        None => f64::NAN,
+    }
+}
+
+/// Computes the circular variance `1 - R` of `angles`, where `R` is the
+/// mean resultant length, between `0` for identical directions and `1` for
+/// directions that cancel out
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `angles` is empty or contains `f64::NAN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::circular_variance;
+/// use std::f64::consts::PI;
+///
+/// assert!(circular_variance(&[1.0, 1.0 + 2.0 * PI]).abs() < 1e-15);
+/// assert!((circular_variance(&[0.0, PI]) - 1.0).abs() < 1e-15);
+/// ```
+pub fn circular_variance(angles: &[f64]) -> f64 {
+    1.0 - mean_resultant_length(angles)
+}
+
+/// Maps `angle` into `(-π, π]` by adding a multiple of `2π`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `angle` is infinite or `f64::NAN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::wrap_to_pi;
+/// use std::f64::consts::PI;
+///
+/// assert!((wrap_to_pi(3.0 * PI / 2.0) - -PI / 2.0).abs() < 1e-15);
+/// assert_eq!(wrap_to_pi(-PI), PI);
+/// ```
+pub fn wrap_to_pi(angle: f64) -> f64 {
+    if -f64::consts::PI < angle && angle <= f64::consts::PI {
+        return angle;
+    }
+    let wrapped = wrap_to_2pi(angle);
+    if wrapped > f64::consts::PI {
+        wrapped - 2.0 * f64::consts::PI
+    } else {
+        wrapped
+    }
+}
+
+/// Maps `angle` into `[0, 2π)` by adding a multiple of `2π`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `angle` is infinite or `f64::NAN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::wrap_to_2pi;
+/// use std::f64::consts::PI;
+///
+/// assert!((wrap_to_2pi(-PI / 2.0) - 3.0 * PI / 2.0).abs() < 1e-15);
+/// assert_eq!(wrap_to_2pi(2.0 * PI), 0.0);
+/// ```
+pub fn wrap_to_2pi(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * f64::consts::PI);
+    // tiny negative angles round up to exactly 2π
+    if wrapped == 2.0 * f64::consts::PI {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// Removes the `2π` discontinuities from the phase sequence `angles`, as
+/// produced by wrapping a continuously varying phase
+///
+/// Whenever consecutive angles differ by more than `π`, the rest of the
+/// sequence is shifted by the multiple of `2π` that brings the difference
+/// into `[-π, π]`. The first angle is left unchanged.
+///
+/// # Remarks
+///
+/// Steps of exactly `π` are ambiguous and left as they are. A `f64::NAN`
+/// entry stays `f64::NAN` and does not change the shift of later entries.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::circular::{unwrap, wrap_to_pi};
+///
+/// let phase: Vec<f64> = (0..10).map(|i| 0.8 * i as f64).collect();
+/// let wrapped: Vec<f64> = phase.iter().map(|&x| wrap_to_pi(x)).collect();
+/// for (x, y) in unwrap(&wrapped).iter().zip(&phase) {
+///     assert!((x - y).abs() < 1e-14);
+/// }
+/// ```
+pub fn unwrap(angles: &[f64]) -> Vec<f64> {
+    let two_pi = 2.0 * f64::consts::PI;
+    let mut turns = 0.0;
+    let mut previous = f64::NAN;
+    angles
+        .iter()
+        .map(|&x| {
+            let step = x - previous;
+            if step.abs() > f64::consts::PI {
+                turns += ((wrap_to_pi(step) - step) / two_pi).round();
+            }
+            if !x.is_nan() {
+                previous = x;
+            }
+            x + turns * two_pi
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_circular_mean() {
+        assert_almost_eq!(circular_mean(&[0.2, 0.4, 0.6]), 0.4, 1e-15);
+        // across the cut at ±π
+        assert_almost_eq!(circular_mean(&[PI - 0.1, -PI + 0.3]), -PI + 0.1, 1e-14);
+        assert_almost_eq!(circular_mean(&[3.0 * PI / 2.0, -PI / 2.0]), -PI / 2.0, 1e-15);
+        // the mean is wrapped into (-π, π]
+        assert_eq!(circular_mean(&[PI]), PI);
+        assert_eq!(circular_mean(&[-PI]), PI);
+        assert!(circular_mean(&[]).is_nan());
+        assert!(circular_mean(&[1.0, f64::NAN]).is_nan());
+    }
+
+    #[test]
+    fn test_circular_variance() {
+        let clustered: Vec<f64> = (0..50).map(|i| 1.0 + 0.004 * (i as f64 - 25.0)).collect();
+        assert!(circular_variance(&clustered) < 0.005);
+        // shifting by whole turns changes nothing
+        let turned: Vec<f64> = clustered.iter().enumerate().map(|(i, x)| x + 2.0 * PI * (i % 3) as f64).collect();
+        assert_almost_eq!(circular_variance(&turned), circular_variance(&clustered), 1e-14);
+        let spread: Vec<f64> = (0..360).map(|i| (i as f64).to_radians()).collect();
+        assert_almost_eq!(circular_variance(&spread), 1.0, 1e-14);
+        assert_almost_eq!(mean_resultant_length(&[0.0, 0.0, PI]), 1.0 / 3.0, 1e-15);
+        assert!(circular_variance(&[]).is_nan());
+        assert!(mean_resultant_length(&[]).is_nan());
+    }
+
+    #[test]
+    fn test_wrap() {
+        for i in -1000..=1000 {
+            let angle = 0.037 * i as f64;
+            let to_pi = wrap_to_pi(angle);
+            let to_2pi = wrap_to_2pi(angle);
+            assert!(-PI < to_pi && to_pi <= PI);
+            assert!((0.0..2.0 * PI).contains(&to_2pi));
+            // the same direction
+            assert_almost_eq!(to_pi.sin(), angle.sin(), 1e-13);
+            assert_almost_eq!(to_pi.cos(), angle.cos(), 1e-13);
+            assert_almost_eq!(wrap_to_pi(to_2pi), to_pi, 1e-13);
+            // idempotent
+            assert_eq!(wrap_to_pi(to_pi), to_pi);
+            assert_eq!(wrap_to_2pi(to_2pi), to_2pi);
+        }
+        assert_eq!(wrap_to_pi(PI), PI);
+        assert_eq!(wrap_to_pi(-PI), PI);
+        assert_eq!(wrap_to_pi(0.0), 0.0);
+        assert_eq!(wrap_to_2pi(-1e-20), 0.0);
+        assert!(wrap_to_pi(-1e-20) < 0.0);
+        assert!(wrap_to_pi(f64::INFINITY).is_nan());
+        assert!(wrap_to_2pi(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_unwrap() {
+        let phase: Vec<f64> = (0..200).map(|i| 0.3 * i as f64 - 0.002 * (i * i) as f64).collect();
+        let wrapped: Vec<f64> = phase.iter().map(|&x| wrap_to_pi(x)).collect();
+        let unwrapped = unwrap(&wrapped);
+        for (x, y) in unwrapped.iter().zip(&phase) {
+            assert_almost_eq!(*x, *y - (phase[0] - unwrapped[0]), 1e-12);
+        }
+        // an induced jump of a whole turn is removed
+        let mut jumped: Vec<f64> = (0..20).map(|i| 0.1 * i as f64).collect();
+        for x in jumped[8..].iter_mut() {
+            *x -= 2.0 * PI;
+        }
+        for (i, x) in unwrap(&jumped).iter().enumerate() {
+            assert_almost_eq!(*x, 0.1 * i as f64, 1e-14);
+        }
+        // several turns at once
+        assert_almost_eq!(unwrap(&[0.0, 0.5 + 6.0 * PI])[1], 0.5, 1e-14);
+        // a step of exactly π is kept
+        assert_eq!(unwrap(&[0.0, PI, 0.0]), vec![0.0, PI, 0.0]);
+        assert_eq!(unwrap(&[1.0, f64::NAN, 1.0 + 2.0 * PI])[2], 1.0);
+        assert!(unwrap(&[1.0, f64::NAN])[1].is_nan());
+        assert!(unwrap(&[]).is_empty());
+    }
+}
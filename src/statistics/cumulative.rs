@@ -0,0 +1,110 @@
+//! Provides running totals of a sequence, where entry `i` of the result
+//! summarizes the entries `0..=i` of the data
+//!
+//! A `f64::NAN` entry makes every later running total `f64::NAN`.
+
+/// Computes the running sums of `data`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::cumulative::cumulative_sum;
+///
+/// assert_eq!(cumulative_sum(&[1.0, 2.0, 3.0, 4.0]), vec![1.0, 3.0, 6.0, 10.0]);
+/// assert!(cumulative_sum(&[]).is_empty());
+/// ```
+pub fn cumulative_sum(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(0.0, |sum, &x| {
+            *sum += x;
+            Some(*sum)
+        })
+        .collect()
+}
+
+/// Computes the running products of `data`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::cumulative::cumulative_product;
+///
+/// assert_eq!(cumulative_product(&[1.0, 2.0, 3.0, 4.0]), vec![1.0, 2.0, 6.0, 24.0]);
+/// ```
+pub fn cumulative_product(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(1.0, |product, &x| {
+            *product *= x;
+            Some(*product)
+        })
+        .collect()
+}
+
+/// Computes the running means of `data`, so that the last entry is the
+/// mean of all of `data`
+///
+/// # Remarks
+///
+/// Uses the same updating formula as `Statistics::mean`, so the last entry
+/// equals it exactly
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::cumulative::cumulative_mean;
+///
+/// assert_eq!(cumulative_mean(&[1.0, 3.0, 8.0]), vec![1.0, 2.0, 4.0]);
+/// ```
+pub fn cumulative_mean(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .enumerate()
+        .scan(0.0, |mean, (i, &x)| {
+            *mean += (x - *mean) / (i + 1) as f64;
+            Some(*mean)
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::Statistics;
+
+    const DATA: [f64; 6] = [2.5, -1.0, 4.0, 0.5, 3.0, -2.0];
+
+    #[test]
+    fn test_cumulative_sum() {
+        assert_eq!(cumulative_sum(&DATA), vec![2.5, 1.5, 5.5, 6.0, 9.0, 7.0]);
+        assert_eq!(cumulative_sum(&[5.0]), vec![5.0]);
+        assert!(cumulative_sum(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_product() {
+        assert_eq!(cumulative_product(&DATA), vec![2.5, -2.5, -10.0, -5.0, -15.0, 30.0]);
+        assert_eq!(cumulative_product(&[2.0, 0.0, 3.0]), vec![2.0, 0.0, 0.0]);
+        assert!(cumulative_product(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_mean() {
+        let means = cumulative_mean(&DATA);
+        let sums = cumulative_sum(&DATA);
+        for (i, (mean, sum)) in means.iter().zip(&sums).enumerate() {
+            assert_almost_eq!(*mean, sum / (i + 1) as f64, 1e-15);
+            assert_eq!(*mean, DATA[..=i].mean());
+        }
+        assert_eq!(*means.last().unwrap(), DATA.mean());
+        assert!(cumulative_mean(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_nan_propagates() {
+        let data = [1.0, 2.0, f64::NAN, 4.0];
+        for totals in &[cumulative_sum(&data), cumulative_product(&data), cumulative_mean(&data)] {
+            assert!(!totals[1].is_nan());
+            assert!(totals[2].is_nan() && totals[3].is_nan());
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Provides moving-window filters that smooth a series by summarizing each
+//! window of consecutive observations
+//!
+//! The filters return one value per full window, so a series of length `n`
+//! filtered with a window of length `w` gives `n - w + 1` values, the `i`-th
+//! summarizing the observations `i` to `i + w - 1`, and a series shorter
+//! than the window gives none.
+
+use crate::statistics::IndexedData;
+use nalgebra::DMatrix;
+use std::f64;
+
+/// Computes the mean of every window of `window` consecutive observations
+/// of `data`
+///
+/// # Remarks
+///
+/// The mean is updated in constant time per window from a running sum.
+/// A single outlier shifts all the means of the `window` windows holding
+/// it, by its deviation divided by `window`.
+///
+/// # Panics
+///
+/// If `window` is zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::filter::rolling_mean;
+///
+/// assert_eq!(rolling_mean(&[1.0, 2.0, 6.0, 3.0], 2), vec![1.5, 4.0, 4.5]);
+/// assert!(rolling_mean(&[1.0, 2.0], 3).is_empty());
+/// ```
+pub fn rolling_mean(data: &[f64], window: usize) -> Vec<f64> {
+    assert!(window > 0, "the window must not be empty");
+    if data.len() < window {
+        return Vec::new();
+    }
+    let mut sum: f64 = data[..window].iter().sum();
+    let mut means = Vec::with_capacity(data.len() - window + 1);
+    means.push(sum / window as f64);
+    for (entering, leaving) in data[window..].iter().zip(data) {
+        sum += entering - leaving;
+        means.push(sum / window as f64);
+    }
+    means
+}
+
+/// Computes the median of every window of `window` consecutive
+/// observations of `data`, the median of an even window being the mean of
+/// its two middle observations
+///
+/// # Remarks
+///
+/// The window is kept in an `IndexedData`, so every step inserts an
+/// observation and removes another in `O(log w)` expected time, for
+/// `O(n log w)` overall instead of the `O(n w)` of sorting every window,
+/// with the same results. While fewer than half of the observations of a
+/// window are outliers, its median lies between clean observations, so
+/// the filter removes isolated spikes and keeps steps, which a mean filter
+/// smears.
+///
+/// # Panics
+///
+/// If `window` is zero or `data` contains a `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::filter::rolling_median;
+///
+/// let spiky = [1.0, 1.1, 50.0, 1.2, 1.0, 0.9];
+/// assert_eq!(rolling_median(&spiky, 3), vec![1.1, 1.2, 1.2, 1.0]);
+/// ```
+pub fn rolling_median(data: &[f64], window: usize) -> Vec<f64> {
+    assert!(window > 0, "the window must not be empty");
+    if data.len() < window {
+        return Vec::new();
+    }
+    let mut index = IndexedData::new(&data[..window]);
+    let mut medians = Vec::with_capacity(data.len() - window + 1);
+    medians.push(index.median());
+    for (&entering, &leaving) in data[window..].iter().zip(data) {
+        index.insert(entering);
+        index.remove(leaving);
+        medians.push(index.median());
+    }
+    medians
+}
+
+/// Computes the convolution coefficients of the Savitzky-Golay filter,
+/// the weights of the observations of a window of odd length `window` in
+/// the value at its centre of the least-squares polynomial of degree
+/// `poly_order` through them
+///
+/// # Formula
+///
+/// ```text
+/// c = e_0' (J' J)^(-1) J',  J_ij = z_i^j,  z_i = (i - h) / h
+/// ```
+///
+/// for `i = 0, ..., window - 1`, `j = 0, ..., poly_order` and
+/// `h = (window - 1) / 2`, the positions being scaled to `[-1, 1]` so that
+/// the normal equations stay well conditioned.
+///
+/// # Remarks
+///
+/// The coefficients sum to one and are symmetric, so the filter preserves
+/// polynomials of degree `poly_order`, and of degree `poly_order + 1` when
+/// `poly_order` is even. A polynomial of degree zero gives the moving
+/// average.
+///
+/// # Panics
+///
+/// If `window` is even or if `poly_order >= window`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::filter::savitzky_golay_coefficients;
+///
+/// let c = savitzky_golay_coefficients(5, 2);
+/// let expected = [-3.0, 12.0, 17.0, 12.0, -3.0];
+/// for (a, b) in c.iter().zip(&expected) {
+///     assert!((a - b / 35.0).abs() < 1e-15);
+/// }
+/// ```
+pub fn savitzky_golay_coefficients(window: usize, poly_order: usize) -> Vec<f64> {
+    assert!(
+        window & 1 == 1,
+        "the window must have an odd length, was {}",
+        window
+    );
+    assert!(
+        poly_order < window,
+        "the polynomial order must be less than the window, was {}",
+        poly_order
+    );
+    if window == 1 {
+        return vec![1.0];
+    }
+    let h = (window / 2) as f64;
+    let design = DMatrix::from_fn(window, poly_order + 1, |i, j| {
+        ((i as f64 - h) / h).powi(j as i32)
+    });
+    let gram = design.transpose() * &design;
+    let projection = gram
+        .cholesky()
+        .expect("the design of distinct positions has full rank")
+        .solve(&design.transpose());
+    projection.row(0).iter().copied().collect()
+}
+
+/// Smooths `data` with the Savitzky-Golay filter, the value at the centre
+/// of every window of `window` consecutive observations of the
+/// least-squares polynomial of degree `poly_order` through them
+///
+/// # Remarks
+///
+/// The filter is the convolution with `savitzky_golay_coefficients`,
+/// computed once, so it takes `O(n w)` time. It preserves polynomials of
+/// degree up to `poly_order` exactly, and so the height and width of peaks
+/// and the derivatives of smooth signals much better than a moving
+/// average of the same window, which it is for `poly_order = 0`. The
+/// noise of independent observations of variance `σ^2` is reduced to a
+/// variance of `σ^2 Σ c_i^2`.
+///
+/// # Panics
+///
+/// If `window` is even or if `poly_order >= window`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::filter::savitzky_golay;
+///
+/// // a parabola is reproduced by a quadratic filter
+/// let data: Vec<f64> = (0..9).map(|i| (i * i) as f64).collect();
+/// let smoothed = savitzky_golay(&data, 5, 2);
+/// assert_eq!(smoothed.len(), 5);
+/// assert!((smoothed[0] - 4.0).abs() < 1e-12);
+/// assert!((smoothed[4] - 36.0).abs() < 1e-12);
+/// ```
+pub fn savitzky_golay(data: &[f64], window: usize, poly_order: usize) -> Vec<f64> {
+    let coefficients = savitzky_golay_coefficients(window, poly_order);
+    data.windows(window)
+        .map(|w| w.iter().zip(&coefficients).map(|(x, c)| x * c).sum())
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_util::SplitMix64;
+    use crate::statistics::{Data, OrderStatistics};
+    use rand::Rng;
+
+    /// A smooth signal with a spike every 17 samples
+    fn spiky(n: usize) -> (Vec<f64>, Vec<f64>) {
+        let clean: Vec<f64> = (0..n).map(|i| (i as f64 * 0.05).sin()).collect();
+        let mut data = clean.clone();
+        for i in (5..n).step_by(17) {
+            data[i] += if i % 2 == 0 { 40.0 } else { -40.0 };
+        }
+        (clean, data)
+    }
+
+    #[test]
+    fn test_rolling_median_matches_sorting() {
+        let (_, data) = spiky(300);
+        let mut data = data;
+        // ties and repeated values
+        for i in (0..300).step_by(7) {
+            data[i] = 0.5;
+        }
+        for &window in &[1, 2, 3, 4, 10, 31, 300] {
+            let medians = rolling_median(&data, window);
+            assert_eq!(medians.len(), data.len() - window + 1);
+            for (i, m) in medians.iter().enumerate() {
+                let mut exact = Data::new(data[i..i + window].to_vec());
+                assert_eq!(*m, exact.median());
+            }
+        }
+        assert!(rolling_median(&data, 301).is_empty());
+        assert!(rolling_median(&[], 1).is_empty());
+    }
+
+    #[test]
+    fn test_rolling_mean_matches_direct() {
+        let (_, data) = spiky(200);
+        for &window in &[1, 5, 16, 200] {
+            let means = rolling_mean(&data, window);
+            assert_eq!(means.len(), data.len() - window + 1);
+            for (i, m) in means.iter().enumerate() {
+                let exact = data[i..i + window].iter().sum::<f64>() / window as f64;
+                assert_almost_eq!(*m, exact, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_despiking() {
+        let (clean, data) = spiky(400);
+        let window = 5;
+        let half = window / 2;
+        let medians = rolling_median(&data, window);
+        let means = rolling_mean(&data, window);
+        let clean_means = rolling_mean(&clean, window);
+        // the medians stay within the local range of the clean signal, the
+        // means are thrown off by every spike
+        let mut worst_median: f64 = 0.0;
+        let mut worst_mean: f64 = 0.0;
+        for i in 0..medians.len() {
+            worst_median = worst_median.max((medians[i] - clean[i + half]).abs());
+            worst_mean = worst_mean.max((means[i] - clean_means[i]).abs());
+        }
+        assert!(worst_median < 0.15);
+        assert!(worst_mean > 7.9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_window() {
+        rolling_median(&[1.0], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nan() {
+        rolling_median(&[1.0, f64::NAN, 2.0], 2);
+    }
+
+    #[test]
+    fn test_savitzky_golay_coefficients() {
+        // the tables of Savitzky and Golay (1964)
+        let check = |window, order, expected: &[f64], norm: f64| {
+            let c = savitzky_golay_coefficients(window, order);
+            assert_eq!(c.len(), expected.len());
+            for (a, b) in c.iter().zip(expected) {
+                assert_almost_eq!(*a, b / norm, 1e-14);
+            }
+        };
+        check(5, 2, &[-3.0, 12.0, 17.0, 12.0, -3.0], 35.0);
+        check(7, 3, &[-2.0, 3.0, 6.0, 7.0, 6.0, 3.0, -2.0], 21.0);
+        check(9, 4, &[15.0, -55.0, 30.0, 135.0, 179.0, 135.0, 30.0, -55.0, 15.0], 429.0);
+        check(5, 0, &[1.0; 5], 5.0);
+        check(1, 0, &[1.0], 1.0);
+        // an interpolating polynomial reproduces the data
+        check(5, 4, &[0.0, 0.0, 1.0, 0.0, 0.0], 1.0);
+    }
+
+    #[test]
+    fn test_savitzky_golay_preserves_polynomials() {
+        let cubic = |t: f64| 2.0 - 0.5 * t + 0.03 * t * t - 0.001 * t * t * t;
+        let data: Vec<f64> = (0..100).map(|i| cubic(i as f64)).collect();
+        for &(window, order) in &[(7, 3), (11, 3), (21, 5), (9, 2)] {
+            let smoothed = savitzky_golay(&data, window, order);
+            assert_eq!(smoothed.len(), data.len() - window + 1);
+            for (i, s) in smoothed.iter().enumerate() {
+                assert_almost_eq!(*s, cubic((i + window / 2) as f64), 1e-9);
+            }
+        }
+        // a linear filter does not follow the curvature
+        let smoothed = savitzky_golay(&data, 11, 1);
+        assert!((smoothed[40] - cubic(45.0)).abs() > 0.1);
+    }
+
+    #[test]
+    fn test_savitzky_golay_reduces_noise() {
+        let mut rng = SplitMix64::new(301);
+        let clean: Vec<f64> = (0..500).map(|i| (i as f64 * 0.03).sin() * 3.0).collect();
+        let noisy: Vec<f64> = clean.iter().map(|x| x + rng.gen::<f64>() - 0.5).collect();
+        let (window, order) = (21, 3);
+        let smoothed = savitzky_golay(&noisy, window, order);
+        let rms = |a: &[f64], b: &[f64]| {
+            (a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f64>() / a.len() as f64).sqrt()
+        };
+        let centre = &clean[window / 2..clean.len() - window / 2];
+        let raw = rms(&noisy[window / 2..noisy.len() - window / 2], centre);
+        let filtered = rms(&smoothed, centre);
+        // uniform noise has variance 1 / 12, reduced by Σ c_i^2
+        let reduction: f64 = savitzky_golay_coefficients(window, order).iter().map(|c| c * c).sum();
+        assert!(filtered < 0.6 * raw);
+        assert_almost_eq!(filtered, (reduction / 12.0).sqrt(), 0.02);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_savitzky_golay_even_window() {
+        savitzky_golay(&[1.0; 10], 4, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_savitzky_golay_order_too_high() {
+        savitzky_golay(&[1.0; 10], 5, 5);
+    }
+}
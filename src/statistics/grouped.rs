@@ -0,0 +1,509 @@
+//! Provides summary statistics of [grouped
+//! data](https://en.wikipedia.org/wiki/Grouped_data), observations known
+//! only through the number falling in each of a set of bins
+
+use crate::distribution::Empirical;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A frequency table of observations grouped into contiguous bins
+///
+/// # Remarks
+///
+/// Bin `i` covers `[edges[i], edges[i + 1])`. The first edge may be
+/// `f64::NEG_INFINITY` and the last `f64::INFINITY` to represent open-ended
+/// bins such as "under 18" or "65 and over". Observations in an open-ended
+/// bin have no midpoint, so the moments and `to_empirical` return an error
+/// when such a bin is not empty, while quantiles are available as long as
+/// they do not fall in it.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::grouped::GroupedData;
+///
+/// let data = GroupedData::new(&[0.0, 10.0, 20.0, 30.0], &[2, 5, 3]).unwrap();
+/// assert_eq!(data.mean().unwrap(), 16.0);
+/// assert_eq!(data.median().unwrap(), 16.0);
+/// assert_eq!(data.mode_bin(), 1);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct GroupedData {
+    edges: Vec<f64>,
+    counts: Vec<u64>,
+    total: u64,
+}
+
+impl GroupedData {
+    /// Constructs a frequency table from the `bin_edges` and the `counts` of
+    /// the bins between consecutive edges
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not exactly one more edge than counts,
+    /// if the edges are not strictly increasing, if an edge is `NaN` or an
+    /// interior edge is infinite, or if all counts are zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::grouped::GroupedData;
+    ///
+    /// assert!(GroupedData::new(&[f64::NEG_INFINITY, 0.0, 1.0], &[1, 4]).is_ok());
+    /// assert!(GroupedData::new(&[0.0, 1.0], &[1, 4]).is_err());
+    /// assert!(GroupedData::new(&[0.0, 1.0, 1.0], &[1, 4]).is_err());
+    /// ```
+    pub fn new(bin_edges: &[f64], counts: &[u64]) -> Result<GroupedData> {
+        if counts.is_empty() || bin_edges.len() != counts.len() + 1 {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if bin_edges.iter().any(|e| e.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        let last = bin_edges.len() - 1;
+        if bin_edges[0] == f64::INFINITY
+            || bin_edges[last] == f64::NEG_INFINITY
+            || bin_edges[1..last].iter().any(|e| e.is_infinite())
+        {
+            return Err(StatsError::SpecialCase(
+                "only the first and last bin edges may be infinite",
+            ));
+        }
+        if bin_edges.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(StatsError::SpecialCase(
+                "bin edges must be strictly increasing",
+            ));
+        }
+        let total = counts.iter().sum();
+        if total == 0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(GroupedData {
+            edges: bin_edges.to_vec(),
+            counts: counts.to_vec(),
+            total,
+        })
+    }
+
+    /// Returns the bin edges
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the count of every bin
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the total number of observations
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the midpoints and counts of the non-empty bins, or an error
+    /// if one of them is open-ended
+    fn midpoints(&self) -> Result<Vec<(f64, f64)>> {
+        let mut points = Vec::with_capacity(self.counts.len());
+        for (w, &f) in self.edges.windows(2).zip(&self.counts) {
+            if f == 0 {
+                continue;
+            }
+            if w[0].is_infinite() || w[1].is_infinite() {
+                return Err(StatsError::SpecialCase(
+                    "moments are undefined with observations in an open-ended bin",
+                ));
+            }
+            points.push((0.5 * (w[0] + w[1]), f as f64));
+        }
+        Ok(points)
+    }
+
+    /// Returns the mean of the grouped data, approximating every
+    /// observation by the midpoint of its bin
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ f_i m_i / n
+    /// ```
+    ///
+    /// where `f_i` and `m_i` are the count and midpoint of bin `i` and
+    /// `n` the total count
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an open-ended bin is not empty
+    pub fn mean(&self) -> Result<f64> {
+        let points = self.midpoints()?;
+        Ok(points.iter().map(|(m, f)| m * f).sum::<f64>() / self.total as f64)
+    }
+
+    /// Returns the unbiased sample variance of the grouped data,
+    /// approximating every observation by the midpoint of its bin and, when
+    /// `sheppard` is set, applying Sheppard's correction for grouping
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ f_i (m_i - x̄)^2 / (n - 1) - Σ f_i h_i^2 / (12 n)
+    /// ```
+    ///
+    /// where `f_i`, `m_i` and `h_i` are the count, midpoint and width of bin
+    /// `i`, `x̄` the grouped mean and `n` the total count. The second term
+    /// is only subtracted with `sheppard`; for bins of common width `h` it
+    /// is the textbook `h^2 / 12`.
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there is a single observation. The correction
+    /// assumes a smooth density that vanishes towards both ends of the
+    /// range and may make the variance negative for very coarse bins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an open-ended bin is not empty
+    pub fn variance(&self, sheppard: bool) -> Result<f64> {
+        let points = self.midpoints()?;
+        let n = self.total as f64;
+        if self.total < 2 {
+            return Ok(f64::NAN);
+        }
+        let mean = points.iter().map(|(m, f)| m * f).sum::<f64>() / n;
+        let grouped = points
+            .iter()
+            .map(|(m, f)| f * (m - mean) * (m - mean))
+            .sum::<f64>()
+            / (n - 1.0);
+        if !sheppard {
+            return Ok(grouped);
+        }
+        let correction = self
+            .edges
+            .windows(2)
+            .zip(&self.counts)
+            .filter(|(_, &f)| f > 0)
+            .map(|(w, &f)| f as f64 * (w[1] - w[0]) * (w[1] - w[0]))
+            .sum::<f64>()
+            / (12.0 * n);
+        Ok(grouped - correction)
+    }
+
+    /// Returns the `p`-th quantile of the grouped data, assuming the
+    /// observations to be spread uniformly within their bin
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// L + (p n - F) / f * h
+    /// ```
+    ///
+    /// where `L`, `h` and `f` are the lower edge, width and count of the bin
+    /// containing the `p n`-th observation and `F` the number of
+    /// observations below that bin
+    ///
+    /// # Remarks
+    ///
+    /// `p = 0` gives the lower edge of the first non-empty bin and `p = 1`
+    /// the upper edge of the last one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `p` is not in `[0, 1]` or if the quantile falls
+    /// in an open-ended bin
+    pub fn quantile(&self, p: f64) -> Result<f64> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(StatsError::ArgIntervalIncl("p", 0.0, 1.0));
+        }
+        let (i, fraction) = self.locate(p);
+        let (lower, upper) = (self.edges[i], self.edges[i + 1]);
+        if lower.is_infinite() || upper.is_infinite() {
+            return Err(StatsError::SpecialCase(
+                "the quantile falls in an open-ended bin",
+            ));
+        }
+        Ok(lower + fraction * (upper - lower))
+    }
+
+    /// Returns the index of the bin holding the `p n`-th observation and
+    /// the fraction of the bin below it
+    fn locate(&self, p: f64) -> (usize, f64) {
+        let target = p * self.total as f64;
+        let mut below = 0.0;
+        // the bin holding the target is the first non-empty one whose
+        // cumulative count reaches it
+        for (i, &f) in self.counts.iter().enumerate() {
+            if f == 0 {
+                continue;
+            }
+            let f = f as f64;
+            if below + f >= target {
+                return (i, ((target - below) / f).clamp(0.0, 1.0));
+            }
+            below += f;
+        }
+        unreachable!("the counts sum to the total")
+    }
+
+    /// Returns the median of the grouped data, see `quantile`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the median falls in an open-ended bin
+    pub fn median(&self) -> Result<f64> {
+        self.quantile(0.5)
+    }
+
+    /// Returns the index of the modal bin, the one with the largest count
+    /// per unit width
+    ///
+    /// # Remarks
+    ///
+    /// Open-ended bins have zero density and ties go to the first bin
+    pub fn mode_bin(&self) -> usize {
+        let mut best = 0;
+        let mut best_density = f64::NEG_INFINITY;
+        for (i, (w, &f)) in self.edges.windows(2).zip(&self.counts).enumerate() {
+            let density = f as f64 / (w[1] - w[0]);
+            if density > best_density {
+                best = i;
+                best_density = density;
+            }
+        }
+        best
+    }
+
+    /// Converts the grouped data to an approximate `Empirical`
+    /// distribution placing the observations of every bin at its midpoint
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an open-ended bin is not empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::grouped::GroupedData;
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let data = GroupedData::new(&[0.0, 10.0, 20.0, 30.0], &[2, 5, 3]).unwrap();
+    /// let empirical = data.to_empirical().unwrap();
+    /// assert_eq!(empirical.mean().unwrap(), 16.0);
+    /// ```
+    pub fn to_empirical(&self) -> Result<Empirical> {
+        let mut empirical = Empirical::new()?;
+        for (m, f) in self.midpoints()? {
+            for _ in 0..f as u64 {
+                empirical.add(m);
+            }
+        }
+        Ok(empirical)
+    }
+}
+
+/// Computes the `tau`-quantile of pre-binned data, as Prometheus'
+/// `histogram_quantile`, interpolating linearly within the bin where the
+/// cumulative count crosses `tau` times the total count
+///
+/// # Formula
+///
+/// ```text
+/// L + (τ n - F) / f * h
+/// ```
+///
+/// where `L`, `h` and `f` are the lower edge, width and count of the bin
+/// where the cumulative count reaches `τ n` and `F` the number of
+/// observations below that bin, see `GroupedData::quantile`
+///
+/// # Remarks
+///
+/// The first edge may be `f64::NEG_INFINITY` and the last `f64::INFINITY`;
+/// a quantile falling in such an open-ended bin is its finite edge. Returns
+/// `f64::NAN` if all counts are zero.
+///
+/// # Panics
+///
+/// If `edges` does not have one more entry than `counts`, if `tau` is not in
+/// `[0, 1]` or if the edges are not valid bin edges for `GroupedData::new`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::grouped::histogram_quantile;
+///
+/// // 7 of the 10 observations are below 20, so the median is in [10, 20)
+/// let edges = [0.0, 10.0, 20.0, 30.0];
+/// assert_eq!(histogram_quantile(&edges, &[2, 5, 3], 0.5), 16.0);
+/// ```
+pub fn histogram_quantile(edges: &[f64], counts: &[u64], tau: f64) -> f64 {
+    if edges.len() != counts.len() + 1 {
+        panic!(
+            "edges must have one more entry than counts, got {} and {}",
+            edges.len(),
+            counts.len()
+        );
+    }
+    if !(0.0..=1.0).contains(&tau) {
+        panic!("tau must be in [0, 1], was {}", tau);
+    }
+    if counts.iter().all(|&f| f == 0) {
+        return f64::NAN;
+    }
+    let data = GroupedData::new(edges, counts).unwrap_or_else(|e| panic!("{}", e));
+    let (i, fraction) = data.locate(tau);
+    let (lower, upper) = (edges[i], edges[i + 1]);
+    if lower.is_infinite() {
+        upper
+    } else if upper.is_infinite() {
+        lower
+    } else {
+        lower + fraction * (upper - lower)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use crate::statistics::{Data, OrderStatistics, Statistics};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn bin(samples: &[f64], width: f64, lo: f64, hi: f64) -> GroupedData {
+        let bins = ((hi - lo) / width).round() as usize;
+        let mut edges = vec![f64::NEG_INFINITY];
+        edges.extend((0..=bins).map(|i| lo + i as f64 * width));
+        edges.push(f64::INFINITY);
+        let mut counts = vec![0; bins + 2];
+        for &x in samples {
+            let i = if x < lo { 0 } else if x >= hi { bins + 1 } else { ((x - lo) / width) as usize + 1 };
+            counts[i] += 1;
+        }
+        GroupedData::new(&edges, &counts).unwrap()
+    }
+
+    #[test]
+    fn test_create() {
+        assert!(GroupedData::new(&[0.0, 1.0, 2.0], &[0, 3]).is_ok());
+        assert!(GroupedData::new(&[0.0, 1.0, 2.0], &[0, 0]).is_err());
+        assert!(GroupedData::new(&[0.0], &[]).is_err());
+        assert!(GroupedData::new(&[0.0, 2.0, 1.0], &[1, 1]).is_err());
+        assert!(GroupedData::new(&[0.0, f64::NAN, 1.0], &[1, 1]).is_err());
+        assert!(GroupedData::new(&[0.0, f64::INFINITY, 1.0], &[1, 1]).is_err());
+        assert!(GroupedData::new(&[f64::INFINITY, 0.0], &[1]).is_err());
+        let data = GroupedData::new(&[f64::NEG_INFINITY, 0.0, f64::INFINITY], &[1, 1]).unwrap();
+        assert_eq!(data.total(), 2);
+        assert_eq!(data.edges().len(), 3);
+        assert_eq!(data.counts(), &[1, 1]);
+    }
+
+    #[test]
+    fn test_fine_bins_reconstruct_sample() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let normal = Normal::new(10.0, 2.0).unwrap();
+        let samples: Vec<f64> = (0..20000).map(|_| normal.sample(&mut rng)).collect();
+        let data = bin(&samples, 0.05, -5.0, 25.0);
+        // the midpoint approximation moves every observation by at most half
+        // a bin width
+        assert!((data.mean().unwrap() - samples.iter().mean()).abs() < 0.025);
+        assert!((data.variance(true).unwrap() - samples.iter().variance()).abs() < 2e-3);
+        let mut sorted = Data::new(samples.clone());
+        for &p in &[0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            assert!((data.quantile(p).unwrap() - sorted.quantile(p)).abs() < 0.05);
+        }
+        assert!((data.median().unwrap() - sorted.median()).abs() < 0.05);
+        // the modal bin is near the center of the normal
+        let mode = data.mode_bin();
+        assert!((data.edges()[mode] - 10.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_sheppard() {
+        // the correction is h^2 / 12 for bins of common width h
+        let data = GroupedData::new(&[0.0, 2.0, 4.0, 6.0, 8.0], &[3, 7, 9, 1]).unwrap();
+        let midpoints = [1.0, 3.0, 5.0, 7.0];
+        let counts = [3.0, 7.0, 9.0, 1.0];
+        let mean = midpoints.iter().zip(&counts).map(|(m, f)| m * f).sum::<f64>() / 20.0;
+        assert_almost_eq!(data.mean().unwrap(), mean, 1e-15);
+        let variance = midpoints.iter().zip(&counts).map(|(m, f)| f * (m - mean) * (m - mean)).sum::<f64>() / 19.0;
+        assert_almost_eq!(data.variance(false).unwrap(), variance, 1e-14);
+        assert_almost_eq!(data.variance(true).unwrap(), variance - 4.0 / 12.0, 1e-14);
+        // on coarse bins the grouped variance of a normal sample is inflated
+        // by about h^2 / 12, which the correction removes
+        let mut rng = StdRng::seed_from_u64(3);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let samples: Vec<f64> = (0..50000).map(|_| normal.sample(&mut rng)).collect();
+        let coarse = bin(&samples, 0.8, -8.0, 8.0);
+        let raw = samples.iter().variance();
+        let uncorrected = coarse.variance(false).unwrap();
+        let corrected = coarse.variance(true).unwrap();
+        assert_almost_eq!(uncorrected - corrected, 0.64 / 12.0, 1e-12);
+        assert!((corrected - raw).abs() < 0.2 * (uncorrected - raw).abs());
+    }
+
+    #[test]
+    fn test_open_ended() {
+        let data = GroupedData::new(&[f64::NEG_INFINITY, 18.0, 40.0, 65.0, f64::INFINITY], &[10, 40, 30, 20]).unwrap();
+        assert!(data.mean().is_err());
+        assert!(data.variance(false).is_err());
+        assert!(data.to_empirical().is_err());
+        assert!(data.quantile(0.05).is_err());
+        assert!(data.quantile(0.95).is_err());
+        // 50 observations are below 40, so the median is the upper edge
+        assert_almost_eq!(data.median().unwrap(), 40.0, 1e-12);
+        assert_almost_eq!(data.quantile(0.3).unwrap(), 18.0 + 20.0 / 40.0 * 22.0, 1e-12);
+        assert_almost_eq!(data.quantile(0.65).unwrap(), 40.0 + 15.0 / 30.0 * 25.0, 1e-12);
+        // empty open-ended bins do not prevent the moments
+        let closed = GroupedData::new(&[f64::NEG_INFINITY, 0.0, 1.0, f64::INFINITY], &[0, 4, 0]).unwrap();
+        assert_eq!(closed.mean().unwrap(), 0.5);
+        assert_eq!(closed.quantile(0.0).unwrap(), 0.0);
+        assert_eq!(closed.quantile(1.0).unwrap(), 1.0);
+        assert_eq!(closed.mode_bin(), 1);
+    }
+
+    #[test]
+    fn test_to_empirical() {
+        use crate::statistics::Distribution;
+        let data = GroupedData::new(&[0.0, 1.0, 3.0], &[2, 2]).unwrap();
+        let empirical = data.to_empirical().unwrap();
+        assert_eq!(empirical.mean().unwrap(), 1.25);
+        assert_almost_eq!(empirical.variance().unwrap(), data.variance(false).unwrap(), 1e-14);
+        assert!(data.quantile(1.5).is_err());
+        assert!(GroupedData::new(&[0.0, 1.0], &[1]).unwrap().variance(false).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_histogram_quantile() {
+        // the request latencies of a Prometheus histogram in seconds, with
+        // 420 of the 600 observations below 0.25
+        let edges = [0.0, 0.05, 0.1, 0.25, 0.5, 1.0, f64::INFINITY];
+        let counts = [120, 180, 120, 90, 60, 30];
+        // the 300th observation is the last of [0.05, 0.1)
+        assert_almost_eq!(histogram_quantile(&edges, &counts, 0.5), 0.1, 1e-15);
+        assert_almost_eq!(histogram_quantile(&edges, &counts, 0.6), 0.1 + 60.0 / 120.0 * 0.15, 1e-15);
+        assert_almost_eq!(histogram_quantile(&edges, &counts, 0.9), 0.5 + 30.0 / 60.0 * 0.5, 1e-15);
+        assert_eq!(histogram_quantile(&edges, &counts, 0.0), 0.0);
+        // the quantiles in the open-ended bin are its lower edge
+        assert_eq!(histogram_quantile(&edges, &counts, 0.99), 1.0);
+        assert_eq!(histogram_quantile(&edges, &counts, 1.0), 1.0);
+        let data = GroupedData::new(&edges, &counts).unwrap();
+        for &tau in &[0.1, 0.25, 0.5, 0.75, 0.9] {
+            assert_eq!(histogram_quantile(&edges, &counts, tau), data.quantile(tau).unwrap());
+        }
+        assert_eq!(histogram_quantile(&[f64::NEG_INFINITY, 0.0, 1.0], &[3, 1], 0.5), 0.0);
+        assert!(histogram_quantile(&[0.0, 1.0], &[0], 0.5).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_histogram_quantile_bad_edges() {
+        histogram_quantile(&[0.0, 1.0], &[1, 2], 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_histogram_quantile_bad_tau() {
+        histogram_quantile(&[0.0, 1.0, 2.0], &[1, 2], 1.5);
+    }
+}
@@ -0,0 +1,371 @@
+//! Provides [Holt-Winters exponential
+//! smoothing](https://en.wikipedia.org/wiki/Exponential_smoothing#Triple_exponential_smoothing_(Holt_Winters))
+//! of time series with a level, a trend and an optional additive seasonal
+//! component
+
+use crate::function::optimize::brent_min;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The tolerance on the smoothing parameters in `HoltWinters::optimize`
+const PARAMETER_TOLERANCE: f64 = 1e-6;
+
+/// The maximum number of sweeps over the smoothing parameters in
+/// `HoltWinters::optimize`
+const MAX_SWEEPS: usize = 50;
+
+/// A Holt-Winters model fitted to a time series, which forecasts by
+/// extrapolating the smoothed level and trend and repeating the smoothed
+/// seasonal pattern
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::holt_winters::HoltWinters;
+///
+/// // a linear trend with a period of four
+/// let pattern = [1.0, -2.0, 3.0, -2.0];
+/// let data: Vec<f64> = (0..20).map(|t| 5.0 + 0.5 * t as f64 + pattern[t % 4]).collect();
+/// let model = HoltWinters::fit(&data, 4, 0.5, 0.2, 0.3).unwrap();
+/// let forecast = model.forecast(2);
+/// assert!((forecast[0] - (5.0 + 0.5 * 20.0 + 1.0)).abs() < 1e-12);
+/// assert!((forecast[1] - (5.0 + 0.5 * 21.0 - 2.0)).abs() < 1e-12);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct HoltWinters {
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    level: f64,
+    trend: f64,
+    seasonal: Vec<f64>,
+    fitted: Vec<f64>,
+    sse: f64,
+}
+
+impl HoltWinters {
+    /// Fits the model to `data` with the smoothing parameters `alpha` of
+    /// the level, `beta` of the trend and `gamma` of the seasonal
+    /// component, whose period is `season_length`, or without a seasonal
+    /// component if `season_length` is zero
+    ///
+    /// # Formula
+    ///
+    /// With `m = season_length`, the components at time `t` are updated as
+    ///
+    /// ```text
+    /// l_t = α (x_t - s_{t-m}) + (1 - α) (l_{t-1} + b_{t-1})
+    /// b_t = β (l_t - l_{t-1}) + (1 - β) b_{t-1}
+    /// s_t = γ (x_t - l_t) + (1 - γ) s_{t-m}
+    /// ```
+    ///
+    /// where the seasonal terms are zero without a seasonal component, and
+    /// the one step ahead prediction of `x_t` is `l_{t-1} + b_{t-1} + s_{t-m}`.
+    ///
+    /// # Remarks
+    ///
+    /// Without a seasonal component, the smoothing starts from `l_1 = x_1`
+    /// and `b_1 = x_1 - x_0`, as R's `HoltWinters`. With one, it starts from
+    /// the line through the means of the first two seasons, the trend
+    /// being their difference divided by `m`, and from the deviations of
+    /// the first season from that line, so a series that is exactly a
+    /// line plus a periodic pattern is fitted and forecast exactly. The
+    /// predictions and the sum of squared errors cover the observations
+    /// after the starting ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha`, `beta` or `gamma` is not in `[0, 1]`,
+    /// if `data` contains a value that is not finite, if `season_length`
+    /// is one, or if `data` has fewer than three observations without a
+    /// seasonal component or fewer than two full seasons and one more
+    /// observation with one
+    pub fn fit(
+        data: &[f64],
+        season_length: usize,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+    ) -> Result<HoltWinters> {
+        check_data(data, season_length)?;
+        for &parameter in &[alpha, beta, gamma] {
+            if !(0.0..=1.0).contains(&parameter) {
+                return Err(StatsError::BadParams);
+            }
+        }
+        Ok(smooth(data, season_length, alpha, beta, gamma))
+    }
+
+    /// Fits the model to `data` with the smoothing parameters that minimize
+    /// the sum of squared one step ahead prediction errors, see `fit`
+    ///
+    /// # Remarks
+    ///
+    /// The parameters are optimized one at a time over `[0, 1]` with
+    /// Brent's method, sweeping over them from `α = 0.3`, `β = 0.1` and
+    /// `γ = 0.1` until a sweep no longer reduces the sum of squared errors.
+    /// This finds a local minimum, which is usually, but not necessarily,
+    /// the global one. Without a seasonal component `γ` is not optimized
+    /// and is zero.
+    ///
+    /// # Errors
+    ///
+    /// As `fit`, except for the conditions on the smoothing parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::holt_winters::HoltWinters;
+    ///
+    /// let data: Vec<f64> = (0..30).map(|t| t as f64 + (t as f64).sin()).collect();
+    /// let optimized = HoltWinters::optimize(&data, 0).unwrap();
+    /// let fixed = HoltWinters::fit(&data, 0, 0.3, 0.1, 0.0).unwrap();
+    /// assert!(optimized.sse() <= fixed.sse());
+    /// ```
+    pub fn optimize(data: &[f64], season_length: usize) -> Result<HoltWinters> {
+        check_data(data, season_length)?;
+        let seasonal = season_length > 0;
+        let mut parameters = [0.3, 0.1, if seasonal { 0.1 } else { 0.0 }];
+        let free = if seasonal { 3 } else { 2 };
+        let sse = |p: &[f64; 3]| smooth(data, season_length, p[0], p[1], p[2]).sse;
+        let mut best = sse(&parameters);
+        for _ in 0..MAX_SWEEPS {
+            let previous = best;
+            for i in 0..free {
+                let (value, minimum) = brent_min(
+                    |x| {
+                        let mut trial = parameters;
+                        trial[i] = x;
+                        sse(&trial)
+                    },
+                    0.0,
+                    1.0,
+                    PARAMETER_TOLERANCE,
+                );
+                if minimum < best {
+                    parameters[i] = value;
+                    best = minimum;
+                }
+            }
+            if best >= previous * (1.0 - 1e-10) {
+                break;
+            }
+        }
+        Ok(smooth(
+            data,
+            season_length,
+            parameters[0],
+            parameters[1],
+            parameters[2],
+        ))
+    }
+
+    /// Returns the smoothing parameter of the level
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the smoothing parameter of the trend
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Returns the smoothing parameter of the seasonal component
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Returns the level at the last observation
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Returns the trend, the change of the level per step, at the last
+    /// observation
+    pub fn trend(&self) -> f64 {
+        self.trend
+    }
+
+    /// Returns the seasonal components of the next `season_length` steps
+    /// after the last observation, or an empty slice without a seasonal
+    /// component
+    pub fn seasonal(&self) -> &[f64] {
+        &self.seasonal
+    }
+
+    /// Returns the one step ahead predictions of the observations after the
+    /// starting ones, see `fit`
+    pub fn fitted(&self) -> &[f64] {
+        &self.fitted
+    }
+
+    /// Returns the sum of squared one step ahead prediction errors
+    pub fn sse(&self) -> f64 {
+        self.sse
+    }
+
+    /// Forecasts the next `steps` observations after the last one
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_{n+h} = l_n + h b_n + s_{n+h-m}
+    /// ```
+    ///
+    /// where the seasonal term repeats the last season and is zero without
+    /// a seasonal component
+    pub fn forecast(&self, steps: usize) -> Vec<f64> {
+        (1..=steps)
+            .map(|h| {
+                let season = if self.seasonal.is_empty() {
+                    0.0
+                } else {
+                    self.seasonal[(h - 1) % self.seasonal.len()]
+                };
+                self.level + h as f64 * self.trend + season
+            })
+            .collect()
+    }
+}
+
+/// Returns an error unless `data` is finite and long enough to start the
+/// smoothing with a period of `season_length`
+fn check_data(data: &[f64], season_length: usize) -> Result<()> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("data must be finite"));
+    }
+    match season_length {
+        0 if data.len() < 3 => Err(StatsError::SpecialCase(
+            "at least three observations are required",
+        )),
+        1 => Err(StatsError::SpecialCase(
+            "the season length must be zero or at least two",
+        )),
+        m if m > 0 && data.len() <= 2 * m => Err(StatsError::SpecialCase(
+            "more than two full seasons of observations are required",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Runs the smoothing of `HoltWinters::fit` on checked data and parameters
+fn smooth(data: &[f64], m: usize, alpha: f64, beta: f64, gamma: f64) -> HoltWinters {
+    // the starting level and trend at time `start - 1` and the seasonal
+    // components of the times `0` to `start - 1`, indexed by time mod `m`
+    let (start, mut level, mut trend, mut seasonal) = if m == 0 {
+        (2, data[1], data[1] - data[0], Vec::new())
+    } else {
+        let first = data[..m].iter().sum::<f64>() / m as f64;
+        let second = data[m..2 * m].iter().sum::<f64>() / m as f64;
+        let trend = (second - first) / m as f64;
+        let centre = (m - 1) as f64 / 2.0;
+        let seasonal: Vec<f64> = data[..m]
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x - (first + trend * (i as f64 - centre)))
+            .collect();
+        (m, first + trend * centre, trend, seasonal)
+    };
+    let mut fitted = Vec::with_capacity(data.len() - start);
+    let mut sse = 0.0;
+    for (t, &x) in data.iter().enumerate().skip(start) {
+        let season = if m == 0 { 0.0 } else { seasonal[t % m] };
+        let prediction = level + trend + season;
+        fitted.push(prediction);
+        sse += (x - prediction) * (x - prediction);
+        let previous = level;
+        level = alpha * (x - season) + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - previous) + (1.0 - beta) * trend;
+        if m > 0 {
+            seasonal[t % m] = gamma * (x - level) + (1.0 - gamma) * season;
+        }
+    }
+    // start the seasonal components at the phase of the first forecast
+    let n = data.len();
+    let seasonal = (0..m).map(|h| seasonal[(n + h) % m]).collect();
+    HoltWinters {
+        alpha,
+        beta,
+        gamma,
+        level,
+        trend,
+        seasonal,
+        fitted,
+        sse,
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERN: [f64; 6] = [4.0, 1.0, -3.0, -5.0, 0.5, 2.5];
+
+    /// A line plus a period of six with a small deterministic wobble
+    fn seasonal_series(n: usize, noise: f64) -> Vec<f64> {
+        (0..n)
+            .map(|t| 20.0 + 0.8 * t as f64 + PATTERN[t % 6] + noise * (1.7 * t as f64).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_trend_and_season() {
+        let data = seasonal_series(36, 0.0);
+        let model = HoltWinters::fit(&data, 6, 0.4, 0.2, 0.3).unwrap();
+        assert_almost_eq!(model.trend(), 0.8, 1e-12);
+        assert_almost_eq!(model.sse(), 0.0, 1e-20);
+        for (h, x) in model.forecast(12).iter().enumerate() {
+            let t = 36 + h;
+            assert_almost_eq!(*x, 20.0 + 0.8 * t as f64 + PATTERN[t % 6], 1e-10);
+        }
+        assert_eq!(model.seasonal().len(), 6);
+        assert_almost_eq!(model.seasonal()[0], PATTERN[0], 1e-12);
+        assert_eq!(model.fitted().len(), 30);
+    }
+
+    #[test]
+    fn test_noisy_trend_and_season() {
+        let data = seasonal_series(60, 0.3);
+        let model = HoltWinters::optimize(&data, 6).unwrap();
+        for (h, x) in model.forecast(12).iter().enumerate() {
+            let t = 60 + h;
+            assert!((x - (20.0 + 0.8 * t as f64 + PATTERN[t % 6])).abs() < 1.0);
+        }
+        assert!((model.trend() - 0.8).abs() < 0.05);
+        let fixed = HoltWinters::fit(&data, 6, 0.3, 0.1, 0.1).unwrap();
+        assert!(model.sse() < fixed.sse());
+        for p in &[model.alpha(), model.beta(), model.gamma()] {
+            assert!((0.0..=1.0).contains(p));
+        }
+    }
+
+    #[test]
+    fn test_without_season() {
+        // a hand computation with α = 0.5 and β = 0.5: the start is l = 3,
+        // b = 2, then x_2 = 4 gives l = 4.5, b = 1.75 and x_3 = 8 gives
+        // l = 7.125, b = 2.1875
+        let model = HoltWinters::fit(&[1.0, 3.0, 4.0, 8.0], 0, 0.5, 0.5, 0.9).unwrap();
+        assert_eq!(model.fitted(), &[5.0, 6.25]);
+        assert_eq!(model.sse(), 1.0 + 3.0625);
+        assert_eq!(model.level(), 7.125);
+        assert_eq!(model.trend(), 2.1875);
+        assert_eq!(model.forecast(2), vec![9.3125, 11.5]);
+        assert!(model.seasonal().is_empty());
+        assert_eq!(HoltWinters::optimize(&[1.0, 3.0, 4.0, 8.0], 0).unwrap().gamma(), 0.0);
+        assert!(model.forecast(0).is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let data = seasonal_series(13, 0.0);
+        assert!(HoltWinters::fit(&data, 6, 1.1, 0.1, 0.1).is_err());
+        assert!(HoltWinters::fit(&data, 6, 0.5, -0.1, 0.1).is_err());
+        assert!(HoltWinters::fit(&data, 6, 0.5, 0.1, f64::NAN).is_err());
+        assert!(HoltWinters::fit(&data, 1, 0.5, 0.1, 0.1).is_err());
+        assert!(HoltWinters::fit(&data[..12], 6, 0.5, 0.1, 0.1).is_err());
+        assert!(HoltWinters::fit(&data, 6, 0.5, 0.1, 0.1).is_ok());
+        assert!(HoltWinters::fit(&[1.0, 2.0], 0, 0.5, 0.1, 0.1).is_err());
+        assert!(HoltWinters::optimize(&[1.0, f64::INFINITY, 2.0], 0).is_err());
+    }
+}
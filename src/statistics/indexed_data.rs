@@ -0,0 +1,510 @@
+use crate::rng_util::SplitMix64;
+use crate::statistics::Data;
+use rand::RngCore;
+use std::f64;
+
+/// The index of the absent child of a node
+const NIL: usize = usize::MAX;
+
+/// The seed of the node priorities, fixed so that the tree shape only
+/// depends on the operations
+const PRIORITY_SEED: u64 = 0x0069_6e64_6578_6564;
+
+#[derive(Copy, Clone, Debug)]
+struct Node {
+    value: f64,
+    count: usize,
+    size: usize,
+    priority: u64,
+    left: usize,
+    right: usize,
+}
+
+/// A multiset of observations indexed for order statistics, supporting
+/// insertion and removal of single observations in `O(log n)` expected
+/// time, so that the quantiles of a dataset that changes one point at a
+/// time, such as a sliding window, need not be recomputed from scratch
+///
+/// # Remarks
+///
+/// The observations are kept in an order-statistic tree, a treap whose
+/// nodes hold a distinct value with its multiplicity and the size of their
+/// subtree. Bulk construction with `new` sorts the data as
+/// `Data` does and builds the tree in linear time. The quantiles are the
+/// same as those of `OrderStatistics` for `Data` holding the same
+/// observations.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::IndexedData;
+///
+/// let mut data = IndexedData::new(&[5.0, 1.0, 4.0]);
+/// assert_eq!(data.median(), 4.0);
+/// data.insert(2.0);
+/// assert_eq!(data.median(), 3.0);
+/// assert!(data.remove(5.0));
+/// assert_eq!(data.median(), 2.0);
+/// assert_eq!(data.rank_of(4.0), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexedData {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: usize,
+    priorities: SplitMix64,
+}
+
+impl IndexedData {
+    /// Constructs an index of `data` by sorting it
+    ///
+    /// # Panics
+    ///
+    /// If `data` contains a `NaN`
+    pub fn new(data: &[f64]) -> IndexedData {
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("data must not contain NaN"));
+        let mut index = IndexedData {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            priorities: SplitMix64::new(PRIORITY_SEED),
+        };
+        // the distinct values in increasing order form a Cartesian tree on
+        // the priorities, built with the stack of its right spine
+        let mut spine: Vec<usize> = Vec::new();
+        let mut i = 0;
+        while i < sorted.len() {
+            let run = sorted[i..].iter().take_while(|&&x| x == sorted[i]).count();
+            let node = index.allocate(sorted[i], run);
+            let mut last = NIL;
+            while let Some(&top) = spine.last() {
+                if index.nodes[top].priority >= index.nodes[node].priority {
+                    break;
+                }
+                last = spine.pop().unwrap();
+            }
+            index.nodes[node].left = last;
+            if let Some(&top) = spine.last() {
+                index.nodes[top].right = node;
+            }
+            spine.push(node);
+            i += run;
+        }
+        index.root = spine.first().copied().unwrap_or(NIL);
+        index.update_sizes(index.root);
+        index
+    }
+
+    /// Returns the number of observations
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// Returns `true` if there are no observations
+    pub fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// Adds the observation `x` in `O(log n)` expected time
+    ///
+    /// # Panics
+    ///
+    /// If `x` is `NaN`
+    pub fn insert(&mut self, x: f64) {
+        if x.is_nan() {
+            panic!("cannot insert NaN");
+        }
+        self.root = self.insert_at(self.root, x);
+    }
+
+    /// Removes one observation equal to `x` in `O(log n)` expected time,
+    /// returning `false` if there is none
+    pub fn remove(&mut self, x: f64) -> bool {
+        let (root, removed) = self.remove_at(self.root, x);
+        self.root = root;
+        removed
+    }
+
+    /// Returns the order statistic `(order 1..N)`, as
+    /// `OrderStatistics::order_statistic`, or `f64::NAN` if `order` is
+    /// outside that range
+    pub fn order_statistic(&self, order: usize) -> f64 {
+        if order < 1 || order > self.len() {
+            f64::NAN
+        } else {
+            self.select(order - 1)
+        }
+    }
+
+    /// Returns the number of observations strictly less than `x`, the
+    /// zero-based rank at which `x` would be inserted before its equals
+    pub fn rank_of(&self, x: f64) -> usize {
+        let mut rank = 0;
+        let mut node = self.root;
+        while node != NIL {
+            let n = &self.nodes[node];
+            if x <= n.value {
+                node = n.left;
+            } else {
+                rank += self.size(n.left) + n.count;
+                node = n.right;
+            }
+        }
+        rank
+    }
+
+    /// Returns the smallest observation, or `f64::NAN` if there is none
+    pub fn min(&self) -> f64 {
+        self.order_statistic(1)
+    }
+
+    /// Returns the largest observation, or `f64::NAN` if there is none
+    pub fn max(&self) -> f64 {
+        self.order_statistic(self.len())
+    }
+
+    /// Returns the median, as `OrderStatistics::median`, or `f64::NAN` if
+    /// there are no observations
+    pub fn median(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            f64::NAN
+        } else if n & 1 == 1 {
+            self.select(n / 2)
+        } else {
+            (self.select(n / 2 - 1) + self.select(n / 2)) / 2.0
+        }
+    }
+
+    /// Estimates the `tau`-th quantile, as `OrderStatistics::quantile`, or
+    /// returns `f64::NAN` if `tau` is not in `[0, 1]` or there are no
+    /// observations
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// x_(h) + (h - floor(h)) (x_(h + 1) - x_(h)), h = (n + 1/3) τ + 1/3
+    /// ```
+    ///
+    /// which is the approximately median-unbiased estimator R-8
+    pub fn quantile(&self, tau: f64) -> f64 {
+        let n = self.len();
+        if !(0.0..=1.0).contains(&tau) || n == 0 {
+            return f64::NAN;
+        }
+        let h = (n as f64 + 1.0 / 3.0) * tau + 1.0 / 3.0;
+        let hf = h as i64;
+        if hf <= 0 || tau == 0.0 {
+            return self.min();
+        }
+        if hf >= n as i64 || ulps_eq!(tau, 1.0) {
+            return self.max();
+        }
+        let a = self.select(hf as usize - 1);
+        let b = self.select(hf as usize);
+        a + (h - hf as f64) * (b - a)
+    }
+
+    /// Returns the observations in increasing order
+    pub fn to_data(&self) -> Data<Vec<f64>> {
+        let mut sorted = Vec::with_capacity(self.len());
+        let mut stack = Vec::new();
+        let mut node = self.root;
+        while node != NIL || !stack.is_empty() {
+            while node != NIL {
+                stack.push(node);
+                node = self.nodes[node].left;
+            }
+            let top = stack.pop().unwrap();
+            let n = &self.nodes[top];
+            sorted.resize(sorted.len() + n.count, n.value);
+            node = n.right;
+        }
+        Data::new(sorted)
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    fn update_size(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = self.size(left) + self.size(right) + self.nodes[node].count;
+    }
+
+    /// Recomputes the sizes of a subtree built without them, in post-order
+    fn update_sizes(&mut self, root: usize) {
+        let mut stack = vec![(root, false)];
+        while let Some((node, visited)) = stack.pop() {
+            if node == NIL {
+                continue;
+            }
+            if visited {
+                self.update_size(node);
+            } else {
+                stack.push((node, true));
+                stack.push((self.nodes[node].left, false));
+                stack.push((self.nodes[node].right, false));
+            }
+        }
+    }
+
+    fn allocate(&mut self, value: f64, count: usize) -> usize {
+        let node = Node {
+            value,
+            count,
+            size: count,
+            priority: self.priorities.next_u64(),
+            left: NIL,
+            right: NIL,
+        };
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Returns the zero-based `rank`-th smallest observation, which must
+    /// exist
+    fn select(&self, mut rank: usize) -> f64 {
+        let mut node = self.root;
+        loop {
+            let n = &self.nodes[node];
+            let left = self.size(n.left);
+            if rank < left {
+                node = n.left;
+            } else if rank < left + n.count {
+                return n.value;
+            } else {
+                rank -= left + n.count;
+                node = n.right;
+            }
+        }
+    }
+
+    fn rotate_right(&mut self, node: usize) -> usize {
+        let left = self.nodes[node].left;
+        self.nodes[node].left = self.nodes[left].right;
+        self.nodes[left].right = node;
+        self.update_size(node);
+        self.update_size(left);
+        left
+    }
+
+    fn rotate_left(&mut self, node: usize) -> usize {
+        let right = self.nodes[node].right;
+        self.nodes[node].right = self.nodes[right].left;
+        self.nodes[right].left = node;
+        self.update_size(node);
+        self.update_size(right);
+        right
+    }
+
+    fn insert_at(&mut self, node: usize, x: f64) -> usize {
+        if node == NIL {
+            return self.allocate(x, 1);
+        }
+        let value = self.nodes[node].value;
+        if x == value {
+            self.nodes[node].count += 1;
+            self.nodes[node].size += 1;
+            return node;
+        }
+        if x < value {
+            let left = self.insert_at(self.nodes[node].left, x);
+            self.nodes[node].left = left;
+            self.update_size(node);
+            if self.nodes[left].priority > self.nodes[node].priority {
+                return self.rotate_right(node);
+            }
+        } else {
+            let right = self.insert_at(self.nodes[node].right, x);
+            self.nodes[node].right = right;
+            self.update_size(node);
+            if self.nodes[right].priority > self.nodes[node].priority {
+                return self.rotate_left(node);
+            }
+        }
+        node
+    }
+
+    fn remove_at(&mut self, node: usize, x: f64) -> (usize, bool) {
+        if node == NIL {
+            return (NIL, false);
+        }
+        let value = self.nodes[node].value;
+        let removed = if x < value {
+            let (left, removed) = self.remove_at(self.nodes[node].left, x);
+            self.nodes[node].left = left;
+            removed
+        } else if x > value {
+            let (right, removed) = self.remove_at(self.nodes[node].right, x);
+            self.nodes[node].right = right;
+            removed
+        } else if self.nodes[node].count > 1 {
+            self.nodes[node].count -= 1;
+            true
+        } else {
+            return (self.delete(node), true);
+        };
+        self.update_size(node);
+        (node, removed)
+    }
+
+    /// Removes `node` from the tree by rotating it down to a leaf, returning
+    /// the root of its subtree
+    fn delete(&mut self, node: usize) -> usize {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        if left == NIL || right == NIL {
+            self.free.push(node);
+            return if left == NIL { right } else { left };
+        }
+        if self.nodes[left].priority > self.nodes[right].priority {
+            let top = self.rotate_right(node);
+            let child = self.delete(node);
+            self.nodes[top].right = child;
+            self.update_size(top);
+            top
+        } else {
+            let top = self.rotate_left(node);
+            let child = self.delete(node);
+            self.nodes[top].left = child;
+            self.update_size(top);
+            top
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::OrderStatistics;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn check(index: &IndexedData, reference: &[f64]) {
+        let mut data = Data::new(reference.to_vec());
+        assert_eq!(index.len(), reference.len());
+        assert_eq!(index.is_empty(), reference.is_empty());
+        assert_eq!(index.median().to_bits(), data.median().to_bits());
+        for &tau in &[0.0, 0.01, 0.1, 0.25, 0.5, 0.6, 0.75, 0.9, 0.99, 1.0] {
+            assert_eq!(index.quantile(tau).to_bits(), data.quantile(tau).to_bits());
+        }
+        let mut sorted = reference.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(index.to_data().iter().copied().collect::<Vec<_>>(), sorted);
+        for order in 1..=sorted.len() {
+            assert_eq!(index.order_statistic(order), sorted[order - 1]);
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let values = [3.0, -1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0];
+        check(&IndexedData::new(&values), &values);
+        check(&IndexedData::new(&[]), &[]);
+        check(&IndexedData::new(&[2.0; 7]), &[2.0; 7]);
+        let index = IndexedData::new(&values);
+        assert_eq!(index.min(), -1.0);
+        assert_eq!(index.max(), 9.0);
+        assert_eq!(index.rank_of(5.0), 6);
+        assert_eq!(index.rank_of(5.5), 9);
+        assert_eq!(index.rank_of(-3.0), 0);
+        assert_eq!(index.rank_of(100.0), 11);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut index = IndexedData::new(&[]);
+        assert!(index.median().is_nan());
+        assert!(index.quantile(0.5).is_nan());
+        assert!(index.min().is_nan() && index.max().is_nan());
+        assert!(index.order_statistic(1).is_nan());
+        assert!(!index.remove(1.0));
+        index.insert(1.0);
+        assert!(index.quantile(1.5).is_nan());
+        assert!(index.order_statistic(0).is_nan() && index.order_statistic(2).is_nan());
+        assert!(index.remove(1.0));
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_fuzz_insert_remove() {
+        let mut rng = StdRng::seed_from_u64(2860);
+        for round in 0..20 {
+            // few distinct values give many duplicates in the early rounds
+            let range = if round < 10 { 10 } else { 1000 };
+            let initial: Vec<f64> = (0..rng.gen_range(0..50)).map(|_| rng.gen_range(0..range) as f64).collect();
+            let mut index = IndexedData::new(&initial);
+            let mut reference = initial;
+            for step in 0..400 {
+                let x = rng.gen_range(0..range) as f64;
+                if rng.gen_bool(0.45) {
+                    let position = reference.iter().position(|&y| y == x);
+                    assert_eq!(index.remove(x), position.is_some());
+                    if let Some(i) = position {
+                        reference.swap_remove(i);
+                    }
+                } else {
+                    index.insert(x);
+                    reference.push(x);
+                }
+                assert_eq!(index.rank_of(x), reference.iter().filter(|&&y| y < x).count());
+                if step % 20 == 0 {
+                    check(&index, &reference);
+                }
+            }
+            check(&index, &reference);
+        }
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let mut rng = StdRng::seed_from_u64(2861);
+        let series: Vec<f64> = (0..2000).map(|_| rng.gen::<f64>()).collect();
+        let window = 101;
+        let mut index = IndexedData::new(&series[..window]);
+        for end in window..series.len() {
+            assert!(index.remove(series[end - window]));
+            index.insert(series[end]);
+            let mut data = Data::new(series[end + 1 - window..=end].to_vec());
+            assert_eq!(index.median(), data.median());
+        }
+        // freed nodes are reused, so the arena does not grow with the series
+        assert!(index.nodes.len() <= 2 * window);
+    }
+
+    #[test]
+    fn test_balanced() {
+        // sorted insertions would degenerate an unbalanced search tree
+        let mut index = IndexedData::new(&[]);
+        for i in 0..100_000 {
+            index.insert(i as f64);
+        }
+        fn depth(index: &IndexedData, node: usize) -> usize {
+            if node == NIL { 0 } else { 1 + depth(index, index.nodes[node].left).max(depth(index, index.nodes[node].right)) }
+        }
+        assert!(depth(&index, index.root) < 100);
+        assert_eq!(index.median(), 49_999.5);
+        let bulk = IndexedData::new(&(0..100_000).map(|i| i as f64).collect::<Vec<_>>());
+        assert!(depth(&bulk, bulk.root) < 100);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_nan() {
+        IndexedData::new(&[1.0]).insert(f64::NAN);
+    }
+}
@@ -238,6 +238,100 @@ where
             f64::NAN
         }
     }
+
+    fn trimmed_variance(self, proportion: f64) -> f64 {
+        let (sorted, g) = match sorted_for_trimming(self, proportion) {
+            Some(s) => s,
+            None => return f64::NAN,
+        };
+        sorted[g..sorted.len() - g].variance()
+    }
+
+    fn winsorized_variance(self, proportion: f64) -> f64 {
+        let (mut sorted, g) = match sorted_for_trimming(self, proportion) {
+            Some(s) => s,
+            None => return f64::NAN,
+        };
+        let n = sorted.len();
+        let (low, high) = (sorted[g], sorted[n - g - 1]);
+        sorted[..g].iter_mut().for_each(|x| *x = low);
+        sorted[n - g..].iter_mut().for_each(|x| *x = high);
+        sorted.variance()
+    }
+
+    fn argmin(self) -> Option<usize> {
+        extremum_index(self, |x, best| x < best)
+    }
+
+    fn argmax(self) -> Option<usize> {
+        extremum_index(self, |x, best| x > best)
+    }
+
+    fn min_max(self) -> Option<(f64, f64)> {
+        let mut iter = self.into_iter();
+        let first = *iter.next()?.borrow();
+        let mut extrema = (first, first);
+        for x in iter {
+            let x = *x.borrow();
+            if x.is_nan() {
+                return Some((f64::NAN, f64::NAN));
+            }
+            if x < extrema.0 {
+                extrema.0 = x;
+            } else if x > extrema.1 {
+                extrema.1 = x;
+            }
+        }
+        if first.is_nan() {
+            return Some((f64::NAN, f64::NAN));
+        }
+        Some(extrema)
+    }
+}
+
+/// Returns the index of the first entry of `data` that is `better` than
+/// every earlier one, or of the first `NaN`, or `None` if `data` is empty
+fn extremum_index<T, F>(data: T, better: F) -> Option<usize>
+where
+    T: IntoIterator,
+    T::Item: Borrow<f64>,
+    F: Fn(f64, f64) -> bool,
+{
+    let mut best: Option<(usize, f64)> = None;
+    for (i, x) in data.into_iter().enumerate() {
+        let x = *x.borrow();
+        if x.is_nan() {
+            return Some(i);
+        }
+        match best {
+            Some((_, b)) if !better(x, b) => {}
+            _ => best = Some((i, x)),
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Returns the sorted data and the number `g = ⌊proportion * N⌋` of entries
+/// trimmed from each end, or `None` if fewer than two entries would remain
+/// or an entry is `NaN`
+fn sorted_for_trimming<T>(data: T, proportion: f64) -> Option<(Vec<f64>, usize)>
+where
+    T: IntoIterator,
+    T::Item: Borrow<f64>,
+{
+    if !(0.0..0.5).contains(&proportion) {
+        panic!("proportion must be in [0, 0.5), was {}", proportion);
+    }
+    let mut sorted: Vec<f64> = data.into_iter().map(|x| *x.borrow()).collect();
+    if sorted.iter().any(|x| x.is_nan()) {
+        return None;
+    }
+    let g = (proportion * sorted.len() as f64).floor() as usize;
+    if sorted.len() < 2 * g + 2 {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Some((sorted, g))
 }
 
 #[rustfmt::skip]
@@ -395,9 +489,87 @@ mod tests {
         assert_almost_eq!((&longer).quadratic_mean(), (0.21875f64).sqrt(), 1e-14);
     }
 
+    #[test]
+    fn test_trimmed_winsorized_variance() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut data: Vec<f64> = (0..200).map(|_| normal.sample(&mut rng)).collect();
+        let clean = data.clone();
+        // contaminate 5% of the sample with gross outliers
+        for x in data.iter_mut().step_by(20) {
+            *x = 50.0;
+        }
+        let ordinary = (&data).variance();
+        let winsorized = (&data).winsorized_variance(0.2);
+        let trimmed = (&data).trimmed_variance(0.2);
+        assert!(ordinary > 100.0);
+        assert!(winsorized < 2.0 && trimmed < 1.0);
+        assert!((winsorized - (&clean).winsorized_variance(0.2)).abs() < 0.3);
+        // no trimming gives the ordinary variance
+        assert_almost_eq!((&clean).winsorized_variance(0.0), (&clean).variance(), 1e-12);
+        assert_almost_eq!((&clean).trimmed_variance(0.0), (&clean).variance(), 1e-12);
+    }
+
+    #[test]
+    fn test_trimmed_winsorized_variance_small() {
+        let x = [7.0, 1.0, 3.0, 100.0, 2.0, 4.0, -50.0, 5.0, 6.0, 8.0];
+        // g = 2 removes -50, 1 and 8, 100, leaving 2..=7
+        assert_almost_eq!(x.trimmed_variance(0.25), 3.5, 1e-14);
+        // winsorized to [2, 2, 2, 3, 4, 5, 6, 7, 7, 7]
+        assert_almost_eq!(x.winsorized_variance(0.25), 85.0 / 18.0, 1e-14);
+        assert!([1.0, 2.0, 3.0].trimmed_variance(0.4).is_nan());
+        assert!([1.0, f64::NAN, 3.0].winsorized_variance(0.1).is_nan());
+        assert!([0.0; 0].trimmed_variance(0.1).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_trimmed_variance_bad_proportion() {
+        [1.0, 2.0, 3.0].trimmed_variance(0.5);
+    }
+
     #[test]
     fn test_quadratic_mean_of_sinusoidal() {
         let data = InfiniteSinusoidal::default(64.0, 16.0, 2.0).take(128).collect::<Vec<f64>>();
         assert_almost_eq!((&data).quadratic_mean(), 2.0 / consts::SQRT_2, 1e-15);
     }
+
+    #[test]
+    fn test_argmin_argmax_min_max() {
+        let x = [4.0, -1.5, 7.25, 0.0, 3.0];
+        assert_eq!(x.argmin(), Some(1));
+        assert_eq!(x.argmax(), Some(2));
+        assert_eq!(x.min_max(), Some((-1.5, 7.25)));
+        assert_eq!(x.iter().map(|v| -v).collect::<Vec<_>>().argmin(), Some(2));
+        // ties give the first index
+        let tied = [2.0, 9.0, -3.0, 9.0, -3.0, 1.0];
+        assert_eq!(tied.argmin(), Some(2));
+        assert_eq!(tied.argmax(), Some(1));
+        assert_eq!([5.0; 4].argmin(), Some(0));
+        assert_eq!([5.0; 4].argmax(), Some(0));
+        assert_eq!([5.0; 4].min_max(), Some((5.0, 5.0)));
+        // consistent with min and max
+        let data: Vec<f64> = (0..100).map(|i| ((i * 37) % 101) as f64 - 50.0).collect();
+        assert_eq!((&data).min_max(), Some(((&data).min(), (&data).max())));
+        assert_eq!(data[(&data).argmin().unwrap()], (&data).min());
+        assert_eq!(data[(&data).argmax().unwrap()], (&data).max());
+        // works on iterators too
+        assert_eq!(x.iter().filter(|v| **v > 0.0).argmin(), Some(2));
+    }
+
+    #[test]
+    fn test_argmin_argmax_min_max_nan_and_empty() {
+        let empty: [f64; 0] = [];
+        assert_eq!(empty.argmin(), None);
+        assert_eq!(empty.argmax(), None);
+        assert_eq!(empty.min_max(), None);
+        let x = [1.0, f64::NAN, -4.0, f64::NAN];
+        assert_eq!(x.argmin(), Some(1));
+        assert_eq!(x.argmax(), Some(1));
+        let (min, max) = x.min_max().unwrap();
+        assert!(min.is_nan() && max.is_nan());
+        let (min, max) = [f64::NAN, 1.0].min_max().unwrap();
+        assert!(min.is_nan() && max.is_nan());
+        assert_eq!([f64::NAN].argmax(), Some(0));
+    }
 }
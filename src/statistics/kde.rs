@@ -0,0 +1,254 @@
+//! Provides [kernel density
+//! estimation](https://en.wikipedia.org/wiki/Kernel_density_estimation)
+//! with a Gaussian kernel
+
+use crate::consts;
+use crate::function::optimize::brent_min;
+use crate::statistics::{Data, OrderStatistics, Statistics};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A Gaussian kernel density estimate, the average of normal densities
+/// centered on the observations with a common standard deviation, the
+/// bandwidth
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::kde::KernelDensity;
+///
+/// let kde = KernelDensity::with_bandwidth(&[0.0, 2.0], 1.0).unwrap();
+/// assert!((kde.pdf(1.0) - (-0.5f64).exp() / (2.0 * std::f64::consts::PI).sqrt()).abs() < 1e-16);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct KernelDensity {
+    data: Vec<f64>,
+    bandwidth: f64,
+}
+
+impl KernelDensity {
+    /// Constructs a kernel density estimate of `data` with the bandwidth of
+    /// Silverman's rule of thumb
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// h = 0.9 min(s, IQR / 1.34) n^(-1/5)
+    /// ```
+    ///
+    /// where `s` is the sample standard deviation, `IQR` the interquartile
+    /// range and `n` the number of observations. If the minimum is zero,
+    /// `s` is used instead, then `|x_1|`, then `1`.
+    ///
+    /// # Remarks
+    ///
+    /// The rule is tuned to a normal sample and oversmooths multimodal
+    /// data, which may hide modes that a smaller bandwidth would show
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries or contains a
+    /// value that is not finite
+    pub fn new(data: &[f64]) -> Result<KernelDensity> {
+        if data.len() < 2 {
+            return Err(StatsError::SpecialCase(
+                "at least two observations are required",
+            ));
+        }
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::SpecialCase("data must be finite"));
+        }
+        let s = data.std_dev();
+        let iqr = Data::new(data.to_vec()).interquartile_range() / 1.34;
+        let spread = [s.min(iqr), s, data[0].abs(), 1.0]
+            .iter()
+            .copied()
+            .find(|&x| x > 0.0)
+            .unwrap();
+        KernelDensity::with_bandwidth(data, 0.9 * spread * (data.len() as f64).powf(-0.2))
+    }
+
+    /// Constructs a kernel density estimate of `data` with the given
+    /// `bandwidth`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or contains a value that is not
+    /// finite, or if `bandwidth` is not finite and positive
+    pub fn with_bandwidth(data: &[f64], bandwidth: f64) -> Result<KernelDensity> {
+        if data.is_empty() {
+            return Err(StatsError::SpecialCase("data must not be empty"));
+        }
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::SpecialCase("data must be finite"));
+        }
+        if !bandwidth.is_finite() || bandwidth <= 0.0 {
+            return Err(StatsError::ArgMustBePositive("bandwidth"));
+        }
+        Ok(KernelDensity {
+            data: data.to_vec(),
+            bandwidth,
+        })
+    }
+
+    /// Returns the bandwidth, the standard deviation of the kernel
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Evaluates the density estimate at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Σ φ((x - x_i) / h) / (n h)
+    /// ```
+    ///
+    /// where `φ` is the standard normal density, `h` the bandwidth and `n`
+    /// the number of observations
+    pub fn pdf(&self, x: f64) -> f64 {
+        let h = self.bandwidth;
+        let sum: f64 = self
+            .data
+            .iter()
+            .map(|xi| {
+                let z = (x - xi) / h;
+                (-0.5 * z * z).exp()
+            })
+            .sum();
+        sum / (self.data.len() as f64 * h * consts::SQRT_2PI)
+    }
+
+    /// Returns the local maxima of the density estimate in increasing
+    /// order, located on a grid of `grid_points` equally spaced points from
+    /// three bandwidths below the smallest observation to three above the
+    /// largest and refined by a golden-section search between the grid
+    /// neighbours
+    ///
+    /// # Remarks
+    ///
+    /// The number of modes is a heuristic for the number of clusters in the
+    /// data. It depends strongly on the bandwidth, and modes closer than the
+    /// grid spacing may be merged, so the grid should be fine compared to
+    /// the bandwidth.
+    ///
+    /// # Panics
+    ///
+    /// If `grid_points < 3`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::kde::KernelDensity;
+    ///
+    /// let data = [-2.1, -2.0, -1.8, -2.3, 1.9, 2.2, 2.0, 2.1];
+    /// let kde = KernelDensity::with_bandwidth(&data, 0.5).unwrap();
+    /// let modes = kde.kde_modes(200);
+    /// assert_eq!(modes.len(), 2);
+    /// assert!((modes[0] - -2.05).abs() < 0.1 && (modes[1] - 2.05).abs() < 0.1);
+    /// ```
+    pub fn kde_modes(&self, grid_points: usize) -> Vec<f64> {
+        if grid_points < 3 {
+            panic!("grid_points must be at least 3, was {}", grid_points);
+        }
+        let pad = 3.0 * self.bandwidth;
+        let lower = self.data.iter().fold(f64::INFINITY, |a, &b| a.min(b)) - pad;
+        let upper = self.data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)) + pad;
+        let step = (upper - lower) / (grid_points - 1) as f64;
+        let grid: Vec<f64> = (0..grid_points).map(|i| lower + step * i as f64).collect();
+        let density: Vec<f64> = grid.iter().map(|&x| self.pdf(x)).collect();
+        (1..grid_points - 1)
+            .filter(|&i| density[i] > density[i - 1] && density[i] >= density[i + 1])
+            .map(|i| {
+                let tol = 1e-10 * self.bandwidth;
+                brent_min(|x| -self.pdf(x), grid[i - 1], grid[i + 1], tol).0
+            })
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_pdf() {
+        let kde = KernelDensity::with_bandwidth(&[1.0], 2.0).unwrap();
+        let normal = Normal::new(1.0, 2.0).unwrap();
+        for &x in &[-3.0, 0.0, 1.0, 2.5, 9.0] {
+            assert_almost_eq!(kde.pdf(x), crate::distribution::Continuous::pdf(&normal, x), 1e-16);
+        }
+        // integrates to one
+        let kde = KernelDensity::with_bandwidth(&[0.0, 1.0, 5.0], 0.7).unwrap();
+        let total: f64 = (0..20_000).map(|i| kde.pdf(-10.0 + 0.001 * (i as f64 + 0.5)) * 0.001).sum();
+        assert_almost_eq!(total, 1.0, 1e-9);
+    }
+
+    #[test]
+    fn test_silverman_bandwidth() {
+        let mut rng = StdRng::seed_from_u64(2840);
+        let normal = Normal::new(0.0, 2.0).unwrap();
+        let data: Vec<f64> = (0..10_000).map(|_| normal.sample(&mut rng)).collect();
+        let kde = KernelDensity::new(&data).unwrap();
+        // 0.9 σ n^(-1/5) for a normal sample
+        assert_almost_eq!(kde.bandwidth(), 0.9 * 2.0 * 10_000f64.powf(-0.2), 0.01);
+        // constant data falls back to |x_1|
+        assert_almost_eq!(KernelDensity::new(&[2.0, 2.0]).unwrap().bandwidth(), 0.9 * 2.0 * 2f64.powf(-0.2), 1e-15);
+        assert!(KernelDensity::new(&[0.0, 0.0]).unwrap().bandwidth() > 0.0);
+    }
+
+    #[test]
+    fn test_kde_modes_bimodal() {
+        let mut rng = StdRng::seed_from_u64(2841);
+        let left = Normal::new(-3.0, 1.0).unwrap();
+        let right = Normal::new(4.0, 0.8).unwrap();
+        let mut data: Vec<f64> = (0..600).map(|_| left.sample(&mut rng)).collect();
+        data.extend((0..400).map(|_| right.sample(&mut rng)));
+        let kde = KernelDensity::new(&data).unwrap();
+        let modes = kde.kde_modes(512);
+        assert_eq!(modes.len(), 2);
+        assert!(modes[0] < modes[1]);
+        assert_almost_eq!(modes[0], -3.0, 0.3);
+        assert_almost_eq!(modes[1], 4.0, 0.3);
+        // the refined modes are stationary points of the density
+        for &m in &modes {
+            let slope = (kde.pdf(m + 1e-5) - kde.pdf(m - 1e-5)) / 2e-5;
+            assert!(slope.abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_kde_modes_unimodal() {
+        let kde = KernelDensity::with_bandwidth(&[-1.0, 0.0, 1.0], 1.0).unwrap();
+        let modes = kde.kde_modes(101);
+        assert_eq!(modes.len(), 1);
+        assert_almost_eq!(modes[0], 0.0, 1e-7);
+        // a small bandwidth separates every observation
+        let kde = KernelDensity::with_bandwidth(&[-1.0, 0.0, 1.0], 0.1).unwrap();
+        let modes = kde.kde_modes(1001);
+        assert_eq!(modes.len(), 3);
+        for (m, x) in modes.iter().zip(&[-1.0, 0.0, 1.0]) {
+            assert_almost_eq!(*m, *x, 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(KernelDensity::new(&[1.0]).is_err());
+        assert!(KernelDensity::new(&[1.0, f64::NAN]).is_err());
+        assert!(KernelDensity::with_bandwidth(&[], 1.0).is_err());
+        assert!(KernelDensity::with_bandwidth(&[1.0], 0.0).is_err());
+        assert!(KernelDensity::with_bandwidth(&[1.0], f64::INFINITY).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_grid() {
+        KernelDensity::with_bandwidth(&[1.0], 1.0).unwrap().kde_modes(2);
+    }
+}
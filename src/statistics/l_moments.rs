@@ -0,0 +1,247 @@
+//! Provides sample [L-moments](https://en.wikipedia.org/wiki/L-moment),
+//! robust analogues of the conventional moments that are linear in the
+//! ordered data
+
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Returns the unbiased probability-weighted moments `b_0, ..., b_(order-1)`
+/// of the sorted sample `sorted`
+fn unbiased_pwms(sorted: &[f64], order: usize) -> Vec<f64> {
+    let n = sorted.len();
+    (0..order)
+        .map(|r| {
+            if r >= n {
+                return f64::NAN;
+            }
+            // the weight of x_(i) is C(i, r) / C(n - 1, r) with 0-based i
+            let total: f64 = sorted
+                .iter()
+                .enumerate()
+                .skip(r)
+                .map(|(i, &x)| {
+                    let weight: f64 = (0..r)
+                        .map(|j| (i - j) as f64 / (n - 1 - j) as f64)
+                        .product();
+                    weight * x
+                })
+                .sum();
+            total / n as f64
+        })
+        .collect()
+}
+
+/// Returns the unbiased probability-weighted moments `b_0, ..., b_order` of
+/// `data`, the sample analogues of `β_r = E[X F(X)^r]`
+///
+/// # Formula
+///
+/// ```text
+/// b_r = n⁻¹ Σ_i C(i - 1, r) / C(n - 1, r) x_(i)
+/// ```
+///
+/// where `x_(1) <= ... <= x_(n)` are the ordered data, so that `b_0` is the
+/// sample mean
+///
+/// # Remarks
+///
+/// Tied values occupy consecutive plotting-position ranks and each receives
+/// the weight of its rank; since the tied values are equal this is the
+/// same as giving each the mean weight of the ranks they share, so the
+/// result does not depend on how ties are ordered. Entries with `r >=
+/// data.len()` are `NaN`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::l_moments::probability_weighted_moments;
+///
+/// let b = probability_weighted_moments(&[3.0, 1.0, 4.0, 1.0, 5.0], 2).unwrap();
+/// assert_eq!(b[0], 2.8);
+/// assert!((b[1] - 1.95).abs() < 1e-15);
+/// ```
+pub fn probability_weighted_moments(data: &[f64], order: usize) -> Result<Vec<f64>> {
+    if data.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::SpecialCase("data must not contain NaN"));
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(unbiased_pwms(&sorted, order + 1))
+}
+
+/// Returns the first `order` sample L-moments of `data` as
+/// `[l1, l2, t3, t4, ...]`: the sample L-location, the sample L-scale and
+/// the sample L-moment ratios `tr = lr / l2` for `r >= 3`
+///
+/// The L-moments are computed from the unbiased estimators of the
+/// probability-weighted moments, so each `lr` is an unbiased estimator of
+/// the population L-moment `λr`.
+///
+/// # Formula
+///
+/// ```text
+/// b_k = n⁻¹ Σ_i C(i - 1, k) / C(n - 1, k) x_(i)
+/// l_(r+1) = Σ_(k=0..r) (-1)^(r-k) C(r, k) C(r + k, k) b_k
+/// ```
+///
+/// where `x_(1) <= ... <= x_(n)` are the ordered data
+///
+/// # Remarks
+///
+/// An L-moment of order `r` needs at least `r` observations; entries that
+/// need more than `data.len()` are `NaN`, so all entries are `NaN` for empty
+/// data. The result is `NaN` if any entry of `data` is `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::l_moments::sample_l_moments;
+///
+/// let l = sample_l_moments(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0], 4);
+/// assert_eq!(l[0], 3.875);
+/// assert!((l[1] - 1.625).abs() < 1e-15);
+/// assert!((l[2] - 3.0 / 13.0).abs() < 1e-15);
+/// ```
+pub fn sample_l_moments(data: &[f64], order: usize) -> Vec<f64> {
+    if data.iter().any(|x| x.is_nan()) {
+        return vec![f64::NAN; order];
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let b = unbiased_pwms(&sorted, order);
+    let mut moments = Vec::with_capacity(order);
+    for r in 0..order {
+        // the coefficients of the shifted Legendre polynomial of degree r
+        let mut coefficient = if r % 2 == 0 { 1.0 } else { -1.0 };
+        let mut lambda = 0.0;
+        for (k, &bk) in b.iter().enumerate().take(r + 1) {
+            lambda += coefficient * bk;
+            coefficient *= -(((r - k) * (r + k + 1)) as f64) / ((k + 1) * (k + 1)) as f64;
+        }
+        moments.push(if r >= 2 { lambda / moments[1] } else { lambda });
+    }
+    moments
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, LMoments, Normal, Uniform};
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_probability_weighted_moments() {
+        let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let b = probability_weighted_moments(&data, 3).unwrap();
+        assert_eq!(b.len(), 4);
+        assert_eq!(b[0], data.iter().sum::<f64>() / 8.0);
+        // from the weights i / 7, i (i - 1) / 42 and i (i - 1) (i - 2) / 210
+        // of the 0-based ranks of [1, 1, 2, 3, 4, 5, 6, 9]
+        assert_almost_eq!(b[1], 154.0 / 56.0, 1e-15);
+        assert_almost_eq!(b[2], 728.0 / 336.0, 1e-15);
+        assert_almost_eq!(b[3], 3024.0 / 1680.0, 1e-15);
+        // the L-moments are linear in the PWMs
+        let l = sample_l_moments(&data, 2);
+        assert_almost_eq!(l[1], 2.0 * b[1] - b[0], 1e-15);
+        // the order of the data and of the tied values does not matter
+        let reordered = [1.0, 9.0, 6.0, 2.0, 1.0, 5.0, 4.0, 3.0];
+        assert_eq!(probability_weighted_moments(&reordered, 3).unwrap(), b);
+        let b = probability_weighted_moments(&[2.0], 1).unwrap();
+        assert_eq!(b[0], 2.0);
+        assert!(b[1].is_nan());
+        assert!(probability_weighted_moments(&[], 2).is_err());
+        assert!(probability_weighted_moments(&[1.0, f64::NAN], 1).is_err());
+    }
+
+    #[test]
+    fn test_small_sample() {
+        // exact values from the rational probability-weighted moments
+        let l = sample_l_moments(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0], 4);
+        assert_almost_eq!(l[0], 31.0 / 8.0, 1e-15);
+        assert_almost_eq!(l[1], 13.0 / 8.0, 1e-15);
+        assert_almost_eq!(l[2], 3.0 / 13.0, 1e-15);
+        assert_almost_eq!(l[3], 1.0 / 13.0, 1e-15);
+        // l2 is half the mean absolute difference between pairs
+        let data = [0.5f64, 2.0, 3.5, 7.0];
+        let mut gini = 0.0;
+        for &x in &data {
+            for &y in &data {
+                gini += (x - y).abs();
+            }
+        }
+        assert_almost_eq!(sample_l_moments(&data, 2)[1], gini / (2.0 * 4.0 * 3.0), 1e-15);
+    }
+
+    #[test]
+    fn test_invariance() {
+        let data = [0.3, -1.2, 2.2, 0.8, 1.1, -0.4, 3.0];
+        let l = sample_l_moments(&data, 5);
+        let shifted: Vec<f64> = data.iter().map(|x| 2.0 * x + 5.0).collect();
+        let m = sample_l_moments(&shifted, 5);
+        assert_almost_eq!(m[0], 2.0 * l[0] + 5.0, 1e-14);
+        assert_almost_eq!(m[1], 2.0 * l[1], 1e-14);
+        for r in 2..5 {
+            assert_almost_eq!(m[r], l[r], 1e-13);
+        }
+        // reflection flips the sign of odd ratios
+        let reflected: Vec<f64> = data.iter().map(|x| -x).collect();
+        let m = sample_l_moments(&reflected, 4);
+        assert_almost_eq!(m[2], -l[2], 1e-14);
+        assert_almost_eq!(m[3], l[3], 1e-14);
+    }
+
+    #[test]
+    fn test_large_normal_sample() {
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(274);
+        let data: Vec<f64> = (0..200_000).map(|_| normal.sample(&mut rng)).collect();
+        let l = sample_l_moments(&data, 4);
+        let expected = normal.l_moments(4);
+        assert_almost_eq!(l[0], expected[0], 0.02);
+        assert_almost_eq!(l[1], expected[1], 0.01);
+        assert_almost_eq!(l[2], expected[2], 0.01);
+        assert_almost_eq!(l[3], expected[3], 0.01);
+    }
+
+    #[test]
+    fn test_large_samples_of_other_families() {
+        let mut rng = StdRng::seed_from_u64(2740);
+        let exp = Exp::new(0.5).unwrap();
+        let data: Vec<f64> = (0..200_000).map(|_| exp.sample(&mut rng)).collect();
+        let (l, expected) = (sample_l_moments(&data, 5), exp.l_moments(5));
+        for r in 0..5 {
+            assert_almost_eq!(l[r], expected[r], 0.02);
+        }
+        let uniform = Uniform::new(-1.0, 3.0).unwrap();
+        let data: Vec<f64> = (0..200_000).map(|_| uniform.sample(&mut rng)).collect();
+        let (l, expected) = (sample_l_moments(&data, 4), uniform.l_moments(4));
+        for r in 0..4 {
+            assert_almost_eq!(l[r], expected[r], 0.01);
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(sample_l_moments(&[], 2).iter().all(|x| x.is_nan()));
+        assert!(sample_l_moments(&[1.0, 2.0], 0).is_empty());
+        let l = sample_l_moments(&[1.0, 2.0], 3);
+        assert_eq!(l[0], 1.5);
+        assert_eq!(l[1], 0.5);
+        assert!(l[2].is_nan());
+        assert!(sample_l_moments(&[1.0, f64::NAN, 2.0], 2)[0].is_nan());
+        // a constant sample has no L-scale
+        let l = sample_l_moments(&[4.0; 5], 3);
+        assert_eq!(l[..2], [4.0, 0.0]);
+        assert!(l[2].is_nan());
+    }
+}
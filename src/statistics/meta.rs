@@ -0,0 +1,450 @@
+//! Provides fixed- and random-effects
+//! [meta-analysis](https://en.wikipedia.org/wiki/Meta-analysis), combining
+//! effect estimates of several studies given their standard errors
+//!
+//! The estimates are combined with inverse-variance weights, and the
+//! confidence intervals are the 95% Wald intervals of R's `metafor::rma`.
+
+use crate::distribution::{ChiSquared, ContinuousCDF};
+use crate::function::root::brent;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The `0.975` quantile of the standard normal distribution
+const Z_975: f64 = 1.959_963_984_540_054;
+
+/// Selects the estimator of the between-study variance `τ²` used by
+/// `random_effects`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Tau2Method {
+    /// The method of moments estimator of DerSimonian and Laird, in closed
+    /// form from Cochran's `Q`
+    DerSimonianLaird,
+    /// The estimator of Paule and Mandel, which solves for the `τ²` whose
+    /// generalized `Q` statistic equals its expectation `k - 1`
+    PauleMandel,
+    /// The restricted maximum likelihood estimator, found by the
+    /// fixed-point iteration of its estimating equation
+    Reml,
+}
+
+/// The combined estimate of a meta-analysis with its heterogeneity
+/// statistics
+#[derive(Clone, PartialEq, Debug)]
+pub struct MetaAnalysis {
+    /// The combined estimate
+    pub estimate: f64,
+    /// The standard error of the combined estimate
+    pub standard_error: f64,
+    /// The lower bound of the 95% confidence interval
+    pub lower: f64,
+    /// The upper bound of the 95% confidence interval
+    pub upper: f64,
+    /// The estimated between-study variance, `0` for the fixed-effect model
+    pub tau2: f64,
+    /// The proportion `I²` of the total variability due to heterogeneity,
+    /// between `0` and `1`
+    pub i2: f64,
+    /// Cochran's heterogeneity statistic `Q` with `k - 1` degrees of
+    /// freedom
+    pub q: f64,
+    /// The p-value of `Q` under the null hypothesis of homogeneity
+    pub q_p_value: f64,
+    /// The weights of the studies in the combined estimate, summing to `1`
+    pub weights: Vec<f64>,
+    estimates: Vec<f64>,
+    standard_errors: Vec<f64>,
+}
+
+/// A row of a forest plot, an estimate with its 95% confidence interval and
+/// its weight
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ForestRow {
+    /// The estimate
+    pub estimate: f64,
+    /// The lower bound of the 95% confidence interval
+    pub lower: f64,
+    /// The upper bound of the 95% confidence interval
+    pub upper: f64,
+    /// The weight in the combined estimate, `1` for the combined estimate
+    /// itself
+    pub weight: f64,
+}
+
+impl MetaAnalysis {
+    /// Returns the rows of a forest plot: one per study, in the order of
+    /// the input, with its Wald interval and weight, followed by one for the
+    /// combined estimate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::meta::fixed_effect;
+    ///
+    /// let meta = fixed_effect(&[0.2, 0.5], &[0.1, 0.2]).unwrap();
+    /// let rows = meta.forest_data();
+    /// assert_eq!(rows.len(), 3);
+    /// assert!((rows[0].weight - 0.8).abs() < 1e-15);
+    /// assert_eq!(rows[2].estimate, meta.estimate);
+    /// ```
+    pub fn forest_data(&self) -> Vec<ForestRow> {
+        let mut rows: Vec<ForestRow> = self
+            .estimates
+            .iter()
+            .zip(&self.standard_errors)
+            .zip(&self.weights)
+            .map(|((&estimate, &se), &weight)| ForestRow {
+                estimate,
+                lower: estimate - Z_975 * se,
+                upper: estimate + Z_975 * se,
+                weight,
+            })
+            .collect();
+        rows.push(ForestRow {
+            estimate: self.estimate,
+            lower: self.lower,
+            upper: self.upper,
+            weight: 1.0,
+        });
+        rows
+    }
+}
+
+/// Combines the `estimates` of several studies with standard errors `ses`
+/// under the fixed-effect model, which assumes that all the studies
+/// estimate the same effect
+///
+/// # Formula
+///
+/// ```text
+/// θ = Σ w_i y_i / Σ w_i, SE = 1 / sqrt(Σ w_i)
+/// Q = Σ w_i (y_i - θ)^2, I² = max(0, (Q - (k - 1)) / Q)
+/// ```
+///
+/// where `w_i = 1 / s_i^2`
+///
+/// # Remarks
+///
+/// A single study is returned as it is, with `Q = 0`, `I² = 0` and a
+/// p-value of `1`
+///
+/// # Errors
+///
+/// Returns an error if there are no studies, if the slices differ in
+/// length, if an estimate is not finite or if a standard error is not
+/// finite and positive
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::meta::fixed_effect;
+///
+/// let meta = fixed_effect(&[1.0, 2.0], &[1.0, 1.0]).unwrap();
+/// assert_eq!(meta.estimate, 1.5);
+/// assert!((meta.standard_error - 0.5f64.sqrt()).abs() < 1e-15);
+/// ```
+pub fn fixed_effect(estimates: &[f64], ses: &[f64]) -> Result<MetaAnalysis> {
+    check_studies(estimates, ses)?;
+    let variances: Vec<f64> = ses.iter().map(|s| s * s).collect();
+    let q = cochran_q(estimates, &variances);
+    let k = estimates.len() as f64;
+    let i2 = if q > k - 1.0 {
+        (q - (k - 1.0)) / q
+    } else {
+        0.0
+    };
+    Ok(combine(estimates, ses, &variances, 0.0, q, i2))
+}
+
+/// Combines the `estimates` of several studies with standard errors `ses`
+/// under the random-effects model, in which the effects of the studies
+/// vary around their mean with a variance `τ²` estimated by `method`
+///
+/// # Formula
+///
+/// ```text
+/// θ = Σ w_i y_i / Σ w_i, SE = 1 / sqrt(Σ w_i)
+/// I² = τ² / (τ² + s²), s² = (k - 1) Σ v_i^-1 / ((Σ v_i^-1)^2 - Σ v_i^-2)
+/// ```
+///
+/// where `w_i = 1 / (s_i^2 + τ²)`, `v_i = s_i^2` and `s²` is the typical
+/// within-study variance of Higgins and Thompson, as in `metafor::rma`.
+/// The DerSimonian-Laird estimator is
+///
+/// ```text
+/// τ² = max(0, (Q - (k - 1)) / (Σ v_i^-1 - Σ v_i^-2 / Σ v_i^-1))
+/// ```
+///
+/// where `Q` is Cochran's statistic of `fixed_effect`, with which `I²`
+/// reduces to `(Q - (k - 1)) / Q`.
+///
+/// # Remarks
+///
+/// Every estimator is truncated at zero. A single study is returned as
+/// it is, with `τ² = 0`.
+///
+/// # Errors
+///
+/// Returns an error under the conditions of `fixed_effect`, or if the
+/// REML iteration fails to converge
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::meta::{random_effects, Tau2Method};
+///
+/// let estimates = [0.1, 0.9, -0.3, 0.6];
+/// let ses = [0.2, 0.2, 0.25, 0.3];
+/// let meta = random_effects(&estimates, &ses, Tau2Method::DerSimonianLaird).unwrap();
+/// assert!(meta.tau2 > 0.0);
+/// assert!(meta.i2 > 0.5 && meta.q_p_value < 0.05);
+/// ```
+pub fn random_effects(estimates: &[f64], ses: &[f64], method: Tau2Method) -> Result<MetaAnalysis> {
+    check_studies(estimates, ses)?;
+    let variances: Vec<f64> = ses.iter().map(|s| s * s).collect();
+    let q = cochran_q(estimates, &variances);
+    let k = estimates.len() as f64;
+    if estimates.len() == 1 {
+        return Ok(combine(estimates, ses, &variances, 0.0, q, 0.0));
+    }
+    let sum_w: f64 = variances.iter().map(|v| 1.0 / v).sum();
+    let sum_w2: f64 = variances.iter().map(|v| 1.0 / (v * v)).sum();
+    let dl = ((q - (k - 1.0)) / (sum_w - sum_w2 / sum_w)).max(0.0);
+    let tau2 = match method {
+        Tau2Method::DerSimonianLaird => dl,
+        Tau2Method::PauleMandel => {
+            let excess = |tau2: f64| generalized_q(estimates, &variances, tau2) - (k - 1.0);
+            if excess(0.0) <= 0.0 {
+                0.0
+            } else {
+                let mut hi = dl.max(1e-8) * 2.0;
+                while excess(hi) > 0.0 {
+                    hi *= 2.0;
+                }
+                brent(excess, 0.0, hi, 1e-15 * hi)?
+            }
+        }
+        Tau2Method::Reml => reml(estimates, &variances, dl)?,
+    };
+    let typical = (k - 1.0) * sum_w / (sum_w * sum_w - sum_w2);
+    let i2 = tau2 / (tau2 + typical);
+    Ok(combine(estimates, ses, &variances, tau2, q, i2))
+}
+
+fn check_studies(estimates: &[f64], ses: &[f64]) -> Result<()> {
+    if estimates.len() != ses.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if estimates.is_empty() {
+        return Err(StatsError::SpecialCase("there must be at least one study"));
+    }
+    if estimates.iter().any(|y| !y.is_finite()) {
+        return Err(StatsError::SpecialCase("the estimates must be finite"));
+    }
+    if ses.iter().any(|&s| !s.is_finite() || s <= 0.0) {
+        return Err(StatsError::SpecialCase(
+            "the standard errors must be finite and positive",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the weighted mean of `estimates` with weights `1 / (v_i + τ²)`
+/// and the sum of the weights
+fn weighted_mean(estimates: &[f64], variances: &[f64], tau2: f64) -> (f64, f64) {
+    let (sum, sum_w) = estimates
+        .iter()
+        .zip(variances)
+        .fold((0.0, 0.0), |(sum, sum_w), (y, v)| {
+            (sum + y / (v + tau2), sum_w + 1.0 / (v + tau2))
+        });
+    (sum / sum_w, sum_w)
+}
+
+/// Returns the generalized `Q` statistic `Σ (y_i - θ)^2 / (v_i + τ²)`
+fn generalized_q(estimates: &[f64], variances: &[f64], tau2: f64) -> f64 {
+    let (mean, _) = weighted_mean(estimates, variances, tau2);
+    estimates
+        .iter()
+        .zip(variances)
+        .map(|(y, v)| (y - mean).powi(2) / (v + tau2))
+        .sum()
+}
+
+fn cochran_q(estimates: &[f64], variances: &[f64]) -> f64 {
+    generalized_q(estimates, variances, 0.0)
+}
+
+/// Iterates `τ² = Σ w_i^2 ((y_i - θ)^2 - v_i) / Σ w_i^2 + 1 / Σ w_i` from
+/// `start`, the fixed point of the REML estimating equation truncated at
+/// zero
+fn reml(estimates: &[f64], variances: &[f64], start: f64) -> Result<f64> {
+    let mut tau2 = start;
+    for _ in 0..1000 {
+        let (mean, sum_w) = weighted_mean(estimates, variances, tau2);
+        let (numerator, sum_w2) =
+            estimates
+                .iter()
+                .zip(variances)
+                .fold((0.0, 0.0), |(numerator, sum_w2), (y, v)| {
+                    let w2 = (v + tau2).powi(-2);
+                    (numerator + w2 * ((y - mean).powi(2) - v), sum_w2 + w2)
+                });
+        let next = (numerator / sum_w2 + 1.0 / sum_w).max(0.0);
+        if (next - tau2).abs() <= 1e-14 * (1.0 + next) {
+            return Ok(next);
+        }
+        tau2 = next;
+    }
+    Err(StatsError::SpecialCase(
+        "the REML iteration did not converge",
+    ))
+}
+
+fn combine(
+    estimates: &[f64],
+    ses: &[f64],
+    variances: &[f64],
+    tau2: f64,
+    q: f64,
+    i2: f64,
+) -> MetaAnalysis {
+    let (estimate, sum_w) = weighted_mean(estimates, variances, tau2);
+    let standard_error = sum_w.sqrt().recip();
+    let freedom = estimates.len() - 1;
+    let q_p_value = if freedom == 0 {
+        1.0
+    } else {
+        ChiSquared::new(freedom as f64).unwrap().sf(q)
+    };
+    MetaAnalysis {
+        estimate,
+        standard_error,
+        lower: estimate - Z_975 * standard_error,
+        upper: estimate + Z_975 * standard_error,
+        tau2,
+        i2,
+        q,
+        q_p_value,
+        weights: variances.iter().map(|v| 1.0 / (v + tau2) / sum_w).collect(),
+        estimates: estimates.to_vec(),
+        standard_errors: ses.to_vec(),
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The log risk ratios of the 13 BCG vaccine trials of Colditz et al.
+    /// (1994), `dat.bcg` of metafor, with their standard errors
+    fn bcg() -> (Vec<f64>, Vec<f64>) {
+        let counts: [(f64, f64, f64, f64); 13] = [
+            (4.0, 119.0, 11.0, 128.0), (6.0, 300.0, 29.0, 274.0), (3.0, 228.0, 11.0, 209.0),
+            (62.0, 13536.0, 248.0, 12619.0), (33.0, 5036.0, 47.0, 5761.0), (180.0, 1361.0, 372.0, 1079.0),
+            (8.0, 2537.0, 10.0, 619.0), (505.0, 87886.0, 499.0, 87892.0), (29.0, 7470.0, 45.0, 7232.0),
+            (17.0, 1699.0, 65.0, 1600.0), (186.0, 50448.0, 141.0, 27197.0), (5.0, 2493.0, 3.0, 2338.0),
+            (27.0, 16886.0, 29.0, 17825.0),
+        ];
+        counts
+            .iter()
+            .map(|&(a, b, c, d)| {
+                let y = ((a / (a + b)) / (c / (c + d))).ln();
+                let v = 1.0 / a - 1.0 / (a + b) + 1.0 / c - 1.0 / (c + d);
+                (y, v.sqrt())
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_fixed_effect_bcg() {
+        // metafor reports -0.4303 (SE 0.0405) and Q = 152.2330; refined
+        // with mpmath
+        let (y, s) = bcg();
+        let meta = fixed_effect(&y, &s).unwrap();
+        assert_almost_eq!(meta.estimate, -0.43028516365409073, 1e-12);
+        assert_almost_eq!(meta.standard_error, 0.040498751710863814, 1e-14);
+        assert_almost_eq!(meta.q, 152.23300808237342, 1e-9);
+        assert!(meta.q_p_value < 1e-25);
+        assert_almost_eq!(meta.i2, (152.23300808237342 - 12.0) / 152.23300808237342, 1e-12);
+        assert_eq!(meta.tau2, 0.0);
+        assert_almost_eq!(meta.weights.iter().sum::<f64>(), 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_random_effects_bcg() {
+        // metafor::rma reports -0.7141, tau^2 = 0.3088 and I^2 = 92.12% for
+        // DL, -0.7145, 0.3132 and 92.22% for REML and tau^2 = 0.3181 for PM;
+        // refined with mpmath
+        let (y, s) = bcg();
+        let check = |method, expected: [f64; 4]| {
+            let meta = random_effects(&y, &s, method).unwrap();
+            assert_almost_eq!(meta.estimate, expected[0], 1e-10);
+            assert_almost_eq!(meta.standard_error, expected[1], 1e-10);
+            assert_almost_eq!(meta.tau2, expected[2], 1e-10);
+            assert_almost_eq!(meta.i2, expected[3], 1e-10);
+            assert_almost_eq!(meta.q, 152.23300808237342, 1e-9);
+            assert_almost_eq!(meta.lower, expected[0] - 1.959963984540054 * expected[1], 1e-10);
+            meta
+        };
+        let dl = check(Tau2Method::DerSimonianLaird, [-0.71411722207322706, 0.17874208953540318, 0.30876026286237647, 0.92117346854562061]);
+        assert_almost_eq!(dl.weights[0], 0.05036491683, 1e-10);
+        check(Tau2Method::PauleMandel, [-0.71496815349344417, 0.18089219153762354, 0.31806845220502417, 0.92330337954153243]);
+        let reml = check(Tau2Method::Reml, [-0.71453234215813923, 0.17978151610520576, 0.31324325813648095, 0.92221384521268954]);
+        assert_almost_eq!(reml.weights[0], 0.05059482734, 1e-10);
+    }
+
+    #[test]
+    fn test_homogeneous() {
+        // Q below its expectation truncates tau^2 at zero
+        let y = [0.5, 0.52, 0.49, 0.51];
+        let s = [0.1, 0.2, 0.15, 0.1];
+        let fixed = fixed_effect(&y, &s).unwrap();
+        for &method in &[Tau2Method::DerSimonianLaird, Tau2Method::PauleMandel, Tau2Method::Reml] {
+            let meta = random_effects(&y, &s, method).unwrap();
+            assert_eq!(meta.tau2, 0.0);
+            assert_eq!(meta.i2, 0.0);
+            assert_almost_eq!(meta.estimate, fixed.estimate, 1e-15);
+        }
+        assert_eq!(fixed.i2, 0.0);
+    }
+
+    #[test]
+    fn test_single_study() {
+        for meta in &[
+            fixed_effect(&[0.3], &[0.1]).unwrap(),
+            random_effects(&[0.3], &[0.1], Tau2Method::PauleMandel).unwrap(),
+        ] {
+            assert_eq!(meta.estimate, 0.3);
+            assert_almost_eq!(meta.standard_error, 0.1, 1e-15);
+            assert_eq!((meta.tau2, meta.i2, meta.q, meta.q_p_value), (0.0, 0.0, 0.0, 1.0));
+            assert_eq!(meta.weights, vec![1.0]);
+            let rows = meta.forest_data();
+            assert_eq!(rows.len(), 2);
+            assert_almost_eq!(rows[0].lower, rows[1].lower, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_forest_data() {
+        let (y, s) = bcg();
+        let meta = random_effects(&y, &s, Tau2Method::DerSimonianLaird).unwrap();
+        let rows = meta.forest_data();
+        assert_eq!(rows.len(), 14);
+        assert_eq!(rows[3].estimate, y[3]);
+        assert_almost_eq!(rows[3].upper - rows[3].lower, 2.0 * 1.959963984540054 * s[3], 1e-14);
+        assert_eq!(rows[3].weight, meta.weights[3]);
+        assert_eq!((rows[13].estimate, rows[13].lower, rows[13].weight), (meta.estimate, meta.lower, 1.0));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(fixed_effect(&[], &[]).is_err());
+        assert!(fixed_effect(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(fixed_effect(&[1.0, 2.0], &[1.0, 0.0]).is_err());
+        assert!(random_effects(&[1.0, 2.0], &[1.0, 0.0], Tau2Method::Reml).is_err());
+        assert!(random_effects(&[1.0, f64::NAN], &[1.0, 1.0], Tau2Method::Reml).is_err());
+        assert!(random_effects(&[1.0, 2.0], &[1.0, f64::INFINITY], Tau2Method::Reml).is_err());
+    }
+}
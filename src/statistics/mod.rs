@@ -1,15 +1,41 @@
 //! Provides traits for statistical computation
 
+pub use self::bayes_histogram::*;
+pub use self::indexed_data::*;
 pub use self::iter_statistics::*;
 pub use self::order_statistics::*;
 pub use self::slice_statistics::*;
 pub use self::statistics::*;
 pub use self::traits::*;
 
+mod bayes_histogram;
+pub mod circular;
+pub mod cumulative;
+pub mod dependence;
+pub mod filter;
+pub mod grouped;
+pub mod holt_winters;
+mod indexed_data;
 mod iter_statistics;
+pub mod kde;
+pub mod l_moments;
+pub mod meta;
 mod order_statistics;
+pub mod regression;
+pub mod resampling;
+pub mod robust;
+pub mod sketch;
 // TODO: fix later
 mod slice_statistics;
+pub mod spc;
+pub mod split;
+pub mod streaming;
 #[allow(clippy::module_inception)]
 mod statistics;
+pub mod survey;
+pub mod survival;
+pub mod tail;
+pub mod time_series;
+pub mod transform;
+pub mod window;
 mod traits;
@@ -0,0 +1,693 @@
+//! Provides regression models fitted by least squares
+//!
+//! The predictors are given as one row of values per observation, and an
+//! intercept is always included, so the coefficients are the intercept
+//! followed by one coefficient per predictor.
+
+use crate::distribution::{Discrete, Poisson};
+use crate::function::logistic::{log1p_exp, logistic};
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use std::f64;
+
+/// A multiple linear regression fitted by ordinary least squares, as
+/// returned by `multiple_linear_regression`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiLinReg {
+    /// The intercept followed by the coefficients of the predictors
+    pub coefficients: Vec<f64>,
+    /// The standard errors of the coefficients, in the same order
+    pub standard_errors: Vec<f64>,
+    /// The coefficient of determination, the fraction of the variance of
+    /// the response explained by the predictors
+    pub r_squared: f64,
+    /// The variance inflation factors of the predictors, `1` for a
+    /// predictor uncorrelated with the others
+    pub vifs: Vec<f64>,
+}
+
+/// Checks that the rows of `x` are non-empty, of equal length and as many
+/// as `y` has values, and that every value is finite, returning the number
+/// of predictors
+pub(crate) fn check_design(x: &[Vec<f64>], y_len: usize) -> Result<usize> {
+    if x.is_empty() || x.len() != y_len {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let predictors = x[0].len();
+    if x.iter().any(|row| row.len() != predictors) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if x.iter().flatten().any(|v| !v.is_finite()) {
+        return Err(StatsError::SpecialCase("the predictors must be finite"));
+    }
+    Ok(predictors)
+}
+
+/// Returns the design matrix of `x` with a leading column of ones for the
+/// intercept
+pub(crate) fn design_matrix(x: &[Vec<f64>]) -> DMatrix<f64> {
+    let columns = x[0].len() + 1;
+    DMatrix::from_fn(
+        x.len(),
+        columns,
+        |i, j| if j == 0 { 1.0 } else { x[i][j - 1] },
+    )
+}
+
+/// Fits the linear regression of `y` on the predictors `x`, one row per
+/// observation, with an intercept, by ordinary least squares
+///
+/// # Formula
+///
+/// ```text
+/// β = (X' X)^(-1) X' y,  se(β_j) = sqrt(s^2 [(X' X)^(-1)]_jj)
+/// VIF_j = [R^(-1)]_jj = 1 / (1 - R_j^2)
+/// ```
+///
+/// where `X` is the design matrix with a column of ones,
+/// `s^2 = RSS / (n - p - 1)` the residual variance, `R` the correlation
+/// matrix of the `p` predictors and `R_j^2` the coefficient of
+/// determination of the regression of predictor `j` on the others.
+///
+/// # Remarks
+///
+/// The variance inflation factors diagnose multicollinearity: the variance
+/// of a coefficient is `VIF_j` times what it would be with predictors
+/// uncorrelated with each other, and values above about `10` are commonly
+/// taken to make the coefficient unreliable. `ridge_regression` stabilizes
+/// the coefficients of collinear predictors.
+///
+/// # Errors
+///
+/// Returns an error if `x` is empty, if its rows differ in length or are
+/// not as many as the values of `y`, if a value is not finite, if there are
+/// not more observations than coefficients, if a predictor is constant, or
+/// if the predictors are exactly collinear
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::regression::multiple_linear_regression;
+///
+/// let x = vec![vec![1.0, 2.0], vec![2.0, 1.0], vec![3.0, 4.0], vec![4.0, 3.0], vec![5.0, 5.5]];
+/// let y: Vec<f64> = x.iter().map(|r| 1.0 + 2.0 * r[0] - r[1]).collect();
+/// let fit = multiple_linear_regression(&x, &y).unwrap();
+/// assert!((fit.coefficients[0] - 1.0).abs() < 1e-12);
+/// assert!((fit.coefficients[1] - 2.0).abs() < 1e-12);
+/// assert!((fit.coefficients[2] + 1.0).abs() < 1e-12);
+/// assert!((fit.r_squared - 1.0).abs() < 1e-12);
+/// ```
+pub fn multiple_linear_regression(x: &[Vec<f64>], y: &[f64]) -> Result<MultiLinReg> {
+    let predictors = check_design(x, y.len())?;
+    if y.iter().any(|v| !v.is_finite()) {
+        return Err(StatsError::SpecialCase("the response must be finite"));
+    }
+    let n = x.len();
+    if n <= predictors + 1 {
+        return Err(StatsError::SpecialCase(
+            "there must be more observations than coefficients",
+        ));
+    }
+    let design = design_matrix(x);
+    let response = DVector::from_column_slice(y);
+    let cholesky = (design.transpose() * &design)
+        .cholesky()
+        .ok_or(StatsError::SpecialCase("the predictors are collinear"))?;
+    let coefficients = cholesky.solve(&(design.transpose() * &response));
+    let residuals = &response - &design * &coefficients;
+    let rss = residuals.norm_squared();
+    let variance = rss / (n - predictors - 1) as f64;
+    let inverse = cholesky.inverse();
+    let standard_errors = (0..=predictors)
+        .map(|j| (variance * inverse[(j, j)]).sqrt())
+        .collect();
+    let mean = response.mean();
+    let tss: f64 = y.iter().map(|v| (v - mean) * (v - mean)).sum();
+    Ok(MultiLinReg {
+        coefficients: coefficients.iter().copied().collect(),
+        standard_errors,
+        r_squared: 1.0 - rss / tss,
+        vifs: variance_inflation_factors(&design.columns(1, predictors).into_owned())?,
+    })
+}
+
+/// Fits the linear regression of `y` on the predictors `x`, one row per
+/// observation, with an intercept, by ridge regression with penalty
+/// `lambda`, returning the intercept followed by the coefficients of the
+/// predictors
+///
+/// # Formula
+///
+/// ```text
+/// β = (X' X + λ D)^(-1) X' y
+/// ```
+///
+/// where `X` is the design matrix with a column of ones and `D` the
+/// identity matrix with a `0` for the intercept, so that the sum of squared
+/// residuals plus `λ` times the sum of the squared coefficients of the
+/// predictors is minimized.
+///
+/// # Remarks
+///
+/// The intercept is not penalized, so the fit does not depend on the
+/// origin of the response. As `λ` grows the coefficients of the predictors
+/// shrink towards `0` and the intercept tends to the mean of `y`; with
+/// `λ = 0` the fit is that of `multiple_linear_regression`. The penalty
+/// makes the system well conditioned however collinear the predictors, at
+/// the cost of a bias, and trades off with the scale of the predictors,
+/// which are usually standardized first.
+///
+/// # Errors
+///
+/// Returns an error if `lambda` is negative or `f64::NAN`, if `x` is
+/// empty, if its rows differ in length or are not as many as the values of
+/// `y`, if a value is not finite, or if the system is singular, which can
+/// only happen for `λ = 0`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::regression::ridge_regression;
+///
+/// // two identical predictors share the effect equally
+/// let x: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64, i as f64]).collect();
+/// let y = [1.0, 3.0, 5.0, 7.0, 9.0];
+/// let beta = ridge_regression(&x, &y, 1e-6).unwrap();
+/// assert!((beta[0] - 1.0).abs() < 1e-5);
+/// assert!((beta[1] - 1.0).abs() < 1e-5);
+/// assert!((beta[2] - 1.0).abs() < 1e-5);
+/// ```
+pub fn ridge_regression(x: &[Vec<f64>], y: &[f64], lambda: f64) -> Result<Vec<f64>> {
+    if lambda.is_nan() || lambda < 0.0 {
+        return Err(StatsError::ArgNotNegative("lambda"));
+    }
+    let predictors = check_design(x, y.len())?;
+    if y.iter().any(|v| !v.is_finite()) {
+        return Err(StatsError::SpecialCase("the response must be finite"));
+    }
+    let design = design_matrix(x);
+    let response = DVector::from_column_slice(y);
+    let mut system = design.transpose() * &design;
+    for j in 1..=predictors {
+        system[(j, j)] += lambda;
+    }
+    let coefficients = system
+        .cholesky()
+        .ok_or(StatsError::SpecialCase("the predictors are collinear"))?
+        .solve(&(design.transpose() * &response));
+    Ok(coefficients.iter().copied().collect())
+}
+
+/// Fits the logistic regression of the binary `y` on the predictors `x`,
+/// one row per observation, with an intercept, by maximum likelihood,
+/// returning the intercept followed by the coefficients of the predictors
+///
+/// # Formula
+///
+/// The model is `P(y_i) = logistic(x_i' β)`, and each iteratively
+/// reweighted least squares step is a Newton step on the log-likelihood,
+///
+/// ```text
+/// β ← β + (X' W X)^(-1) X' (y - p),  W = diag(p_i (1 - p_i))
+/// ```
+///
+/// where `p_i = logistic(x_i' β)`, starting from `β = 0`.
+///
+/// # Remarks
+///
+/// The iterations stop, as in R's `glm`, once the deviance
+/// `D = -2 ln L` changes by less than `tol (|D| + 0.1)`, after `max_iter`
+/// steps, or when the weights vanish so that `X' W X` is singular. When
+/// the classes are separated by a hyperplane the likelihood has no maximum
+/// and the coefficients grow without bound; the ones reached when the
+/// iterations stop still classify the data perfectly, with probabilities
+/// close to `0` and `1`. R uses `tol = 1e-8` and `max_iter = 25`.
+///
+/// # Errors
+///
+/// Returns an error if `max_iter` is `0`, if `tol` is not positive, if `x`
+/// is empty, if its rows differ in length or are not as many as the values
+/// of `y`, or if a value is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::regression::logistic_regression;
+///
+/// let x: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64]).collect();
+/// let y = [false, false, true, false, true, false, true, true];
+/// let beta = logistic_regression(&x, &y, 25, 1e-10).unwrap();
+/// // the probability increases with the predictor
+/// assert!(beta[1] > 0.0);
+/// ```
+pub fn logistic_regression(
+    x: &[Vec<f64>],
+    y: &[bool],
+    max_iter: usize,
+    tol: f64,
+) -> Result<Vec<f64>> {
+    check_glm(x, y.len(), max_iter, tol)?;
+    let design = design_matrix(x);
+    let response = DVector::from_fn(y.len(), |i, _| if y[i] { 1.0 } else { 0.0 });
+    // -2 ln L, with ln(1 - logistic(η)) = -ln(1 + e^η)
+    let deviance = |eta: &DVector<f64>| -> f64 {
+        2.0 * eta
+            .iter()
+            .zip(y)
+            .map(|(&e, &yi)| log1p_exp(if yi { -e } else { e }))
+            .sum::<f64>()
+    };
+    let step = |eta: &DVector<f64>| {
+        let p = eta.map(logistic);
+        (p.map(|pi| pi * (1.0 - pi)), &response - &p)
+    };
+    let eta = DVector::zeros(y.len());
+    Ok(irls(&design, eta, max_iter, tol, step, deviance))
+}
+
+/// Fits a Poisson regression with the log link to the counts `y` given the
+/// rows of predictors `x`, by iteratively reweighted least squares, and
+/// returns the intercept followed by the coefficients of the predictors
+///
+/// The model is `y_i ~ Poisson(exp(x_i' β))`, and each iteratively
+/// reweighted least squares step is a Newton step on the log-likelihood,
+///
+/// ```text
+/// β ← β + (X' W X)^(-1) X' (y - μ),  W = diag(μ_i)
+/// ```
+///
+/// where `μ_i = exp(x_i' β)`. As in R's `glm`, the first step starts from
+/// the linear predictor `ln(y_i + 0.1)` rather than from a value of `β`.
+///
+/// # Remarks
+///
+/// The iterations stop once the deviance, twice the difference between the
+/// Poisson log-likelihoods of the saturated model `μ_i = y_i` and of the
+/// fit, changes by less than `tol (|D| + 0.1)`, after `max_iter` steps, or
+/// when `X' W X` is singular. When the counts of a group of observations
+/// are all zero the likelihood has no maximum and the fitted means of the
+/// group tend to `0`. R uses `tol = 1e-8` and `max_iter = 25`.
+///
+/// # Errors
+///
+/// Returns an error if `max_iter` is `0`, if `tol` is not positive, if `x`
+/// is empty, if its rows differ in length or are not as many as the values
+/// of `y`, or if a value is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::regression::poisson_regression;
+///
+/// let x: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64]).collect();
+/// let y = [1, 1, 2, 4, 5, 9, 14, 21];
+/// let beta = poisson_regression(&x, &y, 25, 1e-10).unwrap();
+/// // the counts grow by about half of their value with each step
+/// assert!((beta[1] - 0.45).abs() < 0.05);
+/// ```
+pub fn poisson_regression(
+    x: &[Vec<f64>],
+    y: &[u64],
+    max_iter: usize,
+    tol: f64,
+) -> Result<Vec<f64>> {
+    check_glm(x, y.len(), max_iter, tol)?;
+    let design = design_matrix(x);
+    let response = DVector::from_fn(y.len(), |i, _| y[i] as f64);
+    // the saturated model has μ_i = y_i, where a zero count has probability 1
+    let saturated: f64 = y
+        .iter()
+        .map(|&yi| Poisson::new(yi as f64).map_or(0.0, |d| d.ln_pmf(yi)))
+        .sum();
+    let deviance = |eta: &DVector<f64>| -> f64 {
+        let fitted: f64 = eta
+            .iter()
+            .zip(y)
+            .map(|(&e, &yi)| Poisson::new(e.exp()).map_or(f64::NAN, |d| d.ln_pmf(yi)))
+            .sum();
+        2.0 * (saturated - fitted)
+    };
+    let step = |eta: &DVector<f64>| {
+        let mu = eta.map(f64::exp);
+        let residual = &response - &mu;
+        (mu, residual)
+    };
+    let eta = response.map(|yi| (yi + 0.1).ln());
+    Ok(irls(&design, eta, max_iter, tol, step, deviance))
+}
+
+/// Checks the arguments of a generalized linear model fitted by
+/// iteratively reweighted least squares
+fn check_glm(x: &[Vec<f64>], y_len: usize, max_iter: usize, tol: f64) -> Result<()> {
+    if max_iter == 0 {
+        return Err(StatsError::ArgMustBePositive("max_iter"));
+    }
+    if tol.is_nan() || tol <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tol"));
+    }
+    check_design(x, y_len).map(|_| ())
+}
+
+/// Fits a generalized linear model with a canonical link by iteratively
+/// reweighted least squares, starting from the linear predictor `eta`
+///
+/// `step` returns the weights and the residuals `y - μ` at a linear
+/// predictor, so that each iteration solves
+/// `X' W X β = X' (W η + y - μ)`, which is a Newton step from `β` when
+/// `η = X β`. The iterations stop as in R's `glm`, once the `deviance`
+/// changes by less than `tol (|D| + 0.1)`, or when `X' W X` is singular,
+/// in which case the last coefficients are kept, zero before the first
+/// step.
+fn irls<S, D>(
+    design: &DMatrix<f64>,
+    mut eta: DVector<f64>,
+    max_iter: usize,
+    tol: f64,
+    step: S,
+    deviance: D,
+) -> Vec<f64>
+where
+    S: Fn(&DVector<f64>) -> (DVector<f64>, DVector<f64>),
+    D: Fn(&DVector<f64>) -> f64,
+{
+    let mut beta = DVector::zeros(design.ncols());
+    let mut current = deviance(&eta);
+    for _ in 0..max_iter {
+        let (weights, residuals) = step(&eta);
+        let mut weighted = design.clone();
+        for (mut row, &w) in weighted.row_iter_mut().zip(weights.iter()) {
+            row *= w;
+        }
+        let cholesky = match design.tr_mul(&weighted).cholesky() {
+            Some(cholesky) => cholesky,
+            None => break,
+        };
+        beta = cholesky.solve(&design.tr_mul(&(weights.component_mul(&eta) + residuals)));
+        eta = design * &beta;
+        let next = deviance(&eta);
+        let converged = (current - next).abs() < tol * (next.abs() + 0.1);
+        current = next;
+        if converged {
+            break;
+        }
+    }
+    beta.iter().copied().collect()
+}
+
+/// Returns the variance inflation factors of the columns of `predictors`,
+/// the diagonal of the inverse of their correlation matrix
+fn variance_inflation_factors(predictors: &DMatrix<f64>) -> Result<Vec<f64>> {
+    let mut standardized = predictors.clone();
+    for mut column in standardized.column_iter_mut() {
+        let mean = column.mean();
+        column.add_scalar_mut(-mean);
+        let norm = column.norm();
+        if norm == 0.0 {
+            return Err(StatsError::SpecialCase("a predictor is constant"));
+        }
+        column /= norm;
+    }
+    let correlation = standardized.transpose() * &standardized;
+    let inverse = correlation
+        .cholesky()
+        .ok_or(StatsError::SpecialCase("the predictors are collinear"))?
+        .inverse();
+    Ok(inverse.diagonal().iter().copied().collect())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_util::SplitMix64;
+    use rand::Rng;
+
+    fn example() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let x1 = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let x2 = [2.0, 1.0, 4.0, 3.0, 6.0, 5.0, 8.0, 9.0];
+        let x = x1.iter().zip(&x2).map(|(&a, &b)| vec![a, b]).collect();
+        (x, vec![3.1, 3.9, 7.2, 7.8, 11.1, 11.9, 15.2, 16.8])
+    }
+
+    #[test]
+    fn test_reference_fit() {
+        // reference values computed with mpmath
+        let (x, y) = example();
+        let fit = multiple_linear_regression(&x, &y).unwrap();
+        let coefficients = [0.57254901960784314, 1.3617647058823529, 0.61568627450980392];
+        let standard_errors = [0.10685464981030201, 0.058369160195913073, 0.050776363977669492];
+        for j in 0..3 {
+            assert_almost_eq!(fit.coefficients[j], coefficients[j], 1e-13);
+            assert_almost_eq!(fit.standard_errors[j], standard_errors[j], 1e-13);
+        }
+        assert_almost_eq!(fit.r_squared, 0.99946169039330878, 1e-14);
+        // with two predictors both factors are 1 / (1 - r^2)
+        assert_eq!(fit.vifs.len(), 2);
+        assert_almost_eq!(fit.vifs[0], 7.6176470588235294, 1e-12);
+        assert_almost_eq!(fit.vifs[1], 7.6176470588235294, 1e-12);
+    }
+
+    #[test]
+    fn test_orthogonal_predictors() {
+        // centred orthogonal columns do not inflate the variances
+        let x = vec![vec![-1.0, -1.0], vec![-1.0, 1.0], vec![1.0, -1.0], vec![1.0, 1.0], vec![0.0, 0.0]];
+        let y = [1.0, 3.0, 2.0, 5.0, 2.5];
+        let fit = multiple_linear_regression(&x, &y).unwrap();
+        assert_almost_eq!(fit.vifs[0], 1.0, 1e-14);
+        assert_almost_eq!(fit.vifs[1], 1.0, 1e-14);
+        assert_almost_eq!(fit.coefficients[0], 2.7, 1e-14);
+        assert_almost_eq!(fit.coefficients[1], 0.75, 1e-14);
+        assert_almost_eq!(fit.coefficients[2], 1.25, 1e-14);
+        // a single predictor has nothing to be collinear with
+        let x: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64]).collect();
+        let fit = multiple_linear_regression(&x, &[1.0, 2.0, 2.0, 4.0, 5.0, 5.0]).unwrap();
+        assert_eq!(fit.vifs.len(), 1);
+        assert_almost_eq!(fit.vifs[0], 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_near_collinear_predictors() {
+        // x2 differs from x1 by a small perturbation, x3 is unrelated
+        let x: Vec<Vec<f64>> = (0..20)
+            .map(|i| {
+                let t = i as f64;
+                vec![t, t + 0.01 * (t * 1.7).sin(), (t * 0.9).cos()]
+            })
+            .collect();
+        let y: Vec<f64> = x.iter().enumerate().map(|(i, r)| 1.0 + r[0] + r[1] + r[2] + 0.1 * (i as f64 * 2.3).sin()).collect();
+        let fit = multiple_linear_regression(&x, &y).unwrap();
+        assert!(fit.vifs[0] > 1e4, "{:?}", fit.vifs);
+        assert!(fit.vifs[1] > 1e4, "{:?}", fit.vifs);
+        assert!(fit.vifs[2] < 2.0, "{:?}", fit.vifs);
+        // se(β_j)^2 = s^2 VIF_j / Σ (x_ij - mean_j)^2
+        let spread = |j: usize| {
+            let mean = x.iter().map(|r| r[j]).sum::<f64>() / x.len() as f64;
+            x.iter().map(|r| (r[j] - mean) * (r[j] - mean)).sum::<f64>()
+        };
+        let scaled = |j: usize| fit.standard_errors[j + 1].powi(2) * spread(j) / fit.vifs[j];
+        assert_almost_eq!(scaled(0) / scaled(2), 1.0, 1e-6);
+        assert_almost_eq!(scaled(1) / scaled(2), 1.0, 1e-6);
+        assert!(fit.r_squared > 0.99);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (x, y) = example();
+        assert!(multiple_linear_regression(&x, &y[1..]).is_err());
+        assert!(multiple_linear_regression(&[], &[]).is_err());
+        let mut ragged = x.clone();
+        ragged[3].push(1.0);
+        assert!(multiple_linear_regression(&ragged, &y).is_err());
+        let mut nan = y.clone();
+        nan[0] = f64::NAN;
+        assert!(multiple_linear_regression(&x, &nan).is_err());
+        assert!(multiple_linear_regression(&x[..3], &y[..3]).is_err());
+        let constant: Vec<Vec<f64>> = x.iter().map(|r| vec![r[0], 2.0]).collect();
+        assert!(multiple_linear_regression(&constant, &y).is_err());
+        let collinear: Vec<Vec<f64>> = x.iter().map(|r| vec![r[0], 2.0 * r[0]]).collect();
+        assert!(multiple_linear_regression(&collinear, &y).is_err());
+    }
+
+    #[test]
+    fn test_ridge_without_penalty() {
+        let (x, y) = example();
+        let ols = multiple_linear_regression(&x, &y).unwrap();
+        let ridge = ridge_regression(&x, &y, 0.0).unwrap();
+        assert_eq!(ridge.len(), 3);
+        for (a, b) in ridge.iter().zip(&ols.coefficients) {
+            assert_almost_eq!(*a, *b, 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_ridge_shrinkage() {
+        let (x, y) = example();
+        let mean = y.iter().sum::<f64>() / y.len() as f64;
+        let norm = |beta: &[f64]| beta[1..].iter().map(|b| b * b).sum::<f64>().sqrt();
+        let mut previous = f64::INFINITY;
+        for &lambda in &[0.0, 0.1, 1.0, 10.0, 100.0, 1e4] {
+            let beta = ridge_regression(&x, &y, lambda).unwrap();
+            assert!(norm(&beta) < previous, "{} {:?}", lambda, beta);
+            previous = norm(&beta);
+        }
+        // the slopes vanish and the intercept tends to the mean response
+        let beta = ridge_regression(&x, &y, 1e12).unwrap();
+        assert!(norm(&beta) < 1e-9);
+        assert_almost_eq!(beta[0], mean, 1e-8);
+        // shifting the response only moves the intercept
+        let shifted: Vec<f64> = y.iter().map(|v| v + 10.0).collect();
+        let a = ridge_regression(&x, &y, 5.0).unwrap();
+        let b = ridge_regression(&x, &shifted, 5.0).unwrap();
+        assert_almost_eq!(b[0], a[0] + 10.0, 1e-12);
+        assert_almost_eq!(b[1], a[1], 1e-12);
+        assert_almost_eq!(b[2], a[2], 1e-12);
+    }
+
+    #[test]
+    fn test_ridge_collinear() {
+        // ordinary least squares fails on identical predictors, ridge splits them
+        let (x, y) = example();
+        let collinear: Vec<Vec<f64>> = x.iter().map(|r| vec![r[0], r[0]]).collect();
+        assert!(multiple_linear_regression(&collinear, &y).is_err());
+        assert!(ridge_regression(&collinear, &y, 0.0).is_err());
+        let beta = ridge_regression(&collinear, &y, 1.0).unwrap();
+        assert_almost_eq!(beta[1], beta[2], 1e-12);
+        // the sum of the coefficients approaches the simple regression slope
+        let single: Vec<Vec<f64>> = x.iter().map(|r| vec![r[0]]).collect();
+        let slope = multiple_linear_regression(&single, &y).unwrap().coefficients[1];
+        let beta = ridge_regression(&collinear, &y, 1e-8).unwrap();
+        assert_almost_eq!(beta[1] + beta[2], slope, 1e-8);
+    }
+
+    #[test]
+    fn test_ridge_errors() {
+        let (x, y) = example();
+        assert!(ridge_regression(&x, &y, -1.0).is_err());
+        assert!(ridge_regression(&x, &y, f64::NAN).is_err());
+        assert!(ridge_regression(&x, &y[1..], 1.0).is_err());
+        assert!(ridge_regression(&[], &[], 1.0).is_err());
+        let mut nan = y.clone();
+        nan[2] = f64::INFINITY;
+        assert!(ridge_regression(&x, &nan, 1.0).is_err());
+        // more coefficients than observations are fine with a penalty
+        assert!(ridge_regression(&x[..2], &y[..2], 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_logistic_reference() {
+        // the maximum likelihood estimate computed with mpmath
+        let x1 = [0.5, 1.2, 1.9, 2.4, 3.1, 3.3, 4.0, 4.8, 5.5, 6.1, 6.6, 7.2];
+        let x2 = [1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0];
+        let y = [false, false, true, false, false, true, false, true, true, false, true, true];
+        let x: Vec<Vec<f64>> = x1.iter().zip(&x2).map(|(&a, &b)| vec![a, b]).collect();
+        let beta = logistic_regression(&x, &y, 25, 1e-12).unwrap();
+        let expected = [-2.4842454377803102, 0.54108733373122574, 0.70781289492014966];
+        for (b, e) in beta.iter().zip(&expected) {
+            assert_almost_eq!(*b, *e, 1e-9);
+        }
+        // the score vanishes at the maximum
+        for j in 0..3 {
+            let score: f64 = x.iter().zip(&y).map(|(row, &yi)| {
+                let eta = beta[0] + beta[1] * row[0] + beta[2] * row[1];
+                let xj = if j == 0 { 1.0 } else { row[j - 1] };
+                xj * (if yi { 1.0 } else { 0.0 } - logistic(eta))
+            }).sum();
+            assert_almost_eq!(score, 0.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_logistic_separable() {
+        // the classes are separated by the line x1 + x2 = 1
+        let mut rng = SplitMix64::new(304);
+        let x: Vec<Vec<f64>> = (0..60).map(|_| vec![rng.gen::<f64>() * 2.0 - 0.5, rng.gen::<f64>() * 2.0 - 0.5]).collect();
+        let y: Vec<bool> = x.iter().map(|r| r[0] + r[1] > 1.0).collect();
+        let beta = logistic_regression(&x, &y, 25, 1e-8).unwrap();
+        assert!(beta.iter().all(|b| b.is_finite()));
+        for (row, &yi) in x.iter().zip(&y) {
+            let eta = beta[0] + beta[1] * row[0] + beta[2] * row[1];
+            assert_eq!(eta > 0.0, yi, "{:?} {:?}", row, beta);
+        }
+        // the coefficients point along the normal of the separating line
+        assert!(beta[1] > 0.0 && beta[2] > 0.0);
+        assert_almost_eq!(beta[1] / beta[2], 1.0, 0.5);
+        // a single iteration already moves towards the separation
+        let one = logistic_regression(&x, &y, 1, 1e-8).unwrap();
+        assert!(one[1] > 0.0 && one[2] > 0.0);
+    }
+
+    #[test]
+    fn test_logistic_errors() {
+        let x = vec![vec![1.0], vec![2.0], vec![3.0]];
+        let y = [false, true, true];
+        assert!(logistic_regression(&x, &y, 0, 1e-8).is_err());
+        assert!(logistic_regression(&x, &y, 25, 0.0).is_err());
+        assert!(logistic_regression(&x, &y, 25, f64::NAN).is_err());
+        assert!(logistic_regression(&x, &y[1..], 25, 1e-8).is_err());
+        assert!(logistic_regression(&[vec![1.0], vec![f64::NAN], vec![3.0]], &y, 25, 1e-8).is_err());
+    }
+
+    #[test]
+    fn test_poisson_reference() {
+        // reference values computed with mpmath
+        let (x, _) = example();
+        let y = [2, 3, 3, 6, 8, 7, 13, 20];
+        let beta = poisson_regression(&x, &y, 25, 1e-12).unwrap();
+        let expected = [0.37079337778444689, 0.24258850419747477, 0.069092003283195807];
+        for (b, e) in beta.iter().zip(&expected) {
+            assert_almost_eq!(*b, *e, 1e-9);
+        }
+        // the score vanishes at the maximum
+        for j in 0..3 {
+            let score: f64 = x.iter().zip(&y).map(|(row, &yi)| {
+                let mu = (beta[0] + beta[1] * row[0] + beta[2] * row[1]).exp();
+                let xj = if j == 0 { 1.0 } else { row[j - 1] };
+                xj * (yi as f64 - mu)
+            }).sum();
+            assert_almost_eq!(score, 0.0, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_poisson_recovers_coefficients() {
+        use crate::distribution::Poisson;
+        use rand::distributions::Distribution;
+        let mut rng = SplitMix64::new(305);
+        let coefficients = [0.5, 1.2, -0.7];
+        let x: Vec<Vec<f64>> = (0..5000).map(|_| vec![rng.gen::<f64>(), rng.gen::<f64>() * 2.0 - 1.0]).collect();
+        let y: Vec<u64> = x.iter().map(|row| {
+            let mu = (coefficients[0] + coefficients[1] * row[0] + coefficients[2] * row[1]).exp();
+            Poisson::new(mu).unwrap().sample(&mut rng) as u64
+        }).collect();
+        let beta = poisson_regression(&x, &y, 25, 1e-10).unwrap();
+        for (b, c) in beta.iter().zip(&coefficients) {
+            assert!((b - c).abs() < 0.06, "{:?}", beta);
+        }
+    }
+
+    #[test]
+    fn test_poisson_single_group() {
+        // a single predictor taking two values fits the means of the groups
+        let x: Vec<Vec<f64>> = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0].iter().map(|&v| vec![v]).collect();
+        let y = [1, 2, 3, 4, 6, 8];
+        let beta = poisson_regression(&x, &y, 25, 1e-12).unwrap();
+        assert_almost_eq!(beta[0], 2f64.ln(), 1e-12);
+        assert_almost_eq!(beta[1], 3f64.ln(), 1e-12);
+        // a group of zero counts drives its fitted mean towards zero
+        let beta = poisson_regression(&x, &[0, 0, 0, 4, 6, 8], 25, 1e-8).unwrap();
+        assert!(beta.iter().all(|b| b.is_finite()));
+        assert!(beta[0] < -5.0);
+        assert_almost_eq!(beta[0] + beta[1], 6f64.ln(), 1e-8);
+    }
+
+    #[test]
+    fn test_poisson_errors() {
+        let (x, _) = example();
+        let y = [1; 8];
+        assert!(poisson_regression(&x, &y, 0, 1e-8).is_err());
+        assert!(poisson_regression(&x, &y, 25, 0.0).is_err());
+        assert!(poisson_regression(&x, &y, 25, f64::NAN).is_err());
+        assert!(poisson_regression(&x, &y[..7], 25, 1e-8).is_err());
+        assert!(poisson_regression(&[], &[], 25, 1e-8).is_err());
+    }
+}
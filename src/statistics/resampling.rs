@@ -0,0 +1,328 @@
+//! Provides resampling estimates of the bias and uncertainty of arbitrary
+//! statistics
+
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::statistics::{Data, OrderStatistics};
+use rand::Rng;
+use std::f64;
+
+/// Returns the statistic of every leave-one-out sample of `data`, the `i`-th
+/// value leaving out `data[i]`
+fn leave_one_out<F: Fn(&[f64]) -> f64>(data: &[f64], statistic: &F) -> Vec<f64> {
+    // the sample without data[i] is kept in place by restoring data[i - 1]
+    // over data[i] after each evaluation
+    let mut sample = data[1..].to_vec();
+    let mut estimates = Vec::with_capacity(data.len());
+    for i in 0..data.len() {
+        estimates.push(statistic(&sample));
+        if i + 1 < data.len() {
+            sample[i] = data[i];
+        }
+    }
+    estimates
+}
+
+/// Computes the jackknife estimates of the bias and standard error of
+/// `statistic` on `data` by recomputing it on every leave-one-out sample,
+/// returned as `(bias, standard_error)`
+///
+/// # Formula
+///
+/// ```text
+/// bias = (n - 1) (θ̄ - θ)
+/// se = sqrt((n - 1) / n Σ (θ_i - θ̄)^2)
+/// ```
+///
+/// where `θ` is the statistic on `data`, `θ_i` the statistic without the
+/// `i`-th value and `θ̄` the mean of the `θ_i`
+///
+/// # Remarks
+///
+/// The statistic is evaluated `n + 1` times, always on a slice that it may
+/// not assume to be sorted. Returns `NaN` for both estimates if `data` has
+/// fewer than two values.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::resampling::jackknife;
+/// use statrs::statistics::Statistics;
+///
+/// let data = [1.0, 2.0, 4.0, 7.0];
+/// let (bias, se) = jackknife(&data, |x| x.mean());
+/// assert!(bias.abs() < 1e-14);
+/// assert!((se - 1.3228756555322954).abs() < 1e-14);
+/// ```
+pub fn jackknife<F: Fn(&[f64]) -> f64>(data: &[f64], statistic: F) -> (f64, f64) {
+    let n = data.len();
+    if n < 2 {
+        return (f64::NAN, f64::NAN);
+    }
+    let full = statistic(data);
+    let estimates = leave_one_out(data, &statistic);
+    let n = n as f64;
+    let mean = estimates.iter().sum::<f64>() / n;
+    let squares: f64 = estimates.iter().map(|t| (t - mean) * (t - mean)).sum();
+    ((n - 1.0) * (mean - full), ((n - 1.0) / n * squares).sqrt())
+}
+
+/// Returns the statistic of `n_resamples` samples drawn with replacement
+/// from `data`
+fn bootstrap_replicates<F, R>(
+    data: &[f64],
+    statistic: &F,
+    n_resamples: usize,
+    rng: &mut R,
+) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    let mut sample = vec![0.0; data.len()];
+    (0..n_resamples)
+        .map(|_| {
+            for x in sample.iter_mut() {
+                *x = data[rng.gen_range(0..data.len())];
+            }
+            statistic(&sample)
+        })
+        .collect()
+}
+
+/// Returns whether the arguments of a bootstrap interval are usable
+fn valid_bootstrap(data: &[f64], level: f64, n_resamples: usize) -> bool {
+    data.len() >= 2 && level > 0.0 && level < 1.0 && n_resamples > 0
+}
+
+/// Computes the percentile bootstrap confidence interval of `statistic` on
+/// `data` at the confidence `level`, from `n_resamples` samples drawn with
+/// replacement
+///
+/// # Remarks
+///
+/// The bounds are the `(1 - level) / 2` and `(1 + level) / 2` quantiles of
+/// the bootstrap replicates, as computed by `OrderStatistics::quantile`.
+/// The interval is transformation-respecting but has coverage errors of
+/// order `1 / sqrt(n)` for biased or skewed statistics, for which
+/// `bca_bootstrap_ci` is more accurate. Returns `NaN` for both bounds if
+/// `data` has fewer than two values, `level` is not in `(0, 1)` or
+/// `n_resamples` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::statistics::resampling::percentile_bootstrap_ci;
+/// use statrs::statistics::Statistics;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let data = [4.1, 5.3, 3.8, 6.0, 5.5, 4.7, 5.1, 4.4, 5.9, 4.9];
+/// let (lower, upper) = percentile_bootstrap_ci(&data, |x| x.mean(), 0.95, 2000, &mut rng);
+/// assert!(lower < 4.97 && 4.97 < upper);
+/// ```
+pub fn percentile_bootstrap_ci<F, R>(
+    data: &[f64],
+    statistic: F,
+    level: f64,
+    n_resamples: usize,
+    rng: &mut R,
+) -> (f64, f64)
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    if !valid_bootstrap(data, level, n_resamples) {
+        return (f64::NAN, f64::NAN);
+    }
+    let mut replicates = Data::new(bootstrap_replicates(data, &statistic, n_resamples, rng));
+    let alpha = (1.0 - level) / 2.0;
+    (replicates.quantile(alpha), replicates.quantile(1.0 - alpha))
+}
+
+/// Computes the bias-corrected and accelerated (BCa) bootstrap confidence
+/// interval of Efron (1987) for `statistic` on `data` at the confidence
+/// `level`, from `n_resamples` samples drawn with replacement
+///
+/// # Formula
+///
+/// The bounds are the quantiles of the bootstrap replicates at
+///
+/// ```text
+/// Φ(z0 + (z0 + z) / (1 - a (z0 + z)))
+/// ```
+///
+/// for `z` the `(1 - level) / 2` and `(1 + level) / 2` quantiles of the
+/// standard normal distribution. The bias correction `z0 = Φ⁻¹(p)` comes
+/// from the proportion `p` of replicates below the statistic on `data`,
+/// ties counting as `1/2`, and the acceleration from the jackknife values
+/// `θ_i` as
+///
+/// ```text
+/// a = Σ (θ̄ - θ_i)^3 / (6 (Σ (θ̄ - θ_i)^2)^(3/2))
+/// ```
+///
+/// # Remarks
+///
+/// The coverage error is of order `1 / n` against `1 / sqrt(n)` for the
+/// percentile interval, at the cost of `n` extra evaluations of the
+/// statistic. With `z0 = 0` and `a = 0` it reduces to the percentile
+/// interval. Returns `NaN` for both bounds if `data` has fewer than two
+/// values, `level` is not in `(0, 1)` or `n_resamples` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::statistics::resampling::bca_bootstrap_ci;
+/// use statrs::statistics::Statistics;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let data = [0.2, 1.9, 0.4, 0.1, 3.2, 0.7, 0.3, 5.8, 1.1, 0.6];
+/// let (lower, upper) = bca_bootstrap_ci(&data, |x| x.variance(), 0.9, 2000, &mut rng);
+/// assert!(lower < data.variance() && data.variance() < upper);
+/// ```
+pub fn bca_bootstrap_ci<F, R>(
+    data: &[f64],
+    statistic: F,
+    level: f64,
+    n_resamples: usize,
+    rng: &mut R,
+) -> (f64, f64)
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    if !valid_bootstrap(data, level, n_resamples) {
+        return (f64::NAN, f64::NAN);
+    }
+    let full = statistic(data);
+    let replicates = bootstrap_replicates(data, &statistic, n_resamples, rng);
+    let below = replicates.iter().filter(|&&t| t < full).count() as f64;
+    let ties = replicates.iter().filter(|&&t| t == full).count() as f64;
+    let normal = Normal::standard();
+    // half a replicate keeps z0 finite when all replicates fall on one side
+    let n = n_resamples as f64;
+    let z0 = normal.inverse_cdf(((below + 0.5 * ties) / n).clamp(0.5 / n, 1.0 - 0.5 / n));
+
+    let estimates = leave_one_out(data, &statistic);
+    let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+    let (squares, cubes) = estimates.iter().fold((0.0, 0.0), |(s, c), t| {
+        let d = mean - t;
+        (s + d * d, c + d * d * d)
+    });
+    let a = if squares > 0.0 {
+        cubes / (6.0 * squares.powf(1.5))
+    } else {
+        0.0
+    };
+
+    let adjust = |p: f64| {
+        let z = z0 + normal.inverse_cdf(p);
+        if a * z >= 1.0 {
+            // the limit of the formula as a z approaches 1
+            if z > 0.0 {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            normal.cdf(z0 + z / (1.0 - a * z))
+        }
+    };
+    let alpha = (1.0 - level) / 2.0;
+    let mut replicates = Data::new(replicates);
+    (
+        replicates.quantile(adjust(alpha)),
+        replicates.quantile(adjust(1.0 - alpha)),
+    )
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Exp;
+    use crate::statistics::Statistics;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_jackknife_mean() {
+        let data = [2.3, -1.1, 4.7, 0.2, 3.3, 9.8, -4.0, 1.5, 2.2];
+        let (bias, se) = jackknife(&data, |x| x.mean());
+        assert!(bias.abs() < 1e-14);
+        let analytic = data.std_dev() / (data.len() as f64).sqrt();
+        assert_almost_eq!(se, analytic, 1e-14);
+    }
+
+    #[test]
+    fn test_jackknife_leaves_each_value_out() {
+        // the maximum changes only without the largest value
+        let data = [3.0, 8.0, 1.0, 5.0];
+        let (bias, se) = jackknife(&data, |x| x.max());
+        assert_almost_eq!(bias, 3.0 / 4.0 * (5.0 - 8.0), 1e-15);
+        assert_almost_eq!(se, (3.0f64 / 4.0 * (9.0 * 3.0 / 16.0 + 81.0 / 16.0)).sqrt(), 1e-15);
+    }
+
+    #[test]
+    fn test_jackknife_biased_variance() {
+        // the jackknife removes the bias of the plug-in variance exactly
+        let data = [1.0, 4.0, 2.0, 8.0, 5.0, 7.0];
+        let (bias, _) = jackknife(&data, |x| x.population_variance());
+        assert_almost_eq!(bias, data.population_variance() - data.variance(), 1e-13);
+    }
+
+    #[test]
+    fn test_jackknife_too_short() {
+        let (bias, se) = jackknife(&[1.0], |x| x.mean());
+        assert!(bias.is_nan() && se.is_nan());
+        let (bias, se) = jackknife(&[], |x| x.mean());
+        assert!(bias.is_nan() && se.is_nan());
+    }
+
+    #[test]
+    fn test_bca_coverage() {
+        // the variance of exponential samples is biased and strongly skewed
+        let exp = Exp::new(1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(278);
+        let trials = 200;
+        let (mut percentile, mut bca) = ([0; 2], [0; 2]);
+        for _ in 0..trials {
+            let data: Vec<f64> = (0..25).map(|_| exp.sample(&mut rng)).collect();
+            let (lower, upper) = percentile_bootstrap_ci(&data, |x| x.variance(), 0.9, 500, &mut rng);
+            percentile[0] += usize::from(1.0 < lower);
+            percentile[1] += usize::from(upper < 1.0);
+            let (lower, upper) = bca_bootstrap_ci(&data, |x| x.variance(), 0.9, 500, &mut rng);
+            bca[0] += usize::from(1.0 < lower);
+            bca[1] += usize::from(upper < 1.0);
+        }
+        // both intervals undercover, the percentile one mostly by missing
+        // the true value above, which the bias correction and acceleration
+        // largely shift back
+        assert!(percentile[1] > 50 && percentile[0] < 5);
+        assert!(bca[1] + 10 < percentile[1]);
+        assert!(bca[0] + bca[1] < percentile[0] + percentile[1]);
+    }
+
+    #[test]
+    fn test_bootstrap_degenerate() {
+        let mut rng = StdRng::seed_from_u64(2780);
+        assert_eq!(bca_bootstrap_ci(&[2.0; 6], |x| x.mean(), 0.95, 100, &mut rng), (2.0, 2.0));
+        assert_eq!(percentile_bootstrap_ci(&[2.0; 6], |x| x.mean(), 0.95, 100, &mut rng), (2.0, 2.0));
+        // every replicate of the maximum is at most the observed maximum
+        let (lower, upper) = bca_bootstrap_ci(&[1.0, 2.0, 3.0, 4.0, 5.0], |x| x.max(), 0.9, 200, &mut rng);
+        assert!(lower <= upper && upper <= 5.0);
+        let invalid = [
+            bca_bootstrap_ci(&[1.0], |x| x.mean(), 0.95, 100, &mut rng),
+            bca_bootstrap_ci(&[1.0, 2.0], |x| x.mean(), 1.0, 100, &mut rng),
+            bca_bootstrap_ci(&[1.0, 2.0], |x| x.mean(), 0.95, 0, &mut rng),
+            percentile_bootstrap_ci(&[1.0, 2.0], |x| x.mean(), 0.0, 100, &mut rng),
+        ];
+        for (lower, upper) in invalid {
+            assert!(lower.is_nan() && upper.is_nan());
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! Provides M-estimators of location and scale, which bound the influence
+//! of every observation and so resist gross outliers
+//!
+//! The estimators are computed by iteratively reweighted least squares from
+//! the median, with the scale fixed at the median absolute deviation
+//! `MAD = 1.4826 median(|x_i - median(x)|)`, consistent for the standard
+//! deviation of normal data, except in `huber_scale` which estimates it
+//! jointly with the location.
+
+use crate::distribution::{Continuous, ContinuousCDF, Normal};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The default relative tolerance of the iterations, as in R's
+/// `MASS::huber`
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// The default maximum number of iterations
+pub const DEFAULT_MAX_ITERATIONS: usize = 100;
+
+/// The factor making the median absolute deviation consistent for the
+/// standard deviation of normal data
+const MAD_CONSISTENCY: f64 = 1.4826;
+
+/// The result of an M-estimator of location
+#[derive(Clone, PartialEq, Debug)]
+pub struct MEstimate {
+    /// The estimate of location
+    pub estimate: f64,
+    /// The scale the residuals are standardized by, the median absolute
+    /// deviation or, for `huber_scale`, the jointly estimated scale
+    pub scale: f64,
+    /// The final weights of the observations, in the order of the data,
+    /// with `1` for observations treated as by the mean and less for
+    /// downweighted ones
+    pub weights: Vec<f64>,
+    /// The number of iterations performed
+    pub iterations: usize,
+}
+
+/// Computes Huber's M-estimator of location of `data` with the tuning
+/// constant `k`, with the default tolerance and maximum number of
+/// iterations, see `huber_mean_with`
+///
+/// # Errors
+///
+/// As `huber_mean_with`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::huber_mean;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let huber = huber_mean(&data, 1.5).unwrap();
+/// assert!(huber.estimate < 4.5);
+/// // the outlier is downweighted
+/// assert!(huber.weights[5] < 0.1);
+/// ```
+pub fn huber_mean(data: &[f64], k: f64) -> Result<MEstimate> {
+    huber_mean_with(data, k, DEFAULT_TOLERANCE, DEFAULT_MAX_ITERATIONS)
+}
+
+/// Computes Huber's M-estimator of location of `data` with the tuning
+/// constant `k`, iterating until the estimate changes by less than
+/// `tol` times the scale, for at most `max_iterations` iterations
+///
+/// # Formula
+///
+/// The estimate `μ` solves `Σ ψ((x_i - μ) / s) = 0` with the scale `s`
+/// fixed at the median absolute deviation and
+///
+/// ```text
+/// ψ(r) = max(-k, min(k, r))
+/// ```
+///
+/// through the weights `w_i = min(1, k s / |x_i - μ|)` and the updates
+/// `μ = Σ w_i x_i / Σ w_i`.
+///
+/// # Remarks
+///
+/// The estimate agrees with R's `MASS::huber`, which iterates the mean of
+/// the data winsorized at `μ ± k s` to the same fixed point. The usual
+/// `k = 1.345` has 95% efficiency at the normal distribution and `k = 1.5`
+/// is the default of `MASS::huber`. A single observation is its own
+/// estimate; when more than half of the data are equal, the median
+/// absolute deviation is zero and the estimate is the median, with weight
+/// one for the observations equal to it and zero for the others, without
+/// iterating.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`, if `k` is not
+/// positive, if `tol` is not positive or if the iterations do not converge
+/// within `max_iterations`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::huber_mean_with;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let huber = huber_mean_with(&data, 1.345, 1e-10, 200).unwrap();
+/// assert!(huber.estimate > 3.0 && huber.estimate < 4.5);
+/// assert!(huber.iterations <= 200);
+/// ```
+pub fn huber_mean_with(data: &[f64], k: f64, tol: f64, max_iterations: usize) -> Result<MEstimate> {
+    check_tuning(k, "k")?;
+    irls(data, tol, max_iterations, |r| {
+        if r.abs() <= k {
+            1.0
+        } else {
+            k / r.abs()
+        }
+    })
+}
+
+/// Computes Tukey's biweight M-estimator of location of `data` with the
+/// tuning constant `c`, with the default tolerance and maximum number of
+/// iterations, see `tukey_biweight_mean_with`
+///
+/// # Errors
+///
+/// As `tukey_biweight_mean_with`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::tukey_biweight_mean;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let biweight = tukey_biweight_mean(&data, 4.685).unwrap();
+/// // the outlier is rejected outright
+/// assert_eq!(biweight.weights[5], 0.0);
+/// ```
+pub fn tukey_biweight_mean(data: &[f64], c: f64) -> Result<MEstimate> {
+    tukey_biweight_mean_with(data, c, DEFAULT_TOLERANCE, DEFAULT_MAX_ITERATIONS)
+}
+
+/// Computes Tukey's biweight M-estimator of location of `data` with the
+/// tuning constant `c`, iterating until the estimate changes by less than
+/// `tol` times the scale, for at most `max_iterations` iterations
+///
+/// # Formula
+///
+/// The estimate `μ` solves `Σ ψ((x_i - μ) / s) = 0` with the scale `s`
+/// fixed at the median absolute deviation and the redescending
+///
+/// ```text
+/// ψ(r) = r (1 - (r / c)^2)^2  if |r| <= c,  0 otherwise
+/// ```
+///
+/// as `MASS::psi.bisquare`, through the weights `w_i = ψ(r_i) / r_i` and
+/// the updates `μ = Σ w_i x_i / Σ w_i`.
+///
+/// # Remarks
+///
+/// Observations further than `c s` from the estimate get weight zero, so
+/// gross outliers have no influence at all. The usual `c = 4.685` has 95%
+/// efficiency at the normal distribution. Since `ψ` redescends, the
+/// estimating equation may have several roots, and the iterations find
+/// the one nearest the median. Degenerate data are handled as by
+/// `huber_mean_with`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`, if `c` is not
+/// positive, if `tol` is not positive or if the iterations do not converge
+/// within `max_iterations`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::tukey_biweight_mean_with;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let biweight = tukey_biweight_mean_with(&data, 4.685, 1e-10, 200).unwrap();
+/// assert!((biweight.estimate - 3.5).abs() < 0.5);
+/// ```
+pub fn tukey_biweight_mean_with(
+    data: &[f64],
+    c: f64,
+    tol: f64,
+    max_iterations: usize,
+) -> Result<MEstimate> {
+    check_tuning(c, "c")?;
+    irls(data, tol, max_iterations, |r| {
+        if r.abs() <= c {
+            let u = r / c;
+            (1.0 - u * u) * (1.0 - u * u)
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Computes Huber's proposal 2 estimates of location and scale of `data`
+/// with the tuning constant `k`, with the default tolerance and maximum
+/// number of iterations, see `huber_scale_with`
+///
+/// # Errors
+///
+/// As `huber_scale_with`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::huber_scale;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let proposal2 = huber_scale(&data, 1.5).unwrap();
+/// assert!(proposal2.scale < 2.0);
+/// ```
+pub fn huber_scale(data: &[f64], k: f64) -> Result<MEstimate> {
+    huber_scale_with(data, k, DEFAULT_TOLERANCE, DEFAULT_MAX_ITERATIONS)
+}
+
+/// Computes Huber's proposal 2 estimates of location and scale of `data`
+/// with the tuning constant `k`, iterating until both change by less than
+/// `tol` times the scale, for at most `max_iterations` iterations
+///
+/// # Formula
+///
+/// The location `μ` and the scale `s` jointly solve
+///
+/// ```text
+/// Σ ψ((x_i - μ) / s) = 0
+/// Σ ψ((x_i - μ) / s)^2 = (n - 1) β
+/// ```
+///
+/// where `ψ` is Huber's function of `huber_mean_with` and
+/// `β = E[ψ(Z)^2] = 2Φ(k) - 1 + k^2 (2 - 2Φ(k)) - 2k φ(k)` for a standard
+/// normal `Z`, which makes `s` consistent for the standard deviation of
+/// normal data.
+///
+/// # Remarks
+///
+/// The iterations are those of R's `MASS::hubers`, starting from the
+/// median and the median absolute deviation and updating the location to
+/// the mean of the data winsorized at `μ ± k s` and the scale to their
+/// standard deviation divided by `sqrt(β)`. The weights are those of
+/// Huber's function at the final estimates. Degenerate data are handled as
+/// by `huber_mean_with`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`, if `k` is not
+/// positive, if `tol` is not positive or if the iterations do not converge
+/// within `max_iterations`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::robust::huber_scale_with;
+///
+/// let data = [2.0, 3.0, 3.5, 4.0, 4.5, 100.0];
+/// let proposal2 = huber_scale_with(&data, 1.5, 1e-10, 500).unwrap();
+/// assert!(proposal2.estimate < 4.5);
+/// ```
+pub fn huber_scale_with(
+    data: &[f64],
+    k: f64,
+    tol: f64,
+    max_iterations: usize,
+) -> Result<MEstimate> {
+    check_tuning(k, "k")?;
+    let (mut location, mut scale) = match start(data, tol)? {
+        Ok(start) => start,
+        Err(degenerate) => return Ok(degenerate),
+    };
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let theta = 2.0 * normal.cdf(k) - 1.0;
+    let beta = theta + k * k * (1.0 - theta) - 2.0 * k * normal.pdf(k);
+    let n = data.len() as f64;
+    for iteration in 1..=max_iterations {
+        let clipped: Vec<f64> = data
+            .iter()
+            .map(|x| x.clamp(location - k * scale, location + k * scale))
+            .collect();
+        let next_location = clipped.iter().sum::<f64>() / n;
+        let squares: f64 = clipped
+            .iter()
+            .map(|x| (x - next_location) * (x - next_location))
+            .sum();
+        let next_scale = (squares / (n - 1.0) / beta).sqrt();
+        let converged = (next_location - location).abs() < tol * scale
+            && (next_scale - scale).abs() < tol * scale;
+        location = next_location;
+        scale = next_scale;
+        if converged {
+            let weights = data
+                .iter()
+                .map(|x| {
+                    let r = (x - location).abs();
+                    if r <= k * scale {
+                        1.0
+                    } else {
+                        k * scale / r
+                    }
+                })
+                .collect();
+            return Ok(MEstimate {
+                estimate: location,
+                scale,
+                weights,
+                iterations: iteration,
+            });
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Returns an error unless the tuning constant `value` is positive
+fn check_tuning(value: f64, name: &'static str) -> Result<()> {
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(StatsError::ArgMustBePositive(name))
+    }
+}
+
+/// Returns the median of `data`, which must not be empty or contain `NaN`
+fn median(data: &[f64]) -> f64 {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    if n & 1 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Checks the data and the tolerance and returns the median and the
+/// median absolute deviation as the starting values, or the estimate of
+/// degenerate data whose median absolute deviation is zero
+fn start(data: &[f64], tol: f64) -> Result<std::result::Result<(f64, f64), MEstimate>> {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    if tol.is_nan() || tol <= 0.0 {
+        return Err(StatsError::ArgMustBePositive("tol"));
+    }
+    let location = median(data);
+    let deviations: Vec<f64> = data.iter().map(|x| (x - location).abs()).collect();
+    let scale = MAD_CONSISTENCY * median(&deviations);
+    if scale > 0.0 {
+        return Ok(Ok((location, scale)));
+    }
+    let weights = data
+        .iter()
+        .map(|&x| if x == location { 1.0 } else { 0.0 })
+        .collect();
+    Ok(Err(MEstimate {
+        estimate: location,
+        scale: 0.0,
+        weights,
+        iterations: 0,
+    }))
+}
+
+/// Iterates the weighted means of `data` with the weights `weight(r)` of
+/// the standardized residuals `r`, from the median with the median absolute
+/// deviation as the scale
+fn irls<W: Fn(f64) -> f64>(
+    data: &[f64],
+    tol: f64,
+    max_iterations: usize,
+    weight: W,
+) -> Result<MEstimate> {
+    let (mut location, scale) = match start(data, tol)? {
+        Ok(start) => start,
+        Err(degenerate) => return Ok(degenerate),
+    };
+    for iteration in 1..=max_iterations {
+        let weights: Vec<f64> = data
+            .iter()
+            .map(|x| weight((x - location) / scale))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(StatsError::SpecialCase("every observation has weight zero"));
+        }
+        let next = weights.iter().zip(data).map(|(w, x)| w * x).sum::<f64>() / total;
+        let converged = (next - location).abs() < tol * scale;
+        location = next;
+        if converged {
+            let weights = data
+                .iter()
+                .map(|x| weight((x - location) / scale))
+                .collect();
+            return Ok(MEstimate {
+                estimate: location,
+                scale,
+                weights,
+                iterations: iteration,
+            });
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the chem data of R's `MASS` package, with a gross outlier at 28.95
+    const CHEM: [f64; 24] = [
+        2.90, 3.10, 3.40, 3.40, 3.70, 3.70, 2.80, 2.50, 2.40, 2.40, 2.70, 2.20,
+        5.28, 3.37, 3.03, 3.03, 28.95, 3.77, 3.40, 2.20, 3.50, 3.60, 3.70, 3.70,
+    ];
+
+    #[test]
+    fn test_huber_mean() {
+        // MASS::huber(chem) gives mu = 3.206724, s = 0.526323
+        let huber = huber_mean(&CHEM, 1.5).unwrap();
+        assert_almost_eq!(huber.estimate, 3.206724, 1e-5);
+        assert_almost_eq!(huber.scale, 0.526323, 1e-6);
+        assert_eq!(huber.weights.len(), CHEM.len());
+        assert!(huber.weights[16] < 0.04);
+        assert_eq!(huber.weights[0], 1.0);
+        assert!(huber.iterations > 1);
+        // the estimating equation holds at the estimate
+        let psi: f64 = CHEM
+            .iter()
+            .map(|x| ((x - huber.estimate) / huber.scale).clamp(-1.5, 1.5))
+            .sum();
+        assert!(psi.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_huber_scale() {
+        // MASS::hubers(chem) gives mu = 3.205498, s = 0.673652
+        let proposal2 = huber_scale(&CHEM, 1.5).unwrap();
+        assert_almost_eq!(proposal2.estimate, 3.205498, 1e-5);
+        assert_almost_eq!(proposal2.scale, 0.673652, 1e-5);
+        assert!(proposal2.weights[16] < 0.05);
+    }
+
+    #[test]
+    fn test_tukey_biweight_mean() {
+        // iterating MASS::psi.bisquare with c = 4.685 and the scale fixed at
+        // the median absolute deviation converges to 3.144294
+        let biweight = tukey_biweight_mean_with(&CHEM, 4.685, 1e-10, 100).unwrap();
+        assert_almost_eq!(biweight.estimate, 3.1442944634504455, 1e-9);
+        assert_eq!(biweight.weights[16], 0.0);
+        // 5.28 is about 4 scales from the estimate and nearly rejected
+        assert!(biweight.weights[12] < 0.1);
+        assert!(biweight.weights.iter().filter(|&&w| w > 0.0).all(|&w| w <= 1.0));
+    }
+
+    #[test]
+    fn test_single_outlier() {
+        let data: Vec<f64> = (0..50).map(|i| 10.0 + (i as f64 * 0.37).sin()).collect();
+        let mut contaminated = data.clone();
+        contaminated.push(1e6);
+        let clean = huber_mean(&data, 1.345).unwrap().estimate;
+        let dirty = huber_mean(&contaminated, 1.345).unwrap().estimate;
+        assert!((dirty - clean).abs() < 0.01 * clean);
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        assert!(mean(&contaminated) > 1000.0 * mean(&data));
+        let clean = tukey_biweight_mean(&data, 4.685).unwrap().estimate;
+        let dirty = tukey_biweight_mean(&contaminated, 4.685).unwrap().estimate;
+        assert!((dirty - clean).abs() < 0.01 * clean);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let single = huber_mean(&[4.0], 1.5).unwrap();
+        assert_eq!(single, MEstimate { estimate: 4.0, scale: 0.0, weights: vec![1.0], iterations: 0 });
+        let constant = tukey_biweight_mean(&[2.0, 2.0, 2.0, 7.0], 4.685).unwrap();
+        assert_eq!(constant.estimate, 2.0);
+        assert_eq!(constant.weights, vec![1.0, 1.0, 1.0, 0.0]);
+        assert_eq!(huber_scale(&[3.0; 5], 1.5).unwrap().estimate, 3.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(huber_mean(&[], 1.5), Err(StatsError::BadParams));
+        assert_eq!(huber_mean(&[1.0, f64::NAN], 1.5), Err(StatsError::BadParams));
+        assert_eq!(huber_mean(&CHEM, 0.0), Err(StatsError::ArgMustBePositive("k")));
+        assert_eq!(tukey_biweight_mean(&CHEM, -1.0), Err(StatsError::ArgMustBePositive("c")));
+        assert_eq!(huber_scale_with(&CHEM, 1.5, 0.0, 10), Err(StatsError::ArgMustBePositive("tol")));
+        assert_eq!(huber_mean_with(&CHEM, 1.5, 1e-12, 1), Err(StatsError::ComputationFailedToConverge));
+    }
+}
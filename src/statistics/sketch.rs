@@ -0,0 +1,531 @@
+//! Provides sketches of the frequencies of the items of a stream, for
+//! alphabets too large to count every distinct item exactly
+//!
+//! Items are hashed with the standard library's `DefaultHasher`, whose keys
+//! are fixed, so sketches built in the same program hash alike and can be
+//! merged. The hashes may change between releases of Rust, so sketches
+//! should not be persisted and merged across builds.
+
+use crate::{Result, StatsError};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::f64;
+use std::hash::{Hash, Hasher};
+
+/// Implements the [Count-Min
+/// sketch](https://en.wikipedia.org/wiki/Count%E2%80%93min_sketch) of
+/// Cormode and Muthukrishnan, a mergeable summary of a stream that
+/// estimates the number of occurrences of any item
+///
+/// # Remarks
+///
+/// The sketch has `depth` rows of `width` counters and every row hashes the
+/// items to one of its counters, which counts all the items hashed to it.
+/// The estimate of an item is the smallest of its counters, so it is never
+/// below the true count `f` and, after `N` items,
+///
+/// ```text
+/// P(estimate > f + ε N) <= δ,  ε = e / width,  δ = e^(-depth)
+/// ```
+///
+/// The rows use the double hashing `h_1 + i h_2` of the two halves of a
+/// single 64-bit hash, which keeps the guarantee in practice at the cost of
+/// one hash per item.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::sketch::CountMin;
+///
+/// let mut sketch = CountMin::new(1000, 5).unwrap();
+/// for word in "the cat and the dog and the bird".split(' ') {
+///     sketch.add(word);
+/// }
+/// assert_eq!(sketch.count(), 8);
+/// assert!(sketch.estimate("the") >= 3);
+/// assert!(sketch.estimate("the") <= 3 + 8);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct CountMin {
+    width: usize,
+    depth: usize,
+    counters: Vec<u64>,
+    count: u64,
+}
+
+impl CountMin {
+    /// Constructs an empty sketch with `depth` rows of `width` counters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `width` or `depth` is zero, or if the counters
+    /// cannot be allocated
+    pub fn new(width: usize, depth: usize) -> Result<CountMin> {
+        if width == 0 {
+            return Err(StatsError::ArgMustBePositive("width"));
+        }
+        if depth == 0 {
+            return Err(StatsError::ArgMustBePositive("depth"));
+        }
+        let too_large =
+            || StatsError::SpecialCase("the counters of the sketch cannot be allocated");
+        let cells = width.checked_mul(depth).ok_or_else(too_large)?;
+        let mut counters = Vec::new();
+        counters.try_reserve_exact(cells).map_err(|_| too_large())?;
+        counters.resize(cells, 0);
+        Ok(CountMin {
+            width,
+            depth,
+            counters,
+            count: 0,
+        })
+    }
+
+    /// Constructs an empty sketch whose estimates exceed the true counts
+    /// by more than `epsilon` times the number of items with probability
+    /// at most `delta`, with `width = ⌈e / ε⌉` and `depth = ⌈ln(1 / δ)⌉`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `epsilon` or `delta` is not in `(0, 1)`, or if
+    /// `epsilon` is so small that the counters cannot be allocated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::sketch::CountMin;
+    ///
+    /// let sketch = CountMin::with_error(0.001, 0.01).unwrap();
+    /// assert_eq!(sketch.width(), 2719);
+    /// assert_eq!(sketch.depth(), 5);
+    /// ```
+    pub fn with_error(epsilon: f64, delta: f64) -> Result<CountMin> {
+        if epsilon.is_nan() || epsilon <= 0.0 || epsilon >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("epsilon", 0.0, 1.0));
+        }
+        if delta.is_nan() || delta <= 0.0 || delta >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("delta", 0.0, 1.0));
+        }
+        let width = (f64::consts::E / epsilon).ceil();
+        if width >= usize::MAX as f64 {
+            return Err(StatsError::SpecialCase(
+                "the counters of the sketch cannot be allocated",
+            ));
+        }
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        CountMin::new(width as usize, depth)
+    }
+
+    /// Returns the number of counters of every row
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of items added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds an occurrence of `item`, in `O(depth)` time
+    pub fn add<T: Hash + ?Sized>(&mut self, item: &T) {
+        self.add_count(item, 1);
+    }
+
+    /// Adds `count` occurrences of `item`, in `O(depth)` time. The counters
+    /// and the number of items saturate at `u64::MAX`.
+    pub fn add_count<T: Hash + ?Sized>(&mut self, item: &T, count: u64) {
+        for cell in self.cells(item) {
+            self.counters[cell] = self.counters[cell].saturating_add(count);
+        }
+        self.count = self.count.saturating_add(count);
+    }
+
+    /// Estimates the number of occurrences of `item`, an upper bound on
+    /// the true count that is tight to within the guarantee of the sketch
+    pub fn estimate<T: Hash + ?Sized>(&self, item: &T) -> u64 {
+        self.cells(item)
+            .map(|cell| self.counters[cell])
+            .min()
+            .unwrap()
+    }
+
+    /// Adds the counts of `other`, so that the sketch summarizes the union
+    /// of both streams exactly as if it had seen every item, with counters
+    /// saturating at `u64::MAX`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sketches do not have the same width and
+    /// depth
+    pub fn merge(&mut self, other: &CountMin) -> Result<()> {
+        if self.width != other.width || self.depth != other.depth {
+            return Err(StatsError::SpecialCase(
+                "sketches must have the same width and depth",
+            ));
+        }
+        for (a, b) in self.counters.iter_mut().zip(&other.counters) {
+            *a = a.saturating_add(*b);
+        }
+        self.count = self.count.saturating_add(other.count);
+        Ok(())
+    }
+
+    /// Estimates the entropy, in nats, of the frequencies of the items, or
+    /// returns `f64::NAN` if there are none
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// Ĥ = max_rows -Σ_j (c_j / N) ln(c_j / N)
+    /// ```
+    ///
+    /// where `c_j` are the counters of a row and `N` is the number of items.
+    ///
+    /// # Remarks
+    ///
+    /// Every row groups the items into `width` buckets, and grouping never
+    /// increases the entropy, so the estimate never exceeds the entropy `H`
+    /// of the empirical frequencies, nor `ln(width)`. The information lost
+    /// by a row is `Σ p_x ln(1 + M_x / p_x)`, where `M_x` is the frequency of
+    /// the other items in the bucket of `x`, whose mean is at most
+    /// `1 / width` for random hashing, so by Jensen's inequality
+    ///
+    /// ```text
+    /// 0 <= H - E[Ĥ] <= Σ p_x ln(1 + 1 / (width p_x)) <= ln(1 + K / width)
+    /// ```
+    ///
+    /// for `K` distinct items. The estimate is thus a lower bound, which is
+    /// accurate when the width is large compared to the number of distinct
+    /// items carrying most of the frequency.
+    pub fn entropy_estimate(&self) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+        let n = self.count as f64;
+        self.counters
+            .chunks(self.width)
+            .map(|row| {
+                -row.iter()
+                    .filter(|&&c| c > 0)
+                    .map(|&c| {
+                        let p = c as f64 / n;
+                        p * p.ln()
+                    })
+                    .sum::<f64>()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the indices into the counters of the cells of `item`, one
+    /// per row
+    fn cells<T: Hash + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+        let (h1, h2) = (hash & 0xffff_ffff, (hash >> 32) | 1);
+        let width = self.width as u64;
+        (0..self.depth as u64)
+            .map(move |i| (i * width + h1.wrapping_add(i.wrapping_mul(h2)) % width) as usize)
+    }
+}
+
+/// An item monitored by `SpaceSaving`, with bounds on its number of
+/// occurrences
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HeavyHitter<T> {
+    /// The item
+    pub item: T,
+    /// The estimated number of occurrences, an upper bound on the true one
+    pub count: u64,
+    /// The largest possible overestimation, so that the true number of
+    /// occurrences is at least `count - error`
+    pub error: u64,
+}
+
+/// Implements the space-saving algorithm of Metwally, Agrawal and El
+/// Abbadi, which finds the most frequent items of a stream while
+/// monitoring at most `k` of them
+///
+/// # Remarks
+///
+/// While fewer than `k` items are monitored, a new item is monitored with
+/// its exact count. Afterwards it replaces the monitored item with the
+/// smallest count `c`, inheriting the count `c + 1` and the error `c`.
+/// After `N` items the smallest count is at most `N / k`, so every item
+/// occurring more than `N / k` times is monitored, and the count of a
+/// monitored item overestimates its true one by at most its error.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::sketch::SpaceSaving;
+///
+/// let mut top = SpaceSaving::new(2).unwrap();
+/// for letter in "abacabadabacaba".chars() {
+///     top.add(letter);
+/// }
+/// let hitters = top.top();
+/// assert_eq!(hitters[0].item, 'a');
+/// assert_eq!(hitters[0].count - hitters[0].error, 8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpaceSaving<T> {
+    capacity: usize,
+    entries: Vec<HeavyHitter<T>>,
+    index: HashMap<T, usize>,
+    /// The counts of the entries with their positions, ordered by count
+    order: BTreeSet<(u64, usize)>,
+    count: u64,
+}
+
+impl<T: Hash + Eq + Clone> SpaceSaving<T> {
+    /// Constructs an empty summary monitoring at most `k` items
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k` is zero
+    pub fn new(k: usize) -> Result<SpaceSaving<T>> {
+        if k == 0 {
+            return Err(StatsError::ArgMustBePositive("k"));
+        }
+        Ok(SpaceSaving {
+            capacity: k,
+            entries: Vec::with_capacity(k),
+            index: HashMap::with_capacity(k),
+            order: BTreeSet::new(),
+            count: 0,
+        })
+    }
+
+    /// Returns the largest number `k` of monitored items
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of items added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds an occurrence of `item`, in `O(log k)` time
+    pub fn add(&mut self, item: T) {
+        self.count += 1;
+        let slot = match self.index.get(&item) {
+            Some(&slot) => slot,
+            None if self.entries.len() < self.capacity => {
+                self.index.insert(item.clone(), self.entries.len());
+                self.entries.push(HeavyHitter {
+                    item,
+                    count: 0,
+                    error: 0,
+                });
+                self.order.insert((0, self.entries.len() - 1));
+                self.entries.len() - 1
+            }
+            None => {
+                let &(smallest, slot) = self.order.iter().next().unwrap();
+                self.index.remove(&self.entries[slot].item);
+                self.index.insert(item.clone(), slot);
+                self.entries[slot] = HeavyHitter {
+                    item,
+                    count: smallest,
+                    error: smallest,
+                };
+                slot
+            }
+        };
+        let entry = &mut self.entries[slot];
+        self.order.remove(&(entry.count, slot));
+        entry.count += 1;
+        self.order.insert((entry.count, slot));
+    }
+
+    /// Returns the monitored entry of `item`, or `None` if it is not
+    /// monitored, in which case it occurred at most `N / k` times
+    pub fn get(&self, item: &T) -> Option<&HeavyHitter<T>> {
+        self.index.get(item).map(|&slot| &self.entries[slot])
+    }
+
+    /// Returns the monitored items by decreasing count
+    pub fn top(&self) -> Vec<HeavyHitter<T>> {
+        self.order
+            .iter()
+            .rev()
+            .map(|&(_, slot)| self.entries[slot].clone())
+            .collect()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_util::SplitMix64;
+    use rand::Rng;
+
+    /// The cumulative probabilities of a Zipf distribution with exponent
+    /// `s` on the ranks `0` to `n - 1`
+    fn zipf_cdf(n: usize, s: f64) -> Vec<f64> {
+        let weights: Vec<f64> = (1..=n).map(|k| (k as f64).powf(-s)).collect();
+        let total: f64 = weights.iter().sum();
+        weights
+            .iter()
+            .scan(0.0, |acc, w| {
+                *acc += w / total;
+                Some(*acc)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_create() {
+        assert!(CountMin::new(0, 3).is_err());
+        assert!(CountMin::new(3, 0).is_err());
+        assert!(CountMin::with_error(0.0, 0.1).is_err());
+        assert!(CountMin::with_error(0.1, 1.0).is_err());
+        assert!(CountMin::with_error(f64::NAN, 0.1).is_err());
+        // counters beyond the address space are rejected instead of aborting
+        assert!(CountMin::with_error(1e-300, 0.01).is_err());
+        assert!(CountMin::new(usize::MAX, 2).is_err());
+        assert!(CountMin::new(usize::MAX / 4, 1).is_err());
+        let sketch = CountMin::with_error(0.01, 0.5).unwrap();
+        assert_eq!((sketch.width(), sketch.depth()), (272, 1));
+        assert!(sketch.entropy_estimate().is_nan());
+        assert_eq!(sketch.estimate(&17), 0);
+        assert!(SpaceSaving::<u8>::new(0).is_err());
+    }
+
+    #[test]
+    fn test_zipf_stream() {
+        // 10^7 items from a Zipf distribution on 10^5 ranks
+        let n = 10_000_000;
+        let cdf = zipf_cdf(100_000, 1.1);
+        let (epsilon, delta) = (1e-4, 0.01);
+        let mut sketch = CountMin::with_error(epsilon, delta).unwrap();
+        let mut top = SpaceSaving::new(100).unwrap();
+        let mut counts = vec![0u64; cdf.len()];
+        let mut rng = SplitMix64::new(2992);
+        for _ in 0..n {
+            let u: f64 = rng.gen();
+            let rank = cdf.partition_point(|&c| c < u).min(cdf.len() - 1) as u64;
+            counts[rank as usize] += 1;
+            sketch.add(&rank);
+            top.add(rank);
+        }
+        assert_eq!(sketch.count(), n);
+
+        // never below the true count, and rarely above it by more than ε N
+        let bound = (epsilon * n as f64) as u64;
+        let mut distinct = 0;
+        let mut violations = 0;
+        for (rank, &count) in counts.iter().enumerate() {
+            let estimate = sketch.estimate(&(rank as u64));
+            assert!(estimate >= count);
+            if count > 0 {
+                distinct += 1;
+                if estimate > count + bound {
+                    violations += 1;
+                }
+            }
+        }
+        assert!((violations as f64) <= delta * distinct as f64);
+
+        // the entropy estimate is within its bias bound below the entropy
+        let entropy: f64 = -counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / n as f64;
+                p * p.ln()
+            })
+            .sum::<f64>();
+        let estimate = sketch.entropy_estimate();
+        assert!(estimate <= entropy + 1e-9);
+        assert!(entropy - estimate <= (1.0 + distinct as f64 / sketch.width() as f64).ln());
+        assert!(estimate <= (sketch.width() as f64).ln());
+
+        // the true ten most frequent items come first, with valid bounds
+        let hitters = top.top();
+        let mut ranked: Vec<usize> = hitters[..10].iter().map(|h| h.item as usize).collect();
+        ranked.sort_unstable();
+        assert_eq!(ranked, (0..10).collect::<Vec<_>>());
+        for hitter in &hitters {
+            let count = counts[hitter.item as usize];
+            assert!(hitter.count >= count && hitter.count - hitter.error <= count);
+        }
+        // every item above N / k is monitored
+        for (rank, &count) in counts.iter().enumerate() {
+            if count > n / 100 {
+                assert!(top.get(&(rank as u64)).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let words: Vec<String> = (0..5000).map(|i| format!("w{}", (i * i) % 613)).collect();
+        let mut whole = CountMin::new(64, 4).unwrap();
+        let mut first = CountMin::new(64, 4).unwrap();
+        let mut second = CountMin::new(64, 4).unwrap();
+        for (i, word) in words.iter().enumerate() {
+            whole.add(word.as_str());
+            if i < 1234 {
+                first.add(word.as_str());
+            } else {
+                second.add(word.as_str());
+            }
+        }
+        first.merge(&second).unwrap();
+        assert_eq!(first, whole);
+        assert_eq!(first.count(), 5000);
+        assert!(first.merge(&CountMin::new(64, 3).unwrap()).is_err());
+        assert!(first.merge(&CountMin::new(63, 4).unwrap()).is_err());
+        let mut counted = CountMin::new(64, 4).unwrap();
+        counted.add_count("w1", 7);
+        assert_eq!(counted.estimate("w1"), 7);
+        // the counts saturate instead of overflowing
+        counted.add_count("w1", u64::MAX);
+        assert_eq!(counted.estimate("w1"), u64::MAX);
+        assert_eq!(counted.count(), u64::MAX);
+        counted.merge(&whole).unwrap();
+        assert_eq!(counted.count(), u64::MAX);
+        assert_eq!(counted.estimate("w1"), u64::MAX);
+    }
+
+    #[test]
+    fn test_exact_when_wide() {
+        // with more counters than items, collisions are unlikely and the
+        // entropy is that of the counts
+        let mut sketch = CountMin::new(1 << 16, 4).unwrap();
+        for (item, times) in [(1u32, 5), (2, 3), (3, 2)].iter() {
+            sketch.add_count(item, *times);
+        }
+        assert_eq!(sketch.estimate(&1u32), 5);
+        assert_eq!(sketch.estimate(&3u32), 2);
+        let expected = -[0.5f64, 0.3, 0.2].iter().map(|p| p * p.ln()).sum::<f64>();
+        assert_almost_eq!(sketch.entropy_estimate(), expected, 1e-15);
+    }
+
+    #[test]
+    fn test_space_saving_small() {
+        let mut top = SpaceSaving::new(3).unwrap();
+        for &x in &[1, 2, 1, 3, 1, 2] {
+            top.add(x);
+        }
+        // exact while at most k items are seen
+        assert_eq!(top.get(&1), Some(&HeavyHitter { item: 1, count: 3, error: 0 }));
+        assert_eq!(top.top().iter().map(|h| h.count).collect::<Vec<_>>(), vec![3, 2, 1]);
+        // 4 replaces 3, the item with the smallest count
+        top.add(4);
+        assert!(top.get(&3).is_none());
+        assert_eq!(top.get(&4), Some(&HeavyHitter { item: 4, count: 2, error: 1 }));
+        assert_eq!(top.count(), 7);
+        assert_eq!(top.capacity(), 3);
+        assert_eq!(top.top().len(), 3);
+    }
+}
@@ -0,0 +1,686 @@
+//! Provides [statistical process control](https://en.wikipedia.org/wiki/Statistical_process_control)
+//! utilities: the unbiasing constants of control charts, process capability
+//! indices, and control chart limits
+
+use crate::distribution::{ChiSquared, ContinuousCDF, NoncentralStudentsT, Normal};
+use crate::function::gamma::ln_gamma;
+use crate::function::integrate::integrate;
+use crate::function::root;
+use crate::statistics::Statistics;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Bound beyond which the standard normal density underflows, so that
+/// integrals over the real line can be restricted to a finite interval
+const NORMAL_BOUND: f64 = 40.0;
+
+/// Maximum number of steps taken to bracket a noncentrality parameter
+const MAX_EXPANSIONS: usize = 100;
+
+/// Maximum number of iterations of Brent's method refining a noncentrality
+/// parameter
+const MAX_ITERATIONS: usize = 200;
+
+/// Minimum degrees of freedom of the standard deviation behind a capability
+/// index; with one, the noncentral t distribution of the `Cpk` statistic
+/// has no mean and its confidence bounds fall below zero even for capable
+/// processes
+const MIN_FREEDOM: usize = 2;
+
+/// Returns the unbiasing constant `c4(n) = E[s] / σ` of the sample standard
+/// deviation of `n` normal observations
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(2 / (n - 1)) * Γ(n / 2) / Γ((n - 1) / 2)
+/// ```
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `n < 2`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::c4;
+///
+/// assert!((c4(5) - 0.9400).abs() < 1e-4);
+/// ```
+pub fn c4(n: usize) -> f64 {
+    if n < 2 {
+        return f64::NAN;
+    }
+    let n = n as f64;
+    (2.0 / (n - 1.0)).sqrt() * (ln_gamma(n / 2.0) - ln_gamma((n - 1.0) / 2.0)).exp()
+}
+
+/// Returns the unbiasing constant `d2(n) = E[W] / σ` where `W` is the range
+/// of `n` normal observations
+///
+/// # Formula
+///
+/// ```text
+/// ∫ 1 - Φ(x)^n - (1 - Φ(x))^n dx
+/// ```
+///
+/// integrated over the real line, where `Φ` is the standard normal cdf
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `n < 2`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::d2;
+///
+/// assert!((d2(2) - 2.0 / std::f64::consts::PI.sqrt()).abs() < 1e-10);
+/// ```
+pub fn d2(n: usize) -> f64 {
+    if n < 2 {
+        return f64::NAN;
+    }
+    let normal = Normal::standard();
+    let n = n as i32;
+    // the integrand vanishes to double precision outside [-40, 40]
+    integrate(
+        |x| 1.0 - normal.cdf(x).powi(n) - normal.sf(x).powi(n),
+        -NORMAL_BOUND,
+        NORMAL_BOUND,
+        1e-13,
+    )
+}
+
+/// Returns the constant `d3(n) = sd(W) / σ` where `W` is the range of `n`
+/// normal observations
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(2 ∫_0^∞ w (1 - F_W(w)) dw - d2(n)^2)
+/// F_W(w) = n ∫ φ(x) (Φ(x + w) - Φ(x))^(n - 1) dx
+/// ```
+///
+/// where `φ` and `Φ` are the standard normal pdf and cdf
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `n < 2`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::d3;
+///
+/// assert!((d3(5) - 0.864).abs() < 1e-3);
+/// ```
+pub fn d3(n: usize) -> f64 {
+    if n < 2 {
+        return f64::NAN;
+    }
+    let normal = Normal::standard();
+    let power = n as i32 - 1;
+    let range_cdf = |w: f64| {
+        let inner = |x: f64| {
+            let phi = (-x * x / 2.0).exp() / crate::consts::SQRT_2PI;
+            phi * (normal.cdf(x + w) - normal.cdf(x)).powi(power)
+        };
+        n as f64 * integrate(inner, -NORMAL_BOUND, NORMAL_BOUND, 1e-13)
+    };
+    let second_moment =
+        2.0 * integrate(|w| w * (1.0 - range_cdf(w)), 0.0, 2.0 * NORMAL_BOUND, 1e-12);
+    let mean = d2(n);
+    (second_moment - mean * mean).sqrt()
+}
+
+/// An estimated process capability index with its two-sided confidence
+/// interval
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CapabilityIndex {
+    /// The point estimate of the index
+    pub estimate: f64,
+    /// The lower confidence bound
+    pub lower: f64,
+    /// The upper confidence bound
+    pub upper: f64,
+}
+
+/// Lower control limit, center line and upper control limit of a control
+/// chart
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ControlLimits {
+    /// The lower control limit
+    pub lower: f64,
+    /// The center line
+    pub center: f64,
+    /// The upper control limit
+    pub upper: f64,
+}
+
+/// Computes the potential capability `Cp = (USL - LSL) / 6σ` using the
+/// within-subgroup standard deviation, with a `1 - alpha` confidence
+/// interval
+///
+/// # Remarks
+///
+/// `σ` is the pooled standard deviation of the subgroups divided by
+/// `c4(d + 1)`, where `d = Σ(n_i - 1)` are its degrees of freedom. The
+/// confidence interval scales the estimate by `sqrt(χ^2_(d, q) / d)` for
+/// the quantiles `q = alpha / 2` and `q = 1 - alpha / 2`, so that it is
+/// built around the reported `Cp` as in Minitab.
+///
+/// # Errors
+///
+/// Returns an error if `lsl >= usl`, `alpha` is not in `(0, 1)`, a subgroup
+/// contains a non-finite value, or the subgroups provide fewer than two
+/// degrees of freedom for the pooled standard deviation
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::cp;
+///
+/// let subgroups = vec![vec![9.9, 10.1, 10.0], vec![10.2, 9.8, 10.0], vec![10.1, 9.9, 10.0]];
+/// let index = cp(&subgroups, 9.0, 11.0, 0.05).unwrap();
+/// assert!(index.lower < index.estimate && index.estimate < index.upper);
+/// ```
+pub fn cp(subgroups: &[Vec<f64>], lsl: f64, usl: f64, alpha: f64) -> Result<CapabilityIndex> {
+    check_spec(lsl, usl, alpha)?;
+    let (_, _, s_p, freedom) = pooled(subgroups)?;
+    Ok(cp_with(s_p / c4(freedom + 1), freedom, lsl, usl, alpha))
+}
+
+/// Computes the capability `Cpk = min(USL - μ, μ - LSL) / 3σ` using the
+/// within-subgroup standard deviation, with a `1 - alpha` confidence
+/// interval
+///
+/// # Remarks
+///
+/// `σ` is estimated as in `cp`. The confidence interval is obtained by
+/// inverting the noncentral t distribution of `sqrt(N) (x̄ - LSL) / s_p`
+/// (or its counterpart for the upper limit) for the specification limit
+/// closest to the mean, and neglects the other limit; it is exact for
+/// one-sided specifications. The bounds are those of the index itself and
+/// do not depend on the unbiasing of `σ`, so unlike for `cp` they are not
+/// proportional to the estimate. Since the index is negative for a mean
+/// outside the specification limits, the lower bound is negative when the
+/// data cannot rule out such a mean.
+///
+/// # Errors
+///
+/// See `cp`. Also returns an error if the noncentrality parameter of a
+/// confidence bound cannot be found.
+pub fn cpk(subgroups: &[Vec<f64>], lsl: f64, usl: f64, alpha: f64) -> Result<CapabilityIndex> {
+    check_spec(lsl, usl, alpha)?;
+    let (mean, count, s_p, freedom) = pooled(subgroups)?;
+    cpk_with(
+        mean,
+        s_p / c4(freedom + 1),
+        s_p,
+        count,
+        freedom,
+        lsl,
+        usl,
+        alpha,
+    )
+}
+
+/// Computes the performance index `Pp = (USL - LSL) / 6s` using the overall
+/// sample standard deviation `s` of `data`, with a `1 - alpha` confidence
+/// interval based on `(n - 1) s^2 / σ^2 ~ χ^2_(n - 1)`
+///
+/// # Errors
+///
+/// Returns an error if `lsl >= usl`, `alpha` is not in `(0, 1)`, `data` has
+/// fewer than three elements or contains a non-finite value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::pp;
+///
+/// let data = [9.9, 10.1, 10.0, 10.2, 9.8, 10.0, 10.1, 9.9, 10.0];
+/// let index = pp(&data, 9.0, 11.0, 0.05).unwrap();
+/// assert!((index.estimate - 2.0 / (6.0 * 0.1225)).abs() < 1e-3);
+/// ```
+pub fn pp(data: &[f64], lsl: f64, usl: f64, alpha: f64) -> Result<CapabilityIndex> {
+    check_spec(lsl, usl, alpha)?;
+    let (_, s, freedom) = overall(data)?;
+    Ok(cp_with(s, freedom, lsl, usl, alpha))
+}
+
+/// Computes the performance index `Ppk = min(USL - x̄, x̄ - LSL) / 3s`
+/// using the overall sample standard deviation `s` of `data`, with a
+/// `1 - alpha` confidence interval computed as for `cpk`, whose lower bound
+/// is negative when the data cannot rule out a mean outside the
+/// specification limits
+///
+/// # Errors
+///
+/// See `pp`. Also returns an error if the noncentrality parameter of a
+/// confidence bound cannot be found.
+pub fn ppk(data: &[f64], lsl: f64, usl: f64, alpha: f64) -> Result<CapabilityIndex> {
+    check_spec(lsl, usl, alpha)?;
+    let (mean, s, freedom) = overall(data)?;
+    cpk_with(mean, s, s, data.len(), freedom, lsl, usl, alpha)
+}
+
+/// Computes the control limits of the `X̄` chart and of the `R` chart for
+/// subgroups of equal size `n`, returned in that order
+///
+/// # Formula
+///
+/// ```text
+/// X̄ chart: x̿ ± 3 R̄ / (d2(n) sqrt(n))
+/// R chart: R̄ * max(0, 1 - 3 d3(n) / d2(n)), R̄, R̄ * (1 + 3 d3(n) / d2(n))
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if there are no subgroups, if the subgroups are not all
+/// of the same size of at least two, or if a value is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::xbar_r_limits;
+///
+/// let subgroups = vec![vec![9.9, 10.1, 10.0], vec![10.2, 9.8, 10.0], vec![10.1, 9.9, 10.0]];
+/// let (xbar, range) = xbar_r_limits(&subgroups).unwrap();
+/// assert!((xbar.center - 10.0).abs() < 1e-12);
+/// assert_eq!(range.lower, 0.0);
+/// ```
+pub fn xbar_r_limits(subgroups: &[Vec<f64>]) -> Result<(ControlLimits, ControlLimits)> {
+    let size = subgroups.first().map_or(0, |s| s.len());
+    if size < 2 {
+        return Err(StatsError::ArgGte("subgroup size", 2.0));
+    }
+    if subgroups.iter().any(|s| s.len() != size) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if subgroups.iter().flatten().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("subgroups"));
+    }
+
+    let grand_mean = subgroups.iter().map(|s| s.mean()).mean();
+    let mean_range = subgroups.iter().map(|s| s.max() - s.min()).mean();
+    let (d2, d3) = (d2(size), d3(size));
+    let half_width = 3.0 * mean_range / (d2 * (size as f64).sqrt());
+    let xbar = ControlLimits {
+        lower: grand_mean - half_width,
+        center: grand_mean,
+        upper: grand_mean + half_width,
+    };
+    let range = ControlLimits {
+        lower: mean_range * (1.0 - 3.0 * d3 / d2).max(0.0),
+        center: mean_range,
+        upper: mean_range * (1.0 + 3.0 * d3 / d2),
+    };
+    Ok((xbar, range))
+}
+
+/// Computes the control limits of the individuals chart and of the moving
+/// range chart (with moving ranges of two consecutive observations),
+/// returned in that order
+///
+/// # Formula
+///
+/// ```text
+/// I chart: x̄ ± 3 MR̄ / d2(2)
+/// MR chart: 0, MR̄, MR̄ * (1 + 3 d3(2) / d2(2))
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two elements or contains a
+/// non-finite value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::spc::individuals_mr_limits;
+///
+/// let data = [10.0, 10.2, 9.9, 10.1, 9.8, 10.0];
+/// let (individuals, moving_range) = individuals_mr_limits(&data).unwrap();
+/// assert!((moving_range.upper / moving_range.center - 3.267).abs() < 1e-3);
+/// ```
+pub fn individuals_mr_limits(data: &[f64]) -> Result<(ControlLimits, ControlLimits)> {
+    if data.len() < 2 {
+        return Err(StatsError::ArgGte("data length", 2.0));
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+
+    let mean = data.mean();
+    let mean_moving_range = data.windows(2).map(|w| (w[1] - w[0]).abs()).mean();
+    let (d2, d3) = (d2(2), d3(2));
+    let half_width = 3.0 * mean_moving_range / d2;
+    let individuals = ControlLimits {
+        lower: mean - half_width,
+        center: mean,
+        upper: mean + half_width,
+    };
+    let moving_range = ControlLimits {
+        lower: 0.0,
+        center: mean_moving_range,
+        upper: mean_moving_range * (1.0 + 3.0 * d3 / d2),
+    };
+    Ok((individuals, moving_range))
+}
+
+fn check_spec(lsl: f64, usl: f64, alpha: f64) -> Result<()> {
+    if lsl.is_nan() || usl.is_nan() || lsl >= usl {
+        return Err(StatsError::ArgLtArg("lsl", "usl"));
+    }
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    Ok(())
+}
+
+/// Returns the grand mean, the total number of observations, the pooled
+/// standard deviation and its degrees of freedom
+fn pooled(subgroups: &[Vec<f64>]) -> Result<(f64, usize, f64, usize)> {
+    if subgroups.iter().flatten().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("subgroups"));
+    }
+    let freedom: usize = subgroups.iter().map(|s| s.len().saturating_sub(1)).sum();
+    if freedom < MIN_FREEDOM {
+        return Err(StatsError::SpecialCase(
+            "the subgroups must provide at least two degrees of freedom",
+        ));
+    }
+    let count: usize = subgroups.iter().map(|s| s.len()).sum();
+    let sum_squares: f64 = subgroups
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mean = s.mean();
+            s.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+        })
+        .sum();
+    let mean = subgroups.iter().flatten().mean();
+    Ok((mean, count, (sum_squares / freedom as f64).sqrt(), freedom))
+}
+
+/// Returns the mean, the sample standard deviation and its degrees of
+/// freedom
+fn overall(data: &[f64]) -> Result<(f64, f64, usize)> {
+    if data.len() < MIN_FREEDOM + 1 {
+        return Err(StatsError::ArgGte("data length", (MIN_FREEDOM + 1) as f64));
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+    Ok((data.mean(), data.std_dev(), data.len() - 1))
+}
+
+/// `Cp`/`Pp` from the standard deviation estimate `sigma` with a confidence
+/// interval scaling the estimate by chi-squared quantiles
+fn cp_with(sigma: f64, freedom: usize, lsl: f64, usl: f64, alpha: f64) -> CapabilityIndex {
+    let chi_sq = ChiSquared::new(freedom as f64).unwrap();
+    let nu = freedom as f64;
+    let estimate = (usl - lsl) / (6.0 * sigma);
+    CapabilityIndex {
+        estimate,
+        lower: estimate * (chi_sq.inverse_cdf(alpha / 2.0) / nu).sqrt(),
+        upper: estimate * (chi_sq.inverse_cdf(1.0 - alpha / 2.0) / nu).sqrt(),
+    }
+}
+
+/// `Cpk`/`Ppk` from the standard deviation estimate `sigma` with a
+/// confidence interval obtained by inverting the noncentral t distribution
+/// of `sqrt(count) * distance / s` for the nearest specification limit
+#[allow(clippy::too_many_arguments)]
+fn cpk_with(
+    mean: f64,
+    sigma: f64,
+    s: f64,
+    count: usize,
+    freedom: usize,
+    lsl: f64,
+    usl: f64,
+    alpha: f64,
+) -> Result<CapabilityIndex> {
+    let distance = (usl - mean).min(mean - lsl);
+    let root_n = (count as f64).sqrt();
+    let nu = freedom as f64;
+    let t = root_n * distance / s;
+    // the cdf at `t` is decreasing in the noncentrality
+    let noncentrality = |p: f64| -> Result<f64> {
+        let g = |d: f64| NoncentralStudentsT::new(nu, d).unwrap().cdf(t) - p;
+        let (lo, hi) = root::bracket_root(g, t, 2.0, MAX_EXPANSIONS)?;
+        let tol = 1e-12 * lo.abs().max(hi.abs()).max(1.0);
+        Ok(root::brent_with(g, lo, hi, tol, MAX_ITERATIONS)?.root)
+    };
+    Ok(CapabilityIndex {
+        estimate: distance / (3.0 * sigma),
+        lower: noncentrality(1.0 - alpha / 2.0)? / (3.0 * root_n),
+        upper: noncentrality(alpha / 2.0)? / (3.0 * root_n),
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::StudentsT;
+
+    // exact values computed with mpmath for n = 2..=25
+    const C4: [f64; 24] = [
+        0.797884560803, 0.886226925453, 0.921317731924, 0.939985602987, 0.951532861948,
+        0.9593687887, 0.965030456147, 0.969310699714, 0.972659274122, 0.975350077145,
+        0.977559351855, 0.979405604314, 0.980971436756, 0.982316177163, 0.983483531616,
+        0.984506405472, 0.985410043808, 0.98621413686, 0.986934267525, 0.987582928826,
+        0.988170253316, 0.988704545234, 0.989192674959, 0.989640375586,
+    ];
+
+    // exact values computed with mpmath for n = 2..=25; they agree with the
+    // three-decimal tables of Montgomery, Introduction to Statistical
+    // Quality Control, Appendix VI
+    const D2: [f64; 24] = [
+        f64::consts::FRAC_2_SQRT_PI, 1.692568750643, 2.058750746008, 2.325928947281, 2.534412721223,
+        2.704356751214, 2.847200612091, 2.970026324418, 3.07750546167, 3.172872703816,
+        3.258455279744, 3.335980354098, 3.4067631082, 3.471826889882, 3.53198278611,
+        3.587883961765, 3.640063757937, 3.688963023208, 3.734950119597, 3.778335829843,
+        3.819384643363, 3.858323423285, 3.895348148451, 3.930629219507,
+    ];
+    // the factors of Montgomery, Introduction to Statistical Quality
+    // Control, Appendix VI, for n = 2..=25: A2, d2, d3, D3 and D4
+    const FACTORS: [[f64; 5]; 24] = [
+        [1.880, 1.128, 0.853, 0.0, 3.267], [1.023, 1.693, 0.888, 0.0, 2.574],
+        [0.729, 2.059, 0.880, 0.0, 2.282], [0.577, 2.326, 0.864, 0.0, 2.114],
+        [0.483, 2.534, 0.848, 0.0, 2.004], [0.419, 2.704, 0.833, 0.076, 1.924],
+        [0.373, 2.847, 0.820, 0.136, 1.864], [0.337, 2.970, 0.808, 0.184, 1.816],
+        [0.308, 3.078, 0.797, 0.223, 1.777], [0.285, 3.173, 0.787, 0.256, 1.744],
+        [0.266, 3.258, 0.778, 0.283, 1.717], [0.249, 3.336, 0.770, 0.307, 1.693],
+        [0.235, 3.407, 0.763, 0.328, 1.672], [0.223, 3.472, 0.756, 0.347, 1.653],
+        [0.212, 3.532, 0.750, 0.363, 1.637], [0.203, 3.588, 0.744, 0.378, 1.622],
+        [0.194, 3.640, 0.739, 0.391, 1.608], [0.187, 3.689, 0.734, 0.403, 1.597],
+        [0.180, 3.735, 0.729, 0.415, 1.585], [0.173, 3.778, 0.724, 0.425, 1.575],
+        [0.167, 3.819, 0.720, 0.434, 1.566], [0.162, 3.858, 0.716, 0.443, 1.557],
+        [0.157, 3.895, 0.712, 0.451, 1.548], [0.153, 3.931, 0.708, 0.459, 1.541],
+    ];
+
+    #[test]
+    fn test_c4() {
+        for (i, &expected) in C4.iter().enumerate() {
+            assert_almost_eq!(c4(i + 2), expected, 1e-11);
+        }
+        assert!(c4(1).is_nan());
+    }
+
+    #[test]
+    fn test_d2_d3_tables() {
+        for n in 2..=25 {
+            assert_almost_eq!(d2(n), D2[n - 2], 1e-10);
+            // the published factors are rounded, and derived from rounded
+            // d2 and d3 for D3 and D4, so they agree to within a unit in
+            // the third decimal
+            assert_almost_eq!(d2(n), FACTORS[n - 2][1], 1e-3);
+            assert_almost_eq!(d3(n), FACTORS[n - 2][2], 1e-3);
+        }
+        assert!(d2(1).is_nan());
+        assert!(d3(0).is_nan());
+    }
+
+    #[test]
+    fn test_d2_d3_exact() {
+        // closed forms for n = 2 and n = 3
+        let pi = f64::consts::PI;
+        assert_almost_eq!(d2(2), 2.0 / pi.sqrt(), 1e-10);
+        assert_almost_eq!(d2(3), 3.0 / pi.sqrt(), 1e-10);
+        assert_almost_eq!(d3(2), (2.0 - 4.0 / pi).sqrt(), 1e-8);
+    }
+
+    #[test]
+    fn test_pp_ppk() {
+        let data = [
+            10.12, 9.87, 10.05, 9.95, 10.21, 9.78, 10.02, 9.91, 10.08, 10.15,
+            9.96, 10.03, 9.89, 10.11, 9.99, 10.06, 9.92, 10.01, 9.97, 10.04,
+        ];
+        let (mean, s, n) = (data.mean(), data.std_dev(), data.len() as f64);
+        let pp = pp(&data, 9.5, 10.6, 0.05).unwrap();
+        assert_almost_eq!(pp.estimate, 1.1 / (6.0 * s), 1e-12);
+        let chi_sq = ChiSquared::new(n - 1.0).unwrap();
+        assert_almost_eq!(pp.lower, pp.estimate * (chi_sq.inverse_cdf(0.025) / (n - 1.0)).sqrt(), 1e-8);
+        assert_almost_eq!(pp.upper, pp.estimate * (chi_sq.inverse_cdf(0.975) / (n - 1.0)).sqrt(), 1e-8);
+
+        let ppk = ppk(&data, 9.5, 10.6, 0.05).unwrap();
+        assert_almost_eq!(ppk.estimate, (mean - 9.5) / (3.0 * s), 1e-12);
+        assert!(ppk.lower < ppk.estimate && ppk.estimate < ppk.upper);
+        // the bounds are noncentrality parameters at which the observed t is a
+        // 97.5% / 2.5% quantile
+        let t = n.sqrt() * (mean - 9.5) / s;
+        let cdf = |d: f64| NoncentralStudentsT::new(n - 1.0, d).unwrap().cdf(t);
+        assert_almost_eq!(cdf(3.0 * n.sqrt() * ppk.lower), 0.975, 1e-8);
+        assert_almost_eq!(cdf(3.0 * n.sqrt() * ppk.upper), 0.025, 1e-8);
+
+        // bounds computed with mpmath, the noncentral t cdf by quadrature
+        assert_almost_eq!(pp.estimate, 1.765502970136021, 1e-12);
+        assert_almost_eq!(pp.lower, 1.208775335954773, 1e-8);
+        assert_almost_eq!(pp.upper, 2.321532241957822, 1e-8);
+        assert_almost_eq!(ppk.estimate, 1.624262732525140, 1e-12);
+        assert_almost_eq!(ppk.lower, 1.089916058563275, 1e-8);
+        assert_almost_eq!(ppk.upper, 2.154821923125986, 1e-8);
+    }
+
+    #[test]
+    fn test_pp_published() {
+        // Montgomery, Introduction to Statistical Quality Control: n = 20,
+        // s = 1.75, LSL = 38 and USL = 62 give Cp = 2.29 with the 95%
+        // confidence interval 1.57 <= Cp <= 3.01
+        let z: Vec<f64> = (0..20).map(|i| i as f64 - 9.5).collect();
+        let scale = 1.75 / (&z).std_dev();
+        let data: Vec<f64> = z.iter().map(|x| 50.0 + scale * x).collect();
+        let pp = pp(&data, 38.0, 62.0, 0.05).unwrap();
+        // the published bounds are computed from the rounded Cp
+        assert_almost_eq!(pp.estimate, 2.29, 5e-3);
+        assert_almost_eq!(pp.lower, 1.57, 1e-2);
+        assert_almost_eq!(pp.upper, 3.01, 1e-2);
+        // and to full precision, with mpmath
+        assert_almost_eq!(pp.lower, 1.564945004537740, 1e-8);
+        assert_almost_eq!(pp.upper, 3.005579429742044, 1e-8);
+    }
+
+    #[test]
+    fn test_cp_cpk() {
+        let subgroups = vec![
+            vec![10.1, 9.9, 10.0, 10.2], vec![9.8, 10.0, 10.1, 9.9],
+            vec![10.3, 10.1, 10.0, 10.2], vec![9.9, 9.7, 10.0, 10.1],
+        ];
+        let cp = cp(&subgroups, 9.4, 10.6, 0.05).unwrap();
+        let cpk = cpk(&subgroups, 9.4, 10.6, 0.05).unwrap();
+        // pooled standard deviation with 12 degrees of freedom
+        let sum_squares: f64 = subgroups.iter()
+            .map(|s| { let m = s.mean(); s.iter().map(|x| (x - m) * (x - m)).sum::<f64>() })
+            .sum();
+        let sigma = (sum_squares / 12.0).sqrt() / c4(13);
+        assert_almost_eq!(cp.estimate, 1.2 / (6.0 * sigma), 1e-12);
+        let mean = subgroups.iter().flatten().mean();
+        assert_almost_eq!(cpk.estimate, (10.6 - mean).min(mean - 9.4) / (3.0 * sigma), 1e-12);
+        assert!(cpk.estimate <= cp.estimate);
+        assert!(cp.lower < cp.estimate && cp.estimate < cp.upper);
+        assert!(cpk.lower < cpk.estimate && cpk.estimate < cpk.upper);
+        // the interval of Cp is proportional to the reported estimate
+        let chi_sq = ChiSquared::new(12.0).unwrap();
+        assert_almost_eq!(cp.lower, cp.estimate * (chi_sq.inverse_cdf(0.025) / 12.0).sqrt(), 1e-12);
+        assert_almost_eq!(cp.upper, cp.estimate * (chi_sq.inverse_cdf(0.975) / 12.0).sqrt(), 1e-12);
+
+        // bounds computed with mpmath, the noncentral t cdf by quadrature
+        assert_almost_eq!(cp.estimate, 1.392359545407582, 1e-12);
+        assert_almost_eq!(cp.lower, 0.8434784675430854, 1e-8);
+        assert_almost_eq!(cp.upper, 1.941691196715788, 1e-8);
+        assert_almost_eq!(cpk.estimate, 1.348848309613595, 1e-12);
+        assert_almost_eq!(cpk.lower, 0.8076317762519726, 1e-8);
+        assert_almost_eq!(cpk.upper, 1.942501989721679, 1e-8);
+    }
+
+    #[test]
+    fn test_bad_capability() {
+        let data = [1.0, 2.0, 3.0];
+        assert!(pp(&data, 2.0, 1.0, 0.05).is_err());
+        assert!(pp(&data, 0.0, 4.0, 0.0).is_err());
+        assert!(pp(&[1.0], 0.0, 4.0, 0.05).is_err());
+        // a single degree of freedom is rejected
+        assert!(pp(&[1.0, 2.0], 0.0, 4.0, 0.05).is_err());
+        assert!(ppk(&[1.9, 2.1], 0.0, 4.0, 0.05).is_err());
+        assert!(cpk(&[vec![1.9, 2.1], vec![2.0]], 0.0, 4.0, 0.05).is_err());
+        let index = ppk(&[1.9, 2.0, 2.1], 0.0, 4.0, 0.05).unwrap();
+        assert!(index.lower > 0.0 && index.upper.is_finite());
+        assert!(ppk(&[1.0, f64::NAN], 0.0, 4.0, 0.05).is_err());
+        assert!(cp(&[vec![1.0], vec![2.0]], 0.0, 4.0, 0.05).is_err());
+        assert!(cpk(&[], 0.0, 4.0, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_xbar_r_limits() {
+        let subgroups = vec![
+            vec![10.1, 9.9, 10.0, 10.2, 9.8], vec![9.8, 10.0, 10.1, 9.9, 10.2],
+            vec![10.3, 10.1, 10.0, 10.2, 9.9], vec![9.9, 9.7, 10.0, 10.1, 10.0],
+        ];
+        let (xbar, range) = xbar_r_limits(&subgroups).unwrap();
+        let mean_range = (0.4 + 0.4 + 0.4 + 0.4) / 4.0;
+        // tabulated A2 = 0.577, D3 = 0, D4 = 2.114 for n = 5
+        assert_almost_eq!(xbar.upper - xbar.center, 0.577 * mean_range, 1e-3);
+        assert_almost_eq!(xbar.center - xbar.lower, 0.577 * mean_range, 1e-3);
+        assert_eq!(range.lower, 0.0);
+        assert_almost_eq!(range.center, mean_range, 1e-12);
+        assert_almost_eq!(range.upper, 2.114 * mean_range, 1e-3);
+
+        assert!(xbar_r_limits(&[]).is_err());
+        assert!(xbar_r_limits(&[vec![1.0]]).is_err());
+        assert!(xbar_r_limits(&[vec![1.0, 2.0], vec![1.0, 2.0, 3.0]]).is_err());
+    }
+
+    #[test]
+    fn test_xbar_r_published_factors() {
+        // two subgroups of every size with ranges 1 and 2, so that R̄ = 1.5
+        for n in 2..=25 {
+            let subgroup: Vec<f64> = (0..n).map(|i| i as f64 / (n - 1) as f64).collect();
+            let subgroups = vec![subgroup.clone(), subgroup.iter().map(|x| 2.0 * x).collect()];
+            let (xbar, range) = xbar_r_limits(&subgroups).unwrap();
+            let [a2, _, _, d3_factor, d4_factor] = FACTORS[n - 2];
+            assert_almost_eq!((xbar.upper - xbar.center) / 1.5, a2, 1e-3);
+            assert_almost_eq!((xbar.center - xbar.lower) / 1.5, a2, 1e-3);
+            assert_almost_eq!(range.center, 1.5, 1e-12);
+            assert_almost_eq!(range.lower / 1.5, d3_factor, 1e-3);
+            assert_almost_eq!(range.upper / 1.5, d4_factor, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_individuals_mr_limits() {
+        let data = [10.0, 10.2, 9.9, 10.1, 9.8, 10.0];
+        let (individuals, moving_range) = individuals_mr_limits(&data).unwrap();
+        let mean_moving_range = (0.2 + 0.3 + 0.2 + 0.3 + 0.2) / 5.0;
+        assert_almost_eq!(individuals.center, data.mean(), 1e-12);
+        // tabulated E2 = 2.660 and D4 = 3.267 for n = 2
+        assert_almost_eq!(individuals.upper - individuals.center, 2.660 * mean_moving_range, 1e-3);
+        assert_almost_eq!(moving_range.center, mean_moving_range, 1e-12);
+        assert_almost_eq!(moving_range.upper, 3.267 * mean_moving_range, 1e-3);
+        assert!(individuals_mr_limits(&[1.0]).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+//! Provides index splits for cross-validation and other resampling
+//! workflows
+
+use crate::{Result, StatsError};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// A `(train, test)` pair of sorted index sets
+pub type Split = (Vec<usize>, Vec<usize>);
+
+/// Builds the `(train, test)` pairs from the assignment of every index to a
+/// fold
+fn splits_from_folds(folds: &[usize], k: usize) -> Vec<Split> {
+    (0..k)
+        .map(|f| {
+            let (test, train): (Vec<usize>, Vec<usize>) =
+                (0..folds.len()).partition(|&i| folds[i] == f);
+            (train, test)
+        })
+        .collect()
+}
+
+/// Partitions the indices `0..n` into `k` folds and returns, for every
+/// fold, the `(train, test)` pair where `test` is the fold and `train` its
+/// complement
+///
+/// # Remarks
+///
+/// Fold sizes differ by at most one, the first `n % k` folds being the
+/// larger ones. Without `shuffle` the folds are contiguous ranges and `rng`
+/// is not used; with it, the indices are permuted by `rng` before being
+/// assigned. Both index sets of every pair are sorted.
+///
+/// # Errors
+///
+/// Returns an error if `k < 2` or `k > n`
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::statistics::split::kfold;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let folds = kfold(5, 2, false, &mut rng).unwrap();
+/// assert_eq!(folds[0], (vec![3, 4], vec![0, 1, 2]));
+/// assert_eq!(folds[1], (vec![0, 1, 2], vec![3, 4]));
+/// ```
+pub fn kfold<R: Rng + ?Sized>(
+    n: usize,
+    k: usize,
+    shuffle: bool,
+    rng: &mut R,
+) -> Result<Vec<Split>> {
+    if k < 2 {
+        return Err(StatsError::ArgGte("k", 2.0));
+    }
+    if k > n {
+        return Err(StatsError::ArgLteArg("k", "n"));
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    if shuffle {
+        order.shuffle(rng);
+    }
+    let (size, extra) = (n / k, n % k);
+    let mut folds = vec![0; n];
+    let mut start = 0;
+    for f in 0..k {
+        let end = start + size + usize::from(f < extra);
+        for &i in &order[start..end] {
+            folds[i] = f;
+        }
+        start = end;
+    }
+    Ok(splits_from_folds(&folds, k))
+}
+
+/// Partitions the indices of `labels` into `k` folds that preserve the
+/// proportion of every label, and returns the `(train, test)` pair of
+/// every fold as `kfold` does
+///
+/// # Remarks
+///
+/// The members of every class are shuffled by `rng` and dealt to the folds
+/// in turn, in increasing order of the labels and continuing from the fold
+/// where the previous class stopped, so
+/// that each fold receives `⌊m / k⌋` or `⌈m / k⌉` members of a class of size
+/// `m` and the remainders of the classes go to the folds that are smallest
+/// so far. Fold sizes therefore also differ by at most one.
+///
+/// # Errors
+///
+/// Returns an error if `k < 2`, if `k` exceeds the number of labels, or if
+/// any label occurs fewer than `k` times
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::statistics::split::stratified_kfold;
+///
+/// let labels = [0, 0, 0, 0, 1, 1, 1, 1];
+/// let mut rng = StdRng::seed_from_u64(0);
+/// for (_, test) in stratified_kfold(&labels, 2, &mut rng).unwrap() {
+///     assert_eq!(test.iter().filter(|&&i| labels[i] == 1).count(), 2);
+/// }
+/// ```
+pub fn stratified_kfold<R: Rng + ?Sized>(
+    labels: &[usize],
+    k: usize,
+    rng: &mut R,
+) -> Result<Vec<Split>> {
+    if k < 2 {
+        return Err(StatsError::ArgGte("k", 2.0));
+    }
+    if k > labels.len() {
+        return Err(StatsError::ArgLteArg("k", "labels length"));
+    }
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, &label) in labels.iter().enumerate() {
+        members.entry(label).or_default().push(i);
+    }
+    if members.values().any(|m| m.len() < k) {
+        return Err(StatsError::SpecialCase(
+            "every label must occur at least k times",
+        ));
+    }
+    let mut folds = vec![0; labels.len()];
+    let mut cursor = 0;
+    for class in members.values_mut() {
+        class.shuffle(rng);
+        for &i in class.iter() {
+            folds[i] = cursor % k;
+            cursor += 1;
+        }
+    }
+    Ok(splits_from_folds(&folds, k))
+}
+
+/// Randomly splits the indices `0..n` into a `(train, test)` pair, the test
+/// set holding `⌈n * test_fraction⌉` indices
+///
+/// # Errors
+///
+/// Returns an error if `test_fraction` is not in `(0, 1)` or if either set
+/// would be empty
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::statistics::split::train_test_split_indices;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (train, test) = train_test_split_indices(10, 0.25, &mut rng).unwrap();
+/// assert_eq!(train.len(), 7);
+/// assert_eq!(test.len(), 3);
+/// ```
+pub fn train_test_split_indices<R: Rng + ?Sized>(
+    n: usize,
+    test_fraction: f64,
+    rng: &mut R,
+) -> Result<Split> {
+    if test_fraction.is_nan() || test_fraction <= 0.0 || test_fraction >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("test_fraction", 0.0, 1.0));
+    }
+    let n_test = (n as f64 * test_fraction).ceil() as usize;
+    if n_test == 0 || n_test >= n {
+        return Err(StatsError::BadParams);
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(rng);
+    let mut test = order[..n_test].to_vec();
+    let mut train = order[n_test..].to_vec();
+    test.sort_unstable();
+    train.sort_unstable();
+    Ok((train, test))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn check_partition(splits: &[Split], n: usize) {
+        let mut seen = vec![0; n];
+        for (train, test) in splits {
+            assert_eq!(train.len() + test.len(), n);
+            assert!(train.windows(2).all(|w| w[0] < w[1]));
+            assert!(test.windows(2).all(|w| w[0] < w[1]));
+            assert!(train.iter().all(|i| test.binary_search(i).is_err()));
+            for &i in test {
+                seen[i] += 1;
+            }
+        }
+        assert!(seen.iter().all(|&s| s == 1));
+        let sizes: Vec<usize> = splits.iter().map(|s| s.1.len()).collect();
+        assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+    }
+
+    #[test]
+    fn test_kfold_partition() {
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for &(n, k) in &[(10, 2), (10, 3), (17, 5), (5, 5), (100, 7)] {
+                check_partition(&kfold(n, k, true, &mut rng).unwrap(), n);
+                check_partition(&kfold(n, k, false, &mut rng).unwrap(), n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kfold_deterministic() {
+        let a = kfold(20, 4, true, &mut StdRng::seed_from_u64(3)).unwrap();
+        let b = kfold(20, 4, true, &mut StdRng::seed_from_u64(3)).unwrap();
+        assert_eq!(a, b);
+        let unshuffled = kfold(7, 3, false, &mut StdRng::seed_from_u64(3)).unwrap();
+        assert_eq!(unshuffled[0].1, vec![0, 1, 2]);
+        assert_eq!(unshuffled[1].1, vec![3, 4]);
+        assert_eq!(unshuffled[2].1, vec![5, 6]);
+    }
+
+    #[test]
+    fn test_stratified_kfold() {
+        // classes of sizes 13, 7 and 30
+        let labels: Vec<usize> = (0..50).map(|i| if i < 13 { 0 } else if i < 20 { 2 } else { 1 }).collect();
+        let counts = [13.0, 30.0, 7.0];
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for k in 2..=7 {
+                let splits = stratified_kfold(&labels, k, &mut rng).unwrap();
+                check_partition(&splits, labels.len());
+                for (_, test) in &splits {
+                    for (c, &m) in counts.iter().enumerate() {
+                        let in_fold = test.iter().filter(|&&i| labels[i] == c).count() as f64;
+                        assert!((in_fold - m / k as f64).abs() < 1.0);
+                    }
+                }
+            }
+        }
+        let a = stratified_kfold(&labels, 3, &mut StdRng::seed_from_u64(9)).unwrap();
+        let b = stratified_kfold(&labels, 3, &mut StdRng::seed_from_u64(9)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_stratified_kfold_sparse_labels() {
+        // only the labels that occur matter, not their magnitude
+        let labels = [usize::MAX, 1 << 40, usize::MAX, 1 << 40, 7, 7];
+        let dense = [2, 1, 2, 1, 0, 0];
+        let a = stratified_kfold(&labels, 2, &mut StdRng::seed_from_u64(5)).unwrap();
+        let b = stratified_kfold(&dense, 2, &mut StdRng::seed_from_u64(5)).unwrap();
+        check_partition(&a, labels.len());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (train, test) = train_test_split_indices(23, 0.3, &mut rng).unwrap();
+            assert_eq!(test.len(), 7);
+            assert_eq!(train.len(), 16);
+            let mut all: Vec<usize> = train.iter().chain(&test).cloned().collect();
+            all.sort_unstable();
+            assert_eq!(all, (0..23).collect::<Vec<_>>());
+            assert!(test.windows(2).all(|w| w[0] < w[1]));
+        }
+        let a = train_test_split_indices(30, 0.5, &mut StdRng::seed_from_u64(1)).unwrap();
+        let b = train_test_split_indices(30, 0.5, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(kfold(3, 4, true, &mut rng).is_err());
+        assert!(kfold(3, 1, true, &mut rng).is_err());
+        assert!(stratified_kfold(&[0, 0, 0, 1, 1], 3, &mut rng).is_err());
+        assert!(stratified_kfold(&[0, 0, 0, 1, 1], 1, &mut rng).is_err());
+        assert!(stratified_kfold(&[0, 0, 0, 2, 2, 2], 3, &mut rng).is_ok());
+        assert!(stratified_kfold(&[], 2, &mut rng).is_err());
+        assert!(train_test_split_indices(10, 0.0, &mut rng).is_err());
+        assert!(train_test_split_indices(10, 1.0, &mut rng).is_err());
+        assert!(train_test_split_indices(1, 0.5, &mut rng).is_err());
+    }
+}
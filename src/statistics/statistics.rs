@@ -424,4 +424,119 @@ pub trait Statistics<T> {
     /// # }
     /// ```
     fn quadratic_mean(self) -> T;
+
+    /// Estimates the variance of the trimmed sample, the data with the
+    /// `g = ⌊proportion * N⌋` smallest and `g` largest entries removed
+    ///
+    /// # Remarks
+    ///
+    /// On the `N - 2g` remaining entries, `N - 2g - 1` is used as a
+    /// normalizer.
+    ///
+    /// Returns `f64::NAN` if fewer than two entries remain or if any entry
+    /// is `f64::NAN`
+    ///
+    /// # Panics
+    ///
+    /// If `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    ///
+    /// let x = [1.0, 2.0, 3.0, 4.0, 100.0];
+    /// assert_eq!(x.trimmed_variance(0.2), 1.0);
+    /// assert!(x.trimmed_variance(0.4).is_nan());
+    /// ```
+    fn trimmed_variance(self, proportion: f64) -> T;
+
+    /// Estimates the winsorized variance, the variance of the data after
+    /// the `g = ⌊proportion * N⌋` smallest entries are set to the next
+    /// smallest one and the `g` largest to the next largest one
+    ///
+    /// # Remarks
+    ///
+    /// On a dataset of size `N`, `N-1` is used as a normalizer, so that
+    /// `(N - 1) s_w^2 / (h (h - 1))` with `h = N - 2g` is the squared
+    /// standard error of the trimmed mean used by Yuen's t-test.
+    ///
+    /// Returns `f64::NAN` if fewer than two entries would be left by
+    /// trimming or if any entry is `f64::NAN`
+    ///
+    /// # Panics
+    ///
+    /// If `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    ///
+    /// // winsorized to [2, 2, 3, 4, 4]
+    /// let x = [1.0, 2.0, 3.0, 4.0, 100.0];
+    /// assert_eq!(x.winsorized_variance(0.2), 1.0);
+    /// ```
+    fn winsorized_variance(self, proportion: f64) -> T;
+
+    /// Returns the index of the minimum value in the data, the first one
+    /// if the minimum is tied
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` if data is empty. If an entry is `f64::NAN`, returns
+    /// the index of the first `f64::NAN`, as `min` returns `f64::NAN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::f64;
+    /// use statrs::statistics::Statistics;
+    ///
+    /// let x: [f64; 0] = [];
+    /// assert_eq!(x.argmin(), None);
+    ///
+    /// let y = [3.0, -2.0, 5.0, -2.0];
+    /// assert_eq!(y.argmin(), Some(1));
+    ///
+    /// let z = [3.0, -2.0, f64::NAN, -5.0];
+    /// assert_eq!(z.argmin(), Some(2));
+    /// ```
+    fn argmin(self) -> Option<usize>;
+
+    /// Returns the index of the maximum value in the data, the first one
+    /// if the maximum is tied
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` if data is empty. If an entry is `f64::NAN`, returns
+    /// the index of the first `f64::NAN`, as `max` returns `f64::NAN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    ///
+    /// let x = [3.0, 5.0, -2.0, 5.0];
+    /// assert_eq!(x.argmax(), Some(1));
+    /// ```
+    fn argmax(self) -> Option<usize>;
+
+    /// Returns the minimum and the maximum value in the data, computed in
+    /// a single pass
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` if data is empty and `Some((f64::NAN, f64::NAN))` if
+    /// an entry is `f64::NAN`, as `min` and `max` return `f64::NAN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    ///
+    /// let x = [0.0, 3.0, -2.0];
+    /// assert_eq!(x.min_max(), Some((-2.0, 3.0)));
+    /// ```
+    fn min_max(self) -> Option<(T, T)>;
 }
@@ -0,0 +1,737 @@
+//! Provides summaries of data streams that see every observation once and
+//! use bounded memory, for streams too large to store
+
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// A cluster of observations summarized by their mean and number
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Implements the merging [t-digest](https://arxiv.org/abs/1902.04023) of
+/// Dunning and Ertl, a mergeable sketch of a stream estimating its
+/// quantiles with an accuracy that is relative to `min(τ, 1 - τ)`, so
+/// that the tails are estimated especially well
+///
+/// # Remarks
+///
+/// The observations are summarized by centroids, weighted means of
+/// neighbouring observations, with room for `5 * compression` observations
+/// waiting to be merged. Centroids are kept small near the tails by the
+/// scale function
+///
+/// ```text
+/// k(q) = δ / Z ln(q / (1 - q)), Z = 4 ln(n / δ) + 24
+/// ```
+///
+/// of Dunning and Ertl, where `δ` is the compression and `n` the number of
+/// observations: a centroid may only span quantiles whose `k` differ by at
+/// most one, so there are `O(δ)` centroids and the observations in the
+/// extreme tails stay single. A larger compression is more accurate and
+/// uses more memory; `100` is typical.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::streaming::TDigest;
+///
+/// let mut digest = TDigest::new(100.0).unwrap();
+/// for i in 0..10_000 {
+///     digest.add(i as f64);
+/// }
+/// assert!((digest.quantile(0.5) - 5000.0).abs() < 50.0);
+/// assert!((digest.quantile(0.99) - 9900.0).abs() < 10.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Constructs an empty t-digest with the given `compression`, the
+    /// parameter `δ` of the scale function
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `compression` is not finite or less than `1`
+    pub fn new(compression: f64) -> Result<TDigest> {
+        if !compression.is_finite() || compression < 1.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        })
+    }
+
+    /// Returns the compression
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Returns the number of observations
+    pub fn count(&self) -> f64 {
+        self.count
+    }
+
+    /// Returns the smallest observation, or `f64::NAN` if there is none
+    pub fn min(&self) -> f64 {
+        if self.count == 0.0 {
+            f64::NAN
+        } else {
+            self.min
+        }
+    }
+
+    /// Returns the largest observation, or `f64::NAN` if there is none
+    pub fn max(&self) -> f64 {
+        if self.count == 0.0 {
+            f64::NAN
+        } else {
+            self.max
+        }
+    }
+
+    /// Adds the observation `x`, in amortized `O(log δ)` time
+    ///
+    /// # Panics
+    ///
+    /// If `x` is `NaN`
+    pub fn add(&mut self, x: f64) {
+        if x.is_nan() {
+            panic!("cannot add NaN to a t-digest");
+        }
+        self.push(Centroid {
+            mean: x,
+            weight: 1.0,
+        });
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Adds all the observations summarized by `other`, so that the digest
+    /// summarizes the union of both streams
+    ///
+    /// # Remarks
+    ///
+    /// The centroids of `other` are merged as they are, so the result is
+    /// about as accurate as a digest that saw every observation, whatever
+    /// the compression of `other`
+    pub fn merge(&mut self, other: &TDigest) {
+        for &centroid in other.centroids.iter().chain(&other.buffer) {
+            self.push(centroid);
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Estimates the `tau`-th quantile, or returns `f64::NAN` if `tau` is
+    /// not in `[0, 1]` or there are no observations
+    ///
+    /// # Remarks
+    ///
+    /// The quantile is interpolated linearly between the means of
+    /// neighbouring centroids, each placed at the middle of its cumulative
+    /// weight, and between the extreme centroids and the smallest and
+    /// largest observations, which are exact
+    pub fn quantile(&self, tau: f64) -> f64 {
+        if !(0.0..=1.0).contains(&tau) || self.count == 0.0 {
+            return f64::NAN;
+        }
+        let merged;
+        let centroids = if self.buffer.is_empty() {
+            &self.centroids
+        } else {
+            merged = self.merged();
+            &merged
+        };
+        if tau == 0.0 {
+            return self.min;
+        }
+        if tau == 1.0 {
+            return self.max;
+        }
+        let index = tau * self.count;
+        let first = centroids[0];
+        if index < first.weight / 2.0 {
+            return self.min + index / (first.weight / 2.0) * (first.mean - self.min);
+        }
+        // the cumulative weight at the middle of the current centroid
+        let mut middle = first.weight / 2.0;
+        for pair in centroids.windows(2) {
+            let next = middle + (pair[0].weight + pair[1].weight) / 2.0;
+            if index < next {
+                let fraction = (index - middle) / (next - middle);
+                return pair[0].mean + fraction * (pair[1].mean - pair[0].mean);
+            }
+            middle = next;
+        }
+        let last = centroids[centroids.len() - 1];
+        let fraction = ((index - middle) / (last.weight / 2.0)).min(1.0);
+        last.mean + fraction * (self.max - last.mean)
+    }
+
+    /// Returns the number of centroids after merging the waiting
+    /// observations
+    pub fn centroid_count(&self) -> usize {
+        if self.buffer.is_empty() {
+            self.centroids.len()
+        } else {
+            self.merged().len()
+        }
+    }
+
+    fn push(&mut self, centroid: Centroid) {
+        self.buffer.push(centroid);
+        self.count += centroid.weight;
+        if self.buffer.len() as f64 >= 5.0 * self.compression {
+            self.centroids = self.merged();
+            self.buffer.clear();
+        }
+    }
+
+    /// Merges the centroids with the waiting observations in increasing
+    /// order of their means, combining neighbours while they span at most
+    /// one unit of the scale function
+    fn merged(&self) -> Vec<Centroid> {
+        let mut all: Vec<Centroid> = self.centroids.iter().chain(&self.buffer).copied().collect();
+        all.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+        let normalizer = 4.0 * (self.count / self.compression).max(1.0).ln() + 24.0;
+        let scale = |q: f64| {
+            let q = q.clamp(1e-15, 1.0 - 1e-15);
+            self.compression / normalizer * (q / (1.0 - q)).ln()
+        };
+        let mut merged = Vec::with_capacity(all.len().min(self.compression as usize * 2));
+        let mut current = all[0];
+        let mut below = 0.0;
+        for &next in &all[1..] {
+            let q_left = below / self.count;
+            let q_right = (below + current.weight + next.weight) / self.count;
+            if scale(q_right) - scale(q_left) <= 1.0 {
+                current.weight += next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / current.weight;
+            } else {
+                below += current.weight;
+                merged.push(current);
+                current = next;
+            }
+        }
+        merged.push(current);
+        merged
+    }
+}
+
+/// Implements the [P² algorithm](https://doi.org/10.1145/4372.4378) of Jain
+/// and Chlamtac, which estimates a single quantile of a stream in constant
+/// memory
+///
+/// # Remarks
+///
+/// Five markers track the smallest observation, the `τ / 2`, `τ` and
+/// `(1 + τ) / 2` quantiles and the largest observation. After every
+/// observation the middle markers move by at most one position towards
+/// their desired positions, adjusting their heights by a piecewise
+/// parabolic interpolation. Unlike the t-digest, only a single quantile
+/// fixed in advance can be estimated, and the estimates cannot be merged,
+/// but every observation takes constant time.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::streaming::PSquare;
+///
+/// let mut p95 = PSquare::new(0.95).unwrap();
+/// for i in 0..10_000 {
+///     p95.observe((i * 7919 % 10_000) as f64);
+/// }
+/// assert!((p95.quantile() - 9500.0).abs() < 50.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PSquare {
+    tau: f64,
+    count: u64,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl PSquare {
+    /// Constructs an estimator of the `tau`-th quantile
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `tau` is not in `(0, 1)`
+    pub fn new(tau: f64) -> Result<PSquare> {
+        if !(tau > 0.0 && tau < 1.0) {
+            return Err(StatsError::ArgIntervalExcl("tau", 0.0, 1.0));
+        }
+        Ok(PSquare {
+            tau,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * tau, 1.0 + 4.0 * tau, 3.0 + 2.0 * tau, 5.0],
+            increments: [0.0, tau / 2.0, tau, (1.0 + tau) / 2.0, 1.0],
+        })
+    }
+
+    /// Returns the estimated quantile level
+    pub fn tau(&self) -> f64 {
+        self.tau
+    }
+
+    /// Returns the number of observations
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds the observation `x`, in constant time
+    ///
+    /// # Panics
+    ///
+    /// If `x` is `NaN`
+    pub fn observe(&mut self, x: f64) {
+        if x.is_nan() {
+            panic!("cannot observe NaN");
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            }
+            return;
+        }
+        self.count += 1;
+
+        // the cell of the observation, extending the extreme markers
+        let cell = if x < self.heights[0] {
+            self.heights[0] = x;
+            0
+        } else if x >= self.heights[4] {
+            self.heights[4] = x;
+            3
+        } else {
+            (1..5).find(|&i| x < self.heights[i]).unwrap() - 1
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(&self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            if (offset >= 1.0 && self.positions[i + 1] - self.positions[i] > 1.0)
+                || (offset <= -1.0 && self.positions[i - 1] - self.positions[i] < -1.0)
+            {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] =
+                    if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                        parabolic
+                    } else {
+                        self.linear(i, step)
+                    };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Returns the estimated quantile, the exact sample quantile of the
+    /// nearest rank for fewer than five observations, or `f64::NAN` if there
+    /// are none
+    pub fn quantile(&self) -> f64 {
+        match self.count {
+            0 => f64::NAN,
+            1..=4 => {
+                let mut sorted = self.heights;
+                let sorted = &mut sorted[..self.count as usize];
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                sorted[((self.count - 1) as f64 * self.tau).round() as usize]
+            }
+            _ => self.heights[2],
+        }
+    }
+
+    /// The piecewise parabolic prediction of the height of marker `i`
+    /// moved by `step`
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    /// The linear prediction of the height of marker `i` moved by `step`
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i]
+            + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+/// Maintains a uniform random sample without replacement of fixed size
+/// `k` from a stream of unknown length, using
+/// [Algorithm L](https://doi.org/10.1145/198429.198435) of Li
+///
+/// # Remarks
+///
+/// After `n >= k` items have been added, every subset of `k` of them is
+/// equally likely to be the sample, and while `n < k` the sample holds
+/// every item. Algorithm L draws the number of items to skip before the
+/// next replacement instead of a random number per item, so that adding
+/// `n` items uses `O(k (1 + ln(n / k)))` random numbers. The order of the
+/// sample is not random.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::rng_util::SplitMix64;
+/// use statrs::statistics::streaming::ReservoirSampler;
+///
+/// let mut sampler = ReservoirSampler::new(10, SplitMix64::new(42)).unwrap();
+/// for i in 0..100_000 {
+///     sampler.add(i);
+/// }
+/// assert_eq!(sampler.count(), 100_000);
+/// assert_eq!(sampler.samples().len(), 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReservoirSampler<T, R: Rng> {
+    capacity: usize,
+    rng: R,
+    reservoir: Vec<T>,
+    count: u64,
+    /// The largest of `k` uniforms, whose distribution is that of the
+    /// threshold for an item to enter the sample
+    w: f64,
+    /// The index of the next item to enter the sample, once it is full
+    next: u64,
+}
+
+impl<T, R: Rng> ReservoirSampler<T, R> {
+    /// Constructs an empty sampler of `k` items drawing random numbers
+    /// from `rng`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k` is zero
+    pub fn new(k: usize, rng: R) -> Result<ReservoirSampler<T, R>> {
+        if k == 0 {
+            return Err(StatsError::ArgMustBePositive("k"));
+        }
+        Ok(ReservoirSampler {
+            capacity: k,
+            rng,
+            reservoir: Vec::with_capacity(k),
+            count: 0,
+            w: 1.0,
+            next: k as u64 - 1,
+        })
+    }
+
+    /// Returns the size `k` of the sample
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of items added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sample, which holds every item added while there are at
+    /// most `k` of them
+    pub fn samples(&self) -> &[T] {
+        &self.reservoir
+    }
+
+    /// Consumes the sampler, returning the sample
+    pub fn into_samples(self) -> Vec<T> {
+        self.reservoir
+    }
+
+    /// Adds `item` to the stream, in constant time
+    pub fn add(&mut self, item: T) {
+        let index = self.count;
+        self.count += 1;
+        if index < self.capacity as u64 {
+            self.reservoir.push(item);
+            if self.reservoir.len() == self.capacity {
+                self.w = (self.uniform().ln() / self.capacity as f64).exp();
+                self.skip();
+            }
+        } else if index == self.next {
+            let slot = self.rng.gen_range(0..self.capacity);
+            self.reservoir[slot] = item;
+            self.w *= (self.uniform().ln() / self.capacity as f64).exp();
+            self.skip();
+        }
+    }
+
+    /// Moves the index of the next item to enter the sample past a
+    /// geometric number of items with success probability `w`
+    fn skip(&mut self) {
+        let gap = (self.uniform().ln() / (-self.w).ln_1p()).floor();
+        self.next = self.next.saturating_add(gap as u64 + 1);
+    }
+
+    /// Returns a uniform in `(0, 1]`, whose logarithm is finite
+    fn uniform(&mut self) -> f64 {
+        1.0 - self.rng.gen::<f64>()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{ContinuousCDF, Normal};
+    use crate::rng_util::SplitMix64;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const TAUS: [f64; 11] = [0.0001, 0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999, 0.9999];
+
+    /// Checks the quantile estimates by the exact proportion of the sorted
+    /// sample below them, within `tolerance` times `min(τ, 1 - τ)` plus four
+    /// observations
+    fn check_ranks(digest: &TDigest, sorted: &[f64], tolerance: f64) {
+        let n = sorted.len() as f64;
+        for &tau in &TAUS {
+            let estimate = digest.quantile(tau);
+            let rank = sorted.partition_point(|&x| x < estimate) as f64 / n;
+            let allowed = tolerance * tau.min(1.0 - tau) + 4.0 / n;
+            assert!((rank - tau).abs() <= allowed, "tau = {}: rank {} of {}", tau, rank, estimate);
+        }
+    }
+
+    fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        (0..n).map(|_| normal.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_create() {
+        assert!(TDigest::new(100.0).is_ok());
+        assert!(TDigest::new(0.5).is_err());
+        assert!(TDigest::new(f64::NAN).is_err());
+        assert!(TDigest::new(f64::INFINITY).is_err());
+        let digest = TDigest::new(50.0).unwrap();
+        assert_eq!(digest.compression(), 50.0);
+        assert_eq!(digest.count(), 0.0);
+        assert!(digest.quantile(0.5).is_nan());
+        assert!(digest.min().is_nan() && digest.max().is_nan());
+    }
+
+    #[test]
+    fn test_small() {
+        let mut digest = TDigest::new(100.0).unwrap();
+        digest.add(5.0);
+        assert_eq!(digest.quantile(0.0), 5.0);
+        assert_eq!(digest.quantile(0.3), 5.0);
+        assert_eq!(digest.quantile(1.0), 5.0);
+        for &x in &[1.0, 3.0, 2.0, 4.0] {
+            digest.add(x);
+        }
+        // few observations are kept exactly
+        assert_eq!(digest.centroid_count(), 5);
+        assert_eq!(digest.quantile(0.5), 3.0);
+        assert_eq!(digest.quantile(0.0), 1.0);
+        assert_eq!(digest.quantile(1.0), 5.0);
+        assert_eq!(digest.quantile(0.3), 2.0);
+        assert!(digest.quantile(1.5).is_nan());
+        assert!(digest.quantile(-0.1).is_nan());
+    }
+
+    #[test]
+    fn test_accuracy() {
+        let mut data = normal_sample(200_000, 2870);
+        let mut digest = TDigest::new(100.0).unwrap();
+        for &x in &data {
+            digest.add(x);
+        }
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(digest.count(), 200_000.0);
+        assert_eq!(digest.min(), data[0]);
+        assert_eq!(digest.max(), data[199_999]);
+        check_ranks(&digest, &data, 0.03);
+        // the estimates are also close in value to the population quantiles
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        for &tau in &[0.01, 0.5, 0.99] {
+            assert!((digest.quantile(tau) - normal.inverse_cdf(tau)).abs() < 0.05);
+        }
+        // the memory stays bounded
+        assert!(digest.centroid_count() <= 100);
+        assert!(digest.centroids.len() + digest.buffer.len() <= 600);
+    }
+
+    #[test]
+    fn test_sorted_stream() {
+        // the worst case for naive clustering
+        let mut digest = TDigest::new(100.0).unwrap();
+        let data: Vec<f64> = (0..100_000).map(|i| i as f64).collect();
+        for &x in &data {
+            digest.add(x);
+        }
+        check_ranks(&digest, &data, 0.03);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut data = normal_sample(200_000, 2871);
+        let mut whole = TDigest::new(100.0).unwrap();
+        let mut merged = TDigest::new(100.0).unwrap();
+        for chunk in data.chunks(20_000) {
+            let mut part = TDigest::new(100.0).unwrap();
+            for &x in chunk {
+                part.add(x);
+                whole.add(x);
+            }
+            merged.merge(&part);
+        }
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(merged.count(), whole.count());
+        assert_eq!((merged.min(), merged.max()), (data[0], data[199_999]));
+        check_ranks(&merged, &data, 0.03);
+        // merging an empty digest changes nothing
+        let before = merged.quantile(0.3);
+        merged.merge(&TDigest::new(10.0).unwrap());
+        assert_eq!(merged.quantile(0.3), before);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_nan() {
+        TDigest::new(100.0).unwrap().add(f64::NAN);
+    }
+    #[test]
+    fn test_p_square_create() {
+        assert!(PSquare::new(0.0).is_err());
+        assert!(PSquare::new(1.0).is_err());
+        assert!(PSquare::new(f64::NAN).is_err());
+        let estimator = PSquare::new(0.9).unwrap();
+        assert_eq!(estimator.tau(), 0.9);
+        assert_eq!(estimator.count(), 0);
+        assert!(estimator.quantile().is_nan());
+    }
+
+    #[test]
+    fn test_p_square_small() {
+        let mut median = PSquare::new(0.5).unwrap();
+        for &x in &[4.0, 1.0, 3.0] {
+            median.observe(x);
+        }
+        assert_eq!(median.quantile(), 3.0);
+        for &x in &[2.0, 5.0] {
+            median.observe(x);
+        }
+        assert_eq!(median.count(), 5);
+        assert_eq!(median.quantile(), 3.0);
+        median.observe(3.5);
+        assert!(median.quantile() >= 3.0 && median.quantile() <= 3.5);
+    }
+
+    #[test]
+    fn test_p_square_accuracy() {
+        let mut data = normal_sample(100_000, 2880);
+        let mut median = PSquare::new(0.5).unwrap();
+        let mut p95 = PSquare::new(0.95).unwrap();
+        for &x in &data {
+            median.observe(x);
+            p95.observe(x);
+        }
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(median.count(), 100_000);
+        assert_almost_eq!(median.quantile(), data[50_000], 0.01);
+        assert_almost_eq!(p95.quantile(), data[95_000], 0.02);
+        // a skewed stream, in the order of arrival and sorted
+        let mut rng = StdRng::seed_from_u64(2881);
+        let exponential = crate::distribution::Exp::new(1.0).unwrap();
+        let mut data: Vec<f64> = (0..100_000).map(|_| exponential.sample(&mut rng)).collect();
+        let mut p95 = PSquare::new(0.95).unwrap();
+        for &x in &data {
+            p95.observe(x);
+        }
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_almost_eq!(p95.quantile(), data[95_000], 0.03);
+        // sorted streams are the worst case, but the estimate is still near
+        // the right rank
+        let mut p95 = PSquare::new(0.95).unwrap();
+        for &x in &data {
+            p95.observe(x);
+        }
+        let rank = data.partition_point(|&x| x < p95.quantile()) as f64 / 100_000.0;
+        assert_almost_eq!(rank, 0.95, 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_p_square_nan() {
+        PSquare::new(0.5).unwrap().observe(f64::NAN);
+    }
+    #[test]
+    fn test_reservoir_short_stream() {
+        assert!(ReservoirSampler::<u32, _>::new(0, SplitMix64::new(1)).is_err());
+        let mut sampler = ReservoirSampler::new(5, SplitMix64::new(2890)).unwrap();
+        assert_eq!(sampler.capacity(), 5);
+        assert!(sampler.samples().is_empty());
+        for i in 0..5 {
+            sampler.add(i);
+        }
+        assert_eq!(sampler.samples(), &[0, 1, 2, 3, 4]);
+        sampler.add(5);
+        assert_eq!(sampler.count(), 6);
+        let mut sample = sampler.into_samples();
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 5);
+        assert!(sample.iter().all(|&x| x < 6));
+    }
+
+    #[test]
+    fn test_reservoir_uniform() {
+        // every item is in the sample with probability k / n
+        for &(k, n, runs) in &[(5, 20, 20_000), (10, 1000, 5000)] {
+            let mut hits = vec![0u32; n];
+            for run in 0..runs {
+                let mut sampler = ReservoirSampler::new(k, SplitMix64::new(2891 + run as u64)).unwrap();
+                for i in 0..n {
+                    sampler.add(i);
+                }
+                let sample = sampler.samples();
+                assert_eq!(sample.len(), k);
+                for &i in sample {
+                    hits[i] += 1;
+                }
+            }
+            let expected = (runs * k) as f64 / n as f64;
+            let sd = (expected * (1.0 - k as f64 / n as f64)).sqrt();
+            let mut chi_squared = 0.0;
+            for &h in &hits {
+                assert!((h as f64 - expected).abs() < 5.0 * sd, "{} hits, {} expected", h, expected);
+                chi_squared += (h as f64 - expected).powi(2) / (sd * sd);
+            }
+            // within five standard deviations of n for n - 1 degrees of freedom
+            assert!((chi_squared - n as f64).abs() < 5.0 * (2.0 * n as f64).sqrt());
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Provides estimators for weighted samples such as survey data, where each
+//! observation stands for `w` members of the population
+//!
+//! All variances assume single-stage sampling with replacement, the
+//! convention of R's `survey` package for a design declared with
+//! `ids = ~1` and sampling weights. Weights must be positive and finite.
+
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A weighted mean together with its linearization-based standard error
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WeightedMeanResult {
+    /// The weighted mean `Σ w_i y_i / Σ w_i`
+    pub mean: f64,
+    /// The standard error of the weighted mean
+    pub std_error: f64,
+    /// Kish's effective sample size of the weights
+    pub effective_sample_size: f64,
+}
+
+/// Checks that `weights` is non-empty with positive, finite entries
+fn check_weights(weights: &[f64]) -> Result<()> {
+    if weights.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    if weights.iter().any(|w| !w.is_finite() || *w <= 0.0) {
+        return Err(StatsError::ArgMustBePositive("weights"));
+    }
+    Ok(())
+}
+
+/// Checks the weights and that `data` is finite with one value per weight
+fn check_data(data: &[f64], weights: &[f64]) -> Result<()> {
+    check_weights(weights)?;
+    if data.len() != weights.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+    Ok(())
+}
+
+/// Checks that there are at least two observations to estimate a variance
+fn check_variance(data: &[f64]) -> Result<()> {
+    if data.len() < 2 {
+        Err(StatsError::SpecialCase(
+            "at least two observations are needed to estimate a variance",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes Kish's effective sample size of `weights`, the size of an
+/// unweighted sample giving the same precision for a mean
+///
+/// # Formula
+///
+/// ```text
+/// (Σ w_i)^2 / Σ w_i^2
+/// ```
+///
+/// # Remarks
+///
+/// The weights are scaled by their maximum first, so that equal weights
+/// give exactly `n`
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty or contains a weight that is not
+/// positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survey::effective_sample_size;
+///
+/// assert_eq!(effective_sample_size(&[0.1, 0.1, 0.1]).unwrap(), 3.0);
+/// assert_eq!(effective_sample_size(&[4.0, 4.0, 2.0, 2.0]).unwrap(), 3.6);
+/// ```
+pub fn effective_sample_size(weights: &[f64]) -> Result<f64> {
+    check_weights(weights)?;
+    let max = weights.iter().fold(0.0, |m: f64, &w| m.max(w));
+    let (sum, sum_sq) = weights.iter().fold((0.0, 0.0), |(s, q), &w| {
+        let w = w / max;
+        (s + w, q + w * w)
+    });
+    Ok(sum * sum / sum_sq)
+}
+
+/// Computes Kish's design effect due to unequal weighting, the factor by
+/// which the weights inflate the variance of a mean
+///
+/// # Formula
+///
+/// ```text
+/// n Σ w_i^2 / (Σ w_i)^2
+/// ```
+///
+/// i.e. `n` divided by the effective sample size
+///
+/// # Errors
+///
+/// Returns an error if `weights` is empty or contains a weight that is not
+/// positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survey::design_effect;
+///
+/// assert_eq!(design_effect(&[2.0, 2.0, 2.0]).unwrap(), 1.0);
+/// assert_eq!(design_effect(&[1.0, 3.0]).unwrap(), 1.25);
+/// ```
+pub fn design_effect(weights: &[f64]) -> Result<f64> {
+    Ok(weights.len() as f64 / effective_sample_size(weights)?)
+}
+
+/// Computes the weighted total `Σ w_i y_i` of `data`, the estimate of the
+/// population total
+///
+/// # Errors
+///
+/// Returns an error if the weights are invalid, if `data` and `weights`
+/// differ in length, or if `data` contains a non-finite value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survey::weighted_total;
+///
+/// assert_eq!(weighted_total(&[1.0, 2.0], &[10.0, 5.0]).unwrap(), 20.0);
+/// ```
+pub fn weighted_total(data: &[f64], weights: &[f64]) -> Result<f64> {
+    check_data(data, weights)?;
+    Ok(data.iter().zip(weights).map(|(y, w)| w * y).sum())
+}
+
+/// Computes the variance of the weighted total of `data` under sampling
+/// with replacement
+///
+/// # Formula
+///
+/// ```text
+/// n / (n - 1) Σ (w_i y_i - T / n)^2
+/// ```
+///
+/// where `T` is the weighted total and `n` the number of observations
+///
+/// # Errors
+///
+/// Returns an error if the weights are invalid, if `data` and `weights`
+/// differ in length, if `data` contains a non-finite value, or if there
+/// are fewer than two observations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survey::weighted_total_variance;
+///
+/// // the products w_i y_i are 10 and 10, so the total is known exactly
+/// assert_eq!(weighted_total_variance(&[1.0, 2.0], &[10.0, 5.0]).unwrap(), 0.0);
+/// ```
+pub fn weighted_total_variance(data: &[f64], weights: &[f64]) -> Result<f64> {
+    let total = weighted_total(data, weights)?;
+    check_variance(data)?;
+    let n = data.len() as f64;
+    let ss: f64 = data
+        .iter()
+        .zip(weights)
+        .map(|(y, w)| {
+            let r = w * y - total / n;
+            r * r
+        })
+        .sum();
+    Ok(n / (n - 1.0) * ss)
+}
+
+/// Computes the weighted mean of `data` with its standard error from the
+/// linearization (Taylor series) of the ratio `Σ w_i y_i / Σ w_i` under
+/// sampling with replacement, as reported by R's `survey::svymean`
+///
+/// # Formula
+///
+/// ```text
+/// z_i = w_i (y_i - ȳ_w) / Σ w_j
+/// SE = sqrt(n / (n - 1) Σ z_i^2)
+/// ```
+///
+/// where `ȳ_w` is the weighted mean and `n` the number of observations
+///
+/// # Errors
+///
+/// Returns an error if the weights are invalid, if `data` and `weights`
+/// differ in length, if `data` contains a non-finite value, or if there
+/// are fewer than two observations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survey::weighted_mean_with_se;
+///
+/// let r = weighted_mean_with_se(&[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0, 1.0, 1.0]).unwrap();
+/// assert_eq!(r.mean, 2.5);
+/// // with equal weights the standard error is s / sqrt(n)
+/// assert!((r.std_error - (5.0f64 / 12.0).sqrt()).abs() < 1e-15);
+/// assert_eq!(r.effective_sample_size, 4.0);
+/// ```
+pub fn weighted_mean_with_se(data: &[f64], weights: &[f64]) -> Result<WeightedMeanResult> {
+    check_data(data, weights)?;
+    check_variance(data)?;
+    let sum_w: f64 = weights.iter().sum();
+    let mean = data.iter().zip(weights).map(|(y, w)| w * y).sum::<f64>() / sum_w;
+    let ss: f64 = data
+        .iter()
+        .zip(weights)
+        .map(|(y, w)| {
+            let z = w * (y - mean) / sum_w;
+            z * z
+        })
+        .sum();
+    let n = data.len() as f64;
+    Ok(WeightedMeanResult {
+        mean,
+        std_error: (n / (n - 1.0) * ss).sqrt(),
+        effective_sample_size: effective_sample_size(weights)?,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::Statistics;
+
+    const DATA: [f64; 6] = [3.2, 4.1, 5.6, 2.8, 6.3, 4.9];
+    const WEIGHTS: [f64; 6] = [1.5, 2.0, 1.0, 3.0, 2.5, 0.5];
+
+    #[test]
+    fn test_effective_sample_size() {
+        // (Σw)^2 / Σw^2 = 10.5^2 / 22.75 = 63 / 13
+        assert_almost_eq!(effective_sample_size(&WEIGHTS).unwrap(), 63.0 / 13.0, 1e-14);
+        assert_almost_eq!(design_effect(&WEIGHTS).unwrap(), 26.0 / 21.0, 1e-15);
+        for &w in &[0.1, 1.0 / 3.0, 7.3, 1e300] {
+            for n in 1..20 {
+                let weights = vec![w; n];
+                assert_eq!(effective_sample_size(&weights).unwrap(), n as f64);
+                assert_eq!(design_effect(&weights).unwrap(), 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_total() {
+        assert_almost_eq!(weighted_total(&DATA, &WEIGHTS).unwrap(), 45.2, 1e-13);
+        assert_almost_eq!(weighted_total_variance(&DATA, &WEIGHTS).unwrap(), 126.91, 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_mean_with_se() {
+        // the with-replacement linearization variance of svymean for
+        // svydesign(ids = ~1, weights = ~w), evaluated in exact arithmetic
+        let r = weighted_mean_with_se(&DATA, &WEIGHTS).unwrap();
+        assert_almost_eq!(r.mean, 4.3047619047619048, 1e-15);
+        assert_almost_eq!(r.std_error, 0.73726776819059552, 1e-15);
+        assert_almost_eq!(r.effective_sample_size, 63.0 / 13.0, 1e-14);
+    }
+
+    #[test]
+    fn test_equal_weights_reduce_to_unweighted() {
+        let r = weighted_mean_with_se(&DATA, &[2.0; 6]).unwrap();
+        assert_almost_eq!(r.mean, DATA.mean(), 1e-15);
+        assert_almost_eq!(r.std_error, DATA.std_dev() / 6f64.sqrt(), 1e-15);
+        assert_eq!(r.effective_sample_size, 6.0);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(effective_sample_size(&[]).is_err());
+        assert!(effective_sample_size(&[1.0, 0.0]).is_err());
+        assert!(design_effect(&[1.0, -2.0]).is_err());
+        assert!(effective_sample_size(&[1.0, f64::NAN]).is_err());
+        assert!(effective_sample_size(&[1.0, f64::INFINITY]).is_err());
+        assert!(weighted_total(&[1.0], &[1.0, 2.0]).is_err());
+        assert!(weighted_total(&[f64::NAN], &[1.0]).is_err());
+        assert!(weighted_total_variance(&[1.0], &[1.0]).is_err());
+        assert!(weighted_mean_with_se(&[1.0], &[1.0]).is_err());
+        assert!(weighted_mean_with_se(&[1.0, 2.0], &[1.0, 0.0]).is_err());
+    }
+}
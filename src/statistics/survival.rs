@@ -0,0 +1,579 @@
+//! Provides nonparametric estimators of the survival function and the
+//! cumulative hazard from right-censored lifetimes
+//!
+//! Every observation is a time with a flag that is `true` if the event,
+//! such as a failure or a death, was observed at that time and `false` if
+//! the observation was censored, meaning the event had not happened yet
+//! when follow-up ended. Censorings tied with events at the same time are
+//! counted as still at risk at that time, as if the events came first.
+
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A row of the risk table of a survival estimator, summarizing all the
+/// observations at one distinct time
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RiskTableRow {
+    /// The time of the observations
+    pub time: f64,
+    /// The number of observations with a time at least `time`
+    pub at_risk: u64,
+    /// The number of events observed at `time`
+    pub events: u64,
+    /// The number of observations censored at `time`
+    pub censored: u64,
+    /// The estimate just after `time`, the survival probability for
+    /// `KaplanMeier` and the cumulative hazard for `NelsonAalen`
+    pub estimate: f64,
+    /// The standard error of `estimate`
+    pub std_err: f64,
+}
+
+/// Counts the observations at risk, the events and the censorings at every
+/// distinct time of `times`, leaving the estimates at zero
+fn risk_table(times: &[f64], events: &[bool]) -> Result<Vec<RiskTableRow>> {
+    if times.len() != events.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if times.is_empty() {
+        return Err(StatsError::SpecialCase("times must not be empty"));
+    }
+    if times.iter().any(|t| !t.is_finite()) {
+        return Err(StatsError::SpecialCase("times must be finite"));
+    }
+    let mut order: Vec<usize> = (0..times.len()).collect();
+    order.sort_by(|&i, &j| times[i].partial_cmp(&times[j]).unwrap());
+    let mut rows: Vec<RiskTableRow> = Vec::new();
+    let mut at_risk = times.len() as u64;
+    for i in order {
+        let new_time = rows.last().map(|row| row.time) != Some(times[i]);
+        if new_time {
+            if let Some(row) = rows.last() {
+                at_risk -= row.events + row.censored;
+            }
+            rows.push(RiskTableRow {
+                time: times[i],
+                at_risk,
+                events: 0,
+                censored: 0,
+                estimate: 0.0,
+                std_err: 0.0,
+            });
+        }
+        let row = rows.last_mut().unwrap();
+        if events[i] {
+            row.events += 1;
+        } else {
+            row.censored += 1;
+        }
+    }
+    Ok(rows)
+}
+
+/// Returns the index of the last row with a time at most `t`, or `None` if
+/// `t` is before the first time
+fn row_index(rows: &[RiskTableRow], t: f64) -> Option<usize> {
+    rows.partition_point(|row| row.time <= t).checked_sub(1)
+}
+
+/// Writes the risk table with a header naming the estimate `name`
+fn write_table(
+    f: &mut std::fmt::Formatter<'_>,
+    rows: &[RiskTableRow],
+    name: &str,
+) -> std::fmt::Result {
+    writeln!(
+        f,
+        "{:>10} {:>8} {:>8} {:>8} {:>10} {:>10}",
+        "time", "at_risk", "events", "censored", name, "std_err"
+    )?;
+    for row in rows {
+        writeln!(
+            f,
+            "{:>10} {:>8} {:>8} {:>8} {:>10.4} {:>10.4}",
+            row.time, row.at_risk, row.events, row.censored, row.estimate, row.std_err
+        )?;
+    }
+    Ok(())
+}
+
+/// The [Kaplan–Meier](https://en.wikipedia.org/wiki/Kaplan%E2%80%93Meier_estimator)
+/// estimate of the survival function from right-censored lifetimes, with
+/// Greenwood standard errors and log-log confidence intervals as in R's
+/// `survival::survfit(..., conf.type = "log-log")`
+///
+/// The estimate is a step function, right-continuous with a drop at every
+/// time with events.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survival::KaplanMeier;
+///
+/// let times = [9.0, 13.0, 13.0, 18.0, 23.0, 28.0, 31.0, 34.0, 45.0, 48.0, 161.0];
+/// let events = [true, true, false, true, true, false, true, true, false, true, false];
+/// let km = KaplanMeier::fit(&times, &events).unwrap();
+/// assert!((km.survival_at(20.0) - 63.0 / 88.0).abs() < 1e-15);
+/// assert_eq!(km.median_survival(), Some(31.0));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct KaplanMeier {
+    rows: Vec<RiskTableRow>,
+    greenwood: Vec<f64>,
+}
+
+impl KaplanMeier {
+    /// Estimates the survival function from the observed `times` and the
+    /// `events` flags, `true` for an observed event and `false` for a
+    /// censoring
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// S(t) = Π_(t_i ≤ t) (1 - d_i / n_i)
+    /// Var(S(t)) = S(t)^2 Σ_(t_i ≤ t) d_i / (n_i (n_i - d_i))
+    /// ```
+    ///
+    /// where `d_i` is the number of events at time `t_i` and `n_i` the
+    /// number at risk
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `times` and `events` differ in length, if they
+    /// are empty, or if a time is not finite
+    pub fn fit(times: &[f64], events: &[bool]) -> Result<KaplanMeier> {
+        let mut rows = risk_table(times, events)?;
+        let mut greenwood = Vec::with_capacity(rows.len());
+        let (mut survival, mut sum) = (1.0, 0.0);
+        for row in rows.iter_mut() {
+            let (n, d) = (row.at_risk as f64, row.events as f64);
+            if row.events > 0 {
+                survival *= 1.0 - d / n;
+                sum += d / (n * (n - d));
+            }
+            row.estimate = survival;
+            row.std_err = if survival > 0.0 {
+                survival * sum.sqrt()
+            } else {
+                f64::NAN
+            };
+            greenwood.push(sum);
+        }
+        Ok(KaplanMeier { rows, greenwood })
+    }
+
+    /// Returns the rows of the risk table, one for every distinct time
+    pub fn risk_table(&self) -> &[RiskTableRow] {
+        &self.rows
+    }
+
+    /// Returns an iterator over the rows of the risk table
+    pub fn iter(&self) -> std::slice::Iter<'_, RiskTableRow> {
+        self.rows.iter()
+    }
+
+    /// Returns the estimated probability of surviving beyond `t`, which is
+    /// `1` before the first time
+    ///
+    /// # Remarks
+    ///
+    /// After the last time the estimate stays at its last value, although
+    /// it is only supported by the data up to the last time
+    pub fn survival_at(&self, t: f64) -> f64 {
+        row_index(&self.rows, t).map_or(1.0, |i| self.rows[i].estimate)
+    }
+
+    /// Returns the Greenwood standard error of `survival_at(t)`, which is
+    /// `0` before the first event and `f64::NAN` once the estimate reaches
+    /// zero
+    pub fn std_err_at(&self, t: f64) -> f64 {
+        row_index(&self.rows, t).map_or(0.0, |i| self.rows[i].std_err)
+    }
+
+    /// Returns the `level` confidence interval for the probability of
+    /// surviving beyond `t`, based on the asymptotic normality of
+    /// `ln(-ln S(t))`
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// [S(t)^exp(z σ), S(t)^exp(-z σ)]
+    /// σ = sqrt(Σ_(t_i ≤ t) d_i / (n_i (n_i - d_i))) / |ln S(t)|
+    /// ```
+    ///
+    /// where `z` is the `(1 + level) / 2` quantile of the standard normal
+    /// distribution
+    ///
+    /// # Remarks
+    ///
+    /// Unlike the plain interval `S(t) ± z se`, the bounds always lie in
+    /// `[0, 1]`. The interval is `[1, 1]` before the first event and
+    /// `[0, 0]` once the estimate reaches zero, where it is degenerate.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::survival::KaplanMeier;
+    ///
+    /// let km = KaplanMeier::fit(&[2.0, 3.0, 3.0, 5.0, 8.0], &[true; 5]).unwrap();
+    /// let (lower, upper) = km.confidence_interval_at(4.0, 0.95);
+    /// assert!(lower < km.survival_at(4.0) && km.survival_at(4.0) < upper);
+    /// ```
+    pub fn confidence_interval_at(&self, t: f64, level: f64) -> (f64, f64) {
+        let z = critical_value(level);
+        match row_index(&self.rows, t) {
+            Some(i) => self.band(i, z),
+            None => (1.0, 1.0),
+        }
+    }
+
+    /// Returns the log-log confidence interval of the estimate at row `i`
+    /// for the normal critical value `z`
+    fn band(&self, i: usize, z: f64) -> (f64, f64) {
+        let survival = self.rows[i].estimate;
+        if survival <= 0.0 || survival >= 1.0 {
+            return (survival, survival);
+        }
+        let sigma = self.greenwood[i].sqrt() / survival.ln().abs();
+        (
+            survival.powf((z * sigma).exp()),
+            survival.powf((-z * sigma).exp()),
+        )
+    }
+
+    /// Returns the median survival time, the first time at which the
+    /// estimate is at most `0.5`, or `None` if it stays above `0.5`
+    ///
+    /// # Remarks
+    ///
+    /// As in R's `survival` package, if the estimate is exactly `0.5` up to
+    /// a later drop, the median is the midpoint between the time it reaches
+    /// `0.5` and the time of that drop
+    pub fn median_survival(&self) -> Option<f64> {
+        let curve: Vec<f64> = self.rows.iter().map(|row| row.estimate).collect();
+        self.median_of(&curve)
+    }
+
+    /// Returns the `level` confidence interval for the median survival
+    /// time, from the times at which the lower and upper bounds of the
+    /// pointwise confidence intervals fall to `0.5`, with `None` for a bound
+    /// that stays above `0.5`
+    ///
+    /// # Panics
+    ///
+    /// If `level` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::survival::KaplanMeier;
+    ///
+    /// let times = [5.0, 5.0, 8.0, 8.0, 12.0, 16.0, 23.0, 27.0, 30.0, 33.0, 43.0, 45.0];
+    /// let mut events = [true; 12];
+    /// events[5] = false;
+    /// let km = KaplanMeier::fit(&times, &events).unwrap();
+    /// assert_eq!(km.median_survival(), Some(23.0));
+    /// assert_eq!(km.median_confidence_interval(0.95), (Some(5.0), Some(33.0)));
+    /// ```
+    pub fn median_confidence_interval(&self, level: f64) -> (Option<f64>, Option<f64>) {
+        let z = critical_value(level);
+        let (lower, upper): (Vec<f64>, Vec<f64>) =
+            (0..self.rows.len()).map(|i| self.band(i, z)).unzip();
+        (self.median_of(&lower), self.median_of(&upper))
+    }
+
+    /// Returns the first time at which the step function with values
+    /// `curve` on the rows is at most `0.5`
+    fn median_of(&self, curve: &[f64]) -> Option<f64> {
+        let i = curve.iter().position(|&s| s <= 0.5)?;
+        let time = self.rows[i].time;
+        if (curve[i] - 0.5).abs() > 1e-12 {
+            return Some(time);
+        }
+        match (i + 1..curve.len()).find(|&j| curve[j] < curve[i]) {
+            Some(j) => Some(0.5 * (time + self.rows[j].time)),
+            None => Some(time),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a KaplanMeier {
+    type Item = &'a RiskTableRow;
+    type IntoIter = std::slice::Iter<'a, RiskTableRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Display for KaplanMeier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_table(f, &self.rows, "survival")
+    }
+}
+
+/// The [Nelson–Aalen](https://en.wikipedia.org/wiki/Nelson%E2%80%93Aalen_estimator)
+/// estimate of the cumulative hazard from right-censored lifetimes, with
+/// the standard errors of R's `survival::survfit`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::survival::NelsonAalen;
+///
+/// let na = NelsonAalen::fit(&[2.0, 3.0, 3.0, 5.0], &[true, true, false, true]).unwrap();
+/// assert_eq!(na.cumulative_hazard_at(4.0), 0.25 + 1.0 / 3.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct NelsonAalen {
+    rows: Vec<RiskTableRow>,
+}
+
+impl NelsonAalen {
+    /// Estimates the cumulative hazard from the observed `times` and the
+    /// `events` flags, `true` for an observed event and `false` for a
+    /// censoring
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// H(t) = Σ_(t_i ≤ t) d_i / n_i
+    /// Var(H(t)) = Σ_(t_i ≤ t) d_i / n_i^2
+    /// ```
+    ///
+    /// where `d_i` is the number of events at time `t_i` and `n_i` the
+    /// number at risk
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `times` and `events` differ in length, if they
+    /// are empty, or if a time is not finite
+    pub fn fit(times: &[f64], events: &[bool]) -> Result<NelsonAalen> {
+        let mut rows = risk_table(times, events)?;
+        let (mut hazard, mut variance) = (0.0, 0.0);
+        for row in rows.iter_mut() {
+            let (n, d) = (row.at_risk as f64, row.events as f64);
+            hazard += d / n;
+            variance += d / (n * n);
+            row.estimate = hazard;
+            row.std_err = variance.sqrt();
+        }
+        Ok(NelsonAalen { rows })
+    }
+
+    /// Returns the rows of the risk table, one for every distinct time
+    pub fn risk_table(&self) -> &[RiskTableRow] {
+        &self.rows
+    }
+
+    /// Returns an iterator over the rows of the risk table
+    pub fn iter(&self) -> std::slice::Iter<'_, RiskTableRow> {
+        self.rows.iter()
+    }
+
+    /// Returns the estimated cumulative hazard up to `t`, which is `0`
+    /// before the first time
+    pub fn cumulative_hazard_at(&self, t: f64) -> f64 {
+        row_index(&self.rows, t).map_or(0.0, |i| self.rows[i].estimate)
+    }
+
+    /// Returns the standard error of `cumulative_hazard_at(t)`
+    pub fn std_err_at(&self, t: f64) -> f64 {
+        row_index(&self.rows, t).map_or(0.0, |i| self.rows[i].std_err)
+    }
+
+    /// Returns the Fleming–Harrington estimate `exp(-H(t))` of the
+    /// probability of surviving beyond `t`, which is always above the
+    /// Kaplan–Meier estimate
+    pub fn survival_at(&self, t: f64) -> f64 {
+        (-self.cumulative_hazard_at(t)).exp()
+    }
+}
+
+impl<'a> IntoIterator for &'a NelsonAalen {
+    type Item = &'a RiskTableRow;
+    type IntoIter = std::slice::Iter<'a, RiskTableRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Display for NelsonAalen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_table(f, &self.rows, "cumhaz")
+    }
+}
+
+/// Returns the two-sided standard normal critical value for `level`
+fn critical_value(level: f64) -> f64 {
+    if !(level > 0.0 && level < 1.0) {
+        panic!("level must be in (0, 1), was {}", level);
+    }
+    Normal::new(0.0, 1.0)
+        .unwrap()
+        .inverse_cdf(0.5 + 0.5 * level)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the acute myelogenous leukemia data `aml` of R's survival package
+    const MAINTAINED: [f64; 11] = [9.0, 13.0, 13.0, 18.0, 23.0, 28.0, 31.0, 34.0, 45.0, 48.0, 161.0];
+    const MAINTAINED_EVENTS: [bool; 11] = [true, true, false, true, true, false, true, true, false, true, false];
+    const NONMAINTAINED: [f64; 12] = [5.0, 5.0, 8.0, 8.0, 12.0, 16.0, 23.0, 27.0, 30.0, 33.0, 43.0, 45.0];
+
+    fn nonmaintained_events() -> [bool; 12] {
+        let mut events = [true; 12];
+        events[5] = false;
+        events
+    }
+
+    #[test]
+    fn test_kaplan_meier_aml() {
+        // reference values of survfit(Surv(time, status) ~ x, aml,
+        // conf.type = "log-log") at the event times
+        let km = KaplanMeier::fit(&MAINTAINED, &MAINTAINED_EVENTS).unwrap();
+        let expected = [
+            (9.0, 11, 0.90909090909090909, 0.086678417204144756, 0.50808020576992935, 0.98667382266782310),
+            (13.0, 10, 0.81818181818181818, 0.11629129983033296, 0.44742861468218109, 0.95116222858249172),
+            (18.0, 8, 0.71590909090909091, 0.13966497055722782, 0.35019038590793824, 0.89902397419105354),
+            (23.0, 7, 0.61363636363636364, 0.15263233102731202, 0.26575204000128596, 0.83529924325022159),
+            (31.0, 5, 0.49090909090909091, 0.16419326722113582, 0.16733090977685073, 0.75339979037081137),
+            (34.0, 4, 0.36818181818181818, 0.16266888582709479, 0.092829574936985156, 0.65704083239816388),
+            (48.0, 2, 0.18409090909090909, 0.15349274578629368, 0.011738480123189575, 0.52501484272664157),
+        ];
+        for &(t, at_risk, survival, std_err, lower, upper) in &expected {
+            let row = km.iter().find(|row| row.time == t).unwrap();
+            assert_eq!(row.at_risk, at_risk);
+            assert_eq!(row.events, 1);
+            assert_almost_eq!(km.survival_at(t), survival, 1e-15);
+            assert_almost_eq!(km.std_err_at(t), std_err, 1e-14);
+            let (l, u) = km.confidence_interval_at(t, 0.95);
+            assert_almost_eq!(l, lower, 1e-13);
+            assert_almost_eq!(u, upper, 1e-13);
+        }
+        assert_eq!(km.median_survival(), Some(31.0));
+        assert_eq!(km.median_confidence_interval(0.95), (Some(13.0), None));
+
+        let km = KaplanMeier::fit(&NONMAINTAINED, &nonmaintained_events()).unwrap();
+        assert_almost_eq!(km.survival_at(23.0), 0.48611111111111111, 1e-15);
+        assert_almost_eq!(km.std_err_at(23.0), 0.14813006255348293, 1e-14);
+        assert_almost_eq!(km.survival_at(43.0), 0.097222222222222222, 1e-15);
+        assert_almost_eq!(km.std_err_at(43.0), 0.091866364967520507, 1e-14);
+        assert_eq!(km.median_survival(), Some(23.0));
+        assert_eq!(km.median_confidence_interval(0.95), (Some(5.0), Some(33.0)));
+        // the curve reaches zero at the last time
+        assert_eq!(km.survival_at(45.0), 0.0);
+        assert!(km.std_err_at(45.0).is_nan());
+        assert_eq!(km.confidence_interval_at(50.0, 0.95), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_kaplan_meier_steps() {
+        let km = KaplanMeier::fit(&MAINTAINED, &MAINTAINED_EVENTS).unwrap();
+        assert_eq!(km.survival_at(0.0), 1.0);
+        assert_eq!(km.std_err_at(8.99), 0.0);
+        assert_eq!(km.confidence_interval_at(5.0, 0.95), (1.0, 1.0));
+        // right-continuous, constant between event times and at censorings
+        assert_eq!(km.survival_at(9.0), km.survival_at(12.99));
+        assert_eq!(km.survival_at(23.0), km.survival_at(28.0));
+        assert_eq!(km.survival_at(48.0), km.survival_at(1000.0));
+        // a censoring tied with an event is at risk at that time
+        let row = km.iter().find(|row| row.time == 13.0).unwrap();
+        assert_eq!((row.at_risk, row.events, row.censored), (10, 1, 1));
+        assert_eq!(km.risk_table().len(), 10);
+        assert_eq!(km.iter().map(|row| row.events).sum::<u64>(), 7);
+        // the order of the observations does not matter
+        let mut shuffled: Vec<(f64, bool)> = MAINTAINED.iter().copied().zip(MAINTAINED_EVENTS.iter().copied()).collect();
+        shuffled.reverse();
+        shuffled.swap(2, 7);
+        let (times, events): (Vec<f64>, Vec<bool>) = shuffled.into_iter().unzip();
+        assert_eq!(KaplanMeier::fit(&times, &events).unwrap(), km);
+    }
+
+    #[test]
+    fn test_kaplan_meier_uncensored_is_empirical() {
+        let times = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let km = KaplanMeier::fit(&times, &[true; 8]).unwrap();
+        for &t in &[0.5, 1.0, 2.5, 4.0, 6.0, 10.0] {
+            let above = times.iter().filter(|&&x| x > t).count() as f64 / 8.0;
+            assert_almost_eq!(km.survival_at(t), above, 1e-15);
+            // Greenwood reduces to the binomial standard error
+            if above > 0.0 {
+                assert_almost_eq!(km.std_err_at(t), (above * (1.0 - above) / 8.0).sqrt(), 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_median_flat_at_half() {
+        // the curve is exactly 0.5 from 2 until the drop at 4
+        let km = KaplanMeier::fit(&[1.0, 2.0, 3.0, 4.0], &[true, true, false, true]).unwrap();
+        assert_eq!(km.survival_at(2.0), 0.5);
+        assert_eq!(km.median_survival(), Some(3.0));
+        // and until the end
+        let km = KaplanMeier::fit(&[1.0, 2.0, 3.0, 4.0], &[true, true, false, false]).unwrap();
+        assert_eq!(km.median_survival(), Some(2.0));
+        // never reaching 0.5
+        let km = KaplanMeier::fit(&[1.0, 2.0, 3.0], &[true, false, false]).unwrap();
+        assert_eq!(km.median_survival(), None);
+    }
+
+    #[test]
+    fn test_nelson_aalen_aml() {
+        // reference values of survfit(...)$cumhaz and $std.chaz
+        let na = NelsonAalen::fit(&MAINTAINED, &MAINTAINED_EVENTS).unwrap();
+        assert_almost_eq!(na.cumulative_hazard_at(13.0), 0.19090909090909091, 1e-15);
+        assert_almost_eq!(na.std_err_at(13.0), 0.13514607952107732, 1e-15);
+        assert_almost_eq!(na.cumulative_hazard_at(50.0), 1.4087662337662338, 1e-15);
+        assert_almost_eq!(na.std_err_at(50.0), 0.63780688776088290, 1e-15);
+        let na = NelsonAalen::fit(&NONMAINTAINED, &nonmaintained_events()).unwrap();
+        assert_almost_eq!(na.cumulative_hazard_at(45.0), 2.9416666666666667, 1e-15);
+        assert_almost_eq!(na.std_err_at(45.0), 1.2413310508392907, 1e-15);
+        assert_eq!(na.cumulative_hazard_at(4.0), 0.0);
+        // the Fleming-Harrington survival is above Kaplan-Meier
+        let km = KaplanMeier::fit(&NONMAINTAINED, &nonmaintained_events()).unwrap();
+        for row in &na {
+            assert!(na.survival_at(row.time) >= km.survival_at(row.time));
+        }
+        // the same risk table
+        for (a, b) in na.iter().zip(&km) {
+            assert_eq!((a.time, a.at_risk, a.events, a.censored), (b.time, b.at_risk, b.events, b.censored));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let km = KaplanMeier::fit(&[2.0, 3.0, 3.0], &[true, true, false]).unwrap();
+        let table = km.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("survival"));
+        assert_eq!(lines[2].split_whitespace().collect::<Vec<_>>()[..5], ["3", "2", "1", "1", "0.3333"]);
+        let na = NelsonAalen::fit(&[2.0, 3.0, 3.0], &[true, true, false]).unwrap();
+        assert!(na.to_string().lines().next().unwrap().contains("cumhaz"));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(KaplanMeier::fit(&[1.0, 2.0], &[true]).is_err());
+        assert!(KaplanMeier::fit(&[], &[]).is_err());
+        assert!(KaplanMeier::fit(&[1.0, f64::NAN], &[true, true]).is_err());
+        assert!(NelsonAalen::fit(&[1.0, f64::INFINITY], &[true, false]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_level() {
+        let km = KaplanMeier::fit(&[1.0, 2.0], &[true, true]).unwrap();
+        km.confidence_interval_at(1.0, 1.0);
+    }
+}
@@ -0,0 +1,116 @@
+//! Provides summaries of the upper tail of a sample, as used to choose the
+//! threshold of peaks over threshold models
+//!
+//! The theoretical counterparts are provided by the
+//! [`TailExpectation`](crate::distribution::TailExpectation) trait.
+
+use std::f64;
+
+/// A point of a mean excess plot, the sample mean excess over a threshold
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MeanExcessPoint {
+    /// The threshold `u`
+    pub threshold: f64,
+    /// The mean of `x - u` over the observations `x > u`, or `f64::NAN` if
+    /// there are none
+    pub mean_excess: f64,
+    /// The number of observations above the threshold
+    pub exceedances: usize,
+}
+
+/// Computes the sample mean excess function of `data` at every one of the
+/// `thresholds`, the points of a mean excess plot
+///
+/// # Formula
+///
+/// ```text
+/// e(u) = Σ_{x_i > u} (x_i - u) / #{x_i > u}
+/// ```
+///
+/// # Remarks
+///
+/// The mean excess function of the generalized Pareto distribution is
+/// linear in the threshold, so the threshold of a peaks over threshold
+/// model is chosen where the plot becomes roughly linear. The points at
+/// the highest thresholds rest on few exceedances and are very variable.
+/// `NaN` observations are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::tail::empirical_mean_excess;
+///
+/// let points = empirical_mean_excess(&[1.0, 2.0, 4.0, 7.0], &[0.0, 3.0, 7.0]);
+/// assert_eq!(points[0].mean_excess, 3.5);
+/// // (4 - 3 + 7 - 3) / 2
+/// assert_eq!(points[1].mean_excess, 2.5);
+/// assert_eq!(points[1].exceedances, 2);
+/// assert!(points[2].mean_excess.is_nan());
+/// ```
+pub fn empirical_mean_excess(data: &[f64], thresholds: &[f64]) -> Vec<MeanExcessPoint> {
+    let mut sorted: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    // upper[i] is the sum of sorted[i..]
+    let mut upper = vec![0.0; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        upper[i] = upper[i + 1] + sorted[i];
+    }
+    thresholds
+        .iter()
+        .map(|&threshold| {
+            let first = sorted.partition_point(|&x| x <= threshold);
+            let exceedances = sorted.len() - first;
+            let mean_excess = if exceedances == 0 {
+                f64::NAN
+            } else {
+                upper[first] / exceedances as f64 - threshold
+            };
+            MeanExcessPoint {
+                threshold,
+                mean_excess,
+                exceedances,
+            }
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Exp, Pareto, TailExpectation};
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_empirical_mean_excess() {
+        let data = [3.0, f64::NAN, 1.0, 6.0, 3.0, 10.0];
+        let points = empirical_mean_excess(&data, &[-1.0, 1.0, 3.0, 5.5, 10.0]);
+        let excesses: Vec<f64> = points.iter().map(|p| p.mean_excess).collect();
+        let counts: Vec<usize> = points.iter().map(|p| p.exceedances).collect();
+        assert_eq!(excesses[..4], [5.6, 4.5, 5.0, 2.5]);
+        assert!(excesses[4].is_nan());
+        assert_eq!(counts, vec![5, 4, 2, 2, 0]);
+        assert_eq!(points[2].threshold, 3.0);
+        assert!(empirical_mean_excess(&data, &[]).is_empty());
+        assert!(empirical_mean_excess(&[], &[0.0])[0].mean_excess.is_nan());
+    }
+
+    #[test]
+    fn test_convergence() {
+        // the sample curve approaches the theoretical one
+        let mut rng = StdRng::seed_from_u64(0);
+        let pareto = Pareto::new(1.0, 4.0).unwrap();
+        let data: Vec<f64> = pareto.sample_iter(&mut rng).take(200_000).collect();
+        for point in empirical_mean_excess(&data, &[0.5, 1.0, 1.5, 2.0]) {
+            let expected = pareto.mean_excess(point.threshold);
+            assert_almost_eq!(point.mean_excess, expected, 0.05 * expected);
+        }
+        let exp = Exp::new(2.0).unwrap();
+        let data: Vec<f64> = exp.sample_iter(&mut rng).take(200_000).collect();
+        for point in empirical_mean_excess(&data, &[0.0, 0.5, 1.0, 2.0]) {
+            assert_almost_eq!(point.mean_excess, 0.5, 0.02);
+        }
+    }
+}
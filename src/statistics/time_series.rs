@@ -0,0 +1,1043 @@
+//! Provides summaries of time series, sequences of observations taken at
+//! equally spaced times
+//!
+//! The autocovariances use the biased estimator with divisor `n`, as R's
+//! `acf`, which makes the sequence positive semi-definite.
+
+use crate::distribution::{ChiSquared, ContinuousCDF, Normal};
+use crate::statistics::window;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+#[cfg(feature = "fft")]
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::f64;
+#[cfg(feature = "fft")]
+use std::f64::consts::PI;
+
+/// Selects the taper applied to every segment by `welch_psd`, see the
+/// [`window`](crate::statistics::window) module for the definitions
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Window {
+    /// No tapering, every coefficient is one
+    Rectangular,
+    /// The Hann window `0.5 - 0.5 cos(2π k / (n - 1))`
+    Hann,
+    /// The Hamming window `0.54 - 0.46 cos(2π k / (n - 1))`
+    Hamming,
+    /// The Blackman window
+    /// `0.42 - 0.5 cos(2π k / (n - 1)) + 0.08 cos(4π k / (n - 1))`
+    Blackman,
+    /// The triangular Bartlett window `1 - |2k / (n - 1) - 1|`
+    Bartlett,
+}
+
+impl Window {
+    /// Returns the `n` symmetric coefficients of the window, `[1.0]` for
+    /// `n = 1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::time_series::Window;
+    ///
+    /// let bartlett = Window::Bartlett.coefficients(5);
+    /// assert_eq!(bartlett, vec![0.0, 0.5, 1.0, 0.5, 0.0]);
+    /// ```
+    pub fn coefficients(&self, n: usize) -> Vec<f64> {
+        match self {
+            Window::Rectangular => vec![1.0; n],
+            Window::Hann => window::hann(n),
+            Window::Hamming => window::hamming(n),
+            Window::Blackman => window::blackman(n),
+            Window::Bartlett => window::bartlett(n),
+        }
+    }
+}
+
+/// Computes the sample autocovariances of `data` at the lags `0` to
+/// `max_lag`, or to `n - 1` if that is smaller, in `O(n max_lag)` time.
+/// Returns an empty vector for empty data.
+///
+/// # Formula
+///
+/// ```text
+/// γ(k) = 1/n Σ_{t = 1}^{n - k} (x_t - x̄)(x_{t + k} - x̄)
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::autocovariance;
+///
+/// let acov = autocovariance(&[1.0, 2.0, 3.0, 4.0], 2);
+/// assert_eq!(acov, vec![1.25, 0.3125, -0.375]);
+/// ```
+pub fn autocovariance(data: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = data.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let centered = centered(data);
+    (0..=max_lag.min(n - 1))
+        .map(|k| {
+            centered[..n - k]
+                .iter()
+                .zip(&centered[k..])
+                .map(|(a, b)| a * b)
+                .sum::<f64>()
+                / n as f64
+        })
+        .collect()
+}
+
+/// Computes the sample autocorrelations `γ(k) / γ(0)` of `data` at the lags
+/// `0` to `max_lag`, or to `n - 1` if that is smaller. Returns an empty
+/// vector for empty data and `f64::NAN` at every lag for constant data.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::autocorrelation;
+///
+/// let acf = autocorrelation(&[1.0, 2.0, 3.0, 4.0], 2);
+/// assert_eq!(acf, vec![1.0, 0.25, -0.3]);
+/// ```
+pub fn autocorrelation(data: &[f64], max_lag: usize) -> Vec<f64> {
+    let acov = autocovariance(data, max_lag);
+    match acov.first() {
+        Some(&variance) => acov.iter().map(|c| c / variance).collect(),
+        None => acov,
+    }
+}
+
+/// Computes the sample autocovariances of `autocovariance` with the fast
+/// Fourier transform, in `O(n log n)` time for all the lags
+///
+/// # Remarks
+///
+/// By the Wiener-Khinchin theorem the autocovariances are the inverse
+/// transform of the periodogram. The centered data are padded with zeros
+/// to a power of two of at least `2n` so that the circular correlation of
+/// the transform does not wrap around. The result agrees with the direct
+/// computation up to rounding of order `ε log(n) γ(0)`, so autocovariances
+/// much smaller than the variance have a lower relative accuracy. It pays
+/// off from a few hundred lags on.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::{autocovariance, autocovariance_fft};
+///
+/// let data = [1.0, 2.0, 3.0, 4.0];
+/// let fast = autocovariance_fft(&data, 2);
+/// for (a, b) in fast.iter().zip(autocovariance(&data, 2)) {
+///     assert!((a - b).abs() < 1e-14);
+/// }
+/// ```
+#[cfg(feature = "fft")]
+pub fn autocovariance_fft(data: &[f64], max_lag: usize) -> Vec<f64> {
+    let n = data.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let size = (2 * n).next_power_of_two();
+    let mut buffer: Vec<Complex<f64>> = centered(data)
+        .into_iter()
+        .map(|x| Complex::new(x, 0.0))
+        .collect();
+    buffer.resize(size, Complex::new(0.0, 0.0));
+    let mut planner = FftPlanner::new();
+    planner.plan_fft_forward(size).process(&mut buffer);
+    for z in buffer.iter_mut() {
+        *z = Complex::new(z.norm_sqr(), 0.0);
+    }
+    planner.plan_fft_inverse(size).process(&mut buffer);
+    // the transforms are unnormalized
+    let scale = (size * n) as f64;
+    buffer[..=max_lag.min(n - 1)]
+        .iter()
+        .map(|z| z.re / scale)
+        .collect()
+}
+
+/// Computes the periodogram of `data` at the Fourier frequencies `j / n`
+/// cycles per observation for `j = 0, ..., ⌊n / 2⌋`. Returns an empty vector
+/// for empty data.
+///
+/// # Formula
+///
+/// ```text
+/// I(j) = 1/n |Σ_{t = 0}^{n - 1} x_t e^(-2πi j t / n)|^2
+/// ```
+///
+/// # Remarks
+///
+/// The data are not centered, so `I(0) = n x̄^2`. With this normalization
+/// the expected periodogram of white noise of variance `σ²` is `σ²` at every
+/// nonzero frequency, and the sum of `I` over all `n` frequencies,
+/// counting every `j` and `n - j` below the Nyquist frequency twice, is
+/// `Σ x_t^2`. The raw periodogram is an inconsistent estimator of the
+/// spectral density, whose variance does not decrease with `n`; see
+/// `welch_psd` for a smoothed estimate.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::periodogram;
+/// use std::f64::consts::PI;
+///
+/// // a cosine with 4 cycles over 32 observations
+/// let data: Vec<f64> = (0..32)
+///     .map(|t| (2.0 * PI * 4.0 * t as f64 / 32.0).cos())
+///     .collect();
+/// let power = periodogram(&data);
+/// assert_eq!(power.len(), 17);
+/// assert!((power[4] - 8.0).abs() < 1e-12);
+/// assert!(power[3] < 1e-12);
+/// ```
+#[cfg(feature = "fft")]
+pub fn periodogram(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut buffer: Vec<Complex<f64>> = data.iter().map(|&x| Complex::new(x, 0.0)).collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buffer);
+    buffer[..=n / 2]
+        .iter()
+        .map(|z| z.norm_sqr() / n as f64)
+        .collect()
+}
+
+/// Estimates the spectral density of `data` by Welch's method, averaging
+/// the periodograms of segments of length `segment` that overlap by
+/// `overlap` observations, each centered and tapered by `window`. Returns
+/// the estimate at the frequencies `j / segment` cycles per observation for
+/// `j = 0, ..., ⌊segment / 2⌋`.
+///
+/// # Formula
+///
+/// ```text
+/// P(j) = 1/K Σ_k |Σ_t w_t (x_{k,t} - x̄_k) e^(-2πi j t / L)|^2 / Σ_t w_t^2
+/// ```
+///
+/// where `L` is the segment length, `K` the number of segments, `x_{k,t}`
+/// the observations of segment `k`, `x̄_k` their mean and `w` the window
+///
+/// # Remarks
+///
+/// Normalizing by the energy of the window gives the scale of
+/// `periodogram`, so that white noise of variance `σ²` has an expected
+/// estimate of `σ²` at nonzero frequencies. Averaging `K` segments divides
+/// the variance of the estimate by up to `K` at the cost of a frequency
+/// resolution of `1 / L`. Observations after the last full segment are
+/// ignored. A Hann window with half overlapping segments is the usual
+/// choice.
+///
+/// # Errors
+///
+/// Returns an error if `segment` is zero or longer than `data`, or if
+/// `overlap` is not smaller than `segment`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::{welch_psd, Window};
+/// use std::f64::consts::PI;
+///
+/// // a sine with a period of 8 observations
+/// let data: Vec<f64> = (0..1024)
+///     .map(|t| (2.0 * PI * t as f64 / 8.0).sin())
+///     .collect();
+/// let psd = welch_psd(&data, 64, 32, Window::Hann).unwrap();
+/// let peak = (0..psd.len()).max_by(|&a, &b| psd[a].partial_cmp(&psd[b]).unwrap());
+/// assert_eq!(peak, Some(8));
+/// ```
+#[cfg(feature = "fft")]
+pub fn welch_psd(data: &[f64], segment: usize, overlap: usize, window: Window) -> Result<Vec<f64>> {
+    if segment == 0 || segment > data.len() {
+        return Err(StatsError::ArgIntervalIncl(
+            "segment",
+            1.0,
+            data.len() as f64,
+        ));
+    }
+    if overlap >= segment {
+        return Err(StatsError::ArgLt("overlap", segment as f64));
+    }
+    let taper = window.coefficients(segment);
+    let energy: f64 = taper.iter().map(|w| w * w).sum();
+    let fft = FftPlanner::new().plan_fft_forward(segment);
+    let step = segment - overlap;
+    let mut psd = vec![0.0; segment / 2 + 1];
+    let mut count = 0;
+    let mut buffer = vec![Complex::new(0.0, 0.0); segment];
+    for start in (0..=data.len() - segment).step_by(step) {
+        let values = &data[start..start + segment];
+        let mean = values.iter().sum::<f64>() / segment as f64;
+        for ((z, x), w) in buffer.iter_mut().zip(values).zip(&taper) {
+            *z = Complex::new((x - mean) * w, 0.0);
+        }
+        fft.process(&mut buffer);
+        for (p, z) in psd.iter_mut().zip(&buffer) {
+            *p += z.norm_sqr();
+        }
+        count += 1;
+    }
+    let scale = count as f64 * energy;
+    Ok(psd.into_iter().map(|p| p / scale).collect())
+}
+
+/// Computes the differences of order `order` of `data`, applying
+/// `x_t - x_{t - 1}` `order` times, as used to remove trends before fitting
+/// an ARIMA model. Returns `order` fewer values than `data`, or an empty
+/// vector if `data` has at most `order` values.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::difference;
+///
+/// let data = [1.0, 4.0, 9.0, 16.0, 25.0];
+/// assert_eq!(difference(&data, 1), vec![3.0, 5.0, 7.0, 9.0]);
+/// assert_eq!(difference(&data, 2), vec![2.0, 2.0, 2.0]);
+/// assert_eq!(difference(&data, 0), data.to_vec());
+/// ```
+pub fn difference(data: &[f64], order: usize) -> Vec<f64> {
+    let mut differences = data.to_vec();
+    for _ in 0..order {
+        if differences.is_empty() {
+            break;
+        }
+        differences = differences.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    differences
+}
+
+/// Inverts `difference`, integrating the differences `diffed` of order
+/// `initial.len()` given the first `initial.len()` values of the original
+/// series. Returns the original series, with `initial.len()` more values
+/// than `diffed`.
+///
+/// # Remarks
+///
+/// The initial values determine the first value of every intermediate
+/// difference, from which each level is recovered by a running sum. The
+/// round trip is exact when the arithmetic is, as for integer-valued data,
+/// and otherwise accurate to the rounding of the running sums.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::{difference, undifference};
+///
+/// let data = [1.0, 4.0, 9.0, 16.0, 25.0];
+/// let diffed = difference(&data, 2);
+/// assert_eq!(undifference(&diffed, &data[..2]), data.to_vec());
+/// ```
+pub fn undifference(diffed: &[f64], initial: &[f64]) -> Vec<f64> {
+    let order = initial.len();
+    // the first value of the differences of every order below `order`
+    let heads: Vec<f64> = (0..order).map(|k| difference(initial, k)[0]).collect();
+    let mut series = diffed.to_vec();
+    for &head in heads.iter().rev() {
+        let mut level = Vec::with_capacity(series.len() + 1);
+        level.push(head);
+        for d in &series {
+            level.push(level[level.len() - 1] + d);
+        }
+        series = level;
+    }
+    series
+}
+
+/// Computes the Durbin-Watson statistic of the regression residuals
+/// `residuals` in time order, a test of first-order autocorrelation.
+/// Returns `f64::NAN` for fewer than two residuals.
+///
+/// # Formula
+///
+/// ```text
+/// d = Σ_{t = 2}^n (e_t - e_{t - 1})^2 / Σ_{t = 1}^n e_t^2
+/// ```
+///
+/// # Remarks
+///
+/// The statistic is about `2 (1 - ρ(1))` where `ρ(1)` is the lag one
+/// autocorrelation of the residuals, so it lies in `[0, 4]` with `2` for
+/// no autocorrelation, less for positive and more for negative
+/// autocorrelation. The residuals are not centered, as those of a
+/// regression with an intercept have mean zero. All-zero residuals give
+/// `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::durbin_watson;
+///
+/// // alternating residuals are negatively autocorrelated
+/// assert_eq!(durbin_watson(&[1.0, -1.0, 1.0, -1.0]), 3.0);
+/// assert!(durbin_watson(&[1.0]).is_nan());
+/// ```
+pub fn durbin_watson(residuals: &[f64]) -> f64 {
+    if residuals.len() < 2 {
+        return f64::NAN;
+    }
+    let differences: f64 = residuals
+        .windows(2)
+        .map(|w| (w[1] - w[0]) * (w[1] - w[0]))
+        .sum();
+    let squares: f64 = residuals.iter().map(|e| e * e).sum();
+    differences / squares
+}
+
+/// Performs the Ljung-Box portmanteau test of the null hypothesis that
+/// `data` are white noise, from the sample autocorrelations at the lags `1`
+/// to `lags`. The p-value of the statistic `Q` is that of the chi-squared
+/// distribution with `lags` degrees of freedom.
+///
+/// # Formula
+///
+/// ```text
+/// Q = n (n + 2) Σ_{k = 1}^h ρ(k)^2 / (n - k)
+/// ```
+///
+/// # Remarks
+///
+/// The weights `(n + 2) / (n - k)` correct the small-sample bias of the
+/// Box-Pierce statistic `n Σ ρ(k)^2`. When testing the residuals of a
+/// fitted ARMA(p, q) model, the degrees of freedom should be reduced by
+/// `p + q`, which this function does not do.
+///
+/// # Errors
+///
+/// Returns an error if `lags` is zero, if there are not more than `lags`
+/// observations, or if the data are not finite or constant
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::ljung_box;
+///
+/// // ρ(1) = 0.25 and ρ(2) = -0.3
+/// let result = ljung_box(&[1.0, 2.0, 3.0, 4.0], 2).unwrap();
+/// let q = result.statistic;
+/// assert!((q - 24.0 * (0.0625 / 3.0 + 0.09 / 2.0)).abs() < 1e-14);
+/// assert!((result.p_value - (-q / 2.0).exp()).abs() < 1e-14);
+/// ```
+pub fn ljung_box(data: &[f64], lags: usize) -> Result<TestResult> {
+    let n = data.len();
+    if lags == 0 {
+        return Err(StatsError::ArgMustBePositive("lags"));
+    }
+    if n <= lags {
+        return Err(StatsError::SpecialCase(
+            "there must be more observations than lags",
+        ));
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("data must be finite"));
+    }
+    let acf = autocorrelation(data, lags);
+    let n = n as f64;
+    let statistic = n
+        * (n + 2.0)
+        * acf
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, rho)| rho * rho / (n - k as f64))
+            .sum::<f64>();
+    if statistic.is_nan() {
+        return Err(StatsError::SpecialCase("data must not be constant"));
+    }
+    let p_value = ChiSquared::new(lags as f64).unwrap().sf(statistic);
+    Ok(TestResult::new(
+        "Box-Ljung test",
+        "X-squared",
+        statistic,
+        p_value,
+        Alternative::TwoSided,
+        vec![data.len()],
+    )
+    .with_df(lags as f64))
+}
+
+/// Performs the augmented Dickey-Fuller test of the null hypothesis that
+/// `data` have a unit root, against the alternative that they are
+/// stationary, with `lags` lagged differences in the regression. The
+/// alternative of the result is `Alternative::Less`.
+///
+/// # Formula
+///
+/// The differences are regressed by least squares on a constant, the
+/// lagged level and the lagged differences,
+///
+/// ```text
+/// Δx_t = α + γ x_{t - 1} + δ_1 Δx_{t - 1} + ... + δ_p Δx_{t - p} + e_t
+/// ```
+///
+/// and the statistic is the t-ratio `γ / se(γ)`, small under the
+/// alternative `γ < 0`.
+///
+/// # Remarks
+///
+/// Under the null the statistic does not follow the t distribution but
+/// the Dickey-Fuller distribution, whose p-values are approximated with
+/// the response surface of MacKinnon (1994) for the regression with a
+/// constant, as in statsmodels' `adfuller`. The asymptotic 1%, 5% and 10%
+/// critical values are about `-3.43`, `-2.86` and `-2.57`. The lags absorb
+/// the autocorrelation of the differences; too few distort the size of
+/// the test, too many lose power.
+///
+/// # Errors
+///
+/// Returns an error if an observation is not finite, if the regression has
+/// no residual degrees of freedom, if the regressors are collinear, or if
+/// the residuals vanish
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::adf_test;
+///
+/// // an alternating series reverts to its mean at once
+/// let data: Vec<f64> = (0..50).map(|t| if t % 2 == 0 { 1.0 } else { -1.0 } + 0.01 * (t % 3) as f64).collect();
+/// let result = adf_test(&data, 0).unwrap();
+/// assert!(result.statistic < -3.43);
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn adf_test(data: &[f64], lags: usize) -> Result<TestResult> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("data must be finite"));
+    }
+    let differences: Vec<f64> = data.windows(2).map(|w| w[1] - w[0]).collect();
+    // the regressions start once `lags` lagged differences are available
+    let rows = differences.len().saturating_sub(lags);
+    let columns = lags + 2;
+    if rows <= columns {
+        return Err(StatsError::SpecialCase(
+            "too few observations for the number of lags",
+        ));
+    }
+    let design = DMatrix::from_fn(rows, columns, |i, j| {
+        let t = i + lags;
+        match j {
+            0 => 1.0,
+            1 => data[t],
+            _ => differences[t - (j - 1)],
+        }
+    });
+    let response = DVector::from_fn(rows, |i, _| differences[i + lags]);
+    let cholesky = (design.transpose() * &design)
+        .cholesky()
+        .ok_or(StatsError::SpecialCase("the regressors are collinear"))?;
+    let coefficients = cholesky.solve(&(design.transpose() * &response));
+    let residuals = response - &design * &coefficients;
+    let variance = residuals.norm_squared() / (rows - columns) as f64;
+    let standard_error = (variance * cholesky.inverse()[(1, 1)]).sqrt();
+    let statistic = coefficients[1] / standard_error;
+    if !statistic.is_finite() {
+        return Err(StatsError::SpecialCase(
+            "the residuals of the regression vanish",
+        ));
+    }
+    Ok(TestResult::new(
+        "Augmented Dickey-Fuller test",
+        "Dickey-Fuller",
+        statistic,
+        mackinnon_p_value(statistic),
+        Alternative::Less,
+        vec![data.len()],
+    ))
+}
+
+/// Approximates the p-value of the Dickey-Fuller statistic `tau` of the
+/// regression with a constant, with the normal response surfaces of
+/// MacKinnon (1994) for a single series
+fn mackinnon_p_value(tau: f64) -> f64 {
+    const TAU_MAX: f64 = 2.74;
+    const TAU_MIN: f64 = -18.83;
+    const TAU_STAR: f64 = -1.61;
+    const SMALL_P: [f64; 3] = [2.1659, 1.4412, 0.038269];
+    const LARGE_P: [f64; 4] = [1.7339, 0.93202, -0.12745, -0.010368];
+    if tau > TAU_MAX {
+        return 1.0;
+    }
+    if tau < TAU_MIN {
+        return 0.0;
+    }
+    let coefficients: &[f64] = if tau <= TAU_STAR { &SMALL_P } else { &LARGE_P };
+    let z = coefficients.iter().rev().fold(0.0, |acc, c| acc * tau + c);
+    Normal::new(0.0, 1.0).unwrap().cdf(z)
+}
+
+/// Computes the sample partial autocorrelations of `data` at the lags `0`
+/// to `max_lag`, or to `n - 1` if that is smaller, the correlations of
+/// `x_t` and `x_{t + k}` given the observations between them. Returns an
+/// empty vector for empty data and `f64::NAN` at every lag for constant
+/// data.
+///
+/// # Remarks
+///
+/// The partial autocorrelation at lag `k` is the last coefficient `φ_k` of
+/// the AR(k) model of `fit_ar_yule_walker`, found for all the lags at once
+/// as the reflection coefficients of the Levinson-Durbin recursion. That of
+/// an AR(p) process vanishes beyond lag `p`, with sample values
+/// approximately normal with variance `1 / n` there.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::partial_autocorrelation;
+///
+/// // the first two lags agree with the autocorrelations
+/// let pacf = partial_autocorrelation(&[1.0, 2.0, 3.0, 4.0], 2);
+/// assert_eq!(pacf[..2], [1.0, 0.25]);
+/// // (ρ2 - ρ1²) / (1 - ρ1²)
+/// assert!((pacf[2] - (-0.3 - 0.0625) / 0.9375).abs() < 1e-15);
+/// ```
+pub fn partial_autocorrelation(data: &[f64], max_lag: usize) -> Vec<f64> {
+    let acov = autocovariance(data, max_lag);
+    if acov.is_empty() {
+        return acov;
+    }
+    let (_, partial, _) = levinson_durbin(&acov);
+    let mut pacf = Vec::with_capacity(acov.len());
+    pacf.push(if acov[0] > 0.0 { 1.0 } else { f64::NAN });
+    pacf.extend(partial);
+    pacf
+}
+
+/// Fits an autoregressive model of order `order`,
+///
+/// ```text
+/// x_t - x̄ = φ_1 (x_{t - 1} - x̄) + ... + φ_p (x_{t - p} - x̄) + e_t
+/// ```
+///
+/// by solving the Yule-Walker equations in the sample autocovariances with
+/// the Levinson-Durbin recursion. Returns the coefficients `[φ_1, ..., φ_p]`
+/// and the variance of the innovations `e_t`.
+///
+/// # Remarks
+///
+/// The autocovariances with divisor `n` make the fitted model stationary.
+/// The estimates are biased towards zero in short series, more so than the
+/// least squares estimates. Constant data give `f64::NAN` coefficients.
+///
+/// # Panics
+///
+/// If `data` does not have more than `order` observations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::time_series::fit_ar_yule_walker;
+///
+/// // φ = γ(1) / γ(0) and σ² = γ(0) (1 - φ²) for an AR(1) model
+/// let (phi, variance) = fit_ar_yule_walker(&[1.0, 2.0, 3.0, 4.0], 1);
+/// assert_eq!(phi, vec![0.25]);
+/// assert_eq!(variance, 1.25 * (1.0 - 0.0625));
+/// ```
+pub fn fit_ar_yule_walker(data: &[f64], order: usize) -> (Vec<f64>, f64) {
+    assert!(
+        data.len() > order,
+        "an AR({}) model needs more than {} observations",
+        order,
+        order
+    );
+    let (coefficients, _, variance) = levinson_durbin(&autocovariance(data, order));
+    (coefficients, variance)
+}
+
+/// Solves the Toeplitz system of the Yule-Walker equations in the
+/// autocovariances `acov` at the lags `0` to `p`, returning the coefficients
+/// of the AR(p) model, the partial autocorrelations at the lags `1` to `p`
+/// and the variance of the innovations
+fn levinson_durbin(acov: &[f64]) -> (Vec<f64>, Vec<f64>, f64) {
+    let order = acov.len() - 1;
+    let mut coefficients: Vec<f64> = Vec::with_capacity(order);
+    let mut partial = Vec::with_capacity(order);
+    let mut variance = acov[0];
+    for k in 1..=order {
+        let fitted: f64 = coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, phi)| phi * acov[k - 1 - j])
+            .sum();
+        let reflection = (acov[k] - fitted) / variance;
+        let previous = coefficients.clone();
+        for (j, phi) in coefficients.iter_mut().enumerate() {
+            *phi -= reflection * previous[k - 2 - j];
+        }
+        coefficients.push(reflection);
+        partial.push(reflection);
+        variance *= 1.0 - reflection * reflection;
+    }
+    (coefficients, partial, variance)
+}
+
+/// Returns the deviations of `data` from its mean
+fn centered(data: &[f64]) -> Vec<f64> {
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    data.iter().map(|x| x - mean).collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_util::SplitMix64;
+    use rand::Rng;
+
+    /// A stationary autoregressive series `x_t = φ x_{t-1} + e_t` with
+    /// uniform innovations
+    fn ar1(n: usize, phi: f64, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        let mut x = 0.0;
+        (0..n)
+            .map(|_| {
+                x = phi * x + rng.gen::<f64>() - 0.5;
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_autocovariance() {
+        // exact values of the definition
+        let x = [2.0, 4.0, 1.0, 5.0, 3.0, 6.0];
+        let acov = autocovariance(&x, 3);
+        let expected = [70.0 / 24.0, -31.0 / 24.0, 38.0 / 24.0, -35.0 / 24.0];
+        for (a, e) in acov.iter().zip(&expected) {
+            assert_almost_eq!(*a, *e, 1e-15);
+        }
+        assert_eq!(acov.len(), 4);
+        // the lags stop at n - 1
+        assert_eq!(autocovariance(&x, 100).len(), 6);
+        assert_eq!(autocovariance(&[3.0], 2), vec![0.0]);
+        assert!(autocovariance(&[], 2).is_empty());
+        // the variance with divisor n
+        assert_almost_eq!(acov[0], 17.5 / 6.0, 1e-15);
+    }
+
+    #[test]
+    fn test_autocorrelation() {
+        let data = ar1(2000, 0.7, 2900);
+        let acf = autocorrelation(&data, 5);
+        assert_eq!(acf[0], 1.0);
+        for (k, r) in acf.iter().enumerate() {
+            assert!((r - 0.7f64.powi(k as i32)).abs() < 0.1);
+        }
+        assert!(autocorrelation(&[2.0, 2.0], 1).iter().all(|r| r.is_nan()));
+        assert!(autocorrelation(&[], 1).is_empty());
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_autocovariance_fft() {
+        for &n in &[1, 2, 7, 1000, 4097] {
+            let data = ar1(n, 0.9, 2901 + n as u64);
+            let direct = autocovariance(&data, 300);
+            let fast = autocovariance_fft(&data, 300);
+            assert_eq!(fast.len(), direct.len());
+            for (a, b) in fast.iter().zip(&direct) {
+                assert!((a - b).abs() <= 1e-12 * direct[0].max(1e-300), "{} against {}", a, b);
+            }
+        }
+        assert!(autocovariance_fft(&[], 3).is_empty());
+    }
+    #[test]
+    fn test_window() {
+        assert_eq!(Window::Rectangular.coefficients(3), vec![1.0; 3]);
+        assert_eq!(Window::Hann.coefficients(1), vec![1.0]);
+        assert_eq!(Window::Hann.coefficients(64), window::hann(64));
+        assert_eq!(Window::Hamming.coefficients(9), window::hamming(9));
+        assert_eq!(Window::Blackman.coefficients(9), window::blackman(9));
+        assert_eq!(Window::Bartlett.coefficients(8), window::bartlett(8));
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_periodogram_sinusoid() {
+        // a sinusoid at bin 37 of 1000 stands out from every other bin
+        let n = 1000;
+        let data: Vec<f64> = (0..n)
+            .map(|t| 3.0 * (2.0 * PI * 37.0 * t as f64 / n as f64 + 0.4).sin())
+            .collect();
+        let power = periodogram(&data);
+        assert_eq!(power.len(), 501);
+        assert_almost_eq!(power[37], 9.0 * n as f64 / 4.0, 1e-8);
+        for (j, &p) in power.iter().enumerate() {
+            if j != 37 {
+                assert!(p < 1e-18 * n as f64, "bin {}: {}", j, p);
+            }
+        }
+        // a frequency between the bins peaks at the nearest one
+        let data: Vec<f64> = (0..n).map(|t| (2.0 * PI * 0.1234 * t as f64).cos()).collect();
+        let power = periodogram(&data);
+        let peak = (0..power.len()).max_by(|&a, &b| power[a].partial_cmp(&power[b]).unwrap());
+        assert_eq!(peak, Some(123));
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_periodogram_parseval() {
+        for &n in &[1, 6, 7, 128] {
+            let data = ar1(n, 0.5, 2910 + n as u64);
+            let power = periodogram(&data);
+            let mut total = power[0];
+            for (j, &p) in power.iter().enumerate().skip(1) {
+                total += if 2 * j == n { p } else { 2.0 * p };
+            }
+            assert_almost_eq!(total, data.iter().map(|x| x * x).sum::<f64>(), 1e-12);
+        }
+        assert!(periodogram(&[]).is_empty());
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_welch_psd() {
+        let mut rng = SplitMix64::new(2911);
+        // uniform noise of variance 1/12 plus a sinusoid of period 16
+        let data: Vec<f64> = (0..16384)
+            .map(|t| rng.gen::<f64>() - 0.5 + (2.0 * PI * t as f64 / 16.0).sin())
+            .collect();
+        for &window in &[
+            Window::Rectangular,
+            Window::Hann,
+            Window::Hamming,
+            Window::Blackman,
+            Window::Bartlett,
+        ] {
+            let psd = welch_psd(&data, 256, 128, window).unwrap();
+            assert_eq!(psd.len(), 129);
+            let peak = (0..psd.len()).max_by(|&a, &b| psd[a].partial_cmp(&psd[b]).unwrap());
+            assert_eq!(peak, Some(16));
+            // far from the peak the estimate is the flat noise level
+            let far: Vec<f64> = psd[40..120].to_vec();
+            let level = far.iter().sum::<f64>() / far.len() as f64;
+            assert!((level - 1.0 / 12.0).abs() < 0.01, "{:?}: {}", window, level);
+            assert!(far.iter().all(|&p| (p - 1.0 / 12.0).abs() < 0.05));
+        }
+        // a single segment with no taper is the periodogram of the centered
+        // data
+        let data = ar1(64, 0.5, 2912);
+        let mean = data.iter().sum::<f64>() / 64.0;
+        let centered: Vec<f64> = data.iter().map(|x| x - mean).collect();
+        let psd = welch_psd(&data, 64, 0, Window::Rectangular).unwrap();
+        for (a, b) in psd.iter().zip(periodogram(&centered)) {
+            assert_almost_eq!(*a, b, 1e-12);
+        }
+    }
+
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_welch_psd_bad_input() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        assert!(welch_psd(&data, 0, 0, Window::Hann).is_err());
+        assert!(welch_psd(&data, 5, 0, Window::Hann).is_err());
+        assert!(welch_psd(&data, 4, 4, Window::Hann).is_err());
+        assert_eq!(welch_psd(&data, 4, 3, Window::Hann).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_fit_ar_yule_walker() {
+        // the innovations are uniform on (-0.5, 0.5), with variance 1 / 12
+        for &phi in [0.6, -0.3, 0.9].iter() {
+            let x = ar1(20000, phi, 17);
+            let (coefficients, variance) = fit_ar_yule_walker(&x, 1);
+            assert_almost_eq!(coefficients[0], phi, 0.02);
+            assert_almost_eq!(variance, 1.0 / 12.0, 0.003);
+            // a higher order finds no further dependence
+            let (coefficients, _) = fit_ar_yule_walker(&x, 3);
+            assert_almost_eq!(coefficients[0], phi, 0.03);
+            assert!(coefficients[1..].iter().all(|c| c.abs() < 0.03));
+        }
+    }
+
+    #[test]
+    fn test_fit_ar_yule_walker_equations() {
+        // the coefficients solve Σ_j φ_j γ(|i - j|) = γ(i) for i = 1, ..., p
+        let x = ar1(200, 0.5, 3);
+        let acov = autocovariance(&x, 4);
+        let (phi, variance) = fit_ar_yule_walker(&x, 4);
+        for i in 1..=4 {
+            let lhs: f64 = (1..=4).map(|j| phi[j - 1] * acov[(i as i32 - j as i32).unsigned_abs() as usize]).sum();
+            assert_almost_eq!(lhs, acov[i], 1e-14);
+        }
+        let explained: f64 = (1..=4).map(|j| phi[j - 1] * acov[j]).sum();
+        assert_almost_eq!(variance, acov[0] - explained, 1e-14);
+        // an AR(0) model is the variance alone
+        assert_eq!(fit_ar_yule_walker(&x, 0), (vec![], acov[0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fit_ar_yule_walker_short() {
+        fit_ar_yule_walker(&[1.0, 2.0], 2);
+    }
+
+    #[test]
+    fn test_partial_autocorrelation() {
+        // an AR(1) process has ρ(1) = φ and no partial autocorrelation
+        // beyond, within about 3 / sqrt(n) of zero
+        let x = ar1(20000, 0.7, 5);
+        let pacf = partial_autocorrelation(&x, 10);
+        assert_eq!(pacf.len(), 11);
+        assert_eq!(pacf[0], 1.0);
+        assert_almost_eq!(pacf[1], 0.7, 0.02);
+        assert!(pacf[2..].iter().all(|p| p.abs() < 0.025));
+        // the lag one value is the autocorrelation
+        assert_almost_eq!(pacf[1], autocorrelation(&x, 1)[1], 1e-15);
+        // the last coefficients of the Yule-Walker fits
+        for (k, p) in pacf.iter().enumerate().take(5).skip(1) {
+            assert_almost_eq!(*p, fit_ar_yule_walker(&x, k).0[k - 1], 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_partial_autocorrelation_edge_cases() {
+        assert!(partial_autocorrelation(&[], 3).is_empty());
+        assert_eq!(partial_autocorrelation(&[1.0, 2.0, 3.0], 10).len(), 3);
+        assert_eq!(partial_autocorrelation(&[1.0, 2.0], 0), vec![1.0]);
+        assert!(partial_autocorrelation(&[2.0; 5], 2).iter().all(|p| p.is_nan()));
+    }
+
+    #[test]
+    fn test_durbin_watson() {
+        // white noise gives about 2, within a few 1 / sqrt(n)
+        let noise = ar1(10000, 0.0, 11);
+        assert_almost_eq!(durbin_watson(&noise), 2.0, 0.05);
+        // and an AR(1) series about 2 (1 - φ)
+        let x = ar1(10000, 0.8, 11);
+        assert_almost_eq!(durbin_watson(&x), 0.4, 0.05);
+        let x = ar1(10000, -0.5, 11);
+        assert_almost_eq!(durbin_watson(&x), 3.0, 0.05);
+        // (1 + 1 + 16) / (1 + 4 + 9 + 1)
+        assert_almost_eq!(durbin_watson(&[1.0, 2.0, 3.0, -1.0]), 18.0 / 15.0, 1e-15);
+        assert!(durbin_watson(&[]).is_nan());
+        assert!(durbin_watson(&[2.0]).is_nan());
+        assert!(durbin_watson(&[0.0, 0.0]).is_nan());
+    }
+
+    #[test]
+    fn test_ljung_box() {
+        // reference values computed with mpmath
+        let result = ljung_box(&[1.0, 3.0, 2.0, 5.0, 4.0, 4.0, 7.0, 6.0], 3).unwrap();
+        assert_almost_eq!(result.statistic, 1.6059280855199223, 1e-14);
+        assert_almost_eq!(result.p_value, 0.65804641429291904, 1e-14);
+        assert_eq!(result.df, Some(3.0));
+        assert_eq!(result.sample_sizes, vec![8]);
+        // white noise is not rejected
+        for seed in 0..5 {
+            let result = ljung_box(&ar1(1000, 0.0, seed), 10).unwrap();
+            assert!(result.p_value > 0.01);
+        }
+        // an AR(1) series is
+        for seed in 0..5 {
+            let result = ljung_box(&ar1(200, 0.5, seed), 10).unwrap();
+            assert!(result.statistic > 30.0);
+            assert!(result.p_value < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_ljung_box_edge_cases() {
+        assert!(ljung_box(&[1.0, 2.0, 3.0], 0).is_err());
+        assert!(ljung_box(&[1.0, 2.0, 3.0], 3).is_err());
+        assert!(ljung_box(&[], 1).is_err());
+        assert!(ljung_box(&[2.0; 10], 2).is_err());
+        assert!(ljung_box(&[1.0, f64::NAN, 3.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_adf_test() {
+        // reference values of the regression computed with mpmath
+        let x = [1.0, 1.4, 0.9, 2.2, 2.0, 2.9, 2.4, 3.3, 3.1, 2.6, 3.8, 3.5, 4.4, 3.9, 4.1];
+        let result = adf_test(&x, 0).unwrap();
+        assert_almost_eq!(result.statistic, -1.4756266798664454, 1e-12);
+        assert_almost_eq!(result.p_value, 0.54553218063855422, 1e-12);
+        assert_eq!(result.alternative, Alternative::Less);
+        let result = adf_test(&x, 1).unwrap();
+        assert_almost_eq!(result.statistic, -1.0135806861696667, 1e-12);
+        assert_almost_eq!(result.p_value, 0.74827888691745934, 1e-12);
+        let result = adf_test(&x, 2).unwrap();
+        assert_almost_eq!(result.statistic, -1.8727613064966098, 1e-12);
+        assert_almost_eq!(result.p_value, 0.3449815206064923, 1e-12);
+    }
+
+    #[test]
+    fn test_adf_test_unit_root() {
+        for seed in 0..5 {
+            // a random walk does not reject the unit root
+            let walk: Vec<f64> = ar1(500, 0.0, seed)
+                .iter()
+                .scan(0.0, |level, e| {
+                    *level += e;
+                    Some(*level)
+                })
+                .collect();
+            assert!(adf_test(&walk, 2).unwrap().p_value > 0.05);
+            // a stationary AR(1) series does
+            let result = adf_test(&ar1(500, 0.5, seed), 2).unwrap();
+            assert!(result.statistic < -3.43);
+            assert!(result.p_value < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_mackinnon_p_value() {
+        // the asymptotic critical values of the regression with a constant
+        assert_almost_eq!(mackinnon_p_value(-3.43), 0.01, 0.001);
+        assert_almost_eq!(mackinnon_p_value(-2.86), 0.05, 0.001);
+        assert_almost_eq!(mackinnon_p_value(-2.57), 0.10, 0.001);
+        // the two surfaces nearly meet at the switch
+        assert_almost_eq!(mackinnon_p_value(-1.61), mackinnon_p_value(-1.6100001), 1e-3);
+        assert_eq!(mackinnon_p_value(3.0), 1.0);
+        assert_eq!(mackinnon_p_value(-20.0), 0.0);
+    }
+
+    #[test]
+    fn test_adf_test_edge_cases() {
+        assert!(adf_test(&[], 0).is_err());
+        assert!(adf_test(&[1.0, 2.0, 4.0], 0).is_err());
+        assert!(adf_test(&[1.0, 2.0, 4.0, 3.0, 5.0], 2).is_err());
+        assert!(adf_test(&[1.0, f64::NAN, 4.0, 3.0, 5.0, 2.0], 0).is_err());
+        // constant differences make the regressors collinear
+        assert!(adf_test(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_difference() {
+        let data = [2.0, 3.0, 7.0, 6.0, 10.0, 15.0];
+        assert_eq!(difference(&data, 1), vec![1.0, 4.0, -1.0, 4.0, 5.0]);
+        assert_eq!(difference(&data, 2), vec![3.0, -5.0, 5.0, 1.0]);
+        assert_eq!(difference(&data, 3), vec![-8.0, 10.0, -4.0]);
+        assert_eq!(difference(&data, 5).len(), 1);
+        assert!(difference(&data, 6).is_empty());
+        assert!(difference(&data, 10).is_empty());
+        assert!(difference(&[], 1).is_empty());
+    }
+
+    #[test]
+    fn test_undifference_round_trip() {
+        let data: Vec<f64> = (0..50).map(|t| ((t * t * 7 + t * 3) % 23) as f64 - 11.0).collect();
+        for order in 0..5 {
+            let diffed = difference(&data, order);
+            assert_eq!(diffed.len(), data.len() - order);
+            assert_eq!(undifference(&diffed, &data[..order]), data);
+        }
+        // fractional data round trip to rounding
+        let x = ar1(200, 0.7, 3);
+        for order in 1..4 {
+            let back = undifference(&difference(&x, order), &x[..order]);
+            for (a, b) in back.iter().zip(&x) {
+                assert_almost_eq!(*a, *b, 1e-9);
+            }
+        }
+        assert_eq!(undifference(&[], &[1.0, 2.0]), vec![1.0, 2.0]);
+        assert_eq!(undifference(&[1.0, 2.0], &[]), vec![1.0, 2.0]);
+    }
+}
@@ -0,0 +1,495 @@
+//! Provides variance-stabilizing transforms for counts and proportions
+//!
+//! The variance of a Poisson count equals its mean and the variance of a
+//! binomial proportion depends on the proportion, so methods that assume a
+//! constant variance, such as least squares or denoisers tuned to Gaussian
+//! noise, are applied to transformed data instead. Each transform below has
+//! a variance close to a known constant whatever the underlying mean.
+//! The Box-Cox power transform instead estimates the power that makes
+//! positive data closest to normal.
+
+use crate::function::optimize::brent_min_with;
+use std::f64;
+
+/// Bound on the magnitude of the power estimated by `box_cox_lambda`
+const BOX_COX_BOUND: f64 = 5.0;
+
+/// Computes the Anscombe transform of the count `x`, whose variance is
+/// close to `1` when `x` follows a Poisson distribution
+///
+/// # Formula
+///
+/// ```text
+/// 2 sqrt(x + 3 / 8)
+/// ```
+///
+/// # Remarks
+///
+/// For a Poisson mean `λ` the variance is `1 + O(λ^-2)`, within 1% of `1`
+/// for `λ ≥ 4`. Returns `f64::NAN` if `x` is negative or `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::anscombe;
+///
+/// assert!((anscombe(0.0) - 1.5f64.sqrt()).abs() < 1e-15);
+/// assert_eq!(anscombe(4.6875), 4.5);
+/// ```
+pub fn anscombe(x: f64) -> f64 {
+    if x >= 0.0 {
+        2.0 * (x + 0.375).sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the closed-form approximation of Makitalo and Foi to the exact
+/// unbiased inverse of the Anscombe transform, which maps the expected value
+/// of `anscombe(X)` back to the Poisson mean of `X`
+///
+/// The algebraic inverse `(y / 2)^2 - 3 / 8` underestimates the mean, by
+/// about 6% at a mean of 4, because the transform is concave. Averages of
+/// transformed counts, such as the output of a denoiser, should be
+/// inverted with this function instead.
+///
+/// # Formula
+///
+/// ```text
+/// y^2 / 4 + sqrt(3 / 2) / (4y) - 11 / (8y^2) + 5 sqrt(3 / 2) / (8y^3) - 1 / 8
+/// ```
+///
+/// # Remarks
+///
+/// Recovers the Poisson mean to within 0.4% for every mean. Returns `0` for
+/// `y ≤ anscombe(0)`, where the formula reaches zero, and `f64::NAN` if `y`
+/// is `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::{anscombe, inverse_anscombe};
+///
+/// assert_eq!(inverse_anscombe(anscombe(0.0)), 0.0);
+/// assert!((inverse_anscombe(anscombe(1e6)) - 1e6).abs() < 1.0);
+/// ```
+pub fn inverse_anscombe(y: f64) -> f64 {
+    if y.is_nan() {
+        f64::NAN
+    } else if y <= anscombe(0.0) {
+        0.0
+    } else {
+        let c = 1.5f64.sqrt();
+        let w = 1.0 / y;
+        0.25 * y * y + w * (0.25 * c - w * (11.0 / 8.0 - w * 0.625 * c)) - 0.125
+    }
+}
+
+/// Computes the Freeman–Tukey transform of the count `x`, whose variance
+/// is close to `1` when `x` follows a Poisson distribution
+///
+/// # Formula
+///
+/// ```text
+/// sqrt(x) + sqrt(x + 1)
+/// ```
+///
+/// # Remarks
+///
+/// For a Poisson mean `λ` the variance is within 2% of `1` for `λ ≥ 4`.
+/// Returns `f64::NAN` if `x` is negative or `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::freeman_tukey;
+///
+/// assert_eq!(freeman_tukey(0.0), 1.0);
+/// assert!((freeman_tukey(3.0) - (2.0 + 3f64.sqrt())).abs() < 1e-15);
+/// ```
+pub fn freeman_tukey(x: f64) -> f64 {
+    if x >= 0.0 {
+        x.sqrt() + (x + 1.0).sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the arcsine square root transform of the proportion `p`, whose
+/// variance is close to `1 / (4n)` when `p` is a binomial proportion out
+/// of `n` trials
+///
+/// # Formula
+///
+/// ```text
+/// asin(sqrt(p))
+/// ```
+///
+/// # Remarks
+///
+/// The result is in `[0, π/2]`. Returns `f64::NAN` if `p` is not in
+/// `[0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::arcsine_sqrt;
+/// use std::f64::consts::PI;
+///
+/// assert_eq!(arcsine_sqrt(0.0), 0.0);
+/// assert!((arcsine_sqrt(0.5) - PI / 4.0).abs() < 1e-15);
+/// assert_eq!(arcsine_sqrt(1.0), PI / 2.0);
+/// ```
+pub fn arcsine_sqrt(p: f64) -> f64 {
+    if (0.0..=1.0).contains(&p) {
+        p.sqrt().asin()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the inverse of the arcsine square root transform, mapping `y`
+/// back to a proportion
+///
+/// # Formula
+///
+/// ```text
+/// sin(y)^2
+/// ```
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `y` is not in `[0, π/2]`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::{arcsine_sqrt, inverse_arcsine_sqrt};
+///
+/// assert!((inverse_arcsine_sqrt(arcsine_sqrt(0.3)) - 0.3).abs() < 1e-15);
+/// assert_eq!(inverse_arcsine_sqrt(arcsine_sqrt(1.0)), 1.0);
+/// ```
+pub fn inverse_arcsine_sqrt(y: f64) -> f64 {
+    if (0.0..=f64::consts::FRAC_PI_2).contains(&y) {
+        y.sin().powi(2)
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the square root transform `2 sqrt(x + offset)` of the Poisson
+/// count `x`, whose variance tends to `1` as the Poisson mean `λ` grows
+///
+/// # Remarks
+///
+/// With `offset = 0` the variance is `1 + O(1 / λ)`. The offset `3 / 8`
+/// cancels the `1 / λ` term, which gives `anscombe`. Returns `f64::NAN`
+/// if `x` or `offset` is negative or `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::{anscombe, vst_poisson};
+///
+/// assert_eq!(vst_poisson(9.0, 0.0), 6.0);
+/// assert_eq!(vst_poisson(9.0, 0.375), anscombe(9.0));
+/// ```
+pub fn vst_poisson(x: f64, offset: f64) -> f64 {
+    if x >= 0.0 && offset >= 0.0 {
+        2.0 * (x + offset).sqrt()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the arcsine transform of `x` successes out of `n` trials,
+/// scaled so that its variance tends to `1` as `n` grows for any success
+/// probability strictly between `0` and `1`
+///
+/// # Formula
+///
+/// ```text
+/// 2 sqrt(n) asin(sqrt((x + c) / (n + 2c)))
+/// ```
+///
+/// where `c` is `offset`
+///
+/// # Remarks
+///
+/// With `offset = 0` this is `2 sqrt(n) arcsine_sqrt(x / n)`. The offset
+/// `3 / 8` is Anscombe's choice, which reduces the bias of the variance
+/// for small `n p`. Returns `f64::NAN` if `n` is zero, if `x` is not in
+/// `[0, n]` or if `offset` is negative or `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::vst_binomial;
+/// use std::f64::consts::PI;
+///
+/// assert_eq!(vst_binomial(0.0, 25, 0.0), 0.0);
+/// assert_eq!(vst_binomial(25.0, 25, 0.0), 5.0 * PI);
+/// assert!(vst_binomial(0.0, 25, 0.375) > 0.0);
+/// ```
+pub fn vst_binomial(x: f64, n: u64, offset: f64) -> f64 {
+    let trials = n as f64;
+    if n > 0 && (0.0..=trials).contains(&x) && offset >= 0.0 {
+        2.0 * trials.sqrt() * arcsine_sqrt((x + offset) / (trials + 2.0 * offset))
+    } else {
+        f64::NAN
+    }
+}
+
+/// Computes the Box-Cox power transform of the positive value `x` with
+/// power `lambda`
+///
+/// # Formula
+///
+/// ```text
+/// (x^λ - 1) / λ  if λ != 0
+/// ln(x)          if λ = 0
+/// ```
+///
+/// # Remarks
+///
+/// The transform is continuous in `λ`, and is computed as
+/// `expm1(λ ln(x)) / λ` so that it stays accurate for `λ` near `0`.
+/// Returns `f64::NAN` if `x` is not positive or if either argument is
+/// `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::box_cox;
+///
+/// assert!((box_cox(4.0, 0.5) - 2.0).abs() < 1e-15);
+/// assert_eq!(box_cox(4.0, 0.0), 4f64.ln());
+/// assert!(box_cox(-1.0, 0.5).is_nan());
+/// ```
+pub fn box_cox(x: f64, lambda: f64) -> f64 {
+    if x.is_nan() || x <= 0.0 || lambda.is_nan() {
+        f64::NAN
+    } else if lambda == 0.0 {
+        x.ln()
+    } else {
+        (lambda * x.ln()).exp_m1() / lambda
+    }
+}
+
+/// Estimates the power of the Box-Cox transform of the positive `data` by
+/// maximum likelihood, the power that makes the transformed data closest
+/// to a normal sample
+///
+/// # Formula
+///
+/// The power maximizes the profile log-likelihood
+///
+/// ```text
+/// l(λ) = -(n / 2) ln(σ^2(λ)) + (λ - 1) Σ ln(x_i)
+/// ```
+///
+/// where `σ^2(λ)` is the variance, with divisor `n`, of the transformed
+/// data.
+///
+/// # Remarks
+///
+/// The maximum is searched for with Brent's method in `[-5, 5]`, as
+/// scipy's `boxcox_normmax` with `method = "mle"` does from the bracket
+/// `(-2, 2)`; a power at an end of the interval suggests that no power
+/// transform normalizes the data. Returns `f64::NAN` if `data` has fewer
+/// than two values, if a value is not positive and finite, or if all values
+/// are equal.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::transform::{box_cox, box_cox_lambda};
+///
+/// // the exponentials of a symmetric sample are normalized by the logarithm
+/// let data: Vec<f64> = [-1.5, -0.6, -0.2, 0.0, 0.2, 0.6, 1.5].iter().map(|x: &f64| x.exp()).collect();
+/// let lambda = box_cox_lambda(&data);
+/// assert!(lambda.abs() < 1e-6);
+/// ```
+pub fn box_cox_lambda(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    if data.len() < 2 || data.iter().any(|&x| !x.is_finite() || x <= 0.0) {
+        return f64::NAN;
+    }
+    if data.iter().all(|&x| x == data[0]) {
+        return f64::NAN;
+    }
+    let sum_ln: f64 = data.iter().map(|x| x.ln()).sum();
+    let negative_log_likelihood = |lambda: f64| {
+        let transformed: Vec<f64> = data.iter().map(|&x| box_cox(x, lambda)).collect();
+        let mean = transformed.iter().sum::<f64>() / n;
+        let variance = transformed
+            .iter()
+            .map(|t| (t - mean) * (t - mean))
+            .sum::<f64>()
+            / n;
+        0.5 * n * variance.ln() - (lambda - 1.0) * sum_ln
+    };
+    brent_min_with(
+        negative_log_likelihood,
+        -BOX_COX_BOUND,
+        BOX_COX_BOUND,
+        1e-10,
+        500,
+    )
+    .argmin
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::summation::expect_discrete;
+    use crate::distribution::{Binomial, Discrete, Poisson};
+    use crate::statistics::Statistics;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn poisson_samples(lambda: f64, n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let dist = Poisson::new(lambda).unwrap();
+        (0..n).map(|_| dist.sample(&mut rng)).collect()
+    }
+
+    /// the expected value of `f(X)` for `X` following a Poisson
+    /// distribution with mean `lambda`
+    fn poisson_expectation<F: Fn(f64) -> f64>(lambda: f64, f: F) -> f64 {
+        let dist = Poisson::new(lambda).unwrap();
+        expect_discrete(&dist, |k| f(k as f64), 1e-18).unwrap()
+    }
+
+    #[test]
+    fn test_poisson_variance_is_stabilized() {
+        for (i, &lambda) in [4.0, 6.0, 10.0, 20.0, 50.0, 200.0].iter().enumerate() {
+            let x = poisson_samples(lambda, 20000, i as u64);
+            let anscombe_x: Vec<f64> = x.iter().map(|&k| anscombe(k)).collect();
+            let freeman_tukey_x: Vec<f64> = x.iter().map(|&k| freeman_tukey(k)).collect();
+            assert!((anscombe_x.variance() - 1.0).abs() < 0.04, "{}", lambda);
+            assert!((freeman_tukey_x.variance() - 1.0).abs() < 0.04, "{}", lambda);
+            // the raw variance grows with the mean
+            assert!((x.variance() / lambda - 1.0).abs() < 0.05);
+        }
+        // exact variances, reference values computed with mpmath
+        let mean = poisson_expectation(4.0, anscombe);
+        assert_almost_eq!(poisson_expectation(4.0, |k| anscombe(k).powi(2)) - mean * mean, 0.998754, 1e-6);
+        let mean = poisson_expectation(4.0, freeman_tukey);
+        assert_almost_eq!(poisson_expectation(4.0, |k| freeman_tukey(k).powi(2)) - mean * mean, 1.0144, 1e-4);
+    }
+
+    #[test]
+    fn test_inverse_anscombe_is_unbiased() {
+        for &lambda in &[0.1, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 64.0, 150.0] {
+            let expected = poisson_expectation(lambda, anscombe);
+            let recovered = inverse_anscombe(expected);
+            assert!((recovered - lambda).abs() < 0.005 * lambda, "{}", lambda);
+            // the algebraic inverse is biased downwards
+            if lambda <= 16.0 {
+                let algebraic = (expected / 2.0).powi(2) - 0.375;
+                assert!(algebraic < 0.99 * lambda, "{}", lambda);
+            }
+        }
+        // the mean of simulated counts, through the transformed domain
+        for (i, &lambda) in [32.0, 100.0].iter().enumerate() {
+            let x = poisson_samples(lambda, 20000, 10 + i as u64);
+            let mean = x.iter().map(|&k| anscombe(k)).collect::<Vec<_>>().mean();
+            assert!((inverse_anscombe(mean) / x.clone().mean() - 1.0).abs() < 0.005);
+        }
+    }
+
+    #[test]
+    fn test_inverse_anscombe_boundary() {
+        assert_eq!(inverse_anscombe(anscombe(0.0)), 0.0);
+        assert!(inverse_anscombe(anscombe(0.0) + 1e-12) >= 0.0);
+        assert_eq!(inverse_anscombe(0.0), 0.0);
+        assert_eq!(inverse_anscombe(-3.0), 0.0);
+        assert!(inverse_anscombe(f64::NAN).is_nan());
+        // continuous where the formula reaches zero
+        assert!(inverse_anscombe(anscombe(0.0) + 1e-6) < 1e-5);
+        let mut previous = 0.0;
+        for i in 1..1000 {
+            let y = anscombe(0.0) + 0.01 * i as f64;
+            let x = inverse_anscombe(y);
+            assert!(x >= previous);
+            previous = x;
+        }
+    }
+
+    #[test]
+    fn test_boundaries() {
+        assert!(!anscombe(0.0).is_nan());
+        assert!(!freeman_tukey(0.0).is_nan());
+        assert!(anscombe(-1.0).is_nan());
+        assert!(freeman_tukey(-1e-300).is_nan());
+        assert!(anscombe(f64::NAN).is_nan());
+        assert_eq!(arcsine_sqrt(0.0), 0.0);
+        assert_eq!(arcsine_sqrt(1.0), f64::consts::FRAC_PI_2);
+        assert!(arcsine_sqrt(1.0 + 1e-15).is_nan());
+        assert!(arcsine_sqrt(-1e-300).is_nan());
+        assert_eq!(inverse_arcsine_sqrt(0.0), 0.0);
+        assert_eq!(inverse_arcsine_sqrt(f64::consts::FRAC_PI_2), 1.0);
+        assert!(inverse_arcsine_sqrt(2.0).is_nan());
+        for i in 0..=100 {
+            let p = i as f64 / 100.0;
+            assert_almost_eq!(inverse_arcsine_sqrt(arcsine_sqrt(p)), p, 1e-15);
+        }
+        assert!(vst_poisson(-1.0, 0.375).is_nan());
+        assert!(vst_poisson(1.0, -0.375).is_nan());
+        assert_eq!(vst_poisson(0.0, 0.0), 0.0);
+        assert!(vst_binomial(0.0, 0, 0.0).is_nan());
+        assert!(vst_binomial(11.0, 10, 0.0).is_nan());
+        assert!(vst_binomial(-1.0, 10, 0.0).is_nan());
+        assert!(vst_binomial(1.0, 10, f64::NAN).is_nan());
+        assert!(vst_binomial(10.0, 10, 0.375) < vst_binomial(10.0, 10, 0.0));
+    }
+
+    #[test]
+    fn test_binomial_variance_is_stabilized() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for &p in &[0.2, 0.5, 0.8] {
+            let dist = Binomial::new(p, 100).unwrap();
+            let x: Vec<f64> = (0..20000).map(|_| vst_binomial(dist.sample(&mut rng), 100, 0.375)).collect();
+            assert!((x.variance() - 1.0).abs() < 0.05, "{}", p);
+        }
+    }
+
+    #[test]
+    fn test_box_cox() {
+        assert_almost_eq!(box_cox(4.0, 0.5), 2.0, 1e-15);
+        assert_almost_eq!(box_cox(2.0, 1.0), 1.0, 1e-15);
+        assert_almost_eq!(box_cox(2.0, -1.0), 0.5, 1e-15);
+        // continuous at λ = 0
+        assert_almost_eq!(box_cox(3.0, 1e-12), 3f64.ln(), 1e-12);
+        assert_eq!(box_cox(3.0, 0.0), 3f64.ln());
+        assert!(box_cox(0.0, 1.0).is_nan());
+        assert!(box_cox(f64::NAN, 1.0).is_nan());
+        assert!(box_cox(1.0, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_box_cox_lambda() {
+        // the maximizer of the profile log-likelihood computed with mpmath
+        let data = [0.8, 2.1, 0.3, 1.7, 0.9, 3.4, 0.2, 1.1, 5.6, 0.5];
+        assert_almost_eq!(box_cox_lambda(&data), 0.0092396073990574063, 1e-8);
+        // the log-likelihood of the exponentials of a symmetric sample is
+        // even in λ, with its maximum at 0
+        let lognormal: Vec<f64> = [-1.5, -0.6, -0.2, 0.0, 0.2, 0.6, 1.5].iter().map(|x: &f64| x.exp()).collect();
+        assert_almost_eq!(box_cox_lambda(&lognormal), 0.0, 1e-7);
+        // the estimate is invariant to scaling the data
+        let scaled: Vec<f64> = data.iter().map(|x| 7.0 * x).collect();
+        assert_almost_eq!(box_cox_lambda(&scaled), box_cox_lambda(&data), 1e-8);
+    }
+
+    #[test]
+    fn test_box_cox_lambda_invalid() {
+        assert!(box_cox_lambda(&[]).is_nan());
+        assert!(box_cox_lambda(&[1.0]).is_nan());
+        assert!(box_cox_lambda(&[2.0, 2.0, 2.0]).is_nan());
+        assert!(box_cox_lambda(&[1.0, 0.0, 2.0]).is_nan());
+        assert!(box_cox_lambda(&[1.0, f64::INFINITY, 2.0]).is_nan());
+    }
+}
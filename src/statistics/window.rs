@@ -0,0 +1,211 @@
+//! Provides the [window functions](https://en.wikipedia.org/wiki/Window_function)
+//! that taper a series towards its ends before spectral analysis, reducing
+//! the leakage of power between frequencies
+//!
+//! All windows are the symmetric forms of length `n`, whose coefficients
+//! `w_k` for `k = 0, ..., n - 1` are computed on one half and mirrored, so
+//! that `w_k == w_{n - 1 - k}` holds exactly. A window of length `1` is
+//! `[1.0]` and a window of length `0` is empty.
+
+use crate::{Result, StatsError};
+use std::f64;
+use std::f64::consts::PI;
+
+/// Returns the `n` coefficients `f(k / (n - 1))` of a symmetric window
+/// given as a function of the relative position in `[0, 1]`
+fn symmetric<F: Fn(f64) -> f64>(n: usize, f: F) -> Vec<f64> {
+    if n == 1 {
+        return vec![1.0];
+    }
+    let mut window = vec![0.0; n];
+    for k in 0..n - n / 2 {
+        let w = f(k as f64 / (n - 1) as f64);
+        window[k] = w;
+        window[n - 1 - k] = w;
+    }
+    window
+}
+
+/// Returns the [Hann](https://en.wikipedia.org/wiki/Hann_function) window
+/// of length `n`, which is zero at both ends
+///
+/// # Formula
+///
+/// ```text
+/// w_k = 0.5 - 0.5 cos(2π k / (n - 1))
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::window::hann;
+///
+/// let w = hann(5);
+/// assert_eq!(w[0], 0.0);
+/// assert!((w[1] - 0.5).abs() < 1e-15);
+/// assert_eq!(w[2], 1.0);
+/// ```
+pub fn hann(n: usize) -> Vec<f64> {
+    symmetric(n, |x| 0.5 - 0.5 * (2.0 * PI * x).cos())
+}
+
+/// Returns the Hamming window of length `n`, whose coefficients are chosen
+/// to cancel the first side lobe of the Hann window, leaving `0.08` at the
+/// ends
+///
+/// # Formula
+///
+/// ```text
+/// w_k = 0.54 - 0.46 cos(2π k / (n - 1))
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::window::hamming;
+///
+/// let w = hamming(3);
+/// assert!((w[0] - 0.08).abs() < 1e-15);
+/// assert_eq!(w[1], 1.0);
+/// ```
+pub fn hamming(n: usize) -> Vec<f64> {
+    symmetric(n, |x| 0.54 - 0.46 * (2.0 * PI * x).cos())
+}
+
+/// Returns the Blackman window of length `n`, whose side lobes are lower
+/// than those of the Hann and Hamming windows at the cost of a wider main
+/// lobe
+///
+/// # Formula
+///
+/// ```text
+/// w_k = 0.42 - 0.5 cos(2π k / (n - 1)) + 0.08 cos(4π k / (n - 1))
+/// ```
+///
+/// # Remarks
+///
+/// The coefficients at the ends are zero up to rounding, within `1e-16`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::window::blackman;
+///
+/// let w = blackman(5);
+/// assert!((w[1] - 0.34).abs() < 1e-15);
+/// assert!((w[2] - 1.0).abs() < 1e-15);
+/// ```
+pub fn blackman(n: usize) -> Vec<f64> {
+    symmetric(n, |x| {
+        0.42 - 0.5 * (2.0 * PI * x).cos() + 0.08 * (4.0 * PI * x).cos()
+    })
+}
+
+/// Returns the triangular Bartlett window of length `n`, which is zero at
+/// both ends
+///
+/// # Formula
+///
+/// ```text
+/// w_k = 1 - |2k / (n - 1) - 1|
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::window::bartlett;
+///
+/// assert_eq!(bartlett(5), vec![0.0, 0.5, 1.0, 0.5, 0.0]);
+/// ```
+pub fn bartlett(n: usize) -> Vec<f64> {
+    symmetric(n, |x| 1.0 - (2.0 * x - 1.0).abs())
+}
+
+/// Multiplies `data` elementwise by the coefficients of `window`
+///
+/// # Errors
+///
+/// Returns an error if `data` and `window` have different lengths
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::window::{apply_window, hann};
+///
+/// let data = [2.0, 2.0, 2.0, 2.0, 2.0];
+/// let tapered = apply_window(&data, &hann(5)).unwrap();
+/// assert_eq!(tapered[0], 0.0);
+/// assert_eq!(tapered[2], 2.0);
+/// assert!(apply_window(&data, &hann(4)).is_err());
+/// ```
+pub fn apply_window(data: &[f64], window: &[f64]) -> Result<Vec<f64>> {
+    if data.len() != window.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    Ok(data.iter().zip(window).map(|(x, w)| x * w).collect())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS: [fn(usize) -> Vec<f64>; 4] = [hann, hamming, blackman, bartlett];
+
+    #[test]
+    fn test_hann_endpoints() {
+        for n in 2..40 {
+            let w = hann(n);
+            assert_eq!(w[0], 0.0);
+            assert_eq!(w[n - 1], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_symmetric() {
+        for window in WINDOWS.iter() {
+            for n in 0..40 {
+                let w = window(n);
+                assert_eq!(w.len(), n);
+                for k in 0..n {
+                    assert_eq!(w[k], w[n - 1 - k]);
+                }
+                // the peak is one, in the middle
+                if n % 2 == 1 {
+                    assert_almost_eq!(w[n / 2], 1.0, 1e-15);
+                }
+                assert!(w.iter().all(|&c| (-1e-16..=1.0).contains(&c)));
+            }
+            assert_eq!(window(1), vec![1.0]);
+        }
+    }
+
+    #[test]
+    fn test_definitions() {
+        // the first half of the windows of length 7, where the cosines are
+        // at multiples of 60 degrees
+        let expected: [[f64; 4]; 4] = [
+            [0.0, 0.25, 0.75, 1.0],
+            [0.08, 0.31, 0.77, 1.0],
+            [0.0, 0.13, 0.63, 1.0],
+            [0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0],
+        ];
+        for (window, e) in WINDOWS.iter().zip(expected.iter()) {
+            let w = window(7);
+            for k in 0..4 {
+                assert_almost_eq!(w[k], e[k], 1e-15);
+            }
+        }
+        // even lengths have no middle coefficient
+        assert_eq!(bartlett(4), vec![0.0, 2.0 / 3.0, 2.0 / 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_apply_window() {
+        let data = [1.0, -2.0, 3.0, 4.0];
+        assert_eq!(apply_window(&data, &[1.0; 4]).unwrap(), data.to_vec());
+        assert_eq!(apply_window(&data, &bartlett(4)).unwrap(), vec![0.0, -4.0 / 3.0, 2.0, 0.0]);
+        assert_eq!(apply_window(&[], &[]).unwrap(), Vec::<f64>::new());
+        assert!(apply_window(&data, &hann(3)).is_err());
+    }
+}
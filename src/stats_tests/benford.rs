@@ -0,0 +1,412 @@
+//! Provides tests of the conformity of data to
+//! [Benford's law](https://en.wikipedia.org/wiki/Benford%27s_law) as used in
+//! forensic accounting, following Nigrini (2012), "Benford's Law:
+//! Applications for Forensic Accounting, Auditing, and Fraud Detection"
+//!
+//! Leading digits are read from the shortest decimal representation of the
+//! magnitude of each value, so negative values count by their absolute value
+//! and values such as `0.00456` or `9.99e-7` yield the digits as written
+//! rather than those of a nearby binary approximation. Zeros have no leading
+//! digit and are excluded and counted.
+
+use crate::distribution::{ChiSquared, ContinuousCDF};
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The degree of conformity to Benford's law assigned by Nigrini's
+/// thresholds on the mean absolute deviation
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Conformity {
+    /// Close conformity
+    Close,
+    /// Acceptable conformity
+    Acceptable,
+    /// Marginally acceptable conformity
+    Marginal,
+    /// Nonconformity
+    Nonconformity,
+}
+
+impl Conformity {
+    /// Classifies `mad` by the upper bounds of the first three classes
+    fn classify(mad: f64, bounds: [f64; 3]) -> Conformity {
+        if mad <= bounds[0] {
+            Conformity::Close
+        } else if mad <= bounds[1] {
+            Conformity::Acceptable
+        } else if mad <= bounds[2] {
+            Conformity::Marginal
+        } else {
+            Conformity::Nonconformity
+        }
+    }
+}
+
+/// The observed and expected proportions of a leading digit or group of
+/// leading digits
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DigitProportion {
+    /// The leading digits, such as `7` for the first digit test or `42` for
+    /// the first-two digits test
+    pub digits: u32,
+    /// The observed proportion
+    pub observed: f64,
+    /// The proportion expected under Benford's law
+    pub expected: f64,
+}
+
+/// The digit proportions and deviation measures of a digit test of
+/// conformity to Benford's law, which accompany its chi-squared test
+#[derive(Clone, PartialEq, Debug)]
+pub struct BenfordReport {
+    /// The number of values with a leading digit
+    pub n: usize,
+    /// The number of zeros, which were excluded
+    pub excluded: usize,
+    /// The observed and expected proportions of every digit group, in
+    /// increasing order
+    pub proportions: Vec<DigitProportion>,
+    /// The mean absolute deviation between the observed and expected
+    /// proportions
+    pub mad: f64,
+    /// The largest absolute deviation between the observed and expected
+    /// cumulative proportions, the Kolmogorov-Smirnov statistic
+    pub ks: f64,
+    /// Nigrini's conformity class of `mad`
+    pub conformity: Conformity,
+}
+
+/// The result of the summation test of Benford's law
+#[derive(Clone, PartialEq, Debug)]
+pub struct SummationReport {
+    /// The number of values with a leading digit
+    pub n: usize,
+    /// The number of zeros, which were excluded
+    pub excluded: usize,
+    /// The observed proportion of the total magnitude carried by the values
+    /// with each of the first-two digits `10` to `99`, all expected to be
+    /// `1 / 90`
+    pub proportions: Vec<DigitProportion>,
+    /// The mean absolute deviation between the observed and expected
+    /// proportions
+    pub mad: f64,
+}
+
+/// The largest number of leading digits that fits in a `u32`
+const MAX_DIGITS: usize = 9;
+
+/// Returns the first `count` significant digits of `x` as an integer, or
+/// `None` if `x` is zero or not finite, or if `count` is not in `1..=9` so
+/// that the digits would not fit in a `u32`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::benford::leading_digits;
+///
+/// assert_eq!(leading_digits(0.00456, 1), Some(4));
+/// assert_eq!(leading_digits(9.99e-7, 2), Some(99));
+/// assert_eq!(leading_digits(-1234.5, 2), Some(12));
+/// assert_eq!(leading_digits(3.0, 2), Some(30));
+/// assert_eq!(leading_digits(0.0, 1), None);
+/// assert_eq!(leading_digits(3.0, 10), None);
+/// ```
+pub fn leading_digits(x: f64, count: usize) -> Option<u32> {
+    if x == 0.0 || !x.is_finite() || !(1..=MAX_DIGITS).contains(&count) {
+        return None;
+    }
+    // the shortest representation that round-trips, as in "4.56e-3"
+    let repr = format!("{:e}", x.abs());
+    let mantissa = repr.split('e').next()?;
+    let mut digits = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| u32::from(b - b'0'));
+    Some((0..count).fold(0, |acc, _| 10 * acc + digits.next().unwrap_or(0)))
+}
+
+/// Extracts the leading digits of the non-zero values of `data`, returning
+/// them with the number of zeros
+fn extract(data: &[f64], count: usize) -> Result<(Vec<(u32, f64)>, usize)> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("data must be finite"));
+    }
+    let digits: Vec<(u32, f64)> = data
+        .iter()
+        .filter_map(|&x| leading_digits(x, count).map(|d| (d, x.abs())))
+        .collect();
+    if digits.is_empty() {
+        return Err(StatsError::SpecialCase(
+            "data must contain a non-zero value",
+        ));
+    }
+    let excluded = data.len() - digits.len();
+    Ok((digits, excluded))
+}
+
+/// Runs the digit test named `method` of the leading digit groups
+/// `low..=high` with Nigrini's `bounds` on the mean absolute deviation
+fn digit_test(
+    method: &'static str,
+    data: &[f64],
+    count: usize,
+    low: u32,
+    high: u32,
+    bounds: [f64; 3],
+) -> Result<(TestResult, BenfordReport)> {
+    let (digits, excluded) = extract(data, count)?;
+    let mut counts = vec![0usize; (high - low + 1) as usize];
+    for &(d, _) in &digits {
+        counts[(d - low) as usize] += 1;
+    }
+    let n = digits.len();
+    let proportions: Vec<DigitProportion> = (low..=high)
+        .zip(&counts)
+        .map(|(d, &c)| DigitProportion {
+            digits: d,
+            observed: c as f64 / n as f64,
+            expected: (1.0 / d as f64).ln_1p() / f64::consts::LN_10,
+        })
+        .collect();
+    let chi_squared: f64 = proportions
+        .iter()
+        .map(|p| n as f64 * (p.observed - p.expected).powi(2) / p.expected)
+        .sum();
+    let freedom = (proportions.len() - 1) as f64;
+    let p_value = ChiSquared::new(freedom).unwrap().sf(chi_squared);
+    let test = TestResult::new(
+        method,
+        "X-squared",
+        chi_squared,
+        p_value,
+        Alternative::TwoSided,
+        vec![n],
+    )
+    .with_df(freedom);
+    let mad = mean_absolute_deviation(&proportions);
+    let (mut observed, mut expected, mut ks) = (0.0, 0.0, 0.0f64);
+    for p in &proportions {
+        observed += p.observed;
+        expected += p.expected;
+        ks = ks.max((observed - expected).abs());
+    }
+    let report = BenfordReport {
+        n,
+        excluded,
+        proportions,
+        mad,
+        ks,
+        conformity: Conformity::classify(mad, bounds),
+    };
+    Ok((test, report))
+}
+
+/// Returns the mean absolute deviation between the observed and expected
+/// proportions
+fn mean_absolute_deviation(proportions: &[DigitProportion]) -> f64 {
+    let sum: f64 = proportions
+        .iter()
+        .map(|p| (p.observed - p.expected).abs())
+        .sum();
+    sum / proportions.len() as f64
+}
+
+/// Tests the conformity of the first significant digits of `data` to
+/// Benford's law, `P(d) = log10(1 + 1/d)` for `d` in `1..=9`, with
+/// Pearson's chi-squared test of the digit counts on `8` degrees of freedom
+/// and the report of the digit proportions and deviations
+///
+/// # Remarks
+///
+/// The conformity class uses Nigrini's bounds `0.006`, `0.012` and `0.015`
+/// on the mean absolute deviation, which unlike the chi-squared p-value
+/// does not reject every large data set for immaterial deviations.
+///
+/// # Errors
+///
+/// Returns an error if `data` contains a non-finite value or no non-zero
+/// value
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::benford::{first_digit_test, Conformity};
+///
+/// // the powers of two follow Benford's law
+/// let data: Vec<f64> = (0..500).map(|k| 2f64.powi(k)).collect();
+/// let (result, report) = first_digit_test(&data).unwrap();
+/// assert!(result.p_value > 0.5);
+/// assert_eq!(report.proportions[0].digits, 1);
+/// assert_eq!(report.conformity, Conformity::Close);
+/// ```
+pub fn first_digit_test(data: &[f64]) -> Result<(TestResult, BenfordReport)> {
+    digit_test(
+        "Benford first digit test",
+        data,
+        1,
+        1,
+        9,
+        [0.006, 0.012, 0.015],
+    )
+}
+
+/// Tests the conformity of the first two significant digits of `data` to
+/// Benford's law, `P(d) = log10(1 + 1/d)` for `d` in `10..=99`, with
+/// Pearson's chi-squared test of the digit counts on `89` degrees of
+/// freedom and the report of the digit proportions and deviations
+///
+/// # Remarks
+///
+/// The conformity class uses Nigrini's bounds `0.0012`, `0.0018` and
+/// `0.0022` on the mean absolute deviation. A value with a single
+/// significant digit such as `3` counts as `30`.
+///
+/// # Errors
+///
+/// Returns an error if `data` contains a non-finite value or no non-zero
+/// value
+pub fn first_two_digits_test(data: &[f64]) -> Result<(TestResult, BenfordReport)> {
+    digit_test(
+        "Benford first-two digits test",
+        data,
+        2,
+        10,
+        99,
+        [0.0012, 0.0018, 0.0022],
+    )
+}
+
+/// Performs Nigrini's summation test, comparing the share of the total
+/// magnitude of `data` carried by each of the first-two digits `10` to `99`
+/// with the equal shares expected under Benford's law
+///
+/// # Remarks
+///
+/// Large shares point to a few unusually large values or to many values
+/// with the same leading digits, such as duplicated transactions. The test
+/// has no p-value since a few large values dominate the sums.
+///
+/// # Errors
+///
+/// Returns an error if `data` contains a non-finite value or no non-zero
+/// value
+pub fn summation_test(data: &[f64]) -> Result<SummationReport> {
+    let (digits, excluded) = extract(data, 2)?;
+    let mut sums = [0.0; 90];
+    for &(d, x) in &digits {
+        sums[(d - 10) as usize] += x;
+    }
+    let total: f64 = sums.iter().sum();
+    let proportions: Vec<DigitProportion> = (10..=99)
+        .zip(&sums)
+        .map(|(d, &s)| DigitProportion {
+            digits: d,
+            observed: s / total,
+            expected: 1.0 / 90.0,
+        })
+        .collect();
+    Ok(SummationReport {
+        n: digits.len(),
+        excluded,
+        mad: mean_absolute_deviation(&proportions),
+        proportions,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_leading_digits() {
+        assert_eq!(leading_digits(0.00456, 1), Some(4));
+        assert_eq!(leading_digits(0.00456, 2), Some(45));
+        assert_eq!(leading_digits(0.00456, 3), Some(456));
+        assert_eq!(leading_digits(9.99e-7, 1), Some(9));
+        assert_eq!(leading_digits(9.99e-7, 2), Some(99));
+        assert_eq!(leading_digits(-87.2, 2), Some(87));
+        assert_eq!(leading_digits(-0.0, 1), None);
+        assert_eq!(leading_digits(1000.0, 2), Some(10));
+        assert_eq!(leading_digits(1e300, 1), Some(1));
+        assert_eq!(leading_digits(5e-324, 2), Some(50));
+        assert_eq!(leading_digits(0.3, 2), Some(30));
+        assert_eq!(leading_digits(0.1 + 0.2, 2), Some(30));
+        assert_eq!(leading_digits(f64::NAN, 1), None);
+        assert_eq!(leading_digits(f64::INFINITY, 1), None);
+        // nine digits are the most that fit in a u32
+        assert_eq!(leading_digits(4294967295.0, 9), Some(429496729));
+        assert_eq!(leading_digits(9.87654321987e20, 9), Some(987654321));
+        assert_eq!(leading_digits(9.87654321987e20, 10), None);
+        assert_eq!(leading_digits(1.5, 0), None);
+        assert_eq!(leading_digits(1.5, usize::MAX), None);
+    }
+
+    #[test]
+    fn test_conforming_data() {
+        // products of many uniforms have nearly uniform mantissas on the
+        // log scale
+        let mut rng = StdRng::seed_from_u64(277);
+        let data: Vec<f64> = (0..5000)
+            .map(|_| 1e6 * (0..12).map(|_| rng.gen::<f64>()).product::<f64>())
+            .collect();
+        let (test, report) = first_digit_test(&data).unwrap();
+        assert_eq!(report.n, 5000);
+        assert!(test.p_value > 0.01, "{}", test.p_value);
+        assert_ne!(report.conformity, Conformity::Nonconformity);
+        assert!(report.ks < 0.02);
+        let (test, report) = first_two_digits_test(&data).unwrap();
+        assert!(test.p_value > 0.01, "{}", test.p_value);
+        assert_ne!(report.conformity, Conformity::Nonconformity);
+        let report = summation_test(&data).unwrap();
+        assert!(report.mad < 0.01);
+    }
+
+    #[test]
+    fn test_uniform_data() {
+        let mut rng = StdRng::seed_from_u64(2770);
+        let data: Vec<f64> = (0..5000).map(|_| rng.gen_range(1.0..1000.0)).collect();
+        let (test, report) = first_digit_test(&data).unwrap();
+        assert!(test.p_value < 1e-10);
+        assert_eq!(report.conformity, Conformity::Nonconformity);
+        let (test, report) = first_two_digits_test(&data).unwrap();
+        assert!(test.p_value < 1e-10);
+        assert_eq!(report.conformity, Conformity::Nonconformity);
+    }
+
+    #[test]
+    fn test_report() {
+        let data = [0.0, 1.0, -1.5, 19.0, 0.0, 2.5, 3e-5, 9.0, -0.0];
+        let (test, report) = first_digit_test(&data).unwrap();
+        assert_eq!(report.n, 6);
+        assert_eq!(report.excluded, 3);
+        assert_eq!(report.proportions.len(), 9);
+        assert_eq!(report.proportions[0].observed, 0.5);
+        assert_almost_eq!(report.proportions[0].expected, 2f64.log10(), 1e-15);
+        assert_almost_eq!(report.proportions.iter().map(|p| p.expected).sum::<f64>(), 1.0, 1e-15);
+        // the counts are 3, 1, 1, 0, 0, 0, 0, 0, 1
+        let expected: Vec<f64> = (1..=9).map(|d| (1.0 + 1.0 / d as f64).log10()).collect();
+        let counts = [3.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        let chi_squared: f64 = (0..9).map(|i| (counts[i] - 6.0 * expected[i]).powi(2) / (6.0 * expected[i])).sum();
+        assert_almost_eq!(test.statistic, chi_squared, 1e-12);
+        assert_eq!(test.df, Some(8.0));
+        let mad = (0..9).map(|i| (counts[i] / 6.0 - expected[i]).abs()).sum::<f64>() / 9.0;
+        assert_almost_eq!(report.mad, mad, 1e-15);
+
+        let report = summation_test(&[12.0, 1.2, 45.0, 0.0]).unwrap();
+        assert_eq!(report.excluded, 1);
+        assert_almost_eq!(report.proportions[2].observed, 13.2 / 58.2, 1e-15);
+        assert_almost_eq!(report.proportions[35].observed, 45.0 / 58.2, 1e-15);
+    }
+
+    #[test]
+    fn test_bad_data() {
+        assert!(first_digit_test(&[]).is_err());
+        assert!(first_digit_test(&[0.0, -0.0]).is_err());
+        assert!(first_two_digits_test(&[1.0, f64::NAN]).is_err());
+        assert!(summation_test(&[1.0, f64::INFINITY]).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Provides tests of uniformity for directional data, angles in radians on
+//! the circle
+
+use crate::statistics::circular::mean_resultant;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Performs the Rayleigh test of the null hypothesis that `angles` are
+/// spread uniformly around the circle, against a unimodal alternative.
+/// Returns the mean resultant length `R` as the statistic with the p-value
+/// of R's `circular::rayleigh.test`.
+///
+/// # Formula
+///
+/// ```text
+/// Z = n R^2
+/// p = e^(-Z) (1 + (2Z - Z^2) / (4n) - (24Z - 132Z^2 + 76Z^3 - 9Z^4) / (288n^2))
+/// ```
+///
+/// where `n` is the number of angles. The leading term `e^(-Z)` is the
+/// large-sample p-value and the remaining terms correct it for small `n`.
+///
+/// # Remarks
+///
+/// The p-value is clamped to `[0, 1]`. The test has little power against
+/// alternatives with several modes, such as two opposite directions.
+///
+/// # Errors
+///
+/// Returns an error if `angles` is empty or contains a non-finite angle
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::circular::rayleigh_test;
+///
+/// let angles = [0.1, 0.3, 0.35, 0.6, 1.2, 5.9, 0.8, 0.45];
+/// let (r, p): (f64, f64) = rayleigh_test(&angles).unwrap().into();
+/// assert!((r - 0.90715356122100459).abs() < 1e-14);
+/// assert!(p < 0.001);
+/// ```
+pub fn rayleigh_test(angles: &[f64]) -> Result<TestResult> {
+    if angles.is_empty() {
+        return Err(StatsError::SpecialCase("angles must not be empty"));
+    }
+    if angles.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("angles must be finite"));
+    }
+    let (c, s) = mean_resultant(angles).unwrap();
+    let n = angles.len() as f64;
+    let r = c.hypot(s);
+    let z = n * r * r;
+    let correction = 1.0 + (2.0 * z - z * z) / (4.0 * n)
+        - (24.0 * z - 132.0 * z * z + 76.0 * z.powi(3) - 9.0 * z.powi(4)) / (288.0 * n * n);
+    Ok(TestResult::new(
+        "Rayleigh test of uniformity",
+        "R",
+        r,
+        ((-z).exp() * correction).clamp(0.0, 1.0),
+        Alternative::TwoSided,
+        vec![angles.len()],
+    ))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_rayleigh_reference() {
+        // reference values computed with mpmath
+        let (r, p): (f64, f64) = rayleigh_test(&[0.1, 0.3, 0.35, 0.6, 1.2, 5.9, 0.8, 0.45]).unwrap().into();
+        assert_almost_eq!(r, 0.90715356122100459217, 1e-14);
+        assert_almost_eq!(p, 0.00013772346197121720017, 1e-16);
+        let (r, p): (f64, f64) = rayleigh_test(&[0.2, 1.9, 2.5, 4.0, 5.1, 3.3, 0.9, 6.0, 1.4, 4.6]).unwrap().into();
+        assert_almost_eq!(r, 0.046137075113191093934, 1e-14);
+        assert_almost_eq!(p, 0.97995411027328832819, 1e-13);
+    }
+
+    #[test]
+    fn test_rayleigh_clustered_and_uniform() {
+        let clustered: Vec<f64> = (0..30).map(|i| 2.0 + 0.6 * ((i * 7 % 30) as f64 / 30.0 - 0.5)).collect();
+        let result = rayleigh_test(&clustered).unwrap();
+        assert!(result.rejects(0.001));
+        assert!(result.statistic > 0.9);
+        let spread: Vec<f64> = (0..30).map(|i| 2.0 * PI * i as f64 / 30.0 + 0.05).collect();
+        let result = rayleigh_test(&spread).unwrap();
+        assert!(!result.rejects(0.5));
+        assert!(result.statistic < 1e-14);
+        assert_eq!(result.p_value, 1.0);
+        // two opposite clusters cancel out
+        let bimodal: Vec<f64> = clustered.iter().enumerate().map(|(i, x)| x + PI * (i % 2) as f64).collect();
+        assert!(!rayleigh_test(&bimodal).unwrap().rejects(0.05));
+    }
+
+    #[test]
+    fn test_rayleigh_bad_input() {
+        assert!(rayleigh_test(&[]).is_err());
+        assert!(rayleigh_test(&[1.0, f64::NAN]).is_err());
+        assert!(rayleigh_test(&[1.0, f64::INFINITY]).is_err());
+        let p = rayleigh_test(&[1.0; 40]).unwrap().p_value;
+        assert!((0.0..1e-15).contains(&p));
+    }
+}
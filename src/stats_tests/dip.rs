@@ -0,0 +1,374 @@
+//! Provides Hartigan's [dip test](https://doi.org/10.1214/aos/1176346577)
+//! of unimodality, a test for multimodality that complements the peak
+//! finding of
+//! [`KernelDensity::kde_modes`](crate::statistics::kde::KernelDensity::kde_modes)
+
+use crate::rng_util::SplitMix64;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::f64;
+
+/// The number of uniform samples simulated to calibrate the p-value
+const NULL_SAMPLES: usize = 2000;
+
+/// The largest size of the simulated uniform samples; the p-value of larger
+/// samples compares `sqrt(n) * dip`, whose null distribution converges
+const NULL_SIZE: usize = 1000;
+
+/// The seed of the simulated uniform samples, so that the p-value of given
+/// data never changes
+const NULL_SEED: u64 = 0x0064_6970_7465_7374;
+
+/// Computes Hartigan's dip statistic of `data`, the largest distance between
+/// the empirical distribution function and the closest unimodal
+/// distribution function
+///
+/// # Formula
+///
+/// ```text
+/// D = inf_F sup_x |F_n(x) - F(x)|
+/// ```
+///
+/// where `F_n` is the empirical distribution function and the infimum is
+/// over the unimodal distribution functions `F`, those convex up to the
+/// mode and concave beyond. It is computed with the algorithm AS 217 of
+/// Hartigan (1985), as R's `diptest::dip`, in `O(n log n)` time for the
+/// sort.
+///
+/// # Remarks
+///
+/// The dip is at least `1 / (2n)`, its value for fewer than four
+/// observations or identical ones, and at most `1 / 4`. The data is sorted
+/// internally. Returns `f64::NAN` if `data` is empty or contains a `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dip::dip_statistic;
+///
+/// // evenly spaced data is as unimodal as possible
+/// assert!((dip_statistic(&[1.0, 2.0, 3.0, 4.0, 5.0]) - 0.1).abs() < 1e-15);
+/// ```
+pub fn dip_statistic(data: &[f64]) -> f64 {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted_dip(&sorted)
+}
+
+/// Performs Hartigan's dip test of the null hypothesis that `data` is drawn
+/// from a unimodal distribution against the alternative that it has at
+/// least two modes
+///
+/// The p-value is calibrated against the uniform distribution, the least
+/// favourable unimodal null (Hartigan & Hartigan, 1985): it is the
+/// proportion of `2000` simulated uniform samples whose dip is at least the
+/// observed one, counting the observation itself, i.e.
+/// `(1 + #{D_null >= D}) / 2001`. Samples larger than `1000` are compared
+/// on the scale `sqrt(n) * D` with uniform samples of `1000`, whose null
+/// distribution is close to the limiting one. The simulation uses a fixed
+/// seed, so the result is reproducible.
+///
+/// # Remarks
+///
+/// A small p-value is evidence of at least two modes; a large one does not
+/// rule them out, since the test has little power against modes of very
+/// different sizes. The result is reported with a two-sided alternative,
+/// as for the other omnibus tests. The calibration takes
+/// `O(2000 m log m)` time for `m = min(n, 1000)`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains a `NaN`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dip::dip_test;
+///
+/// let mut data: Vec<f64> = (0..50).map(|i| i as f64 / 50.0).collect();
+/// data.extend((0..50).map(|i| 10.0 + i as f64 / 50.0));
+/// let result = dip_test(&data).unwrap();
+/// assert!(result.statistic > 0.2);
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn dip_test(data: &[f64]) -> Result<TestResult> {
+    if data.is_empty() {
+        return Err(StatsError::SpecialCase("data must not be empty"));
+    }
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::SpecialCase("data must not contain NaN"));
+    }
+    let dip = dip_statistic(data);
+    let n = data.len();
+    let size = n.min(NULL_SIZE);
+    let scaled = dip * (n as f64 / size as f64).sqrt();
+    let mut rng = SplitMix64::new(NULL_SEED);
+    let mut sample = vec![0.0; size];
+    let exceed = (0..NULL_SAMPLES)
+        .filter(|_| {
+            for x in sample.iter_mut() {
+                *x = rng.gen::<f64>();
+            }
+            sample.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            sorted_dip(&sample) >= scaled
+        })
+        .count();
+    Ok(TestResult::new(
+        "Hartigans' dip test for unimodality",
+        "D",
+        dip,
+        (1 + exceed) as f64 / (1 + NULL_SAMPLES) as f64,
+        Alternative::TwoSided,
+        vec![n],
+    ))
+}
+
+/// Computes the dip of non-empty sorted data with the algorithm AS 217,
+/// working with `2n` times the dip and 1-based indices as the original
+fn sorted_dip(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    let mut x = Vec::with_capacity(n + 1);
+    x.push(f64::NAN);
+    x.extend_from_slice(sorted);
+    let mut dip = 1.0;
+    if n < 2 || x[n] == x[1] {
+        return dip / (2 * n) as f64;
+    }
+
+    // the indices combined by the greatest convex minorant
+    let mut mn = vec![0; n + 1];
+    mn[1] = 1;
+    for j in 2..=n {
+        mn[j] = j - 1;
+        loop {
+            let mnj = mn[j];
+            let mnmnj = mn[mnj];
+            if mnj == 1
+                || (x[j] - x[mnj]) * (mnj as f64 - mnmnj as f64)
+                    < (x[mnj] - x[mnmnj]) * (j as f64 - mnj as f64)
+            {
+                break;
+            }
+            mn[j] = mnmnj;
+        }
+    }
+    // the indices combined by the least concave majorant
+    let mut mj = vec![0; n + 1];
+    mj[n] = n;
+    for k in (1..n).rev() {
+        mj[k] = k + 1;
+        loop {
+            let mjk = mj[k];
+            let mjmjk = mj[mjk];
+            if mjk == n
+                || (x[k] - x[mjk]) * (mjk as f64 - mjmjk as f64)
+                    < (x[mjk] - x[mjmjk]) * (k as f64 - mjk as f64)
+            {
+                break;
+            }
+            mj[k] = mjmjk;
+        }
+    }
+
+    let (mut low, mut high) = (1, n);
+    let mut gcm = vec![0; n + 2];
+    let mut lcm = vec![0; n + 2];
+    loop {
+        // the change points of the minorant from high down to low
+        gcm[1] = high;
+        let mut i = 1;
+        while gcm[i] > low {
+            gcm[i + 1] = mn[gcm[i]];
+            i += 1;
+        }
+        let l_gcm = i;
+        let mut ig = l_gcm;
+        let mut ix = ig - 1;
+        // the change points of the majorant from low up to high
+        lcm[1] = low;
+        let mut i = 1;
+        while lcm[i] < high {
+            lcm[i + 1] = mj[lcm[i]];
+            i += 1;
+        }
+        let l_lcm = i;
+        let mut ih = l_lcm;
+        let mut iv = 2;
+
+        // the largest distance between the minorant and the majorant
+        let mut d = 0.0;
+        if l_gcm != 2 || l_lcm != 2 {
+            loop {
+                let (gcm_ix, lcm_iv) = (gcm[ix], lcm[iv]);
+                if gcm_ix > lcm_iv {
+                    let gcm_next = gcm[ix + 1];
+                    let dx = (lcm_iv as f64 - gcm_next as f64 + 1.0)
+                        - (x[lcm_iv] - x[gcm_next]) * (gcm_ix - gcm_next) as f64
+                            / (x[gcm_ix] - x[gcm_next]);
+                    iv += 1;
+                    if dx >= d {
+                        d = dx;
+                        ig = ix + 1;
+                        ih = iv - 1;
+                    }
+                } else {
+                    let lcm_prev = lcm[iv - 1];
+                    let dx = (x[gcm_ix] - x[lcm_prev]) * (lcm_iv - lcm_prev) as f64
+                        / (x[lcm_iv] - x[lcm_prev])
+                        - (gcm_ix as f64 - lcm_prev as f64 - 1.0);
+                    ix -= 1;
+                    if dx >= d {
+                        d = dx;
+                        ig = ix + 1;
+                        ih = iv;
+                    }
+                }
+                ix = ix.max(1);
+                iv = iv.min(l_lcm);
+                if gcm[ix] == lcm[iv] {
+                    break;
+                }
+            }
+        } else {
+            d = 1.0;
+        }
+        if d < dip {
+            break;
+        }
+
+        // the dips of the minorant and the majorant for the current interval
+        let mut dip_l: f64 = 0.0;
+        for j in ig..l_gcm {
+            let (jb, je) = (gcm[j + 1], gcm[j]);
+            let mut max_t: f64 = 1.0;
+            if je - jb > 1 && x[je] != x[jb] {
+                let c = (je - jb) as f64 / (x[je] - x[jb]);
+                for jj in jb..=je {
+                    max_t = max_t.max((jj - jb + 1) as f64 - (x[jj] - x[jb]) * c);
+                }
+            }
+            dip_l = dip_l.max(max_t);
+        }
+        let mut dip_u: f64 = 0.0;
+        for j in ih..l_lcm {
+            let (jb, je) = (lcm[j], lcm[j + 1]);
+            let mut max_t: f64 = 1.0;
+            if je - jb > 1 && x[je] != x[jb] {
+                let c = (je - jb) as f64 / (x[je] - x[jb]);
+                for jj in jb..=je {
+                    max_t = max_t.max((x[jj] - x[jb]) * c - (jj as f64 - jb as f64 - 1.0));
+                }
+            }
+            dip_u = dip_u.max(max_t);
+        }
+        dip = dip.max(dip_l.max(dip_u));
+
+        // stop once the modal interval no longer shrinks
+        if low == gcm[ig] && high == lcm[ih] {
+            break;
+        }
+        low = gcm[ig];
+        high = lcm[ih];
+    }
+    dip / (2 * n) as f64
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_dip_minimum() {
+        // evenly spaced points are fitted by the uniform distribution
+        for &n in &[4usize, 10, 37] {
+            let data: Vec<f64> = (0..n).map(|i| i as f64).collect();
+            assert_almost_eq!(dip_statistic(&data), 0.5 / n as f64, 1e-15);
+        }
+        assert_eq!(dip_statistic(&[3.0]), 0.5);
+        assert_eq!(dip_statistic(&[2.0, 1.0, 3.0]), 1.0 / 6.0);
+        assert_eq!(dip_statistic(&[2.0; 5]), 0.1);
+    }
+
+    #[test]
+    fn test_dip_two_clusters() {
+        // two equal point masses are as far from unimodal as possible
+        let mut data = vec![0.0; 20];
+        data.extend(vec![1.0; 20]);
+        assert_almost_eq!(dip_statistic(&data), 0.25, 1e-15);
+        let mut data: Vec<f64> = (0..30).map(|i| i as f64 / 30.0).collect();
+        data.extend((0..30).map(|i| 5.0 + i as f64 / 30.0));
+        let dip = dip_statistic(&data);
+        assert!(dip > 0.2 && dip <= 0.25);
+    }
+
+    #[test]
+    fn test_dip_invariance() {
+        let mut rng = StdRng::seed_from_u64(2855);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..200).map(|_| normal.sample(&mut rng)).collect();
+        let dip = dip_statistic(&data);
+        // the dip does not depend on the order, location or scale
+        let mut reversed = data.clone();
+        reversed.reverse();
+        assert_eq!(dip_statistic(&reversed), dip);
+        let affine: Vec<f64> = data.iter().map(|x| 3.0 - 2.0 * x).collect();
+        assert_almost_eq!(dip_statistic(&affine), dip, 1e-12);
+        assert!((0.5 / 200.0..=0.25).contains(&dip));
+    }
+
+    #[test]
+    fn test_dip_test_unimodal() {
+        let mut rng = StdRng::seed_from_u64(2856);
+        let normal = Normal::new(5.0, 2.0).unwrap();
+        let data: Vec<f64> = (0..300).map(|_| normal.sample(&mut rng)).collect();
+        let result = dip_test(&data).unwrap();
+        assert!(result.statistic < 0.03);
+        assert!(result.p_value > 0.1);
+        assert_eq!(result.sample_sizes, vec![300]);
+        // the calibration is reproducible
+        assert_eq!(dip_test(&data).unwrap(), result);
+    }
+
+    #[test]
+    fn test_dip_test_bimodal() {
+        let mut rng = StdRng::seed_from_u64(2857);
+        let left = Normal::new(-4.0, 1.0).unwrap();
+        let right = Normal::new(4.0, 1.0).unwrap();
+        let mut data: Vec<f64> = (0..150).map(|_| left.sample(&mut rng)).collect();
+        data.extend((0..150).map(|_| right.sample(&mut rng)));
+        let result = dip_test(&data).unwrap();
+        assert!(result.statistic > 0.08);
+        assert_almost_eq!(result.p_value, 1.0 / 2001.0, 1e-15);
+    }
+
+    #[test]
+    fn test_dip_test_uniform_calibration() {
+        // uniform samples are the null, so their p-values are not small
+        let mut rng = StdRng::seed_from_u64(2858);
+        let small = (0..20)
+            .filter(|_| {
+                let data: Vec<f64> = (0..100).map(|_| rng.gen::<f64>()).collect();
+                dip_test(&data).unwrap().p_value < 0.05
+            })
+            .count();
+        assert!(small <= 4);
+    }
+
+    #[test]
+    fn test_dip_bad_input() {
+        assert!(dip_statistic(&[]).is_nan());
+        assert!(dip_test(&[]).is_err());
+        assert!(dip_test(&[1.0, f64::NAN]).is_err());
+        // the smallest dip is never significant
+        assert_eq!(dip_test(&[1.0, 1.0, 1.0]).unwrap().p_value, 1.0);
+    }
+}
@@ -0,0 +1,388 @@
+//! Provides tests of Poisson overdispersion in count data, where the
+//! variance of the counts exceeds their mean
+
+use crate::distribution::{ChiSquared, ContinuousCDF, Discrete, NegativeBinomial, Normal, Poisson};
+use crate::function::root;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The maximum number of halvings or doublings of the bracket of the size
+/// of the negative binomial fit
+const MAX_BRACKET_STEPS: usize = 200;
+
+/// The maximum likelihood fits of the Poisson and negative binomial
+/// distributions to count data, with the likelihood-ratio test of
+/// [`fit_poisson_vs_negbin`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct PoissonNegBinFit {
+    /// The Poisson fit, whose rate is the sample mean
+    pub poisson: Poisson,
+    /// The log-likelihood of the Poisson fit
+    pub poisson_log_likelihood: f64,
+    /// The negative binomial fit, or `None` if the likelihood is maximized
+    /// on the Poisson boundary, that is if the counts are not overdispersed
+    pub negative_binomial: Option<NegativeBinomial>,
+    /// The log-likelihood of the negative binomial fit, equal to that of
+    /// the Poisson fit on the boundary
+    pub negative_binomial_log_likelihood: f64,
+    /// The estimated dispersion `α = 1 / r` of the negative binomial fit,
+    /// `0` on the boundary
+    pub dispersion: f64,
+    /// The likelihood-ratio test of the null hypothesis `α = 0`, with the
+    /// boundary-corrected p-value
+    pub test: TestResult,
+}
+
+/// Tests the null hypothesis that `counts` are Poisson against the
+/// alternative that they are overdispersed with Cochran's variance test,
+/// the classical index of dispersion test. The p-value is one-sided.
+///
+/// # Formula
+///
+/// ```text
+/// D = Σ (x_i - x̄)² / x̄
+/// ```
+///
+/// `D` is compared with the chi-squared distribution with `n - 1` degrees
+/// of freedom. The estimate is the ratio `D / (n - 1)` of the sample
+/// variance to the mean.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two counts or if all counts
+/// are zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dispersion::dispersion_test;
+///
+/// let counts = [0, 0, 1, 0, 7, 2, 0, 0, 9, 1, 0, 3];
+/// let result = dispersion_test(&counts).unwrap();
+/// assert!(result.rejects(0.01));
+/// ```
+pub fn dispersion_test(counts: &[u64]) -> Result<TestResult> {
+    let (n, mean, squares) = moments(counts)?;
+    let freedom = (n - 1) as f64;
+    let index = squares / mean;
+    Ok(TestResult::new(
+        "Variance test of Poisson dispersion",
+        "D",
+        index,
+        ChiSquared::new(freedom).unwrap().sf(index),
+        Alternative::Greater,
+        vec![n],
+    )
+    .with_df(freedom)
+    .with_estimate("dispersion index", index / freedom))
+}
+
+/// Tests the null hypothesis that `counts` are Poisson against the
+/// alternative that they are overdispersed with the score test of Dean and
+/// Lawless, also derived by Cameron and Trivedi, against negative binomial
+/// alternatives. The p-value is one-sided.
+///
+/// # Formula
+///
+/// ```text
+/// T = Σ [(x_i - x̄)² - x_i] / (x̄ √(2n))
+/// ```
+///
+/// `T` is compared with the standard normal distribution. The estimate is
+/// the moment estimate of the dispersion `α` in `Var(X) = μ + α μ²`.
+///
+/// # Remarks
+///
+/// The score test is the test of `α = 0` in the model
+/// `Var(X) = μ + α μ²` with a common mean `μ`, without fitting the
+/// negative binomial distribution; it is locally most powerful against
+/// these alternatives.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two counts or if all counts
+/// are zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dispersion::dispersion_score_test;
+///
+/// let counts = [0, 0, 1, 0, 7, 2, 0, 0, 9, 1, 0, 3];
+/// let result = dispersion_score_test(&counts).unwrap();
+/// assert!(result.rejects(0.01));
+/// ```
+pub fn dispersion_score_test(counts: &[u64]) -> Result<TestResult> {
+    let (n, mean, squares) = moments(counts)?;
+    let excess = squares - mean * n as f64;
+    let statistic = excess / (mean * (2.0 * n as f64).sqrt());
+    Ok(TestResult::new(
+        "Score test of Poisson overdispersion",
+        "T",
+        statistic,
+        Normal::new(0.0, 1.0).unwrap().sf(statistic),
+        Alternative::Greater,
+        vec![n],
+    )
+    .with_estimate("dispersion", excess / (n as f64 * mean * mean)))
+}
+
+/// Returns the number of `counts`, their mean and the sum of their squared
+/// deviations from the mean
+fn moments(counts: &[u64]) -> Result<(usize, f64, f64)> {
+    let n = counts.len();
+    if n < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let mean = counts.iter().map(|&x| x as f64).sum::<f64>() / n as f64;
+    if mean == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let squares: f64 = counts
+        .iter()
+        .map(|&x| (x as f64 - mean) * (x as f64 - mean))
+        .sum();
+    Ok((n, mean, squares))
+}
+
+/// Fits the Poisson and negative binomial distributions to `counts` by
+/// maximum likelihood and tests the null hypothesis that the counts are
+/// Poisson with the likelihood-ratio test. The negative binomial is
+/// parametrized by its mean `μ` and dispersion `α = 1 / r`, so that
+/// `Var(X) = μ + α μ²`.
+///
+/// # Formula
+///
+/// ```text
+/// LR = 2 (ℓ_NB - ℓ_P)
+/// p = P(χ²_1 ≥ LR) / 2
+/// ```
+///
+/// # Remarks
+///
+/// Both fits have the sample mean as their mean. The size `r` solves the
+/// profile score equation
+/// `Σ_i Σ_{k < x_i} 1 / (r + k) = n ln(1 + x̄ / r)` with Brent's method,
+/// which has a finite root exactly when the variance of the counts, with
+/// divisor `n`, exceeds their mean. Otherwise the likelihood is maximized
+/// on the Poisson boundary `α = 0`, the negative binomial fit is `None`
+/// and the statistic is `0`.
+///
+/// Since `α = 0` lies on the boundary of the parameter space, the null
+/// distribution of the statistic is an equal mixture of a point mass at
+/// zero and the chi-squared distribution with one degree of freedom, so
+/// the p-value is half the chi-squared tail, and `1` when the statistic is
+/// `0`.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two counts, if all counts are
+/// zero, or if the fit of the size fails to converge
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dispersion::fit_poisson_vs_negbin;
+///
+/// let counts = [0, 0, 1, 0, 7, 2, 0, 0, 9, 1, 0, 3];
+/// let fit = fit_poisson_vs_negbin(&counts).unwrap();
+/// assert!(fit.dispersion > 1.0);
+/// assert!(fit.test.rejects(0.01));
+///
+/// let fit = fit_poisson_vs_negbin(&[2, 3, 2, 3, 2, 3]).unwrap();
+/// assert!(fit.negative_binomial.is_none());
+/// assert_eq!(fit.test.p_value, 1.0);
+/// ```
+pub fn fit_poisson_vs_negbin(counts: &[u64]) -> Result<PoissonNegBinFit> {
+    let n = counts.len();
+    if n < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let mean = counts.iter().map(|&x| x as f64).sum::<f64>() / n as f64;
+    let poisson = Poisson::new(mean)?;
+    let poisson_log_likelihood: f64 = counts.iter().map(|&x| poisson.ln_pmf(x)).sum();
+    let variance = counts
+        .iter()
+        .map(|&x| (x as f64 - mean) * (x as f64 - mean))
+        .sum::<f64>()
+        / n as f64;
+
+    let (negative_binomial, negative_binomial_log_likelihood, dispersion) = if variance > mean {
+        let r = fit_size(counts, mean, variance)?;
+        let dist = NegativeBinomial::new(r, r / (r + mean))?;
+        let log_likelihood: f64 = counts.iter().map(|&x| dist.ln_pmf(x)).sum();
+        (Some(dist), log_likelihood, 1.0 / r)
+    } else {
+        (None, poisson_log_likelihood, 0.0)
+    };
+    let statistic = (2.0 * (negative_binomial_log_likelihood - poisson_log_likelihood)).max(0.0);
+    let p_value = if statistic > 0.0 {
+        0.5 * ChiSquared::new(1.0).unwrap().sf(statistic)
+    } else {
+        1.0
+    };
+    let test = TestResult::new(
+        "Likelihood-ratio test of Poisson against negative binomial",
+        "LR",
+        statistic,
+        p_value,
+        Alternative::Greater,
+        vec![n],
+    )
+    .with_df(1.0)
+    .with_estimate("dispersion", dispersion);
+    Ok(PoissonNegBinFit {
+        poisson,
+        poisson_log_likelihood,
+        negative_binomial,
+        negative_binomial_log_likelihood,
+        dispersion,
+        test,
+    })
+}
+
+/// Returns the maximum likelihood size `r` of the negative binomial
+/// distribution with mean `mean`, given overdispersed counts with
+/// `variance > mean`
+fn fit_size(counts: &[u64], mean: f64, variance: f64) -> Result<f64> {
+    // exceed[k] is the number of counts above k, so that the sum of the
+    // digamma differences ψ(x_i + r) - ψ(r) is Σ_k exceed[k] / (r + k)
+    let max = counts.iter().copied().max().unwrap_or(0) as usize;
+    let mut exceed = vec![0.0; max + 1];
+    for &x in counts {
+        if x > 0 {
+            exceed[x as usize - 1] += 1.0;
+        }
+    }
+    for k in (0..max).rev() {
+        exceed[k] += exceed[k + 1];
+    }
+    exceed.truncate(max);
+    let n = counts.len() as f64;
+    let score = |r: f64| {
+        let sum: f64 = exceed
+            .iter()
+            .enumerate()
+            .map(|(k, &e)| e / (r + k as f64))
+            .sum();
+        sum - n * (mean / r).ln_1p()
+    };
+    // the score is positive below the root and negative above it; start
+    // from the moment estimate
+    let guess = mean * mean / (variance - mean);
+    let mut lower = guess;
+    let mut steps = 0;
+    while score(lower) <= 0.0 {
+        lower *= 0.5;
+        steps += 1;
+        if steps > MAX_BRACKET_STEPS {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    let mut upper = guess;
+    while score(upper) >= 0.0 {
+        upper *= 2.0;
+        steps += 1;
+        if steps > MAX_BRACKET_STEPS {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    root::brent(score, lower, upper, 1e-13 * upper)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution as _;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const COUNTS: [u64; 12] = [0, 0, 1, 0, 7, 2, 0, 0, 9, 1, 0, 3];
+
+    #[test]
+    fn test_dispersion_test() {
+        // reference values computed with mpmath
+        let variance = dispersion_test(&COUNTS).unwrap();
+        assert_almost_eq!(variance.statistic, 52.652173913043478, 1e-12);
+        assert_almost_eq!(variance.p_value, 2.0764301353008526e-7, 1e-18);
+        assert_eq!(variance.df, Some(11.0));
+        assert_almost_eq!(variance.estimate.unwrap().value, 52.652173913043478 / 11.0, 1e-12);
+        let score = dispersion_score_test(&COUNTS).unwrap();
+        assert_almost_eq!(score.statistic, 8.2980902518198244, 1e-12);
+        assert_almost_eq!(score.p_value, 5.2899146792086626e-17, 1e-25);
+        assert_almost_eq!(score.estimate.unwrap().value, 1.7674858223062382, 1e-12);
+    }
+
+    #[test]
+    fn test_fit_poisson_vs_negbin() {
+        // reference values computed with mpmath
+        let fit = fit_poisson_vs_negbin(&COUNTS).unwrap();
+        assert_almost_eq!(fit.poisson.lambda(), 23.0 / 12.0, 1e-15);
+        assert_almost_eq!(fit.poisson_log_likelihood, -31.848381469688448, 1e-12);
+        let negative_binomial = fit.negative_binomial.unwrap();
+        assert_almost_eq!(negative_binomial.r(), 0.40063566241841118, 1e-11);
+        assert_almost_eq!(negative_binomial.r() * (1.0 - negative_binomial.p()) / negative_binomial.p(), 23.0 / 12.0, 1e-12);
+        assert_almost_eq!(fit.negative_binomial_log_likelihood, -21.501754032331514, 1e-11);
+        assert_almost_eq!(fit.dispersion, 2.4960334134099917, 1e-10);
+        assert_almost_eq!(fit.test.statistic, 20.693254874713868, 1e-10);
+        assert_almost_eq!(fit.test.p_value, 2.6952778336980202e-6, 1e-15);
+        assert_eq!(fit.test.estimate.unwrap().value, fit.dispersion);
+    }
+
+    #[test]
+    fn test_underdispersed() {
+        let fit = fit_poisson_vs_negbin(&[2, 3, 2, 3, 2, 3]).unwrap();
+        assert!(fit.negative_binomial.is_none());
+        assert_eq!(fit.dispersion, 0.0);
+        assert_eq!(fit.test.statistic, 0.0);
+        assert_eq!(fit.test.p_value, 1.0);
+        assert_eq!(fit.negative_binomial_log_likelihood, fit.poisson_log_likelihood);
+        assert!(dispersion_test(&[2, 3, 2, 3, 2, 3]).unwrap().p_value > 0.98);
+        assert!(dispersion_score_test(&[2, 3, 2, 3, 2, 3]).unwrap().statistic < 0.0);
+    }
+
+    #[test]
+    fn test_poisson_data() {
+        for seed in 0..5 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let counts: Vec<u64> = Poisson::new(4.0).unwrap().sample_iter(&mut rng).take(1000).map(|x| x as u64).collect();
+            let variance = dispersion_test(&counts).unwrap();
+            assert!(!variance.rejects(0.01));
+            assert_almost_eq!(variance.estimate.unwrap().value, 1.0, 0.15);
+            assert!(!dispersion_score_test(&counts).unwrap().rejects(0.01));
+            let fit = fit_poisson_vs_negbin(&counts).unwrap();
+            assert!(!fit.test.rejects(0.01));
+            assert!(fit.dispersion < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_negative_binomial_data() {
+        // r = 2 and mean 5, so that the dispersion is 0.5
+        let dist = NegativeBinomial::new(2.0, 2.0 / 7.0).unwrap();
+        for seed in 0..5 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let counts: Vec<u64> = dist.sample_iter(&mut rng).take(2000).collect();
+            assert!(dispersion_test(&counts).unwrap().rejects(1e-6));
+            let score = dispersion_score_test(&counts).unwrap();
+            assert!(score.rejects(1e-6));
+            assert_almost_eq!(score.estimate.unwrap().value, 0.5, 0.1);
+            let fit = fit_poisson_vs_negbin(&counts).unwrap();
+            assert!(fit.test.rejects(1e-6));
+            assert_almost_eq!(fit.dispersion, 0.5, 0.1);
+        }
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert_eq!(dispersion_test(&[]), Err(StatsError::BadParams));
+        assert_eq!(dispersion_test(&[3]), Err(StatsError::BadParams));
+        assert_eq!(dispersion_test(&[0, 0, 0]), Err(StatsError::BadParams));
+        assert_eq!(dispersion_score_test(&[]), Err(StatsError::BadParams));
+        assert_eq!(dispersion_score_test(&[0, 0, 0]), Err(StatsError::BadParams));
+        assert_eq!(fit_poisson_vs_negbin(&[5]), Err(StatsError::BadParams));
+        assert_eq!(fit_poisson_vs_negbin(&[0, 0]), Err(StatsError::BadParams));
+    }
+}
@@ -0,0 +1,1025 @@
+//! Provides effect sizes with confidence intervals for two-group
+//! comparisons, the standardized mean differences of continuous outcomes,
+//! and for binary outcomes effect sizes and Fisher's exact test of their
+//! association, computed from the 2×2 table
+//!
+//! ```text
+//!            event   no event
+//! group 1      a        b
+//! group 2      c        d
+//! ```
+
+use crate::distribution::{ContinuousCDF, NoncentralStudentsT, Normal};
+use crate::function::factorial::ln_binomial;
+use crate::function::gamma::ln_gamma;
+use crate::function::root::{self, brent};
+use crate::statistics::Statistics;
+use crate::stats_tests::{Alternative, PValueMethod, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Maximum number of steps taken to bracket a log odds ratio
+const MAX_EXPANSIONS: usize = 100;
+
+/// Maximum number of iterations of Brent's method refining a log odds ratio
+const MAX_ITERATIONS: usize = 200;
+
+/// An estimated effect size with its two-sided confidence interval
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EffectEstimate {
+    /// The point estimate of the effect
+    pub estimate: f64,
+    /// The lower confidence bound
+    pub lower: f64,
+    /// The upper confidence bound
+    pub upper: f64,
+}
+
+/// Selects the construction used by `odds_ratio`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OddsRatioMethod {
+    /// The sample odds ratio `ad / bc` with the normal approximation
+    /// `ln(OR) ± z * sqrt(1/a + 1/b + 1/c + 1/d)`, adding `1/2` to every
+    /// cell when any of them is zero
+    Wald,
+    /// The conditional maximum likelihood estimate with the exact
+    /// conditional interval, obtained from the noncentral hypergeometric
+    /// distribution of `a` given the margins of the table as in R's
+    /// `fisher.test`
+    Exact,
+}
+
+/// Checks that `alpha` is in `(0, 1)` and returns the two-sided standard
+/// normal critical value
+fn critical_value(alpha: f64) -> Result<f64> {
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    Ok(-Normal::standard().inverse_cdf(alpha / 2.0))
+}
+
+/// Checks that both groups of the table are non-empty
+fn check_table(a: u64, b: u64, c: u64, d: u64) -> Result<()> {
+    if a + b == 0 || c + d == 0 {
+        Err(StatsError::SpecialCase("both groups must be non-empty"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes the risk ratio `(a / (a + b)) / (c / (c + d))` of the 2×2 table
+/// with its Wald interval on the log scale
+///
+/// # Formula
+///
+/// ```text
+/// RR * exp(± z * sqrt(1/a - 1/(a + b) + 1/c - 1/(c + d)))
+/// ```
+///
+/// where `z` is the `1 - α / 2` quantile of the standard normal
+/// distribution
+///
+/// # Remarks
+///
+/// When `a` or `c` is zero, `1/2` is added to every cell before the
+/// estimate and the interval are computed, so that both stay finite
+///
+/// # Errors
+///
+/// Returns an error if either group is empty or if `alpha` is not in
+/// `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::risk_ratio;
+///
+/// let rr = risk_ratio(56, 14, 48, 32, 0.05).unwrap();
+/// assert!((rr.estimate - 4.0 / 3.0).abs() < 1e-15);
+/// assert!(rr.lower > 1.0);
+/// ```
+pub fn risk_ratio(a: u64, b: u64, c: u64, d: u64, alpha: f64) -> Result<EffectEstimate> {
+    check_table(a, b, c, d)?;
+    let z = critical_value(alpha)?;
+    let shift = if a == 0 || c == 0 { 0.5 } else { 0.0 };
+    let (a, b, c, d) = (
+        a as f64 + shift,
+        b as f64 + shift,
+        c as f64 + shift,
+        d as f64 + shift,
+    );
+    let estimate = (a / (a + b)) / (c / (c + d));
+    let se = (1.0 / a - 1.0 / (a + b) + 1.0 / c - 1.0 / (c + d)).sqrt();
+    Ok(EffectEstimate {
+        estimate,
+        lower: estimate * (-z * se).exp(),
+        upper: estimate * (z * se).exp(),
+    })
+}
+
+/// Computes the odds ratio `ad / bc` of the 2×2 table with a `1 - alpha`
+/// confidence interval constructed by `method`
+///
+/// # Remarks
+///
+/// The exact conditional estimate is the root of `E[a] = a` under the
+/// noncentral hypergeometric distribution and differs slightly from
+/// `ad / bc`. The interval bounds solve `P(A >= a) = α / 2` and
+/// `P(A <= a) = α / 2` in the odds ratio; when `a` is the smallest or
+/// largest value allowed by the margins, the estimate and the corresponding
+/// bound are `0` or infinite, and when the margins allow a single table the
+/// estimate is `NaN` with the interval `(0, ∞)`. The bounds exclude `1` exactly when the
+/// matching one-sided Fisher's exact test rejects at level `α / 2`.
+///
+/// # Errors
+///
+/// Returns an error if either group is empty, if `alpha` is not in
+/// `(0, 1)`, or if the root finder fails to converge on an exact estimate
+/// or bound
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::{odds_ratio, OddsRatioMethod};
+///
+/// // Fisher's tea tasting experiment
+/// let or = odds_ratio(3, 1, 1, 3, 0.05, OddsRatioMethod::Exact).unwrap();
+/// assert!((or.estimate - 6.40832).abs() < 1e-5);
+/// assert!(or.lower < 1.0 && or.upper > 1.0);
+/// ```
+pub fn odds_ratio(
+    a: u64,
+    b: u64,
+    c: u64,
+    d: u64,
+    alpha: f64,
+    method: OddsRatioMethod,
+) -> Result<EffectEstimate> {
+    check_table(a, b, c, d)?;
+    let z = critical_value(alpha)?;
+    match method {
+        OddsRatioMethod::Wald => {
+            let shift = if a == 0 || b == 0 || c == 0 || d == 0 {
+                0.5
+            } else {
+                0.0
+            };
+            let (a, b, c, d) = (
+                a as f64 + shift,
+                b as f64 + shift,
+                c as f64 + shift,
+                d as f64 + shift,
+            );
+            let estimate = a * d / (b * c);
+            let se = (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt();
+            Ok(EffectEstimate {
+                estimate,
+                lower: estimate * (-z * se).exp(),
+                upper: estimate * (z * se).exp(),
+            })
+        }
+        OddsRatioMethod::Exact => exact_odds_ratio(a, b, c, d, alpha),
+    }
+}
+
+/// The distribution of the top-left cell of a 2×2 table given its margins
+/// when the odds ratio is `e^t`
+struct NoncentralHypergeometric {
+    /// Smallest value allowed by the margins
+    lo: u64,
+    /// Logarithms of the central probabilities, up to a constant, of the
+    /// values `lo, lo + 1, ...`
+    ln_weights: Vec<f64>,
+}
+
+impl NoncentralHypergeometric {
+    fn new(a: u64, b: u64, c: u64, d: u64) -> NoncentralHypergeometric {
+        let (events, non_events, group) = (a + c, b + d, a + b);
+        let lo = group.saturating_sub(non_events);
+        let hi = group.min(events);
+        let ln_weights = (lo..=hi)
+            .map(|s| ln_binomial(events, s) + ln_binomial(non_events, group - s))
+            .collect();
+        NoncentralHypergeometric { lo, ln_weights }
+    }
+
+    fn hi(&self) -> u64 {
+        self.lo + self.ln_weights.len() as u64 - 1
+    }
+
+    /// Returns the probabilities of the support at log odds ratio `t`
+    fn probabilities(&self, t: f64) -> Vec<f64> {
+        let ln: Vec<f64> = self
+            .ln_weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| w + t * (self.lo + i as u64) as f64)
+            .collect();
+        let max = ln.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = ln.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    fn mean(&self, t: f64) -> f64 {
+        self.probabilities(t)
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.lo + i as u64) as f64 * p)
+            .sum()
+    }
+
+    /// Returns `P(A >= x)` at log odds ratio `t`
+    fn sf_inclusive(&self, x: u64, t: f64) -> f64 {
+        self.probabilities(t)[(x - self.lo) as usize..].iter().sum()
+    }
+
+    /// Returns `P(A <= x)` at log odds ratio `t`
+    fn cdf(&self, x: u64, t: f64) -> f64 {
+        self.probabilities(t)[..=(x - self.lo) as usize]
+            .iter()
+            .sum()
+    }
+}
+
+/// Solves `f(t) = target` for the log odds ratio `t`, bracketing the root
+/// from `t = 0` and refining it with Brent's method
+fn solve_log_odds<F: Fn(f64) -> f64>(f: F, target: f64) -> Result<f64> {
+    let g = |t: f64| f(t) - target;
+    let (lo, hi) = root::bracket_root(g, 0.0, 2.0, MAX_EXPANSIONS)?;
+    let tol = 1e-14 * (1.0 + lo.abs().max(hi.abs()));
+    Ok(root::brent_with(g, lo, hi, tol, MAX_ITERATIONS)?.root)
+}
+
+fn exact_odds_ratio(a: u64, b: u64, c: u64, d: u64, alpha: f64) -> Result<EffectEstimate> {
+    let dist = NoncentralHypergeometric::new(a, b, c, d);
+    let (lo, hi) = (dist.lo, dist.hi());
+    if lo == hi {
+        return Ok(EffectEstimate {
+            estimate: f64::NAN,
+            lower: 0.0,
+            upper: f64::INFINITY,
+        });
+    }
+    let estimate = if a == lo {
+        0.0
+    } else if a == hi {
+        f64::INFINITY
+    } else {
+        solve_log_odds(|t| dist.mean(t), a as f64)?.exp()
+    };
+    let lower = if a == lo {
+        0.0
+    } else {
+        solve_log_odds(|t| dist.sf_inclusive(a, t), alpha / 2.0)?.exp()
+    };
+    let upper = if a == hi {
+        f64::INFINITY
+    } else {
+        solve_log_odds(|t| dist.cdf(a, t), alpha / 2.0)?.exp()
+    };
+    Ok(EffectEstimate {
+        estimate,
+        lower,
+        upper,
+    })
+}
+
+/// Performs Fisher's exact test of the null hypothesis that the event is
+/// independent of the group in the 2×2 table, reporting the p-value
+/// selected by `method`. Returns `a` as the statistic with the conditional
+/// maximum likelihood estimate of the odds ratio and its exact 95%
+/// confidence interval, as `odds_ratio` with `OddsRatioMethod::Exact`;
+/// the sample sizes are those of the two groups.
+///
+/// # Remarks
+///
+/// Given the margins of the table, `a` is hypergeometric under the null.
+/// The exact two-sided p-value is the total probability of the tables no
+/// more likely than the observed one, with the relative tolerance of
+/// `1e-7` of R's `fisher.test`; the mid-p value subtracts half the
+/// probability of the observed table.
+///
+/// # Errors
+///
+/// Returns an error if either group is empty, or if the root finder fails
+/// to converge on the estimate or a bound of the odds ratio
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::fisher_exact_test;
+/// use statrs::stats_tests::PValueMethod;
+///
+/// // Fisher's tea tasting experiment
+/// let exact = fisher_exact_test(3, 1, 1, 3, PValueMethod::Exact).unwrap();
+/// assert!((exact.p_value - 34.0 / 70.0).abs() < 1e-14);
+/// let mid_p = fisher_exact_test(3, 1, 1, 3, PValueMethod::MidP).unwrap();
+/// assert!((mid_p.p_value - 26.0 / 70.0).abs() < 1e-14);
+/// ```
+pub fn fisher_exact_test(
+    a: u64,
+    b: u64,
+    c: u64,
+    d: u64,
+    method: PValueMethod,
+) -> Result<TestResult> {
+    check_table(a, b, c, d)?;
+    let dist = NoncentralHypergeometric::new(a, b, c, d);
+    let probabilities = dist.probabilities(0.0);
+    let observed = probabilities[(a - dist.lo) as usize];
+    let threshold = observed * (1.0 + 1e-7);
+    let p_value: f64 = probabilities.iter().filter(|&&p| p <= threshold).sum();
+    let (method_name, p_value) = match method {
+        PValueMethod::Exact => ("Fisher's exact test", p_value),
+        PValueMethod::MidP => ("Fisher's exact test (mid-p)", p_value - 0.5 * observed),
+    };
+    let or = exact_odds_ratio(a, b, c, d, 0.05)?;
+    Ok(TestResult::new(
+        method_name,
+        "a",
+        a as f64,
+        p_value.clamp(0.0, 1.0),
+        Alternative::TwoSided,
+        vec![(a + b) as usize, (c + d) as usize],
+    )
+    .with_estimate("odds ratio", or.estimate)
+    .with_conf_int(or.lower, or.upper, 0.95))
+}
+
+/// Returns the maximum likelihood estimates of the two risks under the
+/// constraint `p1 - p2 = delta`, following Miettinen and Nurminen (1985)
+fn restricted_risks(delta: f64, p1: f64, p2: f64, n1: f64, n2: f64) -> (f64, f64) {
+    let theta = n2 / n1;
+    let a = 1.0 + theta;
+    let b = -(1.0 + theta + p1 + theta * p2 + delta * (theta + 2.0));
+    let c = delta * delta + delta * (2.0 * p1 + theta + 1.0) + p1 + theta * p2;
+    let d = -p1 * delta * (1.0 + delta);
+    let v = b.powi(3) / (27.0 * a.powi(3)) - b * c / (6.0 * a * a) + d / (2.0 * a);
+    let u = v.signum() * (b * b / (9.0 * a * a) - c / (3.0 * a)).max(0.0).sqrt();
+    let ratio = if u == 0.0 {
+        0.0
+    } else {
+        (v / u.powi(3)).clamp(-1.0, 1.0)
+    };
+    let w = (f64::consts::PI + ratio.acos()) / 3.0;
+    let q1 = (2.0 * u * w.cos() - b / (3.0 * a)).clamp(0.0, 1.0);
+    let q2 = (q1 - delta).clamp(0.0, 1.0);
+    (q1, q2)
+}
+
+/// Computes the risk difference `a / (a + b) - c / (c + d)` of the 2×2
+/// table with the Miettinen-Nurminen score interval
+///
+/// # Formula
+///
+/// The interval collects the differences `δ` for which
+///
+/// ```text
+/// |p1 - p2 - δ| / sqrt((q1 (1 - q1) / n1 + q2 (1 - q2) / n2) * N / (N - 1)) <= z
+/// ```
+///
+/// where `p1`, `p2` are the observed risks, `q1 - q2 = δ` the restricted
+/// maximum likelihood estimates, `n1`, `n2` the group sizes, `N = n1 + n2`
+/// and `z` the `1 - α / 2` quantile of the standard normal distribution
+///
+/// # Remarks
+///
+/// The score interval needs no continuity correction: tables with zero
+/// cells give finite bounds within `[-1, 1]`
+///
+/// # Errors
+///
+/// Returns an error if either group is empty or if `alpha` is not in
+/// `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::risk_difference;
+///
+/// // Newcombe (1998) reports (0.0528, 0.3382) for 56/70 versus 48/80
+/// let rd = risk_difference(56, 14, 48, 32, 0.05).unwrap();
+/// assert!((rd.estimate - 0.2).abs() < 1e-15);
+/// assert!((rd.lower - 0.0528).abs() < 1e-4);
+/// assert!((rd.upper - 0.3382).abs() < 1e-4);
+/// ```
+pub fn risk_difference(a: u64, b: u64, c: u64, d: u64, alpha: f64) -> Result<EffectEstimate> {
+    check_table(a, b, c, d)?;
+    let z = critical_value(alpha)?;
+    let (n1, n2) = ((a + b) as f64, (c + d) as f64);
+    let (p1, p2) = (a as f64 / n1, c as f64 / n2);
+    let estimate = p1 - p2;
+    let correction = (n1 + n2) / (n1 + n2 - 1.0);
+    // the score statistic decreases in `delta`
+    let score = |delta: f64| {
+        let (q1, q2) = restricted_risks(delta, p1, p2, n1, n2);
+        let variance = (q1 * (1.0 - q1) / n1 + q2 * (1.0 - q2) / n2) * correction;
+        (estimate - delta) / variance.sqrt()
+    };
+    let bisect = |mut lo: f64, mut hi: f64, target: f64| {
+        for _ in 0..200 {
+            let mid = 0.5 * (lo + hi);
+            if mid == lo || mid == hi {
+                break;
+            }
+            if score(mid) > target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    };
+    Ok(EffectEstimate {
+        estimate,
+        lower: bisect(-1.0, estimate, z),
+        upper: bisect(estimate, 1.0, -z),
+    })
+}
+
+/// Computes the number needed to treat, the reciprocal of the risk
+/// difference of the 2×2 table, with the interval obtained by inverting the
+/// bounds of `risk_difference`
+///
+/// # Remarks
+///
+/// The estimate is positive when group 1 has the higher risk and is the
+/// number of subjects to move from group 2 to group 1 to expect one
+/// additional event. When the interval of the risk difference contains
+/// zero, the interval of the number needed to treat passes through
+/// infinity, as described by Altman (1998): it is then reported with
+/// `lower = 1 / upper_RD > 0` and `upper = 1 / lower_RD < 0`, and covers the
+/// values above `lower` together with those below `upper`.
+///
+/// # Errors
+///
+/// Returns an error if either group is empty or if `alpha` is not in
+/// `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::number_needed_to_treat;
+///
+/// let nnt = number_needed_to_treat(56, 14, 48, 32, 0.05).unwrap();
+/// assert!((nnt.estimate - 5.0).abs() < 1e-13);
+/// assert!(nnt.lower < 5.0 && nnt.upper > 5.0);
+/// ```
+pub fn number_needed_to_treat(
+    a: u64,
+    b: u64,
+    c: u64,
+    d: u64,
+    alpha: f64,
+) -> Result<EffectEstimate> {
+    let rd = risk_difference(a, b, c, d, alpha)?;
+    Ok(EffectEstimate {
+        estimate: 1.0 / rd.estimate,
+        lower: 1.0 / rd.upper,
+        upper: 1.0 / rd.lower,
+    })
+}
+
+/// A standardized mean difference with its standard error and two-sided
+/// confidence interval
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SmdEstimate {
+    /// The point estimate
+    pub estimate: f64,
+    /// The large-sample standard error of the estimate
+    pub standard_error: f64,
+    /// The lower confidence bound
+    pub lower: f64,
+    /// The upper confidence bound
+    pub upper: f64,
+}
+
+/// Selects the standard deviation that `cohens_d` and `hedges_g` divide
+/// the difference of the means by
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SmdMethod {
+    /// The pooled standard deviation of both groups, with
+    /// `n_x + n_y - 2` degrees of freedom, which assumes equal variances
+    Pooled,
+    /// The square root of the unweighted average of the two variances,
+    /// for groups whose variances differ
+    AverageVariance,
+    /// Glass' delta, the standard deviation of the control group `y` only,
+    /// with `n_y - 1` degrees of freedom, for treatments that change the
+    /// spread
+    Glass,
+}
+
+/// Computes the variance of several groups pooled around their own means,
+/// the average of the group variances weighted by their degrees of freedom
+///
+/// # Formula
+///
+/// ```text
+/// Σ (n_i - 1) s_i^2 / Σ (n_i - 1)
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if there is no group, if a group is empty or if there
+/// are no degrees of freedom, every group having a single observation
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::pooled_variance;
+///
+/// let a = [1.0, 2.0, 3.0];
+/// let b = [2.0, 6.0];
+/// // (2 * 1 + 1 * 8) / 3
+/// assert!((pooled_variance(&[&a, &b]).unwrap() - 10.0 / 3.0).abs() < 1e-15);
+/// ```
+pub fn pooled_variance(groups: &[&[f64]]) -> Result<f64> {
+    if groups.is_empty() || groups.iter().any(|g| g.is_empty()) {
+        return Err(StatsError::SpecialCase("every group must be non-empty"));
+    }
+    let freedom: usize = groups.iter().map(|g| g.len() - 1).sum();
+    if freedom == 0 {
+        return Err(StatsError::SpecialCase(
+            "the pooled variance needs a group with two observations",
+        ));
+    }
+    let sum_squares: f64 = groups
+        .iter()
+        .filter(|g| g.len() > 1)
+        .map(|g| (g.len() - 1) as f64 * g.iter().variance())
+        .sum();
+    Ok(sum_squares / freedom as f64)
+}
+
+/// Returns the exact small-sample bias correction of Hedges for a
+/// standardized mean difference whose standard deviation has `freedom`
+/// degrees of freedom, or `f64::NAN` if `freedom` is not greater than `1`
+///
+/// # Formula
+///
+/// ```text
+/// J(ν) = Γ(ν / 2) / (sqrt(ν / 2) Γ((ν - 1) / 2))
+/// ```
+///
+/// # Remarks
+///
+/// The common approximation `1 - 3 / (4 ν - 1)` is accurate for large `ν`
+/// but overestimates `J` for very small samples, as `J(2) = 1 / sqrt(π)`
+/// against `4 / 7`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::hedges_correction;
+///
+/// assert!((hedges_correction(2.0) - 1.0 / std::f64::consts::PI.sqrt()).abs() < 1e-14);
+/// assert!((hedges_correction(100.0) - (1.0 - 3.0 / 399.0)).abs() < 1e-5);
+/// ```
+pub fn hedges_correction(freedom: f64) -> f64 {
+    if freedom.is_nan() || freedom <= 1.0 {
+        return f64::NAN;
+    }
+    (ln_gamma(freedom / 2.0) - 0.5 * (freedom / 2.0).ln() - ln_gamma((freedom - 1.0) / 2.0)).exp()
+}
+
+/// Computes the standardized mean difference `(mean(x) - mean(y)) / s` of
+/// two independent samples, with the standard deviation `s` selected by
+/// `method`, and a `1 - alpha` confidence interval
+///
+/// # Remarks
+///
+/// The interval inverts the noncentral t distribution of the t statistic
+/// as in the `effectsize` R package: the bounds of the noncentrality that
+/// put the observed statistic at the `1 - α / 2` and `α / 2` quantiles are
+/// rescaled to the effect size. This is exact under normality for
+/// `SmdMethod::Pooled` with equal variances and for `SmdMethod::Glass`
+/// with equal variances, and uses Welch's statistic and degrees of freedom
+/// for `SmdMethod::AverageVariance`.
+///
+/// The standard errors are the large-sample ones of Hedges and Olkin,
+/// `sqrt(1/n_x + 1/n_y + d^2 / (2 (n_x + n_y)))` for the pooled standard
+/// deviation and `sqrt(1/n_x + 1/n_y + d^2 / (2 (n_y - 1)))` for Glass'
+/// delta, and that of Bonett (2008) for the average variance.
+///
+/// # Errors
+///
+/// Returns an error if a sample is empty, if there are no degrees of
+/// freedom for the standard deviation, if it is zero or if `alpha` is not
+/// in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::{cohens_d, SmdMethod};
+///
+/// let x = [5.1, 4.8, 6.0, 5.7, 6.3, 5.5];
+/// let y = [4.2, 4.9, 3.8, 4.5, 5.0, 4.1];
+/// let d = cohens_d(&x, &y, SmdMethod::Pooled, 0.05).unwrap();
+/// assert!(d.estimate > 2.0);
+/// assert!(d.lower > 0.0 && d.lower < d.estimate && d.estimate < d.upper);
+/// ```
+pub fn cohens_d(x: &[f64], y: &[f64], method: SmdMethod, alpha: f64) -> Result<SmdEstimate> {
+    standardized_difference(x, y, method, alpha).map(|(d, _)| d)
+}
+
+/// Computes Hedges' g, the standardized mean difference of `cohens_d`
+/// multiplied by the exact bias correction `hedges_correction` for the
+/// degrees of freedom of the standard deviation
+///
+/// # Remarks
+///
+/// The standard error and the confidence bounds are those of `cohens_d`
+/// multiplied by the same correction, as in the `effectsize` R package.
+/// The degrees of freedom are `n_x + n_y - 2` for `SmdMethod::Pooled` and
+/// `SmdMethod::AverageVariance` and `n_y - 1` for `SmdMethod::Glass`.
+///
+/// # Errors
+///
+/// Returns an error under the conditions of `cohens_d` or if there are
+/// fewer than two degrees of freedom
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::{cohens_d, hedges_g, SmdMethod};
+///
+/// let x = [5.1, 4.8, 6.0];
+/// let y = [4.2, 4.9, 3.8];
+/// let d = cohens_d(&x, &y, SmdMethod::Pooled, 0.05).unwrap();
+/// let g = hedges_g(&x, &y, SmdMethod::Pooled, 0.05).unwrap();
+/// // with four degrees of freedom J = 1 / sqrt(2) / Γ(3/2)
+/// assert!((g.estimate / d.estimate - 0.7978845608028654).abs() < 1e-14);
+/// ```
+pub fn hedges_g(x: &[f64], y: &[f64], method: SmdMethod, alpha: f64) -> Result<SmdEstimate> {
+    let (d, freedom) = standardized_difference(x, y, method, alpha)?;
+    if freedom < 2.0 {
+        return Err(StatsError::SpecialCase(
+            "Hedges' correction needs two degrees of freedom",
+        ));
+    }
+    let j = hedges_correction(freedom);
+    Ok(SmdEstimate {
+        estimate: j * d.estimate,
+        standard_error: j * d.standard_error,
+        lower: j * d.lower,
+        upper: j * d.upper,
+    })
+}
+
+/// Returns the standardized difference with the degrees of freedom of its
+/// standard deviation
+fn standardized_difference(
+    x: &[f64],
+    y: &[f64],
+    method: SmdMethod,
+    alpha: f64,
+) -> Result<(SmdEstimate, f64)> {
+    if x.is_empty() || y.is_empty() {
+        return Err(StatsError::SpecialCase("both samples must be non-empty"));
+    }
+    if x.iter().chain(y).any(|v| !v.is_finite()) {
+        return Err(StatsError::SpecialCase("the samples must be finite"));
+    }
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let difference = x.iter().mean() - y.iter().mean();
+    let variance = |v: &[f64]| {
+        if v.len() > 1 {
+            v.iter().variance()
+        } else {
+            0.0
+        }
+    };
+    let (vx, vy) = (variance(x), variance(y));
+    // the standardizer, its degrees of freedom, the degrees of freedom of
+    // the t statistic and the ratio of its standard error to the
+    // standardizer
+    let (standardizer, freedom, t_freedom, scale) = match method {
+        SmdMethod::Pooled => {
+            let freedom = nx + ny - 2.0;
+            let s = pooled_variance(&[x, y])?.sqrt();
+            (s, freedom, freedom, (1.0 / nx + 1.0 / ny).sqrt())
+        }
+        SmdMethod::AverageVariance => {
+            if x.len() < 2 || y.len() < 2 {
+                return Err(StatsError::SpecialCase(
+                    "the average variance needs two observations in every group",
+                ));
+            }
+            let s = ((vx + vy) / 2.0).sqrt();
+            let (ex, ey) = (vx / nx, vy / ny);
+            let welch = (ex + ey).powi(2) / (ex * ex / (nx - 1.0) + ey * ey / (ny - 1.0));
+            (s, nx + ny - 2.0, welch, (ex + ey).sqrt() / s)
+        }
+        SmdMethod::Glass => {
+            if y.len() < 2 {
+                return Err(StatsError::SpecialCase(
+                    "Glass' delta needs two observations in the control group",
+                ));
+            }
+            let freedom = ny - 1.0;
+            (vy.sqrt(), freedom, freedom, (1.0 / nx + 1.0 / ny).sqrt())
+        }
+    };
+    if standardizer == 0.0 {
+        return Err(StatsError::SpecialCase(
+            "the standard deviation of the samples is zero",
+        ));
+    }
+    let estimate = difference / standardizer;
+    let variance = match method {
+        SmdMethod::Pooled => 1.0 / nx + 1.0 / ny + estimate * estimate / (2.0 * (nx + ny)),
+        SmdMethod::AverageVariance => {
+            let s2 = standardizer * standardizer;
+            estimate * estimate * (vx * vx / (nx - 1.0) + vy * vy / (ny - 1.0)) / (8.0 * s2 * s2)
+                + (vx / (nx - 1.0) + vy / (ny - 1.0)) / s2
+        }
+        SmdMethod::Glass => 1.0 / nx + 1.0 / ny + estimate * estimate / (2.0 * (ny - 1.0)),
+    };
+    let t = estimate / scale;
+    let lower = noncentrality_for(t, t_freedom, 1.0 - alpha / 2.0) * scale;
+    let upper = noncentrality_for(t, t_freedom, alpha / 2.0) * scale;
+    Ok((
+        SmdEstimate {
+            estimate,
+            standard_error: variance.sqrt(),
+            lower,
+            upper,
+        },
+        freedom,
+    ))
+}
+
+/// Returns the noncentrality at which the noncentral t distribution with
+/// `freedom` degrees of freedom has `t` as its `p`-th quantile
+fn noncentrality_for(t: f64, freedom: f64, p: f64) -> f64 {
+    // the cdf at t decreases in the noncentrality
+    let f = |ncp: f64| NoncentralStudentsT::new(freedom, ncp).unwrap().cdf(t) - p;
+    let width = 2.0 * (1.0 + t.abs());
+    let (mut lo, mut hi) = (t - width, t + width);
+    while f(lo) < 0.0 {
+        lo -= width;
+    }
+    while f(hi) > 0.0 {
+        hi += width;
+    }
+    brent(f, lo, hi, 1e-12).unwrap()
+}
+
+/// Computes the common-language effect size of McGraw and Wong, the
+/// probability that an observation of `x` exceeds one of `y`, counting
+/// ties as one half, or returns `f64::NAN` if a sample is empty
+///
+/// # Remarks
+///
+/// This is the nonparametric estimate over all pairs, the Mann-Whitney
+/// `U` statistic divided by `n_x n_y`, which is unbiased whatever the
+/// distributions. For normal samples with equal variances it estimates
+/// `Φ(δ / sqrt(2))`, where `δ` is the standardized mean difference.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::effect::cles;
+///
+/// // 5 of the 9 pairs favour x and one is tied
+/// assert_eq!(cles(&[1.0, 4.0, 6.0], &[2.0, 3.0, 4.0]), 5.5 / 9.0);
+/// ```
+pub fn cles(x: &[f64], y: &[f64]) -> f64 {
+    if x.is_empty() || y.is_empty() {
+        return f64::NAN;
+    }
+    let mut sorted = y.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let wins: f64 = x
+        .iter()
+        .map(|&v| {
+            let below = sorted.partition_point(|&w| w < v);
+            let tied = sorted[below..].partition_point(|&w| w <= v);
+            below as f64 + 0.5 * tied as f64
+        })
+        .sum();
+    wins / (x.len() * y.len()) as f64
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{DiscreteCDF, Hypergeometric};
+
+    fn check(e: EffectEstimate, expected: (f64, f64, f64), tol: f64) {
+        assert_almost_eq!(e.estimate, expected.0, tol * expected.0.abs().max(1.0));
+        assert_almost_eq!(e.lower, expected.1, tol * expected.1.abs().max(1.0));
+        assert_almost_eq!(e.upper, expected.2, tol * expected.2.abs().max(1.0));
+    }
+
+    // reference values computed with mpmath, the risk difference bounds by
+    // maximizing the restricted likelihood numerically
+
+    #[test]
+    fn test_risk_ratio() {
+        check(risk_ratio(56, 14, 48, 32, 0.05).unwrap(), (1.3333333333333333, 1.0766264227631927, 1.651248511266387), 1e-14);
+        check(risk_ratio(12, 5, 2, 9, 0.05).unwrap(), (3.8823529411764706, 1.068052787664153, 14.1122840874052), 1e-14);
+        // zero cell, corrected by adding 1/2 to every cell
+        check(risk_ratio(0, 10, 5, 15, 0.05).unwrap(), (0.17355371900826446, 0.010536466046271733, 2.8587282727739364), 1e-14);
+    }
+
+    #[test]
+    fn test_odds_ratio_wald() {
+        let wald = |a, b, c, d| odds_ratio(a, b, c, d, 0.05, OddsRatioMethod::Wald).unwrap();
+        check(wald(3, 1, 1, 3), (9.0, 0.36663693192554544, 220.92700692915743), 1e-14);
+        check(wald(56, 14, 48, 32), (2.6666666666666667, 1.2762178705364337, 5.5720196960743793), 1e-14);
+        check(wald(0, 10, 5, 15), (0.1341991341991342, 0.0066868922123339968, 2.693240304753053), 1e-14);
+    }
+
+    #[test]
+    fn test_published_case_control() {
+        // Feychting et al. (1998) as tabulated in the epiR documentation for
+        // epi.2by2, which reports a risk ratio of 4.01 (1.43, 11.23) and an
+        // odds ratio of 4.03 (1.43, 11.31)
+        let round = |x: f64| (x * 100.0).round() / 100.0;
+        let rr = risk_ratio(13, 2163, 5, 3349, 0.05).unwrap();
+        assert_eq!((round(rr.estimate), round(rr.lower), round(rr.upper)), (4.01, 1.43, 11.23));
+        let or = odds_ratio(13, 2163, 5, 3349, 0.05, OddsRatioMethod::Wald).unwrap();
+        assert_eq!((round(or.estimate), round(or.lower), round(or.upper)), (4.03, 1.43, 11.31));
+    }
+
+    #[test]
+    fn test_odds_ratio_exact() {
+        let exact = |a, b, c, d| odds_ratio(a, b, c, d, 0.05, OddsRatioMethod::Exact).unwrap();
+        // R's fisher.test reports 6.408309 and (0.2117329, 621.9337505) for
+        // the tea tasting table, its upper bound being limited by the
+        // default tolerance of uniroot
+        check(exact(3, 1, 1, 3), (6.4083196581996743, 0.21173559544657857, 626.24353058881404), 1e-11);
+        check(exact(56, 14, 48, 32), (2.6491872957236945, 1.2091154789241256, 6.0398730131334436), 1e-11);
+        check(exact(12, 5, 2, 9), (9.7641173213490408, 1.3526970105998667, 125.14442988021594), 1e-11);
+        // zero cell
+        let e = exact(0, 10, 5, 15);
+        assert_eq!(e.estimate, 0.0);
+        assert_eq!(e.lower, 0.0);
+        assert_almost_eq!(e.upper, 2.0639622115671643, 1e-11);
+        let e = exact(10, 0, 5, 15);
+        assert_eq!(e.estimate, f64::INFINITY);
+        assert_eq!(e.upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_odds_ratio_consistent_with_fisher() {
+        // the exact interval excludes 1 exactly when a one-sided Fisher's
+        // exact test rejects at level alpha / 2
+        for &(a, b, c, d) in &[(3, 1, 1, 3), (56, 14, 48, 32), (12, 5, 2, 9), (7, 3, 2, 8), (1, 9, 6, 4), (8, 2, 3, 7)] {
+            let e = odds_ratio(a, b, c, d, 0.05, OddsRatioMethod::Exact).unwrap();
+            let h = Hypergeometric::new(a + b + c + d, a + c, a + b).unwrap();
+            let greater = if a == 0 { 1.0 } else { h.sf(a - 1) };
+            let less = h.cdf(a);
+            assert_eq!(e.lower > 1.0, greater < 0.025);
+            assert_eq!(e.upper < 1.0, less < 0.025);
+            // at the bounds the one-sided p-values equal alpha / 2
+            let dist = NoncentralHypergeometric::new(a, b, c, d);
+            assert_almost_eq!(dist.sf_inclusive(a, e.lower.ln()), 0.025, 1e-12);
+            assert_almost_eq!(dist.cdf(a, e.upper.ln()), 0.025, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_risk_difference() {
+        // Newcombe (1998) reports (0.0528, 0.3382) for the first table
+        check(risk_difference(56, 14, 48, 32, 0.05).unwrap(), (0.2, 0.052829713245844602, 0.33817294001586835), 1e-12);
+        check(risk_difference(3, 1, 1, 3, 0.05).unwrap(), (0.5, -0.22226643804532588, 0.86793600297863843), 1e-12);
+        check(risk_difference(12, 5, 2, 9, 0.05).unwrap(), (0.52406417112299465, 0.14247311859199197, 0.76292687873747091), 1e-12);
+        check(risk_difference(0, 10, 5, 15, 0.05).unwrap(), (-0.25, -0.47254036328736604, 0.059252949895601008), 1e-12);
+    }
+
+    #[test]
+    fn test_number_needed_to_treat() {
+        let nnt = number_needed_to_treat(56, 14, 48, 32, 0.05).unwrap();
+        assert_almost_eq!(nnt.estimate, 5.0, 1e-13);
+        assert_almost_eq!(nnt.lower, 1.0 / 0.33817294001586835, 1e-10);
+        assert_almost_eq!(nnt.upper, 1.0 / 0.052829713245844602, 1e-9);
+        // the risk difference interval contains zero
+        let nnt = number_needed_to_treat(0, 10, 5, 15, 0.05).unwrap();
+        assert_almost_eq!(nnt.estimate, -4.0, 1e-13);
+        assert!(nnt.lower > 0.0 && nnt.upper < 0.0);
+        assert_eq!(number_needed_to_treat(1, 1, 1, 1, 0.05).unwrap().estimate, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(risk_ratio(0, 0, 1, 1, 0.05).is_err());
+        assert!(risk_ratio(1, 1, 1, 1, 0.0).is_err());
+        assert!(odds_ratio(1, 1, 0, 0, 0.05, OddsRatioMethod::Exact).is_err());
+        assert!(odds_ratio(1, 1, 1, 1, f64::NAN, OddsRatioMethod::Wald).is_err());
+        assert!(risk_difference(1, 1, 1, 1, 1.0).is_err());
+        assert!(number_needed_to_treat(0, 0, 1, 1, 0.05).is_err());
+    }
+    #[test]
+    fn test_fisher_exact_test() {
+        // the convictions of twins of Fisher (1935), the example of R's
+        // fisher.test, which reports a p-value of 0.0005367 and an odds
+        // ratio of 0.04693661; the p-values and interval computed with mpmath
+        let exact = fisher_exact_test(2, 15, 10, 3, PValueMethod::Exact).unwrap();
+        assert_almost_eq!(exact.p_value, 0.0005367241191434358, 1e-15);
+        assert_almost_eq!(exact.estimate.unwrap().value, 0.04693661, 1e-7);
+        let ci = exact.conf_int.unwrap();
+        assert_almost_eq!(ci.lower, 0.0033171639506573612, 1e-12);
+        assert_almost_eq!(ci.upper, 0.36318960235668056, 1e-10);
+        assert_eq!(exact.sample_sizes, vec![17, 13]);
+        let mid_p = fisher_exact_test(2, 15, 10, 3, PValueMethod::MidP).unwrap();
+        assert_almost_eq!(mid_p.p_value, 0.00031187413811891047, 1e-15);
+        assert_eq!(mid_p.method, "Fisher's exact test (mid-p)");
+        // symmetric under exchanging the groups
+        let swapped = fisher_exact_test(10, 3, 2, 15, PValueMethod::Exact).unwrap();
+        assert_almost_eq!(swapped.p_value, exact.p_value, 1e-15);
+        // a single table allowed by the margins
+        assert_eq!(fisher_exact_test(3, 0, 2, 0, PValueMethod::Exact).unwrap().p_value, 1.0);
+        assert_eq!(fisher_exact_test(3, 0, 2, 0, PValueMethod::MidP).unwrap().p_value, 0.5);
+        assert!(fisher_exact_test(0, 0, 2, 1, PValueMethod::Exact).is_err());
+    }
+    const X: [f64; 6] = [5.1, 4.8, 6.0, 5.7, 6.3, 5.5];
+    const Y: [f64; 6] = [4.2, 4.9, 3.8, 4.5, 5.0, 4.1];
+
+    fn check_smd(e: SmdEstimate, expected: [f64; 4]) {
+        assert_almost_eq!(e.estimate, expected[0], 1e-13);
+        assert_almost_eq!(e.standard_error, expected[1], 1e-13);
+        assert_almost_eq!(e.lower, expected[2], 1e-9);
+        assert_almost_eq!(e.upper, expected[3], 1e-9);
+    }
+
+    // reference values computed with mpmath following the effectsize R
+    // package, inverting the noncentral t cdf obtained by quadrature
+
+    #[test]
+    fn test_cohens_d() {
+        check_smd(cohens_d(&X, &Y, SmdMethod::Pooled, 0.05).unwrap(), [2.22905614469002, 0.73509328478829631, 0.71135591939335895, 3.6836725629029612]);
+        check_smd(cohens_d(&X, &Y, SmdMethod::AverageVariance, 0.05).unwrap(), [2.22905614469002, 0.80955469525634978, 0.70248188958465917, 3.6916536880274944]);
+        check_smd(cohens_d(&X, &Y, SmdMethod::Glass, 0.05).unwrap(), [2.44257352264652, 0.96433908698482004, 0.55187612436102491, 4.2581150005324943]);
+        // antisymmetric in the samples
+        let d = cohens_d(&Y, &X, SmdMethod::Pooled, 0.05).unwrap();
+        assert_almost_eq!(d.estimate, -2.22905614469002, 1e-13);
+        assert_almost_eq!(d.lower, -3.6836725629029612, 1e-9);
+    }
+
+    #[test]
+    fn test_hedges_g() {
+        check_smd(hedges_g(&X, &Y, SmdMethod::Pooled, 0.05).unwrap(), [2.0568517676164626, 0.6783041000477176, 0.65640055038278782, 3.3990926789243667]);
+        check_smd(hedges_g(&X, &Y, SmdMethod::AverageVariance, 0.05).unwrap(), [2.0568517676164626, 0.7470130395265521, 0.64821207835107792, 3.4064572270803519]);
+        check_smd(hedges_g(&X, &Y, SmdMethod::Glass, 0.05).unwrap(), [2.0535904709759838, 0.81076681682686717, 0.46398912443749133, 3.5800045764595338]);
+    }
+
+    #[test]
+    fn test_hedges_g_small_samples() {
+        // with two degrees of freedom the exact correction 1 / sqrt(π)
+        // differs from the approximation 4 / 7 in the second digit
+        assert_almost_eq!(hedges_correction(2.0), 0.56418958354775629, 1e-14);
+        assert_almost_eq!(hedges_correction(4.0), 0.79788456080286536, 1e-14);
+        assert!(hedges_correction(1.0).is_nan() && hedges_correction(f64::NAN).is_nan());
+        check_smd(hedges_g(&[1.0, 3.0], &[2.0, 2.5], SmdMethod::Pooled, 0.05).unwrap(), [-0.13683607328474347, 0.566260010995646, -1.2342545074993126, 0.99124623966209405]);
+        let g = hedges_g(&[1.0, 3.0, 2.2], &[2.0, 2.5, 1.5], SmdMethod::Glass, 0.05).unwrap();
+        check_smd(g, [0.075225277806367572, 0.46219184479440469, -0.83872884983856385, 0.97218074502193205]);
+        assert!((g.estimate / 0.13333333333333345 - 4.0 / 7.0).abs() > 5e-3);
+        // Glass' delta needs two degrees of freedom for the correction
+        assert!(hedges_g(&[1.0, 3.0], &[2.0, 2.5], SmdMethod::Glass, 0.05).is_err());
+        assert!(cohens_d(&[1.0, 3.0], &[2.0, 2.5], SmdMethod::Glass, 0.05).is_ok());
+    }
+
+    #[test]
+    fn test_smd_bad_input() {
+        assert!(cohens_d(&[], &Y, SmdMethod::Pooled, 0.05).is_err());
+        assert!(cohens_d(&[1.0], &[2.0], SmdMethod::Pooled, 0.05).is_err());
+        assert!(cohens_d(&[1.0], &Y, SmdMethod::AverageVariance, 0.05).is_err());
+        assert!(cohens_d(&X, &[1.0], SmdMethod::Glass, 0.05).is_err());
+        assert!(cohens_d(&X, &Y, SmdMethod::Pooled, 1.0).is_err());
+        assert!(cohens_d(&[1.0, f64::NAN], &Y, SmdMethod::Pooled, 0.05).is_err());
+        let zero = cohens_d(&[1.0, 1.0], &[2.0, 2.0], SmdMethod::Pooled, 0.05).unwrap_err();
+        assert_eq!(zero.to_string(), StatsError::SpecialCase("the standard deviation of the samples is zero").to_string());
+        assert!(cohens_d(&X, &[2.0, 2.0], SmdMethod::Glass, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_pooled_variance() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [10.0];
+        let c = [0.0, 4.0];
+        // the single observation contributes no degrees of freedom
+        assert_almost_eq!(pooled_variance(&[&a, &b, &c]).unwrap(), (3.0 * 5.0 / 3.0 + 8.0) / 4.0, 1e-15);
+        assert_almost_eq!(pooled_variance(&[&a]).unwrap(), 5.0 / 3.0, 1e-15);
+        assert!(pooled_variance(&[]).is_err());
+        assert!(pooled_variance(&[&a, &[]]).is_err());
+        assert!(pooled_variance(&[&b, &b]).is_err());
+    }
+
+    #[test]
+    fn test_cles() {
+        assert_eq!(cles(&[3.0, 4.0], &[1.0, 2.0]), 1.0);
+        assert_eq!(cles(&[1.0, 2.0], &[3.0, 4.0]), 0.0);
+        assert_eq!(cles(&[2.0, 2.0], &[2.0]), 0.5);
+        assert_almost_eq!(cles(&X, &Y), 34.0 / 36.0, 1e-15);
+        assert_almost_eq!(cles(&X, &Y) + cles(&Y, &X), 1.0, 1e-15);
+        assert!(cles(&[], &Y).is_nan());
+    }
+}
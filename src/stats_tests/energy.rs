@@ -0,0 +1,196 @@
+//! Provides the [energy distance](https://en.wikipedia.org/wiki/Energy_distance)
+//! between two samples and a permutation test built on top of it
+
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::f64;
+
+/// Computes the energy distance between the samples `a` and `b`
+///
+/// # Formula
+///
+/// ```text
+/// 2 * E|X - Y| - E|X - X'| - E|Y - Y'|
+/// ```
+///
+/// where the expectations are replaced by the means over all pairs of
+/// observations (the V-statistic form), so the result is always
+/// non-negative.
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if either sample is empty
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::energy::energy_distance;
+///
+/// let a = [1.0, 2.0, 3.0];
+/// assert_eq!(energy_distance(&a, &a), 0.0);
+/// assert!(energy_distance(&a, &[4.0, 5.0, 6.0]) > 0.0);
+/// ```
+pub fn energy_distance(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return f64::NAN;
+    }
+    2.0 * mean_abs_diff(a, b) - mean_abs_diff(a, a) - mean_abs_diff(b, b)
+}
+
+/// Performs a two-sample permutation test of the null hypothesis that `a`
+/// and `b` are drawn from the same distribution, using the energy distance
+/// as the test statistic. Returns the energy distance with the p-value.
+///
+/// The pooled observations are randomly relabelled `n_perms` times and the
+/// p-value is the proportion of permutations whose energy distance is at
+/// least as large as the observed one, counting the observed labelling
+/// itself, i.e. `(1 + #{E_perm >= E_obs}) / (1 + n_perms)`.
+///
+/// # Remarks
+///
+/// Each permutation costs `O((n + m)^2)` where `n` and `m` are the sample
+/// sizes.
+///
+/// # Errors
+///
+/// Returns an error if either sample is empty, if an observation is not
+/// finite, or if `n_perms` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::stats_tests::energy::energy_test;
+///
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let a = [0.1, 0.5, 0.3, 0.9, 0.4, 0.2, 0.7, 0.8];
+/// let b = [5.2, 5.8, 5.1, 5.6, 5.4, 5.9, 5.3, 5.7];
+/// let result = energy_test(&a, &b, 999, &mut rng).unwrap();
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn energy_test<R: Rng>(
+    a: &[f64],
+    b: &[f64],
+    n_perms: usize,
+    rng: &mut R,
+) -> Result<TestResult> {
+    if a.is_empty() || b.is_empty() {
+        return Err(StatsError::SpecialCase("samples must not be empty"));
+    }
+    if a.iter().chain(b).any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("samples must be finite"));
+    }
+    if n_perms == 0 {
+        return Err(StatsError::ArgMustBePositive("n_perms"));
+    }
+
+    let pooled: Vec<f64> = a.iter().chain(b).copied().collect();
+    let n = pooled.len();
+    let dist: Vec<f64> = pooled
+        .iter()
+        .flat_map(|x| pooled.iter().map(move |y| (x - y).abs()))
+        .collect();
+
+    let mut idx: Vec<usize> = (0..n).collect();
+    let observed = pooled_energy_distance(&dist, &idx, a.len());
+    let mut extreme = 0usize;
+    for _ in 0..n_perms {
+        idx.shuffle(rng);
+        if pooled_energy_distance(&dist, &idx, a.len()) >= observed {
+            extreme += 1;
+        }
+    }
+    Ok(TestResult::new(
+        "Energy distance permutation test",
+        "E",
+        observed,
+        (1 + extreme) as f64 / (1 + n_perms) as f64,
+        Alternative::TwoSided,
+        vec![a.len(), b.len()],
+    ))
+}
+
+/// Mean of `|x - y|` over all pairs `(x, y)` with `x` in `a` and `y` in `b`
+fn mean_abs_diff(a: &[f64], b: &[f64]) -> f64 {
+    let sum: f64 = a
+        .iter()
+        .map(|x| b.iter().map(|y| (x - y).abs()).sum::<f64>())
+        .sum();
+    sum / (a.len() * b.len()) as f64
+}
+
+/// Energy distance between the first `split` pooled observations listed in
+/// `idx` and the remaining ones, using the precomputed row-major distance
+/// matrix `dist` of the pooled sample
+fn pooled_energy_distance(dist: &[f64], idx: &[usize], split: usize) -> f64 {
+    let n = idx.len();
+    let (first, second) = idx.split_at(split);
+    let mean = |xs: &[usize], ys: &[usize]| {
+        let sum: f64 = xs
+            .iter()
+            .map(|&i| ys.iter().map(|&j| dist[i * n + j]).sum::<f64>())
+            .sum();
+        sum / (xs.len() * ys.len()) as f64
+    };
+    2.0 * mean(first, second) - mean(first, first) - mean(second, second)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn sample(dist: Normal, n: usize, rng: &mut StdRng) -> Vec<f64> {
+        (0..n).map(|_| dist.sample(rng)).collect()
+    }
+
+    #[test]
+    fn test_energy_distance() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [2.0, 4.0];
+        // 2 * 8/6 - 8/9 - 4/4
+        assert_almost_eq!(energy_distance(&a, &b), 16.0 / 6.0 - 8.0 / 9.0 - 1.0, 1e-15);
+        assert_almost_eq!(energy_distance(&a, &b), energy_distance(&b, &a), 1e-15);
+        assert_eq!(energy_distance(&a, &a), 0.0);
+        assert!(energy_distance(&a, &[]).is_nan());
+        assert!(energy_distance(&[], &b).is_nan());
+    }
+
+    #[test]
+    fn test_energy_test_same_distribution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let a = sample(normal, 50, &mut rng);
+        let b = sample(normal, 60, &mut rng);
+        let result = energy_test(&a, &b, 499, &mut rng).unwrap();
+        assert!(result.p_value > 0.05, "p = {}", result.p_value);
+        assert_almost_eq!(result.statistic, energy_distance(&a, &b), 1e-12);
+        assert_eq!(result.sample_sizes, vec![50, 60]);
+    }
+
+    #[test]
+    fn test_energy_test_different_distribution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let a = sample(Normal::new(0.0, 1.0).unwrap(), 50, &mut rng);
+        let b = sample(Normal::new(1.0, 3.0).unwrap(), 60, &mut rng);
+        let p = energy_test(&a, &b, 499, &mut rng).unwrap().p_value;
+        assert!(p < 0.01, "p = {}", p);
+    }
+
+    #[test]
+    fn test_energy_test_bad_input() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(energy_test(&[], &[1.0], 10, &mut rng).is_err());
+        assert!(energy_test(&[1.0], &[], 10, &mut rng).is_err());
+        assert!(energy_test(&[1.0, f64::NAN], &[2.0], 10, &mut rng).is_err());
+        assert!(energy_test(&[1.0], &[f64::INFINITY], 10, &mut rng).is_err());
+        assert_eq!(energy_test(&[1.0], &[2.0], 0, &mut rng), Err(StatsError::ArgMustBePositive("n_perms")));
+    }
+}
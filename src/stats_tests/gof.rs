@@ -0,0 +1,536 @@
+//! Provides goodness-of-fit tests of distributions whose parameters are
+//! estimated from the data, with p-values calibrated by the parametric
+//! bootstrap
+//!
+//! The classical p-values of the Kolmogorov-Smirnov and Anderson-Darling
+//! statistics assume a fully specified distribution. Fitting the parameters
+//! to the same data brings the distribution closer to the sample, so those
+//! p-values are much too large, which is the problem solved by Lilliefors'
+//! tables. The parametric bootstrap instead simulates the null distribution
+//! of the statistic by refitting every simulated sample.
+
+use crate::distribution::{ContinuousCDF, Exp, Normal};
+use crate::statistics::Statistics;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use rand::distributions::Distribution;
+use rand::Rng;
+use std::f64;
+
+/// The replicates of a parametric bootstrap goodness-of-fit test, which
+/// accompany its result
+#[derive(Clone, PartialEq, Debug)]
+pub struct BootstrapReplicates {
+    /// The statistics of the simulated samples, in the order of simulation,
+    /// which approximate the null distribution of the statistic
+    pub replicates: Vec<f64>,
+    /// The number of simulated samples that were discarded because their
+    /// fit failed
+    pub failures: usize,
+}
+
+/// Computes the Kolmogorov-Smirnov statistic `D`, the largest distance
+/// between the empirical distribution function of `data` and the cumulative
+/// distribution function of `dist`
+///
+/// # Formula
+///
+/// ```text
+/// D = max_i max(i / n - F(x_(i)), F(x_(i)) - (i - 1) / n)
+/// ```
+///
+/// where `x_(i)` is the `i`-th smallest observation. Returns `f64::NAN` for
+/// empty data.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Uniform;
+/// use statrs::stats_tests::gof::ks_statistic;
+///
+/// let dist = Uniform::new(0.0, 1.0).unwrap();
+/// assert!((ks_statistic(&[0.1, 0.5, 0.6], &dist) - 0.4).abs() < 1e-15);
+/// ```
+pub fn ks_statistic<D: ContinuousCDF<f64, f64>>(data: &[f64], dist: &D) -> f64 {
+    if data.is_empty() {
+        return f64::NAN;
+    }
+    let sorted = sorted(data);
+    let n = sorted.len() as f64;
+    sorted.iter().enumerate().fold(0.0, |acc: f64, (i, &x)| {
+        let cdf = dist.cdf(x);
+        acc.max((i + 1) as f64 / n - cdf).max(cdf - i as f64 / n)
+    })
+}
+
+/// Computes the Anderson-Darling statistic `A²` of `data` against `dist`,
+/// which weights the squared distances between the distribution functions
+/// by the inverse variance of the empirical one and so is more sensitive
+/// in the tails than the Kolmogorov-Smirnov statistic
+///
+/// # Formula
+///
+/// ```text
+/// A² = -n - Σ_i (2i - 1) / n [ln F(x_(i)) + ln(1 - F(x_(n + 1 - i)))]
+/// ```
+///
+/// where `x_(i)` is the `i`-th smallest observation. The upper tail uses the
+/// survival function for accuracy. Returns `f64::NAN` for empty data and
+/// `f64::INFINITY` if an observation is outside the support.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Uniform;
+/// use statrs::stats_tests::gof::anderson_darling_statistic;
+///
+/// let dist = Uniform::new(0.0, 1.0).unwrap();
+/// let a2 = anderson_darling_statistic(&[0.5], &dist);
+/// assert!((a2 - (-1.0 - 2.0 * 0.5f64.ln())).abs() < 1e-15);
+/// ```
+pub fn anderson_darling_statistic<D: ContinuousCDF<f64, f64>>(data: &[f64], dist: &D) -> f64 {
+    if data.is_empty() {
+        return f64::NAN;
+    }
+    let sorted = sorted(data);
+    let n = sorted.len();
+    let sum: f64 = sorted
+        .iter()
+        .zip(sorted.iter().rev())
+        .enumerate()
+        .map(|(i, (&lower, &upper))| {
+            (2 * i + 1) as f64 * (dist.cdf(lower).ln() + dist.sf(upper).ln())
+        })
+        .sum();
+    -(n as f64) - sum / n as f64
+}
+
+/// Performs a goodness-of-fit test of the family fitted by `fit`, with the
+/// statistic `statistic` of the data against the fitted distribution and
+/// the p-value calibrated with `n_boot` samples of the parametric
+/// bootstrap drawn with `rng`. Larger statistics indicate a worse fit.
+///
+/// Every bootstrap replicate draws a sample of the size of `data` from the
+/// distribution fitted to the data, refits the family to it and computes
+/// the statistic of the simulated sample against its own fit, so that the
+/// replicates follow the null distribution of the statistic including the
+/// effect of the estimation. The p-value is
+/// `(1 + #{replicates ≥ observed}) / (1 + replicates)`. Returns the test
+/// together with the replicates.
+///
+/// # Remarks
+///
+/// The p-value is exact in finite samples when the null distribution of
+/// the statistic does not depend on the parameters, as for location-scale
+/// families fitted by equivariant estimators, and asymptotically valid
+/// otherwise. Replicates whose fit fails are discarded and counted in
+/// [`BootstrapReplicates::failures`]. The result is deterministic for a given
+/// state of `rng`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty, if `n_boot` is zero, if `fit` fails
+/// on `data`, if the statistic of the data is `NaN`, or if the fits of all
+/// replicates fail
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::Exp;
+/// use statrs::stats_tests::gof::{anderson_darling_statistic, parametric_bootstrap_gof};
+///
+/// let data = [0.3, 1.2, 0.1, 2.5, 0.7, 0.4, 1.9, 0.05, 0.8, 3.1];
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (result, bootstrap) = parametric_bootstrap_gof(
+///     &data,
+///     Exp::fit_mle,
+///     |x, d: &Exp| anderson_darling_statistic(x, d),
+///     199,
+///     &mut rng,
+/// )
+/// .unwrap();
+/// assert!(result.p_value > 0.05);
+/// assert_eq!(bootstrap.replicates.len(), 199);
+/// ```
+pub fn parametric_bootstrap_gof<D, F, S, R>(
+    data: &[f64],
+    fit: F,
+    statistic: S,
+    n_boot: usize,
+    rng: &mut R,
+) -> Result<(TestResult, BootstrapReplicates)>
+where
+    D: Distribution<f64>,
+    F: Fn(&[f64]) -> Result<D>,
+    S: Fn(&[f64], &D) -> f64,
+    R: Rng + ?Sized,
+{
+    parametric_bootstrap_gof_with(data, fit, statistic, n_boot, rng, |_, _| true)
+}
+
+/// Performs the test of `parametric_bootstrap_gof`, calling `progress` after
+/// every replicate with the number of completed replicates and the number
+/// of them at least as large as the observed statistic. The bootstrap stops
+/// early when `progress` returns `false`, and the p-value is then computed
+/// from the completed replicates.
+///
+/// # Remarks
+///
+/// Stopping once `h` replicates exceed the observed statistic, with `h`
+/// around `10` to `20`, is the sequential Monte Carlo test of Besag and
+/// Clifford: badly fitting data, whose p-values are small, still use all
+/// the replicates, while well fitting data stop after a few, which saves
+/// most of the cost of expensive fits with little loss of power.
+///
+/// # Errors
+///
+/// As `parametric_bootstrap_gof`
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::distribution::Exp;
+/// use statrs::stats_tests::gof::{ks_statistic, parametric_bootstrap_gof_with};
+///
+/// let data = [0.3, 1.2, 0.1, 2.5, 0.7, 0.4, 1.9, 0.05, 0.8, 3.1];
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (_, bootstrap) = parametric_bootstrap_gof_with(
+///     &data,
+///     Exp::fit_mle,
+///     |x, d: &Exp| ks_statistic(x, d),
+///     9999,
+///     &mut rng,
+///     |_, exceedances| exceedances < 10,
+/// )
+/// .unwrap();
+/// // the data fit well, so the bootstrap stops early
+/// assert!(bootstrap.replicates.len() < 100);
+/// ```
+pub fn parametric_bootstrap_gof_with<D, F, S, R, P>(
+    data: &[f64],
+    fit: F,
+    statistic: S,
+    n_boot: usize,
+    rng: &mut R,
+    mut progress: P,
+) -> Result<(TestResult, BootstrapReplicates)>
+where
+    D: Distribution<f64>,
+    F: Fn(&[f64]) -> Result<D>,
+    S: Fn(&[f64], &D) -> f64,
+    R: Rng + ?Sized,
+    P: FnMut(usize, usize) -> bool,
+{
+    if data.is_empty() || n_boot == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let fitted = fit(data)?;
+    let observed = statistic(data, &fitted);
+    if observed.is_nan() {
+        return Err(StatsError::SpecialCase(
+            "the statistic of the data is not a number",
+        ));
+    }
+    let mut replicates = Vec::with_capacity(n_boot);
+    let mut failures = 0;
+    let mut exceedances = 0;
+    let mut sample = vec![0.0; data.len()];
+    for _ in 0..n_boot {
+        for x in sample.iter_mut() {
+            *x = fitted.sample(rng);
+        }
+        let replicate = match fit(&sample) {
+            Ok(refitted) => statistic(&sample, &refitted),
+            Err(_) => f64::NAN,
+        };
+        if replicate.is_nan() {
+            failures += 1;
+        } else {
+            if replicate >= observed {
+                exceedances += 1;
+            }
+            replicates.push(replicate);
+        }
+        if !progress(replicates.len(), exceedances) {
+            break;
+        }
+    }
+    if replicates.is_empty() && failures > 0 {
+        return Err(StatsError::SpecialCase(
+            "the fits of all bootstrap samples failed",
+        ));
+    }
+    let p_value = (1 + exceedances) as f64 / (1 + replicates.len()) as f64;
+    let test = TestResult::new(
+        "Parametric bootstrap goodness-of-fit test",
+        "statistic",
+        observed,
+        p_value,
+        Alternative::Greater,
+        vec![data.len()],
+    );
+    Ok((
+        test,
+        BootstrapReplicates {
+            replicates,
+            failures,
+        },
+    ))
+}
+
+/// Performs the Lilliefors test of normality, the Kolmogorov-Smirnov test
+/// against the normal distribution with the mean and the standard deviation
+/// of `data`, with the p-value calibrated by `n_boot` samples of the
+/// parametric bootstrap of `parametric_bootstrap_gof`
+///
+/// # Remarks
+///
+/// As in Lilliefors' tables, the standard deviation is the sample standard
+/// deviation with divisor `n - 1`. The null distribution of the statistic
+/// does not depend on the parameters, so the p-value is exact up to the
+/// Monte Carlo error.
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations, if an
+/// observation is not finite, if all observations are equal, or if
+/// `n_boot` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::stats_tests::gof::lilliefors_normal;
+///
+/// let data = [2.1, 3.4, 1.9, 2.8, 3.0, 2.5, 2.2, 3.7, 2.9, 2.4];
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (result, _) = lilliefors_normal(&data, 999, &mut rng).unwrap();
+/// assert!(result.p_value > 0.05);
+/// ```
+pub fn lilliefors_normal<R: Rng + ?Sized>(
+    data: &[f64],
+    n_boot: usize,
+    rng: &mut R,
+) -> Result<(TestResult, BootstrapReplicates)> {
+    let fit = |x: &[f64]| {
+        if x.len() < 2 || x.iter().any(|v| !v.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        Normal::new(x.mean(), x.std_dev())
+    };
+    let (mut test, bootstrap) =
+        parametric_bootstrap_gof(data, fit, |x, d: &Normal| ks_statistic(x, d), n_boot, rng)?;
+    test.method = "Lilliefors test of normality";
+    test.statistic_name = "D";
+    Ok((test, bootstrap))
+}
+
+/// Performs the Lilliefors test of exponentiality, the Kolmogorov-Smirnov
+/// test against the exponential distribution with the mean of `data`, with
+/// the p-value calibrated by `n_boot` samples of the parametric bootstrap of
+/// `parametric_bootstrap_gof`
+///
+/// # Remarks
+///
+/// The null distribution of the statistic does not depend on the rate, so
+/// the p-value is exact up to the Monte Carlo error.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty, if an observation is negative or
+/// not finite, if all observations are zero, or if `n_boot` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::stats_tests::gof::lilliefors_exponential;
+///
+/// // data clustered away from zero are far from exponential
+/// let data: Vec<f64> = (1..=50).map(|i| 10.0 + 0.1 * i as f64).collect();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let (result, _) = lilliefors_exponential(&data, 999, &mut rng).unwrap();
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn lilliefors_exponential<R: Rng + ?Sized>(
+    data: &[f64],
+    n_boot: usize,
+    rng: &mut R,
+) -> Result<(TestResult, BootstrapReplicates)> {
+    let (mut test, bootstrap) = parametric_bootstrap_gof(
+        data,
+        Exp::fit_mle,
+        |x, d: &Exp| ks_statistic(x, d),
+        n_boot,
+        rng,
+    )?;
+    test.method = "Lilliefors test of exponentiality";
+    test.statistic_name = "D";
+    Ok((test, bootstrap))
+}
+
+/// Returns `data` sorted in increasing order, with `NaN` last
+fn sorted(data: &[f64]) -> Vec<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Uniform;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// The `q` quantile of the replicates, as the order statistic of rank
+    /// `ceil(q m)`
+    fn quantile(replicates: &[f64], q: f64) -> f64 {
+        let sorted = sorted(replicates);
+        sorted[(q * sorted.len() as f64).ceil() as usize - 1]
+    }
+
+    #[test]
+    fn test_ks_statistic() {
+        let dist = Uniform::new(0.0, 1.0).unwrap();
+        // max(1/3 - 0.1, 0.5 - 1/3, 1 - 0.6, ...) in any order
+        assert_almost_eq!(ks_statistic(&[0.6, 0.1, 0.5], &dist), 0.4, 1e-15);
+        assert_almost_eq!(ks_statistic(&[0.9], &dist), 0.9, 1e-15);
+        assert!(ks_statistic(&[], &dist).is_nan());
+    }
+
+    #[test]
+    fn test_anderson_darling_statistic() {
+        // the terms of the formula with F(x) = x
+        let dist = Uniform::new(0.0, 1.0).unwrap();
+        let a2 = anderson_darling_statistic(&[0.2, 0.7, 0.4], &dist);
+        let expected = -3.0 - (0.2f64.ln() + 0.3f64.ln() + 3.0 * (0.4f64.ln() + 0.6f64.ln()) + 5.0 * (0.7f64.ln() + 0.8f64.ln())) / 3.0;
+        assert_almost_eq!(a2, expected, 1e-14);
+        assert_eq!(anderson_darling_statistic(&[0.5, 1.5], &dist), f64::INFINITY);
+        assert!(anderson_darling_statistic(&[], &dist).is_nan());
+    }
+
+    #[test]
+    fn test_lilliefors_normal_critical_values() {
+        // Lilliefors (1967) gives 0.805 / sqrt(n) and 0.886 / sqrt(n) as the
+        // 10% and 5% critical values for n > 30
+        let mut rng = StdRng::seed_from_u64(1);
+        let data: Vec<f64> = Normal::new(0.0, 1.0).unwrap().sample_iter(&mut rng).take(100).collect();
+        let (result, bootstrap) = lilliefors_normal(&data, 4000, &mut rng).unwrap();
+        assert_eq!(bootstrap.failures, 0);
+        assert_almost_eq!(quantile(&bootstrap.replicates, 0.90), 0.0805, 0.003);
+        assert_almost_eq!(quantile(&bootstrap.replicates, 0.95), 0.0886, 0.003);
+        assert_eq!(result.method, "Lilliefors test of normality");
+        assert_eq!(result.statistic_name, "D");
+        let fit = Normal::new(data.clone().mean(), data.clone().std_dev()).unwrap();
+        assert_eq!(result.statistic, ks_statistic(&data, &fit));
+    }
+
+    #[test]
+    fn test_lilliefors_exponential_critical_values() {
+        // Lilliefors (1969) gives 0.96 / sqrt(n) and 1.06 / sqrt(n) as the
+        // 10% and 5% critical values for n > 30
+        let mut rng = StdRng::seed_from_u64(2);
+        let data: Vec<f64> = Exp::new(3.0).unwrap().sample_iter(&mut rng).take(100).collect();
+        let (result, bootstrap) = lilliefors_exponential(&data, 4000, &mut rng).unwrap();
+        assert_eq!(bootstrap.failures, 0);
+        assert_almost_eq!(quantile(&bootstrap.replicates, 0.90), 0.096, 0.003);
+        assert_almost_eq!(quantile(&bootstrap.replicates, 0.95), 0.106, 0.004);
+        assert_eq!(result.method, "Lilliefors test of exponentiality");
+    }
+
+    #[test]
+    fn test_uniform_p_values_under_null() {
+        // the calibrated p-values of normal samples are about uniform
+        let mut rng = StdRng::seed_from_u64(3);
+        let normal = Normal::new(10.0, 2.0).unwrap();
+        let p_values: Vec<f64> = (0..300)
+            .map(|_| {
+                let data: Vec<f64> = normal.sample_iter(&mut rng).take(20).collect();
+                lilliefors_normal(&data, 199, &mut rng).unwrap().0.p_value
+            })
+            .collect();
+        let below = |alpha: f64| p_values.iter().filter(|&&p| p <= alpha).count() as f64 / 300.0;
+        assert_almost_eq!(below(0.05), 0.05, 0.03);
+        assert_almost_eq!(below(0.25), 0.25, 0.06);
+        assert_almost_eq!(below(0.5), 0.5, 0.07);
+        assert_almost_eq!(p_values.iter().sum::<f64>() / 300.0, 0.5, 0.05);
+    }
+
+    #[test]
+    fn test_power() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let data: Vec<f64> = Exp::new(1.0).unwrap().sample_iter(&mut rng).take(100).collect();
+        assert!(lilliefors_normal(&data, 999, &mut rng).unwrap().0.rejects(0.01));
+        let data: Vec<f64> = Uniform::new(1.0, 2.0).unwrap().sample_iter(&mut rng).take(100).collect();
+        assert!(lilliefors_exponential(&data, 999, &mut rng).unwrap().0.rejects(0.01));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let data = [0.3, 1.2, 0.1, 2.5, 0.7, 0.4, 1.9, 0.05, 0.8, 3.1];
+        let run = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            parametric_bootstrap_gof(&data, Exp::fit_mle, |x, d: &Exp| anderson_darling_statistic(x, d), 99, &mut rng).unwrap()
+        };
+        assert_eq!(run(5), run(5));
+        assert_ne!(run(5).1.replicates, run(6).1.replicates);
+    }
+
+    #[test]
+    fn test_early_stopping() {
+        let data = [0.3, 1.2, 0.1, 2.5, 0.7, 0.4, 1.9, 0.05, 0.8, 3.1];
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut calls = Vec::new();
+        let (result, bootstrap) = parametric_bootstrap_gof_with(
+            &data, Exp::fit_mle, |x, d: &Exp| ks_statistic(x, d), 9999, &mut rng,
+            |completed, exceedances| {
+                calls.push((completed, exceedances));
+                exceedances < 10
+            },
+        ).unwrap();
+        let m = bootstrap.replicates.len();
+        assert_eq!(calls.len(), m);
+        assert_eq!(calls.last(), Some(&(m, 10)));
+        assert!(calls.windows(2).all(|w| w[1].0 == w[0].0 + 1 && w[1].1 >= w[0].1));
+        assert_eq!(result.p_value, 11.0 / (1 + m) as f64);
+    }
+
+    #[test]
+    fn test_failures() {
+        // a fit that fails on every other simulated sample
+        let data = [0.3, 1.2, 0.1, 2.5];
+        let count = std::cell::Cell::new(0);
+        let fit = |x: &[f64]| {
+            count.set(count.get() + 1);
+            if count.get() % 2 == 1 { Exp::fit_mle(x) } else { Err(StatsError::BadParams) }
+        };
+        let mut rng = StdRng::seed_from_u64(8);
+        let (_, bootstrap) = parametric_bootstrap_gof(&data, fit, |x, d: &Exp| ks_statistic(x, d), 10, &mut rng).unwrap();
+        assert_eq!(bootstrap.failures, 5);
+        assert_eq!(bootstrap.replicates.len(), 5);
+        let mut rng = StdRng::seed_from_u64(8);
+        let first = std::cell::Cell::new(true);
+        let fit = |x: &[f64]| if first.replace(false) { Exp::fit_mle(x) } else { Err(StatsError::BadParams) };
+        assert!(parametric_bootstrap_gof(&data, fit, |x, d: &Exp| ks_statistic(x, d), 10, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(lilliefors_normal(&[], 10, &mut rng).is_err());
+        assert!(lilliefors_normal(&[1.0], 10, &mut rng).is_err());
+        assert!(lilliefors_normal(&[2.0; 5], 10, &mut rng).is_err());
+        assert!(lilliefors_normal(&[1.0, f64::NAN, 3.0], 10, &mut rng).is_err());
+        assert!(lilliefors_normal(&[1.0, 2.0, 4.0], 0, &mut rng).is_err());
+        assert!(lilliefors_exponential(&[1.0, -2.0], 10, &mut rng).is_err());
+        assert!(lilliefors_exponential(&[0.0, 0.0], 10, &mut rng).is_err());
+    }
+}
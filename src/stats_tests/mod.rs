@@ -0,0 +1,64 @@
+//! Provides hypothesis tests and related inferential procedures
+//!
+//! The tests and procedures of every submodule are re-exported here, with
+//! the types of their options and results; the statistics they are built
+//! on, such as `gof::ks_statistic` or `energy::energy_distance`, stay in
+//! their submodules.
+
+pub use self::benford::{
+    first_digit_test, first_two_digits_test, summation_test, BenfordReport, Conformity,
+    DigitProportion, SummationReport,
+};
+pub use self::circular::rayleigh_test;
+pub use self::dip::dip_test;
+pub use self::dispersion::{
+    dispersion_score_test, dispersion_test, fit_poisson_vs_negbin, PoissonNegBinFit,
+};
+pub use self::effect::{
+    cles, cohens_d, fisher_exact_test, hedges_g, number_needed_to_treat, odds_ratio,
+    risk_difference, risk_ratio, EffectEstimate, OddsRatioMethod, SmdEstimate, SmdMethod,
+};
+pub use self::energy::energy_test;
+pub use self::gof::{
+    lilliefors_exponential, lilliefors_normal, parametric_bootstrap_gof,
+    parametric_bootstrap_gof_with, BootstrapReplicates,
+};
+pub use self::multinomial::{multinomial_ci, MultinomialCiMethod};
+pub use self::planning::{
+    minimum_detectable_effect, n_for_power, power_t_test, sample_size_for_mean,
+    sample_size_for_proportion,
+};
+pub use self::posthoc::{tukey_hsd, PairwiseComparison};
+pub use self::proportion::{
+    binomial_test, binomial_test_with, confidence_distribution, proportion_ci,
+    BinomialConfidence, CiMethod,
+};
+pub use self::rate::{poisson_rate_ratio_test, poisson_test};
+pub use self::runs::{runs_test, runs_test_binary};
+pub use self::survival::{logrank_test, LogRankEvents};
+pub use self::test_result::{
+    Alternative, ConfidenceInterval, EffectSize, EffectSizeKind, Estimate, PValueMethod,
+    TestResult,
+};
+pub use self::tolerance::{nonparametric_tolerance_interval, normal_tolerance_interval, Sided};
+pub use self::trend::{mann_kendall, seasonal_mann_kendall, sens_slope};
+pub use self::ttest::yuen_t_test;
+
+pub mod benford;
+pub mod circular;
+pub mod dip;
+pub mod dispersion;
+pub mod effect;
+pub mod energy;
+pub mod gof;
+pub mod multinomial;
+pub mod planning;
+pub mod posthoc;
+pub mod proportion;
+pub mod rate;
+pub mod runs;
+pub mod survival;
+pub mod test_result;
+pub mod tolerance;
+pub mod trend;
+pub mod ttest;
@@ -0,0 +1,295 @@
+//! Provides simultaneous confidence intervals for the category
+//! probabilities of a multinomial distribution
+
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::function::{beta, gamma};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Selects the construction used by `multinomial_ci`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MultinomialCiMethod {
+    /// Goodman's (1965) intervals, inverting a chi-squared test with one
+    /// degree of freedom at the Bonferroni-adjusted level `alpha / k`
+    Goodman,
+    /// Sison and Glaz's (1995) intervals, based on an Edgeworth
+    /// approximation to the distribution of the maximum deviation of the
+    /// counts from their expectations under truncated Poisson sampling
+    SisonGlaz,
+    /// Per-category exact Clopper-Pearson intervals at the
+    /// Bonferroni-adjusted level `alpha / k`
+    ClopperPearson,
+}
+
+/// Computes simultaneous `1 - alpha` confidence intervals for the
+/// probabilities of the `k` categories of a multinomial distribution given
+/// the observed `counts`, returning one `(lower, upper)` pair per category
+///
+/// # Remarks
+///
+/// Categories with a zero count get the one-sided interval `[0, upper]`
+/// under every method. The Sison-Glaz intervals follow the algorithm of the
+/// original paper as implemented by `DescTools::MultinomCI` in R; they are
+/// generally the shortest when no category dominates the counts.
+///
+/// # Errors
+///
+/// Returns an error if `counts` is empty or sums to zero, or if `alpha` is
+/// not in `(0, 1)`. The `SisonGlaz` method returns an error if no
+/// interval half-width attains the requested coverage.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{multinomial_ci, MultinomialCiMethod};
+///
+/// let ci = multinomial_ci(&[0, 5, 15, 30], 0.1, MultinomialCiMethod::Goodman).unwrap();
+/// assert_eq!(ci[0].0, 0.0);
+/// assert!(ci.iter().zip(&[0.0, 0.1, 0.3, 0.6]).all(|(c, p)| c.0 <= *p && *p <= c.1));
+/// ```
+pub fn multinomial_ci(
+    counts: &[u64],
+    alpha: f64,
+    method: MultinomialCiMethod,
+) -> Result<Vec<(f64, f64)>> {
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    let n: u64 = counts.iter().sum();
+    if counts.is_empty() || n == 0 {
+        return Err(StatsError::BadParams);
+    }
+    match method {
+        MultinomialCiMethod::Goodman => Ok(goodman(counts, n, alpha)),
+        MultinomialCiMethod::SisonGlaz => sison_glaz(counts, n, alpha),
+        MultinomialCiMethod::ClopperPearson => Ok(clopper_pearson(counts, n, alpha)),
+    }
+}
+
+fn goodman(counts: &[u64], n: u64, alpha: f64) -> Vec<(f64, f64)> {
+    let k = counts.len() as f64;
+    let n = n as f64;
+    // the 1 - alpha / k quantile of χ²_1 is the square of a normal quantile
+    let z = -Normal::standard().inverse_cdf(alpha / (2.0 * k));
+    let a = z * z;
+    counts
+        .iter()
+        .map(|&x| {
+            let x = x as f64;
+            let center = a + 2.0 * x;
+            let half = (a * (a + 4.0 * x * (n - x) / n)).sqrt();
+            let denom = 2.0 * (n + a);
+            (
+                ((center - half) / denom).max(0.0),
+                ((center + half) / denom).min(1.0),
+            )
+        })
+        .collect()
+}
+
+fn clopper_pearson(counts: &[u64], n: u64, alpha: f64) -> Vec<(f64, f64)> {
+    let tail = alpha / (2.0 * counts.len() as f64);
+    counts
+        .iter()
+        .map(|&x| {
+            let lower = if x == 0 {
+                0.0
+            } else {
+                beta::inv_beta_reg(x as f64, (n - x + 1) as f64, tail)
+            };
+            let upper = if x == n {
+                1.0
+            } else {
+                beta::inv_beta_reg((x + 1) as f64, (n - x) as f64, 1.0 - tail)
+            };
+            (lower, upper)
+        })
+        .collect()
+}
+
+/// Poisson cdf `P(X <= q)` with rate `lambda`, accepting a zero rate and a
+/// negative `q`
+fn poisson_cdf(q: f64, lambda: f64) -> f64 {
+    if q < 0.0 {
+        0.0
+    } else if lambda == 0.0 {
+        1.0
+    } else {
+        gamma::gamma_ur(q.floor() + 1.0, lambda)
+    }
+}
+
+/// First four central moments of a Poisson(`lambda`) variable truncated to
+/// `[lambda - c, lambda + c]`, the fourth being returned as a cumulant,
+/// followed by the probability of the truncation interval
+fn truncated_poisson_moments(c: f64, lambda: f64) -> [f64; 5] {
+    let a = lambda + c;
+    let b = (lambda - c).max(0.0);
+    let den = poisson_cdf(a, lambda) - poisson_cdf(b - 1.0, lambda);
+    let mut mu = [0.0; 5];
+    for (r, m) in mu.iter_mut().enumerate().skip(1) {
+        let r = r as f64;
+        let pois_a = poisson_cdf(a, lambda) - poisson_cdf(a - r, lambda);
+        let pois_b = poisson_cdf(b - 1.0, lambda) - poisson_cdf(b - r - 1.0, lambda);
+        *m = lambda.powf(r) * (1.0 - (pois_a - pois_b) / den);
+    }
+    let m1 = mu[1];
+    let m2 = mu[2] + m1 - m1 * m1;
+    let m3 = mu[3] + mu[2] * (3.0 - 3.0 * m1) + (m1 - 3.0 * m1 * m1 + 2.0 * m1.powi(3));
+    let m4 = mu[4] + mu[3] * (6.0 - 4.0 * m1) + mu[2] * (7.0 - 12.0 * m1 + 6.0 * m1 * m1) + m1
+        - 4.0 * m1 * m1
+        + 6.0 * m1.powi(3)
+        - 3.0 * m1.powi(4);
+    [m1, m2, m3, m4 - 3.0 * m2 * m2, den]
+}
+
+/// Edgeworth approximation of the probability that every count lies within
+/// `c` of its observed value
+fn sison_glaz_coverage(c: f64, counts: &[u64], n: f64) -> f64 {
+    let mut sums = [0.0; 4];
+    let mut prob_x = 1.0;
+    for &x in counts {
+        let m = truncated_poisson_moments(c, x as f64);
+        for (s, v) in sums.iter_mut().zip(m.iter()) {
+            *s += v;
+        }
+        prob_x *= m[4];
+    }
+    let prob_n = 1.0 / (poisson_cdf(n, n) - poisson_cdf(n - 1.0, n));
+    let z = (n - sums[0]) / sums[1].sqrt();
+    let g1 = sums[2] / sums[1].powf(1.5);
+    let g2 = sums[3] / (sums[1] * sums[1]);
+    let z2 = z * z;
+    let poly = 1.0
+        + g1 * (z2 * z - 3.0 * z) / 6.0
+        + g2 * (z2 * z2 - 6.0 * z2 + 3.0) / 24.0
+        + g1 * g1 * (z2 * z2 * z2 - 15.0 * z2 * z2 + 45.0 * z2 - 15.0) / 72.0;
+    let f = poly * (-z2 / 2.0).exp() / (2.0 * f64::consts::PI).sqrt();
+    prob_n * prob_x * f / sums[1].sqrt()
+}
+
+fn sison_glaz(counts: &[u64], n: u64, alpha: f64) -> Result<Vec<(f64, f64)>> {
+    let target = 1.0 - alpha;
+    let mut previous = 0.0;
+    let mut found = None;
+    for c in 1..=n {
+        let coverage = sison_glaz_coverage(c as f64, counts, n as f64);
+        if coverage > target && previous < target {
+            found = Some((c, coverage));
+            break;
+        }
+        previous = coverage;
+    }
+    let (c, coverage) = found.ok_or(StatsError::ComputationFailedToConverge)?;
+    let delta = (target - previous) / (coverage - previous);
+    let n = n as f64;
+    let c = (c - 1) as f64;
+    Ok(counts
+        .iter()
+        .map(|&x| {
+            let p = x as f64 / n;
+            ((p - c / n).max(0.0), (p + c / n + 2.0 * delta / n).min(1.0))
+        })
+        .collect())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTS: [u64; 7] = [56, 72, 73, 59, 62, 87, 58];
+    const SPARSE: [u64; 4] = [0, 5, 15, 30];
+
+    // reference values computed with mpmath, the Sison-Glaz ones from a
+    // transcription of the DescTools::MultinomCI algorithm
+    fn check(counts: &[u64], alpha: f64, method: MultinomialCiMethod, expected: &[(f64, f64)]) {
+        let ci = multinomial_ci(counts, alpha, method).unwrap();
+        assert_eq!(ci.len(), expected.len());
+        for (c, e) in ci.iter().zip(expected) {
+            assert_almost_eq!(c.0, e.0, 1e-10);
+            assert_almost_eq!(c.1, e.1, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_goodman() {
+        check(&COUNTS, 0.05, MultinomialCiMethod::Goodman, &[
+            (0.0851672391387468, 0.166261408130465),
+            (0.114532918904883, 0.204372582694992),
+            (0.116395289223748, 0.206727515771794),
+            (0.0906056708911603, 0.173474886565051),
+            (0.0960773800481545, 0.180655087595056),
+            (0.142749109163055, 0.239415943371816),
+            (0.0887890581298042, 0.171074195930741),
+        ]);
+        check(&SPARSE, 0.1, MultinomialCiMethod::Goodman, &[
+            (0.0, 0.0913037325319469),
+            (0.0387914803260014, 0.234251505699556),
+            (0.178592429788962, 0.457929063223816),
+            (0.442558266684907, 0.739180986808703),
+        ]);
+    }
+
+    #[test]
+    fn test_sison_glaz() {
+        check(&COUNTS, 0.05, MultinomialCiMethod::SisonGlaz, &[
+            (0.0792291220556745, 0.164355734040221),
+            (0.113490364025696, 0.198616976010243),
+            (0.115631691648822, 0.200758303633369),
+            (0.0856531049250535, 0.1707797169096),
+            (0.0920770877944325, 0.177203699778979),
+            (0.145610278372591, 0.230736890357138),
+            (0.0835117773019272, 0.168638389286474),
+        ]);
+        check(&SPARSE, 0.1, MultinomialCiMethod::SisonGlaz, &[
+            (0.0, 0.128976878210499),
+            (0.0, 0.228976878210499),
+            (0.2, 0.428976878210499),
+            (0.5, 0.728976878210499),
+        ]);
+    }
+
+    #[test]
+    fn test_clopper_pearson() {
+        check(&COUNTS, 0.05, MultinomialCiMethod::ClopperPearson, &[
+            (0.082795820197107, 0.165679975197835),
+            (0.112192206384016, 0.204001399629031),
+            (0.114057580728145, 0.206368850612219),
+            (0.0882371040687242, 0.172934523547676),
+            (0.0937131635737979, 0.180154951060004),
+            (0.140463262597298, 0.239226737865861),
+            (0.0864193657596098, 0.170520241054771),
+        ]);
+        check(&SPARSE, 0.1, MultinomialCiMethod::ClopperPearson, &[
+            (0.0, 0.0839098780079905),
+            (0.0278208205534688, 0.236034803338767),
+            (0.164755605721585, 0.466128440565704),
+            (0.432210184644054, 0.752417973481079),
+        ]);
+    }
+
+    #[test]
+    fn test_zero_and_full_counts() {
+        let methods = [MultinomialCiMethod::Goodman, MultinomialCiMethod::SisonGlaz, MultinomialCiMethod::ClopperPearson];
+        for &method in &methods {
+            let ci = multinomial_ci(&[0, 12, 0], 0.05, method).unwrap();
+            for &i in &[0, 2] {
+                assert_eq!(ci[i].0, 0.0);
+                assert!(ci[i].1 > 0.0 && ci[i].1 < 1.0);
+            }
+            assert!(ci[1].0 <= ci[1].1);
+            assert_almost_eq!(ci[1].1, 1.0, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let m = MultinomialCiMethod::Goodman;
+        assert!(multinomial_ci(&[], 0.05, m).is_err());
+        assert!(multinomial_ci(&[0, 0], 0.05, m).is_err());
+        assert!(multinomial_ci(&[1, 2], 0.0, m).is_err());
+        assert!(multinomial_ci(&[1, 2], 1.0, m).is_err());
+        assert!(multinomial_ci(&[1, 2], f64::NAN, m).is_err());
+    }
+}
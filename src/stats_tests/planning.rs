@@ -0,0 +1,451 @@
+//! Provides sample size and power calculations for planning studies
+
+use crate::distribution::{ContinuousCDF, NoncentralStudentsT, Normal, StudentsT};
+use crate::{Result, StatsError};
+
+/// Returns the two-sided standard normal critical value for `confidence`
+fn critical_value(confidence: f64) -> Result<f64> {
+    if confidence.is_nan() || confidence <= 0.0 || confidence >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("confidence", 0.0, 1.0));
+    }
+    Ok(-Normal::standard().inverse_cdf((1.0 - confidence) / 2.0))
+}
+
+/// Checks that `margin` is positive and finite
+fn check_margin(margin: f64) -> Result<()> {
+    if margin.is_nan() || margin <= 0.0 || margin.is_infinite() {
+        Err(StatsError::ArgMustBePositive("margin"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Rounds the sample size `n` up to an integer
+fn ceil_count(n: f64) -> Result<u64> {
+    let n = n.ceil();
+    if n.is_nan() || n >= u64::MAX as f64 {
+        return Err(StatsError::SpecialCase(
+            "the sample size does not fit in a u64",
+        ));
+    }
+    Ok(n as u64)
+}
+
+/// Computes the number of observations needed to estimate a proportion to
+/// within `margin` with the given `confidence`, using the normal
+/// approximation to the binomial
+///
+/// # Formula
+///
+/// ```text
+/// ⌈z^2 * p(1 - p) / E^2⌉
+/// ```
+///
+/// where `z` is the `(1 + confidence) / 2` quantile of the standard normal
+/// distribution, `p` the anticipated proportion and `E` the margin
+///
+/// # Remarks
+///
+/// `p_estimate = 0.5` gives the most conservative sample size when nothing
+/// is known about the proportion
+///
+/// # Errors
+///
+/// Returns an error if `margin` is not positive and finite, if `confidence`
+/// is not in `(0, 1)`, if `p_estimate` is not in `[0, 1]`, or if the
+/// sample size does not fit in a `u64`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::planning::sample_size_for_proportion;
+///
+/// assert_eq!(sample_size_for_proportion(0.05, 0.95, 0.5).unwrap(), 385);
+/// ```
+pub fn sample_size_for_proportion(margin: f64, confidence: f64, p_estimate: f64) -> Result<u64> {
+    check_margin(margin)?;
+    let z = critical_value(confidence)?;
+    if !(0.0..=1.0).contains(&p_estimate) {
+        return Err(StatsError::ArgIntervalIncl("p_estimate", 0.0, 1.0));
+    }
+    let n = z * z * p_estimate * (1.0 - p_estimate) / (margin * margin);
+    ceil_count(n)
+}
+
+/// Computes the number of observations needed to estimate a mean to within
+/// `margin` with the given `confidence`, for a population with known
+/// standard deviation `std_dev`
+///
+/// # Formula
+///
+/// ```text
+/// ⌈(z * σ / E)^2⌉
+/// ```
+///
+/// where `z` is the `(1 + confidence) / 2` quantile of the standard normal
+/// distribution, `σ` the standard deviation and `E` the margin
+///
+/// # Errors
+///
+/// Returns an error if `margin` or `std_dev` are not positive and finite,
+/// if `confidence` is not in `(0, 1)`, or if the sample size does not fit
+/// in a `u64`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::planning::sample_size_for_mean;
+///
+/// assert_eq!(sample_size_for_mean(5.0, 0.95, 15.0).unwrap(), 35);
+/// ```
+pub fn sample_size_for_mean(margin: f64, confidence: f64, std_dev: f64) -> Result<u64> {
+    check_margin(margin)?;
+    let z = critical_value(confidence)?;
+    if std_dev.is_nan() || std_dev <= 0.0 || std_dev.is_infinite() {
+        return Err(StatsError::ArgMustBePositive("std_dev"));
+    }
+    let n = z * std_dev / margin;
+    ceil_count(n * n)
+}
+
+/// Checks that `alpha` is in `(0, 1)`
+fn check_alpha(alpha: f64) -> Result<()> {
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0))
+    } else {
+        Ok(())
+    }
+}
+
+/// Degrees of freedom above which the central t quantile is taken from its
+/// expansion about the normal quantile, accurate to double precision there
+const EXPANSION_FREEDOM: f64 = 1000.0;
+
+/// Maximum number of steps of the searches over the sample size and the
+/// effect size
+const MAX_ITERATIONS: usize = 200;
+
+/// Returns the `p` quantile of the central t distribution with `freedom`
+/// degrees of freedom, using the expansion of Abramowitz and Stegun
+/// (26.7.5) in powers of `1 / ν` for large `freedom`
+fn t_quantile(p: f64, freedom: f64) -> f64 {
+    if freedom <= EXPANSION_FREEDOM {
+        return StudentsT::new(0.0, 1.0, freedom).unwrap().inverse_cdf(p);
+    }
+    let z = Normal::standard().inverse_cdf(p);
+    let z2 = z * z;
+    let g1 = z * (z2 + 1.0) / 4.0;
+    let g2 = z * ((5.0 * z2 + 16.0) * z2 + 3.0) / 96.0;
+    let g3 = z * (((3.0 * z2 + 19.0) * z2 + 17.0) * z2 - 15.0) / 384.0;
+    let g4 = z * ((((79.0 * z2 + 776.0) * z2 + 1482.0) * z2 - 1920.0) * z2 - 945.0) / 92160.0;
+    z + (g1 + (g2 + (g3 + g4 / freedom) / freedom) / freedom) / freedom
+}
+
+/// Power of the two-sided two-sample t-test, without argument checks
+fn two_sample_t_power(effect_size: f64, n_per_group: usize, alpha: f64) -> f64 {
+    let n = n_per_group as f64;
+    let freedom = 2.0 * n - 2.0;
+    let delta = effect_size * (n / 2.0).sqrt();
+    let critical = t_quantile(1.0 - alpha / 2.0, freedom);
+    let noncentral = NoncentralStudentsT::new(freedom, delta).unwrap();
+    let upper = noncentral.sf(critical);
+    let lower = noncentral.cdf(-critical);
+    (upper + lower).clamp(0.0, 1.0)
+}
+
+/// Computes the power of the two-sided two-sample t-test at significance
+/// level `alpha` with `n_per_group` observations in each group, when the
+/// standardized difference of the means (Cohen's `d`) is `effect_size`
+///
+/// # Formula
+///
+/// ```text
+/// 1 - F(t_c; ν, δ) + F(-t_c; ν, δ)
+/// ```
+///
+/// where `F(·; ν, δ)` is the cdf of the noncentral t distribution with
+/// `ν = 2n - 2` degrees of freedom and noncentrality `δ = d * sqrt(n / 2)`,
+/// and `t_c` is the `1 - α / 2` quantile of the central t distribution
+///
+/// # Remarks
+///
+/// Both rejection regions are counted, as G*Power does, so the power equals
+/// `alpha` when `effect_size` is zero. R's `power.t.test` ignores the
+/// opposite tail by default and reports a marginally smaller value.
+///
+/// # Errors
+///
+/// Returns an error if `effect_size` is not finite, if `n_per_group < 2`
+/// or if `alpha` is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::planning::power_t_test;
+///
+/// let power = power_t_test(0.5, 64, 0.05).unwrap();
+/// assert!((power - 0.8014596).abs() < 1e-7);
+/// ```
+pub fn power_t_test(effect_size: f64, n_per_group: usize, alpha: f64) -> Result<f64> {
+    if !effect_size.is_finite() {
+        return Err(StatsError::ArgFinite("effect_size"));
+    }
+    if n_per_group < 2 {
+        return Err(StatsError::ArgGte("n_per_group", 2.0));
+    }
+    check_alpha(alpha)?;
+    Ok(two_sample_t_power(effect_size, n_per_group, alpha))
+}
+
+/// Computes the smallest number of observations per group for which the
+/// two-sided two-sample t-test at significance level `alpha` reaches
+/// `power` when Cohen's `d` is `effect_size`
+///
+/// # Remarks
+///
+/// The power increases with the sample size, so the smallest sample size is
+/// bracketed by doubling the normal approximation
+/// `2 ((z_(1 - α/2) + z_power) / d)^2` until `power_t_test` reaches `power`
+/// and then found by bisection
+///
+/// # Errors
+///
+/// Returns an error if `effect_size` is zero or not finite, or if `power`
+/// or `alpha` are not in `(0, 1)`. Returns
+/// `StatsError::ComputationFailedToConverge` if the sample size does not
+/// fit in a `usize` or is not bracketed within a bounded number of steps.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::planning::n_for_power;
+///
+/// assert_eq!(n_for_power(0.5, 0.8, 0.05).unwrap(), 64);
+/// ```
+pub fn n_for_power(effect_size: f64, power: f64, alpha: f64) -> Result<usize> {
+    if !effect_size.is_finite() || effect_size == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    if power.is_nan() || power <= 0.0 || power >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("power", 0.0, 1.0));
+    }
+    check_alpha(alpha)?;
+    let std_normal = Normal::standard();
+    let z = std_normal.inverse_cdf(1.0 - alpha / 2.0) + std_normal.inverse_cdf(power);
+    let guess = (2.0 * (z / effect_size).powi(2)).ceil().max(2.0);
+    if guess >= usize::MAX as f64 {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let reaches = |n: usize| two_sample_t_power(effect_size, n, alpha) >= power;
+    // `lo` never reaches the power, taking a single observation as the
+    // sentinel, and `hi` always does
+    let (mut lo, mut hi) = (1, guess as usize);
+    let mut bracketed = false;
+    for _ in 0..MAX_ITERATIONS {
+        if reaches(hi) {
+            bracketed = true;
+            break;
+        }
+        lo = hi;
+        hi = hi
+            .checked_mul(2)
+            .ok_or(StatsError::ComputationFailedToConverge)?;
+    }
+    if !bracketed {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if reaches(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Ok(hi)
+}
+
+/// Computes the smallest Cohen's `d` that the two-sided two-sample t-test at
+/// significance level `alpha` detects with probability `power` when there
+/// are `n_per_group` observations in each group
+///
+/// # Remarks
+///
+/// The power function of `power_t_test` increases with the effect size from
+/// `alpha` at `d = 0`, so it is inverted by bisection starting from the
+/// normal approximation `(z_(1 - α/2) + z_power) * sqrt(2 / n)`. A `power`
+/// not above `alpha` is reached without any effect and gives `0`.
+///
+/// # Errors
+///
+/// Returns an error if `n_per_group < 2`, or if `power` or `alpha` are not
+/// in `(0, 1)`. Returns `StatsError::ComputationFailedToConverge` if the
+/// effect size is not bracketed within a bounded number of steps.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::planning::minimum_detectable_effect;
+///
+/// let d = minimum_detectable_effect(64, 0.8, 0.05).unwrap();
+/// assert!((d - 0.4991).abs() < 1e-4);
+/// ```
+pub fn minimum_detectable_effect(n_per_group: usize, power: f64, alpha: f64) -> Result<f64> {
+    if n_per_group < 2 {
+        return Err(StatsError::ArgGte("n_per_group", 2.0));
+    }
+    if power.is_nan() || power <= 0.0 || power >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("power", 0.0, 1.0));
+    }
+    check_alpha(alpha)?;
+    if power <= alpha {
+        return Ok(0.0);
+    }
+    let std_normal = Normal::standard();
+    let z = std_normal.inverse_cdf(1.0 - alpha / 2.0) + std_normal.inverse_cdf(power);
+    let guess = z * (2.0 / n_per_group as f64).sqrt();
+    let (mut lo, mut hi) = (0.0, guess);
+    let mut bracketed = false;
+    for _ in 0..MAX_ITERATIONS {
+        if two_sample_t_power(hi, n_per_group, alpha) >= power {
+            bracketed = true;
+            break;
+        }
+        lo = hi;
+        hi *= 2.0;
+    }
+    if !bracketed || !hi.is_finite() {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    for _ in 0..MAX_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if hi - lo <= 1e-14 * hi {
+            break;
+        }
+        if two_sample_t_power(mid, n_per_group, alpha) < power {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(0.5 * (lo + hi))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_size_for_proportion() {
+        // the textbook 1.96^2 * 0.25 / 0.05^2 = 384.16 for a 95% poll
+        assert_eq!(sample_size_for_proportion(0.05, 0.95, 0.5).unwrap(), 385);
+        assert_eq!(sample_size_for_proportion(0.03, 0.95, 0.5).unwrap(), 1068);
+        assert_eq!(sample_size_for_proportion(0.05, 0.99, 0.5).unwrap(), 664);
+        assert_eq!(sample_size_for_proportion(0.02, 0.90, 0.1).unwrap(), 609);
+        assert_eq!(sample_size_for_proportion(0.05, 0.95, 0.0).unwrap(), 0);
+        // sizes beyond u64::MAX are errors rather than saturating
+        assert!(sample_size_for_proportion(1e-9, 0.95, 0.5).is_ok());
+        assert!(sample_size_for_proportion(1e-10, 0.95, 0.5).is_err());
+        assert!(sample_size_for_proportion(1e-200, 0.95, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_sample_size_for_mean() {
+        assert_eq!(sample_size_for_mean(5.0, 0.95, 15.0).unwrap(), 35);
+        assert_eq!(sample_size_for_mean(1.0, 0.99, 10.0).unwrap(), 664);
+        assert_eq!(sample_size_for_mean(0.5, 0.90, 2.0).unwrap(), 44);
+        let n = sample_size_for_mean(1.0, 0.95, 1.6e9).unwrap();
+        assert!(n > 9e18 as u64 && n < u64::MAX);
+        assert!(sample_size_for_mean(1.0, 0.95, 1e10).is_err());
+        assert!(sample_size_for_mean(1e-200, 0.95, 1e200).is_err());
+    }
+
+    #[test]
+    fn test_power_t_test() {
+        // G*Power reports 0.8014596 for d = 0.5, n = 64 per group at 5%;
+        // the remaining references were computed with mpmath
+        assert_almost_eq!(power_t_test(0.5, 64, 0.05).unwrap(), 0.80145955792225420, 1e-9);
+        assert_almost_eq!(power_t_test(0.5, 20, 0.05).unwrap(), 0.33793902892504112, 1e-9);
+        assert_almost_eq!(power_t_test(1.0, 20, 0.05).unwrap(), 0.86895302772398981, 1e-9);
+        assert_almost_eq!(power_t_test(0.2, 100, 0.01).unwrap(), 0.12034614604268739, 1e-9);
+        assert_almost_eq!(power_t_test(-0.5, 64, 0.05).unwrap(), 0.80145955792225420, 1e-9);
+        assert_almost_eq!(power_t_test(0.0, 10, 0.05).unwrap(), 0.05, 1e-9);
+    }
+
+    #[test]
+    fn test_n_for_power() {
+        // power is 0.79517 with 63 and 0.80146 with 64 per group
+        assert_eq!(n_for_power(0.5, 0.8, 0.05).unwrap(), 64);
+        assert_eq!(n_for_power(-0.5, 0.8, 0.05).unwrap(), 64);
+        assert_eq!(n_for_power(0.8, 0.8, 0.05).unwrap(), 26);
+        assert_eq!(n_for_power(1.0, 0.8, 0.05).unwrap(), 17);
+        assert_eq!(n_for_power(5.0, 0.5, 0.05).unwrap(), 2);
+        for &(d, power) in &[(0.3, 0.9), (1.2, 0.95), (0.5, 0.5)] {
+            let n = n_for_power(d, power, 0.05).unwrap();
+            assert!(power_t_test(d, n, 0.05).unwrap() >= power);
+            assert!(n == 2 || power_t_test(d, n - 1, 0.05).unwrap() < power);
+        }
+    }
+
+    #[test]
+    fn test_tiny_effect() {
+        // about 15.7 million per group, with the critical values taken from
+        // the expansion of the t quantile
+        let n = n_for_power(0.001, 0.8, 0.05).unwrap();
+        assert!(power_t_test(0.001, n, 0.05).unwrap() >= 0.8);
+        assert!(power_t_test(0.001, n - 1, 0.05).unwrap() < 0.8);
+        // the normal approximation ignores the opposite rejection region,
+        // about 1e-6 of power or 40 observations here
+        let normal = 2.0 * ((1.959963984540054 + 0.8416212335729143) / 0.001f64).powi(2);
+        assert!(n as f64 <= normal && n as f64 > normal - 50.0);
+        let d = minimum_detectable_effect(n, 0.8, 0.05).unwrap();
+        assert_almost_eq!(d, 0.001, 1e-7);
+        // the sample size overflows
+        assert_eq!(n_for_power(1e-12, 0.8, 0.05), Err(StatsError::ComputationFailedToConverge));
+        // the expansion agrees with the t quantile where both are accurate
+        for &p in &[0.9, 0.975, 0.995] {
+            let t = StudentsT::new(0.0, 1.0, 1000.0).unwrap().inverse_cdf(p);
+            assert_almost_eq!(t_quantile(p, 1000.0 + 1e-9), t, 1e-11);
+        }
+    }
+
+    #[test]
+    fn test_minimum_detectable_effect() {
+        // G*Power reports 0.4991 for n = 64 per group, 80% power at 5%; the
+        // references were computed with mpmath
+        assert_almost_eq!(minimum_detectable_effect(64, 0.8, 0.05).unwrap(), 0.49906917796582083, 1e-9);
+        assert_almost_eq!(minimum_detectable_effect(20, 0.9, 0.05).unwrap(), 1.0519929482772261, 1e-9);
+        assert_almost_eq!(minimum_detectable_effect(100, 0.8, 0.05).unwrap(), 0.39813813756823599, 1e-9);
+        let mut previous = f64::INFINITY;
+        for n in 2..200 {
+            let d = minimum_detectable_effect(n, 0.8, 0.05).unwrap();
+            assert!(d < previous);
+            assert_almost_eq!(power_t_test(d, n, 0.05).unwrap(), 0.8, 1e-9);
+            previous = d;
+        }
+        assert_eq!(minimum_detectable_effect(10, 0.05, 0.05).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(sample_size_for_proportion(0.0, 0.95, 0.5).is_err());
+        assert!(sample_size_for_proportion(f64::INFINITY, 0.95, 0.5).is_err());
+        assert!(sample_size_for_proportion(0.05, 1.0, 0.5).is_err());
+        assert!(sample_size_for_proportion(0.05, 0.0, 0.5).is_err());
+        assert!(sample_size_for_proportion(0.05, 0.95, 1.5).is_err());
+        assert!(sample_size_for_proportion(0.05, 0.95, f64::NAN).is_err());
+        assert!(sample_size_for_mean(-1.0, 0.95, 1.0).is_err());
+        assert!(sample_size_for_mean(1.0, f64::NAN, 1.0).is_err());
+        assert!(sample_size_for_mean(1.0, 0.95, 0.0).is_err());
+        assert!(power_t_test(f64::NAN, 10, 0.05).is_err());
+        assert!(power_t_test(0.5, 1, 0.05).is_err());
+        assert!(power_t_test(0.5, 10, 1.0).is_err());
+        assert!(n_for_power(0.0, 0.8, 0.05).is_err());
+        assert!(n_for_power(0.5, 1.0, 0.05).is_err());
+        assert!(n_for_power(0.5, 0.8, 0.0).is_err());
+        assert!(minimum_detectable_effect(1, 0.8, 0.05).is_err());
+        assert!(minimum_detectable_effect(10, 1.0, 0.05).is_err());
+        assert!(minimum_detectable_effect(10, 0.8, f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Provides post hoc multiple comparisons of group means following an
+//! analysis of variance
+
+use crate::distribution::{ContinuousCDF, StudentizedRange};
+use crate::statistics::Statistics;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The confidence level of the simultaneous intervals of `tukey_hsd`
+const TUKEY_LEVEL: f64 = 0.95;
+
+/// The comparison of the means of two groups, with a confidence interval
+/// and a p-value adjusted for all the pairwise comparisons made
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PairwiseComparison {
+    /// The index of the group whose mean is subtracted
+    pub first: usize,
+    /// The index of the other group, greater than `first`
+    pub second: usize,
+    /// The difference of the means, that of `second` minus that of `first`
+    pub difference: f64,
+    /// The lower simultaneous confidence bound of the difference
+    pub lower: f64,
+    /// The upper simultaneous confidence bound of the difference
+    pub upper: f64,
+    /// The p-value of the difference, adjusted for multiple comparisons
+    pub p_value: f64,
+}
+
+/// Performs [Tukey's honestly significant
+/// difference](https://en.wikipedia.org/wiki/Tukey%27s_range_test) test of
+/// all pairwise differences between the means of `groups`, with the
+/// Tukey-Kramer standard errors for unequal group sizes
+///
+/// Returns the comparisons in the order `(0, 1), (0, 2), ..., (1, 2), ...`
+/// with 95% simultaneous confidence intervals, the layout of R's
+/// `TukeyHSD`.
+///
+/// # Formula
+///
+/// ```text
+/// q = |x̄_j - x̄_i| / sqrt(MSE / 2 * (1 / n_i + 1 / n_j))
+/// ```
+///
+/// where `MSE` is the pooled within-group variance with `N - k` degrees of
+/// freedom. The p-value is the upper tail of the studentized range
+/// distribution of `k` means at `q`, and the interval is the difference
+/// plus or minus its upper 5% quantile times the standard error.
+///
+/// # Remarks
+///
+/// The comparisons hold the familywise error rate exactly with equal group
+/// sizes and conservatively otherwise, assuming normal errors with a common
+/// variance.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, if a group is
+/// empty, or if there are no within-group degrees of freedom
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::posthoc::tukey_hsd;
+///
+/// let a = [4.2, 4.8, 5.1, 3.9, 4.5, 5.0];
+/// let b = [5.6, 6.1, 5.3, 5.9, 6.4];
+/// let c = [4.9, 5.2, 5.8, 4.7, 5.5, 5.0, 5.3];
+/// let comparisons = tukey_hsd(&[&a, &b, &c]).unwrap();
+/// assert_eq!(comparisons.len(), 3);
+/// // b differs from a, c is between them
+/// assert!(comparisons[0].p_value < 0.001);
+/// assert!(comparisons[0].lower > 0.0);
+/// ```
+pub fn tukey_hsd(groups: &[&[f64]]) -> Result<Vec<PairwiseComparison>> {
+    if groups.len() < 2 {
+        return Err(StatsError::SpecialCase("at least two groups are needed"));
+    }
+    if groups.iter().any(|g| g.is_empty()) {
+        return Err(StatsError::SpecialCase("every group must be non-empty"));
+    }
+    let total: usize = groups.iter().map(|g| g.len()).sum();
+    let k = groups.len();
+    if total <= k {
+        return Err(StatsError::SpecialCase(
+            "the pooled variance needs a group with two observations",
+        ));
+    }
+    let freedom = (total - k) as f64;
+    let means: Vec<f64> = groups.iter().map(|g| g.iter().mean()).collect();
+    let sum_squares: f64 = groups
+        .iter()
+        .zip(&means)
+        .map(|(g, m)| g.iter().map(|x| (x - m) * (x - m)).sum::<f64>())
+        .sum();
+    let mse = sum_squares / freedom;
+    let range = StudentizedRange::new(k as u64, freedom)?;
+    let critical = range.inverse_cdf(TUKEY_LEVEL);
+    let mut comparisons = Vec::with_capacity(k * (k - 1) / 2);
+    for first in 0..k {
+        for second in first + 1..k {
+            let difference = means[second] - means[first];
+            let sizes = 1.0 / groups[first].len() as f64 + 1.0 / groups[second].len() as f64;
+            let standard_error = (0.5 * mse * sizes).sqrt();
+            comparisons.push(PairwiseComparison {
+                first,
+                second,
+                difference,
+                lower: difference - critical * standard_error,
+                upper: difference + critical * standard_error,
+                p_value: range.sf(difference.abs() / standard_error),
+            });
+        }
+    }
+    Ok(comparisons)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::StudentsT;
+
+    const A: [f64; 6] = [4.2, 4.8, 5.1, 3.9, 4.5, 5.0];
+    const B: [f64; 5] = [5.6, 6.1, 5.3, 5.9, 6.4];
+    const C: [f64; 7] = [4.9, 5.2, 5.8, 4.7, 5.5, 5.0, 5.3];
+
+    #[test]
+    fn test_tukey_hsd() {
+        // reference values of the studentized range computed with nested
+        // adaptive quadrature in mpmath, MSE = 0.17868888888888889 with 15
+        // degrees of freedom and a critical value of 3.6733776588971; see
+        // test_tukey_hsd_r for the comparison with R
+        let comparisons = tukey_hsd(&[&A, &B, &C]).unwrap();
+        let expected = [
+            (0, 1, 1.2766666666666667, 0.6117992163194, 1.94153411701393, 0.000448690444453),
+            (0, 2, 0.6166666666666667, 0.00580005610178658, 1.22753327723155, 0.0477198346346),
+            (1, 2, -0.66, -1.3029185514274, -0.0170814485726011, 0.0438625329134),
+        ];
+        assert_eq!(comparisons.len(), 3);
+        for (c, &(first, second, difference, lower, upper, p)) in comparisons.iter().zip(expected.iter()) {
+            assert_eq!((c.first, c.second), (first, second));
+            assert_almost_eq!(c.difference, difference, 1e-14);
+            assert_almost_eq!(c.lower, lower, 1e-9);
+            assert_almost_eq!(c.upper, upper, 1e-9);
+            assert_almost_eq!(c.p_value, p, 1e-9 * p.max(1e-3));
+        }
+    }
+
+    #[test]
+    fn test_tukey_hsd_r() {
+        // R's PlantGrowth data, groups ctrl, trt1 and trt2
+        let ctrl = [4.17, 5.58, 5.18, 6.11, 4.50, 4.61, 5.17, 4.53, 5.33, 5.14];
+        let trt1 = [4.81, 4.17, 4.41, 3.59, 5.87, 3.83, 6.03, 4.89, 4.32, 4.69];
+        let trt2 = [6.31, 5.12, 5.54, 5.50, 5.37, 5.29, 4.92, 6.15, 5.80, 5.26];
+        // TukeyHSD(aov(weight ~ group, data = PlantGrowth)):
+        //             diff        lwr       upr     p adj
+        // trt1-ctrl -0.371 -1.0622161 0.3202161 0.3908711
+        // trt2-ctrl  0.494 -0.1972161 1.1852161 0.1979960
+        // trt2-trt1  0.865  0.1737839 1.5562161 0.0120064
+        let comparisons = tukey_hsd(&[&ctrl, &trt1, &trt2]).unwrap();
+        let expected = [
+            (0, 1, -0.371, -1.0622161, 0.3202161, 0.3908711),
+            (0, 2, 0.494, -0.1972161, 1.1852161, 0.1979960),
+            (1, 2, 0.865, 0.1737839, 1.5562161, 0.0120064),
+        ];
+        for (c, &(first, second, difference, lower, upper, p)) in comparisons.iter().zip(expected.iter()) {
+            assert_eq!((c.first, c.second), (first, second));
+            assert_almost_eq!(c.difference, difference, 1e-12);
+            assert_almost_eq!(c.lower, lower, 5e-8);
+            assert_almost_eq!(c.upper, upper, 5e-8);
+            assert_almost_eq!(c.p_value, p, 5e-8);
+        }
+    }
+
+    #[test]
+    fn test_tukey_hsd_two_groups() {
+        // with two groups the test is the pooled two-sample t-test
+        let comparisons = tukey_hsd(&[&A, &C]).unwrap();
+        let variance = crate::stats_tests::effect::pooled_variance(&[&A, &C]).unwrap();
+        let se = (variance * (1.0 / 6.0 + 1.0 / 7.0)).sqrt();
+        let t = StudentsT::new(0.0, 1.0, 11.0).unwrap();
+        let p = 2.0 * t.sf(comparisons[0].difference / se);
+        assert_almost_eq!(comparisons[0].p_value, p, 1e-9);
+    }
+
+    #[test]
+    fn test_tukey_hsd_bad_input() {
+        assert!(tukey_hsd(&[&A]).is_err());
+        assert!(tukey_hsd(&[&A, &[]]).is_err());
+        assert!(tukey_hsd(&[&[1.0], &[2.0]]).is_err());
+        assert!(tukey_hsd(&[&[1.0, 1.5], &[2.0]]).is_ok());
+    }
+}
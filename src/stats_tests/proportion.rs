@@ -0,0 +1,553 @@
+//! Provides [confidence intervals for a binomial proportion](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval)
+//! and exact tests of its value
+
+use crate::distribution::{Binomial, ContinuousCDF, Discrete, DiscreteCDF, Normal};
+use crate::function::beta;
+use crate::function::root::brent;
+use crate::stats_tests::{Alternative, PValueMethod, TestResult};
+use crate::{Result, StatsError};
+
+/// Selects the construction used by `proportion_ci`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CiMethod {
+    /// The normal approximation `p̂ ± z * sqrt(p̂(1 - p̂) / n)`, which
+    /// degenerates to a single point when there are no successes or no
+    /// failures and is not recommended for small samples
+    Wald,
+    /// Wilson's score interval, inverting the score test of the proportion
+    Wilson,
+    /// The Agresti-Coull interval, applying the Wald formula after adding
+    /// `z^2 / 2` pseudo-successes and pseudo-failures
+    AgrestiCoull,
+    /// The Jeffreys interval, the equal-tailed credible interval under the
+    /// `Beta(1/2, 1/2)` prior
+    Jeffreys,
+    /// The Clopper-Pearson interval, inverting the two one-sided exact
+    /// binomial tests, whose coverage is never below the nominal level
+    ClopperPearson,
+    /// The mid-p interval, inverting the two one-sided mid-p binomial tests
+    /// as the central interval of `confidence_distribution`, which is
+    /// always narrower than the Clopper-Pearson interval
+    MidP,
+}
+
+/// Computes a `1 - alpha` confidence interval `(lower, upper)` for the
+/// success probability of a binomial distribution given `successes` out of
+/// `trials`
+///
+/// # Remarks
+///
+/// Every interval is clipped to `[0, 1]`. The Jeffreys interval uses the
+/// quantiles of `Beta(x + 1/2, n - x + 1/2)`, with its lower limit set to
+/// `0` when `x = 0` and its upper limit set to `1` when `x = n`, and the
+/// Clopper-Pearson limits are the quantiles of `Beta(x, n - x + 1)` and
+/// `Beta(x + 1, n - x)` with the same convention. Wilson,
+/// Agresti-Coull and Jeffreys intervals are the ones recommended by Brown,
+/// Cai and DasGupta (2001) for small samples.
+///
+/// # Errors
+///
+/// Returns an error if `trials == 0`, if `successes > trials` or if `alpha`
+/// is not in `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{proportion_ci, CiMethod};
+///
+/// let (lower, upper) = proportion_ci(81, 263, 0.05, CiMethod::Wilson).unwrap();
+/// assert!((lower - 0.2553).abs() < 1e-4);
+/// assert!((upper - 0.3662).abs() < 1e-4);
+/// ```
+pub fn proportion_ci(
+    successes: u64,
+    trials: u64,
+    alpha: f64,
+    method: CiMethod,
+) -> Result<(f64, f64)> {
+    if trials == 0 {
+        return Err(StatsError::ArgMustBePositive("trials"));
+    }
+    if successes > trials {
+        return Err(StatsError::ArgLteArg("successes", "trials"));
+    }
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    let x = successes as f64;
+    let n = trials as f64;
+    let z = -Normal::standard().inverse_cdf(alpha / 2.0);
+    let z2 = z * z;
+    let (lower, upper) = match method {
+        CiMethod::Wald => {
+            let p = x / n;
+            let half = z * (p * (1.0 - p) / n).sqrt();
+            (p - half, p + half)
+        }
+        CiMethod::Wilson => {
+            let p = x / n;
+            let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+            let half = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+            (center - half, center + half)
+        }
+        CiMethod::AgrestiCoull => {
+            let n_adj = n + z2;
+            let p = (x + z2 / 2.0) / n_adj;
+            let half = z * (p * (1.0 - p) / n_adj).sqrt();
+            (p - half, p + half)
+        }
+        CiMethod::Jeffreys => {
+            let (a, b) = (x + 0.5, n - x + 0.5);
+            let lower = if successes == 0 {
+                0.0
+            } else {
+                beta::inv_beta_reg(a, b, alpha / 2.0)
+            };
+            let upper = if successes == trials {
+                1.0
+            } else {
+                beta::inv_beta_reg(a, b, 1.0 - alpha / 2.0)
+            };
+            (lower, upper)
+        }
+        CiMethod::ClopperPearson => {
+            let lower = if successes == 0 {
+                0.0
+            } else {
+                beta::inv_beta_reg(x, n - x + 1.0, alpha / 2.0)
+            };
+            let upper = if successes == trials {
+                1.0
+            } else {
+                beta::inv_beta_reg(x + 1.0, n - x, 1.0 - alpha / 2.0)
+            };
+            (lower, upper)
+        }
+        CiMethod::MidP => {
+            let dist = BinomialConfidence { successes, trials };
+            (
+                dist.inverse_cdf(alpha / 2.0),
+                dist.inverse_cdf(1.0 - alpha / 2.0),
+            )
+        }
+    };
+    Ok((lower.max(0.0), upper.min(1.0)))
+}
+
+/// Performs the exact two-sided binomial test of the null hypothesis that
+/// the success probability is `p`, given `successes` out of `trials`.
+/// Returns the number of successes as the statistic with the p-value and
+/// the estimated success probability.
+///
+/// This is `binomial_test_with` reporting the `PValueMethod::Exact`
+/// p-value.
+///
+/// # Remarks
+///
+/// The p-value is the total probability under the null of the outcomes
+/// no more likely than the observed one, as in R's `binom.test`. Outcomes
+/// whose probability exceeds that of the observed one by a relative error
+/// of at most `1e-7` count as equally likely, so that ties are not lost to
+/// rounding.
+///
+/// # Errors
+///
+/// Returns an error if `trials == 0`, if `successes > trials` or if `p` is
+/// not in `[0, 1]`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::binomial_test;
+///
+/// // Mendel's peas, 682 of 925 round against a 3:1 ratio
+/// let result = binomial_test(682, 925, 0.75).unwrap();
+/// assert!((result.p_value - 0.3825).abs() < 1e-4);
+/// ```
+pub fn binomial_test(successes: u64, trials: u64, p: f64) -> Result<TestResult> {
+    binomial_test_with(successes, trials, p, PValueMethod::Exact)
+}
+
+/// Performs the two-sided binomial test of `binomial_test`, reporting the
+/// p-value selected by `method`
+///
+/// # Errors
+///
+/// Returns an error if `trials == 0`, if `successes > trials` or if `p` is
+/// not in `[0, 1]`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{binomial_test_with, PValueMethod};
+///
+/// let exact = binomial_test_with(3, 10, 0.5, PValueMethod::Exact).unwrap();
+/// let mid_p = binomial_test_with(3, 10, 0.5, PValueMethod::MidP).unwrap();
+/// // half the probability of 3 successes is removed
+/// assert!((exact.p_value - mid_p.p_value - 0.5 * 120.0 / 1024.0).abs() < 1e-15);
+/// ```
+pub fn binomial_test_with(
+    successes: u64,
+    trials: u64,
+    p: f64,
+    method: PValueMethod,
+) -> Result<TestResult> {
+    if trials == 0 {
+        return Err(StatsError::ArgMustBePositive("trials"));
+    }
+    if successes > trials {
+        return Err(StatsError::ArgLteArg("successes", "trials"));
+    }
+    if !(0.0..=1.0).contains(&p) {
+        return Err(StatsError::ArgIntervalIncl("p", 0.0, 1.0));
+    }
+    let dist = Binomial::new(p, trials)?;
+    let observed = dist.pmf(successes) * (1.0 + 1e-7);
+    let mode = p * trials as f64;
+    let x = successes as f64;
+    let p_value = if x == mode {
+        1.0
+    } else if x < mode {
+        // count the outcomes above the mode no more likely than observed
+        let y = (mode.ceil() as u64..=trials)
+            .rev()
+            .take_while(|&k| dist.pmf(k) <= observed)
+            .count() as u64;
+        let upper = if y == 0 {
+            0.0
+        } else {
+            dist.sf(trials - y)
+        };
+        dist.cdf(successes) + upper
+    } else {
+        let y = (0..=mode.floor() as u64)
+            .take_while(|&k| dist.pmf(k) <= observed)
+            .count() as u64;
+        let lower = if y == 0 { 0.0 } else { dist.cdf(y - 1) };
+        lower + dist.sf(successes - 1)
+    };
+    let (method_name, p_value) = match method {
+        PValueMethod::Exact => ("Exact binomial test", p_value),
+        PValueMethod::MidP => (
+            "Exact binomial test (mid-p)",
+            p_value - 0.5 * dist.pmf(successes),
+        ),
+    };
+    Ok(TestResult::new(
+        method_name,
+        "number of successes",
+        x,
+        p_value.clamp(0.0, 1.0),
+        Alternative::TwoSided,
+        vec![trials as usize],
+    )
+    .with_estimate("probability of success", x / trials as f64))
+}
+
+/// The mid-p confidence distribution of a binomial success probability
+/// given `successes` out of `trials`, returned by `confidence_distribution`
+///
+/// # Remarks
+///
+/// The confidence distribution function is the one-sided mid-p value of
+/// the null hypothesis `p <= p0` against `p > p0`,
+///
+/// ```text
+/// C(p0) = P(X > x; p0) + P(X = x; p0) / 2
+/// ```
+///
+/// which increases from `C(0)` to `C(1)`. These are `0` and `1` unless
+/// there are no successes, when `C(0) = 1/2`, or no failures, when
+/// `C(1) = 1/2`. Its quantiles are the mid-p confidence limits and the
+/// doubled one-sided p-value traces the confidence curve.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BinomialConfidence {
+    successes: u64,
+    trials: u64,
+}
+
+/// Returns the mid-p confidence distribution of a binomial success
+/// probability given `successes` out of `trials`, to evaluate mid-p values
+/// and confidence limits for any null value or level
+///
+/// # Errors
+///
+/// Returns an error if `trials == 0` or if `successes > trials`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::confidence_distribution;
+///
+/// let dist = confidence_distribution(1, 2).unwrap();
+/// // with one success in two trials C(p0) = p0
+/// assert!((dist.cdf(0.3) - 0.3).abs() < 1e-14);
+/// assert!((dist.p_value(0.3) - 0.6).abs() < 1e-14);
+/// assert!((dist.inverse_cdf(0.025) - 0.025).abs() < 1e-12);
+/// ```
+pub fn confidence_distribution(successes: u64, trials: u64) -> Result<BinomialConfidence> {
+    if trials == 0 {
+        return Err(StatsError::ArgMustBePositive("trials"));
+    }
+    if successes > trials {
+        return Err(StatsError::ArgLteArg("successes", "trials"));
+    }
+    Ok(BinomialConfidence { successes, trials })
+}
+
+impl BinomialConfidence {
+    /// Returns the number of successes
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+
+    /// Returns the number of trials
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+
+    /// Evaluates the confidence distribution function `C(p0)`, or returns
+    /// `f64::NAN` if `p0` is not in `[0, 1]`
+    pub fn cdf(&self, p0: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p0) {
+            return f64::NAN;
+        }
+        let (x, n) = (self.successes as f64, self.trials as f64);
+        // P(X >= x) and P(X > x) as regularized incomplete beta functions
+        let at_least = if self.successes == 0 {
+            1.0
+        } else {
+            beta::beta_reg(x, n - x + 1.0, p0)
+        };
+        let above = if self.successes == self.trials {
+            0.0
+        } else {
+            beta::beta_reg(x + 1.0, n - x, p0)
+        };
+        0.5 * (at_least + above)
+    }
+
+    /// Returns the two-sided mid-p value `2 min(C(p0), 1 - C(p0))` of the
+    /// null hypothesis that the success probability is `p0`, or `f64::NAN`
+    /// if `p0` is not in `[0, 1]`
+    ///
+    /// # Remarks
+    ///
+    /// This doubles the smaller one-sided p-value, so that the values of
+    /// `p0` where it exceeds `alpha` make up the `CiMethod::MidP` interval.
+    /// It differs from the p-value of `binomial_test_with`, which sums the
+    /// probabilities of the outcomes at most as likely as the observed one.
+    pub fn p_value(&self, p0: f64) -> f64 {
+        let c = self.cdf(p0);
+        if c.is_nan() {
+            return f64::NAN;
+        }
+        (2.0 * c.min(1.0 - c)).min(1.0)
+    }
+
+    /// Returns the smallest `p0` with `C(p0) >= level`, the lower confidence
+    /// limit at level `1 - level` for `level < 1/2`, or `f64::NAN` if
+    /// `level` is not in `[0, 1]`
+    pub fn inverse_cdf(&self, level: f64) -> f64 {
+        if !(0.0..=1.0).contains(&level) {
+            return f64::NAN;
+        }
+        if level <= self.cdf(0.0) {
+            return 0.0;
+        }
+        if level >= self.cdf(1.0) {
+            return 1.0;
+        }
+        // C is continuous and increasing, so the root is bracketed
+        brent(|p0| self.cdf(p0) - level, 0.0, 1.0, 1e-15).unwrap()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHODS: [CiMethod; 4] = [CiMethod::Wald, CiMethod::Wilson, CiMethod::AgrestiCoull, CiMethod::Jeffreys];
+
+    fn check(successes: u64, trials: u64, expected: [(f64, f64); 4]) {
+        for (&method, e) in METHODS.iter().zip(&expected) {
+            let (lower, upper) = proportion_ci(successes, trials, 0.05, method).unwrap();
+            assert_almost_eq!(lower, e.0, 1e-12);
+            assert_almost_eq!(upper, e.1, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_small_sample() {
+        // reference values computed with mpmath
+        check(3, 10, [
+            (0.015974234910674673, 0.58402576508932533),
+            (0.10779126740630104, 0.60322185253885463),
+            (0.1033384179224253, 0.60767470202273037),
+            (0.092694593938153164, 0.60581831814867124),
+        ]);
+    }
+
+    #[test]
+    fn test_published() {
+        // 81 successes in 263 trials, Wilson (0.2553, 0.3662) and Jeffreys
+        // (0.2545, 0.3656) as published, refined with mpmath
+        check(81, 263, [
+            (0.25219012621310719, 0.36377945553594224),
+            (0.25528851987827424, 0.36620957698280006),
+            (0.25522066518999679, 0.36627743167107751),
+            (0.25452193499102347, 0.3656474991740824),
+        ]);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        check(0, 20, [
+            (0.0, 0.0),
+            (0.0, 0.16112515805281933),
+            (0.0, 0.1898095605424888),
+            (0.0, 0.11663898290487539),
+        ]);
+        check(20, 20, [
+            (1.0, 1.0),
+            (0.83887484194718067, 1.0),
+            (0.8101904394575112, 1.0),
+            (0.88336101709512458, 1.0),
+        ]);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(proportion_ci(0, 0, 0.05, CiMethod::Wilson).is_err());
+        assert!(proportion_ci(3, 2, 0.05, CiMethod::Wilson).is_err());
+        assert!(proportion_ci(1, 2, 0.0, CiMethod::Wilson).is_err());
+        assert!(proportion_ci(1, 2, f64::NAN, CiMethod::Jeffreys).is_err());
+    }
+
+    #[test]
+    fn test_binomial_test() {
+        // reference values computed with mpmath using the rule of binom.test
+        assert_almost_eq!(binomial_test(682, 925, 0.75).unwrap().p_value, 0.38249155957485168, 1e-12);
+        assert_almost_eq!(binomial_test(3, 10, 0.5).unwrap().p_value, 0.34375, 1e-14);
+        assert_almost_eq!(binomial_test(7, 10, 0.5).unwrap().p_value, 0.34375, 1e-14);
+        assert_almost_eq!(binomial_test(0, 5, 0.2).unwrap().p_value, 0.5904, 1e-14);
+        assert_almost_eq!(binomial_test(10, 12, 1.0 / 3.0).unwrap().p_value, 0.00054380448629292810, 1e-15);
+        assert_almost_eq!(binomial_test(1, 12, 1.0 / 3.0).unwrap().p_value, 0.072709858667283857, 1e-14);
+        assert_eq!(binomial_test(5, 10, 0.5).unwrap().p_value, 1.0);
+        assert_eq!(binomial_test(3, 10, 0.5).unwrap().estimate.unwrap().value, 0.3);
+        assert_eq!(binomial_test(0, 10, 0.0).unwrap().p_value, 1.0);
+        assert_eq!(binomial_test(3, 10, 0.0).unwrap().p_value, 0.0);
+        assert_eq!(binomial_test(3, 10, 1.0).unwrap().p_value, 0.0);
+    }
+
+    #[test]
+    fn test_binomial_test_bad_input() {
+        assert!(binomial_test(0, 0, 0.5).is_err());
+        assert!(binomial_test(3, 2, 0.5).is_err());
+        assert!(binomial_test(1, 2, 1.5).is_err());
+        assert!(binomial_test(1, 2, f64::NAN).is_err());
+    }
+    #[test]
+    fn test_clopper_pearson() {
+        // reference values computed with mpmath
+        let (lower, upper) = proportion_ci(3, 10, 0.05, CiMethod::ClopperPearson).unwrap();
+        assert_almost_eq!(lower, 0.066739511177734469, 1e-12);
+        assert_almost_eq!(upper, 0.65245285005999724, 1e-12);
+        let (lower, upper) = proportion_ci(0, 10, 0.05, CiMethod::ClopperPearson).unwrap();
+        assert_eq!(lower, 0.0);
+        assert_almost_eq!(upper, 1.0 - 0.025f64.powf(0.1), 1e-12);
+    }
+
+    #[test]
+    fn test_mid_p_interval() {
+        // reference values computed with mpmath; with no successes the upper
+        // limit solves (1 - p)^n / 2 = α / 2, and with one success in two
+        // trials C(p) = p
+        let (lower, upper) = proportion_ci(3, 10, 0.05, CiMethod::MidP).unwrap();
+        assert_almost_eq!(lower, 0.082600216978502891, 1e-12);
+        assert_almost_eq!(upper, 0.61991856592638932, 1e-12);
+        let (lower, upper) = proportion_ci(81, 263, 0.05, CiMethod::MidP).unwrap();
+        assert_almost_eq!(lower, 0.25440210494552239, 1e-12);
+        assert_almost_eq!(upper, 0.36577347425777715, 1e-12);
+        let (lower, upper) = proportion_ci(0, 10, 0.05, CiMethod::MidP).unwrap();
+        assert_eq!(lower, 0.0);
+        assert_almost_eq!(upper, 1.0 - 0.05f64.powf(0.1), 1e-12);
+        let (lower, upper) = proportion_ci(10, 10, 0.05, CiMethod::MidP).unwrap();
+        assert_almost_eq!(lower, 0.05f64.powf(0.1), 1e-12);
+        assert_eq!(upper, 1.0);
+        let (lower, upper) = proportion_ci(1, 2, 0.05, CiMethod::MidP).unwrap();
+        assert_almost_eq!(lower, 0.025, 1e-12);
+        assert_almost_eq!(upper, 0.975, 1e-12);
+    }
+
+    #[test]
+    fn test_mid_p_narrower_than_clopper_pearson() {
+        for trials in 1..=40 {
+            for successes in 0..=trials {
+                for &alpha in &[0.01, 0.05, 0.2] {
+                    let (mid_lower, mid_upper) = proportion_ci(successes, trials, alpha, CiMethod::MidP).unwrap();
+                    let (cp_lower, cp_upper) = proportion_ci(successes, trials, alpha, CiMethod::ClopperPearson).unwrap();
+                    assert!(mid_upper - mid_lower < cp_upper - cp_lower);
+                    assert!(mid_lower >= cp_lower && mid_upper <= cp_upper);
+                    if successes > 0 {
+                        assert!(mid_lower > cp_lower);
+                    }
+                    if successes < trials {
+                        assert!(mid_upper < cp_upper);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_confidence_distribution() {
+        // reference value computed with mpmath
+        let dist = confidence_distribution(3, 10).unwrap();
+        assert_eq!((dist.successes(), dist.trials()), (3, 10));
+        assert_almost_eq!(dist.cdf(0.4), 0.7252148224, 1e-14);
+        assert_almost_eq!(dist.p_value(0.4), 2.0 * (1.0 - 0.7252148224), 1e-14);
+        assert_eq!((dist.cdf(0.0), dist.cdf(1.0)), (0.0, 1.0));
+        assert!(dist.cdf(1.5).is_nan() && dist.p_value(-0.1).is_nan());
+        assert!(dist.inverse_cdf(1.5).is_nan());
+        // the p-value is one at the median of the confidence distribution
+        assert_almost_eq!(dist.p_value(dist.inverse_cdf(0.5)), 1.0, 1e-12);
+        assert_eq!(confidence_distribution(0, 10).unwrap().cdf(0.0), 0.5);
+        assert_eq!(confidence_distribution(10, 10).unwrap().cdf(1.0), 0.5);
+        assert!(confidence_distribution(0, 0).is_err());
+        assert!(confidence_distribution(3, 2).is_err());
+    }
+
+    #[test]
+    fn test_confidence_distribution_monotone() {
+        for &(successes, trials) in &[(0, 5), (3, 10), (10, 10), (81, 263)] {
+            let dist = confidence_distribution(successes, trials).unwrap();
+            let mut previous = dist.cdf(0.0);
+            for i in 1..=1000 {
+                let c = dist.cdf(i as f64 / 1000.0);
+                assert!(c >= previous);
+                previous = c;
+            }
+            for &level in &[0.01, 0.1, 0.5, 0.9, 0.99] {
+                let p0 = dist.inverse_cdf(level);
+                if p0 > 0.0 && p0 < 1.0 {
+                    assert_almost_eq!(dist.cdf(p0), level, 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_test_mid_p() {
+        // reference values computed with mpmath
+        let mid_p = |x, n, p| binomial_test_with(x, n, p, PValueMethod::MidP).unwrap().p_value;
+        assert_almost_eq!(mid_p(682, 925, 0.75), 0.37244236487169872, 1e-12);
+        assert_almost_eq!(mid_p(3, 10, 0.5), 0.28515625, 1e-14);
+        assert_almost_eq!(mid_p(10, 12, 1.0 / 3.0), 0.00029542319843595056, 1e-15);
+        assert_eq!(mid_p(0, 10, 0.0), 0.5);
+        let exact = binomial_test_with(3, 10, 0.5, PValueMethod::Exact).unwrap();
+        assert_eq!(exact, binomial_test(3, 10, 0.5).unwrap());
+        assert_eq!(binomial_test_with(3, 10, 0.5, PValueMethod::MidP).unwrap().method, "Exact binomial test (mid-p)");
+    }
+}
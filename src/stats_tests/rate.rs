@@ -0,0 +1,232 @@
+//! Provides tests of the rates of Poisson processes observed over known
+//! exposures, such as incidence rates per person-time
+
+use crate::distribution::{Discrete, DiscreteCDF, Poisson};
+use crate::stats_tests::{binomial_test, Alternative, PValueMethod, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Performs the exact two-sample test of the null hypothesis that events
+/// occur at the same rate in two groups, given `count1` events over
+/// `exposure1` and `count2` events over `exposure2`. Returns `count1` as the
+/// statistic with the two-sided p-value and the rate ratio estimate; the
+/// sample sizes are the two counts.
+///
+/// Conditionally on the total `n = count1 + count2`, the first count is
+/// binomial with `n` trials and success probability
+/// `exposure1 / (exposure1 + exposure2)` under the null, so the p-value is
+/// that of the exact binomial test, as in R's `poisson.test`.
+///
+/// # Formula
+///
+/// ```text
+/// RR = (count1 / exposure1) / (count2 / exposure2)
+/// ```
+///
+/// # Remarks
+///
+/// When both counts are zero the estimate is `NaN` and the p-value is `1`;
+/// when only `count2` is zero the estimate is infinite.
+///
+/// # Errors
+///
+/// Returns an error if an exposure is not positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::rate::poisson_rate_ratio_test;
+///
+/// let result = poisson_rate_ratio_test(11, 800.0, 21, 3011.0).unwrap();
+/// assert!((result.estimate.unwrap().value - 1.9715).abs() < 1e-4);
+/// assert!((result.p_value - 0.07967).abs() < 1e-5);
+/// ```
+pub fn poisson_rate_ratio_test(
+    count1: u64,
+    exposure1: f64,
+    count2: u64,
+    exposure2: f64,
+) -> Result<TestResult> {
+    check_positive(exposure1, "exposure1")?;
+    check_positive(exposure2, "exposure2")?;
+    let ratio = (count1 as f64 / exposure1) / (count2 as f64 / exposure2);
+    let total = count1
+        .checked_add(count2)
+        .ok_or(StatsError::SpecialCase("the total count overflows"))?;
+    let p_value = if total == 0 {
+        1.0
+    } else {
+        binomial_test(count1, total, exposure1 / (exposure1 + exposure2))?.p_value
+    };
+    Ok(TestResult::new(
+        "Comparison of Poisson rates",
+        "count1",
+        count1 as f64,
+        p_value,
+        Alternative::TwoSided,
+        vec![count1 as usize, count2 as usize],
+    )
+    .with_estimate("rate ratio", ratio))
+}
+
+/// Performs the exact two-sided test of the null hypothesis that events
+/// occur at `rate`, given `count` events over `exposure`, reporting the
+/// p-value selected by `method`. Returns `count` as the statistic with the
+/// estimated rate `count / exposure`; the sample size is the count.
+///
+/// # Remarks
+///
+/// Under the null the count is Poisson with mean `exposure * rate`. The
+/// exact p-value is the total probability of the counts no more likely
+/// than the observed one, with the relative tolerance of `1e-7` of R's
+/// `poisson.test`; the mid-p value subtracts half the probability of the
+/// observed count.
+///
+/// # Errors
+///
+/// Returns an error if `exposure` or `rate` is not positive and finite, or
+/// if their product is not finite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::rate::poisson_test;
+/// use statrs::stats_tests::PValueMethod;
+///
+/// let exact = poisson_test(10, 1.0, 5.0, PValueMethod::Exact).unwrap();
+/// assert_eq!(exact.estimate.unwrap().value, 10.0);
+/// assert!((exact.p_value - 0.03857).abs() < 1e-5);
+/// let mid_p = poisson_test(10, 1.0, 5.0, PValueMethod::MidP).unwrap();
+/// assert!(mid_p.p_value < exact.p_value);
+/// ```
+pub fn poisson_test(
+    count: u64,
+    exposure: f64,
+    rate: f64,
+    method: PValueMethod,
+) -> Result<TestResult> {
+    check_positive(exposure, "exposure")?;
+    check_positive(rate, "rate")?;
+    let mean = exposure * rate;
+    if !mean.is_finite() {
+        return Err(StatsError::SpecialCase("the expected count must be finite"));
+    }
+    let dist = Poisson::new(mean)?;
+    let probability = dist.pmf(count);
+    let observed = probability * (1.0 + 1e-7);
+    let x = count as f64;
+    let p_value = if x == mean {
+        1.0
+    } else if x < mean {
+        // the first count above the mean no more likely than observed
+        let first = (mean.ceil() as u64..)
+            .find(|&k| dist.pmf(k) <= observed)
+            .unwrap();
+        dist.cdf(count) + dist.sf(first - 1)
+    } else {
+        let y = (0..=mean.floor() as u64)
+            .take_while(|&k| dist.pmf(k) <= observed)
+            .count() as u64;
+        let lower = if y == 0 { 0.0 } else { dist.cdf(y - 1) };
+        lower + dist.sf(count - 1)
+    };
+    let (method_name, p_value) = match method {
+        PValueMethod::Exact => ("Exact Poisson test", p_value),
+        PValueMethod::MidP => ("Exact Poisson test (mid-p)", p_value - 0.5 * probability),
+    };
+    Ok(TestResult::new(
+        method_name,
+        "number of events",
+        count as f64,
+        p_value.clamp(0.0, 1.0),
+        Alternative::TwoSided,
+        vec![count as usize],
+    )
+    .with_estimate("event rate", count as f64 / exposure))
+}
+
+/// Checks that the argument `name` is positive and finite
+fn check_positive(value: f64, name: &'static str) -> Result<()> {
+    if !value.is_finite() {
+        Err(StatsError::ArgFinite(name))
+    } else if value <= 0.0 {
+        Err(StatsError::ArgMustBePositive(name))
+    } else {
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio_and_p(result: Result<TestResult>) -> (f64, f64) {
+        let result = result.unwrap();
+        (result.estimate.unwrap().value, result.p_value)
+    }
+
+    #[test]
+    fn test_published() {
+        // example of R's poisson.test, which reports a p-value of 0.07967
+        // and an expected count1 of 6.7174; refined with mpmath
+        let (ratio, p) = ratio_and_p(poisson_rate_ratio_test(11, 800.0, 6 + 8 + 7, 1083.0 + 1050.0 + 878.0));
+        assert_almost_eq!(ratio, 1.9714880952380952, 1e-14);
+        assert_almost_eq!(p, 0.079668633033329474, 1e-12);
+    }
+
+    #[test]
+    fn test_rate_ratio() {
+        // reference values computed with mpmath
+        let (ratio, p) = ratio_and_p(poisson_rate_ratio_test(2, 17877.0, 7, 16093.0));
+        assert_almost_eq!(ratio, 0.25720199138557924, 1e-14);
+        assert_almost_eq!(p, 0.094790873115294520, 1e-12);
+        let (ratio, p) = ratio_and_p(poisson_rate_ratio_test(10, 1.0, 2, 1.0));
+        assert_eq!(ratio, 5.0);
+        assert_almost_eq!(p, 0.03857421875, 1e-14);
+        // symmetric in the groups
+        let (ratio, q) = ratio_and_p(poisson_rate_ratio_test(2, 1.0, 10, 1.0));
+        assert_eq!(ratio, 0.2);
+        assert_almost_eq!(p, q, 1e-15);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let (ratio, p) = ratio_and_p(poisson_rate_ratio_test(0, 1.0, 0, 2.0));
+        assert!(ratio.is_nan());
+        assert_eq!(p, 1.0);
+        let (ratio, p) = ratio_and_p(poisson_rate_ratio_test(4, 1.0, 0, 1.0));
+        assert_eq!(ratio, f64::INFINITY);
+        assert_almost_eq!(p, 0.125, 1e-15);
+        assert_eq!(poisson_rate_ratio_test(1, 0.0, 1, 1.0), Err(StatsError::ArgMustBePositive("exposure1")));
+        assert_eq!(poisson_rate_ratio_test(1, 1.0, 1, f64::NAN), Err(StatsError::ArgFinite("exposure2")));
+        assert_eq!(poisson_rate_ratio_test(1, f64::INFINITY, 1, 1.0), Err(StatsError::ArgFinite("exposure1")));
+        assert!(poisson_rate_ratio_test(u64::MAX, 1.0, 1, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_poisson_test() {
+        // reference values computed with mpmath using the rule of
+        // poisson.test
+        let p = |count, exposure, rate, method| poisson_test(count, exposure, rate, method).unwrap().p_value;
+        assert_almost_eq!(p(10, 1.0, 5.0, PValueMethod::Exact), 0.038566004305290279, 1e-14);
+        assert_almost_eq!(p(10, 1.0, 5.0, PValueMethod::MidP), 0.029499609951379342, 1e-14);
+        assert_almost_eq!(p(2, 2.0, 3.25, PValueMethod::Exact), 0.076915505961291193, 1e-14);
+        assert_almost_eq!(p(2, 2.0, 3.25, PValueMethod::MidP), 0.061035429485465584, 1e-14);
+        // the example of R's poisson.test, 137 cases against 24.19893 expected
+        let result = poisson_test(137, 24.19893, 1.0, PValueMethod::Exact).unwrap();
+        assert_almost_eq!(result.p_value, 2.8452272641144834e-56, 1e-67);
+        assert_almost_eq!(result.estimate.unwrap().value, 137.0 / 24.19893, 1e-14);
+        assert_eq!(p(0, 1.0, 1e-12, PValueMethod::Exact), 1.0);
+        assert_eq!(p(4, 2.0, 2.0, PValueMethod::Exact), 1.0);
+        assert_eq!(poisson_test(4, 2.0, 2.0, PValueMethod::MidP).unwrap().method, "Exact Poisson test (mid-p)");
+    }
+
+    #[test]
+    fn test_poisson_test_bad_input() {
+        assert_eq!(poisson_test(1, 0.0, 1.0, PValueMethod::Exact), Err(StatsError::ArgMustBePositive("exposure")));
+        assert_eq!(poisson_test(1, 1.0, f64::NAN, PValueMethod::Exact), Err(StatsError::ArgFinite("rate")));
+        assert_eq!(poisson_test(1, 1.0, f64::INFINITY, PValueMethod::MidP), Err(StatsError::ArgFinite("rate")));
+        assert!(poisson_test(1, 1e200, 1e200, PValueMethod::Exact).is_err());
+    }
+}
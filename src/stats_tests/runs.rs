@@ -0,0 +1,158 @@
+//! Provides the Wald-Wolfowitz runs test of the randomness of the order of
+//! a sequence
+
+use crate::distribution::{DiscreteCDF, Runs};
+use crate::statistics::{Data, Distribution, OrderStatistics};
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Tests the null hypothesis that the order of the binary sequence `data`
+/// is random, given the numbers of `true` and `false` values, against the
+/// `alternative`, with the exact null distribution of the number of runs
+///
+/// # Remarks
+///
+/// The statistic is the number of runs `R`, the maximal blocks of equal
+/// values, whose null distribution is `Runs`. `Alternative::Less` is the
+/// alternative of fewer runs than expected, as from clustering or a trend,
+/// and `Alternative::Greater` that of more runs, as from alternation. The
+/// two-sided p-value doubles the smaller one-sided one, capped at `1`.
+///
+/// # Errors
+///
+/// Returns an error unless both `true` and `false` occur in `data`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::runs::runs_test_binary;
+/// use statrs::stats_tests::Alternative;
+///
+/// // two runs out of the 70 arrangements of four of each, two of which
+/// // have two runs
+/// let data = [true, true, true, true, false, false, false, false];
+/// let result = runs_test_binary(&data, Alternative::Less).unwrap();
+/// assert_eq!(result.statistic, 2.0);
+/// assert!((result.p_value - 2.0 / 70.0).abs() < 1e-15);
+/// ```
+pub fn runs_test_binary(data: &[bool], alternative: Alternative) -> Result<TestResult> {
+    let m = data.iter().filter(|&&x| x).count();
+    let n = data.len() - m;
+    if m == 0 || n == 0 {
+        return Err(StatsError::SpecialCase(
+            "both values must occur in the sequence",
+        ));
+    }
+    let runs = 1 + data.windows(2).filter(|w| w[0] != w[1]).count() as u64;
+    let dist = Runs::new(m as u64, n as u64)?;
+    let less = dist.cdf(runs);
+    let greater = dist.sf(runs - 1);
+    let p_value = match alternative {
+        Alternative::Less => less,
+        Alternative::Greater => greater,
+        Alternative::TwoSided => (2.0 * less.min(greater)).min(1.0),
+    };
+    Ok(TestResult::new(
+        "Wald-Wolfowitz runs test",
+        "number of runs",
+        runs as f64,
+        p_value,
+        alternative,
+        vec![m, n],
+    )
+    .with_estimate("expected number of runs", dist.mean().unwrap()))
+}
+
+/// Tests the null hypothesis that the order of `data` is random against
+/// the `alternative`, with the runs test of `runs_test_binary` on the
+/// sequence of observations above and below the median
+///
+/// # Remarks
+///
+/// The observations equal to the median are dropped, as in the runs test
+/// of R's `randtests` package. Too few runs suggest a trend or clustering
+/// of high and low values, too many an oscillation.
+///
+/// # Errors
+///
+/// Returns an error if `data` contains a value that is not finite, or
+/// unless there are observations both above and below the median
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::runs::runs_test;
+/// use statrs::stats_tests::Alternative;
+///
+/// // an increasing series has two runs about its median
+/// let data: Vec<f64> = (0..20).map(|i| i as f64).collect();
+/// let result = runs_test(&data, Alternative::TwoSided).unwrap();
+/// assert_eq!(result.statistic, 2.0);
+/// assert!(result.p_value < 1e-4);
+/// ```
+pub fn runs_test(data: &[f64], alternative: Alternative) -> Result<TestResult> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("data must be finite"));
+    }
+    let median = Data::new(data.to_vec()).median();
+    let above: Vec<bool> = data
+        .iter()
+        .filter(|&&x| x != median)
+        .map(|&x| x > median)
+        .collect();
+    runs_test_binary(&above, alternative)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Discrete;
+
+    #[test]
+    fn test_binary_exact() {
+        // m = 4, n = 4: P(R <= 2) = 2 / 70 and P(R >= 8) = 2 / 70
+        let alternating = [true, false, true, false, true, false, true, false];
+        let result = runs_test_binary(&alternating, Alternative::Greater).unwrap();
+        assert_eq!(result.statistic, 8.0);
+        assert_almost_eq!(result.p_value, 2.0 / 70.0, 1e-15);
+        let result = runs_test_binary(&alternating, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(result.p_value, 4.0 / 70.0, 1e-15);
+        let result = runs_test_binary(&alternating, Alternative::Less).unwrap();
+        assert_almost_eq!(result.p_value, 1.0, 1e-14);
+        assert_eq!(result.sample_sizes, vec![4, 4]);
+        assert_eq!(result.method, "Wald-Wolfowitz runs test");
+    }
+
+    #[test]
+    fn test_matches_distribution() {
+        let data = [true, true, false, true, false, false, false, true, true, true, false, true];
+        let dist = Runs::new(7, 5).unwrap();
+        let result = runs_test_binary(&data, Alternative::Less).unwrap();
+        assert_eq!(result.statistic, 7.0);
+        let expected: f64 = (0..=7).map(|r| dist.pmf(r)).sum();
+        assert_almost_eq!(result.p_value, expected, 1e-14);
+        let two_sided = runs_test_binary(&data, Alternative::TwoSided).unwrap();
+        assert!(two_sided.p_value > 0.5 && two_sided.p_value <= 1.0);
+    }
+
+    #[test]
+    fn test_median_dichotomy() {
+        // the median 3 is dropped, leaving low, high, low, high, low, high
+        let data = [1.0, 5.0, 3.0, 2.0, 4.0, 0.0, 6.0];
+        let result = runs_test(&data, Alternative::Greater).unwrap();
+        assert_eq!(result.statistic, 6.0);
+        assert_eq!(result.sample_sizes, vec![3, 3]);
+        // 2 of the 20 arrangements of three of each alternate
+        assert_almost_eq!(result.p_value, 2.0 / 20.0, 1e-15);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(runs_test_binary(&[], Alternative::TwoSided).is_err());
+        assert!(runs_test_binary(&[true, true], Alternative::TwoSided).is_err());
+        assert!(runs_test(&[2.0, 2.0, 2.0], Alternative::TwoSided).is_err());
+        assert!(runs_test(&[1.0, f64::NAN, 2.0], Alternative::TwoSided).is_err());
+    }
+}
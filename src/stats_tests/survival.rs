@@ -0,0 +1,255 @@
+//! Provides tests comparing the survival of several groups from
+//! right-censored lifetimes, as estimated by
+//! [`KaplanMeier`](crate::statistics::survival::KaplanMeier)
+
+use crate::distribution::{ChiSquared, ContinuousCDF};
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use nalgebra::{DMatrix, DVector};
+use std::f64;
+
+/// The weighted numbers of events of a log-rank test observed in every
+/// group and those expected if all groups had the same survival
+#[derive(Clone, PartialEq, Debug)]
+pub struct LogRankEvents {
+    /// The weighted number of events observed in every group, in the order
+    /// of the groups
+    pub observed: Vec<f64>,
+    /// The weighted number of events expected in every group under the
+    /// null hypothesis
+    pub expected: Vec<f64>,
+}
+
+/// Performs the [log-rank
+/// test](https://en.wikipedia.org/wiki/Logrank_test) of the null hypothesis
+/// that all `groups` have the same survival function, or a test of the
+/// Fleming–Harrington `G-rho` family, as R's `survival::survdiff`. Every
+/// group is a pair of the observed times and the event flags, `true` for an
+/// observed event and `false` for a censoring.
+///
+/// At every event time the events are weighted by `S(t-)^rho`, where `S` is
+/// the Kaplan–Meier estimate of the pooled sample just before the time:
+/// `rho = 0` gives the log-rank test, with equal weights, and `rho = 1` the
+/// Peto–Peto modification of the Gehan–Wilcoxon test, which emphasizes
+/// early differences.
+///
+/// # Formula
+///
+/// ```text
+/// O_j = Σ w_i d_ij
+/// E_j = Σ w_i d_i n_ij / n_i
+/// V_jl = Σ w_i^2 d_i (n_i - d_i) / (n_i - 1) (n_ij / n_i) (δ_jl - n_il / n_i)
+/// χ^2 = (O - E)' V^-1 (O - E)
+/// ```
+///
+/// over the event times `t_i`, where `d_ij` and `n_ij` are the number of
+/// events and the number at risk in group `j`, `d_i` and `n_i` their totals
+/// and `w_i` the weight. The vectors and matrix leave out the last group.
+/// The p-value is `P(X > χ^2)` for `X` following a chi-squared distribution
+/// with `k - 1` degrees of freedom, where `k` is the number of groups.
+/// Returns the test together with the weighted numbers of observed and
+/// expected events.
+///
+/// # Remarks
+///
+/// Tied event times are pooled across the groups, with the hypergeometric
+/// variance term `d_i (n_i - d_i) / (n_i - 1)`. Censorings tied with events
+/// are at risk at that time.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, if the times and
+/// flags of a group differ in length, if a group is empty, if a time is not
+/// finite, if `rho` is negative or not finite, or if the variance matrix is
+/// singular, as when there are no events
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::logrank_test;
+///
+/// let maintained = [9.0, 13.0, 13.0, 18.0, 23.0, 28.0, 31.0, 34.0, 45.0, 48.0, 161.0];
+/// let m_events = [true, true, false, true, true, false, true, true, false, true, false];
+/// let nonmaintained = [5.0, 5.0, 8.0, 8.0, 12.0, 16.0, 23.0, 27.0, 30.0, 33.0, 43.0, 45.0];
+/// let mut n_events = [true; 12];
+/// n_events[5] = false;
+/// let groups = [(&maintained[..], &m_events[..]), (&nonmaintained[..], &n_events[..])];
+/// let (result, events) = logrank_test(&groups, 0.0).unwrap();
+/// assert!((result.statistic - 3.3963886989776019).abs() < 1e-12);
+/// assert_eq!(events.observed, vec![7.0, 11.0]);
+/// ```
+pub fn logrank_test(groups: &[(&[f64], &[bool])], rho: f64) -> Result<(TestResult, LogRankEvents)> {
+    if groups.len() < 2 {
+        return Err(StatsError::SpecialCase("at least two groups are required"));
+    }
+    if !rho.is_finite() || rho < 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    for &(times, events) in groups {
+        if times.len() != events.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if times.is_empty() {
+            return Err(StatsError::SpecialCase("groups must not be empty"));
+        }
+        if times.iter().any(|t| !t.is_finite()) {
+            return Err(StatsError::SpecialCase("times must be finite"));
+        }
+    }
+    let k = groups.len();
+    let mut event_times: Vec<f64> = groups
+        .iter()
+        .flat_map(|&(times, events)| {
+            times
+                .iter()
+                .zip(events)
+                .filter(|(_, &e)| e)
+                .map(|(&t, _)| t)
+        })
+        .collect();
+    event_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    event_times.dedup();
+
+    let mut observed = vec![0.0; k];
+    let mut expected = vec![0.0; k];
+    let mut variance = DMatrix::<f64>::zeros(k - 1, k - 1);
+    let mut survival: f64 = 1.0;
+    for &t in &event_times {
+        let (at_risk, deaths): (Vec<f64>, Vec<f64>) = groups
+            .iter()
+            .map(|&(times, events)| {
+                let n = times.iter().filter(|&&x| x >= t).count();
+                let d = times
+                    .iter()
+                    .zip(events)
+                    .filter(|(&x, &e)| e && x == t)
+                    .count();
+                (n as f64, d as f64)
+            })
+            .unzip();
+        let n: f64 = at_risk.iter().sum();
+        let d: f64 = deaths.iter().sum();
+        let weight = survival.powf(rho);
+        for j in 0..k {
+            observed[j] += weight * deaths[j];
+            expected[j] += weight * d * at_risk[j] / n;
+        }
+        if n > 1.0 {
+            let scale = weight * weight * d * (n - d) / (n - 1.0);
+            for j in 0..k - 1 {
+                for l in 0..k - 1 {
+                    let delta = if j == l { 1.0 } else { 0.0 };
+                    variance[(j, l)] += scale * at_risk[j] / n * (delta - at_risk[l] / n);
+                }
+            }
+        }
+        survival *= 1.0 - d / n;
+    }
+
+    let difference = DVector::from_fn(k - 1, |j, _| observed[j] - expected[j]);
+    let cholesky = variance
+        .cholesky()
+        .ok_or(StatsError::SpecialCase("the variance matrix is singular"))?;
+    let chi_squared = difference.dot(&cholesky.solve(&difference));
+    let freedom = (k - 1) as f64;
+    let p_value = ChiSquared::new(freedom).unwrap().sf(chi_squared);
+    let method = if rho == 0.0 {
+        "Log-rank test"
+    } else {
+        "Fleming-Harrington G-rho test"
+    };
+    let test = TestResult::new(
+        method,
+        "Chisq",
+        chi_squared,
+        p_value,
+        Alternative::TwoSided,
+        groups.iter().map(|(times, _)| times.len()).collect(),
+    )
+    .with_df(freedom);
+    Ok((test, LogRankEvents { observed, expected }))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the acute myelogenous leukemia data `aml` of R's survival package
+    const MAINTAINED: [f64; 11] = [9.0, 13.0, 13.0, 18.0, 23.0, 28.0, 31.0, 34.0, 45.0, 48.0, 161.0];
+    const MAINTAINED_EVENTS: [bool; 11] = [true, true, false, true, true, false, true, true, false, true, false];
+    const NONMAINTAINED: [f64; 12] = [5.0, 5.0, 8.0, 8.0, 12.0, 16.0, 23.0, 27.0, 30.0, 33.0, 43.0, 45.0];
+    const NONMAINTAINED_EVENTS: [bool; 12] = [true, true, true, true, true, false, true, true, true, true, true, true];
+    const THIRD: [f64; 8] = [1.0, 3.0, 3.0, 4.0, 7.0, 8.0, 9.0, 12.0];
+    const THIRD_EVENTS: [bool; 8] = [true, true, true, false, true, true, false, true];
+
+    fn two_groups() -> [(&'static [f64], &'static [bool]); 2] {
+        [(&MAINTAINED, &MAINTAINED_EVENTS), (&NONMAINTAINED, &NONMAINTAINED_EVENTS)]
+    }
+
+    fn three_groups() -> [(&'static [f64], &'static [bool]); 3] {
+        [(&MAINTAINED, &MAINTAINED_EVENTS), (&NONMAINTAINED, &NONMAINTAINED_EVENTS), (&THIRD, &THIRD_EVENTS)]
+    }
+
+    fn check((test, events): &(TestResult, LogRankEvents), statistic: f64, p_value: f64, observed: &[f64], expected: &[f64]) {
+        assert_almost_eq!(test.statistic, statistic, 1e-12);
+        assert_almost_eq!(test.p_value, p_value, 1e-12);
+        for (a, b) in events.observed.iter().zip(observed) {
+            assert_almost_eq!(*a, *b, 1e-13);
+        }
+        for (a, b) in events.expected.iter().zip(expected) {
+            assert_almost_eq!(*a, *b, 1e-13);
+        }
+        // the weighted events add up across the groups
+        let total: f64 = events.observed.iter().sum();
+        assert_almost_eq!(events.expected.iter().sum::<f64>(), total, 1e-12);
+    }
+
+    #[test]
+    fn test_logrank_aml() {
+        // reference values of survdiff(Surv(time, status) ~ x, aml, rho)
+        let report = logrank_test(&two_groups(), 0.0).unwrap();
+        check(&report, 3.3963886989776019, 0.065339322040504936, &[7.0, 11.0], &[10.689335992300725, 7.3106640076992749]);
+        assert_eq!(report.0.df, Some(1.0));
+        assert_eq!(report.0.sample_sizes, vec![11, 12]);
+        let report = logrank_test(&two_groups(), 1.0).unwrap();
+        check(&report, 2.7792795447517725, 0.095491115406492656, &[3.8454106280193237, 7.1815044858523119], &[6.1428571428571429, 4.8840579710144928]);
+        let report = logrank_test(&two_groups(), 0.5).unwrap();
+        check(&report, 3.0186432878698326, 0.082312300579023591, &[5.0535219122710186, 8.6606715085145665], &[7.8469663647850297, 5.8672270560005554]);
+    }
+
+    #[test]
+    fn test_logrank_three_groups() {
+        let report = logrank_test(&three_groups(), 0.0).unwrap();
+        check(&report, 15.665673500904991, 0.00039649911439321029, &[7.0, 11.0, 6.0], &[12.845120242157094, 9.3983510256180784, 1.7565287322248273]);
+        assert_eq!(report.0.df, Some(2.0));
+        let report = logrank_test(&three_groups(), 1.0).unwrap();
+        check(&report, 14.50994163315458, 0.00070665300155371097, &[3.1478896364766955, 6.1649863408022246, 5.2437275985663082], &[7.0309375589511413, 5.9761470582070469, 1.5495189586870402]);
+        // the statistic does not depend on which group is left out
+        let groups = three_groups();
+        let reordered = [groups[2], groups[0], groups[1]];
+        assert_almost_eq!(logrank_test(&reordered, 0.0).unwrap().0.statistic, 15.665673500904991, 1e-11);
+    }
+
+    #[test]
+    fn test_logrank_identical_groups() {
+        let groups = [(&MAINTAINED[..], &MAINTAINED_EVENTS[..]), (&MAINTAINED[..], &MAINTAINED_EVENTS[..])];
+        let (test, events) = logrank_test(&groups, 0.0).unwrap();
+        assert_almost_eq!(test.statistic, 0.0, 1e-15);
+        assert_almost_eq!(test.p_value, 1.0, 1e-12);
+        assert_eq!(events.observed, events.expected);
+    }
+
+    #[test]
+    fn test_logrank_bad_input() {
+        let groups = two_groups();
+        assert!(logrank_test(&groups[..1], 0.0).is_err());
+        assert!(logrank_test(&groups, -1.0).is_err());
+        assert!(logrank_test(&groups, f64::NAN).is_err());
+        assert!(logrank_test(&[groups[0], (&[1.0, 2.0], &[true])], 0.0).is_err());
+        assert!(logrank_test(&[groups[0], (&[], &[])], 0.0).is_err());
+        assert!(logrank_test(&[groups[0], (&[1.0, f64::NAN], &[true, true])], 0.0).is_err());
+        // no events at all
+        assert!(logrank_test(&[(&[1.0, 2.0], &[false, false]), (&[3.0], &[false])], 0.0).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+//! Provides the result type shared by the hypothesis tests of this crate,
+//! which prints like the `htest` objects of R
+
+use crate::stats_tests::effect::EffectEstimate;
+use std::f64;
+use std::fmt;
+
+/// The alternative hypothesis of a test
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Alternative {
+    /// The parameter differs from its null value in either direction, or,
+    /// for omnibus tests such as chi-squared tests, the data depart from
+    /// the null hypothesis in any way
+    TwoSided,
+    /// The parameter is less than its null value
+    Less,
+    /// The parameter is greater than its null value
+    Greater,
+}
+
+impl fmt::Display for Alternative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alternative::TwoSided => write!(f, "two.sided"),
+            Alternative::Less => write!(f, "less"),
+            Alternative::Greater => write!(f, "greater"),
+        }
+    }
+}
+
+/// Selects the p-value reported by an exact test of discrete data
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PValueMethod {
+    /// The probability under the null hypothesis of the outcomes at most as
+    /// likely as the observed one, which never exceeds the nominal level but
+    /// is conservative as the outcomes are discrete
+    Exact,
+    /// Lancaster's mid-p value, the exact p-value less half the probability
+    /// of the observed outcome, whose size is closer to the nominal level on
+    /// average at the cost of occasionally exceeding it
+    MidP,
+}
+
+/// The measure reported by an `EffectSize`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EffectSizeKind {
+    /// The standardized mean difference of Cohen
+    CohensD,
+    /// Cohen's d with the small-sample correction of Hedges
+    HedgesG,
+    /// A product-moment correlation coefficient
+    Correlation,
+    /// Kendall's rank correlation coefficient
+    KendallTau,
+    /// The ratio of the odds of an event in two groups
+    OddsRatio,
+    /// The ratio of the risks of an event in two groups
+    RiskRatio,
+}
+
+impl fmt::Display for EffectSizeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EffectSizeKind::CohensD => "Cohen's d",
+            EffectSizeKind::HedgesG => "Hedges' g",
+            EffectSizeKind::Correlation => "correlation",
+            EffectSizeKind::KendallTau => "Kendall's tau",
+            EffectSizeKind::OddsRatio => "odds ratio",
+            EffectSizeKind::RiskRatio => "risk ratio",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A standardized measure of the size of an effect
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EffectSize {
+    /// The measure
+    pub kind: EffectSizeKind,
+    /// Its value
+    pub value: f64,
+}
+
+/// A named point estimate of the parameter under test
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Estimate {
+    /// What is estimated, such as `"difference in means"`
+    pub name: &'static str,
+    /// The estimate
+    pub value: f64,
+}
+
+/// A confidence interval with its confidence level
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ConfidenceInterval {
+    /// The lower bound
+    pub lower: f64,
+    /// The upper bound
+    pub upper: f64,
+    /// The confidence level, such as `0.95`
+    pub level: f64,
+}
+
+/// The result of a hypothesis test
+///
+/// The method, statistic, p-value, alternative and sample sizes are always
+/// set; the other fields are `None` for the tests that do not define them.
+/// Tests that cannot be computed on their input report a `NaN` statistic
+/// and p-value.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{binomial_test, Alternative};
+///
+/// let result = binomial_test(682, 925, 0.75).unwrap();
+/// assert_eq!(result.alternative, Alternative::TwoSided);
+/// assert!(!result.rejects(0.05));
+/// println!("{}", result);
+///
+/// let (statistic, p_value) = result.into();
+/// assert_eq!(statistic, 682.0);
+/// assert!((p_value - 0.3825).abs() < 1e-4);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct TestResult {
+    /// The name of the test
+    pub method: &'static str,
+    /// The name of the test statistic, such as `"t"`
+    pub statistic_name: &'static str,
+    /// The value of the test statistic
+    pub statistic: f64,
+    /// The p-value
+    pub p_value: f64,
+    /// The degrees of freedom of the reference distribution of the
+    /// statistic
+    pub df: Option<f64>,
+    /// The point estimate of the parameter under test
+    pub estimate: Option<Estimate>,
+    /// The confidence interval of the parameter under test
+    pub conf_int: Option<ConfidenceInterval>,
+    /// The size of the effect
+    pub effect_size: Option<EffectSize>,
+    /// The alternative hypothesis
+    pub alternative: Alternative,
+    /// The number of observations in every sample, in the order of the
+    /// arguments of the test
+    pub sample_sizes: Vec<usize>,
+}
+
+impl TestResult {
+    /// Creates a result with the mandatory fields and no optional ones
+    pub(crate) fn new(
+        method: &'static str,
+        statistic_name: &'static str,
+        statistic: f64,
+        p_value: f64,
+        alternative: Alternative,
+        sample_sizes: Vec<usize>,
+    ) -> TestResult {
+        TestResult {
+            method,
+            statistic_name,
+            statistic,
+            p_value,
+            df: None,
+            estimate: None,
+            conf_int: None,
+            effect_size: None,
+            alternative,
+            sample_sizes,
+        }
+    }
+
+    /// Sets the degrees of freedom
+    pub(crate) fn with_df(mut self, df: f64) -> TestResult {
+        self.df = Some(df);
+        self
+    }
+
+    /// Sets the point estimate
+    pub(crate) fn with_estimate(mut self, name: &'static str, value: f64) -> TestResult {
+        self.estimate = Some(Estimate { name, value });
+        self
+    }
+
+    /// Sets the confidence interval
+    pub(crate) fn with_conf_int(mut self, lower: f64, upper: f64, level: f64) -> TestResult {
+        self.conf_int = Some(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        });
+        self
+    }
+
+    /// Sets the effect size
+    pub(crate) fn with_effect_size(mut self, kind: EffectSizeKind, value: f64) -> TestResult {
+        self.effect_size = Some(EffectSize { kind, value });
+        self
+    }
+
+    /// Returns whether the null hypothesis is rejected at the significance
+    /// level `alpha`, that is whether the p-value is below `alpha`
+    pub fn rejects(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+
+    /// Returns the point estimate with its confidence interval, or `None`
+    /// if the test does not report both
+    pub fn effect_estimate(&self) -> Option<EffectEstimate> {
+        match (self.estimate, self.conf_int) {
+            (Some(estimate), Some(conf_int)) => Some(EffectEstimate {
+                estimate: estimate.value,
+                lower: conf_int.lower,
+                upper: conf_int.upper,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<TestResult> for (f64, f64) {
+    /// Returns the statistic and the p-value
+    fn from(result: TestResult) -> (f64, f64) {
+        (result.statistic, result.p_value)
+    }
+}
+
+/// Formats `x` with four significant digits in fixed or scientific
+/// notation, whichever is shorter, as R's `format(x, digits = 4)`
+fn format_significant(x: f64) -> String {
+    if x == 0.0 || !x.is_finite() {
+        return format!("{}", x);
+    }
+    let magnitude = x.abs().log10().floor() as i32;
+    let decimals = (3 - magnitude).max(0) as usize;
+    let mut fixed = format!("{:.*}", decimals, x);
+    if fixed.contains('.') {
+        fixed = fixed
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string();
+    }
+    let scientific = format!("{:.3e}", x);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let mantissa = if mantissa.contains('.') {
+        mantissa.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        mantissa
+    };
+    let exponent: i32 = exponent.parse().unwrap();
+    let sign = if exponent < 0 { '-' } else { '+' };
+    let scientific = format!("{}e{}{:02}", mantissa, sign, exponent.abs());
+    if fixed.len() <= scientific.len() {
+        fixed
+    } else {
+        scientific
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "\t{}", self.method)?;
+        writeln!(f)?;
+        let sizes: Vec<String> = self.sample_sizes.iter().map(|n| n.to_string()).collect();
+        writeln!(f, "data:  n = {}", sizes.join(", "))?;
+        write!(
+            f,
+            "{} = {}, ",
+            self.statistic_name,
+            format_significant(self.statistic)
+        )?;
+        if let Some(df) = self.df {
+            write!(f, "df = {}, ", format_significant(df))?;
+        }
+        if self.p_value < f64::EPSILON {
+            writeln!(f, "p-value < {}", format_significant(f64::EPSILON))?;
+        } else {
+            writeln!(f, "p-value = {}", format_significant(self.p_value))?;
+        }
+        writeln!(f, "alternative hypothesis: {}", self.alternative)?;
+        if let Some(conf_int) = self.conf_int {
+            writeln!(
+                f,
+                "{} percent confidence interval:",
+                format_significant(100.0 * conf_int.level)
+            )?;
+            writeln!(
+                f,
+                " {} {}",
+                format_significant(conf_int.lower),
+                format_significant(conf_int.upper)
+            )?;
+        }
+        if let Some(estimate) = self.estimate {
+            writeln!(f, "sample estimates:")?;
+            writeln!(
+                f,
+                "{}: {}",
+                estimate.name,
+                format_significant(estimate.value)
+            )?;
+        }
+        if let Some(effect_size) = self.effect_size {
+            writeln!(f, "effect size:")?;
+            writeln!(
+                f,
+                "{}: {}",
+                effect_size.kind,
+                format_significant(effect_size.value)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::dependence::hoeffding_d;
+    use crate::statistics::time_series::{adf_test, ljung_box};
+    use crate::stats_tests::benford::first_digit_test;
+    use crate::stats_tests::circular::rayleigh_test;
+    use crate::stats_tests::dip::dip_test;
+    use crate::stats_tests::dispersion::dispersion_score_test;
+    use crate::stats_tests::energy::energy_test;
+    use crate::stats_tests::rate::{poisson_rate_ratio_test, poisson_test};
+    use crate::stats_tests::ttest::yuen_t_test;
+    use crate::stats_tests::{binomial_test, dispersion_test, lilliefors_normal, logrank_test, mann_kendall, seasonal_mann_kendall};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_format_significant() {
+        assert_eq!(format_significant(0.38249155957485168), "0.3825");
+        assert_eq!(format_significant(-4.8406314548425697), "-4.841");
+        assert_eq!(format_significant(2.5), "2.5");
+        assert_eq!(format_significant(95.0), "95");
+        assert_eq!(format_significant(12345.6), "12346");
+        assert_eq!(format_significant(0.00012345), "0.0001234");
+        assert_eq!(format_significant(1.2e-5), "1.2e-05");
+        assert_eq!(format_significant(f64::EPSILON), "2.22e-16");
+        assert_eq!(format_significant(6.02e23), "6.02e+23");
+        assert_eq!(format_significant(0.0), "0");
+        assert_eq!(format_significant(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn test_display() {
+        let result = TestResult::new("Welch Two Sample t-test", "t", -1.6275, 0.1233, Alternative::TwoSided, vec![10, 12])
+            .with_df(17.776)
+            .with_conf_int(-2.48, 0.32, 0.95)
+            .with_estimate("difference in means", -1.08)
+            .with_effect_size(EffectSizeKind::HedgesG, -0.68);
+        let expected = "\n\tWelch Two Sample t-test\n\n\
+                        data:  n = 10, 12\n\
+                        t = -1.627, df = 17.78, p-value = 0.1233\n\
+                        alternative hypothesis: two.sided\n\
+                        95 percent confidence interval:\n\
+                        \x20-2.48 0.32\n\
+                        sample estimates:\n\
+                        difference in means: -1.08\n\
+                        effect size:\n\
+                        Hedges' g: -0.68\n";
+        assert_eq!(result.to_string(), expected);
+
+        let result = TestResult::new("Exact binomial test", "number of successes", 90.0, 1e-20, Alternative::Greater, vec![100]);
+        let expected = "\n\tExact binomial test\n\n\
+                        data:  n = 100\n\
+                        number of successes = 90, p-value < 2.22e-16\n\
+                        alternative hypothesis: greater\n";
+        assert_eq!(result.to_string(), expected);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let result = TestResult::new("test", "z", 2.0, 0.04, Alternative::Less, vec![5])
+            .with_estimate("mean", 1.5);
+        assert!(result.rejects(0.05));
+        assert!(!result.rejects(0.01));
+        assert_eq!(result.effect_estimate(), None);
+        let result = result.with_conf_int(1.0, 2.0, 0.9);
+        assert_eq!(result.effect_estimate(), Some(EffectEstimate { estimate: 1.5, lower: 1.0, upper: 2.0 }));
+        let TestResult { statistic, p_value, .. } = result.clone();
+        assert_eq!((statistic, p_value), result.into());
+    }
+
+    #[test]
+    fn test_mandatory_fields() {
+        let xs = [1.2, 3.4, 2.2, 5.1, 4.4, 3.3, 2.8, 6.1, 3.9, 4.1, 5.0, 4.7];
+        let ys = [5.5, 6.1, 4.9, 7.2, 6.6, 5.8, 6.0, 5.2, 4.0, 6.4, 7.0, 6.3];
+        let counts: Vec<u64> = xs.iter().map(|&x| x as u64).collect();
+        let events = [true; 12];
+        let mut rng = StdRng::seed_from_u64(2782);
+        let results = [
+            binomial_test(7, 20, 0.5).unwrap(),
+            poisson_rate_ratio_test(11, 800.0, 21, 3011.0).unwrap(),
+            poisson_test(10, 1.0, 5.0, PValueMethod::Exact).unwrap(),
+            yuen_t_test(&xs, &ys, 0.2).unwrap(),
+            energy_test(&xs, &ys, 99, &mut rng).unwrap(),
+            lilliefors_normal(&xs, 99, &mut rng).unwrap().0,
+            rayleigh_test(&xs).unwrap(),
+            dispersion_test(&counts).unwrap(),
+            dispersion_score_test(&counts).unwrap(),
+            logrank_test(&[(&xs[..], &events[..]), (&ys[..], &events[..])], 0.0).unwrap().0,
+            mann_kendall(&xs).unwrap(),
+            seasonal_mann_kendall(&xs, 3).unwrap(),
+            hoeffding_d(&xs, &ys).unwrap(),
+            first_digit_test(&xs).unwrap().0,
+            dip_test(&xs).unwrap(),
+            ljung_box(&xs, 3).unwrap(),
+            adf_test(&xs, 1).unwrap(),
+        ];
+        for result in &results {
+            assert!(!result.method.is_empty());
+            assert!(!result.statistic_name.is_empty());
+            assert!(result.statistic.is_finite(), "{}", result);
+            assert!((0.0..=1.0).contains(&result.p_value), "{}", result);
+            assert!(!result.sample_sizes.is_empty(), "{}", result);
+            assert!(result.sample_sizes.iter().all(|&n| n > 0), "{}", result);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Provides [tolerance intervals](https://en.wikipedia.org/wiki/Tolerance_interval),
+//! i.e. intervals that contain at least a proportion `p` of the population
+//! with confidence `γ`
+
+use crate::distribution::{
+    Beta, ChiSquared, Continuous, ContinuousCDF, NoncentralStudentsT, Normal,
+};
+use crate::function::integrate::integrate;
+use crate::function::root;
+use crate::statistics::Statistics;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Maximum number of steps taken to bracket the two-sided factor
+const MAX_EXPANSIONS: usize = 100;
+
+/// Maximum number of iterations of Brent's method refining the two-sided
+/// factor or the coverage radius
+const MAX_ITERATIONS: usize = 200;
+
+/// Specifies whether a tolerance interval bounds the population on both
+/// sides or on a single side
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Sided {
+    /// The interval `[x̄ - k * s, x̄ + k * s]` jointly covers a proportion
+    /// `p` of the population
+    Two,
+    /// Each of the limits `x̄ - k * s` and `x̄ + k * s` individually bounds
+    /// a proportion `p` of the population from below and above respectively
+    One,
+}
+
+/// Computes the exact tolerance factor `k` for a sample of size `n` from a
+/// normal population such that `x̄ ± k * s` is a tolerance interval with
+/// coverage `coverage_p` and confidence `confidence_gamma`
+///
+/// # Remarks
+///
+/// The one-sided factor is `t'(γ; n - 1, z_p * sqrt(n)) / sqrt(n)` where
+/// `t'` is the quantile of the noncentral t distribution. The two-sided
+/// factor is the exact solution of the Weissberg-Beatty integral equation
+///
+/// ```text
+/// γ = sqrt(2n / π) ∫_0^∞ Q(ν * r(x)^2 / k^2) * exp(-n * x^2 / 2) dx
+/// ```
+///
+/// where `ν = n - 1`, `Q` is the survival function of `χ^2_ν` and `r(x)`
+/// solves `Φ(x + r) - Φ(x - r) = p`. Howe's approximation is accurate to
+/// roughly two decimal places for small `n`, while the values computed here
+/// agree with the tables of ISO 16269-6.
+///
+/// # Errors
+///
+/// Returns an error if `n < 2`, if `coverage_p` or `confidence_gamma` are
+/// not in `(0, 1)`, or if the root finder fails to converge on the
+/// two-sided factor
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::tolerance::{normal_tolerance_factor, Sided};
+///
+/// let k = normal_tolerance_factor(10, 0.9, 0.95, Sided::Two).unwrap();
+/// assert!((k - 2.8563).abs() < 1e-4);
+/// ```
+pub fn normal_tolerance_factor(
+    n: usize,
+    coverage_p: f64,
+    confidence_gamma: f64,
+    sided: Sided,
+) -> Result<f64> {
+    if n < 2 {
+        return Err(StatsError::ArgGte("n", 2.0));
+    }
+    if !(coverage_p > 0.0 && coverage_p < 1.0) {
+        return Err(StatsError::ArgIntervalExcl("coverage_p", 0.0, 1.0));
+    }
+    if !(confidence_gamma > 0.0 && confidence_gamma < 1.0) {
+        return Err(StatsError::ArgIntervalExcl("confidence_gamma", 0.0, 1.0));
+    }
+
+    let n_f = n as f64;
+    let freedom = n_f - 1.0;
+    let std_normal = Normal::standard();
+    match sided {
+        Sided::One => {
+            let delta = std_normal.inverse_cdf(coverage_p) * n_f.sqrt();
+            let t = NoncentralStudentsT::new(freedom, delta)
+                .unwrap()
+                .inverse_cdf(confidence_gamma);
+            Ok(t / n_f.sqrt())
+        }
+        Sided::Two => {
+            let z = std_normal.inverse_cdf((1.0 + coverage_p) / 2.0);
+            let chi_sq = ChiSquared::new(freedom).unwrap();
+            let upper_x = 10.0 / n_f.sqrt();
+            let confidence = |k: f64| {
+                let integrand = |x: f64| {
+                    let r = coverage_radius(x, coverage_p, z);
+                    chi_sq.sf(freedom * r * r / (k * k)) * (-n_f * x * x / 2.0).exp()
+                };
+                (2.0 * n_f / f64::consts::PI).sqrt() * integrate(integrand, 0.0, upper_x, 1e-12)
+            };
+            let g = |k: f64| confidence(k) - confidence_gamma;
+            let (lo, hi) = root::bracket_root(g, z, 2.0, MAX_EXPANSIONS)?;
+            Ok(root::brent_with(g, lo, hi, 1e-12 * hi, MAX_ITERATIONS)?.root)
+        }
+    }
+}
+
+/// Computes a tolerance interval containing at least a proportion
+/// `coverage_p` of a normal population with confidence `confidence_gamma`,
+/// based on the sample mean and standard deviation of `data`
+///
+/// # Remarks
+///
+/// For `Sided::One` the returned limits are each one-sided tolerance
+/// limits; the interval between them covers more than `coverage_p`.
+/// See `normal_tolerance_factor` for the computation of the factor `k`.
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two elements, contains a
+/// non-finite value, or if `coverage_p` or `confidence_gamma` are not in
+/// `(0, 1)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::tolerance::{normal_tolerance_interval, Sided};
+///
+/// let data = [9.8, 10.2, 10.1, 9.9, 10.0, 10.3, 9.7, 10.0, 10.1, 9.9];
+/// let (lower, upper) = normal_tolerance_interval(&data, 0.9, 0.95, Sided::Two).unwrap();
+/// assert!(lower < 9.7 && upper > 10.3);
+/// ```
+pub fn normal_tolerance_interval(
+    data: &[f64],
+    coverage_p: f64,
+    confidence_gamma: f64,
+    sided: Sided,
+) -> Result<(f64, f64)> {
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+    let k = normal_tolerance_factor(data.len(), coverage_p, confidence_gamma, sided)?;
+    let mean = data.mean();
+    let std_dev = data.std_dev();
+    Ok((mean - k * std_dev, mean + k * std_dev))
+}
+
+/// Computes a distribution-free two-sided tolerance interval from the order
+/// statistics of `data`
+///
+/// The interval `[x_(r), x_(n - r + 1)]` covers a random proportion of the
+/// population distributed as `Beta(n - 2r + 1, 2r)` for any continuous
+/// population, so its confidence of covering at least `p` is
+/// `P(Binomial(n, p) <= n - 2r)`. The widest trimming `r` achieving
+/// confidence `gamma` is used.
+///
+/// # Errors
+///
+/// Returns an error if `data` is not finite, if `p` or `gamma` are not in
+/// `(0, 1)`, or if `data` is too small for even the sample range to reach
+/// the requested confidence, i.e. if `1 - n * p^(n - 1) + (n - 1) * p^n <
+/// gamma`. For example at least 93 observations are needed for
+/// `p = gamma = 0.95`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::tolerance::nonparametric_tolerance_interval;
+///
+/// let data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+/// let (lower, upper) = nonparametric_tolerance_interval(&data, 0.95, 0.95).unwrap();
+/// assert_eq!((lower, upper), (0.0, 99.0));
+///
+/// assert!(nonparametric_tolerance_interval(&data[..50], 0.95, 0.95).is_err());
+/// ```
+pub fn nonparametric_tolerance_interval(data: &[f64], p: f64, gamma: f64) -> Result<(f64, f64)> {
+    if !(p > 0.0 && p < 1.0) {
+        return Err(StatsError::ArgIntervalExcl("p", 0.0, 1.0));
+    }
+    if !(gamma > 0.0 && gamma < 1.0) {
+        return Err(StatsError::ArgIntervalExcl("gamma", 0.0, 1.0));
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+
+    let n = data.len();
+    let r = (1..=n / 2)
+        .take_while(|&r| order_statistic_confidence(n, r, p) >= gamma)
+        .last()
+        .ok_or(StatsError::SpecialCase(
+            "sample too small for a nonparametric tolerance interval",
+        ))?;
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok((sorted[r - 1], sorted[n - r]))
+}
+
+/// Confidence that `[x_(r), x_(n - r + 1)]` covers a proportion `p`
+fn order_statistic_confidence(n: usize, r: usize, p: f64) -> f64 {
+    if 2 * r > n {
+        return 0.0;
+    }
+    let coverage = Beta::new((n - 2 * r + 1) as f64, (2 * r) as f64).unwrap();
+    coverage.sf(p)
+}
+
+/// Solves `Φ(x + r) - Φ(x - r) = p` for `r >= 0` with Brent's method,
+/// where `z` is the `(1 + p) / 2` quantile of the standard normal so that
+/// `[0, |x| + z]` brackets the solution
+fn coverage_radius(x: f64, p: f64, z: f64) -> f64 {
+    let std_normal = Normal::standard();
+    let g = |r: f64| std_normal.cdf(x + r) - std_normal.cdf(x - r) - p;
+    let high = x.abs() + z;
+    root::brent_with(g, 0.0, high, 1e-15 * high, MAX_ITERATIONS).map_or(f64::NAN, |r| r.root)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Binomial, DiscreteCDF};
+
+    #[test]
+    fn test_two_sided_factor() {
+        // ISO 16269-6 table values
+        let k = |n, p, g| normal_tolerance_factor(n, p, g, Sided::Two).unwrap();
+        assert_almost_eq!(k(10, 0.90, 0.95), 2.856310849, 1e-4);
+        assert_almost_eq!(k(10, 0.95, 0.95), 3.393429479, 1e-4);
+        assert_almost_eq!(k(20, 0.95, 0.95), 2.760346178, 1e-4);
+        assert_almost_eq!(k(30, 0.95, 0.95), 2.554892813, 1e-4);
+        assert_almost_eq!(k(5, 0.99, 0.99), 10.22009031, 1e-4);
+    }
+
+    #[test]
+    fn test_one_sided_factor() {
+        let k = |n, p, g| normal_tolerance_factor(n, p, g, Sided::One).unwrap();
+        assert_almost_eq!(k(10, 0.90, 0.95), 2.354640132, 1e-4);
+        assert_almost_eq!(k(10, 0.95, 0.95), 2.910963413, 1e-4);
+        assert_almost_eq!(k(20, 0.95, 0.95), 2.396001684, 1e-4);
+        assert_almost_eq!(k(30, 0.95, 0.95), 2.219837532, 1e-4);
+        assert_almost_eq!(k(5, 0.99, 0.99), 8.939024939, 1e-4);
+    }
+
+    #[test]
+    fn test_bad_factor() {
+        assert!(normal_tolerance_factor(1, 0.9, 0.95, Sided::Two).is_err());
+        assert!(normal_tolerance_factor(10, 0.0, 0.95, Sided::Two).is_err());
+        assert!(normal_tolerance_factor(10, 0.9, 1.0, Sided::One).is_err());
+        assert!(normal_tolerance_factor(10, f64::NAN, 0.95, Sided::One).is_err());
+    }
+
+    #[test]
+    fn test_normal_tolerance_interval() {
+        let data = [9.8, 10.2, 10.1, 9.9, 10.0, 10.3, 9.7, 10.0, 10.1, 9.9];
+        let mean = data.mean();
+        let std_dev = data.std_dev();
+        let (lower, upper) = normal_tolerance_interval(&data, 0.9, 0.95, Sided::Two).unwrap();
+        assert_almost_eq!(lower, mean - 2.856310849 * std_dev, 1e-4);
+        assert_almost_eq!(upper, mean + 2.856310849 * std_dev, 1e-4);
+        let (lower, upper) = normal_tolerance_interval(&data, 0.9, 0.95, Sided::One).unwrap();
+        assert_almost_eq!(lower, mean - 2.354640132 * std_dev, 1e-4);
+        assert_almost_eq!(upper, mean + 2.354640132 * std_dev, 1e-4);
+        assert!(normal_tolerance_interval(&[1.0, f64::NAN], 0.9, 0.95, Sided::Two).is_err());
+    }
+
+    #[test]
+    fn test_coverage_binomial_identity() {
+        for &(n, r, p) in &[(50, 1, 0.9), (100, 3, 0.95), (200, 5, 0.9), (30, 2, 0.75)] {
+            let binomial = Binomial::new(p, n as u64).unwrap();
+            let expected = binomial.cdf((n - 2 * r) as u64);
+            assert_almost_eq!(order_statistic_confidence(n, r, p), expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_nonparametric_tolerance_interval() {
+        let data: Vec<f64> = (0..300).rev().map(|x| x as f64).collect();
+        let (lower, upper) = nonparametric_tolerance_interval(&data, 0.95, 0.95).unwrap();
+        // for n = 300 the widest symmetric trimming with 95% confidence is r = 4
+        assert!(order_statistic_confidence(300, 4, 0.95) >= 0.95);
+        assert!(order_statistic_confidence(300, 5, 0.95) < 0.95);
+        assert_eq!((lower, upper), (3.0, 296.0));
+    }
+
+    #[test]
+    fn test_nonparametric_minimum_sample_size() {
+        let data: Vec<f64> = (0..93).map(|x| x as f64).collect();
+        assert!(nonparametric_tolerance_interval(&data, 0.95, 0.95).is_ok());
+        assert!(nonparametric_tolerance_interval(&data[..92], 0.95, 0.95).is_err());
+        assert!(nonparametric_tolerance_interval(&data, 1.0, 0.95).is_err());
+        assert!(nonparametric_tolerance_interval(&data, 0.95, 0.0).is_err());
+        assert!(nonparametric_tolerance_interval(&[], 0.95, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_nonparametric_non_finite() {
+        let mut data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+        data[17] = f64::NAN;
+        assert_eq!(nonparametric_tolerance_interval(&data, 0.95, 0.95), Err(StatsError::ArgFinite("data")));
+        data[17] = f64::INFINITY;
+        assert_eq!(nonparametric_tolerance_interval(&data, 0.95, 0.95), Err(StatsError::ArgFinite("data")));
+    }
+}
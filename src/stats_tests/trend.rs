@@ -0,0 +1,495 @@
+//! Provides nonparametric tests for monotonic trends in series observed at
+//! equally spaced times, as used in environmental monitoring
+//!
+//! Missing observations are encoded as `NaN` and handled by pairwise
+//! deletion: only pairs of observed values enter the statistics, while Sen's
+//! slope keeps the original time of every observation.
+
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::stats_tests::effect::EffectEstimate;
+use crate::stats_tests::{Alternative, EffectSizeKind, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Largest number of observations for which `mann_kendall` computes the
+/// exact permutation p-value
+pub const MANN_KENDALL_EXACT_MAX: usize = 10;
+
+/// The statistics of a single series: `S`, its variance, the number of
+/// observed values and the sizes of the groups of tied values
+struct Parts {
+    s: f64,
+    variance: f64,
+    n: usize,
+    ties: Vec<usize>,
+}
+
+impl Parts {
+    /// Returns the number of pairs of observed values and the number of
+    /// those pairs that are tied
+    fn pairs(&self) -> (f64, f64) {
+        let n = self.n as f64;
+        let tied: f64 = self.ties.iter().map(|&t| (t * (t - 1)) as f64 / 2.0).sum();
+        (n * (n - 1.0) / 2.0, tied)
+    }
+}
+
+/// Computes `S` and its tie-corrected variance over the observed values of
+/// `data`
+fn parts<'a, I: IntoIterator<Item = &'a f64>>(data: I) -> Parts {
+    let x: Vec<f64> = data.into_iter().copied().filter(|x| !x.is_nan()).collect();
+    let n = x.len();
+    let mut s = 0.0;
+    for (i, &a) in x.iter().enumerate() {
+        for &b in &x[i + 1..] {
+            if b > a {
+                s += 1.0;
+            } else if b < a {
+                s -= 1.0;
+            }
+        }
+    }
+    let mut sorted = x;
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut ties = Vec::new();
+    let mut run = 1;
+    for i in 1..=n {
+        if i < n && sorted[i] == sorted[i - 1] {
+            run += 1;
+        } else {
+            if run > 1 {
+                ties.push(run);
+            }
+            run = 1;
+        }
+    }
+    let term = |t: usize| (t * (t - 1) * (2 * t + 5)) as f64;
+    let variance = if n < 2 {
+        0.0
+    } else {
+        (term(n) - ties.iter().map(|&t| term(t)).sum::<f64>()) / 18.0
+    };
+    Parts {
+        s,
+        variance,
+        n,
+        ties,
+    }
+}
+
+/// Returns the continuity-corrected normal score of `s` and its two-sided
+/// p-value
+fn normal_score(s: f64, variance: f64) -> (f64, f64) {
+    if variance <= 0.0 || s == 0.0 {
+        return (0.0, 1.0);
+    }
+    let z = (s - s.signum()) / variance.sqrt();
+    (z, (2.0 * Normal::standard().sf(z.abs())).min(1.0))
+}
+
+/// Returns the exact two-sided p-value of `S` under random permutation of
+/// the observed values, from the distribution of the number of inversions
+/// of a multiset permutation, whose generating function is the
+/// q-multinomial coefficient `[n]_q! / Π [t]_q!`
+fn exact_p_value(parts: &Parts) -> f64 {
+    let n = parts.n;
+    let max_inversions = n * (n - 1) / 2;
+    let mut counts = vec![0i64; max_inversions + 1];
+    counts[0] = 1;
+    // multiply by [k]_q = (1 - q^k) / (1 - q) for k = 1..n
+    for k in 1..=n {
+        for i in (k..=max_inversions).rev() {
+            counts[i] -= counts[i - k];
+        }
+        for i in 1..=max_inversions {
+            counts[i] += counts[i - 1];
+        }
+    }
+    // divide by [k]_q for every k up to the size of each group of ties
+    for &t in &parts.ties {
+        for k in 1..=t {
+            for i in (1..=max_inversions).rev() {
+                counts[i] -= counts[i - 1];
+            }
+            for i in k..=max_inversions {
+                counts[i] += counts[i - k];
+            }
+        }
+    }
+    let (pairs, tied) = parts.pairs();
+    let untied = pairs - tied;
+    let total: i64 = counts.iter().sum();
+    let extreme: i64 = counts
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| (untied - 2.0 * i as f64).abs() >= parts.s.abs())
+        .map(|(_, &c)| c)
+        .sum();
+    extreme as f64 / total as f64
+}
+
+/// Returns Kendall's tau-b between the values and the times from the
+/// pooled pair counts
+fn tau(s: f64, pairs: f64, tied: f64) -> f64 {
+    s / ((pairs - tied) * pairs).sqrt()
+}
+
+/// Performs the Mann-Kendall test of the null hypothesis that the
+/// observations of the series `data` are independent and identically
+/// distributed, against a monotonic trend in time. Returns the normal score
+/// `z` as the statistic with the two-sided p-value, `S` as the estimate
+/// and Kendall's tau between the values and the times as the effect size.
+///
+/// # Formula
+///
+/// ```text
+/// S = Σ_(i<j) sign(x_j - x_i)
+/// Var(S) = (n (n - 1) (2n + 5) - Σ_t t (t - 1) (2t + 5)) / 18
+/// z = (S - sign(S)) / sqrt(Var(S))
+/// ```
+///
+/// where the sum over `t` runs over the sizes of the groups of tied values,
+/// and `tau` is Kendall's tau-b, `S / sqrt((n0 - n1) n0)` with
+/// `n0 = n (n - 1) / 2` pairs of which `n1` are tied
+///
+/// # Remarks
+///
+/// The p-value is exact for at most [`MANN_KENDALL_EXACT_MAX`] observed
+/// values, by enumerating the permutation distribution of `S` given the
+/// ties, and otherwise uses the normal approximation as R's
+/// `trend::mk.test`. Missing values are deleted pairwise, which for `S` is
+/// the same as dropping them: `n` and the variance are those of the
+/// observed values only, so gaps lower the power of the test but do not
+/// bias it.
+///
+/// # Errors
+///
+/// Returns an error if fewer than two values are observed
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::mann_kendall;
+///
+/// let result = mann_kendall(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+/// assert_eq!(result.estimate.unwrap().value, 10.0);
+/// assert_eq!(result.effect_size.unwrap().value, 1.0);
+/// assert!((result.p_value - 1.0 / 60.0).abs() < 1e-15);
+/// ```
+pub fn mann_kendall(data: &[f64]) -> Result<TestResult> {
+    let parts = parts(data);
+    if parts.n < 2 {
+        return Err(StatsError::SpecialCase(
+            "at least two values must be observed",
+        ));
+    }
+    let (z, normal_p) = normal_score(parts.s, parts.variance);
+    let p_value = if parts.n <= MANN_KENDALL_EXACT_MAX {
+        exact_p_value(&parts)
+    } else {
+        normal_p
+    };
+    let (pairs, tied) = parts.pairs();
+    Ok(TestResult::new(
+        "Mann-Kendall trend test",
+        "z",
+        z,
+        p_value,
+        Alternative::TwoSided,
+        vec![parts.n],
+    )
+    .with_estimate("S", parts.s)
+    .with_effect_size(EffectSizeKind::KendallTau, tau(parts.s, pairs, tied)))
+}
+
+/// Performs the seasonal Mann-Kendall test of Hirsch, Slack and Smith
+/// (1982) on the series `data` with `period` seasons per cycle, such as 12
+/// for monthly data starting in the first season
+///
+/// The Mann-Kendall statistic and its variance are computed separately
+/// for every season and summed, so that seasonality does not mask or
+/// mimic a trend. The result is reported as by [`mann_kendall`], with the
+/// number of observed values of every season as the sample sizes.
+///
+/// # Formula
+///
+/// ```text
+/// S = Σ_k S_k
+/// Var(S) = Σ_k Var(S_k)
+/// z = (S - sign(S)) / sqrt(Var(S))
+/// ```
+///
+/// where `S_k` and `Var(S_k)` are those of [`mann_kendall`] for the values
+/// of season `k`, and `tau` is computed from the pair counts pooled over
+/// the seasons
+///
+/// # Remarks
+///
+/// The p-value always uses the normal approximation. Missing values are
+/// deleted pairwise within each season.
+///
+/// # Errors
+///
+/// Returns an error if `period` is zero or if no season has two observed
+/// values
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::seasonal_mann_kendall;
+///
+/// // a winter peak on top of a rising level
+/// let data = [5.0, 1.0, 2.0, 6.0, 1.5, 2.5, 7.0, 2.0, 3.0, 8.0, 2.5, 3.5];
+/// let result = seasonal_mann_kendall(&data, 3).unwrap();
+/// assert_eq!(result.estimate.unwrap().value, 18.0);
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn seasonal_mann_kendall(data: &[f64], period: usize) -> Result<TestResult> {
+    if period == 0 {
+        return Err(StatsError::ArgMustBePositive("period"));
+    }
+    let (mut s, mut variance, mut pairs, mut tied) = (0.0, 0.0, 0.0, 0.0);
+    let mut sizes = Vec::with_capacity(period);
+    for season in 0..period {
+        let parts = parts(data.iter().skip(season).step_by(period));
+        let (p, t) = parts.pairs();
+        sizes.push(parts.n);
+        s += parts.s;
+        variance += parts.variance;
+        pairs += p;
+        tied += t;
+    }
+    if pairs == 0.0 {
+        return Err(StatsError::SpecialCase(
+            "at least one season must have two observed values",
+        ));
+    }
+    let (z, p_value) = normal_score(s, variance);
+    Ok(TestResult::new(
+        "Seasonal Mann-Kendall trend test",
+        "z",
+        z,
+        p_value,
+        Alternative::TwoSided,
+        sizes,
+    )
+    .with_estimate("S", s)
+    .with_effect_size(EffectSizeKind::KendallTau, tau(s, pairs, tied)))
+}
+
+/// Computes Sen's slope of the series `data`, the median of the slopes
+/// between all pairs of observations, with its `1 - alpha` confidence
+/// interval
+///
+/// # Formula
+///
+/// ```text
+/// Q_ij = (x_j - x_i) / (j - i),  i < j
+/// C = z_(1 - α/2) sqrt(Var(S))
+/// ```
+///
+/// The `N` slopes are sorted and the interval is bounded by those of rank
+/// `(N - C) / 2` and `(N + C) / 2 + 1`, rounded to the nearest integer, as
+/// in R's `trend::sens.slope`, where `Var(S)` is the tie-corrected
+/// variance of [`mann_kendall`]
+///
+/// # Remarks
+///
+/// Missing values are deleted pairwise, and the slopes between the
+/// remaining observations use their original times.
+///
+/// # Errors
+///
+/// Returns an error if `alpha` is not in `(0, 1)`, if fewer than two
+/// values are observed or if any value is infinite
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::sens_slope;
+///
+/// let slope = sens_slope(&[1.0, 3.0, f64::NAN, 7.0, 9.5, 11.0], 0.05).unwrap();
+/// assert_eq!(slope.estimate, 2.0);
+/// ```
+pub fn sens_slope(data: &[f64], alpha: f64) -> Result<EffectEstimate> {
+    if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+    }
+    if data.iter().any(|x| x.is_infinite()) {
+        return Err(StatsError::ArgFinite("data"));
+    }
+    let mut slopes = Vec::new();
+    for (i, &a) in data.iter().enumerate().filter(|(_, x)| !x.is_nan()) {
+        for (j, &b) in data.iter().enumerate().skip(i + 1) {
+            if !b.is_nan() {
+                slopes.push((b - a) / (j - i) as f64);
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return Err(StatsError::SpecialCase(
+            "at least two values must be observed",
+        ));
+    }
+    slopes.sort_by(f64::total_cmp);
+    let count = slopes.len();
+    let estimate = if count % 2 == 1 {
+        slopes[count / 2]
+    } else {
+        0.5 * (slopes[count / 2 - 1] + slopes[count / 2])
+    };
+    let variance = parts(data).variance;
+    let c = -Normal::standard().inverse_cdf(alpha / 2.0) * variance.sqrt();
+    // 1-based ranks clamped to the available slopes
+    let rank = |r: f64| (r.round().max(1.0) as usize).min(count) - 1;
+    Ok(EffectEstimate {
+        estimate,
+        lower: slopes[rank((count as f64 - c) / 2.0)],
+        upper: slopes[rank((count as f64 + c) / 2.0 + 1.0)],
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Annual flow of the Nile at Aswan in 10^8 m^3, 1871-1970, as in R's
+    /// `Nile` dataset
+    const NILE: [f64; 100] = [
+        1120.0, 1160.0, 963.0, 1210.0, 1160.0, 1160.0, 813.0, 1230.0, 1370.0, 1140.0,
+        995.0, 935.0, 1110.0, 994.0, 1020.0, 960.0, 1180.0, 799.0, 958.0, 1140.0,
+        1100.0, 1210.0, 1150.0, 1250.0, 1260.0, 1220.0, 1030.0, 1100.0, 774.0, 840.0,
+        874.0, 694.0, 940.0, 833.0, 701.0, 916.0, 692.0, 1020.0, 1050.0, 969.0,
+        831.0, 726.0, 456.0, 824.0, 702.0, 1120.0, 1100.0, 832.0, 764.0, 821.0,
+        768.0, 845.0, 864.0, 862.0, 698.0, 845.0, 744.0, 796.0, 1040.0, 759.0,
+        781.0, 865.0, 845.0, 944.0, 984.0, 897.0, 822.0, 1010.0, 771.0, 676.0,
+        649.0, 846.0, 812.0, 742.0, 801.0, 1040.0, 860.0, 874.0, 848.0, 890.0,
+        744.0, 749.0, 838.0, 1050.0, 918.0, 986.0, 797.0, 923.0, 975.0, 815.0,
+        1020.0, 906.0, 901.0, 1170.0, 912.0, 746.0, 919.0, 718.0, 714.0, 740.0,
+    ];
+
+    /// Returns `S` and its variance, recovered from the normal score
+    fn s_and_variance(result: &TestResult) -> (f64, f64) {
+        let s = result.estimate.unwrap().value;
+        (s, ((s.abs() - 1.0) / result.statistic).powi(2))
+    }
+
+    #[test]
+    fn test_river_discharge() {
+        // reference values from an independent implementation of the
+        // formulas of trend::mk.test and trend::sens.slope
+        let result = mann_kendall(&NILE).unwrap();
+        let (s, variance) = s_and_variance(&result);
+        assert_eq!(s, -1387.0);
+        assert_almost_eq!(variance, 112728.33333333333, 1e-9);
+        assert_almost_eq!(result.statistic, -4.128066522844101, 1e-12);
+        assert_almost_eq!(result.p_value, 3.6582629216643274e-5, 1e-13);
+        assert_almost_eq!(result.effect_size.unwrap().value, -0.280741334724613, 1e-12);
+        assert_eq!(result.sample_sizes, vec![100]);
+        let slope = sens_slope(&NILE, 0.05).unwrap();
+        assert_almost_eq!(slope.estimate, -2.6, 1e-12);
+        assert_almost_eq!(slope.lower, -3.627906976744186, 1e-12);
+        assert_almost_eq!(slope.upper, -1.4285714285714286, 1e-12);
+    }
+
+    #[test]
+    fn test_exact_p_value() {
+        // checked against full enumeration of the permutations
+        assert_almost_eq!(mann_kendall(&[5.0, 4.0, 3.0, 2.0, 1.0]).unwrap().p_value, 1.0 / 60.0, 1e-15);
+        let result = mann_kendall(&[1.0, 2.0, 2.0, 3.0]).unwrap();
+        assert_eq!(result.estimate.unwrap().value, 5.0);
+        assert_almost_eq!(result.p_value, 1.0 / 6.0, 1e-15);
+        let result = mann_kendall(&[3.1, 2.2, 4.8, 4.8, 5.0, 6.3, 5.9, 7.7]).unwrap();
+        assert_eq!(result.estimate.unwrap().value, 23.0);
+        assert_almost_eq!(result.p_value, 1.0 / 360.0, 1e-15);
+        assert_eq!(mann_kendall(&[2.0, 2.0, 2.0]).unwrap().p_value, 1.0);
+        // the exact and approximate p-values are close at the cutoff
+        let data = [0.3, 1.2, 0.8, 1.9, 1.4, 2.8, 2.2, 3.1, 2.9, 3.6];
+        let exact = mann_kendall(&data).unwrap();
+        let (s, variance) = s_and_variance(&exact);
+        assert!((exact.p_value - normal_score(s, variance).1).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_seasonal() {
+        let data = [
+            10.2, 3.1, 5.5, 8.0, 11.0, 3.5, 5.1, 8.8, 10.9, 4.2, 6.0, 8.1,
+            12.4, 4.0, 6.6, 9.3, 12.0, 4.9, 6.1, 9.9, 13.1, 5.2, 7.0, 9.4,
+        ];
+        let result = seasonal_mann_kendall(&data, 4).unwrap();
+        let (s, variance) = s_and_variance(&result);
+        assert_eq!(s, 46.0);
+        assert_almost_eq!(variance, 113.33333333333333, 1e-12);
+        assert_almost_eq!(result.statistic, 4.227013964824759, 1e-12);
+        assert_eq!(result.sample_sizes, vec![6; 4]);
+        assert_almost_eq!(result.p_value, 2.3681297566498853e-5, 1e-13);
+        // the seasonal pattern hides the trend from the plain test
+        assert!(mann_kendall(&data).unwrap().p_value > result.p_value);
+        // a single season is the plain test with the normal approximation
+        let plain = mann_kendall(&NILE).unwrap();
+        let seasonal = seasonal_mann_kendall(&NILE, 1).unwrap();
+        assert_eq!((seasonal.statistic, seasonal.p_value), (plain.statistic, plain.p_value));
+        assert_eq!((seasonal.estimate, seasonal.effect_size), (plain.estimate, plain.effect_size));
+        assert_eq!(seasonal.sample_sizes, plain.sample_sizes);
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let data = [1.0, f64::NAN, 2.5, 2.0, f64::NAN, 4.0, 3.5, 5.0, 6.5, 6.0, 7.0, 8.5];
+        let observed: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+        assert_eq!(mann_kendall(&data).unwrap(), mann_kendall(&observed).unwrap());
+        // slopes keep the original spacing
+        assert_eq!(sens_slope(&[1.0, f64::NAN, 3.0], 0.05).unwrap().estimate, 1.0);
+        assert_eq!(sens_slope(&[1.0, 3.0], 0.05).unwrap().estimate, 2.0);
+        let seasonal = [1.0, 5.0, f64::NAN, 6.0, 3.0, 7.0];
+        let result = seasonal_mann_kendall(&seasonal, 2).unwrap();
+        assert_eq!(result.estimate.unwrap().value, 4.0);
+        assert_eq!(result.sample_sizes, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_white_noise() {
+        // under no trend the p-value is roughly uniform over seeds
+        let normal = crate::distribution::Normal::standard();
+        let mut p_values = Vec::new();
+        for seed in 0..400 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let data: Vec<f64> = (0..40).map(|_| normal.sample(&mut rng)).collect();
+            p_values.push(mann_kendall(&data).unwrap().p_value);
+        }
+        let mean = p_values.iter().sum::<f64>() / p_values.len() as f64;
+        assert!((mean - 0.5).abs() < 0.05, "{}", mean);
+        for &level in &[0.1, 0.25, 0.5, 0.75] {
+            let fraction = p_values.iter().filter(|&&p| p <= level).count() as f64 / 400.0;
+            assert!((fraction - level).abs() < 0.06, "{} {}", level, fraction);
+        }
+        // and the slope interval covers zero about 95% of the time
+        let mut covered = 0;
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(1000 + seed);
+            let data: Vec<f64> = (0..30).map(|_| normal.sample(&mut rng)).collect();
+            let slope = sens_slope(&data, 0.05).unwrap();
+            if slope.lower <= 0.0 && 0.0 <= slope.upper {
+                covered += 1;
+            }
+        }
+        assert!(covered >= 180, "{}", covered);
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(mann_kendall(&[]).is_err());
+        assert!(mann_kendall(&[1.0, f64::NAN]).is_err());
+        assert!(seasonal_mann_kendall(&[1.0, 2.0, 3.0], 0).is_err());
+        assert!(seasonal_mann_kendall(&[1.0, 2.0, 3.0], 3).is_err());
+        assert!(sens_slope(&[1.0], 0.05).is_err());
+        assert!(sens_slope(&[1.0, 2.0], 0.0).is_err());
+        assert!(sens_slope(&[1.0, 2.0], f64::NAN).is_err());
+        assert_eq!(sens_slope(&[1.0, f64::INFINITY, 3.0], 0.05), Err(StatsError::ArgFinite("data")));
+        assert_eq!(sens_slope(&[f64::NEG_INFINITY, 2.0], 0.05), Err(StatsError::ArgFinite("data")));
+    }
+}
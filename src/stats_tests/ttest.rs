@@ -0,0 +1,181 @@
+//! Provides robust variants of the two-sample
+//! [t-test](https://en.wikipedia.org/wiki/Student%27s_t-test)
+
+use crate::distribution::{ContinuousCDF, StudentsT};
+use crate::statistics::Statistics;
+use crate::stats_tests::{Alternative, TestResult};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Performs Yuen's two-sample t-test of the null hypothesis that `a` and
+/// `b` have equal trimmed means, trimming `⌊trim * n⌋` entries from each
+/// end of a sample of size `n`. Returns the t statistic with its degrees
+/// of freedom, the two-sided p-value, and the difference in trimmed means
+/// with its 95% confidence interval as in R's `WRS2::yuen`.
+///
+/// This is the robust counterpart of Welch's t-test: means are replaced by
+/// trimmed means and variances by winsorized variances, so a few outliers
+/// have only a bounded effect on the result. With `trim = 0` it reduces to
+/// Welch's t-test.
+///
+/// # Formula
+///
+/// ```text
+/// d_i = (n_i - 1) s_wi^2 / (h_i (h_i - 1))
+/// t = (m_t1 - m_t2) / sqrt(d_1 + d_2)
+/// ν = (d_1 + d_2)^2 / (d_1^2 / (h_1 - 1) + d_2^2 / (h_2 - 1))
+/// ```
+///
+/// where `m_ti` is the trimmed mean, `s_wi^2` the winsorized variance and
+/// `h_i` the number of entries left after trimming sample `i`. The p-value
+/// is `2 P(T > |t|)` for `T` following a Student's t distribution with `ν`
+/// degrees of freedom.
+///
+/// # Errors
+///
+/// Returns an error if `trim` is not in `[0, 0.5)`, if an entry is not
+/// finite, if fewer than two entries of either sample are left after
+/// trimming, or if both winsorized variances are zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::ttest::yuen_t_test;
+///
+/// let a = [1.2, 3.4, 2.2, 5.1, 4.4, 3.3, 2.8, 30.0, 3.9, 4.1];
+/// let b = [5.5, 6.1, 4.9, 7.2, 6.6, 5.8, 6.0, 5.2, -20.0, 6.4, 7.0];
+/// let result = yuen_t_test(&a, &b, 0.2).unwrap();
+/// assert!((result.statistic - -4.8406314548425697).abs() < 1e-12);
+/// assert!(result.p_value < 0.001);
+/// ```
+pub fn yuen_t_test(a: &[f64], b: &[f64], trim: f64) -> Result<TestResult> {
+    if !(0.0..0.5).contains(&trim) {
+        return Err(StatsError::ArgIntervalExclMax("trim", 0.0, 0.5));
+    }
+    if a.iter().chain(b).any(|x| !x.is_finite()) {
+        return Err(StatsError::SpecialCase("samples must be finite"));
+    }
+    let too_few = || StatsError::SpecialCase("fewer than two entries are left after trimming");
+    let (mean_a, d_a, h_a) = trimmed_parts(a, trim).ok_or_else(too_few)?;
+    let (mean_b, d_b, h_b) = trimmed_parts(b, trim).ok_or_else(too_few)?;
+    let d = d_a + d_b;
+    if d == 0.0 {
+        return Err(StatsError::SpecialCase(
+            "the winsorized variances must not both be zero",
+        ));
+    }
+    let t = (mean_a - mean_b) / d.sqrt();
+    let freedom = d * d / (d_a * d_a / (h_a - 1.0) + d_b * d_b / (h_b - 1.0));
+    let dist = StudentsT::new(0.0, 1.0, freedom).unwrap();
+    let p = 2.0 * dist.sf(t.abs());
+    let margin = dist.inverse_cdf(0.975) * d.sqrt();
+    let difference = mean_a - mean_b;
+    Ok(TestResult::new(
+        "Yuen's two-sample trimmed means test",
+        "t",
+        t,
+        p.min(1.0),
+        Alternative::TwoSided,
+        vec![a.len(), b.len()],
+    )
+    .with_df(freedom)
+    .with_estimate("difference in trimmed means", difference)
+    .with_conf_int(difference - margin, difference + margin, 0.95))
+}
+
+/// Returns the trimmed mean, the squared standard error `d` of the trimmed
+/// mean and the number `h` of entries left after trimming, or `None` if
+/// fewer than two entries remain or an entry is `NaN`
+fn trimmed_parts(x: &[f64], trim: f64) -> Option<(f64, f64, f64)> {
+    let winsorized = x.winsorized_variance(trim);
+    if winsorized.is_nan() {
+        return None;
+    }
+    let n = x.len();
+    let g = (trim * n as f64).floor() as usize;
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let kept = &sorted[g..n - g];
+    let h = kept.len() as f64;
+    let mean = kept.mean();
+    let d = (n as f64 - 1.0) * winsorized / (h * (h - 1.0));
+    Some((mean, d, h))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: [f64; 10] = [1.2, 3.4, 2.2, 5.1, 4.4, 3.3, 2.8, 30.0, 3.9, 4.1];
+    const B: [f64; 11] = [5.5, 6.1, 4.9, 7.2, 6.6, 5.8, 6.0, 5.2, -20.0, 6.4, 7.0];
+
+    fn welch_t_test(a: &[f64], b: &[f64]) -> (f64, f64) {
+        let d_a = a.variance() / a.len() as f64;
+        let d_b = b.variance() / b.len() as f64;
+        let t = (a.mean() - b.mean()) / (d_a + d_b).sqrt();
+        let freedom = (d_a + d_b).powi(2)
+            / (d_a * d_a / (a.len() as f64 - 1.0) + d_b * d_b / (b.len() as f64 - 1.0));
+        let p = 2.0 * StudentsT::new(0.0, 1.0, freedom).unwrap().sf(t.abs());
+        (t, p)
+    }
+
+    #[test]
+    fn test_yuen_reference() {
+        // reference values computed with mpmath
+        let (t, p): (f64, f64) = yuen_t_test(&A, &B, 0.2).unwrap().into();
+        assert_almost_eq!(t, -4.8406314548425697, 1e-13);
+        assert_almost_eq!(p, 0.00074885274162986716, 1e-12);
+        let result = yuen_t_test(&A, &B, 0.2).unwrap();
+        assert_almost_eq!(result.df.unwrap(), 9.6726912276213341, 1e-10);
+        let interval = result.effect_estimate().unwrap();
+        assert_almost_eq!(interval.estimate, -2.2928571428571429, 1e-14);
+        assert_almost_eq!(interval.lower, -3.3531184286332447, 1e-10);
+        assert_almost_eq!(interval.upper, -1.2325958570810411, 1e-10);
+        let (t, p): (f64, f64) = yuen_t_test(&A, &B, 0.1).unwrap().into();
+        assert_almost_eq!(t, -4.4793821202764989, 1e-13);
+        assert_almost_eq!(p, 0.00068373592582825257, 1e-12);
+    }
+
+    #[test]
+    fn test_yuen_zero_trim_is_welch() {
+        let (t, p): (f64, f64) = yuen_t_test(&A, &B, 0.0).unwrap().into();
+        let (t_welch, p_welch) = welch_t_test(&A, &B);
+        assert_almost_eq!(t, t_welch, 1e-13);
+        assert_almost_eq!(p, p_welch, 1e-12);
+        assert_almost_eq!(t, 0.65212639476581299, 1e-13);
+        assert_almost_eq!(p, 0.52236334720796486, 1e-12);
+    }
+
+    #[test]
+    fn test_yuen_outliers() {
+        let clean_a = &A[..7];
+        let clean_b = &B[..8];
+        let (t_clean, _): (f64, f64) = yuen_t_test(clean_a, clean_b, 0.2).unwrap().into();
+        let (t_welch_clean, _) = welch_t_test(clean_a, clean_b);
+        let (t, p): (f64, f64) = yuen_t_test(&A, &B, 0.2).unwrap().into();
+        let (t_welch, p_welch) = welch_t_test(&A, &B);
+        // a single outlier per sample flips the sign of Welch's statistic
+        // and hides the difference, while Yuen's barely moves
+        assert!(t_welch_clean < -4.0 && t_welch > 0.0);
+        assert!((t - t_clean).abs() < 0.5 * t_clean.abs());
+        assert!(p < 0.001 && p_welch > 0.5);
+    }
+
+    #[test]
+    fn test_yuen_bad_input() {
+        assert!(yuen_t_test(&[1.0], &B, 0.0).is_err());
+        assert!(yuen_t_test(&[], &B, 0.0).is_err());
+        assert!(yuen_t_test(&A, &[1.0, 2.0, 3.0], 0.4).is_err());
+        assert!(yuen_t_test(&[1.0, f64::NAN, 3.0], &B, 0.1).is_err());
+        assert!(yuen_t_test(&A, &[1.0, f64::INFINITY, 3.0], 0.1).is_err());
+        assert!(yuen_t_test(&[1.0, 1.0], &[1.0, 1.0], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_yuen_bad_trim() {
+        assert_eq!(yuen_t_test(&A, &B, 0.5), Err(StatsError::ArgIntervalExclMax("trim", 0.0, 0.5)));
+        assert!(yuen_t_test(&A, &B, -0.1).is_err());
+        assert!(yuen_t_test(&A, &B, f64::NAN).is_err());
+    }
+}